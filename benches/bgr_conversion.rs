@@ -0,0 +1,30 @@
+// benches/bgr_conversion.rs - Sequential vs Parallel BGR to RGBA Conversion
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mivi_frame_viewer::backend::frame_processor::{bgr_to_rgba, bgr_to_rgba_parallel};
+
+/// Common capture resolutions, from SD ultrasound to 4K endoscopy
+const RESOLUTIONS: [(usize, usize); 3] = [(1024, 768), (1920, 1080), (3840, 2160)];
+
+fn bench_bgr_conversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bgr_to_rgba");
+
+    for (width, height) in RESOLUTIONS {
+        let src: Vec<u8> = (0..width * height * 3).map(|i| i as u8).collect();
+        let mut dst = vec![0u8; width * height * 4];
+        let label = format!("{}x{}", width, height);
+        group.throughput(Throughput::Bytes(src.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("sequential", &label), &src, |b, src| {
+            b.iter(|| bgr_to_rgba(black_box(src), &mut dst, 3));
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", &label), &src, |b, src| {
+            b.iter(|| bgr_to_rgba_parallel(black_box(src), &mut dst, width, 3));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_bgr_conversion);
+criterion_main!(benches);
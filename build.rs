@@ -1,4 +1,6 @@
 // build.rs - Slint UI compilation
 fn main() {
+    // Library builds without the "ui" feature have no window to compile
+    #[cfg(feature = "ui")]
     slint_build::compile("src/ui/main_window.slint").unwrap();
 }
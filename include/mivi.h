@@ -0,0 +1,77 @@
+/* include/mivi.h - C API for embedding the MiVi frame backend without the UI
+ *
+ * Link against the mivi_frame_viewer cdylib. Functions returning int yield
+ * MIVI_OK (or a positive value) on success and MIVI_ERROR on failure;
+ * mivi_last_error() then describes the failure on the calling thread.
+ */
+
+#ifndef MIVI_H
+#define MIVI_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define MIVI_OK 0
+#define MIVI_ERROR (-1)
+
+typedef struct MiviBackend MiviBackend;
+
+/* Tightly packed RGBA8 pixels, width * 4 bytes per row */
+typedef struct MiviFrame {
+    uint64_t frame_id;
+    uint64_t sequence_number;
+    uint64_t timestamp_ns;
+    uint32_t width;
+    uint32_t height;
+    const uint8_t *rgba;
+    size_t rgba_len;
+} MiviFrame;
+
+typedef struct MiviStats {
+    int connected;
+    uint64_t total_frames_received;
+    uint64_t total_frames_processed;
+    uint64_t frames_dropped;
+    uint64_t frames_corrupted;
+    double current_fps;
+    double average_latency_ms;
+    double jitter_ms;
+} MiviStats;
+
+/* Runs on a backend thread; frame is only valid during the call.
+ * The callback must not unwind: a C++ exception or longjmp escaping it is undefined behaviour. */
+typedef void (*MiviFrameCallback)(const MiviFrame *frame, void *user_data);
+
+/* format may be NULL; width/height of 0 keep the defaults. Returns NULL on failure. */
+MiviBackend *mivi_backend_create(const char *shm_name, const char *format, uint32_t width, uint32_t height);
+int mivi_backend_start(MiviBackend *backend);
+
+/* 1 = frame written to *frame (valid until the next poll), 0 = timeout */
+int mivi_backend_poll_frame(MiviBackend *backend, MiviFrame *frame, uint32_t timeout_ms);
+/* Pass NULL to unregister and return to polling. Returns once the previous callback
+ * has finished, so its user_data may be freed; must not be called from a callback. */
+int mivi_backend_set_frame_callback(MiviBackend *backend, MiviFrameCallback callback, void *user_data);
+
+int mivi_backend_connect(MiviBackend *backend, const char *shm_name);
+int mivi_backend_disconnect(MiviBackend *backend);
+int mivi_backend_set_window_level(MiviBackend *backend, float center, float width);
+int mivi_backend_set_orientation(MiviBackend *backend, int rotation_degrees, bool flip_horizontal, bool flip_vertical);
+int mivi_backend_set_catch_up(MiviBackend *backend, bool enabled);
+int mivi_backend_get_stats(MiviBackend *backend, MiviStats *stats);
+
+/* Waits for a running callback to return; must not be called from a callback */
+void mivi_backend_destroy(MiviBackend *backend);
+
+const char *mivi_last_error(void);
+const char *mivi_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MIVI_H */
@@ -0,0 +1,472 @@
+// src/backend/adjustments.rs - Display Adjustments (Window/Level, Gamma, Colormaps) for Medical Imaging
+
+use serde::{Deserialize, Serialize};
+
+/// Window/level (contrast/brightness) setting in normalized intensity units
+///
+/// `center` is the intensity mapped to mid-gray and `width` the intensity range
+/// stretched over the full display range, both in `0.0..=1.0` of the source bit depth.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowLevel {
+    pub center: f32,
+    pub width: f32,
+}
+
+impl WindowLevel {
+    /// Minimum window width to avoid a degenerate (binary) mapping
+    pub const MIN_WIDTH: f32 = 1.0 / 1024.0;
+
+    /// Create a clamped window/level setting
+    pub fn new(center: f32, width: f32) -> Self {
+        Self {
+            center: center.clamp(0.0, 1.0),
+            width: width.clamp(Self::MIN_WIDTH, 1.0),
+        }
+    }
+
+    /// Check if this setting leaves intensities unchanged
+    pub fn is_identity(&self) -> bool {
+        (self.center - 0.5).abs() < f32::EPSILON && (self.width - 1.0).abs() < f32::EPSILON
+    }
+
+    /// Map a normalized input intensity to a normalized output intensity
+    pub fn apply(&self, value: f32) -> f32 {
+        let low = self.center - self.width / 2.0;
+        ((value - low) / self.width).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for WindowLevel {
+    fn default() -> Self {
+        Self { center: 0.5, width: 1.0 }
+    }
+}
+
+/// Display gamma correction (output = input^(1/gamma))
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Gamma(pub f32);
+
+impl Gamma {
+    /// Supported gamma range
+    pub const MIN: f32 = 0.1;
+    pub const MAX: f32 = 5.0;
+
+    /// Create a clamped gamma value
+    pub fn new(gamma: f32) -> Self {
+        if gamma.is_finite() {
+            Self(gamma.clamp(Self::MIN, Self::MAX))
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Check if this gamma leaves intensities unchanged
+    pub fn is_identity(&self) -> bool {
+        (self.0 - 1.0).abs() < 1e-3
+    }
+
+    /// Map a normalized intensity
+    pub fn apply(&self, value: f32) -> f32 {
+        if self.is_identity() {
+            value
+        } else {
+            value.clamp(0.0, 1.0).powf(1.0 / self.0)
+        }
+    }
+}
+
+impl Default for Gamma {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// 4x4 Bayer matrix for ordered dithering
+const BAYER_4X4: [[u32; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// 8x8 Bayer matrix for ordered dithering
+const BAYER_8X8: [[u32; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Dithering applied when high bit depth sources are reduced to 8 bits
+///
+/// Ordered dithering spreads the fractional part of each output level over a
+/// tile of neighbouring pixels, so smooth gradients show no bands. The 8x8
+/// matrix has finer steps, the 4x4 one a less visible pattern at low zoom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Dithering {
+    /// Round to the nearest level
+    #[default]
+    Off,
+    /// Ordered dithering over a 4x4 Bayer matrix
+    Bayer4,
+    /// Ordered dithering over an 8x8 Bayer matrix
+    Bayer8,
+}
+
+impl Dithering {
+    /// All dithering modes
+    pub const ALL: [Dithering; 3] = [Dithering::Off, Dithering::Bayer4, Dithering::Bayer8];
+
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Dithering::Off => "Off",
+            Dithering::Bayer4 => "Bayer 4x4",
+            Dithering::Bayer8 => "Bayer 8x8",
+        }
+    }
+
+    /// Check if levels are rounded rather than dithered
+    pub fn is_off(self) -> bool {
+        self == Dithering::Off
+    }
+
+    /// Offset in 1/256 of an output level added at pixel (x, y) before truncating
+    #[inline]
+    fn threshold(self, x: usize, y: usize) -> u32 {
+        match self {
+            Dithering::Off => 128,
+            Dithering::Bayer4 => BAYER_4X4[y & 3][x & 3] * 16 + 8,
+            Dithering::Bayer8 => BAYER_8X8[y & 7][x & 7] * 4 + 2,
+        }
+    }
+}
+
+/// Precomputed lookup tables mapping source intensities to 8-bit display values
+///
+/// Window/level is applied first, then gamma, both in floating point before
+/// quantizing to 8 bits.
+#[derive(Debug, Clone)]
+pub struct DisplayLut {
+    window_level: WindowLevel,
+    gamma: Gamma,
+    lut8: Vec<u8>,
+    lut10: Vec<u8>,
+    lut12: Vec<u8>,
+    // 10-bit and 12-bit to 8.8 fixed point, for dithering instead of truncating
+    fine10: Vec<u16>,
+    fine12: Vec<u16>,
+}
+
+impl DisplayLut {
+    /// Build lookup tables for 8-bit, 10-bit and 12-bit sources
+    pub fn new(window_level: WindowLevel, gamma: Gamma) -> Self {
+        Self {
+            window_level,
+            gamma,
+            lut8: Self::build(&window_level, &gamma, 8),
+            lut10: Self::build(&window_level, &gamma, 10),
+            lut12: Self::build(&window_level, &gamma, 12),
+            fine10: Self::build_fine(&window_level, &gamma, 10),
+            fine12: Self::build_fine(&window_level, &gamma, 12),
+        }
+    }
+
+    fn build_fine(window_level: &WindowLevel, gamma: &Gamma, bits: u32) -> Vec<u16> {
+        let max_value = ((1u32 << bits) - 1) as f32;
+        (0..=max_value as u32)
+            .map(|value| {
+                let normalized = gamma.apply(window_level.apply(value as f32 / max_value));
+                (normalized * 255.0 * 256.0).round() as u16
+            })
+            .collect()
+    }
+
+    fn build(window_level: &WindowLevel, gamma: &Gamma, bits: u32) -> Vec<u8> {
+        let max_value = ((1u32 << bits) - 1) as f32;
+        (0..=max_value as u32)
+            .map(|value| {
+                let normalized = gamma.apply(window_level.apply(value as f32 / max_value));
+                (normalized * 255.0).round() as u8
+            })
+            .collect()
+    }
+
+    /// Get the window/level setting this table was built from
+    pub fn window_level(&self) -> WindowLevel {
+        self.window_level
+    }
+
+    /// Get the gamma this table was built from
+    pub fn gamma(&self) -> Gamma {
+        self.gamma
+    }
+
+    /// Check if the table leaves 8-bit data unchanged
+    pub fn is_identity(&self) -> bool {
+        self.window_level.is_identity() && self.gamma.is_identity()
+    }
+
+    /// Map an 8-bit source value
+    #[inline]
+    pub fn map8(&self, value: u8) -> u8 {
+        self.lut8[value as usize]
+    }
+
+    /// Map a 10-bit source value (upper bits are ignored)
+    #[inline]
+    pub fn map10(&self, value: u16) -> u8 {
+        self.lut10[(value & 0x03FF) as usize]
+    }
+
+    /// Map a 10-bit source value to 8 bits, dithered at pixel (x, y)
+    ///
+    /// Fractional output levels are spread over neighbouring pixels instead of
+    /// truncated, so smooth 10-bit gradients do not band on 8-bit displays.
+    #[inline]
+    pub fn map10_dithered(&self, value: u16, x: usize, y: usize, dithering: Dithering) -> u8 {
+        ((self.fine10[(value & 0x03FF) as usize] as u32 + dithering.threshold(x, y)) >> 8).min(255) as u8
+    }
+
+    /// Map a 12-bit source value to 8 bits, dithered at pixel (x, y)
+    #[inline]
+    pub fn map12_dithered(&self, value: u16, x: usize, y: usize, dithering: Dithering) -> u8 {
+        ((self.fine12[(value & 0x0FFF) as usize] as u32 + dithering.threshold(x, y)) >> 8).min(255) as u8
+    }
+
+    /// Map a 12-bit source value (upper bits are ignored)
+    #[inline]
+    pub fn map12(&self, value: u16) -> u8 {
+        self.lut12[(value & 0x0FFF) as usize]
+    }
+
+    /// Apply the 8-bit table in place to the color channels of RGBA data
+    pub fn apply_rgba(&self, rgba_data: &mut [u8]) {
+        if self.is_identity() {
+            return;
+        }
+
+        for pixel in rgba_data.chunks_exact_mut(4) {
+            pixel[0] = self.lut8[pixel[0] as usize];
+            pixel[1] = self.lut8[pixel[1] as usize];
+            pixel[2] = self.lut8[pixel[2] as usize];
+        }
+    }
+}
+
+impl Default for DisplayLut {
+    fn default() -> Self {
+        Self::new(WindowLevel::default(), Gamma::default())
+    }
+}
+
+/// Pseudo-color map applied to single-channel frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Colormap {
+    #[default]
+    Grayscale,
+    HotMetal,
+    Rainbow,
+    Pet,
+}
+
+impl Colormap {
+    /// All colormaps in UI order
+    pub const ALL: [Colormap; 4] = [Colormap::Grayscale, Colormap::HotMetal, Colormap::Rainbow, Colormap::Pet];
+
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Colormap::Grayscale => "Grayscale",
+            Colormap::HotMetal => "Hot Metal",
+            Colormap::Rainbow => "Rainbow",
+            Colormap::Pet => "PET",
+        }
+    }
+
+    /// Get colormap from its UI index
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Get the UI index of this colormap
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|&c| c == self).unwrap_or(0)
+    }
+
+    /// Get colormap from its display name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Map a normalized intensity to an RGB color
+    pub fn color(self, t: f32) -> [u8; 3] {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        match self {
+            Colormap::Grayscale => [channel(t); 3],
+            Colormap::HotMetal => [channel(3.0 * t), channel(3.0 * t - 1.0), channel(3.0 * t - 2.0)],
+            Colormap::Rainbow => [
+                channel(1.5 - (4.0 * t - 3.0).abs()),
+                channel(1.5 - (4.0 * t - 2.0).abs()),
+                channel(1.5 - (4.0 * t - 1.0).abs()),
+            ],
+            Colormap::Pet => {
+                const STOPS: [(f32, [f32; 3]); 6] = [
+                    (0.0, [0.0, 0.0, 0.0]),
+                    (0.2, [0.0, 0.0, 0.67]),
+                    (0.4, [0.51, 0.0, 0.78]),
+                    (0.6, [0.9, 0.16, 0.24]),
+                    (0.8, [1.0, 0.7, 0.0]),
+                    (1.0, [1.0, 1.0, 1.0]),
+                ];
+
+                let upper = STOPS.iter().position(|&(stop, _)| stop >= t).unwrap_or(STOPS.len() - 1).max(1);
+                let (t0, c0) = STOPS[upper - 1];
+                let (t1, c1) = STOPS[upper];
+                let f = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
+
+                [
+                    channel(c0[0] + (c1[0] - c0[0]) * f),
+                    channel(c0[1] + (c1[1] - c0[1]) * f),
+                    channel(c0[2] + (c1[2] - c0[2]) * f),
+                ]
+            }
+        }
+    }
+}
+
+/// 256-entry RGB palette built from a colormap
+#[derive(Debug, Clone)]
+pub struct ColorPalette {
+    colormap: Colormap,
+    entries: Vec<[u8; 3]>,
+}
+
+impl ColorPalette {
+    /// Build the palette for a colormap
+    pub fn new(colormap: Colormap) -> Self {
+        Self {
+            colormap,
+            entries: (0..256).map(|i| colormap.color(i as f32 / 255.0)).collect(),
+        }
+    }
+
+    /// Get the colormap this palette was built from
+    pub fn colormap(&self) -> Colormap {
+        self.colormap
+    }
+
+    /// Check if the palette leaves gray values unchanged
+    pub fn is_identity(&self) -> bool {
+        self.colormap == Colormap::Grayscale
+    }
+
+    /// Get the palette entries
+    pub fn entries(&self) -> &[[u8; 3]] {
+        &self.entries
+    }
+
+    /// Colorize gray RGBA data in place (the red channel carries the intensity)
+    pub fn apply_rgba(&self, rgba_data: &mut [u8]) {
+        if self.is_identity() {
+            return;
+        }
+
+        for pixel in rgba_data.chunks_exact_mut(4) {
+            let [r, g, b] = self.entries[pixel[0] as usize];
+            pixel[0] = r;
+            pixel[1] = g;
+            pixel[2] = b;
+        }
+    }
+
+    /// Render a horizontal legend strip as RGBA data
+    pub fn legend_rgba(&self, width: usize, height: usize) -> Vec<u8> {
+        let mut rgba_data = Vec::with_capacity(width * height * 4);
+        for _ in 0..height {
+            for x in 0..width {
+                let index = if width > 1 { x * 255 / (width - 1) } else { 0 };
+                let [r, g, b] = self.entries[index];
+                rgba_data.extend_from_slice(&[r, g, b, 255]);
+            }
+        }
+        rgba_data
+    }
+}
+
+impl Default for ColorPalette {
+    fn default() -> Self {
+        Self::new(Colormap::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_lut() {
+        let lut = DisplayLut::default();
+        assert!(lut.is_identity());
+        assert_eq!(lut.map8(0), 0);
+        assert_eq!(lut.map8(128), 128);
+        assert_eq!(lut.map8(255), 255);
+        assert_eq!(lut.map10(1023), 255);
+        assert_eq!(lut.map10(512), 128);
+        assert_eq!(lut.map12(4095), 255);
+        assert_eq!(lut.map12(2048), 128);
+        assert_eq!(lut.map10_dithered(1023, 3, 2, Dithering::Bayer4), 255);
+        assert_eq!(lut.map12_dithered(4095, 7, 5, Dithering::Bayer8), 255);
+    }
+
+    #[test]
+    fn test_dithering_keeps_the_mean() {
+        // 514 falls between two 8-bit levels; a 4x4 tile averages to it
+        let lut = DisplayLut::default();
+        let exact = 514.0 * 255.0 / 1023.0;
+        let sum: u32 = (0..4).flat_map(|y| (0..4).map(move |x| (x, y))).map(|(x, y)| lut.map10_dithered(514, x, y, Dithering::Bayer4) as u32).sum();
+        assert!((sum as f64 / 16.0 - exact).abs() < 0.1);
+        let sum: u32 = (0..8).flat_map(|y| (0..8).map(move |x| (x, y))).map(|(x, y)| lut.map10_dithered(514, x, y, Dithering::Bayer8) as u32).sum();
+        assert!((sum as f64 / 64.0 - exact).abs() < 0.05);
+        assert_eq!(lut.map10_dithered(0, 1, 1, Dithering::Bayer8), 0);
+
+        // Without dithering every pixel rounds to the same level
+        assert!((0..4).all(|x| lut.map10_dithered(514, x, 0, Dithering::Off) == lut.map10(514)));
+    }
+
+    #[test]
+    fn test_window_level_and_gamma() {
+        // Narrow window around mid-gray saturates the extremes
+        let lut = DisplayLut::new(WindowLevel::new(0.5, 0.5), Gamma::default());
+        assert_eq!(lut.map8(50), 0);
+        assert!((lut.map8(128) as i32 - 128).abs() <= 1);
+        assert_eq!(lut.map8(200), 255);
+
+        // Gamma > 1 brightens mid-tones but keeps the end points
+        let lut = DisplayLut::new(WindowLevel::default(), Gamma::new(2.2));
+        assert!(lut.map8(64) > 64);
+        assert_eq!(lut.map8(0), 0);
+        assert_eq!(lut.map8(255), 255);
+    }
+
+    #[test]
+    fn test_colormaps() {
+        for colormap in Colormap::ALL {
+            assert_eq!(Colormap::from_index(colormap.index()), colormap);
+            assert_eq!(Colormap::from_name(colormap.name()), Some(colormap));
+        }
+
+        assert_eq!(Colormap::HotMetal.color(0.0), [0, 0, 0]);
+        assert_eq!(Colormap::HotMetal.color(1.0), [255, 255, 255]);
+        assert_eq!(Colormap::Pet.color(0.0), [0, 0, 0]);
+        assert_eq!(Colormap::Pet.color(1.0), [255, 255, 255]);
+
+        let mut pixel = [200, 200, 200, 255];
+        ColorPalette::new(Colormap::Grayscale).apply_rgba(&mut pixel);
+        assert_eq!(pixel, [200, 200, 200, 255]);
+        ColorPalette::new(Colormap::HotMetal).apply_rgba(&mut pixel);
+        assert_eq!(pixel[0], 255);
+        assert_eq!(pixel[3], 255);
+    }
+}
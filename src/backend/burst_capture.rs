@@ -0,0 +1,183 @@
+// src/backend/burst_capture.rs - Producer-Triggered Burst Capture from a Cine Buffer
+
+//! A producer marks a frame of interest (a footswitch press, a detected event)
+//! by setting `FLAG_TRIGGER` in its header. The consumer then saves the frames
+//! around it: up to `pre_frames` from before the trigger, held in a short cine
+//! buffer, the trigger frame itself and `post_frames` after it. Bursts are
+//! written in the recording format of `sinks::RecorderSink`, with metadata
+//! anonymized and, with a recording key, sealed as a whole under an `.enc` suffix.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use tracing::{debug, info, warn};
+
+use crate::backend::frame_sink::FrameSink;
+use crate::backend::sinks::{encode_display_frame, RecordingPolicy, RECORDING_EXTENSION};
+use crate::backend::types::ProcessedFrame;
+use crate::backend::BackendEvent;
+use crate::encryption;
+
+/// Header flag: capture the frames around this one
+pub const FLAG_TRIGGER: u32 = 0x0000_0100;
+
+/// Most frames kept on either side of a trigger, bounding the cine buffer's memory
+pub const MAX_BURST_FRAMES: usize = 600;
+
+/// Where bursts go and how many frames surround the trigger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurstConfig {
+    pub dir: PathBuf,
+    pub pre_frames: usize,
+    pub post_frames: usize,
+}
+
+/// Cine buffer collecting the frames around triggers
+///
+/// A trigger arriving while a burst is still collecting is part of that burst.
+pub struct BurstCollector {
+    pre_frames: usize,
+    post_frames: usize,
+    cine: VecDeque<ProcessedFrame>,
+    burst: Vec<ProcessedFrame>,
+    remaining: Option<usize>,
+}
+
+impl BurstCollector {
+    /// Create a collector keeping `pre_frames` before and `post_frames` after each trigger
+    pub fn new(pre_frames: usize, post_frames: usize) -> Self {
+        Self { pre_frames, post_frames, cine: VecDeque::with_capacity(pre_frames), burst: Vec::new(), remaining: None }
+    }
+
+    /// Check if a burst is being collected
+    pub fn is_capturing(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// Add a frame, returning the burst it completes
+    pub fn push(&mut self, frame: ProcessedFrame) -> Option<Vec<ProcessedFrame>> {
+        match self.remaining {
+            Some(remaining) => {
+                if frame.header.flags & FLAG_TRIGGER != 0 {
+                    debug!("🎯 Trigger at frame {} falls into the current burst", frame.header.frame_id);
+                }
+                self.burst.push(frame);
+                self.remaining = Some(remaining - 1);
+            }
+            None if frame.header.flags & FLAG_TRIGGER != 0 => {
+                info!("🎯 Trigger at frame {}: capturing {} + {} frames", frame.header.frame_id, self.cine.len(), self.post_frames);
+                self.burst = self.cine.drain(..).collect();
+                self.burst.push(frame);
+                self.remaining = Some(self.post_frames);
+            }
+            None => {
+                if self.pre_frames > 0 {
+                    if self.cine.len() == self.pre_frames {
+                        self.cine.pop_front();
+                    }
+                    self.cine.push_back(frame);
+                }
+                return None;
+            }
+        }
+
+        if self.remaining == Some(0) {
+            self.remaining = None;
+            return Some(std::mem::take(&mut self.burst));
+        }
+        None
+    }
+
+    /// Hand out a burst cut short, e.g. because the stream ended
+    pub fn take_partial(&mut self) -> Option<Vec<ProcessedFrame>> {
+        self.remaining.take().map(|_| std::mem::take(&mut self.burst))
+    }
+}
+
+/// Writes a file for every triggered burst
+pub struct BurstCaptureSink {
+    dir: PathBuf,
+    policy: RecordingPolicy,
+    collector: BurstCollector,
+}
+
+impl BurstCaptureSink {
+    /// Create the sink writing bursts protected by `policy`; the directory is created with the first burst
+    pub fn new(config: &BurstConfig, policy: &RecordingPolicy) -> Self {
+        info!("🎯 Burst capture to {} ({} frames before, {} after a trigger)", config.dir.display(), config.pre_frames, config.post_frames);
+        Self { dir: config.dir.clone(), policy: policy.clone(), collector: BurstCollector::new(config.pre_frames, config.post_frames) }
+    }
+
+    /// Write a burst off the async runtime so the queue keeps draining
+    fn save(&self, burst: Vec<ProcessedFrame>) {
+        let Some(trigger) = burst.iter().find(|frame| frame.header.flags & FLAG_TRIGGER != 0) else {
+            return;
+        };
+        let name = format!("burst_{}_{:06}.{}", chrono::Local::now().format("%Y%m%d_%H%M%S"), trigger.header.frame_id, RECORDING_EXTENSION);
+        let path = self.dir.join(name);
+        let dir = self.dir.clone();
+        let policy = self.policy.clone();
+        tokio::task::spawn_blocking(move || {
+            let data: Vec<u8> = burst.iter().flat_map(|frame| encode_display_frame(frame, policy.anonymization)).collect();
+            let written = std::fs::create_dir_all(&dir)
+                .map_err(encryption::EncryptionError::from)
+                .and_then(|_| encryption::write_recording(&path, &data, policy.encryption.as_ref()));
+            match written {
+                Ok(written) => info!("🎯 Saved {} frame burst to {}", burst.len(), written.display()),
+                Err(e) => warn!("⚠️ Failed to save burst to {}: {}", path.display(), e),
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl FrameSink for BurstCaptureSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        if let Some(burst) = self.collector.push(frame) {
+            self.save(burst);
+        }
+    }
+
+    async fn on_event(&mut self, _event: BackendEvent) {}
+
+    async fn finish(&mut self) {
+        if let Some(burst) = self.collector.take_partial() {
+            self.save(burst);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::pixel_pool::RgbaBuffer;
+    use crate::backend::types::{FrameFormat, FrameHeader};
+
+    fn frame(frame_id: u64, flags: u32) -> ProcessedFrame {
+        let header = FrameHeader { frame_id, sequence_number: frame_id, flags, ..FrameHeader::for_test(1, 1, 4, 0) };
+        ProcessedFrame::new(header, RgbaBuffer::new(1, 1), None, std::time::Instant::now(), FrameFormat::RGBA)
+    }
+
+    #[test]
+    fn test_burst_around_trigger() {
+        let mut collector = BurstCollector::new(2, 2);
+        for id in 1..=4 {
+            assert!(collector.push(frame(id, 0)).is_none());
+        }
+        assert!(collector.push(frame(5, FLAG_TRIGGER)).is_none());
+        assert!(collector.is_capturing());
+        // A second trigger inside the burst does not start another one
+        assert!(collector.push(frame(6, FLAG_TRIGGER)).is_none());
+
+        let burst = collector.push(frame(7, 0)).unwrap();
+        let ids: Vec<u64> = burst.iter().map(|frame| frame.header.frame_id).collect();
+        assert_eq!(ids, vec![3, 4, 5, 6, 7]);
+        assert!(!collector.is_capturing());
+
+        // The cine buffer starts over after a burst
+        assert!(collector.push(frame(8, FLAG_TRIGGER)).is_none());
+        let partial = collector.take_partial().unwrap();
+        assert_eq!(partial.len(), 1);
+    }
+}
@@ -0,0 +1,94 @@
+// src/backend/calibration.rs - Pixel Spacing Calibration for Physical Measurements
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::transform::Orientation;
+
+/// Physical size of one pixel in millimeters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PixelSpacing {
+    pub x_mm: f32,
+    pub y_mm: f32,
+}
+
+impl PixelSpacing {
+    /// Create a spacing, rejecting non-positive or non-finite values
+    pub fn new(x_mm: f32, y_mm: f32) -> Option<Self> {
+        let valid = |v: f32| v.is_finite() && v > 0.0;
+        (valid(x_mm) && valid(y_mm)).then_some(Self { x_mm, y_mm })
+    }
+
+    /// Create a spacing with square pixels
+    pub fn isotropic(mm: f32) -> Option<Self> {
+        Self::new(mm, mm)
+    }
+
+    /// Read the pixel spacing from per-frame JSON metadata
+    ///
+    /// Accepts `pixel_spacing` as `[row, column]` in millimeters (DICOM order,
+    /// i.e. vertical then horizontal) or as a single number, `pixel_spacing_mm`,
+    /// or separate `pixel_spacing_x` / `pixel_spacing_y` values.
+    pub fn from_metadata(metadata: &str) -> Option<Self> {
+        let json: serde_json::Value = serde_json::from_str(metadata).ok()?;
+        let number = |key: &str| json.get(key).and_then(|v| v.as_f64()).map(|v| v as f32);
+
+        if let Some(values) = json.get("pixel_spacing").and_then(|v| v.as_array()) {
+            let row = values.first().and_then(|v| v.as_f64())?;
+            let column = values.get(1).and_then(|v| v.as_f64()).unwrap_or(row);
+            return Self::new(column as f32, row as f32);
+        }
+
+        if let Some(spacing) = number("pixel_spacing").or_else(|| number("pixel_spacing_mm")) {
+            return Self::isotropic(spacing);
+        }
+
+        match (number("pixel_spacing_x"), number("pixel_spacing_y")) {
+            (Some(x), Some(y)) => Self::new(x, y),
+            (Some(x), None) => Self::isotropic(x),
+            (None, Some(y)) => Self::isotropic(y),
+            (None, None) => None,
+        }
+    }
+
+    /// Get the spacing of the displayed frame after rotation
+    pub fn oriented(self, orientation: &Orientation) -> Self {
+        if orientation.swaps_axes() {
+            Self { x_mm: self.y_mm, y_mm: self.x_mm }
+        } else {
+            self
+        }
+    }
+
+    /// Convert a pixel displacement to millimeters
+    pub fn length_mm(&self, dx_px: f32, dy_px: f32) -> f32 {
+        let dx = dx_px * self.x_mm;
+        let dy = dy_px * self.y_mm;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::transform::Rotation;
+
+    #[test]
+    fn test_spacing_from_metadata() {
+        let spacing = PixelSpacing::from_metadata(r#"{"pixel_spacing": [0.2, 0.1]}"#).unwrap();
+        assert_eq!(spacing, PixelSpacing { x_mm: 0.1, y_mm: 0.2 });
+
+        let spacing = PixelSpacing::from_metadata(r#"{"probe": "L12", "pixel_spacing_mm": 0.15}"#).unwrap();
+        assert_eq!(spacing, PixelSpacing { x_mm: 0.15, y_mm: 0.15 });
+
+        assert!(PixelSpacing::from_metadata(r#"{"pixel_spacing": 0}"#).is_none());
+        assert!(PixelSpacing::from_metadata("not json").is_none());
+    }
+
+    #[test]
+    fn test_oriented_spacing() {
+        let spacing = PixelSpacing::new(0.1, 0.3).unwrap();
+        let rotated = spacing.oriented(&Orientation::new(Rotation::Cw90, false, false));
+        assert_eq!(rotated, PixelSpacing { x_mm: 0.3, y_mm: 0.1 });
+        assert!((spacing.length_mm(30.0, 0.0) - 3.0).abs() < 1e-5);
+    }
+}
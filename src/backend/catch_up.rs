@@ -0,0 +1,59 @@
+// src/backend/catch_up.rs - Adaptive Catch-Up Policy Driven by Ring Buffer Backlog
+
+/// Backlog thresholds for switching between sequential reads and skipping to the latest frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveCatchUp {
+    /// Start skipping once more than this many frames are waiting
+    pub skip_above: u64,
+    /// Return to sequential reads once the backlog is at or below this
+    pub resume_at: u64,
+}
+
+impl Default for AdaptiveCatchUp {
+    fn default() -> Self {
+        Self { skip_above: 4, resume_at: 1 }
+    }
+}
+
+impl AdaptiveCatchUp {
+    /// Create thresholds, keeping the resume level below the skip level
+    pub fn new(skip_above: u64, resume_at: u64) -> Self {
+        let skip_above = skip_above.max(1);
+        Self { skip_above, resume_at: resume_at.min(skip_above - 1) }
+    }
+
+    /// Decide whether the next read skips to the latest frame
+    ///
+    /// Between the two thresholds the current mode is kept, so a backlog hovering
+    /// around one level doesn't flip the reader back and forth every frame.
+    pub fn should_skip(&self, backlog: u64, skipping: bool) -> bool {
+        if skipping {
+            backlog > self.resume_at
+        } else {
+            backlog > self.skip_above
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptive_hysteresis() {
+        let policy = AdaptiveCatchUp::new(4, 1);
+
+        // Sequential until the backlog passes the upper threshold
+        assert!(!policy.should_skip(1, false));
+        assert!(!policy.should_skip(4, false));
+        assert!(policy.should_skip(5, false));
+
+        // Keeps skipping until the backlog drains to the lower threshold
+        assert!(policy.should_skip(3, true));
+        assert!(policy.should_skip(2, true));
+        assert!(!policy.should_skip(1, true));
+
+        assert_eq!(AdaptiveCatchUp::new(2, 5), AdaptiveCatchUp { skip_above: 2, resume_at: 1 });
+        assert_eq!(AdaptiveCatchUp::new(0, 0), AdaptiveCatchUp { skip_above: 1, resume_at: 0 });
+    }
+}
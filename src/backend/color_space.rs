@@ -0,0 +1,251 @@
+// src/backend/color_space.rs - Color Space Tagging and DICOM GSDF Display Calibration
+
+//! Producers may declare the color space of their frames in the JSON metadata
+//! (`{"color_space": "bt709"}`). Frames are converted to the display space after
+//! the filter pipeline; untagged frames are taken as sRGB. BT.709 shares the sRGB
+//! primaries, so only the transfer curve differs and one table per channel does.
+//!
+//! Diagnostic reading calls for the DICOM Grayscale Standard Display Function
+//! (PS3.14): equal steps in presentation value should look like equal steps in
+//! brightness. With a `DisplayCalibration` the monitor is modelled as a native
+//! 2.2 gamma between its minimum and maximum luminance, and drive levels are
+//! pre-distorted so it follows the GSDF. Untagged frames are then taken as
+//! presentation values (P-values), as DICOM does for grayscale.
+
+/// Metadata key declaring the color space of a frame
+pub const METADATA_KEY: &str = "color_space";
+
+/// Color space a producer can declare
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    /// sRGB transfer curve (computer graphics, most cameras)
+    Srgb,
+    /// BT.709 video, shown through the BT.1886 2.4 gamma
+    Bt709,
+    /// DICOM GSDF presentation values
+    Gsdf,
+}
+
+impl ColorSpace {
+    /// All color spaces, in table order
+    pub const ALL: [ColorSpace; 3] = [ColorSpace::Srgb, ColorSpace::Bt709, ColorSpace::Gsdf];
+
+    /// Parse a color space name as used in metadata
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "srgb" => Some(Self::Srgb),
+            "bt709" | "bt.709" | "rec709" => Some(Self::Bt709),
+            "gsdf" | "dicom_gsdf" | "dicom-gsdf" => Some(Self::Gsdf),
+            _ => None,
+        }
+    }
+
+    /// Get the name of the color space
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Srgb => "srgb",
+            Self::Bt709 => "bt709",
+            Self::Gsdf => "gsdf",
+        }
+    }
+
+    /// Color space a frame's metadata declares, if any
+    pub fn from_metadata(metadata: Option<&str>) -> Option<Self> {
+        // Only parse metadata that mentions the key
+        let metadata = metadata.filter(|text| text.contains(METADATA_KEY))?;
+        let json: serde_json::Value = serde_json::from_str(metadata).ok()?;
+        json.get(METADATA_KEY).and_then(|v| v.as_str()).and_then(Self::from_name)
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Self::Srgb => 0,
+            Self::Bt709 => 1,
+            Self::Gsdf => 2,
+        }
+    }
+}
+
+/// Lowest luminance the GSDF is defined for, in cd/m²
+pub const GSDF_MIN_LUMINANCE: f64 = 0.05;
+
+/// Highest luminance the GSDF is defined for, in cd/m²
+pub const GSDF_MAX_LUMINANCE: f64 = 3993.0;
+
+/// Luminance range of a diagnostic monitor, in cd/m²
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayCalibration {
+    pub min_luminance: f64,
+    pub max_luminance: f64,
+}
+
+impl DisplayCalibration {
+    /// Response assumed for the uncalibrated monitor
+    pub const NATIVE_GAMMA: f64 = 2.2;
+
+    /// Check that the range is usable for the GSDF
+    pub fn is_valid(&self) -> bool {
+        GSDF_MIN_LUMINANCE <= self.min_luminance
+            && self.min_luminance < self.max_luminance
+            && self.max_luminance <= GSDF_MAX_LUMINANCE
+    }
+}
+
+impl Default for DisplayCalibration {
+    fn default() -> Self {
+        Self { min_luminance: 0.5, max_luminance: 350.0 }
+    }
+}
+
+/// GSDF luminance in cd/m² of a just-noticeable-difference index (1 to 1023)
+pub fn gsdf_luminance(jnd: f64) -> f64 {
+    let x = jnd.clamp(1.0, 1023.0).ln();
+    let numerator = -1.3011877 + 8.0242636e-2 * x + 1.3646699e-1 * x.powi(2) - 2.5468404e-2 * x.powi(3) + 1.3635334e-3 * x.powi(4);
+    let denominator = 1.0 - 2.5840191e-2 * x - 1.0320229e-1 * x.powi(2) + 2.8745620e-2 * x.powi(3) - 3.1978977e-3 * x.powi(4)
+        + 1.2992634e-4 * x.powi(5);
+    10f64.powf(numerator / denominator)
+}
+
+/// GSDF just-noticeable-difference index of a luminance in cd/m²
+pub fn gsdf_jnd(luminance: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        71.498068, 94.593053, 41.912053, 9.8247004, 0.28175407, -1.1878455, -0.18014349, 0.14710899, -0.017046845,
+    ];
+    let y = luminance.clamp(GSDF_MIN_LUMINANCE, GSDF_MAX_LUMINANCE).log10();
+    COEFFICIENTS.iter().rev().fold(0.0, |jnd, coefficient| jnd * y + coefficient)
+}
+
+fn srgb_to_linear(value: f64) -> f64 {
+    if value <= 0.04045 { value / 12.92 } else { ((value + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(value: f64) -> f64 {
+    if value <= 0.0031308 { value * 12.92 } else { 1.055 * value.powf(1.0 / 2.4) - 0.055 }
+}
+
+/// Per-channel table taking one color space to the display
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorTransform {
+    lut: Option<Box<[u8; 256]>>,
+}
+
+impl ColorTransform {
+    /// Build the table for `source` on an sRGB monitor, or a GSDF-calibrated one
+    pub fn new(source: ColorSpace, calibration: Option<&DisplayCalibration>) -> Self {
+        // GSDF values need a luminance range even on an uncalibrated monitor
+        let range = calibration.copied().unwrap_or_default();
+        let (jnd_min, jnd_max) = (gsdf_jnd(range.min_luminance), gsdf_jnd(range.max_luminance));
+        let (luminance_min, luminance_max) = (gsdf_luminance(jnd_min), gsdf_luminance(jnd_max));
+
+        let mut lut = Box::new([0u8; 256]);
+        for (value, out) in lut.iter_mut().enumerate() {
+            let value = value as f64 / 255.0;
+
+            // Luminance relative to the display range
+            let relative = match source {
+                ColorSpace::Srgb => srgb_to_linear(value),
+                ColorSpace::Bt709 => value.powf(2.4),
+                ColorSpace::Gsdf => {
+                    let luminance = gsdf_luminance(jnd_min + value * (jnd_max - jnd_min));
+                    (luminance - luminance_min) / (luminance_max - luminance_min)
+                }
+            }
+            .clamp(0.0, 1.0);
+
+            // Drive level producing it on the monitor
+            let drive = match calibration {
+                Some(_) => relative.powf(1.0 / DisplayCalibration::NATIVE_GAMMA),
+                None => linear_to_srgb(relative),
+            };
+            *out = (drive * 255.0).round() as u8;
+        }
+
+        let is_identity = lut.iter().enumerate().all(|(value, &out)| out as usize == value);
+        Self { lut: (!is_identity).then_some(lut) }
+    }
+
+    /// Check if the transform leaves frames unchanged
+    pub fn is_identity(&self) -> bool {
+        self.lut.is_none()
+    }
+
+    /// Apply the table in place to the color channels of RGBA data
+    pub fn apply_rgba(&self, rgba_data: &mut [u8]) {
+        let Some(lut) = &self.lut else {
+            return;
+        };
+        for pixel in rgba_data.chunks_exact_mut(4) {
+            pixel[0] = lut[pixel[0] as usize];
+            pixel[1] = lut[pixel[1] as usize];
+            pixel[2] = lut[pixel[2] as usize];
+        }
+    }
+}
+
+/// Display transforms for every declared color space under one monitor setup
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorManagement {
+    calibration: Option<DisplayCalibration>,
+    transforms: Vec<ColorTransform>,
+}
+
+impl ColorManagement {
+    /// Build the transforms for an sRGB monitor, or a GSDF-calibrated one
+    pub fn new(calibration: Option<DisplayCalibration>) -> Self {
+        let transforms = ColorSpace::ALL.iter().map(|source| ColorTransform::new(*source, calibration.as_ref())).collect();
+        Self { calibration, transforms }
+    }
+
+    /// Get the monitor calibration, if any
+    pub fn calibration(&self) -> Option<DisplayCalibration> {
+        self.calibration
+    }
+
+    /// Transform for a frame declaring `source`; untagged frames are sRGB, or P-values when calibrated
+    pub fn transform(&self, source: Option<ColorSpace>) -> &ColorTransform {
+        let default = if self.calibration.is_some() { ColorSpace::Gsdf } else { ColorSpace::Srgb };
+        &self.transforms[source.unwrap_or(default).index()]
+    }
+}
+
+impl Default for ColorManagement {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gsdf_curve() {
+        assert!((gsdf_luminance(1.0) - 0.05).abs() < 1e-3);
+        assert!((gsdf_luminance(1023.0) - 3993.0).abs() < 1.0);
+        assert!((gsdf_jnd(gsdf_luminance(500.0)) - 500.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_display_transforms() {
+        let management = ColorManagement::default();
+        assert!(management.transform(None).is_identity());
+        assert!(management.transform(Some(ColorSpace::Srgb)).is_identity());
+
+        // BT.709 mid-gray is darker than sRGB mid-gray
+        let mut pixel = [128u8, 128, 128, 255];
+        management.transform(Some(ColorSpace::Bt709)).apply_rgba(&mut pixel);
+        assert_eq!(pixel, [120, 120, 120, 255]);
+
+        // Calibrated: untagged frames follow the GSDF and keep their end points
+        let calibrated = ColorManagement::new(Some(DisplayCalibration::default()));
+        let transform = calibrated.transform(None);
+        assert_eq!(transform, calibrated.transform(Some(ColorSpace::Gsdf)));
+        let mut ramp: Vec<u8> = (0..=255u8).flat_map(|value| [value, value, value, 255]).collect();
+        transform.apply_rgba(&mut ramp);
+        assert_eq!((ramp[0], ramp[255 * 4]), (0, 255));
+        assert!(ramp.chunks_exact(4).zip(ramp.chunks_exact(4).skip(1)).all(|(a, b)| a[0] <= b[0]));
+
+        assert_eq!(ColorSpace::from_metadata(Some(r#"{"color_space": "Rec709"}"#)), Some(ColorSpace::Bt709));
+        assert_eq!(ColorSpace::from_metadata(Some(r#"{"probe": "linear"}"#)), None);
+    }
+}
@@ -6,23 +6,31 @@ use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
 use crate::backend::{
+    frame_source::{open_source, FrameSource},
+    memory::MappingMemory,
+    protocol::Handshake,
     shared_memory::SharedMemoryError,
+    reconnect::ReconnectPolicy,
     types::RawFrame,
-    ConnectionConfig, ConnectionStatus, SharedMemoryReader,
+    ConnectionConfig, ConnectionStatus,
 };
 
 /// Connection manager for medical imaging devices
 pub struct ConnectionManager {
-    // Shared memory reader
-    reader: Arc<RwLock<Option<SharedMemoryReader>>>,
+    // Frame source (shared memory or network)
+    reader: Arc<RwLock<Option<Box<dyn FrameSource>>>>,
 
     // Connection state
     connection_status: Arc<RwLock<ConnectionStatus>>,
     current_config: Arc<RwLock<Option<ConnectionConfig>>>,
+    /// Source last asked for, kept while waiting for its producer
+    target: Arc<RwLock<Option<(String, ConnectionConfig)>>>,
 
     // Reconnection management
     reconnect_attempts: Arc<RwLock<u32>>,
     last_reconnect_attempt: Arc<RwLock<Option<Instant>>>,
+    /// Backoff to wait after the last attempt before the next one
+    reconnect_backoff: Arc<RwLock<Duration>>,
 
     // Statistics
     connection_stats: Arc<RwLock<ConnectionStatistics>>,
@@ -38,8 +46,10 @@ impl ConnectionManager {
             reader: Arc::new(RwLock::new(None)),
             connection_status: Arc::new(RwLock::new(ConnectionStatus::Disconnected)),
             current_config: Arc::new(RwLock::new(None)),
+            target: Arc::new(RwLock::new(None)),
             reconnect_attempts: Arc::new(RwLock::new(0)),
             last_reconnect_attempt: Arc::new(RwLock::new(None)),
+            reconnect_backoff: Arc::new(RwLock::new(Duration::ZERO)),
             connection_stats: Arc::new(RwLock::new(ConnectionStatistics::default())),
             base_config,
         }
@@ -55,9 +65,10 @@ impl ConnectionManager {
 
         // Update connection status
         *self.connection_status.write().await = ConnectionStatus::Connecting;
+        *self.target.write().await = Some((shm_name.to_string(), config.clone()));
 
-        // Create shared memory reader
-        let mut reader = SharedMemoryReader::new(shm_name, config.clone())
+        // Create the frame source for this name
+        let mut reader = open_source(shm_name, config.clone())
             .map_err(|e| ConnectionManagerError::SharedMemory(e))?;
 
         // Attempt connection
@@ -66,8 +77,10 @@ impl ConnectionManager {
                 // Store successful connection
                 *self.reader.write().await = Some(reader);
                 *self.connection_status.write().await = ConnectionStatus::Connected;
+                if config.reconnect.reset_on_success {
+                    *self.reconnect_attempts.write().await = 0;
+                }
                 *self.current_config.write().await = Some(config);
-                *self.reconnect_attempts.write().await = 0;
 
                 // Update statistics
                 {
@@ -80,6 +93,14 @@ impl ConnectionManager {
                 info!("✅ Successfully connected to medical device: {}", shm_name);
                 Ok(())
             }
+            Err(SharedMemoryError::NotFound(name)) => {
+                // Not an error yet: the producer has not created its region
+                *self.connection_status.write().await = ConnectionStatus::WaitingForProducer;
+                self.connection_stats.write().await.failed_connections += 1;
+
+                info!("⏳ Waiting for producer: {}", name);
+                Err(ConnectionManagerError::SharedMemory(SharedMemoryError::NotFound(name)))
+            }
             Err(e) => {
                 // Connection failed
                 *self.connection_status.write().await = ConnectionStatus::Error(e.to_string());
@@ -109,6 +130,7 @@ impl ConnectionManager {
         // Update status
         *self.connection_status.write().await = ConnectionStatus::Disconnected;
         *self.current_config.write().await = None;
+        *self.target.write().await = None;
 
         // Update statistics
         {
@@ -135,6 +157,33 @@ impl ConnectionManager {
         self.connection_status.read().await.clone()
     }
 
+    /// Name of the source last connected to, or being waited for
+    pub async fn target_name(&self) -> Option<String> {
+        self.target.read().await.as_ref().map(|(name, _)| name.clone())
+    }
+
+    /// Connect again to the last requested source, replacing a lost reader
+    pub async fn retry_connect(&self) -> Result<(), ConnectionManagerError> {
+        let (shm_name, config) = self.target.read().await.clone().ok_or(ConnectionManagerError::NoConfiguration)?;
+
+        if let Some(mut reader) = self.reader.write().await.take() {
+            reader.disconnect().await;
+        }
+
+        self.connect(&shm_name, config).await
+    }
+
+    /// Number of frames waiting in the ring buffer (0 when not connected)
+    pub async fn backlog(&self) -> u64 {
+        self.reader.read().await.as_ref().map_or(0, |reader| reader.backlog())
+    }
+
+    /// Producer's total frames written, for sources with a control block
+    pub async fn producer_frames_written(&self) -> Option<u64> {
+        let reader = self.reader.read().await;
+        reader.as_ref()?.get_statistics().control_block.map(|control_block| control_block.total_frames_written)
+    }
+
     /// Get next frame from shared memory
     pub async fn get_next_frame(
         &self,
@@ -197,18 +246,20 @@ impl ConnectionManager {
 
     /// Attempt automatic reconnection
     async fn attempt_reconnection(&self) -> Result<(), ConnectionManagerError> {
+        let policy = self.reconnect_policy().await;
         let mut attempts = self.reconnect_attempts.write().await;
         let mut last_attempt = self.last_reconnect_attempt.write().await;
+        let mut backoff = self.reconnect_backoff.write().await;
 
         // Check if we should attempt reconnection
         if let Some(last_attempt_time) = *last_attempt {
-            if last_attempt_time.elapsed() < self.base_config.reconnect_delay {
+            if last_attempt_time.elapsed() < *backoff {
                 return Err(ConnectionManagerError::ReconnectTooSoon);
             }
         }
 
         // Check if we've exceeded max attempts
-        if *attempts >= self.base_config.max_reconnect_attempts {
+        if !policy.allows(*attempts) {
             warn!("🔄 Maximum reconnection attempts exceeded: {}", *attempts);
             *self.connection_status.write().await = ConnectionStatus::Error(format!(
                 "Max reconnection attempts exceeded: {}",
@@ -217,6 +268,7 @@ impl ConnectionManager {
             return Err(ConnectionManagerError::MaxReconnectAttemptsExceeded);
         }
 
+        *backoff = policy.next_delay(*attempts);
         *attempts += 1;
         *last_attempt = Some(Instant::now());
 
@@ -238,7 +290,10 @@ impl ConnectionManager {
                     // Successful reconnection
                     *self.reader.write().await = Some(reader);
                     *self.connection_status.write().await = ConnectionStatus::Connected;
-                    *attempts = 0; // Reset attempts counter
+                    if policy.reset_on_success {
+                        *attempts = 0;
+                        *backoff = Duration::ZERO;
+                    }
 
                     // Update statistics
                     {
@@ -258,7 +313,7 @@ impl ConnectionManager {
                         stats.failed_reconnections += 1;
                     }
 
-                    if *attempts >= self.base_config.max_reconnect_attempts {
+                    if !policy.allows(*attempts) {
                         *self.connection_status.write().await = ConnectionStatus::Error(format!(
                             "Reconnection failed after {} attempts",
                             *attempts
@@ -316,6 +371,9 @@ impl ConnectionManager {
             let reader_stats = reader.get_statistics();
             stats.frames_processed = reader_stats.frames_processed;
             stats.error_count = reader_stats.error_count;
+            stats.corrupted_frames = reader_stats.corrupted_frames;
+            stats.memory = reader_stats.memory;
+            stats.protocol = reader_stats.protocol;
             stats.last_frame_elapsed = reader_stats.last_frame_elapsed;
         }
 
@@ -326,8 +384,9 @@ impl ConnectionManager {
     pub async fn force_reconnect(&self) -> Result<(), ConnectionManagerError> {
         info!("🔄 Forcing manual reconnection");
 
-        // Reset attempts counter for manual reconnection
+        // Reset attempts counter and backoff for manual reconnection
         *self.reconnect_attempts.write().await = 0;
+        *self.reconnect_backoff.write().await = Duration::ZERO;
 
         self.attempt_reconnection().await
     }
@@ -338,13 +397,13 @@ impl ConnectionManager {
         let last_attempt = *self.last_reconnect_attempt.read().await;
 
         // Check attempts limit
-        if attempts >= self.base_config.max_reconnect_attempts {
+        if !self.reconnect_policy().await.allows(attempts) {
             return false;
         }
 
         // Check time delay
         if let Some(last_attempt_time) = last_attempt {
-            if last_attempt_time.elapsed() < self.base_config.reconnect_delay {
+            if last_attempt_time.elapsed() < *self.reconnect_backoff.read().await {
                 return false;
             }
         }
@@ -352,6 +411,15 @@ impl ConnectionManager {
         // Must have configuration to reconnect
         self.current_config.read().await.is_some()
     }
+
+    /// Reconnect policy of the current connection, or the default one
+    pub async fn reconnect_policy(&self) -> ReconnectPolicy {
+        self.current_config
+            .read()
+            .await
+            .as_ref()
+            .map_or(self.base_config.reconnect, |config| config.reconnect)
+    }
 }
 
 /// Connection manager errors
@@ -388,6 +456,13 @@ pub enum ConnectionManagerError {
     Other(String),
 }
 
+impl ConnectionManagerError {
+    /// Check if the producer has not created its region (or socket) yet
+    pub fn is_producer_missing(&self) -> bool {
+        matches!(self, ConnectionManagerError::SharedMemory(SharedMemoryError::NotFound(_)))
+    }
+}
+
 /// Connection statistics and monitoring
 #[derive(Debug, Clone, Default)]
 pub struct ConnectionStatistics {
@@ -407,7 +482,10 @@ pub struct ConnectionStatistics {
     // Frame processing (from reader)
     pub frames_processed: u64,
     pub error_count: u64,
+    pub corrupted_frames: u64,
     pub last_frame_elapsed: Duration,
+    pub memory: MappingMemory,
+    pub protocol: Option<Handshake>,
 
     // Error tracking
     pub last_error: Option<String>,
@@ -0,0 +1,231 @@
+// src/backend/deinterlace.rs - Bob and Weave Deinterlacing of Interlaced Producers
+
+//! Older fluoroscopy chains and analog capture cards deliver interlaced video:
+//! the even (top) and odd (bottom) rows are captured one field period apart,
+//! so anything moving shows comb teeth. Producers flag such frames in the
+//! header, either as a full frame holding both fields interleaved
+//! (`FLAG_INTERLACED`, with `FLAG_BOTTOM_FIELD` when the bottom field came
+//! first) or as one field at half height (`FLAG_INTERLACED | FLAG_SINGLE_FIELD`,
+//! with `FLAG_BOTTOM_FIELD` marking a bottom field).
+//!
+//! Bob shows one field on its own and interpolates the missing rows: no combing,
+//! at half the vertical resolution. Weave puts two fields together at full
+//! resolution: sharp on still anatomy, but moving edges still comb. Frames
+//! leave progressive and unflagged, at full height.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::stride::row_bytes;
+use crate::backend::types::{FrameFormat, FrameHeader, RawFrame};
+
+/// Header flag: the frame carries interlaced fields
+pub const FLAG_INTERLACED: u32 = 0x0000_0800;
+
+/// Header flag: the frame is a single field at half height
+pub const FLAG_SINGLE_FIELD: u32 = 0x0000_1000;
+
+/// Header flag: the (first) field is the bottom one
+pub const FLAG_BOTTOM_FIELD: u32 = 0x0000_2000;
+
+const FIELD_FLAGS: u32 = FLAG_INTERLACED | FLAG_SINGLE_FIELD | FLAG_BOTTOM_FIELD;
+
+/// Deinterlacing applied to frames flagged as interlaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Deinterlace {
+    /// Show frames as they arrive
+    #[default]
+    Off,
+    /// Show every field on its own, interpolating the missing rows
+    Bob,
+    /// Combine two fields into one full-resolution frame
+    Weave,
+}
+
+impl Deinterlace {
+    /// All deinterlacing modes
+    pub const ALL: [Deinterlace; 3] = [Deinterlace::Off, Deinterlace::Bob, Deinterlace::Weave];
+
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Deinterlace::Off => "Off",
+            Deinterlace::Bob => "Bob",
+            Deinterlace::Weave => "Weave",
+        }
+    }
+}
+
+/// Check if a frame's rows can be averaged byte by byte (8-bit samples)
+///
+/// Rows of other formats are repeated instead.
+fn interpolates(format: FrameFormat) -> bool {
+    matches!(
+        format,
+        FrameFormat::YUV | FrameFormat::Grayscale | FrameFormat::RGB | FrameFormat::RGBA | FrameFormat::BGR | FrameFormat::BGRA
+    )
+}
+
+/// Fill the rows of `rows` whose parity is not `kept` from their neighbours
+fn fill_rows(data: &mut [u8], row: usize, rows: usize, kept: usize, interpolate: bool) {
+    for y in (0..rows).filter(|y| y % 2 != kept) {
+        let above = y.checked_sub(1);
+        let below = (y + 1 < rows).then_some(y + 1);
+        match (above, below) {
+            (Some(above), Some(below)) if interpolate => {
+                let (head, tail) = data.split_at_mut(y * row);
+                let (out, rest) = tail.split_at_mut(row);
+                let (above, below) = (&head[above * row..][..row], &rest[(below - y - 1) * row..][..row]);
+                for ((out, &a), &b) in out.iter_mut().zip(above).zip(below) {
+                    *out = ((a as u16 + b as u16 + 1) / 2) as u8;
+                }
+            }
+            (Some(source), _) | (None, Some(source)) => data.copy_within(source * row..(source + 1) * row, y * row),
+            (None, None) => {}
+        }
+    }
+}
+
+/// Deinterlacing state of one stream
+#[derive(Debug, Default)]
+pub struct Deinterlacer {
+    mode: Deinterlace,
+    /// Last single field, woven with the next one of opposite parity
+    previous_field: Option<RawFrame>,
+}
+
+impl Deinterlacer {
+    /// Create a deinterlacer in the given mode
+    pub fn new(mode: Deinterlace) -> Self {
+        Self { mode, previous_field: None }
+    }
+
+    /// Get the deinterlacing mode
+    pub fn mode(&self) -> Deinterlace {
+        self.mode
+    }
+
+    /// Change the mode, forgetting the held field
+    pub fn set_mode(&mut self, mode: Deinterlace) {
+        if mode != self.mode {
+            self.mode = mode;
+            self.reset();
+        }
+    }
+
+    /// Forget the held field (new stream or disconnect)
+    pub fn reset(&mut self) {
+        self.previous_field = None;
+    }
+
+    /// Deinterlace a frame, returning progressive frames unchanged
+    ///
+    /// 12-bit packed rows do not end on byte boundaries and pass unchanged, as
+    /// do frames whose data does not fit their header (the converter reports those).
+    pub fn process(&mut self, raw_frame: RawFrame) -> RawFrame {
+        let header = raw_frame.header;
+        let format = FrameFormat::from_code(header.format_code);
+        let (row, rows) = (row_bytes(&header), header.height as usize);
+        if self.mode == Deinterlace::Off
+            || header.flags & FLAG_INTERLACED == 0
+            || format == FrameFormat::Gray12Packed
+            || row == 0
+            || raw_frame.data.len() != row * rows
+        {
+            return raw_frame;
+        }
+
+        let interpolate = interpolates(format);
+        let bottom = (header.flags & FLAG_BOTTOM_FIELD != 0) as usize;
+        if header.flags & FLAG_SINGLE_FIELD == 0 {
+            // Both fields are already woven; bob keeps the earlier one
+            if self.mode == Deinterlace::Weave {
+                return progressive(raw_frame, header.height, None);
+            }
+            let mut data = raw_frame.data.to_vec();
+            fill_rows(&mut data, row, rows, bottom, interpolate);
+            return progressive(raw_frame, header.height, Some(data));
+        }
+
+        let previous = self.previous_field.replace(raw_frame.clone());
+        let mut data = vec![0u8; row * rows * 2];
+        let mut place = |field: &[u8], parity: usize| {
+            for (y, source) in field.chunks_exact(row).enumerate() {
+                data[(y * 2 + parity) * row..][..row].copy_from_slice(source);
+            }
+        };
+        place(&raw_frame.data, bottom);
+
+        let pair = previous.filter(|previous| {
+            self.mode == Deinterlace::Weave
+                && same_geometry(&previous.header, &header)
+                && (previous.header.flags & FLAG_BOTTOM_FIELD != 0) as usize != bottom
+        });
+        match pair {
+            Some(previous) => place(&previous.data, 1 - bottom),
+            None => fill_rows(&mut data, row, rows * 2, bottom, interpolate),
+        }
+        progressive(raw_frame, header.height * 2, Some(data))
+    }
+}
+
+fn same_geometry(a: &FrameHeader, b: &FrameHeader) -> bool {
+    (a.width, a.height, a.bytes_per_pixel, a.format_code) == (b.width, b.height, b.bytes_per_pixel, b.format_code)
+}
+
+/// Frame with the field flags cleared, and new data and height if given
+fn progressive(raw_frame: RawFrame, height: u32, data: Option<Vec<u8>>) -> RawFrame {
+    let mut header = raw_frame.header;
+    header.flags &= !FIELD_FLAGS;
+    header.height = height;
+    let data = data.map_or(raw_frame.data, |data| Arc::from(data.into_boxed_slice()));
+    header.data_size = data.len() as u32;
+    RawFrame { header, data, metadata: raw_frame.metadata, received_at: raw_frame.received_at }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(data: Vec<u8>, height: u32, flags: u32) -> RawFrame {
+        let header = FrameHeader { flags, ..FrameHeader::for_test(1, height, 1, FrameFormat::Grayscale.to_code()) };
+        RawFrame::new(header, Arc::from(data), None)
+    }
+
+    #[test]
+    fn test_bob_interleaved_frame() {
+        let mut bob = Deinterlacer::new(Deinterlace::Bob);
+        let progressive = frame(vec![10, 200, 30, 200], 4, 0);
+        assert_eq!(&bob.process(progressive).data[..], &[10, 200, 30, 200]);
+
+        // Top field kept, bottom rows interpolated (the last one repeated)
+        let top_first = bob.process(frame(vec![10, 200, 30, 200], 4, FLAG_INTERLACED));
+        assert_eq!(&top_first.data[..], &[10, 20, 30, 30]);
+        assert_eq!(top_first.header.flags & FIELD_FLAGS, 0);
+
+        let bottom_first = bob.process(frame(vec![10, 200, 30, 100], 4, FLAG_INTERLACED | FLAG_BOTTOM_FIELD));
+        assert_eq!(&bottom_first.data[..], &[200, 200, 150, 100]);
+
+        let mut weave = Deinterlacer::new(Deinterlace::Weave);
+        assert_eq!(&weave.process(frame(vec![10, 200], 2, FLAG_INTERLACED)).data[..], &[10, 200]);
+    }
+
+    #[test]
+    fn test_single_fields() {
+        let top = || frame(vec![10, 30], 2, FLAG_INTERLACED | FLAG_SINGLE_FIELD);
+        let bottom = || frame(vec![20, 40], 2, FLAG_INTERLACED | FLAG_SINGLE_FIELD | FLAG_BOTTOM_FIELD);
+
+        let mut bob = Deinterlacer::new(Deinterlace::Bob);
+        let doubled = bob.process(bottom());
+        assert_eq!(doubled.header.height, 4);
+        assert_eq!(&doubled.data[..], &[20, 20, 30, 40]);
+
+        // Weave bobs until it holds a field of the other parity
+        let mut weave = Deinterlacer::new(Deinterlace::Weave);
+        assert_eq!(&weave.process(top()).data[..], &[10, 20, 30, 30]);
+        assert_eq!(&weave.process(bottom()).data[..], &[10, 20, 30, 40]);
+        weave.reset();
+        assert_eq!(&weave.process(bottom()).data[..], &[20, 20, 30, 40]);
+    }
+}
@@ -0,0 +1,341 @@
+// src/backend/detections.rs - Detection Overlays Pushed by External Analysis
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use super::resample::Zoom;
+use super::transform::{CropRect, Orientation};
+use super::BackendEvent;
+
+/// Frames whose detections are kept for late-displayed frames
+pub const DETECTION_HISTORY: usize = 256;
+
+/// Labeled point, normalized (0-1) to the full sensor frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keypoint {
+    #[serde(default)]
+    pub label: String,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Bounding box, normalized (0-1) to the full sensor frame
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// One detected object: a box, keypoints, or both
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Detection {
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub confidence: Option<f32>,
+    #[serde(default, rename = "box")]
+    pub bbox: Option<BoundingBox>,
+    #[serde(default)]
+    pub keypoints: Vec<Keypoint>,
+}
+
+impl Detection {
+    /// Check that all coordinates are finite numbers
+    pub fn is_valid(&self) -> bool {
+        let box_valid = self.bbox.map_or(true, |b| {
+            [b.x, b.y, b.width, b.height].iter().all(|v| v.is_finite()) && b.width >= 0.0 && b.height >= 0.0
+        });
+        box_valid && self.keypoints.iter().all(|k| k.x.is_finite() && k.y.is_finite())
+    }
+
+    /// Get the text drawn next to the box, with the confidence as a percentage
+    pub fn caption(&self) -> String {
+        match self.confidence {
+            Some(confidence) => format!("{} {:.0}%", self.label, confidence * 100.0).trim().to_string(),
+            None => self.label.clone(),
+        }
+    }
+}
+
+/// Detections for a single frame, matched to frames by `frame_id`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameDetections {
+    pub frame_id: u64,
+    #[serde(default)]
+    pub detections: Vec<Detection>,
+}
+
+impl FrameDetections {
+    /// Parse one line of the IPC protocol, dropping detections with invalid coordinates
+    pub fn parse(line: &str) -> Result<Self, serde_json::Error> {
+        let mut frame: FrameDetections = serde_json::from_str(line)?;
+        frame.detections.retain(Detection::is_valid);
+        Ok(frame)
+    }
+
+    /// Map the detections into view coordinates for a frame shown with `geometry` and `zoom`
+    pub fn overlays(&self, geometry: &DisplayGeometry, zoom: &Zoom) -> DetectionOverlays {
+        let view = zoom.view_rect();
+        let to_view = |(x, y): (f32, f32)| ((x - view.x) / view.width, (y - view.y) / view.height);
+
+        let mut overlays = DetectionOverlays::default();
+        for detection in &self.detections {
+            if let Some(bbox) = detection.bbox {
+                let rect = geometry.map_rect(&bbox);
+                overlays.boxes.push(BoxOverlay {
+                    rect: zoom.rect_in_view(&rect),
+                    caption: detection.caption(),
+                });
+            }
+            for keypoint in &detection.keypoints {
+                let (x, y) = to_view(geometry.map_point(keypoint.x, keypoint.y));
+                overlays.keypoints.push(KeypointOverlay { x, y, label: keypoint.label.clone() });
+            }
+        }
+        overlays
+    }
+}
+
+/// Box in view coordinates (x, y, width, height), with its caption
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxOverlay {
+    pub rect: (f32, f32, f32, f32),
+    pub caption: String,
+}
+
+/// Keypoint in view coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeypointOverlay {
+    pub x: f32,
+    pub y: f32,
+    pub label: String,
+}
+
+/// Everything drawn for the detections of the displayed frame
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DetectionOverlays {
+    pub boxes: Vec<BoxOverlay>,
+    pub keypoints: Vec<KeypointOverlay>,
+}
+
+impl DetectionOverlays {
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty() && self.keypoints.is_empty()
+    }
+}
+
+/// How sensor coordinates relate to a displayed frame before digital zoom
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DisplayGeometry {
+    /// Region of the sensor frame that was cut out
+    pub crop: CropRect,
+    /// Orientation applied after cropping (header flags and operator)
+    pub orientation: Orientation,
+}
+
+impl DisplayGeometry {
+    pub fn new(crop: CropRect, orientation: Orientation) -> Self {
+        Self { crop, orientation }
+    }
+
+    /// Map a normalized sensor point to the displayed frame; points outside the crop fall outside 0-1
+    pub fn map_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let x = (x - self.crop.x) / self.crop.width.max(f32::EPSILON);
+        let y = (y - self.crop.y) / self.crop.height.max(f32::EPSILON);
+        self.orientation.map_point(x, y)
+    }
+
+    /// Map a normalized sensor box to the displayed frame, without clamping it to the frame
+    pub fn map_rect(&self, bbox: &BoundingBox) -> CropRect {
+        let (x0, y0) = self.map_point(bbox.x, bbox.y);
+        let (x1, y1) = self.map_point(bbox.x + bbox.width, bbox.y + bbox.height);
+        CropRect { x: x0.min(x1), y: y0.min(y1), width: (x1 - x0).abs(), height: (y1 - y0).abs() }
+    }
+}
+
+/// Recent detections by frame id
+///
+/// Results usually arrive after their frame, so a bounded history is kept
+/// for the frame the display is about to show.
+#[derive(Debug)]
+pub struct DetectionStore {
+    frames: parking_lot::Mutex<VecDeque<FrameDetections>>,
+    capacity: usize,
+}
+
+impl DetectionStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: parking_lot::Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Store detections, replacing earlier ones for the same frame
+    pub fn push(&self, detections: FrameDetections) {
+        let mut frames = self.frames.lock();
+        frames.retain(|frame| frame.frame_id != detections.frame_id);
+        if frames.len() >= self.capacity {
+            frames.pop_front();
+        }
+        frames.push_back(detections);
+    }
+
+    /// Get the detections for a frame, if any were pushed
+    pub fn get(&self, frame_id: u64) -> Option<FrameDetections> {
+        self.frames.lock().iter().rev().find(|frame| frame.frame_id == frame_id).cloned()
+    }
+
+    pub fn clear(&self) {
+        self.frames.lock().clear();
+    }
+}
+
+impl Default for DetectionStore {
+    fn default() -> Self {
+        Self::new(DETECTION_HISTORY)
+    }
+}
+
+/// Store detections and tell the frontend they arrived
+pub(crate) fn publish(store: &DetectionStore, event_tx: &broadcast::Sender<BackendEvent>, detections: FrameDetections) {
+    store.push(detections.clone());
+    let _ = event_tx.send(BackendEvent::DetectionsReceived(detections));
+}
+
+/// TCP endpoint taking detections as newline-delimited JSON, one `FrameDetections` per line
+///
+/// Example line: `{"frame_id": 42, "detections": [{"label": "needle", "confidence": 0.9,
+/// "box": {"x": 0.4, "y": 0.3, "width": 0.1, "height": 0.2}}]}`
+pub struct DetectionServer {
+    address: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl DetectionServer {
+    /// Listen for analysis clients on `host:port`
+    pub async fn bind(
+        address: &str,
+        store: Arc<DetectionStore>,
+        event_tx: broadcast::Sender<BackendEvent>,
+    ) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+        let task = tokio::spawn(Self::accept_clients(listener, store, event_tx));
+
+        info!("🎯 Detection input listening on {}", address);
+        Ok(Self { address, task })
+    }
+
+    /// Address the endpoint listens on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    async fn accept_clients(listener: TcpListener, store: Arc<DetectionStore>, event_tx: broadcast::Sender<BackendEvent>) {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    info!("🎯 Detection client connected: {}", peer);
+                    let store = Arc::clone(&store);
+                    let event_tx = event_tx.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = Self::read_client(stream, &store, &event_tx).await {
+                            debug!("Detection client {} failed: {}", peer, e);
+                        }
+                        info!("🎯 Detection client disconnected: {}", peer);
+                    });
+                }
+                Err(e) => warn!("⚠️ Detection input accept failed: {}", e),
+            }
+        }
+    }
+
+    async fn read_client(
+        stream: TcpStream,
+        store: &DetectionStore,
+        event_tx: &broadcast::Sender<BackendEvent>,
+    ) -> std::io::Result<()> {
+        let mut lines = BufReader::new(stream).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match FrameDetections::parse(&line) {
+                Ok(detections) => publish(store, event_tx, detections),
+                Err(e) => warn!("⚠️ Ignoring malformed detections: {}", e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DetectionServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::transform::Rotation;
+
+    #[test]
+    fn test_parse_and_store() {
+        let line = r#"{"frame_id": 7, "detections": [
+            {"label": "needle", "confidence": 0.87, "box": {"x": 0.1, "y": 0.2, "width": 0.3, "height": 0.4}},
+            {"keypoints": [{"label": "tip", "x": 0.5, "y": 0.5}]},
+            {"box": {"x": 0.1, "y": 0.1, "width": -1.0, "height": 0.1}}
+        ]}"#;
+        let frame = FrameDetections::parse(&line.replace('\n', " ")).unwrap();
+        assert_eq!(frame.frame_id, 7);
+        assert_eq!(frame.detections.len(), 2);
+        assert_eq!(frame.detections[0].caption(), "needle 87%");
+
+        let store = DetectionStore::new(2);
+        store.push(frame.clone());
+        store.push(FrameDetections { frame_id: 8, detections: Vec::new() });
+        store.push(FrameDetections { frame_id: 9, detections: Vec::new() });
+        assert!(store.get(7).is_none());
+        assert!(store.get(9).is_some());
+    }
+
+    #[test]
+    fn test_geometry_mapping() {
+        // Crop to the right half, then rotate a quarter turn clockwise
+        let geometry = DisplayGeometry::new(
+            CropRect::new(0.5, 0.0, 0.5, 1.0),
+            Orientation::new(Rotation::from_degrees(90), false, false),
+        );
+        let (x, y) = geometry.map_point(0.5, 0.0);
+        assert!((x - 1.0).abs() < 1e-6 && y.abs() < 1e-6);
+
+        let rect = geometry.map_rect(&BoundingBox { x: 0.5, y: 0.0, width: 0.25, height: 0.5 });
+        assert!((rect.x - 0.5).abs() < 1e-6 && rect.y.abs() < 1e-6);
+        assert!((rect.width - 0.5).abs() < 1e-6 && (rect.height - 0.5).abs() < 1e-6);
+
+        // Identity geometry and zoom leave boxes where they are
+        let frame = FrameDetections {
+            frame_id: 1,
+            detections: vec![Detection {
+                label: "a".to_string(),
+                confidence: None,
+                bbox: Some(BoundingBox { x: 0.2, y: 0.2, width: 0.2, height: 0.2 }),
+                keypoints: Vec::new(),
+            }],
+        };
+        let overlays = frame.overlays(&DisplayGeometry::default(), &Zoom::default());
+        assert_eq!(overlays.boxes[0].rect, (0.2, 0.2, 0.2, 0.2));
+    }
+}
@@ -0,0 +1,122 @@
+// src/backend/fd_passing.rs - memfd Regions Shared over Unix Domain Sockets
+
+use std::ffi::CString;
+use std::fs::File;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::net::UnixStream;
+
+/// Prefix of `--shm-name` values that name a producer socket instead of a file
+pub const SOCKET_PREFIX: &str = "unix:";
+
+/// Socket path if the region is handed over a Unix socket (`unix:/run/mivi/frames.sock`)
+pub fn socket_path(shm_name: &str) -> Option<&str> {
+    shm_name.strip_prefix(SOCKET_PREFIX).filter(|path| !path.is_empty())
+}
+
+/// Create an anonymous memfd of `size` bytes whose size is sealed
+///
+/// Nothing appears in /dev/shm; the memory is released once every process
+/// holding the descriptor or a mapping of it has exited.
+pub fn create_memfd(name: &str, size: u64) -> io::Result<File> {
+    let name = CString::new(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let fd = unsafe { libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let file = unsafe { File::from_raw_fd(fd) };
+    file.set_len(size)?;
+
+    // Readers map the whole region, so it must never shrink under them
+    let seals = libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_SEAL;
+    if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_ADD_SEALS, seals) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(file)
+}
+
+/// Send a descriptor as SCM_RIGHTS ancillary data
+pub fn send_fd(stream: &UnixStream, fd: RawFd) -> io::Result<()> {
+    let mut byte = [0u8; 1];
+    let mut iov = libc::iovec { iov_base: byte.as_mut_ptr() as *mut libc::c_void, iov_len: byte.len() };
+    let mut control = [0u64; 8];
+    let space = unsafe { libc::CMSG_SPACE(std::mem::size_of::<RawFd>() as u32) } as usize;
+
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = space as _;
+
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(std::mem::size_of::<RawFd>() as u32) as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut RawFd, fd);
+    }
+
+    if unsafe { libc::sendmsg(stream.as_raw_fd(), &msg, libc::MSG_NOSIGNAL) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Receive a descriptor sent with `send_fd`
+pub fn recv_fd(stream: &UnixStream) -> io::Result<OwnedFd> {
+    let mut byte = [0u8; 1];
+    let mut iov = libc::iovec { iov_base: byte.as_mut_ptr() as *mut libc::c_void, iov_len: byte.len() };
+    let mut control = [0u64; 8];
+    let space = unsafe { libc::CMSG_SPACE(std::mem::size_of::<RawFd>() as u32) } as usize;
+
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = space as _;
+
+    let received = unsafe { libc::recvmsg(stream.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC) };
+    if received < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if received == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "producer closed the socket"));
+    }
+
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        if cmsg.is_null() || (*cmsg).cmsg_level != libc::SOL_SOCKET || (*cmsg).cmsg_type != libc::SCM_RIGHTS {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "producer did not send a descriptor"));
+        }
+        let fd = std::ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const RawFd);
+        Ok(OwnedFd::from_raw_fd(fd))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    #[test]
+    fn test_memfd_survives_fd_passing() {
+        assert_eq!(socket_path("unix:/run/mivi.sock"), Some("/run/mivi.sock"));
+        assert_eq!(socket_path("ultrasound_frames"), None);
+
+        let mut memfd = create_memfd("mivi_test", 16).unwrap();
+        memfd.write_all(b"frame").unwrap();
+        assert!(memfd.set_len(8).is_err(), "size is sealed");
+
+        let (producer, consumer) = UnixStream::pair().unwrap();
+        send_fd(&producer, memfd.as_raw_fd()).unwrap();
+        let mut received = File::from(recv_fd(&consumer).unwrap());
+
+        let mut contents = [0u8; 5];
+        received.seek(SeekFrom::Start(0)).unwrap();
+        received.read_exact(&mut contents).unwrap();
+        assert_eq!(&contents, b"frame");
+        assert_eq!(received.metadata().unwrap().len(), 16);
+    }
+}
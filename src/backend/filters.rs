@@ -0,0 +1,600 @@
+// src/backend/filters.rs - Image Enhancement Filters for Medical Imaging
+
+use serde::{Deserialize, Serialize};
+
+/// Contrast-limited adaptive histogram equalization parameters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClaheParams {
+    /// Number of tiles horizontally
+    pub tiles_x: usize,
+    /// Number of tiles vertically
+    pub tiles_y: usize,
+    /// Histogram clip limit as a multiple of the mean bin count (1.0 = no enhancement)
+    pub clip_limit: f32,
+}
+
+impl ClaheParams {
+    /// Create parameters with the default 8x8 tile grid
+    pub fn new(clip_limit: f32) -> Self {
+        Self {
+            clip_limit: clip_limit.clamp(1.0, 16.0),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ClaheParams {
+    fn default() -> Self {
+        Self {
+            tiles_x: 8,
+            tiles_y: 8,
+            clip_limit: 2.5,
+        }
+    }
+}
+
+/// Rec. 601 luma of an RGB pixel
+#[inline]
+pub fn luma(r: u8, g: u8, b: u8) -> u8 {
+    ((77 * r as u32 + 150 * g as u32 + 29 * b as u32 + 128) >> 8) as u8
+}
+
+/// Apply CLAHE in place to RGBA data, equalizing luma and shifting all color channels
+pub fn clahe_rgba(rgba_data: &mut [u8], width: usize, height: usize, params: &ClaheParams) {
+    if width == 0 || height == 0 || rgba_data.len() < width * height * 4 {
+        return;
+    }
+
+    let luma_plane: Vec<u8> = rgba_data
+        .chunks_exact(4)
+        .take(width * height)
+        .map(|p| luma(p[0], p[1], p[2]))
+        .collect();
+
+    let equalized = clahe_plane(&luma_plane, width, height, params);
+
+    for ((pixel, &old), &new) in rgba_data.chunks_exact_mut(4).zip(&luma_plane).zip(&equalized) {
+        if old == new {
+            continue;
+        }
+        let delta = new as i16 - old as i16;
+        for channel in &mut pixel[..3] {
+            *channel = (*channel as i16 + delta).clamp(0, 255) as u8;
+        }
+    }
+}
+
+/// Apply CLAHE to a single 8-bit plane
+pub fn clahe_plane(plane: &[u8], width: usize, height: usize, params: &ClaheParams) -> Vec<u8> {
+    let tiles_x = params.tiles_x.clamp(1, width.max(1));
+    let tiles_y = params.tiles_y.clamp(1, height.max(1));
+    let tile_w = width.div_ceil(tiles_x);
+    let tile_h = height.div_ceil(tiles_y);
+
+    // Per-tile mapping tables
+    let mut maps = vec![[0u8; 256]; tiles_x * tiles_y];
+    for ty in 0..tiles_y {
+        for tx in 0..tiles_x {
+            let x0 = tx * tile_w;
+            let y0 = ty * tile_h;
+            let x1 = (x0 + tile_w).min(width);
+            let y1 = (y0 + tile_h).min(height);
+
+            let mut histogram = [0u32; 256];
+            for y in y0..y1 {
+                for &value in &plane[y * width + x0..y * width + x1] {
+                    histogram[value as usize] += 1;
+                }
+            }
+
+            let pixel_count = ((x1.saturating_sub(x0)) * (y1.saturating_sub(y0))) as u32;
+            maps[ty * tiles_x + tx] = tile_mapping(&mut histogram, pixel_count, params.clip_limit);
+        }
+    }
+
+    // Bilinear interpolation between the four nearest tile centers
+    let mut output = vec![0u8; width * height];
+    for y in 0..height {
+        let fy = (y as f32 + 0.5) / tile_h as f32 - 0.5;
+        let ty0 = (fy.floor().max(0.0) as usize).min(tiles_y - 1);
+        let ty1 = (ty0 + 1).min(tiles_y - 1);
+        let wy = (fy - ty0 as f32).clamp(0.0, 1.0);
+
+        for x in 0..width {
+            let fx = (x as f32 + 0.5) / tile_w as f32 - 0.5;
+            let tx0 = (fx.floor().max(0.0) as usize).min(tiles_x - 1);
+            let tx1 = (tx0 + 1).min(tiles_x - 1);
+            let wx = (fx - tx0 as f32).clamp(0.0, 1.0);
+
+            let value = plane[y * width + x] as usize;
+            let top = maps[ty0 * tiles_x + tx0][value] as f32 * (1.0 - wx)
+                + maps[ty0 * tiles_x + tx1][value] as f32 * wx;
+            let bottom = maps[ty1 * tiles_x + tx0][value] as f32 * (1.0 - wx)
+                + maps[ty1 * tiles_x + tx1][value] as f32 * wx;
+
+            output[y * width + x] = (top * (1.0 - wy) + bottom * wy).round() as u8;
+        }
+    }
+
+    output
+}
+
+/// Clip a tile histogram, redistribute the excess and build the equalization mapping
+fn tile_mapping(histogram: &mut [u32; 256], pixel_count: u32, clip_limit: f32) -> [u8; 256] {
+    let mut mapping = [0u8; 256];
+    if pixel_count == 0 {
+        for (i, entry) in mapping.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+        return mapping;
+    }
+
+    let limit = ((clip_limit * pixel_count as f32 / 256.0).ceil() as u32).max(1);
+    let mut excess = 0u32;
+    for bin in histogram.iter_mut() {
+        if *bin > limit {
+            excess += *bin - limit;
+            *bin = limit;
+        }
+    }
+
+    let increment = excess / 256;
+    let remainder = (excess % 256) as usize;
+    for (i, bin) in histogram.iter_mut().enumerate() {
+        *bin += increment + u32::from(i < remainder);
+    }
+
+    let scale = 255.0 / pixel_count as f32;
+    let mut cumulative = 0u32;
+    for (entry, &bin) in mapping.iter_mut().zip(histogram.iter()) {
+        cumulative += bin;
+        *entry = (cumulative as f32 * scale).round().min(255.0) as u8;
+    }
+
+    mapping
+}
+
+/// Smooth RGBA data in place with a 3x3 Gaussian, blended by `strength` (0.0-1.0)
+pub fn denoise_rgba(rgba_data: &mut [u8], width: usize, height: usize, strength: f32) {
+    let strength = strength.clamp(0.0, 1.0);
+    if strength <= 0.0 || rgba_data.len() < width * height * 4 {
+        return;
+    }
+
+    let blurred = gaussian3_rgba(rgba_data, width, height);
+    for (pixel, smooth) in rgba_data.chunks_exact_mut(4).zip(blurred.chunks_exact(4)) {
+        for (channel, &smooth) in pixel[..3].iter_mut().zip(&smooth[..3]) {
+            let value = *channel as f32 + (smooth as f32 - *channel as f32) * strength;
+            *channel = value.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+/// Sharpen RGBA data in place with an unsharp mask of the given amount (0.0-1.0)
+pub fn sharpen_rgba(rgba_data: &mut [u8], width: usize, height: usize, amount: f32) {
+    let amount = amount.clamp(0.0, 1.0);
+    if amount <= 0.0 || rgba_data.len() < width * height * 4 {
+        return;
+    }
+
+    let blurred = gaussian3_rgba(rgba_data, width, height);
+    for (pixel, smooth) in rgba_data.chunks_exact_mut(4).zip(blurred.chunks_exact(4)) {
+        for (channel, &smooth) in pixel[..3].iter_mut().zip(&smooth[..3]) {
+            let value = *channel as f32 + (*channel as f32 - smooth as f32) * amount;
+            *channel = value.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+/// Reduce speckle noise in place with a 3x3 median, blended by `strength` (0.0-1.0)
+///
+/// Single-channel frames only filter the red channel and replicate it, color
+/// frames filter each color channel independently. Alpha is preserved.
+pub fn speckle_rgba(rgba_data: &mut [u8], width: usize, height: usize, strength: f32, single_channel: bool) {
+    let strength = strength.clamp(0.0, 1.0);
+    if strength <= 0.0 || width == 0 || height == 0 || rgba_data.len() < width * height * 4 {
+        return;
+    }
+
+    let channels = if single_channel { 1 } else { 3 };
+    for channel in 0..channels {
+        let plane: Vec<u8> = rgba_data.chunks_exact(4).take(width * height).map(|p| p[channel]).collect();
+        let filtered = median3_plane(&plane, width, height);
+
+        for ((pixel, &old), &median) in rgba_data.chunks_exact_mut(4).zip(&plane).zip(&filtered) {
+            let value = if strength >= 1.0 {
+                median
+            } else {
+                (old as f32 + (median as f32 - old as f32) * strength).round() as u8
+            };
+
+            if single_channel {
+                pixel[..3].fill(value);
+            } else {
+                pixel[channel] = value;
+            }
+        }
+    }
+}
+
+/// 3x3 median of an 8-bit plane (edges clamped), vectorized 16 pixels at a time where available
+pub fn median3_plane(plane: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let mut output = vec![0u8; width * height];
+    if width == 0 || height == 0 {
+        return output;
+    }
+
+    for y in 0..height {
+        let up = &plane[y.saturating_sub(1) * width..][..width];
+        let row = &plane[y * width..][..width];
+        let down = &plane[(y + 1).min(height - 1) * width..][..width];
+        let out = &mut output[y * width..][..width];
+
+        // SSE2 is part of the x86_64 baseline, no runtime detection needed
+        #[cfg(target_arch = "x86_64")]
+        let mut x = {
+            // Left edge uses clamped neighbors
+            out[0] = median3_at(up, row, down, 0);
+
+            let mut x = 1;
+            while x + 16 < width {
+                // SAFETY: x >= 1 and x + 17 <= width, so all loads and stores are in bounds
+                unsafe { simd::median3_row16(up, row, down, x, out) };
+                x += 16;
+            }
+            x
+        };
+        #[cfg(not(target_arch = "x86_64"))]
+        let mut x = 0;
+
+        while x < width {
+            out[x] = median3_at(up, row, down, x);
+            x += 1;
+        }
+    }
+
+    output
+}
+
+/// Scalar 3x3 median at one column with clamped neighbors
+fn median3_at(up: &[u8], row: &[u8], down: &[u8], x: usize) -> u8 {
+    let left = x.saturating_sub(1);
+    let right = (x + 1).min(row.len() - 1);
+    median9([
+        up[left], up[x], up[right],
+        row[left], row[x], row[right],
+        down[left], down[x], down[right],
+    ])
+}
+
+/// Lane type usable by the median sorting network
+trait MinMax: Copy {
+    fn min_lane(self, other: Self) -> Self;
+    fn max_lane(self, other: Self) -> Self;
+}
+
+impl MinMax for u8 {
+    #[inline(always)]
+    fn min_lane(self, other: Self) -> Self {
+        self.min(other)
+    }
+
+    #[inline(always)]
+    fn max_lane(self, other: Self) -> Self {
+        self.max(other)
+    }
+}
+
+/// Median of 9 values with a 19 compare-exchange sorting network
+#[inline(always)]
+fn median9<T: MinMax>(mut p: [T; 9]) -> T {
+    #[inline(always)]
+    fn sort<T: MinMax>(p: &mut [T; 9], a: usize, b: usize) {
+        let (lo, hi) = (p[a].min_lane(p[b]), p[a].max_lane(p[b]));
+        p[a] = lo;
+        p[b] = hi;
+    }
+
+    const NETWORK: [(usize, usize); 19] = [
+        (1, 2), (4, 5), (7, 8), (0, 1), (3, 4), (6, 7), (1, 2), (4, 5), (7, 8), (0, 3),
+        (5, 8), (4, 7), (3, 6), (1, 4), (2, 5), (4, 7), (4, 2), (6, 4), (4, 2),
+    ];
+    for (a, b) in NETWORK {
+        sort(&mut p, a, b);
+    }
+    p[4]
+}
+
+#[cfg(target_arch = "x86_64")]
+mod simd {
+    use super::{median9, MinMax};
+    use std::arch::x86_64::*;
+
+    #[derive(Clone, Copy)]
+    struct U8x16(__m128i);
+
+    impl MinMax for U8x16 {
+        #[inline(always)]
+        fn min_lane(self, other: Self) -> Self {
+            // SAFETY: SSE2 is always available on x86_64
+            unsafe { U8x16(_mm_min_epu8(self.0, other.0)) }
+        }
+
+        #[inline(always)]
+        fn max_lane(self, other: Self) -> Self {
+            // SAFETY: SSE2 is always available on x86_64
+            unsafe { U8x16(_mm_max_epu8(self.0, other.0)) }
+        }
+    }
+
+    /// Median of 16 consecutive pixels starting at column `x`
+    ///
+    /// # Safety
+    /// Requires `x >= 1` and `x + 17 <= width` for all rows.
+    #[inline]
+    pub(super) unsafe fn median3_row16(up: &[u8], row: &[u8], down: &[u8], x: usize, out: &mut [u8]) {
+        let load = |line: &[u8], offset: usize| U8x16(_mm_loadu_si128(line.as_ptr().add(offset) as *const __m128i));
+
+        let median = median9([
+            load(up, x - 1), load(up, x), load(up, x + 1),
+            load(row, x - 1), load(row, x), load(row, x + 1),
+            load(down, x - 1), load(down, x), load(down, x + 1),
+        ]);
+        _mm_storeu_si128(out.as_mut_ptr().add(x) as *mut __m128i, median.0);
+    }
+}
+
+/// Boost edges in place by adding the Sobel gradient magnitude of luma, scaled by `amount` (0.0-1.0)
+///
+/// Thin high-contrast structures such as needles gain a bright outline while
+/// flat regions are left untouched.
+pub fn edge_enhance_rgba(rgba_data: &mut [u8], width: usize, height: usize, amount: f32) {
+    let amount = amount.clamp(0.0, 1.0);
+    if amount <= 0.0 || width == 0 || height == 0 || rgba_data.len() < width * height * 4 {
+        return;
+    }
+
+    let luma_plane: Vec<u8> = rgba_data
+        .chunks_exact(4)
+        .take(width * height)
+        .map(|p| luma(p[0], p[1], p[2]))
+        .collect();
+    let magnitude = sobel_plane(&luma_plane, width, height);
+
+    for (pixel, &edge) in rgba_data.chunks_exact_mut(4).zip(&magnitude) {
+        if edge == 0 {
+            continue;
+        }
+        let boost = (edge as f32 * amount).round() as u16;
+        for channel in &mut pixel[..3] {
+            *channel = (*channel as u16 + boost).min(255) as u8;
+        }
+    }
+}
+
+/// Sobel gradient magnitude (|gx| + |gy|, scaled to 8 bits) of a plane, edges clamped
+pub fn sobel_plane(plane: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let mut output = vec![0u8; width * height];
+    if width == 0 || height == 0 {
+        return output;
+    }
+
+    for y in 0..height {
+        let up = &plane[y.saturating_sub(1) * width..][..width];
+        let row = &plane[y * width..][..width];
+        let down = &plane[(y + 1).min(height - 1) * width..][..width];
+
+        for x in 0..width {
+            let left = x.saturating_sub(1);
+            let right = (x + 1).min(width - 1);
+            let at = |line: &[u8], i: usize| line[i] as i32;
+
+            let gx = (at(up, right) + 2 * at(row, right) + at(down, right))
+                - (at(up, left) + 2 * at(row, left) + at(down, left));
+            let gy = (at(down, left) + 2 * at(down, x) + at(down, right))
+                - (at(up, left) + 2 * at(up, x) + at(up, right));
+
+            output[y * width + x] = ((gx.abs() + gy.abs()) / 4).min(255) as u8;
+        }
+    }
+
+    output
+}
+
+/// Motion-adaptive exponential moving average across frames (persistence)
+///
+/// Keeps a running average of the color channels in 8.8 fixed point. Pixels
+/// that differ from the average by more than the motion threshold follow the
+/// new frame so moving structures are not smeared.
+#[derive(Debug, Default)]
+pub struct TemporalFilter {
+    accumulator: Vec<u16>,
+    width: usize,
+    height: usize,
+}
+
+impl TemporalFilter {
+    /// Create an empty filter (the first frame seeds the average)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop the running average
+    pub fn reset(&mut self) {
+        self.accumulator.clear();
+        self.width = 0;
+        self.height = 0;
+    }
+
+    /// Blend a frame in place with the running average of roughly the last `frames` frames
+    pub fn apply_rgba(&mut self, rgba_data: &mut [u8], width: usize, height: usize, frames: u32, motion_threshold: u8) {
+        let pixel_count = width * height;
+        if frames <= 1 || pixel_count == 0 || rgba_data.len() < pixel_count * 4 {
+            return;
+        }
+
+        // Seed on the first frame or after a resolution change
+        if self.width != width || self.height != height || self.accumulator.len() != pixel_count * 3 {
+            self.accumulator = rgba_data
+                .chunks_exact(4)
+                .take(pixel_count)
+                .flat_map(|p| [(p[0] as u16) << 8, (p[1] as u16) << 8, (p[2] as u16) << 8])
+                .collect();
+            self.width = width;
+            self.height = height;
+            return;
+        }
+
+        // EMA weight of the new frame for an N-frame window
+        let alpha = 2.0 / (frames as f32 + 1.0);
+        let threshold = motion_threshold.max(1) as f32;
+
+        for (pixel, average) in rgba_data.chunks_exact_mut(4).zip(self.accumulator.chunks_exact_mut(3)) {
+            let difference = pixel[..3]
+                .iter()
+                .zip(average.iter())
+                .map(|(&value, &avg)| (value as i32 - (avg >> 8) as i32).abs())
+                .max()
+                .unwrap_or(0);
+            let motion = (difference as f32 / threshold).min(1.0);
+            let weight = alpha + (1.0 - alpha) * motion;
+
+            for (channel, avg) in pixel[..3].iter_mut().zip(average.iter_mut()) {
+                let previous = *avg as f32;
+                let current = ((*channel as u16) << 8) as f32;
+                *avg = (previous + (current - previous) * weight).round() as u16;
+                *channel = ((*avg as u32 + 128) >> 8).min(255) as u8;
+            }
+        }
+    }
+}
+
+/// Separable 3x3 Gaussian blur (1-2-1 kernel) of the color channels, edges clamped
+fn gaussian3_rgba(rgba_data: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let mut horizontal = vec![0u16; width * height * 4];
+    for y in 0..height {
+        let row = y * width;
+        for x in 0..width {
+            let left = (row + x.saturating_sub(1)) * 4;
+            let center = (row + x) * 4;
+            let right = (row + (x + 1).min(width - 1)) * 4;
+            for c in 0..3 {
+                horizontal[center + c] = rgba_data[left + c] as u16
+                    + 2 * rgba_data[center + c] as u16
+                    + rgba_data[right + c] as u16;
+            }
+        }
+    }
+
+    let mut output = rgba_data[..width * height * 4].to_vec();
+    for y in 0..height {
+        let up = y.saturating_sub(1) * width;
+        let row = y * width;
+        let down = (y + 1).min(height - 1) * width;
+        for x in 0..width {
+            for c in 0..3 {
+                let sum = horizontal[(up + x) * 4 + c]
+                    + 2 * horizontal[(row + x) * 4 + c]
+                    + horizontal[(down + x) * 4 + c];
+                output[(row + x) * 4 + c] = ((sum + 8) / 16) as u8;
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clahe_stretches_low_contrast() {
+        let (width, height) = (64, 64);
+        let plane: Vec<u8> = (0..width * height).map(|i| 100 + ((i % width) / 8) as u8).collect();
+
+        let output = clahe_plane(&plane, width, height, &ClaheParams::new(4.0));
+        let input_range = plane.iter().max().unwrap() - plane.iter().min().unwrap();
+        let output_range = output.iter().max().unwrap() - output.iter().min().unwrap();
+
+        assert_eq!(output.len(), plane.len());
+        assert!(output_range > input_range);
+    }
+
+    #[test]
+    fn test_clahe_rgba_keeps_alpha() {
+        let mut rgba = vec![120u8; 16 * 16 * 4];
+        clahe_rgba(&mut rgba, 16, 16, &ClaheParams::default());
+        assert!(rgba.chunks_exact(4).all(|p| p[3] == 120 && p[0] == p[1] && p[1] == p[2]));
+    }
+
+    #[test]
+    fn test_denoise_and_sharpen() {
+        // Flat images are unchanged
+        let mut flat = vec![90u8; 8 * 8 * 4];
+        denoise_rgba(&mut flat, 8, 8, 1.0);
+        sharpen_rgba(&mut flat, 8, 8, 1.0);
+        assert!(flat.iter().all(|&v| v == 90));
+
+        // A single bright speck is smoothed out
+        let mut speck = vec![0u8; 8 * 8 * 4];
+        speck[(4 * 8 + 4) * 4] = 255;
+        denoise_rgba(&mut speck, 8, 8, 1.0);
+        assert!(speck[(4 * 8 + 4) * 4] < 255);
+        assert!(speck[(4 * 8 + 5) * 4] > 0);
+    }
+
+    #[test]
+    fn test_speckle_median() {
+        // Isolated specks vanish, edges survive; width covers both SIMD and scalar paths
+        let (width, height) = (40, 6);
+        let mut plane: Vec<u8> = (0..width * height).map(|i| if i % width < 20 { 10 } else { 200 }).collect();
+        plane[2 * width + 7] = 255;
+        plane[3 * width + 30] = 0;
+
+        let filtered = median3_plane(&plane, width, height);
+        assert_eq!(filtered[2 * width + 7], 10);
+        assert_eq!(filtered[3 * width + 30], 200);
+        assert_eq!(filtered[2 * width + 19], 10);
+        assert_eq!(filtered[2 * width + 20], 200);
+
+        let mut rgba: Vec<u8> = plane.iter().flat_map(|&v| [v, v, v, 255]).collect();
+        speckle_rgba(&mut rgba, width, height, 1.0, true);
+        assert_eq!(&rgba[(2 * width + 7) * 4..][..4], &[10, 10, 10, 255]);
+    }
+
+    #[test]
+    fn test_edge_enhance() {
+        // A vertical step edge lights up, flat areas stay put
+        let (width, height) = (8, 4);
+        let mut rgba: Vec<u8> = (0..width * height)
+            .flat_map(|i| if i % width < 4 { [50, 50, 50, 255] } else { [100, 100, 100, 255] })
+            .collect();
+        edge_enhance_rgba(&mut rgba, width, height, 1.0);
+
+        assert_eq!(rgba[(width + 1) * 4], 50);
+        assert!(rgba[(width + 4) * 4] > 100);
+        assert_eq!(rgba[(width + 4) * 4 + 3], 255);
+    }
+
+    #[test]
+    fn test_temporal_persistence() {
+        let mut filter = TemporalFilter::new();
+        let frame = |v: u8| vec![v, v, v, 255];
+
+        // First frame seeds, small flicker is averaged out
+        let mut rgba = frame(100);
+        filter.apply_rgba(&mut rgba, 1, 1, 4, 32);
+        assert_eq!(rgba[0], 100);
+
+        let mut rgba = frame(110);
+        filter.apply_rgba(&mut rgba, 1, 1, 4, 32);
+        assert!(rgba[0] > 100 && rgba[0] < 110);
+
+        // Large changes are treated as motion and followed immediately
+        let mut rgba = frame(250);
+        filter.apply_rgba(&mut rgba, 1, 1, 4, 32);
+        assert_eq!(rgba[0], 250);
+        assert_eq!(rgba[3], 255);
+    }
+}
@@ -0,0 +1,186 @@
+// src/backend/frame_delivery.rs - Latest-Frame Hand-off to the Frontend
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{watch, Notify};
+
+use crate::backend::types::ProcessedFrame;
+
+/// What to do with a processed frame while the frontend has not taken the previous one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameDropPolicy {
+    /// Replace the waiting frame, so the display always shows the newest
+    #[default]
+    DropOldest,
+    /// Keep the waiting frame and discard the new one
+    DropNewest,
+    /// Hold the frame loop until the frontend takes the waiting frame, then replace it after the timeout
+    Block(Duration),
+}
+
+impl FrameDropPolicy {
+    /// Get display name
+    pub fn name(&self) -> String {
+        match self {
+            FrameDropPolicy::DropOldest => "drop oldest".to_string(),
+            FrameDropPolicy::DropNewest => "drop newest".to_string(),
+            FrameDropPolicy::Block(timeout) => format!("block up to {}ms", timeout.as_millis()),
+        }
+    }
+}
+
+/// Outcome of handing a frame to the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// The slot was free
+    Delivered,
+    /// The frame replaced one the frontend never took
+    ReplacedPending,
+    /// The frame was discarded to keep the waiting one
+    Discarded,
+}
+
+impl Delivery {
+    /// Check if a frame was lost because the frontend fell behind
+    pub fn dropped(self) -> bool {
+        !matches!(self, Delivery::Delivered)
+    }
+}
+
+/// Backend side of the one-frame slot between the frame loop and the frontend
+///
+/// The slot counts as pending from a delivery until any receiver takes the frame.
+pub struct FrameSender {
+    tx: watch::Sender<Option<ProcessedFrame>>,
+    pending: Arc<parking_lot::Mutex<bool>>,
+    taken: Arc<Notify>,
+}
+
+impl FrameSender {
+    /// Create an empty slot
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(None);
+        Self { tx, pending: Arc::new(parking_lot::Mutex::new(false)), taken: Arc::new(Notify::new()) }
+    }
+
+    /// Get a receiver; it only sees frames delivered from now on
+    pub fn subscribe(&self) -> FrameReceiver {
+        FrameReceiver { rx: self.tx.subscribe(), pending: Arc::clone(&self.pending), taken: Arc::clone(&self.taken) }
+    }
+
+    /// Hand a frame to the frontend according to `policy`
+    ///
+    /// Without receivers nothing can fall behind, so the frame is always delivered.
+    pub async fn deliver(&self, frame: ProcessedFrame, policy: FrameDropPolicy) -> Delivery {
+        if self.tx.receiver_count() == 0 {
+            self.tx.send_replace(Some(frame));
+            return Delivery::Delivered;
+        }
+
+        match policy {
+            FrameDropPolicy::DropOldest => {}
+            FrameDropPolicy::DropNewest => {
+                if *self.pending.lock() {
+                    return Delivery::Discarded;
+                }
+            }
+            FrameDropPolicy::Block(timeout) => {
+                let deadline = tokio::time::Instant::now() + timeout;
+                while *self.pending.lock() {
+                    if tokio::time::timeout_at(deadline, self.taken.notified()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Replace and flag under one lock so a receiver never clears the flag of a frame it did not see
+        let mut pending = self.pending.lock();
+        let replaced = std::mem::replace(&mut *pending, true);
+        self.tx.send_replace(Some(frame));
+        if replaced { Delivery::ReplacedPending } else { Delivery::Delivered }
+    }
+
+    /// Empty the slot after a disconnect
+    pub fn clear(&self) {
+        let mut pending = self.pending.lock();
+        *pending = false;
+        self.tx.send_replace(None);
+        self.taken.notify_one();
+    }
+}
+
+impl Default for FrameSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Frontend side of the frame slot
+#[derive(Clone)]
+pub struct FrameReceiver {
+    rx: watch::Receiver<Option<ProcessedFrame>>,
+    pending: Arc<parking_lot::Mutex<bool>>,
+    taken: Arc<Notify>,
+}
+
+impl FrameReceiver {
+    /// Wait until a frame is delivered or the slot is cleared
+    pub async fn changed(&mut self) -> Result<(), watch::error::RecvError> {
+        self.rx.changed().await
+    }
+
+    /// Take the latest frame, freeing the slot for the next one
+    ///
+    /// None after a disconnect.
+    pub fn take_latest(&mut self) -> Option<ProcessedFrame> {
+        let frame = {
+            let mut pending = self.pending.lock();
+            *pending = false;
+            self.rx.borrow_and_update().clone()
+        };
+        self.taken.notify_one();
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::pixel_pool::RgbaBuffer;
+    use crate::backend::types::{FrameFormat, FrameHeader};
+
+    fn frame(frame_id: u64) -> ProcessedFrame {
+        let header = FrameHeader { frame_id, sequence_number: frame_id, ..FrameHeader::for_test(1, 1, 4, 0) };
+        ProcessedFrame::new(header, RgbaBuffer::new(1, 1), None, std::time::Instant::now(), FrameFormat::RGBA)
+    }
+
+    #[test]
+    fn test_drop_policies() {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let sender = FrameSender::new();
+            let mut receiver = sender.subscribe();
+
+            assert_eq!(sender.deliver(frame(1), FrameDropPolicy::DropOldest).await, Delivery::Delivered);
+            assert_eq!(sender.deliver(frame(2), FrameDropPolicy::DropOldest).await, Delivery::ReplacedPending);
+            assert_eq!(receiver.take_latest().map(|f| f.header.frame_id), Some(2));
+
+            // The waiting frame survives under drop-newest
+            assert_eq!(sender.deliver(frame(3), FrameDropPolicy::DropNewest).await, Delivery::Delivered);
+            assert_eq!(sender.deliver(frame(4), FrameDropPolicy::DropNewest).await, Delivery::Discarded);
+            assert_eq!(receiver.take_latest().map(|f| f.header.frame_id), Some(3));
+
+            // Blocking gives up after the timeout and replaces the waiting frame
+            let block = FrameDropPolicy::Block(Duration::from_millis(20));
+            assert_eq!(sender.deliver(frame(5), block).await, Delivery::Delivered);
+            assert_eq!(sender.deliver(frame(6), block).await, Delivery::ReplacedPending);
+            assert!(!Delivery::Delivered.dropped());
+
+            sender.clear();
+            assert!(receiver.take_latest().is_none());
+            assert_eq!(sender.deliver(frame(7), block).await, Delivery::Delivered);
+        });
+    }
+}
@@ -0,0 +1,179 @@
+// src/backend/frame_dumper.rs - Raw Frame Dumping for Debugging Producer Integrations
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::backend::types::{format_code_to_string, ProcessedFrame, RawFrame};
+use crate::encryption::{self, EncryptionError, RecordingCipher};
+use crate::redaction::{self, AnonymizationProfile};
+
+/// Where and how many frames to dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpConfig {
+    pub dir: PathBuf,
+    pub max_frames: u32,
+    /// Also write the converted display image as PNG
+    pub png: bool,
+    /// Applied to producer metadata in the JSON sidecar
+    pub anonymization: AnonymizationProfile,
+    /// Seal every written file with the recording key
+    pub encryption: Option<RecordingCipher>,
+}
+
+/// JSON sidecar describing a dumped raw frame
+#[derive(Debug, Clone, Serialize)]
+pub struct DumpHeader {
+    pub index: u32,
+    pub frame_id: u64,
+    pub sequence_number: u64,
+    pub timestamp_ns: u64,
+    pub width: u32,
+    pub height: u32,
+    pub bytes_per_pixel: u32,
+    pub data_size: u32,
+    pub format_code: u32,
+    pub format: String,
+    pub flags: u32,
+    /// Producer metadata, parsed as JSON when possible and anonymized
+    pub metadata: Option<serde_json::Value>,
+    pub raw_file: String,
+    pub png_file: Option<String>,
+}
+
+/// Writes the first N raw frames of a stream with a JSON header sidecar
+#[derive(Debug)]
+pub struct FrameDumper {
+    config: DumpConfig,
+    claimed: AtomicU32,
+}
+
+impl FrameDumper {
+    /// Create a dumper for the given configuration
+    pub fn new(config: DumpConfig) -> Self {
+        Self { config, claimed: AtomicU32::new(0) }
+    }
+
+    /// Get the dump configuration
+    pub fn config(&self) -> &DumpConfig {
+        &self.config
+    }
+
+    /// Check if all requested frames have been claimed
+    pub fn is_done(&self) -> bool {
+        self.claimed.load(Ordering::Relaxed) >= self.config.max_frames
+    }
+
+    /// Reserve the next dump slot, or None once the limit is reached
+    pub fn claim(&self) -> Option<u32> {
+        self.claimed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| (n < self.config.max_frames).then_some(n + 1))
+            .ok()
+    }
+
+    /// Write a claimed frame: raw bytes, JSON header and optionally the converted PNG
+    pub fn dump(&self, index: u32, raw: &RawFrame, processed: Option<&ProcessedFrame>) -> Result<DumpHeader, DumpError> {
+        std::fs::create_dir_all(&self.config.dir)?;
+
+        let stem = format!("frame_{:04}_{}", index, raw.header.frame_id);
+        let raw_file = self.write_file(&format!("{}.raw", stem), &raw.data[..])?;
+
+        let png_file = match processed {
+            Some(frame) if self.config.png => Some(self.write_file(&format!("{}.png", stem), &Self::encode_png(frame)?)?),
+            _ => None,
+        };
+
+        let header = DumpHeader {
+            index,
+            frame_id: raw.header.frame_id,
+            sequence_number: raw.header.sequence_number,
+            timestamp_ns: raw.header.timestamp,
+            width: raw.header.width,
+            height: raw.header.height,
+            bytes_per_pixel: raw.header.bytes_per_pixel,
+            data_size: raw.header.data_size,
+            format_code: raw.header.format_code,
+            format: format_code_to_string(raw.header.format_code).to_string(),
+            flags: raw.header.flags,
+            metadata: raw.metadata.as_deref().map(|text| {
+                let metadata = serde_json::from_str(text).unwrap_or_else(|_| serde_json::Value::String(text.to_string()));
+                redaction::register_metadata(&metadata);
+                self.config.anonymization.metadata(metadata)
+            }),
+            raw_file,
+            png_file,
+        };
+        self.write_file(&format!("{}.json", stem), serde_json::to_string_pretty(&header)?.as_bytes())?;
+
+        Ok(header)
+    }
+
+    /// Write one file of a dump, encrypted if configured, returning the file name written
+    fn write_file(&self, name: &str, data: &[u8]) -> Result<String, DumpError> {
+        let path = encryption::write_recording(&self.config.dir.join(name), data, self.config.encryption.as_ref())?;
+        Ok(path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default())
+    }
+
+    /// Encode the RGBA display image of a processed frame as PNG
+    pub(crate) fn encode_png(frame: &ProcessedFrame) -> Result<Vec<u8>, DumpError> {
+        use image::ImageEncoder;
+
+        let (width, height) = frame.dimensions();
+        let rgba = frame.rgba();
+        if rgba.as_bytes().len() != width as usize * height as usize * 4 {
+            return Err(DumpError::Image(format!("{} bytes do not match a {}x{} RGBA image", rgba.as_bytes().len(), width, height)));
+        }
+        let mut png = Vec::new();
+        image::codecs::png::PngEncoder::new(&mut png)
+            .write_image(rgba.as_bytes(), width, height, image::ColorType::Rgba8)
+            .map_err(|e| DumpError::Image(e.to_string()))?;
+        Ok(png)
+    }
+}
+
+/// Frame dump errors
+#[derive(Debug, Error)]
+pub enum DumpError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Header serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("PNG encoding error: {0}")]
+    Image(String),
+
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] EncryptionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::types::FrameHeader;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_dump_first_frames() {
+        let dir = std::env::temp_dir().join(format!("mivi_dump_test_{}", std::process::id()));
+        let dumper = FrameDumper::new(DumpConfig { dir: dir.clone(), max_frames: 2, png: false, anonymization: AnonymizationProfile::Basic, encryption: None });
+
+        let header = FrameHeader { frame_id: 42, sequence_number: 7, ..FrameHeader::for_test(2, 2, 1, 0x10) };
+        let frame = RawFrame::new(header, Arc::from(vec![1u8, 2, 3, 4]), Some("{\"probe\":\"L12\"}".to_string()));
+
+        let index = dumper.claim().unwrap();
+        let written = dumper.dump(index, &frame, None).unwrap();
+        assert_eq!(written.raw_file, "frame_0000_42.raw");
+        assert_eq!(written.metadata, Some(serde_json::json!({ "probe": "L12" })));
+        assert_eq!(std::fs::read(dir.join("frame_0000_42.raw")).unwrap(), [1, 2, 3, 4]);
+        assert!(dir.join("frame_0000_42.json").exists());
+
+        assert_eq!(dumper.claim(), Some(1));
+        assert_eq!(dumper.claim(), None);
+        assert!(dumper.is_done());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,11 +1,31 @@
 // src/backend/frame_processor.rs - Zero-Copy Frame Processing for Medical Imaging
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
-use tracing::{debug, warn, error};
-
+use tracing::{debug, info, warn, error};
+
+use crate::backend::adjustments::{Colormap, Dithering, DisplayLut, Gamma, WindowLevel};
+use crate::backend::filters::ClaheParams;
+use crate::backend::transform::{CropRect, Orientation};
+use crate::backend::resample::{self, ResampleQuality, Zoom};
+use crate::backend::roi::{Roi, RoiStatistics};
+use crate::backend::calibration::PixelSpacing;
+use crate::backend::numa::{self, NumaStatistics};
+use crate::backend::pixel_pool::{PixelBufferPool, RgbaBuffer};
+use crate::backend::packed12;
+use crate::backend::palette::{Palette, PaletteError};
+use crate::backend::color_space::{ColorManagement, ColorSpace, DisplayCalibration};
+use crate::backend::yuv::{YuvConverter, YuvEncoding};
+use crate::backend::deinterlace::{Deinterlace, Deinterlacer};
+use crate::backend::gpu_frame::{GpuFrame, GpuToneMapping};
+use crate::backend::detections::DisplayGeometry;
+use crate::backend::plugins::{FrameFilter, PluginStage};
+use crate::backend::inference::{InferenceStatistics, SegmentationStage};
+use crate::backend::pipeline::{self, Pipeline, PipelineState, StageKind, StageStatistics, StageTiming};
+use crate::backend::scheduling::ThreadScheduling;
 use crate::backend::types::{
-    RawFrame, ProcessedFrame, FrameFormat
+    RawFrame, ProcessedFrame, FrameFormat, StreamGeometry, read_sample16
 };
 
 /// Frame processor for converting raw medical imaging data to display format
@@ -17,58 +37,551 @@ pub struct FrameProcessor {
     // Performance optimization flags
     use_simd: bool,
     parallel_processing: bool,
+
+    // Filter pipeline (copied on change, shared with in-flight frames)
+    pipeline: parking_lot::RwLock<Arc<Pipeline>>,
+    stage_timings: parking_lot::Mutex<HashMap<StageKind, StageTiming>>,
+    pipeline_state: parking_lot::Mutex<PipelineState>,
+
+    // Display orientation chosen by the operator (combined with header flags)
+    orientation: parking_lot::RwLock<Orientation>,
+    last_header_flags: std::sync::atomic::AtomicU32,
+
+    // Region of interest in sensor coordinates, applied before conversion
+    crop: parking_lot::RwLock<Option<CropRect>>,
+
+    // Digital zoom on the displayed frame and its interpolation kernel
+    zoom: parking_lot::RwLock<Zoom>,
+    resample_quality: parking_lot::RwLock<ResampleQuality>,
+
+    // Region of interest for raw-value statistics, in full-sensor coordinates
+    roi: parking_lot::RwLock<Option<Roi>>,
+
+    // Conversion of declared color spaces to the display, optionally GSDF-calibrated
+    color_management: parking_lot::RwLock<Arc<ColorManagement>>,
+
+    // Placement of frame buffers relative to the converting thread (multi-node machines only)
+    numa_stats: Option<parking_lot::Mutex<NumaStatistics>>,
+
+    // Display buffers handed to the UI and recycled once it has moved on
+    pixel_pool: Arc<PixelBufferPool>,
+
+    // Leave unfiltered 8-bit frames for the GPU renderer to convert
+    gpu_upload: std::sync::atomic::AtomicBool,
+
+    // Keep 10-bit precision: shader window/level on the GPU path, dithering on the CPU
+    deep_color: std::sync::atomic::AtomicBool,
+
+    // Dithering when 10/12-bit sources are reduced to 8 bits
+    dithering: parking_lot::RwLock<Dithering>,
+
+    // Deinterlacing of frames flagged as interlaced, with the field held for weaving
+    deinterlacer: parking_lot::Mutex<Deinterlacer>,
+
+    // Optional AI segmentation, run on filtered frames before orientation and zoom
+    segmentation: Option<SegmentationStage>,
+
+    // Third-party filter plugins, run in order after the built-in stages
+    plugins: Vec<PluginStage>,
+
+    // Conversion threads, kept off the async runtime (None = convert on the caller)
+    workers: Option<rayon::ThreadPool>,
+}
+
+/// Conversion threads when none are configured: 75% of the cores, between 1 and 8
+pub fn default_worker_count() -> usize {
+    (num_cpus::get() * 3 / 4).clamp(1, 8)
 }
 
 impl FrameProcessor {
-    /// Create a new frame processor
+    /// Create a new frame processor with the default number of conversion threads
     pub fn new() -> Self {
+        Self::with_workers(default_worker_count(), &ThreadScheduling::default())
+    }
+
+    /// Create a frame processor converting on `threads` dedicated threads
+    ///
+    /// The threads get the same CPU affinity and priority as the frame loop.
+    pub fn with_workers(threads: usize, scheduling: &ThreadScheduling) -> Self {
+        let threads = threads.max(1);
+        let thread_scheduling = scheduling.clone();
+        let workers = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("mivi-convert-{}", index))
+            .start_handler(move |_| {
+                for result in thread_scheduling.apply_to_current_thread() {
+                    if let Err(e) = result {
+                        warn!("⚠️ Conversion thread scheduling: {}", e);
+                    }
+                }
+            })
+            .build();
+        let workers = match workers {
+            Ok(workers) => {
+                debug!("🧵 {} conversion thread(s)", threads);
+                Some(workers)
+            }
+            Err(e) => {
+                warn!("⚠️ Failed to start conversion threads, converting on the frame loop: {}", e);
+                None
+            }
+        };
+
         Self {
             conversion_stats: parking_lot::RwLock::new(ConversionStats::default()),
             use_simd: is_simd_available(),
-            parallel_processing: num_cpus::get() > 2,
+            parallel_processing: threads > 1,
+            pipeline: parking_lot::RwLock::new(Arc::new(Pipeline::default())),
+            stage_timings: parking_lot::Mutex::new(HashMap::new()),
+            pipeline_state: parking_lot::Mutex::new(PipelineState::default()),
+            orientation: parking_lot::RwLock::new(Orientation::default()),
+            last_header_flags: std::sync::atomic::AtomicU32::new(0),
+            crop: parking_lot::RwLock::new(None),
+            zoom: parking_lot::RwLock::new(Zoom::default()),
+            resample_quality: parking_lot::RwLock::new(ResampleQuality::default()),
+            roi: parking_lot::RwLock::new(None),
+            color_management: parking_lot::RwLock::new(Arc::new(ColorManagement::default())),
+            numa_stats: {
+                let topology = numa::NumaTopology::detect();
+                topology.is_numa().then(|| parking_lot::Mutex::new(NumaStatistics { nodes: topology.nodes.len(), ..Default::default() }))
+            },
+            pixel_pool: Arc::new(PixelBufferPool::default()),
+            gpu_upload: std::sync::atomic::AtomicBool::new(false),
+            deep_color: std::sync::atomic::AtomicBool::new(false),
+            dithering: parking_lot::RwLock::new(Dithering::default()),
+            deinterlacer: parking_lot::Mutex::new(Deinterlacer::default()),
+            segmentation: None,
+            plugins: Vec::new(),
+            workers,
         }
     }
 
+    /// Modify the pipeline without disturbing frames currently being processed
+    fn update_pipeline<R>(&self, update: impl FnOnce(&mut Pipeline) -> R) -> R {
+        let mut pipeline = self.pipeline.write();
+        update(Arc::make_mut(&mut *pipeline))
+    }
+
+    /// Get a snapshot of the current pipeline
+    pub fn pipeline(&self) -> Arc<Pipeline> {
+        self.pipeline.read().clone()
+    }
+
+    /// Enable or disable a pipeline stage
+    pub fn set_stage_enabled(&self, kind: StageKind, enabled: bool) {
+        debug!("🧩 Stage {}: {}", kind.name(), if enabled { "enabled" } else { "disabled" });
+        self.update_pipeline(|p| p.set_enabled(kind, enabled));
+    }
+
+    /// Reorder the pipeline stages
+    pub fn set_stage_order(&self, order: &[StageKind]) {
+        debug!("🧩 Stage order: {:?}", order);
+        self.update_pipeline(|p| p.set_order(order));
+    }
+
+    /// Set the strength of a pipeline stage, returning false if the stage has none
+    pub fn set_stage_strength(&self, kind: StageKind, value: f32) -> bool {
+        debug!("🧩 Stage {} strength: {:.2}", kind.name(), value);
+        self.update_pipeline(|p| p.set_strength(kind, value))
+    }
+
+    /// Forget frame history of stateful stages (e.g. persistence) when the stream changes
+    pub fn reset_stream_state(&self) {
+        self.pipeline_state.lock().reset();
+        self.deinterlacer.lock().reset();
+        if let Some(segmentation) = &self.segmentation {
+            segmentation.clear_mask();
+        }
+        for plugin in &self.plugins {
+            plugin.reset();
+        }
+    }
+
+    /// Prepare for frames of a new size or format: drop idle buffers of the old size and frame history
+    pub fn renegotiate(&self, geometry: &StreamGeometry) {
+        info!("📐 Re-negotiating conversion for {}", geometry);
+        self.pixel_pool.clear();
+        self.reset_stream_state();
+    }
+
+    /// Run third-party filters on the frames, in order (set before the processor is shared)
+    pub fn with_plugins(mut self, filters: Vec<Box<dyn FrameFilter>>) -> Self {
+        self.plugins = filters.into_iter().map(PluginStage::new).collect();
+        self
+    }
+
+    /// Run a segmentation model on the frames (set before the processor is shared)
+    pub fn with_segmentation(mut self, segmentation: SegmentationStage) -> Self {
+        self.segmentation = Some(segmentation);
+        self
+    }
+
+    /// Set the segmentation overlay opacity, returning false without a model
+    pub fn set_overlay_opacity(&self, opacity: f32) -> bool {
+        match &self.segmentation {
+            Some(segmentation) => {
+                debug!("🧠 Overlay opacity: {:.2}", opacity);
+                segmentation.set_opacity(opacity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get segmentation timing, or None without a model
+    pub fn inference_statistics(&self) -> Option<InferenceStatistics> {
+        self.segmentation.as_ref().map(SegmentationStage::statistics)
+    }
+
+    /// Get NUMA placement statistics, or None on single-node machines
+    pub fn numa_statistics(&self) -> Option<NumaStatistics> {
+        self.numa_stats.as_ref().map(|stats| *stats.lock())
+    }
+
+    /// Get per-stage timing statistics in pipeline order
+    pub fn stage_statistics(&self) -> Vec<StageStatistics> {
+        let snapshot = self.pipeline();
+        pipeline::stage_statistics(&snapshot, &self.stage_timings.lock())
+    }
+
+    /// Set the operator orientation applied after the probe orientation from the header
+    pub fn set_orientation(&self, orientation: Orientation) {
+        debug!("🔄 Orientation: {}°, flip H {}, flip V {}",
+               orientation.rotation.degrees(), orientation.flip_horizontal, orientation.flip_vertical);
+        *self.orientation.write() = orientation;
+        // The ROI was drawn on the previous display geometry
+        *self.roi.write() = None;
+    }
+
+    /// Get the operator orientation
+    pub fn orientation(&self) -> Orientation {
+        *self.orientation.read()
+    }
+
+    /// Get the orientation currently applied to displayed frames (header + operator)
+    pub fn display_orientation(&self) -> Orientation {
+        let flags = self.last_header_flags.load(std::sync::atomic::Ordering::Relaxed);
+        Orientation::from_header_flags(flags).then(&self.orientation.read())
+    }
+
+    /// Narrow the region of interest to a rectangle selected on the displayed frame
+    ///
+    /// The selection is relative to what is currently shown (already cropped,
+    /// rotated, flipped and zoomed) and is mapped back to sensor coordinates.
+    /// `None` restores the full frame. Any digital zoom is reset because the
+    /// view it referred to no longer exists. Returns the resulting sensor-space crop.
+    pub fn select_crop(&self, selection: Option<CropRect>) -> Option<CropRect> {
+        let view = std::mem::take(&mut *self.zoom.write()).view_rect();
+        *self.roi.write() = None;
+        let mut crop = self.crop.write();
+        let current = crop.unwrap_or_default();
+        *crop = selection
+            .map(|display_rect| {
+                let display_rect = view.sub_rect(&display_rect);
+                let sensor_rect = self.display_orientation().inverse().map_rect(&display_rect);
+                current.sub_rect(&sensor_rect)
+            })
+            .filter(|rect| !rect.is_full());
+        if let Some(segmentation) = &self.segmentation {
+            segmentation.clear_mask();
+        }
+        for plugin in &self.plugins {
+            plugin.reset();
+        }
+
+        debug!("✂️ Crop region: {:?}", *crop);
+        *crop
+    }
+
+    /// Get the current region of interest in sensor coordinates
+    pub fn crop(&self) -> Option<CropRect> {
+        *self.crop.read()
+    }
+
+    /// Set the ROI used for raw-value statistics from a region on the displayed frame
+    ///
+    /// The region is relative to the displayed frame before digital zoom (already
+    /// cropped, rotated and flipped) and is mapped back to full-sensor coordinates.
+    /// Returns the resulting sensor-space ROI.
+    pub fn select_roi(&self, selection: Option<Roi>) -> Option<Roi> {
+        let crop = self.crop.read().unwrap_or_default();
+        let roi = selection.map(|roi| {
+            let sensor_rect = self.display_orientation().inverse().map_rect(&roi.bounds);
+            Roi::new(roi.shape, crop.sub_rect(&sensor_rect))
+        });
+
+        debug!("🔲 ROI: {:?}", roi);
+        *self.roi.write() = roi;
+        roi
+    }
+
+    /// Get the current ROI in full-sensor coordinates
+    pub fn roi(&self) -> Option<Roi> {
+        *self.roi.read()
+    }
+
+    /// Set the digital zoom applied to the displayed (oriented) frame
+    pub fn set_zoom(&self, zoom: Zoom) {
+        debug!("🔍 Zoom: {:.2}x at ({:.3}, {:.3})", zoom.factor, zoom.center_x, zoom.center_y);
+        *self.zoom.write() = zoom;
+    }
+
+    /// Get the current digital zoom
+    pub fn zoom(&self) -> Zoom {
+        *self.zoom.read()
+    }
+
+    /// Set the interpolation kernel used for digital zoom
+    pub fn set_resample_quality(&self, quality: ResampleQuality) {
+        debug!("🔍 Zoom quality: {}", quality.name());
+        *self.resample_quality.write() = quality;
+    }
+
+    /// Get the interpolation kernel used for digital zoom
+    pub fn resample_quality(&self) -> ResampleQuality {
+        *self.resample_quality.read()
+    }
+
+    /// Calibrate the display to the DICOM GSDF, or treat it as sRGB with None
+    pub fn set_display_calibration(&self, calibration: Option<DisplayCalibration>) {
+        match calibration {
+            Some(range) => debug!("🩻 GSDF calibration: {:.2} to {:.0} cd/m²", range.min_luminance, range.max_luminance),
+            None => debug!("🩻 GSDF calibration off"),
+        }
+        *self.color_management.write() = Arc::new(ColorManagement::new(calibration));
+    }
+
+    /// Get the GSDF display calibration, if any
+    pub fn display_calibration(&self) -> Option<DisplayCalibration> {
+        self.color_management.read().calibration()
+    }
+
+    /// Set the window/level applied to converted frames
+    pub fn set_window_level(&self, window_level: WindowLevel) {
+        debug!("🎚️ Window/level: center {:.3}, width {:.3}", window_level.center, window_level.width);
+        self.update_pipeline(|p| p.set_window_level(window_level));
+    }
+
+    /// Set the display gamma applied to converted frames
+    pub fn set_gamma(&self, gamma: Gamma) {
+        debug!("🌗 Gamma: {:.2}", gamma.0);
+        self.update_pipeline(|p| p.set_gamma(gamma));
+    }
+
+    /// Set the pseudo-color map applied to single-channel frames
+    pub fn set_colormap(&self, colormap: Colormap) {
+        debug!("🌈 Colormap: {}", colormap.name());
+        self.update_pipeline(|p| p.set_colormap(colormap));
+    }
+
+    /// Get the current pseudo-color map
+    pub fn colormap(&self) -> Colormap {
+        self.pipeline.read().colormap()
+    }
+
+    /// Enable (Some) or disable (None) the CLAHE contrast enhancement stage
+    pub fn set_clahe(&self, params: Option<ClaheParams>) {
+        debug!("📈 CLAHE: {:?}", params);
+        self.update_pipeline(|p| p.set_clahe(params));
+    }
+
+    /// Get the current CLAHE parameters
+    pub fn clahe(&self) -> Option<ClaheParams> {
+        self.pipeline.read().clahe()
+    }
+
+    /// Get the current display gamma
+    pub fn gamma(&self) -> Gamma {
+        self.pipeline.read().gamma()
+    }
+
+    /// Get the current window/level
+    pub fn window_level(&self) -> WindowLevel {
+        self.pipeline.read().window_level()
+    }
+
     /// Process a raw frame into display-ready format (optimized for zero-copy)
-    pub async fn process_frame(&self, raw_frame: RawFrame) -> Result<ProcessedFrame, ProcessingError> {
+    ///
+    /// Conversion runs on the worker threads, so the frame loop keeps handling
+    /// commands and timers meanwhile; row-parallel conversion stays on those threads too.
+    pub async fn process_frame(self: &Arc<Self>, raw_frame: RawFrame) -> Result<ProcessedFrame, ProcessingError> {
+        let Some(workers) = self.workers.as_ref() else {
+            return self.convert_frame(raw_frame);
+        };
+
+        let (result_tx, result_rx) = tokio::sync::oneshot::channel();
+        let processor = Arc::clone(self);
+        // Keep the caller's span so logs and timings from the worker are attributed to this frame
+        let span = tracing::Span::current();
+        workers.spawn(move || {
+            let _ = result_tx.send(span.in_scope(|| processor.convert_frame(raw_frame)));
+        });
+        result_rx.await
+            .map_err(|_| ProcessingError::ParallelProcessing("conversion thread stopped".to_string()))?
+    }
+
+    /// Convert a raw frame on the calling thread
+    fn convert_frame(&self, raw_frame: RawFrame) -> Result<ProcessedFrame, ProcessingError> {
         let start_time = Instant::now();
 
+        // ROI statistics use the untouched sensor values
+        let roi = *self.roi.read();
+        let roi_statistics = roi.and_then(|roi| {
+            roi.statistics(
+                &raw_frame.data,
+                raw_frame.header.width as usize,
+                raw_frame.header.height as usize,
+                FrameFormat::from_code(raw_frame.header.format_code),
+                raw_frame.header.is_big_endian(),
+            )
+        });
+
+        // Cut the region of interest out before converting so only it is processed
+        let crop = *self.crop.read();
+        let raw_frame = match crop {
+            Some(rect) => Self::crop_raw_frame(raw_frame, &rect),
+            None => raw_frame,
+        };
+
         // Determine the frame format
         let format = FrameFormat::from_code(raw_frame.header.format_code);
-
-        // Convert to RGB format for display
-        let rgb_data = match format {
-            FrameFormat::RGB => {
-                // Already RGB - can use zero-copy if the data is properly aligned
-                if raw_frame.header.bytes_per_pixel == 3 {
-                    self.convert_rgb_to_rgba_zero_copy(&raw_frame)?
-                } else {
-                    raw_frame.data.clone() // Direct zero-copy for RGBA
+        let pipeline = self.pipeline();
+        let display_lut = pipeline.conversion_lut();
+        let color_management = self.color_management.read().clone();
+        let color_transform = color_management.transform(ColorSpace::from_metadata(raw_frame.metadata.as_deref()));
+
+        // Unfiltered 8-bit frames are left for the GPU to convert while uploading, and with
+        // deep color 10-bit frames too, window/level applied in the shader at full precision
+        let deep_color = self.deep_color.load(std::sync::atomic::Ordering::Relaxed);
+        let dithering = match *self.dithering.read() {
+            // Deep color never truncates; dither unless a matrix was chosen
+            Dithering::Off if deep_color => Dithering::Bayer4,
+            dithering => dithering,
+        };
+        if self.gpu_upload.load(std::sync::atomic::Ordering::Relaxed) && self.segmentation.is_none() && self.plugins.is_empty() {
+            let orientation = Orientation::from_header_flags(raw_frame.header.flags).then(&self.orientation.read());
+            if pipeline.active_stages(format).is_empty() && orientation.is_identity() && self.zoom.read().is_identity()
+                && color_transform.is_identity() {
+                let gpu_frame = GpuFrame::new(raw_frame.clone(), format).or_else(|| {
+                    let tone = GpuToneMapping { window_level: display_lut.window_level(), gamma: display_lut.gamma(), dithering };
+                    deep_color.then(|| GpuFrame::deep(raw_frame.clone(), format, tone)).flatten()
+                });
+                if let Some(gpu_frame) = gpu_frame {
+                    let mut processed_frame = self.gpu_frame(raw_frame, gpu_frame, format, roi_statistics, start_time);
+                    processed_frame.geometry = DisplayGeometry::new(crop.unwrap_or_default(), orientation);
+                    return Ok(processed_frame);
                 }
             }
-            FrameFormat::BGR => {
-                self.convert_bgr_to_rgba(&raw_frame).await?
-            }
-            FrameFormat::BGRA => {
-                self.convert_bgra_to_rgba(&raw_frame).await?
-            }
-            FrameFormat::YUV => {
-                self.convert_yuv_to_rgba(&raw_frame).await?
-            }
-            FrameFormat::Grayscale => {
-                self.convert_grayscale_to_rgba(&raw_frame).await?
+        }
+
+        // Convert straight into a pooled display buffer the UI can show as-is
+        let mut rgb_data = self.pixel_pool.acquire(raw_frame.header.width, raw_frame.header.height);
+        {
+            let dst = rgb_data.make_mut_bytes();
+            match format {
+                FrameFormat::RGB => {
+                    if raw_frame.header.bytes_per_pixel == 3 {
+                        self.convert_rgb_to_rgba(&raw_frame, dst)?
+                    } else {
+                        Self::copy_rgba(&raw_frame, dst)?
+                    }
+                }
+                FrameFormat::BGR => self.convert_bgr_to_rgba(&raw_frame, dst)?,
+                FrameFormat::BGRA => self.convert_bgra_to_rgba(&raw_frame, dst)?,
+                FrameFormat::YUV => Self::convert_yuv_to_rgba(&raw_frame, dst)?,
+                FrameFormat::Grayscale => Self::convert_grayscale_to_rgba(&raw_frame, dst)?,
+                FrameFormat::YUV10 => Self::convert_yuv10_to_rgba(&raw_frame, dst, display_lut, dithering)?,
+                FrameFormat::RGB10 => Self::convert_rgb10_to_rgba(&raw_frame, dst, display_lut, dithering)?,
+                FrameFormat::RGB565 => Self::convert_rgb565_to_rgba(&raw_frame, dst)?,
+                FrameFormat::Indexed => Self::convert_indexed_to_rgba(&raw_frame, dst)?,
+                FrameFormat::Gray12Packed => Self::convert_gray12_packed_to_rgba(&raw_frame, dst, display_lut, dithering)?,
+                _ => {
+                    warn!("⚠️ Unknown format code: {}, treating as grayscale", raw_frame.header.format_code);
+                    Self::convert_grayscale_to_rgba(&raw_frame, dst)?
+                }
             }
-            FrameFormat::YUV10 => {
-                self.convert_yuv10_to_rgba(&raw_frame).await?
+        }
+
+        // Account for source or output memory living on another node than this thread
+        if let (Some(numa_stats), Some(node)) = (&self.numa_stats, numa::current_node()) {
+            numa_stats.lock().record(node, &[
+                (numa::page_node(&raw_frame.data), raw_frame.data.len()),
+                (numa::page_node(rgb_data.as_bytes()), rgb_data.as_bytes().len()),
+            ]);
+        }
+
+        // Filter pipeline stages, timed individually
+        let stages = pipeline.active_stages(format);
+        if !stages.contains(&StageKind::Temporal) {
+            self.pipeline_state.lock().temporal.reset();
+        }
+        if !stages.is_empty() {
+            let width = raw_frame.header.width as usize;
+            let height = raw_frame.header.height as usize;
+            let data = rgb_data.make_mut_bytes();
+
+            let mut timings = Vec::with_capacity(stages.len());
+            {
+                let mut state = self.pipeline_state.lock();
+                for kind in stages {
+                    let stage_start = Instant::now();
+                    pipeline.apply_stage(kind, data, width, height, format, &mut state);
+                    timings.push((kind, stage_start.elapsed()));
+                }
             }
-            FrameFormat::RGB10 => {
-                self.convert_rgb10_to_rgba(&raw_frame).await?
+
+            let mut stage_timings = self.stage_timings.lock();
+            for (kind, elapsed) in timings {
+                stage_timings.entry(kind).or_default().record(elapsed);
             }
-            _ => {
-                warn!("⚠️ Unknown format code: {}, treating as grayscale", raw_frame.header.format_code);
-                self.convert_grayscale_to_rgba(&raw_frame).await?
+        }
+
+        // Plugins see the same sensor geometry as the built-in stages
+        if !self.plugins.is_empty() {
+            let (width, height) = (raw_frame.header.width as usize, raw_frame.header.height as usize);
+            let data = rgb_data.make_mut_bytes();
+            for plugin in &self.plugins {
+                plugin.process_rgba(data, width, height);
             }
-        };
+        }
+
+        // Bring the declared color space to the display, before overlays are drawn
+        if !color_transform.is_identity() {
+            color_transform.apply_rgba(rgb_data.make_mut_bytes());
+        }
+
+        // Segmentation sees the filtered sensor geometry; its overlay is then oriented and zoomed with the frame
+        if let Some(segmentation) = &self.segmentation {
+            let (width, height) = (raw_frame.header.width as usize, raw_frame.header.height as usize);
+            segmentation.process_rgba(rgb_data.make_mut_bytes(), width, height);
+        }
+
+        // Rotate/flip last so stateful stages always see the sensor geometry
+        let mut header = raw_frame.header;
+        self.last_header_flags.store(header.flags, std::sync::atomic::Ordering::Relaxed);
+        let orientation = Orientation::from_header_flags(header.flags).then(&self.orientation.read());
+        let (width, height) = (header.width as usize, header.height as usize);
+        if !orientation.is_identity() {
+            let (transformed, out_width, out_height) = orientation.apply_rgba(rgb_data.as_bytes(), width, height);
+            rgb_data = self.replace_buffer(rgb_data, &transformed, out_width as u32, out_height as u32);
+            header.width = out_width as u32;
+            header.height = out_height as u32;
+        }
+
+        // Physical pixel size follows the displayed axes
+        let pixel_spacing = raw_frame.metadata.as_deref()
+            .and_then(PixelSpacing::from_metadata)
+            .map(|spacing| spacing.oriented(&orientation));
+
+        // Digital zoom resamples the visible view back to the full frame size
+        let zoom = *self.zoom.read();
+        let (width, height) = (header.width as usize, header.height as usize);
+        if !zoom.is_identity() {
+            let zoomed = resample::resample_rgba(
+                rgb_data.as_bytes(), width, height, &zoom.view_rect(), width, height, *self.resample_quality.read(),
+            );
+            rgb_data = self.replace_buffer(rgb_data, &zoomed, width as u32, height as u32);
+        }
 
         // Update conversion statistics
         {
@@ -79,13 +592,16 @@ impl FrameProcessor {
         }
 
         // Create processed frame
-        let processed_frame = ProcessedFrame::new(
-            raw_frame.header,
+        let mut processed_frame = ProcessedFrame::new(
+            header,
             rgb_data,
             raw_frame.metadata,
             raw_frame.received_at,
             format,
         );
+        processed_frame.roi_statistics = roi_statistics;
+        processed_frame.pixel_spacing = pixel_spacing;
+        processed_frame.geometry = DisplayGeometry::new(crop.unwrap_or_default(), orientation);
 
         debug!("📸 Processed frame {}: {}x{} {} -> RGBA in {:?}", 
                raw_frame.header.frame_id,
@@ -97,267 +613,302 @@ impl FrameProcessor {
         Ok(processed_frame)
     }
 
-    /// Convert RGB to RGBA with zero-copy optimization for aligned data
-    fn convert_rgb_to_rgba_zero_copy(&self, raw_frame: &RawFrame) -> Result<Arc<[u8]>, ProcessingError> {
+    /// Build a processed frame whose conversion is left to the GPU
+    fn gpu_frame(
+        &self,
+        raw_frame: RawFrame,
+        gpu_frame: GpuFrame,
+        format: FrameFormat,
+        roi_statistics: Option<RoiStatistics>,
+        start_time: Instant,
+    ) -> ProcessedFrame {
+        self.pipeline_state.lock().temporal.reset();
+        self.last_header_flags.store(raw_frame.header.flags, std::sync::atomic::Ordering::Relaxed);
+
+        {
+            let mut stats = self.conversion_stats.write();
+            stats.frames_processed += 1;
+            stats.total_processing_time += start_time.elapsed();
+            stats.last_conversion_time = start_time.elapsed();
+        }
+
+        let pixel_spacing = raw_frame.metadata.as_deref().and_then(PixelSpacing::from_metadata);
+        let mut processed_frame = ProcessedFrame::new(
+            raw_frame.header,
+            RgbaBuffer::new(0, 0),
+            raw_frame.metadata,
+            raw_frame.received_at,
+            format,
+        );
+        processed_frame.roi_statistics = roi_statistics;
+        processed_frame.pixel_spacing = pixel_spacing;
+        processed_frame.gpu_source = Some(gpu_frame);
+
+        debug!("📸 Frame {} left for GPU conversion: {}x{} {}",
+               raw_frame.header.frame_id, raw_frame.header.width, raw_frame.header.height, format.to_string());
+        processed_frame
+    }
+
+    /// Copy the region of interest out of a raw frame, leaving frames with unexpected sizes untouched
+    fn crop_raw_frame(raw_frame: RawFrame, rect: &CropRect) -> RawFrame {
         let width = raw_frame.header.width as usize;
         let height = raw_frame.header.height as usize;
-        let expected_size = width * height * 3;
+        let pixel_count = width * height;
+
+        // Packed samples straddle byte boundaries, so crop them unpacked
+        if FrameFormat::from_code(raw_frame.header.format_code) == FrameFormat::Gray12Packed {
+            let Some(samples) = packed12::unpack(&raw_frame.data, pixel_count) else {
+                warn!("⚠️ Cannot crop frame {}: {} bytes for {}x{} packed 12-bit",
+                      raw_frame.header.frame_id, raw_frame.data.len(), width, height);
+                return raw_frame;
+            };
+            let bytes: Vec<u8> = samples.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+            let (cropped, crop_width, crop_height) = rect.crop_packed(&bytes, width, height, 2);
+            let samples: Vec<u16> = cropped.chunks_exact(2).map(|sample| u16::from_le_bytes([sample[0], sample[1]])).collect();
+            let data = packed12::pack(&samples);
+
+            let mut header = raw_frame.header;
+            header.width = crop_width as u32;
+            header.height = crop_height as u32;
+            header.data_size = data.len() as u32;
+            return RawFrame {
+                header,
+                data: Arc::from(data.into_boxed_slice()),
+                metadata: raw_frame.metadata,
+                received_at: raw_frame.received_at,
+            };
+        }
+        if pixel_count == 0 || raw_frame.data.len() % pixel_count != 0 {
+            warn!("⚠️ Cannot crop frame {}: {} bytes for {}x{}",
+                  raw_frame.header.frame_id, raw_frame.data.len(), width, height);
+            return raw_frame;
+        }
+
+        let bytes_per_pixel = raw_frame.data.len() / pixel_count;
+        let (data, crop_width, crop_height) = rect.crop_packed(&raw_frame.data, width, height, bytes_per_pixel);
+
+        let mut header = raw_frame.header;
+        header.width = crop_width as u32;
+        header.height = crop_height as u32;
+        header.data_size = data.len() as u32;
+
+        RawFrame {
+            header,
+            data: Arc::from(data.into_boxed_slice()),
+            metadata: raw_frame.metadata,
+            received_at: raw_frame.received_at,
+        }
+    }
 
+    /// Move a transformed frame into a pooled buffer, returning the previous one to the pool
+    fn replace_buffer(&self, previous: RgbaBuffer, rgba: &[u8], width: u32, height: u32) -> RgbaBuffer {
+        let mut buffer = self.pixel_pool.acquire(width, height);
+        buffer.make_mut_bytes().copy_from_slice(rgba);
+        self.pixel_pool.recycle(previous);
+        buffer
+    }
+
+    /// Check that a raw frame holds exactly `bytes_per_pixel` bytes for every pixel
+    fn check_size(raw_frame: &RawFrame, bytes_per_pixel: usize) -> Result<(), ProcessingError> {
+        let expected_size = raw_frame.header.width as usize * raw_frame.header.height as usize * bytes_per_pixel;
         if raw_frame.data.len() != expected_size {
             return Err(ProcessingError::InvalidDataSize {
                 expected: expected_size,
                 actual: raw_frame.data.len(),
             });
         }
+        Ok(())
+    }
+
+    /// Copy frames that are already RGBA
+    fn copy_rgba(raw_frame: &RawFrame, dst: &mut [u8]) -> Result<(), ProcessingError> {
+        Self::check_size(raw_frame, 4)?;
+        dst.copy_from_slice(&raw_frame.data);
+        Ok(())
+    }
 
-        // Convert RGB to RGBA by adding alpha channel
-        let mut rgba_data = Vec::with_capacity(width * height * 4);
+    /// Convert RGB to RGBA
+    fn convert_rgb_to_rgba(&self, raw_frame: &RawFrame, dst: &mut [u8]) -> Result<(), ProcessingError> {
+        Self::check_size(raw_frame, 3)?;
 
-        if self.use_simd && width % 16 == 0 {
+        if self.use_simd && raw_frame.header.width % 16 == 0 {
             // SIMD-optimized conversion for aligned data
-            self.convert_rgb_to_rgba_simd(&raw_frame.data, &mut rgba_data, width, height)?;
+            self.convert_rgb_to_rgba_simd(&raw_frame.data, dst)
         } else {
-            // Standard conversion
-            for chunk in raw_frame.data.chunks_exact(3) {
-                rgba_data.extend_from_slice(&[chunk[0], chunk[1], chunk[2], 255]);
+            for (pixel, out) in raw_frame.data.chunks_exact(3).zip(dst.chunks_exact_mut(4)) {
+                out.copy_from_slice(&[pixel[0], pixel[1], pixel[2], 255]);
             }
+            Ok(())
         }
-
-        Ok(Arc::from(rgba_data.into_boxed_slice()))
     }
 
     /// SIMD-optimized RGB to RGBA conversion (when available)
-    fn convert_rgb_to_rgba_simd(
-        &self,
-        rgb_data: &[u8],
-        rgba_data: &mut Vec<u8>,
-        _width: usize,
-        _height: usize,
-    ) -> Result<(), ProcessingError> {
+    fn convert_rgb_to_rgba_simd(&self, rgb_data: &[u8], dst: &mut [u8]) -> Result<(), ProcessingError> {
         // This is a placeholder for SIMD optimization
         // In a real implementation, you would use SIMD intrinsics
         // For now, fall back to standard conversion
-        for chunk in rgb_data.chunks_exact(3) {
-            rgba_data.extend_from_slice(&[chunk[0], chunk[1], chunk[2], 255]);
+        for (pixel, out) in rgb_data.chunks_exact(3).zip(dst.chunks_exact_mut(4)) {
+            out.copy_from_slice(&[pixel[0], pixel[1], pixel[2], 255]);
         }
         Ok(())
     }
 
     /// Convert BGR to RGBA (common in medical imaging)
-    async fn convert_bgr_to_rgba(&self, raw_frame: &RawFrame) -> Result<Arc<[u8]>, ProcessingError> {
+    fn convert_bgr_to_rgba(&self, raw_frame: &RawFrame, dst: &mut [u8]) -> Result<(), ProcessingError> {
         let width = raw_frame.header.width as usize;
         let height = raw_frame.header.height as usize;
         let bpp = raw_frame.header.bytes_per_pixel as usize;
-        let expected_size = width * height * bpp;
-
-        if raw_frame.data.len() != expected_size {
-            return Err(ProcessingError::InvalidDataSize {
-                expected: expected_size,
-                actual: raw_frame.data.len(),
-            });
-        }
-
-        let mut rgba_data = Vec::with_capacity(width * height * 4);
+        Self::check_size(raw_frame, bpp)?;
 
         if self.parallel_processing && height > 100 {
             // Parallel processing for large images
-            self.convert_bgr_to_rgba_parallel(&raw_frame.data, &mut rgba_data, width, height, bpp).await?;
+            bgr_to_rgba_parallel(&raw_frame.data, dst, width, bpp);
         } else {
             // Sequential processing
-            self.convert_bgr_to_rgba_sequential(&raw_frame.data, &mut rgba_data, bpp);
+            bgr_to_rgba(&raw_frame.data, dst, bpp);
         }
 
-        Ok(Arc::from(rgba_data.into_boxed_slice()))
+        Ok(())
     }
 
-    /// Sequential BGR to RGBA conversion
-    fn convert_bgr_to_rgba_sequential(&self, bgr_data: &[u8], rgba_data: &mut Vec<u8>, bpp: usize) {
-        match bpp {
-            3 => {
-                // BGR -> RGBA
-                for chunk in bgr_data.chunks_exact(3) {
-                    rgba_data.extend_from_slice(&[chunk[2], chunk[1], chunk[0], 255]); // B,G,R -> R,G,B,A
-                }
-            }
-            4 => {
-                // BGRA -> RGBA
-                for chunk in bgr_data.chunks_exact(4) {
-                    rgba_data.extend_from_slice(&[chunk[2], chunk[1], chunk[0], chunk[3]]); // B,G,R,A -> R,G,B,A
-                }
-            }
-            _ => {
-                // Fallback to grayscale
-                for &pixel in bgr_data {
-                    rgba_data.extend_from_slice(&[pixel, pixel, pixel, 255]);
-                }
-            }
-        }
+    /// Convert BGRA to RGBA
+    fn convert_bgra_to_rgba(&self, raw_frame: &RawFrame, dst: &mut [u8]) -> Result<(), ProcessingError> {
+        // This is essentially the same as BGR conversion with alpha channel
+        self.convert_bgr_to_rgba(raw_frame, dst)
     }
 
-    /// Parallel BGR to RGBA conversion for large images
-    async fn convert_bgr_to_rgba_parallel(
-        &self,
-        bgr_data: &[u8],
-        rgba_data: &mut Vec<u8>,
-        width: usize,
-        height: usize,
-        bpp: usize,
-    ) -> Result<(), ProcessingError> {
-        use std::sync::atomic::{AtomicUsize, Ordering};
-
-        rgba_data.resize(width * height * 4, 0);
-
-        let num_threads = num_cpus::get().min(8);
-        let rows_per_thread = height / num_threads;
-        let processed_rows = Arc::new(AtomicUsize::new(0));
-
-        let tasks: Vec<_> = (0..num_threads).map(|thread_id| {
-            let start_row = thread_id * rows_per_thread;
-            let end_row = if thread_id == num_threads - 1 { height } else { (thread_id + 1) * rows_per_thread };
-
-            let bgr_data = bgr_data.to_vec(); // Clone for thread safety
-            let processed_rows = Arc::clone(&processed_rows);
-
-            tokio::spawn(async move {
-                for row in start_row..end_row {
-                    let row_start = row * width * bpp;
-                    let row_end = row_start + width * bpp;
-                    let _rgba_row_start = row * width * 4;
-
-                    for (_i, chunk) in bgr_data[row_start..row_end].chunks_exact(bpp).enumerate() {
-                        let _rgba_pixel = match bpp {
-                            3 => {
-                                // BGR -> RGBA
-                                [chunk[2], chunk[1], chunk[0], 255]
-                            }
-                            4 => {
-                                // BGRA -> RGBA
-                                [chunk[2], chunk[1], chunk[0], chunk[3]]
-                            }
-                            _ => [chunk[0], chunk[0], chunk[0], 255],
-                        };
-                    }
+    /// Convert packed YUV to RGBA (luma-only is common in ultrasound imaging)
+    fn convert_yuv_to_rgba(raw_frame: &RawFrame, dst: &mut [u8]) -> Result<(), ProcessingError> {
+        let bytes_per_pixel = match raw_frame.header.bytes_per_pixel {
+            bpp @ (2 | 3) => bpp as usize,
+            _ => 1,
+        };
+        Self::check_size(raw_frame, bytes_per_pixel)?;
 
-                    processed_rows.fetch_add(1, Ordering::Relaxed);
-                }
+        // Matrix and range come from the metadata, or the conventions for the frame size
+        let encoding = YuvEncoding::from_frame(&raw_frame.header, raw_frame.metadata.as_deref(), bytes_per_pixel > 1);
+        YuvConverter::new(&encoding).packed_to_rgba(&raw_frame.data, dst, bytes_per_pixel, encoding.chroma_first);
 
-                Ok::<(), ProcessingError>(())
-            })
-        }).collect();
+        Ok(())
+    }
 
-        // Wait for all tasks to complete
-        for task in tasks {
-            task.await.map_err(|e| ProcessingError::ParallelProcessing(e.to_string()))??;
+    /// Convert grayscale to RGBA
+    fn convert_grayscale_to_rgba(raw_frame: &RawFrame, dst: &mut [u8]) -> Result<(), ProcessingError> {
+        Self::check_size(raw_frame, 1)?;
+
+        for (&gray_value, out) in raw_frame.data.iter().zip(dst.chunks_exact_mut(4)) {
+            out.copy_from_slice(&[gray_value, gray_value, gray_value, 255]);
         }
 
         Ok(())
     }
 
-    /// Convert BGRA to RGBA
-    async fn convert_bgra_to_rgba(&self, raw_frame: &RawFrame) -> Result<Arc<[u8]>, ProcessingError> {
-        // This is essentially the same as BGR conversion with alpha channel
-        self.convert_bgr_to_rgba(raw_frame).await
-    }
+    /// Convert YUV10 (10-bit) to RGBA
+    fn convert_yuv10_to_rgba(raw_frame: &RawFrame, dst: &mut [u8], display_lut: &DisplayLut, dithering: Dithering) -> Result<(), ProcessingError> {
+        Self::check_size(raw_frame, 2)?; // 10-bit packed data
 
-    /// Convert YUV to RGBA (common in ultrasound imaging)
-    async fn convert_yuv_to_rgba(&self, raw_frame: &RawFrame) -> Result<Arc<[u8]>, ProcessingError> {
+        // Map 10-bit to 8-bit through the window/level LUT (keeps full precision before truncation)
         let width = raw_frame.header.width as usize;
-        let height = raw_frame.header.height as usize;
-        let expected_size = width * height; // Assuming single-plane YUV (grayscale)
+        map10_to_rgba(&raw_frame.data, dst, width, 1, raw_frame.header.is_big_endian(), display_lut, dithering);
 
-        if raw_frame.data.len() != expected_size {
-            return Err(ProcessingError::InvalidDataSize {
-                expected: expected_size,
-                actual: raw_frame.data.len(),
-            });
-        }
+        Ok(())
+    }
 
-        // For medical ultrasound, YUV is often just Y (luminance/grayscale)
-        let mut rgba_data = Vec::with_capacity(width * height * 4);
+    /// Convert RGB10 (10-bit) to RGBA
+    fn convert_rgb10_to_rgba(raw_frame: &RawFrame, dst: &mut [u8], display_lut: &DisplayLut, dithering: Dithering) -> Result<(), ProcessingError> {
+        Self::check_size(raw_frame, 6)?; // 3 channels * 2 bytes per 10-bit value
 
-        for &y_value in raw_frame.data.iter() {
-            rgba_data.extend_from_slice(&[y_value, y_value, y_value, 255]);
-        }
+        let width = raw_frame.header.width as usize;
+        map10_to_rgba(&raw_frame.data, dst, width, 3, raw_frame.header.is_big_endian(), display_lut, dithering);
 
-        Ok(Arc::from(rgba_data.into_boxed_slice()))
+        Ok(())
     }
 
-    /// Convert grayscale to RGBA
-    async fn convert_grayscale_to_rgba(&self, raw_frame: &RawFrame) -> Result<Arc<[u8]>, ProcessingError> {
-        let width = raw_frame.header.width as usize;
-        let height = raw_frame.header.height as usize;
-        let expected_size = width * height;
+    /// Convert RGB565 (5-bit red, 6-bit green, 5-bit blue in one 16-bit word) to RGBA
+    fn convert_rgb565_to_rgba(raw_frame: &RawFrame, dst: &mut [u8]) -> Result<(), ProcessingError> {
+        Self::check_size(raw_frame, 2)?;
 
-        if raw_frame.data.len() != expected_size {
-            return Err(ProcessingError::InvalidDataSize {
-                expected: expected_size,
-                actual: raw_frame.data.len(),
-            });
+        let big_endian = raw_frame.header.is_big_endian();
+        for (chunk, out) in raw_frame.data.chunks_exact(2).zip(dst.chunks_exact_mut(4)) {
+            out.copy_from_slice(&rgb565_to_rgba(read_sample16(chunk, big_endian)));
         }
 
-        let mut rgba_data = Vec::with_capacity(width * height * 4);
+        Ok(())
+    }
 
-        for &gray_value in raw_frame.data.iter() {
-            rgba_data.extend_from_slice(&[gray_value, gray_value, gray_value, 255]);
-        }
+    /// Convert 8-bit indexed color to RGBA through the palette in the frame metadata
+    fn convert_indexed_to_rgba(raw_frame: &RawFrame, dst: &mut [u8]) -> Result<(), ProcessingError> {
+        Self::check_size(raw_frame, 1)?;
 
-        Ok(Arc::from(rgba_data.into_boxed_slice()))
-    }
+        let palette = Palette::from_metadata(raw_frame.metadata.as_deref())?;
+        palette.to_rgba(&raw_frame.data, dst);
 
-    /// Convert YUV10 (10-bit) to RGBA
-    async fn convert_yuv10_to_rgba(&self, raw_frame: &RawFrame) -> Result<Arc<[u8]>, ProcessingError> {
-        let width = raw_frame.header.width as usize;
-        let height = raw_frame.header.height as usize;
-        let expected_size = width * height * 2; // 10-bit packed data
+        Ok(())
+    }
 
+    /// Convert 12-bit packed grayscale (two pixels in three bytes) to RGBA
+    fn convert_gray12_packed_to_rgba(raw_frame: &RawFrame, dst: &mut [u8], display_lut: &DisplayLut, dithering: Dithering) -> Result<(), ProcessingError> {
+        let pixels = raw_frame.header.width as usize * raw_frame.header.height as usize;
+        let expected_size = packed12::packed_len(pixels);
         if raw_frame.data.len() != expected_size {
-            return Err(ProcessingError::InvalidDataSize {
-                expected: expected_size,
-                actual: raw_frame.data.len(),
-            });
+            return Err(ProcessingError::InvalidDataSize { expected: expected_size, actual: raw_frame.data.len() });
         }
 
-        let mut rgba_data = Vec::with_capacity(width * height * 4);
-
-        // Convert 10-bit to 8-bit by right-shifting 2 bits
-        for chunk in raw_frame.data.chunks_exact(2) {
-            let value_10bit = u16::from_le_bytes([chunk[0], chunk[1]]);
-            let value_8bit = (value_10bit >> 2) as u8; // Convert 10-bit to 8-bit
-            rgba_data.extend_from_slice(&[value_8bit, value_8bit, value_8bit, 255]);
+        // The full 12 bits go through the window/level LUT
+        let width = (raw_frame.header.width as usize).max(1);
+        for (index, out) in dst.chunks_exact_mut(4).take(pixels).enumerate() {
+            let value = packed12::sample(&raw_frame.data, index);
+            let value_8bit = if dithering.is_off() {
+                display_lut.map12(value)
+            } else {
+                display_lut.map12_dithered(value, index % width, index / width, dithering)
+            };
+            out.copy_from_slice(&[value_8bit, value_8bit, value_8bit, 255]);
         }
 
-        Ok(Arc::from(rgba_data.into_boxed_slice()))
+        Ok(())
     }
 
-    /// Convert RGB10 (10-bit) to RGBA
-    async fn convert_rgb10_to_rgba(&self, raw_frame: &RawFrame) -> Result<Arc<[u8]>, ProcessingError> {
-        let width = raw_frame.header.width as usize;
-        let height = raw_frame.header.height as usize;
-        let expected_size = width * height * 6; // 3 channels * 2 bytes per 10-bit value
+    /// Enable or disable leaving frames unconverted for the GPU upload path
+    pub fn set_gpu_upload(&self, enabled: bool) {
+        self.gpu_upload.store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
 
-        if raw_frame.data.len() != expected_size {
-            return Err(ProcessingError::InvalidDataSize {
-                expected: expected_size,
-                actual: raw_frame.data.len(),
-            });
-        }
+    /// Set the dithering applied when 10/12-bit frames are reduced to 8 bits
+    pub fn set_dithering(&self, dithering: Dithering) {
+        debug!("🎨 Dithering: {}", dithering.name());
+        *self.dithering.write() = dithering;
+    }
+
+    /// Get the dithering applied when 10/12-bit frames are reduced to 8 bits
+    pub fn dithering(&self) -> Dithering {
+        *self.dithering.read()
+    }
 
-        let mut rgba_data = Vec::with_capacity(width * height * 4);
+    /// Set the deinterlacing of frames flagged as interlaced
+    pub fn set_deinterlace(&self, mode: Deinterlace) {
+        debug!("🎞️ Deinterlacing: {}", mode.name());
+        self.deinterlacer.lock().set_mode(mode);
+    }
 
-        // Convert 10-bit RGB to 8-bit RGBA
-        for chunk in raw_frame.data.chunks_exact(6) {
-            let r_10bit = u16::from_le_bytes([chunk[0], chunk[1]]);
-            let g_10bit = u16::from_le_bytes([chunk[2], chunk[3]]);
-            let b_10bit = u16::from_le_bytes([chunk[4], chunk[5]]);
+    /// Get the deinterlacing of frames flagged as interlaced
+    pub fn deinterlace_mode(&self) -> Deinterlace {
+        self.deinterlacer.lock().mode()
+    }
 
-            let r_8bit = (r_10bit >> 2) as u8;
-            let g_8bit = (g_10bit >> 2) as u8;
-            let b_8bit = (b_10bit >> 2) as u8;
+    /// Deinterlace a raw frame on arrival, returning progressive frames unchanged
+    pub fn deinterlace(&self, raw_frame: RawFrame) -> RawFrame {
+        self.deinterlacer.lock().process(raw_frame)
+    }
 
-            rgba_data.extend_from_slice(&[r_8bit, g_8bit, b_8bit, 255]);
-        }
+    /// Enable or disable keeping 10-bit frames at full precision up to the display
+    pub fn set_deep_color(&self, enabled: bool) {
+        self.deep_color.store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
 
-        Ok(Arc::from(rgba_data.into_boxed_slice()))
+    /// Pool the UI returns displayed buffers to
+    pub fn pixel_pool(&self) -> Arc<PixelBufferPool> {
+        Arc::clone(&self.pixel_pool)
     }
 
     /// Get processing statistics
@@ -369,9 +920,71 @@ impl FrameProcessor {
     pub fn reset_statistics(&self) {
         let mut stats = self.conversion_stats.write();
         *stats = ConversionStats::default();
+        self.stage_timings.lock().clear();
+    }
+}
+
+/// Rows converted per rayon task, large enough to amortize scheduling
+const PARALLEL_ROWS_PER_TASK: usize = 16;
+
+/// Map one packed BGR, BGRA or single-channel pixel to RGBA
+#[inline]
+fn bgr_pixel_to_rgba(pixel: &[u8]) -> [u8; 4] {
+    match pixel.len() {
+        3 => [pixel[2], pixel[1], pixel[0], 255], // B,G,R -> R,G,B,A
+        4 => [pixel[2], pixel[1], pixel[0], pixel[3]], // B,G,R,A -> R,G,B,A
+        _ => [pixel[0], pixel[0], pixel[0], 255], // Fallback to grayscale
+    }
+}
+
+/// Convert packed BGR(A) pixels into a preallocated RGBA buffer
+pub fn bgr_to_rgba(src: &[u8], dst: &mut [u8], bpp: usize) {
+    for (pixel, out) in src.chunks_exact(bpp.max(1)).zip(dst.chunks_exact_mut(4)) {
+        out.copy_from_slice(&bgr_pixel_to_rgba(pixel));
+    }
+}
+
+/// Convert packed BGR(A) pixels into a preallocated RGBA buffer, in row chunks across the rayon pool
+pub fn bgr_to_rgba_parallel(src: &[u8], dst: &mut [u8], width: usize, bpp: usize) {
+    use rayon::prelude::*;
+
+    let bpp = bpp.max(1);
+    if width == 0 {
+        return;
+    }
+
+    src.par_chunks(width * bpp * PARALLEL_ROWS_PER_TASK)
+        .zip(dst.par_chunks_mut(width * 4 * PARALLEL_ROWS_PER_TASK))
+        .for_each(|(src_rows, dst_rows)| bgr_to_rgba(src_rows, dst_rows, bpp));
+}
+
+/// Map 10-bit samples with 1 or 3 channels to RGBA through the LUT, dithered or rounded
+pub fn map10_to_rgba(src: &[u8], dst: &mut [u8], width: usize, channels: usize, big_endian: bool, lut: &DisplayLut, dithering: Dithering) {
+    let width = width.max(1);
+    for (index, (pixel, out)) in src.chunks_exact(channels * 2).zip(dst.chunks_exact_mut(4)).enumerate() {
+        let (x, y) = (index % width, index / width);
+        let mut rgba = [0, 0, 0, 255];
+        for (channel, sample) in pixel.chunks_exact(2).enumerate() {
+            let value = read_sample16(sample, big_endian);
+            rgba[channel] = if dithering.is_off() { lut.map10(value) } else { lut.map10_dithered(value, x, y, dithering) };
+        }
+        if channels == 1 {
+            rgba[1] = rgba[0];
+            rgba[2] = rgba[0];
+        }
+        out.copy_from_slice(&rgba);
     }
 }
 
+/// Expand one RGB565 pixel to RGBA, replicating the top bits so full scale maps to 255
+#[inline]
+pub fn rgb565_to_rgba(value: u16) -> [u8; 4] {
+    let r = (value >> 11) as u8 & 0x1F;
+    let g = (value >> 5) as u8 & 0x3F;
+    let b = value as u8 & 0x1F;
+    [(r << 3) | (r >> 2), (g << 2) | (g >> 4), (b << 3) | (b >> 2), 255]
+}
+
 /// Check if SIMD instructions are available
 fn is_simd_available() -> bool {
     // This is a simplified check - in a real implementation,
@@ -426,6 +1039,9 @@ pub enum ProcessingError {
     #[error("Unsupported format: {0}")]
     UnsupportedFormat(String),
 
+    #[error("Palette error: {0}")]
+    Palette(#[from] PaletteError),
+
     #[error("Parallel processing error: {0}")]
     ParallelProcessing(String),
 
@@ -450,8 +1066,44 @@ impl FrameFormat {
             FrameFormat::RGBA => "RGBA".to_string(),
             FrameFormat::YUV10 => "YUV10".to_string(),
             FrameFormat::RGB10 => "RGB10".to_string(),
+            FrameFormat::RGB565 => "RGB565".to_string(),
+            FrameFormat::Indexed => "Indexed".to_string(),
             FrameFormat::Grayscale => "Grayscale".to_string(),
+            FrameFormat::Gray12Packed => "Gray12Packed".to_string(),
             FrameFormat::Unknown => "Unknown".to_string(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_bgr_matches_sequential() {
+        // Height not a multiple of the task size, so the last chunk is partial
+        let (width, height) = (37, 101);
+        for bpp in [3, 4] {
+            let src: Vec<u8> = (0..width * height * bpp).map(|i| (i * 7 % 251) as u8).collect();
+            let mut sequential = vec![0u8; width * height * 4];
+            let mut parallel = vec![0u8; width * height * 4];
+
+            bgr_to_rgba(&src, &mut sequential, bpp);
+            bgr_to_rgba_parallel(&src, &mut parallel, width, bpp);
+
+            assert_eq!(parallel, sequential);
+            assert_eq!(&sequential[..4], &[src[2], src[1], src[0], if bpp == 4 { src[3] } else { 255 }]);
+        }
+    }
+
+    #[test]
+    fn test_rgb565_expansion() {
+        assert_eq!(rgb565_to_rgba(0xFFFF), [255, 255, 255, 255]);
+        assert_eq!(rgb565_to_rgba(0x0000), [0, 0, 0, 255]);
+        assert_eq!(rgb565_to_rgba(0xF800), [255, 0, 0, 255]);
+        assert_eq!(rgb565_to_rgba(0x07E0), [0, 255, 0, 255]);
+        assert_eq!(rgb565_to_rgba(0x001F), [0, 0, 255, 255]);
+        // Mid-scale green: 32 of 63
+        assert_eq!(rgb565_to_rgba(32 << 5)[1], 130);
+    }
+}
@@ -0,0 +1,231 @@
+// src/backend/frame_sink.rs - Frame Consumers Shared by All Frontends
+
+//! The consuming half of the pipeline, as `FrameSource` is the producing half.
+//!
+//! Every frontend receives the same two streams from the backend: processed
+//! frames from the latest-frame slot and control events from the broadcast
+//! channel. A frontend implements `FrameSink` to react to them; `run_sink`
+//! does the receiving, so lag handling and shutdown cannot drift apart
+//! between frontends.
+//!
+//! Further consumers (recorders, forwarders, snapshot writers) attach to a
+//! `SinkHub` instead. Each gets its own bounded queue: a slow sink loses its
+//! own frames without holding up the display or the other sinks.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::backend::frame_delivery::FrameReceiver;
+use crate::backend::types::ProcessedFrame;
+use crate::backend::BackendEvent;
+
+/// Anything that displays or otherwise consumes the backend's output
+#[async_trait]
+pub trait FrameSink: Send {
+    /// Consume the newest processed frame
+    async fn on_frame(&mut self, frame: ProcessedFrame);
+
+    /// React to a connection, statistics or settings event
+    async fn on_event(&mut self, event: BackendEvent);
+
+    /// Checked before every wait; returning false stops the sink
+    fn keep_running(&self) -> bool {
+        true
+    }
+
+    /// Called once when the sink stops, to flush or close what it writes to
+    async fn finish(&mut self) {}
+}
+
+/// Feed frames and events to `sink` until it stops or the backend goes away
+///
+/// Frames the sink was too slow for are handled by the backend's drop policy;
+/// events it was too slow for are skipped with a warning.
+pub async fn run_sink<S: FrameSink + ?Sized>(
+    mut events: broadcast::Receiver<BackendEvent>,
+    mut frames: FrameReceiver,
+    sink: &mut S,
+) {
+    while sink.keep_running() {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => sink.on_event(event).await,
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    warn!("Backend event receiver lagged by {} events", count);
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    info!("Backend event channel closed");
+                    break;
+                }
+            },
+
+            changed = frames.changed() => {
+                if changed.is_err() {
+                    info!("Backend frame channel closed");
+                    break;
+                }
+                if let Some(frame) = frames.take_latest() {
+                    sink.on_frame(frame).await;
+                }
+            }
+        }
+    }
+    sink.finish().await;
+}
+
+/// Frames queued per attached sink before new ones are dropped for it
+pub const DEFAULT_SINK_QUEUE: usize = 4;
+
+/// Delivery counters of an attached sink
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SinkStatistics {
+    /// Frames queued for the sink
+    pub delivered: u64,
+    /// Frames dropped because the sink's queue was full
+    pub dropped: u64,
+}
+
+struct AttachedSink {
+    name: String,
+    frames: mpsc::Sender<ProcessedFrame>,
+    stats: Arc<parking_lot::Mutex<SinkStatistics>>,
+    task: JoinHandle<()>,
+}
+
+/// Fans processed frames out to attached sinks, each with its own queue
+///
+/// Publishing never waits: a full queue drops the frame for that sink only.
+#[derive(Default)]
+pub struct SinkHub {
+    sinks: parking_lot::Mutex<Vec<AttachedSink>>,
+}
+
+impl SinkHub {
+    /// Run `sink` on its own task with room for `queue` waiting frames
+    pub fn attach<S: FrameSink + 'static>(
+        &self,
+        name: &str,
+        mut sink: S,
+        queue: usize,
+        mut events: broadcast::Receiver<BackendEvent>,
+    ) {
+        let (frames, mut frame_rx) = mpsc::channel(queue.max(1));
+        let task_name = name.to_string();
+        let task = tokio::spawn(async move {
+            while sink.keep_running() {
+                tokio::select! {
+                    frame = frame_rx.recv() => match frame {
+                        Some(frame) => sink.on_frame(frame).await,
+                        None => break,
+                    },
+                    event = events.recv() => match event {
+                        Ok(event) => sink.on_event(event).await,
+                        Err(broadcast::error::RecvError::Lagged(count)) => {
+                            debug!("Sink {} skipped {} events", task_name, count);
+                        }
+                        // Keep draining frames; the frame queue decides when to stop
+                        Err(broadcast::error::RecvError::Closed) => events = broadcast::channel(1).1,
+                    },
+                }
+            }
+            sink.finish().await;
+        });
+
+        info!("🚰 Frame sink attached: {} (queue {})", name, queue.max(1));
+        self.sinks.lock().push(AttachedSink {
+            name: name.to_string(),
+            frames,
+            stats: Arc::default(),
+            task,
+        });
+    }
+
+    /// Queue a frame for every attached sink
+    pub fn publish(&self, frame: &ProcessedFrame) {
+        let mut sinks = self.sinks.lock();
+        sinks.retain(|sink| !sink.frames.is_closed());
+        for sink in sinks.iter() {
+            let mut stats = sink.stats.lock();
+            match sink.frames.try_send(frame.clone()) {
+                Ok(()) => stats.delivered += 1,
+                Err(_) => stats.dropped += 1,
+            }
+        }
+    }
+
+    /// Check if any sink is attached
+    pub fn is_empty(&self) -> bool {
+        self.sinks.lock().is_empty()
+    }
+
+    /// Delivery counters per sink name
+    pub fn statistics(&self) -> Vec<(String, SinkStatistics)> {
+        self.sinks.lock().iter().map(|sink| (sink.name.clone(), *sink.stats.lock())).collect()
+    }
+
+    /// Let every sink drain its queue and finish, then detach them all
+    pub async fn close(&self) {
+        let sinks = std::mem::take(&mut *self.sinks.lock());
+        for sink in sinks {
+            drop(sink.frames);
+            let stats = *sink.stats.lock();
+            if let Err(e) = sink.task.await {
+                warn!("⚠️ Frame sink {} failed: {}", sink.name, e);
+            }
+            info!("🚰 Frame sink {} stopped ({} frames, {} dropped)", sink.name, stats.delivered, stats.dropped);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::frame_delivery::{FrameDropPolicy, FrameSender};
+    use crate::backend::pixel_pool::RgbaBuffer;
+    use crate::backend::types::{FrameFormat, FrameHeader};
+
+    /// Counts what it receives and stops after the first frame
+    #[derive(Default)]
+    struct CountingSink {
+        frames: Vec<u64>,
+        events: usize,
+    }
+
+    #[async_trait]
+    impl FrameSink for CountingSink {
+        async fn on_frame(&mut self, frame: ProcessedFrame) {
+            self.frames.push(frame.header.frame_id);
+        }
+
+        async fn on_event(&mut self, _event: BackendEvent) {
+            self.events += 1;
+        }
+
+        fn keep_running(&self) -> bool {
+            self.frames.is_empty()
+        }
+    }
+
+    #[test]
+    fn test_sink_receives_events_and_frames() {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let (event_tx, events) = broadcast::channel(4);
+            let sender = FrameSender::new();
+            let frames = sender.subscribe();
+
+            event_tx.send(BackendEvent::Connected).unwrap();
+            let header = FrameHeader { frame_id: 7, sequence_number: 7, ..FrameHeader::for_test(1, 1, 4, 0) };
+            let frame = ProcessedFrame::new(header, RgbaBuffer::new(1, 1), None, std::time::Instant::now(), FrameFormat::RGBA);
+            sender.deliver(frame, FrameDropPolicy::DropOldest).await;
+
+            let mut sink = CountingSink::default();
+            run_sink(events, frames, &mut sink).await;
+            assert_eq!(sink.frames, vec![7]);
+        });
+    }
+}
@@ -0,0 +1,293 @@
+// src/backend/frame_source.rs - Transport-Independent Frame Sources
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use tracing::warn;
+
+use crate::backend::fd_passing;
+use crate::backend::igtl::{self, IgtlFrameSource};
+use crate::backend::integrity::Integrity;
+use crate::backend::memory::MappingMemory;
+use crate::backend::shared_memory::{region_path, ConnectionStatistics, SharedMemoryError, SharedMemoryReader};
+use crate::backend::tcp_source::{self, TcpFrameReceiver};
+use crate::backend::types::{ConnectionConfig, RawFrame};
+
+/// Anything the connection manager can pull frames from
+///
+/// Shared memory is the primary transport; the others deliver the same
+/// `FrameHeader` + payload so the rest of the pipeline cannot tell them apart.
+#[async_trait]
+pub trait FrameSource: Send + Sync {
+    /// Open the transport
+    async fn connect(&mut self) -> Result<(), SharedMemoryError>;
+
+    /// Close the transport
+    async fn disconnect(&mut self);
+
+    /// Next frame in sequence, or the newest one when catching up; None if nothing new arrived
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError>;
+
+    /// Check that the producer is still there and sending frames
+    fn check_connection_health(&self) -> bool;
+
+    /// Frames received but not yet read
+    fn backlog(&self) -> u64;
+
+    /// Get connection statistics
+    fn get_statistics(&self) -> ConnectionStatistics;
+
+    /// Force reconnection attempt
+    async fn force_reconnect(&mut self) -> Result<(), SharedMemoryError> {
+        self.disconnect().await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        self.connect().await
+    }
+}
+
+/// Create the source named by `--shm-name`
+///
+/// `tcp://host:port` receives frames over the network, `rtsp://` URLs decode an
+/// IP camera stream, `gst:<pipeline>` runs a GStreamer pipeline and `v4l2:/dev/videoN`
+/// captures from a frame grabber, `ndi:<source>` receives an NDI feed and `igtl://host[:port]`
+/// receives OpenIGTLink images, `zmq:<endpoint>[#topic]` subscribes to a ZeroMQ
+/// publisher; anything else is a shared memory region (a name, a path, or
+/// a `unix:` socket handing over a memfd).
+pub fn open_source(name: &str, config: ConnectionConfig) -> Result<Box<dyn FrameSource>, SharedMemoryError> {
+    if let Some(address) = tcp_source::tcp_address(name) {
+        return Ok(Box::new(TcpFrameReceiver::new(address, config)));
+    }
+
+    if let Some(address) = igtl::igtl_address(name) {
+        return Ok(Box::new(IgtlFrameSource::new(&address, config)));
+    }
+
+    if let Some(description) = name.strip_prefix("gst:") {
+        #[cfg(feature = "gstreamer")]
+        return Ok(Box::new(crate::backend::gstreamer_source::GStreamerFrameSource::new(description.trim(), config)));
+        #[cfg(not(feature = "gstreamer"))]
+        return Err(SharedMemoryError::Other(format!(
+            "GStreamer pipeline \"{}\" needs a build with the \"gstreamer\" feature", description.trim()
+        )));
+    }
+
+    if name.starts_with("v4l2:") {
+        #[cfg(feature = "v4l2")]
+        {
+            use crate::backend::v4l2_source::{parse_device, V4l2FrameSource};
+            let (path, size) = parse_device(name)
+                .ok_or_else(|| SharedMemoryError::Other(format!("Invalid V4L2 device \"{}\" (use v4l2:/dev/video0[@WxH])", name)))?;
+            return Ok(Box::new(V4l2FrameSource::new(path, size, config)));
+        }
+        #[cfg(not(feature = "v4l2"))]
+        return Err(SharedMemoryError::Other("V4L2 capture needs a build with the \"v4l2\" feature".to_string()));
+    }
+
+    if let Some(source_name) = name.strip_prefix("ndi:").filter(|source_name| !source_name.is_empty()) {
+        #[cfg(feature = "ndi")]
+        return Ok(Box::new(crate::backend::ndi_source::NdiFrameSource::new(source_name, config)));
+        #[cfg(not(feature = "ndi"))]
+        return Err(SharedMemoryError::Other(format!(
+            "NDI source \"{}\" needs a build with the \"ndi\" feature", source_name
+        )));
+    }
+
+    if name.starts_with("zmq:") {
+        #[cfg(feature = "zmq")]
+        {
+            use crate::backend::zmq_source::{parse_endpoint, ZmqFrameSource};
+            let (endpoint, topic) = parse_endpoint(name)
+                .ok_or_else(|| SharedMemoryError::Other(format!("Invalid ZeroMQ endpoint \"{}\" (use zmq:tcp://host:port[#topic])", name)))?;
+            return Ok(Box::new(ZmqFrameSource::new(endpoint, topic, config)));
+        }
+        #[cfg(not(feature = "zmq"))]
+        return Err(SharedMemoryError::Other("ZeroMQ sources need a build with the \"zmq\" feature".to_string()));
+    }
+
+    if is_rtsp_url(name) {
+        #[cfg(feature = "rtsp")]
+        return Ok(Box::new(crate::backend::rtsp_source::RtspFrameSource::new(name, config)?));
+        #[cfg(not(feature = "rtsp"))]
+        return Err(SharedMemoryError::Other("RTSP sources need a build with the \"rtsp\" feature".to_string()));
+    }
+
+    Ok(Box::new(SharedMemoryReader::new(name, config)?))
+}
+
+/// File whose appearance means the producer behind `name` is up, for sources that have one
+///
+/// Shared memory regions, `unix:` sockets and V4L2 devices can be watched;
+/// network sources and pipelines cannot.
+pub fn watch_path(name: &str) -> Option<PathBuf> {
+    if let Some(socket) = fd_passing::socket_path(name) {
+        return Some(PathBuf::from(socket));
+    }
+    if let Some(device) = name.strip_prefix("v4l2:") {
+        return Some(PathBuf::from(device.split('@').next().unwrap_or(device)));
+    }
+    if name.contains("://") || ["gst:", "ndi:", "zmq:"].iter().any(|prefix| name.starts_with(prefix)) {
+        return None;
+    }
+    Some(region_path(name))
+}
+
+/// Check if the name is an RTSP URL rather than a shared memory region
+pub fn is_rtsp_url(name: &str) -> bool {
+    name.starts_with("rtsp://") || name.starts_with("rtsps://")
+}
+
+/// Frames pushed by a receiving task and pulled by `FrameSource::get_next_frame`
+///
+/// Network sources cannot be read on demand like a ring buffer, so they queue
+/// what arrives and drop the oldest frame once `capacity` are waiting.
+pub struct FrameQueue {
+    frames: Mutex<VecDeque<RawFrame>>,
+    capacity: usize,
+    connected: AtomicBool,
+    last_frame_time: RwLock<Instant>,
+    frames_processed: AtomicU64,
+    error_count: AtomicU64,
+    corrupted_count: AtomicU64,
+}
+
+impl FrameQueue {
+    /// Create an empty, disconnected queue
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity.max(1),
+            connected: AtomicBool::new(false),
+            last_frame_time: RwLock::new(Instant::now()),
+            frames_processed: AtomicU64::new(0),
+            error_count: AtomicU64::new(0),
+            corrupted_count: AtomicU64::new(0),
+        }
+    }
+
+    /// Queue a received frame, dropping it if its checksum does not match
+    pub fn push(&self, frame: RawFrame) {
+        if let Integrity::Corrupted { expected, actual } = Integrity::verify(&frame.header, &frame.data) {
+            let corrupted = self.corrupted_count.fetch_add(1, Ordering::Relaxed) + 1;
+            warn!("🧨 Corrupted frame {} (seq {}): checksum {:08x}, expected {:08x} ({} corrupted so far)",
+                  frame.header.frame_id, frame.header.sequence_number, actual, expected, corrupted);
+            return;
+        }
+
+        *self.last_frame_time.write() = Instant::now();
+        let mut frames = self.frames.lock();
+        if frames.len() >= self.capacity {
+            frames.pop_front();
+        }
+        frames.push_back(frame);
+    }
+
+    /// Next frame in order, or the newest one (discarding the rest) when catching up
+    ///
+    /// Once the receiving task has marked the queue disconnected, draining it
+    /// reports `ConnectionLost`.
+    pub fn next(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        let frame = {
+            let mut frames = self.frames.lock();
+            if catch_up {
+                let newest = frames.pop_back();
+                frames.clear();
+                newest
+            } else {
+                frames.pop_front()
+            }
+        };
+
+        match frame {
+            Some(frame) => {
+                self.frames_processed.fetch_add(1, Ordering::Relaxed);
+                Ok(Some(frame))
+            }
+            None if !self.is_connected() => Err(SharedMemoryError::ConnectionLost),
+            None => Ok(None),
+        }
+    }
+
+    /// Mark the transport opened (clearing stale frames) or closed
+    pub fn set_connected(&self, connected: bool) {
+        if connected {
+            self.frames.lock().clear();
+            *self.last_frame_time.write() = Instant::now();
+        }
+        self.connected.store(connected, Ordering::Release);
+    }
+
+    /// Check if the transport is open
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Acquire)
+    }
+
+    /// Count a transport error
+    pub fn record_error(&self) {
+        self.error_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a frame dropped because the transport's own checksum did not match
+    pub fn record_corrupted(&self) {
+        self.corrupted_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Check that the transport is open and frames arrived within `timeout`
+    pub fn is_healthy(&self, timeout: Duration) -> bool {
+        self.is_connected() && self.last_frame_time.read().elapsed() <= timeout
+    }
+
+    /// Frames waiting to be read
+    pub fn backlog(&self) -> u64 {
+        self.frames.lock().len() as u64
+    }
+
+    /// Statistics for a source without a control block
+    pub fn statistics(&self, name: String) -> ConnectionStatistics {
+        ConnectionStatistics {
+            connected: self.is_connected(),
+            shm_name: name,
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            error_count: self.error_count.load(Ordering::Relaxed),
+            corrupted_frames: self.corrupted_count.load(Ordering::Relaxed),
+            last_frame_elapsed: self.last_frame_time.read().elapsed(),
+            control_block: None,
+            memory: MappingMemory::default(),
+            protocol: None,
+        }
+    }
+}
+
+#[async_trait]
+impl FrameSource for SharedMemoryReader {
+    async fn connect(&mut self) -> Result<(), SharedMemoryError> {
+        SharedMemoryReader::connect(self).await
+    }
+
+    async fn disconnect(&mut self) {
+        SharedMemoryReader::disconnect(self).await
+    }
+
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        SharedMemoryReader::get_next_frame(self, catch_up).await
+    }
+
+    fn check_connection_health(&self) -> bool {
+        SharedMemoryReader::check_connection_health(self)
+    }
+
+    fn backlog(&self) -> u64 {
+        SharedMemoryReader::backlog(self)
+    }
+
+    fn get_statistics(&self) -> ConnectionStatistics {
+        SharedMemoryReader::get_statistics(self)
+    }
+
+    async fn force_reconnect(&mut self) -> Result<(), SharedMemoryError> {
+        SharedMemoryReader::force_reconnect(self).await
+    }
+}
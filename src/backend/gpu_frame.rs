@@ -0,0 +1,153 @@
+// src/backend/gpu_frame.rs - Raw Frames Handed to the GPU for Conversion
+
+use crate::backend::adjustments::{Dithering, DisplayLut, Gamma, WindowLevel};
+use crate::backend::frame_processor::{bgr_to_rgba, bgr_to_rgba_parallel, map10_to_rgba};
+use crate::backend::pixel_pool::RgbaBuffer;
+use crate::backend::types::{FrameFormat, RawFrame};
+use crate::backend::yuv::{YuvEncoding, YuvRange};
+
+/// How a packed frame is uploaded as a texture and swizzled to RGBA
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GpuLayout {
+    /// Samples per pixel, uploaded as a 1, 3 or 4 channel texture
+    pub channels: u32,
+    /// Blue comes first in memory (BGR/BGRA)
+    pub swap_red_blue: bool,
+    /// Significant bits per sample: 8, or 10 in 16-bit little-endian words
+    pub sample_bits: u32,
+}
+
+impl GpuLayout {
+    /// Layout for formats the GPU shader converts, None for ones that need the CPU path
+    ///
+    /// 10-bit formats go through the window/level LUT on the CPU, so they are not
+    /// offered here; see `for_deep_frame`.
+    pub fn for_frame(format: FrameFormat, bytes_per_pixel: u32) -> Option<Self> {
+        let swap_red_blue = match format {
+            FrameFormat::RGB | FrameFormat::RGBA => false,
+            FrameFormat::BGR | FrameFormat::BGRA => true,
+            FrameFormat::Grayscale | FrameFormat::YUV if bytes_per_pixel == 1 => false,
+            _ => return None,
+        };
+
+        matches!(bytes_per_pixel, 1 | 3 | 4).then_some(Self { channels: bytes_per_pixel, swap_red_blue, sample_bits: 8 })
+    }
+
+    /// Layout for 10-bit formats uploaded as 16-bit integer textures (deep color)
+    pub fn for_deep_frame(format: FrameFormat, bytes_per_pixel: u32) -> Option<Self> {
+        let channels = match (format, bytes_per_pixel) {
+            (FrameFormat::YUV10, 2) => 1,
+            (FrameFormat::RGB10, 6) => 3,
+            _ => return None,
+        };
+        Some(Self { channels, swap_red_blue: false, sample_bits: 10 })
+    }
+
+    /// Bytes one pixel takes in the frame data
+    pub fn bytes_per_pixel(&self) -> usize {
+        self.channels as usize * if self.sample_bits > 8 { 2 } else { 1 }
+    }
+}
+
+/// Window/level and gamma the shader applies to 10-bit frames
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpuToneMapping {
+    pub window_level: WindowLevel,
+    pub gamma: Gamma,
+    /// Used when the frame has to be converted on the CPU after all
+    pub dithering: Dithering,
+}
+
+/// Unconverted frame for the GPU upload path
+#[derive(Debug, Clone)]
+pub struct GpuFrame {
+    pub frame: RawFrame,
+    pub layout: GpuLayout,
+    /// Set for 10-bit frames, whose window/level is left to the GPU too
+    pub tone: Option<GpuToneMapping>,
+}
+
+impl GpuFrame {
+    /// Wrap a raw frame if its format and size suit the GPU path
+    ///
+    /// The shader shows luma as-is, so limited-range YUV stays on the CPU path.
+    pub fn new(frame: RawFrame, format: FrameFormat) -> Option<Self> {
+        let layout = GpuLayout::for_frame(format, frame.header.bytes_per_pixel)?;
+        if format == FrameFormat::YUV
+            && YuvEncoding::from_frame(&frame.header, frame.metadata.as_deref(), false).range == YuvRange::Limited
+        {
+            return None;
+        }
+        Self::with_layout(frame, layout, None)
+    }
+
+    /// Wrap a 10-bit frame whose window/level and gamma the shader applies
+    ///
+    /// Textures take native-endian samples, so frames in another byte order
+    /// stay on the CPU path.
+    pub fn deep(frame: RawFrame, format: FrameFormat, tone: GpuToneMapping) -> Option<Self> {
+        if frame.header.is_big_endian() != cfg!(target_endian = "big") {
+            return None;
+        }
+        let layout = GpuLayout::for_deep_frame(format, frame.header.bytes_per_pixel)?;
+        Self::with_layout(frame, layout, Some(tone))
+    }
+
+    fn with_layout(frame: RawFrame, layout: GpuLayout, tone: Option<GpuToneMapping>) -> Option<Self> {
+        let expected = frame.header.width as usize * frame.header.height as usize * layout.bytes_per_pixel();
+        (frame.data.len() == expected && expected > 0).then_some(Self { frame, layout, tone })
+    }
+
+    /// Frame size in pixels
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.frame.header.width, self.frame.header.height)
+    }
+
+    /// Convert on the CPU, for exports and renderers without OpenGL
+    pub fn to_rgba(&self) -> RgbaBuffer {
+        let (width, height) = self.dimensions();
+        let mut rgba = RgbaBuffer::new(width, height);
+        let dst = rgba.make_mut_bytes();
+        let src = &self.frame.data;
+        let channels = self.layout.channels as usize;
+
+        if let Some(tone) = &self.tone {
+            let lut = DisplayLut::new(tone.window_level, tone.gamma);
+            map10_to_rgba(src, dst, width as usize, channels, self.frame.header.is_big_endian(), &lut, tone.dithering);
+        } else if self.layout.swap_red_blue {
+            bgr_to_rgba_parallel(src, dst, width as usize, channels);
+        } else if channels == 4 {
+            dst.copy_from_slice(src);
+        } else if channels == 1 {
+            // Single-channel input takes bgr_to_rgba's grayscale branch
+            bgr_to_rgba(src, dst, 1);
+        } else {
+            for (pixel, out) in src.chunks_exact(3).zip(dst.chunks_exact_mut(4)) {
+                out.copy_from_slice(&[pixel[0], pixel[1], pixel[2], 255]);
+            }
+        }
+
+        rgba
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpu_layouts() {
+        assert_eq!(GpuLayout::for_frame(FrameFormat::BGR, 3), Some(GpuLayout { channels: 3, swap_red_blue: true, sample_bits: 8 }));
+        assert_eq!(GpuLayout::for_frame(FrameFormat::RGB, 4), Some(GpuLayout { channels: 4, swap_red_blue: false, sample_bits: 8 }));
+        assert_eq!(GpuLayout::for_frame(FrameFormat::Grayscale, 1), Some(GpuLayout { channels: 1, swap_red_blue: false, sample_bits: 8 }));
+        assert_eq!(GpuLayout::for_frame(FrameFormat::Grayscale, 2), None);
+        assert_eq!(GpuLayout::for_frame(FrameFormat::YUV10, 2), None);
+        assert_eq!(GpuLayout::for_frame(FrameFormat::RGB10, 6), None);
+
+        // Deep color uploads 10-bit samples as 16-bit words
+        let deep = GpuLayout::for_deep_frame(FrameFormat::RGB10, 6).unwrap();
+        assert_eq!((deep.channels, deep.sample_bits, deep.bytes_per_pixel()), (3, 10, 6));
+        assert_eq!(GpuLayout::for_deep_frame(FrameFormat::YUV10, 2).map(|layout| layout.channels), Some(1));
+        assert_eq!(GpuLayout::for_deep_frame(FrameFormat::BGR, 3), None);
+    }
+}
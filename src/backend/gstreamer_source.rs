@@ -0,0 +1,259 @@
+// src/backend/gstreamer_source.rs - GStreamer appsink Source (feature "gstreamer")
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use gstreamer_video as gst_video;
+use tracing::{info, warn};
+
+use crate::backend::frame_source::{FrameQueue, FrameSource};
+use crate::backend::shared_memory::{ConnectionStatistics, SharedMemoryError};
+use crate::backend::types::{ConnectionConfig, FrameFormat, FrameHeader, RawFrame};
+
+/// Prefix of `--shm-name` values that describe a GStreamer pipeline
+pub const GSTREAMER_PREFIX: &str = "gst:";
+
+/// Name of the appsink the viewer pulls from (appended if the pipeline has none)
+pub const APPSINK_NAME: &str = "mivi";
+
+/// Samples buffered before the oldest is dropped
+const MAX_QUEUED_FRAMES: usize = 4;
+
+/// Pipeline description if the name is `gst:<pipeline>`
+pub fn pipeline_description(name: &str) -> Option<&str> {
+    name.strip_prefix(GSTREAMER_PREFIX).map(str::trim).filter(|pipeline| !pipeline.is_empty())
+}
+
+/// Format code and bytes per pixel for raw video formats the processor converts
+///
+/// Anything else has to be converted in the pipeline (add `videoconvert`);
+/// the appsink caps only accept these formats, so negotiation does that.
+pub fn format_code(format: gst_video::VideoFormat) -> Option<(u32, u32)> {
+    match format {
+        gst_video::VideoFormat::Bgr => Some((FrameFormat::BGR.to_code(), 3)),
+        gst_video::VideoFormat::Bgrx | gst_video::VideoFormat::Bgra => Some((FrameFormat::BGR.to_code(), 4)),
+        gst_video::VideoFormat::Gray8 => Some((FrameFormat::Grayscale.to_code(), 1)),
+        _ => None,
+    }
+}
+
+/// Frames from any GStreamer pipeline ending in an appsink
+///
+/// `gst:v4l2src ! videoconvert` gets an appsink named `mivi` appended;
+/// pipelines that already contain one are used as written.
+pub struct GStreamerFrameSource {
+    description: String,
+    config: ConnectionConfig,
+    queue: Arc<FrameQueue>,
+    pipeline: Option<gst::Pipeline>,
+}
+
+impl GStreamerFrameSource {
+    /// Create a source for a `gst-launch-1.0` style pipeline description
+    pub fn new(description: &str, config: ConnectionConfig) -> Self {
+        let description = if description.contains("appsink") {
+            description.to_string()
+        } else {
+            format!("{} ! appsink name={}", description, APPSINK_NAME)
+        };
+
+        Self {
+            description,
+            config,
+            queue: Arc::new(FrameQueue::new(MAX_QUEUED_FRAMES)),
+            pipeline: None,
+        }
+    }
+
+    fn build_pipeline(&self) -> Result<gst::Pipeline, SharedMemoryError> {
+        let gst_error = |e: gst::glib::Error| SharedMemoryError::Other(format!("GStreamer: {}", e));
+
+        gst::init().map_err(gst_error)?;
+        let pipeline = gst::parse::launch(&self.description)
+            .map_err(gst_error)?
+            .downcast::<gst::Pipeline>()
+            .map_err(|_| SharedMemoryError::Other("GStreamer description is not a pipeline".to_string()))?;
+
+        let sink = pipeline
+            .by_name(APPSINK_NAME)
+            .or_else(|| pipeline.iterate_sinks().into_iter().flatten().find(|sink| sink.is::<gst_app::AppSink>()))
+            .and_then(|sink| sink.downcast::<gst_app::AppSink>().ok())
+            .ok_or_else(|| SharedMemoryError::Other("GStreamer pipeline has no appsink".to_string()))?;
+
+        let caps = gst_video::VideoCapsBuilder::new()
+            .format_list([
+                gst_video::VideoFormat::Bgr,
+                gst_video::VideoFormat::Bgrx,
+                gst_video::VideoFormat::Bgra,
+                gst_video::VideoFormat::Gray8,
+            ])
+            .build();
+        sink.set_caps(Some(&caps));
+        sink.set_max_buffers(MAX_QUEUED_FRAMES as u32);
+        sink.set_drop(true);
+
+        let queue = Arc::clone(&self.queue);
+        let mut sequence = 0u64;
+        sink.set_callbacks(
+            gst_app::AppSinkCallbacks::builder()
+                .new_sample(move |sink| {
+                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
+                    sequence += 1;
+                    match sample_to_frame(&sample, sequence) {
+                        Some(frame) => queue.push(frame),
+                        None => {
+                            warn!("⚠️ Dropping GStreamer sample {} with unusable caps", sequence);
+                            queue.record_error();
+                        }
+                    }
+                    Ok(gst::FlowSuccess::Ok)
+                })
+                .build(),
+        );
+
+        Ok(pipeline)
+    }
+
+    /// Report errors and end-of-stream from the pipeline bus until stopped
+    fn watch_bus(bus: gst::Bus, queue: Arc<FrameQueue>) {
+        for message in bus.iter_timed(gst::ClockTime::NONE) {
+            match message.view() {
+                gst::MessageView::Eos(..) => {
+                    info!("🔌 GStreamer pipeline reached end of stream");
+                    break;
+                }
+                gst::MessageView::Error(err) => {
+                    warn!("❌ GStreamer error from {:?}: {} ({:?})",
+                          err.src().map(|src| src.path_string()), err.error(), err.debug());
+                    queue.record_error();
+                    break;
+                }
+                gst::MessageView::Application(..) => break, // Posted by disconnect()
+                _ => {}
+            }
+        }
+
+        queue.set_connected(false);
+    }
+}
+
+#[async_trait]
+impl FrameSource for GStreamerFrameSource {
+    async fn connect(&mut self) -> Result<(), SharedMemoryError> {
+        if self.config.verbose_logging {
+            info!("🔌 Starting GStreamer pipeline: {}", self.description);
+        }
+
+        let pipeline = self.build_pipeline()?;
+        let bus = pipeline
+            .bus()
+            .ok_or_else(|| SharedMemoryError::Other("GStreamer pipeline has no bus".to_string()))?;
+
+        self.queue.set_connected(true);
+        let queue = Arc::clone(&self.queue);
+        std::thread::Builder::new()
+            .name("mivi-gst-bus".to_string())
+            .spawn(move || Self::watch_bus(bus, queue))?;
+
+        if let Err(e) = pipeline.set_state(gst::State::Playing) {
+            let _ = pipeline.set_state(gst::State::Null);
+            self.queue.set_connected(false);
+            return Err(SharedMemoryError::Other(format!("GStreamer pipeline failed to start: {}", e)));
+        }
+        self.pipeline = Some(pipeline);
+
+        info!("🔗 GStreamer pipeline playing");
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) {
+        if let Some(pipeline) = self.pipeline.take() {
+            let _ = pipeline.set_state(gst::State::Null);
+            if let Some(bus) = pipeline.bus() {
+                let _ = bus.post(gst::message::Application::new(gst::Structure::new_empty("mivi-stop")));
+            }
+        }
+        self.queue.set_connected(false);
+
+        info!("🔌 GStreamer pipeline stopped");
+    }
+
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        self.queue.next(catch_up)
+    }
+
+    fn check_connection_health(&self) -> bool {
+        self.queue.is_healthy(self.config.frame_timeout)
+    }
+
+    fn backlog(&self) -> u64 {
+        self.queue.backlog()
+    }
+
+    fn get_statistics(&self) -> ConnectionStatistics {
+        self.queue.statistics(format!("{}{}", GSTREAMER_PREFIX, self.description))
+    }
+}
+
+impl Drop for GStreamerFrameSource {
+    fn drop(&mut self) {
+        if let Some(pipeline) = self.pipeline.take() {
+            let _ = pipeline.set_state(gst::State::Null);
+        }
+    }
+}
+
+/// Copy a sample into a tightly packed frame, dropping any row padding
+fn sample_to_frame(sample: &gst::Sample, sequence: u64) -> Option<RawFrame> {
+    let info = gst_video::VideoInfo::from_caps(sample.caps()?).ok()?;
+    let (format_code, bytes_per_pixel) = format_code(info.format())?;
+    let frame = gst_video::VideoFrameRef::from_buffer_ref_readable(sample.buffer()?, &info).ok()?;
+
+    let (width, height) = (info.width() as usize, info.height() as usize);
+    let stride = frame.plane_stride()[0] as usize;
+    let plane = frame.plane_data(0).ok()?;
+    let row = width * bytes_per_pixel as usize;
+
+    let mut data = Vec::with_capacity(row * height);
+    for y in 0..height {
+        data.extend_from_slice(plane.get(y * stride..y * stride + row)?);
+    }
+
+    let header = FrameHeader {
+        frame_id: sequence,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64,
+        width: width as u32,
+        height: height as u32,
+        bytes_per_pixel,
+        data_size: data.len() as u32,
+        format_code,
+        flags: 0,
+        sequence_number: sequence,
+        metadata_offset: 0,
+        metadata_size: 0,
+        padding: [0; 4],
+    };
+    Some(RawFrame::new(header, Arc::from(data), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_names_and_formats() {
+        assert_eq!(pipeline_description("gst: v4l2src ! videoconvert"), Some("v4l2src ! videoconvert"));
+        assert_eq!(pipeline_description("gst:"), None);
+
+        let source = GStreamerFrameSource::new("videotestsrc ! videoconvert", ConnectionConfig::default());
+        assert_eq!(source.description, "videotestsrc ! videoconvert ! appsink name=mivi");
+
+        assert_eq!(format_code(gst_video::VideoFormat::Bgrx), Some((0x02, 4)));
+        assert_eq!(format_code(gst_video::VideoFormat::Gray8), Some((0x10, 1)));
+        assert_eq!(format_code(gst_video::VideoFormat::I420), None);
+    }
+}
@@ -0,0 +1,334 @@
+// src/backend/health.rs - Liveness and Readiness Health Checks
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Longest the UI event loop may go without a tick before it counts as hung
+pub const DEFAULT_UI_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Address `mivi healthcheck` asks by default
+pub const DEFAULT_HEALTH_ADDRESS: &str = "127.0.0.1:9110";
+
+/// Largest request head read from a client
+const MAX_REQUEST_SIZE: usize = 4096;
+
+/// Errors from the health endpoint or the health check client
+#[derive(Debug, Error)]
+pub enum HealthError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Health endpoint did not answer within {0:?}")]
+    Timeout(Duration),
+
+    #[error("Malformed health response: {0}")]
+    Response(String),
+}
+
+#[derive(Debug, Default)]
+struct HealthInner {
+    connected: bool,
+    last_frame: Option<Instant>,
+    last_ui_tick: Option<Instant>,
+}
+
+/// Health of the running viewer, fed by the frame loop and the UI event loop
+///
+/// Live while the UI keeps responding; ready once it is also connected and
+/// receiving frames within the frame timeout.
+#[derive(Debug)]
+pub struct HealthMonitor {
+    started: Instant,
+    frame_timeout: Duration,
+    ui_timeout: Duration,
+    inner: parking_lot::Mutex<HealthInner>,
+}
+
+/// Health at one point in time, as served by the endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub live: bool,
+    pub ready: bool,
+    pub connected: bool,
+    /// A frame arrived within the frame timeout
+    pub receiving: bool,
+    pub ui_responsive: bool,
+    /// Age of the last frame, None before the first one
+    pub last_frame_age_ms: Option<u64>,
+    /// Age of the last UI tick, None before the first one
+    pub last_ui_tick_age_ms: Option<u64>,
+    pub uptime_s: u64,
+}
+
+impl HealthMonitor {
+    /// Create a monitor expecting frames every `frame_timeout` and UI ticks every `ui_timeout`
+    pub fn new(frame_timeout: Duration, ui_timeout: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            frame_timeout,
+            ui_timeout,
+            inner: parking_lot::Mutex::new(HealthInner::default()),
+        }
+    }
+
+    /// Record the connection state
+    pub fn set_connected(&self, connected: bool) {
+        self.inner.lock().connected = connected;
+    }
+
+    /// Record that a frame was read
+    pub fn frame_received(&self) {
+        self.inner.lock().last_frame = Some(Instant::now());
+    }
+
+    /// Record that the UI event loop ran; call it from the UI thread
+    pub fn ui_tick(&self) {
+        self.inner.lock().last_ui_tick = Some(Instant::now());
+    }
+
+    /// Current health
+    pub fn report(&self) -> HealthReport {
+        self.report_at(Instant::now())
+    }
+
+    /// Health as seen at `now`
+    ///
+    /// A UI that has not ticked yet is given the UI timeout from startup to come up.
+    pub fn report_at(&self, now: Instant) -> HealthReport {
+        let inner = self.inner.lock();
+        let age = |at: Option<Instant>| at.map(|at| now.saturating_duration_since(at));
+        let frame_age = age(inner.last_frame);
+        let ui_age = age(inner.last_ui_tick);
+
+        let receiving = inner.connected && frame_age.is_some_and(|age| age <= self.frame_timeout);
+        let ui_responsive = ui_age.unwrap_or(now.saturating_duration_since(self.started)) <= self.ui_timeout;
+
+        HealthReport {
+            live: ui_responsive,
+            ready: ui_responsive && inner.connected && receiving,
+            connected: inner.connected,
+            receiving,
+            ui_responsive,
+            last_frame_age_ms: frame_age.map(|age| age.as_millis() as u64),
+            last_ui_tick_age_ms: ui_age.map(|age| age.as_millis() as u64),
+            uptime_s: now.saturating_duration_since(self.started).as_secs(),
+        }
+    }
+}
+
+/// Which check a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthProbe {
+    /// `/health/live`: the process and its UI respond
+    Liveness,
+    /// `/health/ready` or `/health`: the viewer is showing frames
+    Readiness,
+}
+
+impl HealthProbe {
+    /// Map a request path to a probe
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path.split('?').next().unwrap_or_default().trim_end_matches('/') {
+            "/health" | "/health/ready" => Some(HealthProbe::Readiness),
+            "/health/live" => Some(HealthProbe::Liveness),
+            _ => None,
+        }
+    }
+
+    /// Request path of the probe
+    pub fn path(self) -> &'static str {
+        match self {
+            HealthProbe::Liveness => "/health/live",
+            HealthProbe::Readiness => "/health/ready",
+        }
+    }
+
+    /// Check if the report passes the probe
+    pub fn passes(self, report: &HealthReport) -> bool {
+        match self {
+            HealthProbe::Liveness => report.live,
+            HealthProbe::Readiness => report.ready,
+        }
+    }
+}
+
+/// Minimal HTTP endpoint serving health reports as JSON
+///
+/// Answers 200 when the probe passes and 503 when it does not, so it works with
+/// supervisors that only look at the status code.
+pub struct HealthServer {
+    address: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl HealthServer {
+    /// Listen for health requests on `host:port`
+    pub async fn bind(address: &str, monitor: Arc<HealthMonitor>) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+        let task = tokio::spawn(Self::accept_requests(listener, monitor));
+
+        info!("🩺 Health endpoint listening on http://{}/health", address);
+        Ok(Self { address, task })
+    }
+
+    /// Address the endpoint listens on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    async fn accept_requests(listener: TcpListener, monitor: Arc<HealthMonitor>) {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let monitor = Arc::clone(&monitor);
+                    tokio::spawn(async move {
+                        if let Err(e) = Self::serve_request(stream, &monitor).await {
+                            debug!("Health request from {} failed: {}", peer, e);
+                        }
+                    });
+                }
+                Err(e) => warn!("⚠️ Health endpoint accept failed: {}", e),
+            }
+        }
+    }
+
+    async fn serve_request(mut stream: TcpStream, monitor: &HealthMonitor) -> std::io::Result<()> {
+        let mut request = Vec::with_capacity(512);
+        let mut buffer = [0u8; 512];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_SIZE {
+            let read = stream.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+        }
+
+        let request = String::from_utf8_lossy(&request);
+        let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+        let response = match (request_line.next(), request_line.next().and_then(HealthProbe::from_path)) {
+            (Some("GET"), Some(probe)) => {
+                let report = monitor.report();
+                let status = if probe.passes(&report) { "200 OK" } else { "503 Service Unavailable" };
+                let body = serde_json::to_string(&report).unwrap_or_default();
+                http_response(status, &body)
+            }
+            (Some("GET"), None) => http_response("404 Not Found", "{\"error\":\"not found\"}"),
+            _ => http_response("405 Method Not Allowed", "{\"error\":\"method not allowed\"}"),
+        };
+
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+impl Drop for HealthServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Ask a running viewer's health endpoint, returning whether the probe passed and the report
+pub async fn query(address: &str, probe: HealthProbe, timeout: Duration) -> Result<(bool, HealthReport), HealthError> {
+    let exchange = async {
+        let mut stream = TcpStream::connect(address).await?;
+        let request = format!("GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", probe.path(), address);
+        stream.write_all(request.as_bytes()).await?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await?;
+        Ok::<_, HealthError>(response)
+    };
+    let response = tokio::time::timeout(timeout, exchange).await.map_err(|_| HealthError::Timeout(timeout))??;
+    parse_response(&String::from_utf8_lossy(&response))
+}
+
+fn parse_response(response: &str) -> Result<(bool, HealthReport), HealthError> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| HealthError::Response("missing header terminator".to_string()))?;
+    let status: u16 = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| HealthError::Response("missing status code".to_string()))?;
+
+    match status {
+        200 | 503 => {
+            let report = serde_json::from_str(body).map_err(|e| HealthError::Response(e.to_string()))?;
+            Ok((status == 200, report))
+        }
+        other => Err(HealthError::Response(format!("unexpected status {}", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_model() {
+        let monitor = HealthMonitor::new(Duration::from_secs(1), Duration::from_secs(5));
+        let now = Instant::now();
+
+        // Starting up: the UI gets a grace period, nothing is connected yet
+        let report = monitor.report_at(now);
+        assert!(report.live && !report.ready && !report.receiving);
+
+        monitor.set_connected(true);
+        monitor.ui_tick();
+        monitor.frame_received();
+        let report = monitor.report_at(Instant::now());
+        assert!(report.live && report.ready);
+        assert!(HealthProbe::Readiness.passes(&report));
+
+        // Frames stop arriving, then the UI hangs
+        let report = monitor.report_at(Instant::now() + Duration::from_secs(2));
+        assert!(report.live && !report.ready && !report.receiving);
+        let report = monitor.report_at(Instant::now() + Duration::from_secs(6));
+        assert!(!report.live && !HealthProbe::Liveness.passes(&report));
+
+        assert_eq!(HealthProbe::from_path("/health"), Some(HealthProbe::Readiness));
+        assert_eq!(HealthProbe::from_path("/health/live/"), Some(HealthProbe::Liveness));
+        assert_eq!(HealthProbe::from_path("/metrics"), None);
+    }
+
+    #[test]
+    fn test_endpoint_round_trip() {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let monitor = Arc::new(HealthMonitor::new(Duration::from_secs(1), DEFAULT_UI_TIMEOUT));
+            let server = HealthServer::bind("127.0.0.1:0", Arc::clone(&monitor)).await.unwrap();
+            let address = server.local_addr().to_string();
+            let timeout = Duration::from_secs(2);
+
+            let (ready, report) = query(&address, HealthProbe::Readiness, timeout).await.unwrap();
+            assert!(!ready && !report.connected);
+            let (live, _) = query(&address, HealthProbe::Liveness, timeout).await.unwrap();
+            assert!(live);
+
+            monitor.set_connected(true);
+            monitor.frame_received();
+            let (ready, report) = query(&address, HealthProbe::Readiness, timeout).await.unwrap();
+            assert!(ready && report.receiving);
+        });
+    }
+}
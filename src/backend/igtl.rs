@@ -0,0 +1,609 @@
+// src/backend/igtl.rs - OpenIGTLink IMAGE Receiver and Re-Broadcaster
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::backend::calibration::PixelSpacing;
+use crate::backend::frame_source::{FrameQueue, FrameSource};
+use crate::backend::shared_memory::{ConnectionStatistics, SharedMemoryError};
+use crate::backend::tcp_source::MAX_MESSAGE_SIZE;
+use crate::backend::types::{read_sample16, ConnectionConfig, FrameFormat, FrameHeader, RawFrame};
+
+/// Prefix of `--shm-name` values that name an OpenIGTLink server (3D Slicer, PLUS)
+pub const IGTL_PREFIX: &str = "igtl://";
+
+/// Port OpenIGTLink servers listen on unless told otherwise
+pub const DEFAULT_PORT: u16 = 18944;
+
+/// Size of the big-endian message header in front of every body
+pub const HEADER_SIZE: usize = 58;
+
+/// Size of the IMAGE header in front of the pixel data
+pub const IMAGE_HEADER_SIZE: usize = 72;
+
+/// Device name the broadcaster sends images under
+pub const DEVICE_NAME: &str = "MiVi";
+
+/// Frames buffered before the oldest is dropped
+const MAX_QUEUED_FRAMES: usize = 4;
+
+const SCALAR_UINT8: u8 = 3;
+const SCALAR_UINT16: u8 = 5;
+const ENDIAN_BIG: u8 = 1;
+const ENDIAN_LITTLE: u8 = 2;
+const COORDINATE_RAS: u8 = 1;
+
+/// Lookup table for the CRC-64 (ECMA-182) polynomial, MSB first
+const CRC64_TABLE: [u64; 256] = crc64_table();
+
+const fn crc64_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u64) << 56;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & (1 << 63) != 0 { (crc << 1) ^ 0x42F0_E1EB_A9EA_3693 } else { crc << 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-64 (ECMA-182) of a message body, as carried in the OpenIGTLink header
+pub fn crc64(data: &[u8]) -> u64 {
+    data.iter().fold(0u64, |crc, &byte| {
+        CRC64_TABLE[(((crc >> 56) as u8) ^ byte) as usize] ^ (crc << 8)
+    })
+}
+
+/// Server address if the name is `igtl://host[:port]`, with the default port filled in
+pub fn igtl_address(name: &str) -> Option<String> {
+    let address = name.strip_prefix(IGTL_PREFIX).filter(|address| !address.is_empty())?;
+    let has_port = address.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+    Some(if has_port { address.to_string() } else { format!("{}:{}", address, DEFAULT_PORT) })
+}
+
+/// Why a received message did not yield a frame
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum IgtlError {
+    #[error("CRC mismatch: {actual:016x}, expected {expected:016x}")]
+    Crc { expected: u64, actual: u64 },
+
+    #[error("Message truncated: {0}")]
+    Truncated(&'static str),
+
+    #[error("Unsupported image: {components} components of scalar type {scalar_type}")]
+    UnsupportedImage { components: u8, scalar_type: u8 },
+}
+
+/// The header in front of every OpenIGTLink message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageHeader {
+    pub version: u16,
+    pub message_type: String,
+    pub device_name: String,
+    /// Seconds in the upper 32 bits, fraction of a second in the lower 32
+    pub timestamp: u64,
+    pub body_size: u64,
+    pub crc: u64,
+}
+
+impl MessageHeader {
+    fn parse(bytes: &[u8; HEADER_SIZE]) -> Self {
+        let text = |range: std::ops::Range<usize>| {
+            let field = &bytes[range];
+            let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+            String::from_utf8_lossy(&field[..end]).into_owned()
+        };
+
+        Self {
+            version: u16::from_be_bytes([bytes[0], bytes[1]]),
+            message_type: text(2..14),
+            device_name: text(14..34),
+            timestamp: be_u64(&bytes[34..42]),
+            body_size: be_u64(&bytes[42..50]),
+            crc: be_u64(&bytes[50..58]),
+        }
+    }
+
+    fn encode(&self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0u8; HEADER_SIZE];
+        bytes[0..2].copy_from_slice(&self.version.to_be_bytes());
+        let text = |bytes: &mut [u8], value: &str| {
+            let value = &value.as_bytes()[..value.len().min(bytes.len())];
+            bytes[..value.len()].copy_from_slice(value);
+        };
+        text(&mut bytes[2..14], &self.message_type);
+        text(&mut bytes[14..34], &self.device_name);
+        bytes[34..42].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[42..50].copy_from_slice(&self.body_size.to_be_bytes());
+        bytes[50..58].copy_from_slice(&self.crc.to_be_bytes());
+        bytes
+    }
+
+    /// Timestamp in nanoseconds since the epoch, or None if the sender left it unset
+    pub fn timestamp_nanos(&self) -> Option<u64> {
+        let seconds = self.timestamp >> 32;
+        let fraction = self.timestamp & 0xFFFF_FFFF;
+        (self.timestamp != 0).then(|| seconds * 1_000_000_000 + ((fraction * 1_000_000_000) >> 32))
+    }
+}
+
+fn be_u16(bytes: &[u8]) -> u16 {
+    u16::from_be_bytes([bytes[0], bytes[1]])
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn be_u64(bytes: &[u8]) -> u64 {
+    u64::from_be_bytes(bytes[..8].try_into().unwrap_or_default())
+}
+
+fn be_f32(bytes: &[u8]) -> f32 {
+    f32::from_bits(be_u32(bytes))
+}
+
+/// Read one message header and its body
+pub async fn read_message<R: AsyncRead + Unpin>(stream: &mut R) -> Result<(MessageHeader, Vec<u8>), SharedMemoryError> {
+    let mut bytes = [0u8; HEADER_SIZE];
+    stream.read_exact(&mut bytes).await?;
+    let header = MessageHeader::parse(&bytes);
+
+    if header.body_size > MAX_MESSAGE_SIZE as u64 {
+        return Err(SharedMemoryError::InvalidFrameSize { start: HEADER_SIZE, end: header.body_size as usize, total: MAX_MESSAGE_SIZE });
+    }
+
+    let mut body = vec![0u8; header.body_size as usize];
+    stream.read_exact(&mut body).await?;
+    Ok((header, body))
+}
+
+/// The message content without the version 2 extended header and metadata
+fn message_content<'a>(header: &MessageHeader, body: &'a [u8]) -> Result<&'a [u8], IgtlError> {
+    if header.version < 2 {
+        return Ok(body);
+    }
+
+    if body.len() < 12 {
+        return Err(IgtlError::Truncated("extended header"));
+    }
+    let extended_header_size = be_u16(&body[0..2]) as usize;
+    let metadata_header_size = be_u16(&body[2..4]) as usize;
+    let metadata_size = be_u32(&body[4..8]) as usize;
+
+    let end = body.len().checked_sub(metadata_header_size + metadata_size).ok_or(IgtlError::Truncated("metadata"))?;
+    body.get(extended_header_size..end).ok_or(IgtlError::Truncated("extended header"))
+}
+
+/// Convert an IMAGE message into a frame, keeping the first slice of a volume
+///
+/// 8-bit images with 1, 3 or 4 components become grayscale, BGR or BGRA frames;
+/// 16-bit grayscale is reduced to the 10-bit path. The image spacing goes into
+/// the metadata, where calibration picks it up.
+pub fn decode_image(header: &MessageHeader, body: &[u8], sequence: u64) -> Result<RawFrame, IgtlError> {
+    let actual = crc64(body);
+    if actual != header.crc {
+        return Err(IgtlError::Crc { expected: header.crc, actual });
+    }
+
+    let content = message_content(header, body)?;
+    let image = content.get(..IMAGE_HEADER_SIZE).ok_or(IgtlError::Truncated("image header"))?;
+    let (components, scalar_type, endian) = (image[2], image[3], image[4]);
+    let size = [be_u16(&image[6..8]), be_u16(&image[8..10]), be_u16(&image[10..12])];
+    let matrix: Vec<f32> = (0..12).map(|i| be_f32(&image[12 + i * 4..])).collect();
+    let subvolume = [be_u16(&image[66..68]), be_u16(&image[68..70])];
+
+    // The pixel data covers the subvolume; an unset one means the whole image
+    let (width, height) = match subvolume {
+        [0, _] | [_, 0] => (size[0] as usize, size[1] as usize),
+        [i, j] => (i as usize, j as usize),
+    };
+    let pixels = width * height * components as usize;
+    let data = &content[IMAGE_HEADER_SIZE..];
+
+    let (format, bytes_per_pixel, data) = match (scalar_type, components) {
+        (SCALAR_UINT8, 1) => (FrameFormat::Grayscale, 1, data.get(..pixels).ok_or(IgtlError::Truncated("image data"))?.to_vec()),
+        (SCALAR_UINT8, 3 | 4) => {
+            let mut bgr = data.get(..pixels).ok_or(IgtlError::Truncated("image data"))?.to_vec();
+            for pixel in bgr.chunks_exact_mut(components as usize) {
+                pixel.swap(0, 2);
+            }
+            (FrameFormat::BGR, components as u32, bgr)
+        }
+        (SCALAR_UINT16, 1) => {
+            let samples = data.get(..pixels * 2).ok_or(IgtlError::Truncated("image data"))?;
+            let data = samples
+                .chunks_exact(2)
+                .flat_map(|sample| {
+                    let value = match endian {
+                        ENDIAN_LITTLE => u16::from_le_bytes([sample[0], sample[1]]),
+                        _ => u16::from_be_bytes([sample[0], sample[1]]),
+                    };
+                    (value >> 6).to_le_bytes()
+                })
+                .collect();
+            (FrameFormat::YUV10, 2, data)
+        }
+        _ => return Err(IgtlError::UnsupportedImage { components, scalar_type }),
+    };
+
+    let norm = |v: &[f32]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    let metadata = serde_json::json!({
+        "igtl_device": header.device_name,
+        "pixel_spacing_x": norm(&matrix[0..3]),
+        "pixel_spacing_y": norm(&matrix[3..6]),
+    })
+    .to_string();
+
+    let header = FrameHeader {
+        frame_id: sequence,
+        timestamp: header.timestamp_nanos()
+            .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64),
+        width: width as u32,
+        height: height as u32,
+        bytes_per_pixel,
+        data_size: data.len() as u32,
+        format_code: format.to_code(),
+        flags: 0,
+        sequence_number: sequence,
+        metadata_offset: 0,
+        metadata_size: 0,
+        padding: [0; 4],
+    };
+    Ok(RawFrame::new(header, Arc::from(data), Some(metadata)))
+}
+
+/// Encode a frame as a version 1 IMAGE message, or None for formats OpenIGTLink cannot carry
+///
+/// The inverse of `decode_image`: BGR is swapped back to RGB and 10-bit samples
+/// are scaled up to 16 bits. Calibrated frames carry their pixel spacing in the
+/// image matrix.
+pub fn encode_image(frame: &RawFrame, device_name: &str) -> Option<Vec<u8>> {
+    let header = &frame.header;
+    let pixels = header.width as usize * header.height as usize;
+
+    let (scalar_type, components, data): (u8, u8, Vec<u8>) = match (FrameFormat::from_code(header.format_code), header.bytes_per_pixel) {
+        (FrameFormat::Grayscale, 1) => (SCALAR_UINT8, 1, frame.data.get(..pixels)?.to_vec()),
+        (FrameFormat::BGR, bytes_per_pixel @ (3 | 4)) => {
+            let mut rgb = frame.data.get(..pixels * bytes_per_pixel as usize)?.to_vec();
+            for pixel in rgb.chunks_exact_mut(bytes_per_pixel as usize) {
+                pixel.swap(0, 2);
+            }
+            (SCALAR_UINT8, bytes_per_pixel as u8, rgb)
+        }
+        (FrameFormat::YUV10, 2) => {
+            let big_endian = header.is_big_endian();
+            let data = frame.data
+                .get(..pixels * 2)?
+                .chunks_exact(2)
+                .flat_map(|sample| (read_sample16(sample, big_endian) << 6).to_be_bytes())
+                .collect();
+            (SCALAR_UINT16, 1, data)
+        }
+        _ => return None,
+    };
+
+    let spacing = frame.metadata.as_deref().and_then(PixelSpacing::from_metadata);
+    let (spacing_x, spacing_y) = spacing.map_or((1.0, 1.0), |spacing| (spacing.x_mm, spacing.y_mm));
+    let matrix = [spacing_x, 0.0, 0.0, 0.0, spacing_y, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0];
+    let (width, height) = (header.width as u16, header.height as u16);
+
+    let mut body = Vec::with_capacity(IMAGE_HEADER_SIZE + data.len());
+    body.extend_from_slice(&1u16.to_be_bytes());
+    body.extend_from_slice(&[components, scalar_type, ENDIAN_BIG, COORDINATE_RAS]);
+    for value in [width, height, 1] {
+        body.extend_from_slice(&value.to_be_bytes());
+    }
+    for value in matrix {
+        body.extend_from_slice(&value.to_bits().to_be_bytes());
+    }
+    for value in [0, 0, 0, width, height, 1] {
+        body.extend_from_slice(&value.to_be_bytes());
+    }
+    body.extend_from_slice(&data);
+
+    let seconds = header.timestamp / 1_000_000_000;
+    let fraction = ((header.timestamp % 1_000_000_000) << 32) / 1_000_000_000;
+    let message_header = MessageHeader {
+        version: 1,
+        message_type: "IMAGE".to_string(),
+        device_name: device_name.to_string(),
+        timestamp: (seconds << 32) | fraction,
+        body_size: body.len() as u64,
+        crc: crc64(&body),
+    };
+
+    let mut message = message_header.encode().to_vec();
+    message.extend_from_slice(&body);
+    Some(message)
+}
+
+/// Receives IMAGE messages from an OpenIGTLink server such as 3D Slicer or PLUS
+///
+/// Other message types (TRANSFORM, STATUS, ...) are read and ignored, so the
+/// stream stays in step.
+pub struct IgtlFrameSource {
+    address: String,
+    config: ConnectionConfig,
+    queue: Arc<FrameQueue>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl IgtlFrameSource {
+    /// Create a source for `host:port`
+    pub fn new(address: &str, config: ConnectionConfig) -> Self {
+        Self {
+            address: address.to_string(),
+            config,
+            queue: Arc::new(FrameQueue::new(MAX_QUEUED_FRAMES)),
+            task: None,
+        }
+    }
+
+    async fn receive_images(mut stream: TcpStream, queue: Arc<FrameQueue>) {
+        let mut sequence = 0u64;
+        loop {
+            let (header, body) = match read_message(&mut stream).await {
+                Ok(message) => message,
+                Err(SharedMemoryError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    info!("🔌 OpenIGTLink server closed the connection");
+                    break;
+                }
+                Err(e) => {
+                    warn!("❌ OpenIGTLink stream failed: {}", e);
+                    queue.record_error();
+                    break;
+                }
+            };
+
+            if header.message_type != "IMAGE" {
+                continue;
+            }
+
+            sequence += 1;
+            match decode_image(&header, &body, sequence) {
+                Ok(frame) => queue.push(frame),
+                Err(e @ IgtlError::Crc { .. }) => {
+                    warn!("🧨 Corrupted OpenIGTLink image from {}: {}", header.device_name, e);
+                    queue.record_corrupted();
+                }
+                Err(e) => {
+                    warn!("⚠️ Dropping OpenIGTLink image from {}: {}", header.device_name, e);
+                    queue.record_error();
+                }
+            }
+        }
+
+        queue.set_connected(false);
+    }
+}
+
+#[async_trait]
+impl FrameSource for IgtlFrameSource {
+    async fn connect(&mut self) -> Result<(), SharedMemoryError> {
+        if self.config.verbose_logging {
+            info!("🔌 Connecting to OpenIGTLink server: {}", self.address);
+        }
+
+        let stream = tokio::time::timeout(self.config.frame_timeout, TcpStream::connect(&self.address))
+            .await
+            .map_err(|_| SharedMemoryError::NotFound(self.address.clone()))?
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::ConnectionRefused => SharedMemoryError::NotFound(self.address.clone()),
+                _ => SharedMemoryError::Io(e),
+            })?;
+        stream.set_nodelay(true)?;
+
+        self.queue.set_connected(true);
+        self.task = Some(tokio::spawn(Self::receive_images(stream, Arc::clone(&self.queue))));
+
+        info!("🔗 Connected to OpenIGTLink server: {}", self.address);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.queue.set_connected(false);
+
+        info!("🔌 Disconnected from OpenIGTLink server: {}", self.address);
+    }
+
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        self.queue.next(catch_up)
+    }
+
+    fn check_connection_health(&self) -> bool {
+        self.queue.is_healthy(self.config.frame_timeout)
+    }
+
+    fn backlog(&self) -> u64 {
+        self.queue.backlog()
+    }
+
+    fn get_statistics(&self) -> ConnectionStatistics {
+        self.queue.statistics(format!("{}{}", IGTL_PREFIX, self.address))
+    }
+}
+
+impl Drop for IgtlFrameSource {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+/// Serves every displayed frame as IMAGE messages to OpenIGTLink clients
+///
+/// Each client gets its own task; one that falls behind skips frames rather
+/// than holding up the frame loop or the other clients.
+pub struct IgtlBroadcaster {
+    address: SocketAddr,
+    messages: broadcast::Sender<Arc<[u8]>>,
+    task: JoinHandle<()>,
+}
+
+impl IgtlBroadcaster {
+    /// Listen for clients on `host:port`
+    pub async fn bind(address: &str) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+        let (messages, _) = broadcast::channel(MAX_QUEUED_FRAMES);
+        let task = tokio::spawn(Self::accept_clients(listener, messages.clone()));
+
+        info!("📡 Broadcasting frames over OpenIGTLink on {}", address);
+        Ok(Self { address, messages, task })
+    }
+
+    /// Address clients connect to
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Number of connected clients
+    pub fn client_count(&self) -> usize {
+        self.messages.receiver_count()
+    }
+
+    /// Send a frame to all clients; false if nobody is listening or the format cannot be sent
+    pub fn broadcast(&self, frame: &RawFrame) -> bool {
+        if self.client_count() == 0 {
+            return false;
+        }
+
+        match encode_image(frame, DEVICE_NAME) {
+            Some(message) => self.messages.send(Arc::from(message)).is_ok(),
+            None => {
+                debug!("OpenIGTLink cannot carry format {:#04x} frames", frame.header.format_code);
+                false
+            }
+        }
+    }
+
+    async fn accept_clients(listener: TcpListener, messages: broadcast::Sender<Arc<[u8]>>) {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    info!("🔗 OpenIGTLink client connected: {}", peer);
+                    let _ = stream.set_nodelay(true);
+                    tokio::spawn(Self::serve_client(stream, peer, messages.subscribe()));
+                }
+                Err(e) => warn!("⚠️ OpenIGTLink accept failed: {}", e),
+            }
+        }
+    }
+
+    async fn serve_client(mut stream: TcpStream, peer: SocketAddr, mut messages: broadcast::Receiver<Arc<[u8]>>) {
+        loop {
+            match messages.recv().await {
+                Ok(message) => {
+                    if let Err(e) = stream.write_all(&message).await {
+                        info!("🔌 OpenIGTLink client {} disconnected: {}", peer, e);
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!("OpenIGTLink client {} skipped {} frames", peer, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+impl Drop for IgtlBroadcaster {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(format_code: u32, bytes_per_pixel: u32, data: Vec<u8>, metadata: Option<&str>) -> RawFrame {
+        let header = FrameHeader {
+            timestamp: 1_700_000_000_500_000_000,
+            data_size: data.len() as u32,
+            ..FrameHeader::for_test(2, 1, bytes_per_pixel, format_code)
+        };
+        RawFrame::new(header, Arc::from(data), metadata.map(str::to_string))
+    }
+
+    fn split(message: &[u8]) -> (MessageHeader, &[u8]) {
+        let header = MessageHeader::parse(message[..HEADER_SIZE].try_into().unwrap());
+        (header, &message[HEADER_SIZE..])
+    }
+
+    #[test]
+    fn test_crc64_and_addresses() {
+        assert_eq!(crc64(b"123456789"), 0x6C40_DF5F_0B49_7347);
+        assert_eq!(igtl_address("igtl://slicer.local").as_deref(), Some("slicer.local:18944"));
+        assert_eq!(igtl_address("igtl://10.0.0.7:18945").as_deref(), Some("10.0.0.7:18945"));
+        assert_eq!(igtl_address("igtl://"), None);
+    }
+
+    #[test]
+    fn test_image_round_trip() {
+        let bgr = frame(0x02, 3, vec![1, 2, 3, 4, 5, 6], Some(r#"{"pixel_spacing_x":0.2,"pixel_spacing_y":0.3}"#));
+        let message = encode_image(&bgr, DEVICE_NAME).unwrap();
+        let (header, body) = split(&message);
+        assert_eq!((header.message_type.as_str(), header.device_name.as_str()), ("IMAGE", DEVICE_NAME));
+        assert_eq!(header.body_size as usize, IMAGE_HEADER_SIZE + 6);
+        assert_eq!(&body[IMAGE_HEADER_SIZE..], &[3, 2, 1, 6, 5, 4]); // RGB on the wire
+
+        let decoded = decode_image(&header, body, 7).unwrap();
+        assert_eq!((decoded.header.width, decoded.header.height, decoded.header.sequence_number), (2, 1, 7));
+        assert_eq!(&decoded.data[..], &[1, 2, 3, 4, 5, 6]);
+        assert!(decoded.header.timestamp.abs_diff(bgr.header.timestamp) < 10);
+        let spacing = PixelSpacing::from_metadata(decoded.metadata.as_deref().unwrap()).unwrap();
+        assert!((spacing.x_mm - 0.2).abs() < 1e-6 && (spacing.y_mm - 0.3).abs() < 1e-6);
+
+        // 10-bit samples travel as 16-bit big-endian
+        let yuv10 = frame(0x03, 2, vec![0xFF, 0x03, 0x00, 0x02], None);
+        let message = encode_image(&yuv10, DEVICE_NAME).unwrap();
+        let (header, body) = split(&message);
+        assert_eq!(&decode_image(&header, body, 1).unwrap().data[..], &[0xFF, 0x03, 0x00, 0x02]);
+
+        // A flipped bit fails the CRC
+        let mut corrupted = body.to_vec();
+        corrupted[IMAGE_HEADER_SIZE] ^= 1;
+        assert!(matches!(decode_image(&header, &corrupted, 1), Err(IgtlError::Crc { .. })));
+
+        assert!(encode_image(&frame(0x01, 2, vec![0; 4], None), DEVICE_NAME).is_none());
+    }
+
+    #[test]
+    fn test_version_2_content() {
+        let gray = frame(0x10, 1, vec![10, 20], None);
+        let message = encode_image(&gray, DEVICE_NAME).unwrap();
+        let (mut header, content) = split(&message);
+
+        // Extended header in front, an empty metadata header behind
+        let mut body = vec![0, 12, 0, 2, 0, 0, 0, 0, 0, 0, 0, 9];
+        body.extend_from_slice(content);
+        body.extend_from_slice(&[0, 0]);
+        header.version = 2;
+        header.body_size = body.len() as u64;
+        header.crc = crc64(&body);
+
+        let decoded = decode_image(&header, &body, 1).unwrap();
+        assert_eq!((decoded.header.format_code, &decoded.data[..]), (0x10, &[10u8, 20][..]));
+    }
+}
@@ -0,0 +1,424 @@
+// src/backend/inference.rs - ONNX Segmentation Inference and Mask Overlay
+
+//! Optional AI stage: runs an ONNX segmentation model on every Nth displayed
+//! frame and blends the latest mask over every frame.
+//!
+//! Inference runs on its own thread so a slow model never holds up conversion;
+//! a frame arriving while the model is still busy is simply not analysed. The
+//! model takes one RGB image as `[1, 3, H, W]` float32 in `0..=1` and returns
+//! per-class scores `[1, C, H, W]` (argmax) or a single probability map
+//! `[1, 1, H, W]` (thresholded at 0.5). Running models needs the `onnx` feature;
+//! without it loading reports [`InferenceError::Unavailable`].
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::backend::pipeline::StageTiming;
+
+/// Analyse every Nth frame by default
+pub const DEFAULT_INFERENCE_INTERVAL: u32 = 5;
+
+/// Default mask overlay opacity
+pub const DEFAULT_OVERLAY_OPACITY: f32 = 0.4;
+
+/// Probability above which a single-channel output marks a pixel
+const PROBABILITY_THRESHOLD: f32 = 0.5;
+
+/// Overlay colors by class; class 0 is background and never drawn
+const CLASS_COLORS: [[u8; 3]; 8] = [
+    [0, 0, 0],
+    [239, 68, 68],
+    [34, 197, 94],
+    [59, 130, 246],
+    [234, 179, 8],
+    [168, 85, 247],
+    [6, 182, 212],
+    [249, 115, 22],
+];
+
+/// Errors that can occur while loading or running a model
+#[derive(Debug, Error)]
+pub enum InferenceError {
+    #[error("ONNX inference is not available in this build (enable the \"onnx\" feature)")]
+    Unavailable,
+
+    #[error("Model error: {0}")]
+    Model(String),
+
+    #[error("Unsupported model output shape {0:?}")]
+    OutputShape(Vec<i64>),
+
+    #[error("Failed to start inference thread: {0}")]
+    Thread(#[from] std::io::Error),
+}
+
+/// Segmentation stage configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferenceConfig {
+    /// ONNX model file
+    pub model: PathBuf,
+    /// Analyse every Nth frame (1 = every frame)
+    pub interval: u32,
+    /// Mask overlay opacity, 0.0 hides the overlay
+    pub opacity: f32,
+    /// Threads the model may use
+    pub threads: usize,
+}
+
+impl InferenceConfig {
+    /// Run `model` with the default interval and opacity
+    pub fn new(model: PathBuf) -> Self {
+        Self {
+            model,
+            interval: DEFAULT_INFERENCE_INTERVAL,
+            opacity: DEFAULT_OVERLAY_OPACITY,
+            threads: 2,
+        }
+    }
+}
+
+/// Per-pixel class labels produced by the model, at the model's resolution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentationMask {
+    pub width: usize,
+    pub height: usize,
+    pub classes: Vec<u8>,
+}
+
+impl SegmentationMask {
+    /// Build a mask from a model output tensor
+    ///
+    /// The last two dimensions are height and width; the remaining ones (batch of
+    /// one and channels) are flattened into the class scores.
+    pub fn from_output(shape: &[i64], data: &[f32]) -> Result<Self, InferenceError> {
+        let invalid = || InferenceError::OutputShape(shape.to_vec());
+        if shape.len() < 2 || shape.iter().any(|&dim| dim <= 0) {
+            return Err(invalid());
+        }
+
+        let height = shape[shape.len() - 2] as usize;
+        let width = shape[shape.len() - 1] as usize;
+        let plane = width * height;
+        let channels = shape[..shape.len() - 2].iter().product::<i64>() as usize;
+        if plane == 0 || data.len() != plane * channels {
+            return Err(invalid());
+        }
+
+        let classes = if channels == 1 {
+            data.iter().map(|&p| u8::from(p > PROBABILITY_THRESHOLD)).collect()
+        } else {
+            (0..plane)
+                .map(|i| {
+                    let mut best = 0;
+                    for class in 1..channels {
+                        if data[class * plane + i] > data[best * plane + i] {
+                            best = class;
+                        }
+                    }
+                    best.min(u8::MAX as usize) as u8
+                })
+                .collect()
+        };
+
+        Ok(Self { width, height, classes })
+    }
+
+    /// Check if any pixel belongs to a foreground class
+    pub fn is_empty(&self) -> bool {
+        self.classes.iter().all(|&class| class == 0)
+    }
+
+    /// Blend the mask over an RGBA frame of any size, scaling it to fit
+    pub fn overlay_rgba(&self, rgba_data: &mut [u8], width: usize, height: usize, opacity: f32) {
+        let alpha = (opacity.clamp(0.0, 1.0) * 256.0) as u32;
+        if alpha == 0 || width == 0 || height == 0 || rgba_data.len() < width * height * 4 {
+            return;
+        }
+
+        let columns: Vec<usize> = (0..width).map(|x| x * self.width / width).collect();
+        for (y, row) in rgba_data.chunks_exact_mut(width * 4).take(height).enumerate() {
+            let mask_row = &self.classes[(y * self.height / height) * self.width..][..self.width];
+            for (pixel, &column) in row.chunks_exact_mut(4).zip(&columns) {
+                let class = mask_row[column] as usize;
+                if class == 0 {
+                    continue;
+                }
+                let color = CLASS_COLORS[1 + (class - 1) % (CLASS_COLORS.len() - 1)];
+                for (channel, &tint) in pixel[..3].iter_mut().zip(&color) {
+                    *channel = ((*channel as u32 * (256 - alpha) + tint as u32 * alpha) >> 8) as u8;
+                }
+            }
+        }
+    }
+}
+
+/// Scale an RGBA frame to the model input and lay it out as planar RGB in `0..=1`
+pub fn preprocess_rgba(rgba_data: &[u8], width: usize, height: usize, input_width: usize, input_height: usize) -> Vec<f32> {
+    let plane = input_width * input_height;
+    let mut tensor = vec![0.0f32; plane * 3];
+    for y in 0..input_height {
+        let source_row = (y * height / input_height) * width;
+        for x in 0..input_width {
+            let source = (source_row + x * width / input_width) * 4;
+            for channel in 0..3 {
+                tensor[channel * plane + y * input_width + x] = rgba_data[source + channel] as f32 / 255.0;
+            }
+        }
+    }
+    tensor
+}
+
+/// Timing of the segmentation stage reported to the frontend
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferenceStatistics {
+    pub model: String,
+    pub interval: u32,
+    pub opacity: f32,
+    pub runs: u64,
+    pub last_ms: f64,
+    pub average_ms: f64,
+    /// Frames due for analysis that were passed over because the model was busy
+    pub skipped: u64,
+}
+
+#[cfg(feature = "onnx")]
+mod engine {
+    use std::path::Path;
+
+    use ort::session::builder::GraphOptimizationLevel;
+    use ort::session::Session;
+    use ort::value::{Tensor, ValueType};
+
+    use super::{preprocess_rgba, InferenceError, SegmentationMask};
+
+    /// Loaded ONNX model
+    pub struct InferenceEngine {
+        session: Session,
+        input_size: Option<(usize, usize)>,
+    }
+
+    fn model_error(e: ort::Error) -> InferenceError {
+        InferenceError::Model(e.to_string())
+    }
+
+    impl InferenceEngine {
+        /// Load a model file
+        pub fn load(path: &Path, threads: usize) -> Result<Self, InferenceError> {
+            let session = Session::builder()
+                .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Level3))
+                .and_then(|builder| builder.with_intra_threads(threads.max(1)))
+                .and_then(|builder| builder.commit_from_file(path))
+                .map_err(model_error)?;
+
+            // Fixed input sizes are honoured; dynamic ones take the frame as it is
+            let input_size = match session.inputs.first().map(|input| &input.input_type) {
+                Some(ValueType::Tensor { dimensions, .. }) if dimensions.len() == 4 && dimensions[2] > 0 && dimensions[3] > 0 => {
+                    Some((dimensions[3] as usize, dimensions[2] as usize))
+                }
+                Some(ValueType::Tensor { .. }) => None,
+                _ => return Err(InferenceError::Model("model input is not a tensor".to_string())),
+            };
+            Ok(Self { session, input_size })
+        }
+
+        /// Segment one RGBA frame
+        pub fn run(&self, rgba_data: &[u8], width: usize, height: usize) -> Result<SegmentationMask, InferenceError> {
+            let (input_width, input_height) = self.input_size.unwrap_or((width, height));
+            let tensor = preprocess_rgba(rgba_data, width, height, input_width, input_height);
+            let input = Tensor::from_array(([1usize, 3, input_height, input_width], tensor)).map_err(model_error)?;
+
+            let outputs = self.session.run(ort::inputs![input].map_err(model_error)?).map_err(model_error)?;
+            let (shape, data) = outputs[0].try_extract_raw_tensor::<f32>().map_err(model_error)?;
+            SegmentationMask::from_output(&shape, data)
+        }
+    }
+}
+
+#[cfg(not(feature = "onnx"))]
+mod engine {
+    use std::path::Path;
+
+    use super::{InferenceError, SegmentationMask};
+
+    /// Stand-in when the `onnx` feature is disabled
+    pub struct InferenceEngine;
+
+    impl InferenceEngine {
+        pub fn load(_path: &Path, _threads: usize) -> Result<Self, InferenceError> {
+            Err(InferenceError::Unavailable)
+        }
+
+        pub fn run(&self, _rgba_data: &[u8], _width: usize, _height: usize) -> Result<SegmentationMask, InferenceError> {
+            Err(InferenceError::Unavailable)
+        }
+    }
+}
+
+pub use engine::InferenceEngine;
+
+/// A frame copied for the inference thread
+struct InferenceJob {
+    rgba: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+/// Runs the model beside the frame processor and blends its latest mask over frames
+pub struct SegmentationStage {
+    model: String,
+    jobs: std::sync::mpsc::SyncSender<InferenceJob>,
+    mask: Arc<parking_lot::Mutex<Option<SegmentationMask>>>,
+    timing: Arc<parking_lot::Mutex<StageTiming>>,
+    interval: AtomicU32,
+    opacity_bits: AtomicU32,
+    frames_seen: AtomicU64,
+    skipped: AtomicU64,
+}
+
+impl SegmentationStage {
+    /// Load the model and start the inference thread
+    pub fn start(config: &InferenceConfig) -> Result<Self, InferenceError> {
+        let engine = InferenceEngine::load(&config.model, config.threads)?;
+        let model = model_name(&config.model);
+        info!("🧠 Segmentation model {} loaded, analysing every {} frame(s)", model, config.interval.max(1));
+
+        // One frame in flight at most, so the model always sees a recent frame
+        let (jobs, job_rx) = std::sync::mpsc::sync_channel::<InferenceJob>(1);
+        let mask = Arc::new(parking_lot::Mutex::new(None));
+        let timing = Arc::new(parking_lot::Mutex::new(StageTiming::default()));
+
+        let thread_mask = Arc::clone(&mask);
+        let thread_timing = Arc::clone(&timing);
+        std::thread::Builder::new()
+            .name("mivi-inference".to_string())
+            .spawn(move || {
+                // Ends when the stage, and with it the sender, is dropped
+                for job in job_rx {
+                    let start = Instant::now();
+                    match engine.run(&job.rgba, job.width, job.height) {
+                        Ok(result) => {
+                            thread_timing.lock().record(start.elapsed());
+                            *thread_mask.lock() = Some(result);
+                        }
+                        Err(e) => warn!("⚠️ Segmentation failed: {}", e),
+                    }
+                }
+                debug!("🧠 Inference thread stopped");
+            })?;
+
+        let stage = Self {
+            model,
+            jobs,
+            mask,
+            timing,
+            interval: AtomicU32::new(0),
+            opacity_bits: AtomicU32::new(0),
+            frames_seen: AtomicU64::new(0),
+            skipped: AtomicU64::new(0),
+        };
+        stage.set_interval(config.interval);
+        stage.set_opacity(config.opacity);
+        Ok(stage)
+    }
+
+    /// Analyse every Nth frame
+    pub fn set_interval(&self, interval: u32) {
+        self.interval.store(interval.max(1), Ordering::Relaxed);
+    }
+
+    /// Set the overlay opacity (0.0 hides the mask, inference keeps running)
+    pub fn set_opacity(&self, opacity: f32) {
+        self.opacity_bits.store(opacity.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the overlay opacity
+    pub fn opacity(&self) -> f32 {
+        f32::from_bits(self.opacity_bits.load(Ordering::Relaxed))
+    }
+
+    /// Hand the frame to the model if it is due, then blend the latest mask over it
+    ///
+    /// The model sees the frame before the overlay is drawn.
+    pub fn process_rgba(&self, rgba_data: &mut [u8], width: usize, height: usize) {
+        let frame = self.frames_seen.fetch_add(1, Ordering::Relaxed);
+        if frame % self.interval.load(Ordering::Relaxed) as u64 == 0 {
+            let job = InferenceJob { rgba: rgba_data.to_vec(), width, height };
+            if self.jobs.try_send(job).is_err() {
+                self.skipped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        if let Some(mask) = self.mask.lock().as_ref() {
+            mask.overlay_rgba(rgba_data, width, height, self.opacity());
+        }
+    }
+
+    /// Forget the last mask when the image geometry or stream changes
+    pub fn clear_mask(&self) {
+        *self.mask.lock() = None;
+    }
+
+    /// Get timing statistics
+    pub fn statistics(&self) -> InferenceStatistics {
+        let timing = *self.timing.lock();
+        InferenceStatistics {
+            model: self.model.clone(),
+            interval: self.interval.load(Ordering::Relaxed),
+            opacity: self.opacity(),
+            runs: timing.runs,
+            last_ms: timing.last.as_secs_f64() * 1000.0,
+            average_ms: timing.average_ms(),
+            skipped: self.skipped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn model_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_from_output() {
+        // Two classes over a 2x2 image: class 1 wins in the right column
+        let scores = [0.9, 0.1, 0.8, 0.3, 0.1, 0.7, 0.2, 0.6];
+        let mask = SegmentationMask::from_output(&[1, 2, 2, 2], &scores).unwrap();
+        assert_eq!(mask.classes, vec![0, 1, 0, 1]);
+
+        let mask = SegmentationMask::from_output(&[1, 1, 1, 3], &[0.2, 0.6, 0.9]).unwrap();
+        assert_eq!(mask.classes, vec![0, 1, 1]);
+        assert!(!mask.is_empty());
+
+        assert!(SegmentationMask::from_output(&[1, 2, 2, 2], &scores[..4]).is_err());
+        assert!(SegmentationMask::from_output(&[1, -1, 2], &scores).is_err());
+    }
+
+    #[test]
+    fn test_overlay_scales_mask() {
+        let mask = SegmentationMask { width: 2, height: 1, classes: vec![0, 1] };
+        let mut rgba = vec![100u8; 4 * 4];
+        mask.overlay_rgba(&mut rgba, 4, 1, 1.0);
+
+        // Left half untouched, right half painted in the class color
+        assert_eq!(&rgba[..8], &[100; 8]);
+        assert_eq!(&rgba[8..11], &CLASS_COLORS[1]);
+        assert_eq!(rgba[11], 100);
+
+        let mut rgba = vec![100u8; 4 * 4];
+        mask.overlay_rgba(&mut rgba, 4, 1, 0.0);
+        assert_eq!(rgba, vec![100u8; 16]);
+
+        let tensor = preprocess_rgba(&[255, 0, 51, 255], 1, 1, 2, 1);
+        assert_eq!(tensor, vec![1.0, 1.0, 0.0, 0.0, 0.2, 0.2]);
+    }
+}
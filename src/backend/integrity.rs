@@ -0,0 +1,116 @@
+// src/backend/integrity.rs - Frame Integrity Verification via Producer Checksums
+
+use crate::backend::types::FrameHeader;
+
+/// Header flag: the low 32 bits of `padding[0]` carry a CRC32 (IEEE) of the frame data
+///
+/// Bits 0-3 hold the probe orientation (see `Orientation::from_header_flags`).
+pub const FLAG_CRC32: u32 = 0x0000_4000;
+
+/// Lookup table for the reflected IEEE polynomial
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// CRC32 (IEEE 802.3, as used by zlib and PNG) of a byte slice
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Result of checking frame data against the producer checksum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrity {
+    /// Producer did not send a checksum
+    Unchecked,
+    Valid,
+    /// Data changed between the producer writing it and the consumer copying it
+    Corrupted { expected: u32, actual: u32 },
+}
+
+impl Integrity {
+    /// Verify frame data against the checksum announced in its header
+    pub fn verify(header: &FrameHeader, data: &[u8]) -> Self {
+        if header.flags & FLAG_CRC32 == 0 {
+            return Self::Unchecked;
+        }
+
+        let expected = header.padding[0] as u32;
+        let actual = crc32(data);
+        if actual == expected {
+            Self::Valid
+        } else {
+            Self::Corrupted { expected, actual }
+        }
+    }
+
+    /// Check if the frame failed verification
+    pub fn is_corrupted(&self) -> bool {
+        matches!(self, Self::Corrupted { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_verification() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(&[]), 0);
+
+        let data = vec![7u8; 64];
+        let mut header = FrameHeader::for_test(8, 8, 1, 0x10);
+        assert_eq!(Integrity::verify(&header, &data), Integrity::Unchecked);
+
+        header.flags |= FLAG_CRC32;
+        header.padding[0] = crc32(&data) as u64;
+        assert_eq!(Integrity::verify(&header, &data), Integrity::Valid);
+
+        // A byte overwritten by the producer mid-copy no longer matches
+        let mut torn = data.clone();
+        torn[40] = 0;
+        assert!(Integrity::verify(&header, &torn).is_corrupted());
+    }
+
+    #[test]
+    fn test_header_flags_disjoint() {
+        use crate::backend::{burst_capture, deinterlace, stride, types, Orientation};
+
+        let flags = [
+            ("rotation", Orientation::FLAG_ROTATION_MASK),
+            ("flip horizontal", Orientation::FLAG_FLIP_HORIZONTAL),
+            ("flip vertical", Orientation::FLAG_FLIP_VERTICAL),
+            ("trigger", burst_capture::FLAG_TRIGGER),
+            ("row stride", stride::FLAG_ROW_STRIDE),
+            ("big endian", types::FLAG_BIG_ENDIAN),
+            ("interlaced", deinterlace::FLAG_INTERLACED),
+            ("single field", deinterlace::FLAG_SINGLE_FIELD),
+            ("bottom field", deinterlace::FLAG_BOTTOM_FIELD),
+            ("crc32", FLAG_CRC32),
+        ];
+        for (i, (a, a_bits)) in flags.iter().enumerate() {
+            for (b, b_bits) in &flags[i + 1..] {
+                assert_eq!(a_bits & b_bits, 0, "{a} and {b} share header flag bits");
+            }
+        }
+
+        // A checksummed frame is not a rotated one
+        assert!(Orientation::from_header_flags(FLAG_CRC32).is_identity());
+    }
+}
@@ -0,0 +1,101 @@
+// src/backend/memory.rs - Memory Locking and Huge Page Advice for Frame Buffers
+
+use memmap2::MmapMut;
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// What to pin in RAM to avoid page faults on the frame path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemoryLock {
+    #[default]
+    None,
+    /// Lock only the shared memory mapping (mlock)
+    Mapping,
+    /// Lock the whole process, including future allocations (mlockall)
+    All,
+}
+
+/// Memory residency options for the frame path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryConfig {
+    pub lock: MemoryLock,
+    /// Advise transparent huge pages on the shared memory mapping
+    pub huge_pages: bool,
+}
+
+/// What was actually applied to a mapping, for statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MappingMemory {
+    pub locked: bool,
+    pub huge_pages: bool,
+}
+
+impl MemoryConfig {
+    /// Lock the whole process in memory if requested, returning whether it is locked
+    pub fn lock_process(&self) -> Result<bool, MemoryError> {
+        if self.lock != MemoryLock::All {
+            return Ok(false);
+        }
+
+        lock_all()?;
+        info!("🔒 Process memory locked (mlockall)");
+        Ok(true)
+    }
+
+    /// Apply locking and huge page advice to a fresh mapping
+    ///
+    /// Failures are reported and the mapping is used as-is, since an unlocked
+    /// mapping still works, only with occasional page-fault latency.
+    pub fn prepare_mapping(&self, mmap: &MmapMut) -> MappingMemory {
+        let mut applied = MappingMemory::default();
+
+        if self.huge_pages {
+            match advise_huge_pages(mmap) {
+                Ok(()) => applied.huge_pages = true,
+                Err(e) => warn!("⚠️ Huge page advice not applied to the mapping: {}", e),
+            }
+        }
+
+        match self.lock {
+            MemoryLock::None => {}
+            // mlockall with MCL_FUTURE already covers new mappings
+            MemoryLock::All => applied.locked = true,
+            MemoryLock::Mapping => match mmap.lock() {
+                Ok(()) => applied.locked = true,
+                Err(e) => warn!("⚠️ Could not lock {} bytes of shared memory (check RLIMIT_MEMLOCK): {}", mmap.len(), e),
+            },
+        }
+
+        applied
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn advise_huge_pages(mmap: &MmapMut) -> std::io::Result<()> {
+    mmap.advise(memmap2::Advice::HugePage)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn advise_huge_pages(_mmap: &MmapMut) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "transparent huge pages are Linux-only"))
+}
+
+#[cfg(unix)]
+fn lock_all() -> Result<(), MemoryError> {
+    if unsafe { libc::mlockall(libc::MCL_CURRENT | libc::MCL_FUTURE) } != 0 {
+        return Err(MemoryError::LockAll(std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn lock_all() -> Result<(), MemoryError> {
+    Err(MemoryError::LockAll(std::io::Error::new(std::io::ErrorKind::Unsupported, "mlockall is not available")))
+}
+
+/// Errors locking process memory
+#[derive(Debug, Error)]
+pub enum MemoryError {
+    #[error("Failed to lock process memory (needs CAP_IPC_LOCK or a higher RLIMIT_MEMLOCK): {0}")]
+    LockAll(std::io::Error),
+}
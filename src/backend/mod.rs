@@ -1,24 +1,144 @@
 // src/backend/mod.rs - Backend Module for Medical Frame Streaming
 
 pub mod shared_memory;
+pub mod shared_memory_writer;
+pub mod frame_source;
+pub mod tcp_source;
+pub mod igtl;
+#[cfg(feature = "rtsp")]
+pub mod rtsp_source;
+#[cfg(feature = "gstreamer")]
+pub mod gstreamer_source;
+#[cfg(feature = "v4l2")]
+pub mod v4l2_source;
+#[cfg(feature = "ndi")]
+pub mod ndi_source;
+#[cfg(feature = "zmq")]
+pub mod zmq_source;
 pub mod frame_processor;
+pub mod inference;
+pub mod plugins;
+pub mod wasm_filter;
+pub mod pixel_pool;
+pub mod gpu_frame;
+pub mod adjustments;
+pub mod filters;
+pub mod pipeline;
+pub mod transform;
+pub mod resample;
+pub mod roi;
+pub mod calibration;
+pub mod integrity;
+pub mod stride;
+pub mod planar;
+pub mod packed12;
+pub mod palette;
+pub mod color_space;
+pub mod yuv;
+pub mod deinterlace;
+pub mod catch_up;
+pub mod scheduling;
+pub mod memory;
+pub mod numa;
+pub mod protocol;
+pub mod producer_watch;
+pub mod reconnect;
+pub mod registry;
+pub mod fd_passing;
+pub mod frame_dumper;
+pub mod frame_delivery;
+pub mod frame_sink;
+pub mod sinks;
+pub mod burst_capture;
+pub mod watchdog;
+pub mod health;
+pub mod detections;
 pub mod connection_manager;
 pub mod types;
 
 pub use shared_memory::SharedMemoryReader;
+pub use shared_memory_writer::{SharedMemoryWriter, WriterConfig};
+pub use frame_source::FrameSource;
+pub use tcp_source::TcpFrameReceiver;
+pub use igtl::{IgtlBroadcaster, IgtlFrameSource};
+#[cfg(feature = "rtsp")]
+pub use rtsp_source::RtspFrameSource;
+#[cfg(feature = "gstreamer")]
+pub use gstreamer_source::GStreamerFrameSource;
+#[cfg(feature = "v4l2")]
+pub use v4l2_source::V4l2FrameSource;
+#[cfg(feature = "ndi")]
+pub use ndi_source::NdiFrameSource;
+#[cfg(feature = "zmq")]
+pub use zmq_source::ZmqFrameSource;
 pub use frame_processor::FrameProcessor;
+pub use inference::{InferenceConfig, InferenceStatistics};
+pub use plugins::{FrameFilter, PluginError, PluginManifest, PluginRuntime};
+pub use pixel_pool::{PixelBufferPool, RgbaBuffer};
+pub use gpu_frame::{GpuFrame, GpuLayout};
 pub use connection_manager::ConnectionManager;
+pub use adjustments::{Colormap, Dithering, Gamma, WindowLevel};
+pub use filters::ClaheParams;
+pub use pipeline::{StageKind, StageStatistics};
+pub use transform::{CropRect, Orientation, Rotation};
+pub use resample::{ResampleQuality, Zoom};
+pub use roi::{Roi, RoiShape, RoiStatistics};
+pub use calibration::PixelSpacing;
+pub use frame_dumper::{DumpConfig, FrameDumper};
+pub use frame_delivery::{FrameDropPolicy, FrameReceiver, FrameSender};
+pub use frame_sink::{FrameSink, SinkHub, SinkStatistics};
+pub use sinks::{AutoRecorderSink, ForwarderSink, RecorderSink, RecordingPolicy, SnapshotSink};
+pub use burst_capture::{BurstCaptureSink, BurstConfig};
+pub use color_space::{ColorManagement, ColorSpace, DisplayCalibration};
+pub use yuv::{YuvEncoding, YuvMatrix, YuvRange};
+pub use deinterlace::Deinterlace;
+pub use watchdog::{PipelineHeartbeat, RecoveryReport, StallReason};
+pub use health::{HealthMonitor, HealthProbe, HealthReport, HealthServer};
+pub use detections::{Detection, DetectionServer, DetectionStore, DisplayGeometry, FrameDetections};
+pub use integrity::Integrity;
+pub use catch_up::AdaptiveCatchUp;
+pub use scheduling::{SchedulingError, ThreadScheduling};
+pub use memory::{MemoryConfig, MemoryLock};
+pub use numa::{NumaStatistics, NumaTopology};
+pub use protocol::{Handshake, ProtocolVersion};
+pub use producer_watch::{ProducerWait, ProducerWatch};
+pub use reconnect::ReconnectPolicy;
+pub use registry::{Registry, StreamAnnouncement};
 pub use types::*;
 
 use std::sync::Arc;
+use futures::stream::{self, Stream};
 use tokio::sync::{broadcast, mpsc, RwLock};
-use tracing::{info, warn, error, debug};
+use tracing::{info, info_span, warn, error, debug, Instrument};
 use crate::backend::connection_manager::ConnectionManagerError;
+use crate::perf::PerformanceMonitor;
+use crate::encryption::RecordingCipher;
+use crate::redaction::AnonymizationProfile;
+use crate::backend::inference::SegmentationStage;
+
+/// Frames the performance monitor averages over
+const PERF_MONITOR_SAMPLES: usize = 120;
+
+/// Control events buffered per receiver by default
+pub const DEFAULT_EVENT_CAPACITY: usize = 64;
+
+/// Raw frames buffered per `raw_frame_stream` before a slow reader skips ahead
+pub const RAW_FRAME_CAPACITY: usize = 8;
 
 /// Backend service that manages all frame streaming operations
 pub struct MedicalFrameBackend {
     connection_manager: Arc<ConnectionManager>,
     frame_processor: Arc<FrameProcessor>,
+    frame_dumper: Option<Arc<FrameDumper>>,
+    igtl_broadcast: Option<String>,
+    scheduling: ThreadScheduling,
+    watchdog_timeout: Option<std::time::Duration>,
+    heartbeat: Arc<PipelineHeartbeat>,
+    health: Arc<HealthMonitor>,
+    health_listen: Option<String>,
+    detections: Arc<DetectionStore>,
+    detections_listen: Option<String>,
+    performance: Option<Arc<parking_lot::Mutex<PerformanceMonitor>>>,
     
     // Communication channels
     command_tx: mpsc::UnboundedSender<BackendCommand>,
@@ -27,6 +147,21 @@ pub struct MedicalFrameBackend {
     // Event broadcasting
     event_tx: broadcast::Sender<BackendEvent>,
     
+    // Latest processed frame; the drop policy decides what happens when the reader falls behind
+    frame_tx: Arc<FrameSender>,
+    
+    // Raw frames as read from the source, only sent while someone streams them
+    raw_tx: broadcast::Sender<RawFrame>,
+    
+    // Recorders, forwarders and other consumers, each with its own queue
+    sinks: Arc<SinkHub>,
+    record_to: Option<std::path::PathBuf>,
+    forward_listen: Option<String>,
+    snapshot: Option<(std::path::PathBuf, std::time::Duration)>,
+    auto_record: Option<(std::path::PathBuf, std::time::Duration)>,
+    burst_capture: Option<BurstConfig>,
+    recording_policy: RecordingPolicy,
+    
     // State management
     current_state: Arc<RwLock<BackendState>>,
 }
@@ -35,33 +170,112 @@ impl MedicalFrameBackend {
     /// Create a new backend service
     pub fn new(config: BackendConfig) -> Self {
         let (command_tx, command_rx) = mpsc::unbounded_channel();
-        let (event_tx, _) = broadcast::channel(1000);
+        let (event_tx, _) = broadcast::channel(config.event_capacity.max(1));
+        let frame_tx = Arc::new(FrameSender::new());
+        let (raw_tx, _) = broadcast::channel(RAW_FRAME_CAPACITY);
+
+        // Seed the state with the device profile targets
+        let mut state = BackendState::default();
+        state.apply_config(&config);
+
+        let frame_dumper = config.dump.clone().map(|dump| {
+            info!("💾 Dumping the first {} frames to {}{}", dump.max_frames, dump.dir.display(),
+                  if dump.png { " (with PNG)" } else { "" });
+            Arc::new(FrameDumper::new(dump))
+        });
+
+        let igtl_broadcast = config.igtl_broadcast.clone();
+        let health_listen = config.health_listen.clone();
+        let detections_listen = config.detections_listen.clone();
+        let record_to = config.record_to.clone();
+        let forward_listen = config.forward_listen.clone();
+        let snapshot = config.snapshot_dir.clone().map(|dir| (dir, config.snapshot_interval));
+        let auto_record = config.auto_record_dir.clone().map(|dir| (dir, config.auto_record_idle));
+        let burst_capture = config.burst_capture.clone();
+        let recording_policy = RecordingPolicy {
+            anonymization: config.export_anonymization,
+            encryption: config.recording_encryption.clone(),
+        };
+        let health = Arc::new(HealthMonitor::new(config.frame_timeout, health::DEFAULT_UI_TIMEOUT));
+        let scheduling = config.scheduling.clone();
+        let gpu_upload = config.gpu_upload;
+        let display_calibration = config.display_calibration;
+        let deep_color = config.deep_color;
+        let dithering = config.dithering;
+        let deinterlace = config.deinterlace;
+        let inference = config.inference.clone();
+        let plugin_dir = config.plugin_dir.clone();
+        let watchdog_timeout = config.watchdog_timeout;
+        let conversion_threads = config.conversion_threads.unwrap_or_else(frame_processor::default_worker_count);
+        let performance = config.perf_monitor.then(|| Arc::new(parking_lot::Mutex::new(PerformanceMonitor::new(PERF_MONITOR_SAMPLES))));
+
+        if let Err(e) = config.memory.lock_process() {
+            warn!("⚠️ {}", e);
+        }
 
         // Convert BackendConfig to ConnectionConfig
         let connection_config = Self::convert_config(config);
 
         let connection_manager = Arc::new(ConnectionManager::new(connection_config));
-        let frame_processor = Arc::new(FrameProcessor::new());
+        let mut frame_processor = FrameProcessor::with_workers(conversion_threads, &scheduling);
+        if let Some(inference) = &inference {
+            match SegmentationStage::start(inference) {
+                Ok(segmentation) => frame_processor = frame_processor.with_segmentation(segmentation),
+                Err(e) => warn!("⚠️ Segmentation disabled: {}", e),
+            }
+        }
+        if let Some(dir) = &plugin_dir {
+            match plugins::load_dir(dir) {
+                Ok(filters) if !filters.is_empty() => frame_processor = frame_processor.with_plugins(filters),
+                Ok(_) => warn!("⚠️ No plugins loaded from {}", dir.display()),
+                Err(e) => warn!("⚠️ Plugins disabled: {}", e),
+            }
+        }
+        let frame_processor = Arc::new(frame_processor);
+        frame_processor.set_gpu_upload(gpu_upload);
+        frame_processor.set_display_calibration(display_calibration);
+        frame_processor.set_deep_color(deep_color);
+        frame_processor.set_dithering(dithering);
+        frame_processor.set_deinterlace(deinterlace);
 
-        let current_state = Arc::new(RwLock::new(BackendState::default()));
+        let current_state = Arc::new(RwLock::new(state));
 
         Self {
             connection_manager,
             frame_processor,
+            frame_dumper,
+            igtl_broadcast,
+            scheduling,
+            watchdog_timeout,
+            heartbeat: Arc::new(PipelineHeartbeat::default()),
+            health,
+            health_listen,
+            detections: Arc::new(DetectionStore::default()),
+            detections_listen,
+            performance,
             command_tx,
             command_rx: Arc::new(RwLock::new(Some(command_rx))),
             event_tx,
+            frame_tx,
+            raw_tx,
+            sinks: Arc::new(SinkHub::default()),
+            record_to,
+            forward_listen,
+            snapshot,
+            auto_record,
+            burst_capture,
+            recording_policy,
             current_state,
         }
     }
 
     fn convert_config(config: BackendConfig) -> ConnectionConfig {
         let connection_config = ConnectionConfig {
-            reconnect_delay: config.reconnect_delay,
-            max_reconnect_attempts: 10,
-            frame_timeout: std::time::Duration::from_secs(5),
+            reconnect: config.reconnect,
+            frame_timeout: config.frame_timeout,
             buffer_size: 1024 * 1024 * 50,
             verbose_logging: config.verbose,
+            memory: config.memory,
         };
         connection_config
     }
@@ -72,19 +286,130 @@ impl MedicalFrameBackend {
     }
     
     /// Get an event receiver for frontend communication
+    ///
+    /// Carries connection and statistics events only; frames arrive on `get_frame_receiver`.
     pub fn get_event_receiver(&self) -> broadcast::Receiver<BackendEvent> {
         self.event_tx.subscribe()
     }
     
+    /// Get a receiver for the latest processed frame
+    ///
+    /// Holds one frame: a frame arriving before the last one was taken is handled
+    /// by the configured `FrameDropPolicy`, so a slow reader never queues stale frames.
+    pub fn get_frame_receiver(&self) -> FrameReceiver {
+        self.frame_tx.subscribe()
+    }
+    
+    /// Feed frames and events to `sink` on a background task until it stops
+    ///
+    /// The receivers are subscribed before returning, so nothing sent afterwards is missed.
+    pub fn spawn_sink<S: FrameSink + 'static>(&self, mut sink: S) -> tokio::task::JoinHandle<()> {
+        let events = self.get_event_receiver();
+        let frames = self.get_frame_receiver();
+        tokio::spawn(async move { frame_sink::run_sink(events, frames, &mut sink).await })
+    }
+
+    /// Run `sink` alongside the frontend with its own queue of `queue` frames
+    ///
+    /// Unlike `spawn_sink`, the sink sees every frame until its queue is full,
+    /// and falling behind drops frames for this sink only. Attached sinks are
+    /// flushed and detached when the backend stops.
+    pub fn attach_sink<S: FrameSink + 'static>(&self, name: &str, sink: S, queue: usize) {
+        self.sinks.attach(name, sink, queue, self.get_event_receiver());
+    }
+
+    /// Delivery counters of the attached sinks
+    pub fn sink_statistics(&self) -> Vec<(String, SinkStatistics)> {
+        self.sinks.statistics()
+    }
+
+    /// Stream processed frames as they are delivered
+    ///
+    /// Backed by a frame receiver, so a slow consumer only ever sees the newest
+    /// frame and the configured `FrameDropPolicy` applies. Ends when the backend is dropped.
+    pub fn frame_stream(&self) -> impl Stream<Item = ProcessedFrame> + Send + 'static {
+        stream::unfold(self.get_frame_receiver(), |mut receiver| async move {
+            loop {
+                receiver.changed().await.ok()?;
+                // A cleared slot (disconnect) is not a frame
+                if let Some(frame) = receiver.take_latest() {
+                    return Some((frame, receiver));
+                }
+            }
+        })
+    }
+
+    /// Stream raw frames as read from the source, before conversion
+    ///
+    /// Frames skipped by the display frame-rate cap are not included. A consumer
+    /// more than `RAW_FRAME_CAPACITY` frames behind skips ahead to the oldest
+    /// buffered frame. Ends when the backend is dropped.
+    pub fn raw_frame_stream(&self) -> impl Stream<Item = RawFrame> + Send + 'static {
+        stream::unfold(self.raw_tx.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(frame) => return Some((frame, receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug!("Raw frame stream lagged, skipped {} frames", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+    
+    /// Get the pool the frontend returns displayed frame buffers to
+    pub fn pixel_pool(&self) -> Arc<PixelBufferPool> {
+        self.frame_processor.pixel_pool()
+    }
+
+    /// Get the performance monitor, if enabled in the configuration
+    ///
+    /// The backend records conversion times; the frontend adds render times.
+    pub fn performance_monitor(&self) -> Option<Arc<parking_lot::Mutex<PerformanceMonitor>>> {
+        self.performance.clone()
+    }
+    
+    /// Get the health monitor the frame loop reports to
+    ///
+    /// The frontend feeds it UI ticks so the health endpoint can tell a hung UI apart.
+    pub fn health_monitor(&self) -> Arc<HealthMonitor> {
+        Arc::clone(&self.health)
+    }
+    
+    /// Get the detections pushed by external analysis, by frame id
+    pub fn detections(&self) -> Arc<DetectionStore> {
+        Arc::clone(&self.detections)
+    }
+
+    /// Attach detections to a frame for the frontend to draw over it
+    ///
+    /// In-process counterpart of the `detections_listen` endpoint. Coordinates are
+    /// normalized to the full sensor frame; the frontend follows crop, orientation and zoom.
+    pub fn push_detections(&self, detections: FrameDetections) {
+        detections::publish(&self.detections, &self.event_tx, detections);
+    }
+    
     /// Get current backend state
     pub async fn get_state(&self) -> BackendState {
         self.current_state.read().await.clone()
     }
     
     /// Start the backend service
-    pub async fn start(&self) -> Result<(), BackendError> {
+    ///
+    /// The returned handle stops the backend with `BackendCommand::Shutdown` and
+    /// joins its tasks; once it has stopped, `start` can be called again.
+    pub async fn start(&self) -> Result<BackendHandle, BackendError> {
         info!("🚀 Starting MiVi Medical Frame Backend");
         
+        // Pinned or real-time frame processing needs its own OS thread, since tokio workers are shared.
+        // It is created first: once the loop owns the command receiver, a failure could not hand it back.
+        let frame_thread = if self.scheduling.is_default() {
+            None
+        } else {
+            Some(Self::spawn_frame_thread(self.scheduling.clone())?)
+        };
+        
         // Take the command receiver
         let mut command_rx = {
             let mut rx_guard = self.command_rx.write().await;
@@ -94,27 +419,150 @@ impl MedicalFrameBackend {
         // Clone necessary components for the async task
         let connection_manager = Arc::clone(&self.connection_manager);
         let frame_processor = Arc::clone(&self.frame_processor);
+        let frame_dumper = self.frame_dumper.clone();
+        let igtl_broadcaster = match &self.igtl_broadcast {
+            Some(address) => match IgtlBroadcaster::bind(address).await {
+                Ok(broadcaster) => Some(Arc::new(broadcaster)),
+                Err(e) => {
+                    // Hand the receiver back so a later start can succeed
+                    *self.command_rx.write().await = Some(command_rx);
+                    return Err(e.into());
+                }
+            },
+            None => None,
+        };
+        let health_server = match &self.health_listen {
+            Some(address) => match HealthServer::bind(address, Arc::clone(&self.health)).await {
+                Ok(server) => Some(server),
+                Err(e) => {
+                    *self.command_rx.write().await = Some(command_rx);
+                    return Err(e.into());
+                }
+            },
+            None => None,
+        };
+        let detection_server = match &self.detections_listen {
+            Some(address) => match DetectionServer::bind(address, Arc::clone(&self.detections), self.event_tx.clone()).await {
+                Ok(server) => Some(server),
+                Err(e) => {
+                    *self.command_rx.write().await = Some(command_rx);
+                    return Err(e.into());
+                }
+            },
+            None => None,
+        };
+        let forwarder = match &self.forward_listen {
+            Some(address) => match ForwarderSink::bind(address, self.recording_policy.anonymization).await {
+                Ok(forwarder) => Some(forwarder),
+                Err(e) => {
+                    *self.command_rx.write().await = Some(command_rx);
+                    return Err(e.into());
+                }
+            },
+            None => None,
+        };
+        if let Some(path) = &self.record_to {
+            match RecorderSink::create(path, &self.recording_policy).await {
+                Ok(recorder) => self.attach_sink("recorder", recorder, frame_sink::DEFAULT_SINK_QUEUE),
+                Err(e) => {
+                    *self.command_rx.write().await = Some(command_rx);
+                    return Err(e.into());
+                }
+            }
+        }
+        if let Some(forwarder) = forwarder {
+            self.attach_sink("forwarder", forwarder, frame_sink::DEFAULT_SINK_QUEUE);
+        }
+        if let Some((dir, interval)) = &self.snapshot {
+            self.attach_sink("snapshots", SnapshotSink::new(dir, *interval, self.recording_policy.encryption.clone()), 1);
+        }
+        if let Some((dir, idle_timeout)) = &self.auto_record {
+            self.attach_sink("auto-recorder", AutoRecorderSink::new(dir, *idle_timeout, &self.recording_policy), frame_sink::DEFAULT_SINK_QUEUE);
+        }
+        if let Some(burst) = &self.burst_capture {
+            self.attach_sink("burst-capture", BurstCaptureSink::new(burst, &self.recording_policy), frame_sink::DEFAULT_SINK_QUEUE);
+        }
+        let health = Arc::clone(&self.health);
         let event_tx = self.event_tx.clone();
+        let frame_tx = Arc::clone(&self.frame_tx);
+        let raw_tx = self.raw_tx.clone();
+        let sinks = Arc::clone(&self.sinks);
         let current_state = Arc::clone(&self.current_state);
+        let heartbeat = Arc::clone(&self.heartbeat);
+        let performance = self.performance.clone();
+        let command_slot = Arc::clone(&self.command_rx);
         
-        // Start the main backend loop
-        tokio::spawn(async move {
-            let mut frame_timer = tokio::time::interval(std::time::Duration::from_millis(16)); // ~60 FPS
-            let mut stats_timer = tokio::time::interval(std::time::Duration::from_secs(1));
+        // Restart the connection if the loop below stops reading while the producer keeps writing
+        let watchdog = self.watchdog_timeout.map(|timeout| {
+            tokio::spawn(Self::run_watchdog(
+                timeout,
+                Arc::clone(&heartbeat),
+                Arc::clone(&connection_manager),
+                Arc::clone(&frame_processor),
+                event_tx.clone(),
+                Arc::clone(&current_state),
+            ))
+        });
+        let watchdog_abort = watchdog.as_ref().map(|task| task.abort_handle());
+        
+        // Main backend loop
+        let backend_loop = async move {
+            let (mut poll_interval, mut stats_interval) = {
+                let state = current_state.read().await;
+                (state.poll_interval(), state.stats_interval)
+            };
+            let mut frame_timer = tokio::time::interval(poll_interval);
+            let mut stats_timer = tokio::time::interval(stats_interval);
+            let mut producer_wait: Option<ProducerWait> = None;
             
             loop {
+                // While the connection is down, watch for the producer instead of polling blindly
+                let (awaiting, reconnect) = {
+                    let state = current_state.read().await;
+                    (state.connection_status.awaits_producer(), state.reconnect)
+                };
+                match (awaiting, producer_wait.is_some()) {
+                    (true, false) => {
+                        let name = connection_manager.target_name().await.unwrap_or_default();
+                        producer_wait = Some(ProducerWait::new(&name, reconnect));
+                    }
+                    (false, true) => producer_wait = None,
+                    _ => {}
+                }
+
                 tokio::select! {
                     // Handle commands from frontend
                     Some(command) = command_rx.recv() => {
+                        let shutdown = matches!(command, BackendCommand::Shutdown);
                         if let Err(e) = Self::handle_command(
                             command,
                             &connection_manager,
                             &frame_processor,
                             &event_tx,
+                            &frame_tx,
                             &current_state,
                         ).await {
                             error!("Command handling error: {}", e);
                         }
+                        if shutdown {
+                            break;
+                        }
+
+                        // Follow the polling rate of the active device profile and configuration
+                        let (interval, stats) = {
+                            let state = current_state.read().await;
+                            (state.poll_interval(), state.stats_interval)
+                        };
+                        if interval != poll_interval {
+                            debug!("⏱️ Frame poll interval: {:?}", interval);
+                            poll_interval = interval;
+                            frame_timer = tokio::time::interval(poll_interval);
+                        }
+                        if stats != stats_interval {
+                            debug!("⏱️ Statistics interval: {:?}", stats);
+                            stats_interval = stats;
+                            stats_timer = tokio::time::interval(stats_interval);
+                        }
                     }
                     
                     // Process frames at regular intervals
@@ -122,6 +570,14 @@ impl MedicalFrameBackend {
                         if let Err(e) = Self::process_frame_cycle(
                             &connection_manager,
                             &frame_processor,
+                            frame_dumper.as_ref(),
+                            igtl_broadcaster.as_ref(),
+                            &heartbeat,
+                            &health,
+                            performance.as_ref(),
+                            &frame_tx,
+                            &raw_tx,
+                            &sinks,
                             &event_tx,
                             &current_state,
                         ).await {
@@ -129,39 +585,86 @@ impl MedicalFrameBackend {
                         }
                     }
                     
+                    // Reconnect as soon as the producer creates its region, or after the backoff delay
+                    _ = async {
+                        match producer_wait.as_ref() {
+                            Some(wait) => wait.ready().await,
+                            None => std::future::pending::<()>().await,
+                        }
+                    } => {
+                        if let Some(wait) = producer_wait.as_mut() {
+                            wait.schedule_retry();
+                            Self::retry_connection(wait, &connection_manager, &frame_processor, &event_tx, &current_state).await;
+                        }
+                    }
+                    
                     // Update statistics
                     _ = stats_timer.tick() => {
-                        Self::update_statistics(&event_tx, &current_state).await;
+                        Self::update_statistics(&connection_manager, &frame_processor, &health, performance.as_ref(), &event_tx, &current_state).await;
                     }
                 }
             }
-        });
+
+            if let Some(watchdog) = watchdog_abort {
+                watchdog.abort();
+            }
+            drop(health_server);
+            drop(detection_server);
+            sinks.close().await;
+            health.set_connected(false);
+            *command_slot.write().await = Some(command_rx);
+            info!("🛑 MiVi Medical Frame Backend stopped");
+        };
+        
+        let frame_loop = match frame_thread {
+            None => FrameLoop::Task(tokio::spawn(backend_loop)),
+            Some((thread, loop_tx)) => match loop_tx.send(Box::pin(backend_loop)) {
+                Ok(()) => FrameLoop::Thread(thread),
+                Err(std::sync::mpsc::SendError(backend_loop)) => {
+                    warn!("⚠️ Frame thread exited early, running the frame loop on the runtime");
+                    FrameLoop::Task(tokio::spawn(backend_loop))
+                }
+            },
+        };
         
         info!("✅ MiVi Medical Frame Backend started successfully");
-        Ok(())
+        Ok(BackendHandle { frame_loop, watchdog, command_tx: self.command_tx.clone() })
     }
     
     /// Handle commands from frontend
     async fn handle_command(
         command: BackendCommand,
         connection_manager: &Arc<ConnectionManager>,
-        _frame_processor: &Arc<FrameProcessor>,
+        frame_processor: &Arc<FrameProcessor>,
         event_tx: &broadcast::Sender<BackendEvent>,
+        frame_tx: &FrameSender,
         current_state: &Arc<RwLock<BackendState>>,
     ) -> Result<(), BackendError> {
         match command {
             BackendCommand::Connect { shm_name, config } => {
                 info!("🔌 Connecting to shared memory: {}", shm_name);
+                current_state.write().await.apply_config(&config);
+                frame_processor.set_deinterlace(config.deinterlace);
                 let connection_config = Self::convert_config(config);
                 match connection_manager.connect(&shm_name, connection_config).await {
                     Ok(_) => {
+                        frame_processor.reset_stream_state();
+
                         let mut state = current_state.write().await;
                         state.connection_status = ConnectionStatus::Connected;
                         state.shm_name = shm_name;
+                        state.frame_stats.reset_stream();
                         
                         let _ = event_tx.send(BackendEvent::Connected);
                         info!("✅ Connected to shared memory");
                     }
+                    Err(e) if e.is_producer_missing() => {
+                        let mut state = current_state.write().await;
+                        state.connection_status = ConnectionStatus::WaitingForProducer;
+                        state.shm_name = shm_name.clone();
+                        
+                        let _ = event_tx.send(BackendEvent::WaitingForProducer(shm_name));
+                    }
                     Err(e) => {
                         let mut state = current_state.write().await;
                         state.connection_status = ConnectionStatus::Error(e.to_string());
@@ -176,15 +679,32 @@ impl MedicalFrameBackend {
                 info!("🔌 Disconnecting from shared memory");
                 
                 connection_manager.disconnect().await;
+                frame_processor.reset_stream_state();
                 
                 let mut state = current_state.write().await;
                 state.connection_status = ConnectionStatus::Disconnected;
                 state.current_frame = None;
+                frame_tx.clear();
                 
                 let _ = event_tx.send(BackendEvent::Disconnected);
                 info!("✅ Disconnected from shared memory");
             }
             
+            BackendCommand::Shutdown => {
+                info!("🛑 Shutting down backend");
+                
+                // The frame loop exits after this command; release the source first
+                connection_manager.disconnect().await;
+                frame_processor.reset_stream_state();
+                
+                let mut state = current_state.write().await;
+                state.connection_status = ConnectionStatus::Disconnected;
+                state.current_frame = None;
+                frame_tx.clear();
+                
+                let _ = event_tx.send(BackendEvent::Disconnected);
+            }
+            
             BackendCommand::SetCatchUpMode(enabled) => {
                 info!("⚙️ Setting catch-up mode: {}", enabled);
                 
@@ -194,8 +714,121 @@ impl MedicalFrameBackend {
                 let _ = event_tx.send(BackendEvent::SettingsChanged);
             }
             
+            BackendCommand::SetWindowLevel(window_level) => {
+                info!("🎚️ Setting window/level: center {:.3}, width {:.3}", window_level.center, window_level.width);
+                frame_processor.set_window_level(window_level);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetGamma(gamma) => {
+                info!("🌗 Setting display gamma: {:.2}", gamma.0);
+                frame_processor.set_gamma(gamma);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetColormap(colormap) => {
+                info!("🌈 Setting colormap: {}", colormap.name());
+                frame_processor.set_colormap(colormap);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetClahe(params) => {
+                info!("📈 Setting CLAHE: {:?}", params);
+                frame_processor.set_clahe(params);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetOrientation(orientation) => {
+                info!("🔄 Setting orientation: {}°, flip H {}, flip V {}",
+                      orientation.rotation.degrees(), orientation.flip_horizontal, orientation.flip_vertical);
+                frame_processor.set_orientation(orientation);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetCropRegion(selection) => {
+                let crop = frame_processor.select_crop(selection);
+                match crop {
+                    Some(rect) => info!("✂️ Cropping to {:.0}%x{:.0}% at ({:.0}%, {:.0}%)",
+                                        rect.width * 100.0, rect.height * 100.0, rect.x * 100.0, rect.y * 100.0),
+                    None => info!("✂️ Crop cleared"),
+                }
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetZoom(zoom) => {
+                info!("🔍 Setting zoom: {:.2}x at ({:.0}%, {:.0}%)",
+                      zoom.factor, zoom.center_x * 100.0, zoom.center_y * 100.0);
+                frame_processor.set_zoom(zoom);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetRoi(selection) => {
+                match frame_processor.select_roi(selection) {
+                    Some(roi) => info!("🔲 Setting {} ROI: {:.0}%x{:.0}% at ({:.0}%, {:.0}%)", roi.shape.name(),
+                                       roi.bounds.width * 100.0, roi.bounds.height * 100.0,
+                                       roi.bounds.x * 100.0, roi.bounds.y * 100.0),
+                    None => info!("🔲 ROI cleared"),
+                }
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetResampleQuality(quality) => {
+                info!("🔍 Setting zoom quality: {}", quality.name());
+                frame_processor.set_resample_quality(quality);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetDeinterlace(mode) => {
+                info!("🎞️ Setting deinterlacing: {}", mode.name());
+                frame_processor.set_deinterlace(mode);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetStageEnabled(kind, enabled) => {
+                info!("🧩 {} pipeline stage: {}", if enabled { "Enabling" } else { "Disabling" }, kind.name());
+                frame_processor.set_stage_enabled(kind, enabled);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetStageOrder(order) => {
+                info!("🧩 Reordering pipeline stages: {:?}", order);
+                frame_processor.set_stage_order(&order);
+                
+                let _ = event_tx.send(BackendEvent::SettingsChanged);
+            }
+            
+            BackendCommand::SetStageStrength(kind, value) => {
+                if frame_processor.set_stage_strength(kind, value) {
+                    info!("🧩 Setting {} strength: {:.2}", kind.name(), value);
+                    let _ = event_tx.send(BackendEvent::SettingsChanged);
+                } else {
+                    warn!("⚠️ Pipeline stage {} has no strength setting", kind.name());
+                }
+            }
+            
+            BackendCommand::SetOverlayOpacity(opacity) => {
+                if frame_processor.set_overlay_opacity(opacity) {
+                    info!("🧠 Setting segmentation overlay opacity: {:.0}%", opacity * 100.0);
+                    let _ = event_tx.send(BackendEvent::SettingsChanged);
+                } else {
+                    warn!("⚠️ No segmentation model loaded");
+                }
+            }
+            
             BackendCommand::UpdateConfig(config) => {
                 info!("⚙️ Updating configuration");
+                current_state.write().await.apply_config(&config);
                 let connection_config = Self::convert_config(config);
 
                 connection_manager.update_config(connection_config).await?;
@@ -210,6 +843,14 @@ impl MedicalFrameBackend {
     async fn process_frame_cycle(
         connection_manager: &Arc<ConnectionManager>,
         frame_processor: &Arc<FrameProcessor>,
+        frame_dumper: Option<&Arc<FrameDumper>>,
+        igtl_broadcaster: Option<&Arc<IgtlBroadcaster>>,
+        heartbeat: &PipelineHeartbeat,
+        health: &HealthMonitor,
+        performance: Option<&Arc<parking_lot::Mutex<PerformanceMonitor>>>,
+        frame_tx: &FrameSender,
+        raw_tx: &broadcast::Sender<RawFrame>,
+        sinks: &SinkHub,
         event_tx: &broadcast::Sender<BackendEvent>,
         current_state: &Arc<RwLock<BackendState>>,
     ) -> Result<(), BackendError> {
@@ -218,27 +859,172 @@ impl MedicalFrameBackend {
             return Err(BackendError::NotConnected);
         }
         
-        // Get the current catch-up mode
-        let catch_up_mode = {
-            let state = current_state.read().await;
-            state.catch_up_mode
-        };
+        // Decide between sequential and catch-up reads for this frame
+        let backlog = connection_manager.backlog().await;
+        let catch_up_mode = current_state.write().await.resolve_catch_up(backlog);
         
         // Try to get a new frame
-        match connection_manager.get_next_frame(catch_up_mode).await {
+        match connection_manager.get_next_frame(catch_up_mode).instrument(info_span!("acquire", catch_up = catch_up_mode, backlog)).await {
             Ok(Some(raw_frame)) => {
+                heartbeat.beat();
+                health.frame_received();
+
+                // Decimate to the display frame-rate cap before paying for conversion
+                {
+                    let mut state = current_state.write().await;
+                    if !state.admit_frame(std::time::Instant::now()) {
+                        state.frame_stats.frames_decimated += 1;
+                        if catch_up_mode {
+                            state.frame_stats.update_sequence_skipped(raw_frame.header.sequence_number);
+                        } else {
+                            state.frame_stats.update_sequence(raw_frame.header.sequence_number);
+                        }
+                        return Ok(());
+                    }
+                }
+
+                // Unpack planes and drop row padding first, so the broadcasters and the converter see packed rows
+                let (frame_id, sequence) = (raw_frame.header.frame_id, raw_frame.header.sequence_number);
+                let received_geometry = StreamGeometry::from_header(&raw_frame.header);
+                let received_bytes = raw_frame.data.len();
+                let packed = match planar::PlanarLayout::from_frame(&raw_frame.header, raw_frame.metadata.as_deref()) {
+                    Ok(Some(layout)) => layout.to_bgr(raw_frame).map_err(|e| e.to_string()),
+                    Ok(None) => stride::pack_rows(raw_frame).map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                let raw_frame = match packed {
+                    Ok(raw_frame) => raw_frame,
+                    Err(e) => {
+                        let mut state = current_state.write().await;
+                        state.frame_stats.frames_malformed += 1;
+                        if state.malformed_geometry != Some((received_geometry, received_bytes)) {
+                            state.malformed_geometry = Some((received_geometry, received_bytes));
+                            warn!(frame_id, sequence, actual_bytes = received_bytes, "📐 {}, skipping frames until it does", e);
+                        }
+                        return Ok(());
+                    }
+                };
+
+                // Pass the frame on to OpenIGTLink clients before conversion
+                if let Some(broadcaster) = igtl_broadcaster {
+                    broadcaster.broadcast(&raw_frame);
+                }
+                if raw_tx.receiver_count() > 0 {
+                    let _ = raw_tx.send(raw_frame.clone());
+                }
+
+                // Keep the raw frame for the dumper while it still wants frames
+                let dump = frame_dumper
+                    .and_then(|dumper| dumper.claim().map(|index| (Arc::clone(dumper), index, raw_frame.clone())));
+
+                // Deinterlace for display only; broadcasters and the dumper keep the fields as sent
+                let raw_frame = frame_processor.deinterlace(raw_frame);
+
+                // Re-negotiate before converting when the producer switched resolution or format
+                let geometry = StreamGeometry::from_header(&raw_frame.header);
+                if current_state.write().await.observe_geometry(geometry) {
+                    frame_processor.renegotiate(&geometry);
+                    let _ = event_tx.send(BackendEvent::SettingsChanged);
+                }
+
                 // Process the frame (zero-copy)
-                let processed_frame = frame_processor.process_frame(raw_frame).await?;
+                let processing_started = std::time::Instant::now();
+                let convert_span = info_span!("convert", frame_id, sequence);
+                let processed_frame = match frame_processor.process_frame(raw_frame).instrument(convert_span).await {
+                    Ok(processed_frame) => processed_frame,
+                    Err(frame_processor::ProcessingError::InvalidDataSize { expected, actual }) => {
+                        // Skip frames whose data does not fit their header rather than showing garbage
+                        let mut state = current_state.write().await;
+                        state.frame_stats.frames_malformed += 1;
+                        if state.malformed_geometry != Some((geometry, actual)) {
+                            state.malformed_geometry = Some((geometry, actual));
+                            warn!(
+                                frame_id,
+                                sequence,
+                                width = geometry.width,
+                                height = geometry.height,
+                                format = geometry.format_name(),
+                                expected_bytes = expected,
+                                actual_bytes = actual,
+                                "📐 Frame data does not match its header geometry, skipping frames until it does"
+                            );
+                        }
+                        return Ok(());
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+                let converted = processing_started.elapsed();
+                if let Some(performance) = performance {
+                    performance.lock().record_frame(converted);
+                }
+
+                if let Some((dumper, index, raw_frame)) = dump {
+                    let processed = processed_frame.clone();
+                    tokio::task::spawn_blocking(move || {
+                        match dumper.dump(index, &raw_frame, Some(&processed)) {
+                            Ok(header) => info!("💾 Dumped frame {} ({}/{}) to {}", header.frame_id, index + 1,
+                                                dumper.config().max_frames, dumper.config().dir.join(&header.raw_file).display()),
+                            Err(e) => warn!("⚠️ Failed to dump frame {}: {}", raw_frame.header.frame_id, e),
+                        }
+                    });
+                }
                 
                 // Update state
-                {
+                let drop_policy = {
                     let mut state = current_state.write().await;
+                    if state.malformed_geometry.take().is_some() {
+                        info!(frame_id, "📐 Frame data matches its header geometry again");
+                    }
                     state.current_frame = Some(processed_frame.clone());
                     state.frame_stats.update_frame_received();
-                }
+                    state.frame_stats.update_frame_processed(processed_frame.total_latency_ms());
+
+                    let sequence = processed_frame.header.sequence_number;
+                    if catch_up_mode {
+                        let skipped = state.frame_stats.update_sequence_skipped(sequence);
+                        if skipped > 0 {
+                            debug!("⏩ Caught up to {} (backlog {}): skipped {} frame(s)", sequence, backlog, skipped);
+                        }
+                    } else {
+                        let missing = state.frame_stats.update_sequence(sequence);
+                        if missing > 0 {
+                            debug!("🕳️ Sequence gap before {}: {} frame(s) missing", sequence, missing);
+                        }
+                    }
+                    state.frame_drop_policy
+                };
                 
-                // Notify frontend (zero-copy)
-                let _ = event_tx.send(BackendEvent::NewFrame(processed_frame));
+                // Attached sinks get their own copy; a full queue only drops for that sink
+                sinks.publish(&processed_frame);
+
+                // Hand the frame to the frontend (zero-copy)
+                let delivery = frame_tx.deliver(processed_frame, drop_policy).instrument(info_span!("dispatch", frame_id, sequence)).await;
+                let processing = processing_started.elapsed();
+
+                let mut state = current_state.write().await;
+                if delivery.dropped() {
+                    state.frame_stats.frames_dropped_by_consumer += 1;
+                    debug!("🐢 Frontend behind at frame {}: {:?}", frame_id, delivery);
+                }
+                if state.frame_stats.update_processing_time(processing.as_secs_f64() * 1000.0) {
+                    // One warning per second at most; the count covers the ones in between
+                    let now = std::time::Instant::now();
+                    if !state.last_overrun_warning.is_some_and(|last| now.duration_since(last) < std::time::Duration::from_secs(1)) {
+                        let overruns = state.frame_stats.deadline_overruns.saturating_sub(state.overruns_at_last_warning);
+                        warn!(
+                            frame_id,
+                            sequence,
+                            convert_ms = converted.as_secs_f64() * 1000.0,
+                            dispatch_ms = (processing - converted).as_secs_f64() * 1000.0,
+                            total_ms = processing.as_secs_f64() * 1000.0,
+                            deadline_ms = state.frame_stats.processing_deadline_ms,
+                            overruns,
+                            "⏰ Frame processing overran its deadline"
+                        );
+                        state.last_overrun_warning = Some(now);
+                        state.overruns_at_last_warning = state.frame_stats.deadline_overruns;
+                    }
+                }
             }
             Ok(None) => {
                 // No new frame available
@@ -261,14 +1047,177 @@ impl MedicalFrameBackend {
         Ok(())
     }
     
+    /// Connect again to the source the backend is waiting for
+    ///
+    /// A missing region keeps the backend waiting however long the producer takes;
+    /// other failures (a region still being set up, a protocol mismatch) count
+    /// against the reconnect policy's attempt limit.
+    async fn retry_connection(
+        wait: &mut ProducerWait,
+        connection_manager: &Arc<ConnectionManager>,
+        frame_processor: &Arc<FrameProcessor>,
+        event_tx: &broadcast::Sender<BackendEvent>,
+        current_state: &Arc<RwLock<BackendState>>,
+    ) {
+        let Some(shm_name) = connection_manager.target_name().await else {
+            return;
+        };
+
+        match connection_manager.retry_connect().await {
+            Ok(()) => {
+                frame_processor.reset_stream_state();
+
+                let mut state = current_state.write().await;
+                state.connection_status = ConnectionStatus::Connected;
+                state.shm_name = shm_name;
+                state.frame_stats.reset_stream();
+
+                let _ = event_tx.send(BackendEvent::Connected);
+                info!("✅ Producer is back, connected");
+            }
+            Err(e) if e.is_producer_missing() => {
+                let mut state = current_state.write().await;
+                if state.connection_status != ConnectionStatus::WaitingForProducer {
+                    state.connection_status = ConnectionStatus::WaitingForProducer;
+                    let _ = event_tx.send(BackendEvent::WaitingForProducer(shm_name));
+                }
+            }
+            Err(e) => {
+                wait.failures += 1;
+                let mut state = current_state.write().await;
+                if !wait.can_retry() {
+                    state.connection_status = ConnectionStatus::Error(e.to_string());
+                    let _ = event_tx.send(BackendEvent::ConnectionError(e.to_string()));
+                    warn!("❌ Giving up on {} after {} attempts: {}", shm_name, wait.failures, e);
+                } else {
+                    // connect() marked the manager failed; keep waiting for the producer
+                    state.connection_status = ConnectionStatus::Reconnecting;
+                    debug!("🔄 Producer not ready ({}/{}): {}", wait.failures, state.reconnect.max_attempts, e);
+                }
+            }
+        }
+    }
+    
+    /// Start the dedicated frame thread, which runs the loop it is sent
+    ///
+    /// The thread exits without running anything if the sender is dropped, so a
+    /// `start` failing after this call leaves nothing behind.
+    fn spawn_frame_thread(
+        scheduling: ThreadScheduling,
+    ) -> Result<(std::thread::JoinHandle<()>, std::sync::mpsc::SyncSender<BoxedFrameLoop>), BackendError> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        let (loop_tx, loop_rx) = std::sync::mpsc::sync_channel::<BoxedFrameLoop>(1);
+        let thread = std::thread::Builder::new()
+            .name("mivi-frames".to_string())
+            .spawn(move || {
+                let Ok(backend_loop) = loop_rx.recv() else {
+                    return;
+                };
+                for result in scheduling.apply_to_current_thread() {
+                    if let Err(e) = result {
+                        warn!("⚠️ Frame thread scheduling: {}", e);
+                    }
+                }
+                info!("📌 Frame thread: affinity {:?}, real-time priority {:?}",
+                      scheduling.cpu_affinity, scheduling.rt_priority);
+                runtime.block_on(backend_loop);
+            })?;
+        Ok((thread, loop_tx))
+    }
+    
+    /// Watch the frame loop and restart the connection when it stalls
+    ///
+    /// Runs on its own task so it still fires when the loop is stuck awaiting the
+    /// source. Every step that touches the source is bounded by `timeout`.
+    async fn run_watchdog(
+        timeout: std::time::Duration,
+        heartbeat: Arc<PipelineHeartbeat>,
+        connection_manager: Arc<ConnectionManager>,
+        frame_processor: Arc<FrameProcessor>,
+        event_tx: broadcast::Sender<BackendEvent>,
+        current_state: Arc<RwLock<BackendState>>,
+    ) {
+        let mut detector = watchdog::StallDetector::new(timeout, std::time::Instant::now());
+        let mut check_timer = tokio::time::interval((timeout / 4).max(std::time::Duration::from_millis(100)));
+        let mut recoveries = 0;
+        info!("🐕 Pipeline watchdog started (stall timeout {:?})", timeout);
+
+        loop {
+            check_timer.tick().await;
+            let now = std::time::Instant::now();
+
+            if !current_state.read().await.connection_status.is_connected() {
+                detector.reset(now);
+                continue;
+            }
+
+            let sample = tokio::time::timeout(timeout, async {
+                watchdog::WatchdogSample {
+                    frames_read: heartbeat.frames_read(),
+                    producer_frames_written: connection_manager.producer_frames_written().await,
+                    backlog: connection_manager.backlog().await,
+                }
+            }).await;
+            let Ok(sample) = sample else {
+                warn!("🐕 Watchdog could not sample the source within {:?}", timeout);
+                continue;
+            };
+
+            let Some(reason) = detector.check(sample, now) else {
+                continue;
+            };
+
+            recoveries += 1;
+            let stalled_for = detector.stalled_for(now);
+            warn!("🐕 Frame pipeline stalled for {:?} ({}), restarting the connection", stalled_for, reason.name());
+
+            frame_processor.reset_stream_state();
+            let error = match tokio::time::timeout(timeout, connection_manager.retry_connect()).await {
+                Ok(Ok(())) => {
+                    let mut state = current_state.write().await;
+                    state.connection_status = ConnectionStatus::Connected;
+                    state.frame_stats.reset_stream();
+                    None
+                }
+                Ok(Err(e)) => {
+                    // Let the producer wait in the frame loop take over
+                    current_state.write().await.connection_status = ConnectionStatus::Reconnecting;
+                    Some(e.to_string())
+                }
+                Err(_) => Some(format!("no response within {:?}", timeout)),
+            };
+
+            let report = RecoveryReport { reason, stalled_for, sample, recoveries, error };
+            if report.recovered() {
+                info!("✅ Pipeline recovered: {}", report);
+            } else {
+                error!("❌ Pipeline recovery failed: {}", report);
+            }
+            let _ = event_tx.send(BackendEvent::Recovered(report));
+            detector.reset(std::time::Instant::now());
+        }
+    }
+    
     /// Update statistics and send to frontend
     async fn update_statistics(
+        connection_manager: &Arc<ConnectionManager>,
+        frame_processor: &Arc<FrameProcessor>,
+        health: &HealthMonitor,
+        performance: Option<&Arc<parking_lot::Mutex<PerformanceMonitor>>>,
         event_tx: &broadcast::Sender<BackendEvent>,
         current_state: &Arc<RwLock<BackendState>>,
     ) {
+        let connection = connection_manager.get_statistics().await;
         let stats = {
             let mut state = current_state.write().await;
             state.frame_stats.calculate_fps();
+            state.frame_stats.pipeline_stages = frame_processor.stage_statistics();
+            state.frame_stats.numa = frame_processor.numa_statistics();
+            state.frame_stats.inference = frame_processor.inference_statistics();
+            state.frame_stats.frames_corrupted = connection.corrupted_frames;
+            state.frame_stats.protocol = connection.protocol;
+            state.frame_stats.performance = performance.map(|monitor| monitor.lock().snapshot());
+            health.set_connected(state.connection_status.is_connected());
             state.frame_stats.clone()
         };
         
@@ -293,12 +1242,96 @@ impl From<frame_processor::ProcessingError> for BackendError {
 #[derive(Debug, Clone)]
 pub struct BackendConfig {
     pub shm_name: String,
+    /// Expected format, width and height; frames are decoded by what their headers report
     pub format: String,
     pub width: usize,
     pub height: usize,
     pub catch_up: bool,
     pub verbose: bool,
-    pub reconnect_delay: std::time::Duration,
+    /// Backoff between reconnection attempts and how many to make
+    pub reconnect: ReconnectPolicy,
+    
+    // Device profile
+    pub frame_timeout: std::time::Duration,
+    pub expected_fps: f64,
+    pub latency_target_ms: f64,
+    pub device_profile: Option<String>,
+    
+    /// Skip to the latest frame only while the backlog is large (takes precedence over `catch_up`)
+    pub adaptive_catch_up: Option<AdaptiveCatchUp>,
+    
+    /// Display frame-rate cap; faster streams are decimated before conversion (0 = unlimited)
+    pub max_display_fps: u32,
+    
+    // Backend loop timing
+    pub frame_polling: FramePolling,
+    pub stats_interval: std::time::Duration,
+    /// CPU affinity and real-time priority of the frame thread (applied at start)
+    pub scheduling: ThreadScheduling,
+    /// Memory locking and huge page advice for the shared memory mapping
+    pub memory: MemoryConfig,
+    /// Leave unfiltered 8-bit frames unconverted for the GPU (applied at start)
+    pub gpu_upload: bool,
+    /// Keep 10-bit frames at full precision up to the display instead of truncating them (applied at creation)
+    pub deep_color: bool,
+    /// Dithering when 10/12-bit frames are reduced to 8 bits (applied at creation)
+    pub dithering: Dithering,
+    /// Deinterlacing of frames the producer flags as interlaced (applied at creation and on connect, per stream)
+    pub deinterlace: Deinterlace,
+    /// Calibrate the display to the DICOM GSDF over this luminance range (applied at creation)
+    pub display_calibration: Option<DisplayCalibration>,
+    
+    // Debugging: dump the first frames to disk
+    pub dump: Option<DumpConfig>,
+    /// Re-broadcast received frames as OpenIGTLink IMAGE messages on this address (applied at start)
+    pub igtl_broadcast: Option<String>,
+    /// Restart the connection when no frame is read for this long while the producer is active (applied at start)
+    pub watchdog_timeout: Option<std::time::Duration>,
+    /// Control events buffered per receiver before the oldest are dropped (applied at creation)
+    pub event_capacity: usize,
+    /// What happens to frames the frontend has not caught up with
+    pub frame_drop_policy: FrameDropPolicy,
+    /// Longest conversion and hand-off to the frontend may take before it counts as an overrun
+    pub processing_deadline: Option<std::time::Duration>,
+    /// Threads converting frames, apart from the async runtime (None = 75% of the cores, up to 8; applied at creation)
+    pub conversion_threads: Option<usize>,
+    /// Track conversion and render times for the performance panel (applied at creation)
+    pub perf_monitor: bool,
+    /// How long a CPU profile requested from the UI samples the process
+    pub profile_duration: std::time::Duration,
+    /// Serve liveness and readiness checks over HTTP on this address (applied at start)
+    pub health_listen: Option<String>,
+    /// Take detection overlays as newline-delimited JSON on this address (applied at start)
+    pub detections_listen: Option<String>,
+    /// Append every displayed frame to this file in the TCP message format (applied at start)
+    pub record_to: Option<std::path::PathBuf>,
+    /// Forward displayed frames to TCP clients on this address (applied at start)
+    pub forward_listen: Option<String>,
+    /// Write a PNG of the displayed frame into this directory every `snapshot_interval` (applied at start)
+    pub snapshot_dir: Option<std::path::PathBuf>,
+    pub snapshot_interval: std::time::Duration,
+    /// Start a new recording in this directory whenever frames begin arriving (applied at start)
+    pub auto_record_dir: Option<std::path::PathBuf>,
+    /// How long without frames ends an automatic recording
+    pub auto_record_idle: std::time::Duration,
+    /// Save the frames around frames the producer flags as triggers (applied at start)
+    pub burst_capture: Option<BurstConfig>,
+    /// Hash-chained audit log the frontend appends user actions to
+    pub audit_log: Option<std::path::PathBuf>,
+    /// Applied to measurement exports and session reports, and to metadata recorded or forwarded by the sinks
+    pub export_anonymization: AnonymizationProfile,
+    /// Seals session reports and their snapshots, recordings and periodic snapshots (frame dumps carry their own)
+    pub recording_encryption: Option<RecordingCipher>,
+    /// Accounts the frontend asks to sign in; None gives the system user full access
+    pub users_file: Option<std::path::PathBuf>,
+    /// PIN the frontend asks for to leave privacy mode; None resumes on a click
+    pub privacy_pin: Option<String>,
+    /// Segmentation model run on displayed frames (--onnx-model)
+    pub inference: Option<InferenceConfig>,
+    /// Directory of frame filter plugin manifests (applied at creation)
+    pub plugin_dir: Option<std::path::PathBuf>,
+    /// Automation script the frontend runs on viewer events
+    pub script: Option<std::path::PathBuf>,
 }
 
 impl Default for BackendConfig {
@@ -310,7 +1343,48 @@ impl Default for BackendConfig {
             height: 768,
             catch_up: false,
             verbose: false,
-            reconnect_delay: std::time::Duration::from_secs(1),
+            reconnect: ReconnectPolicy::default(),
+            frame_timeout: std::time::Duration::from_secs(5),
+            expected_fps: 30.0,
+            latency_target_ms: 50.0,
+            device_profile: None,
+            adaptive_catch_up: None,
+            max_display_fps: 0,
+            frame_polling: FramePolling::Auto,
+            stats_interval: std::time::Duration::from_secs(1),
+            scheduling: ThreadScheduling::default(),
+            memory: MemoryConfig::default(),
+            gpu_upload: false,
+            deep_color: false,
+            dithering: Dithering::Off,
+            deinterlace: Deinterlace::Off,
+            display_calibration: None,
+            dump: None,
+            igtl_broadcast: None,
+            watchdog_timeout: Some(std::time::Duration::from_secs(5)),
+            event_capacity: DEFAULT_EVENT_CAPACITY,
+            frame_drop_policy: FrameDropPolicy::default(),
+            processing_deadline: None,
+            conversion_threads: None,
+            perf_monitor: false,
+            profile_duration: std::time::Duration::from_secs(10),
+            health_listen: None,
+            detections_listen: None,
+            record_to: None,
+            forward_listen: None,
+            snapshot_dir: None,
+            snapshot_interval: std::time::Duration::from_secs(10),
+            auto_record_dir: None,
+            auto_record_idle: std::time::Duration::from_secs(30),
+            burst_capture: None,
+            audit_log: None,
+            export_anonymization: AnonymizationProfile::default(),
+            recording_encryption: None,
+            users_file: None,
+            privacy_pin: None,
+            inference: None,
+            plugin_dir: None,
+            script: None,
         }
     }
 }
@@ -323,6 +1397,29 @@ pub struct BackendState {
     pub current_frame: Option<ProcessedFrame>,
     pub frame_stats: FrameStatistics,
     pub catch_up_mode: bool,
+    pub adaptive_catch_up: Option<AdaptiveCatchUp>,
+    /// Whether the adaptive policy is currently skipping to the latest frame
+    pub catch_up_skipping: bool,
+    pub expected_fps: f64,
+    pub device_profile: Option<String>,
+    pub max_display_fps: u32,
+    /// When the last frame passed the display frame-rate cap
+    pub last_display_time: Option<std::time::Instant>,
+    pub frame_polling: FramePolling,
+    pub stats_interval: std::time::Duration,
+    /// Retry schedule while waiting for the producer
+    pub reconnect: ReconnectPolicy,
+    /// What happens to frames the frontend has not caught up with
+    pub frame_drop_policy: FrameDropPolicy,
+    /// When a deadline overrun was last logged, to keep the log readable
+    pub last_overrun_warning: Option<std::time::Instant>,
+    pub overruns_at_last_warning: u64,
+    /// Width, height and format from the configuration; the frame headers take precedence
+    pub geometry_hint: (usize, usize, String),
+    /// Geometry of the stream as its latest frame header reports it
+    pub stream_geometry: Option<StreamGeometry>,
+    /// Header geometry and data size of the last frame whose data did not fit, until one fits again
+    pub malformed_geometry: Option<(StreamGeometry, usize)>,
 }
 
 impl Default for BackendState {
@@ -333,6 +1430,127 @@ impl Default for BackendState {
             current_frame: None,
             frame_stats: FrameStatistics::default(),
             catch_up_mode: false,
+            adaptive_catch_up: None,
+            catch_up_skipping: false,
+            expected_fps: 30.0,
+            device_profile: None,
+            max_display_fps: 0,
+            last_display_time: None,
+            frame_polling: FramePolling::Auto,
+            stats_interval: std::time::Duration::from_secs(1),
+            reconnect: ReconnectPolicy::default(),
+            frame_drop_policy: FrameDropPolicy::default(),
+            last_overrun_warning: None,
+            overruns_at_last_warning: 0,
+            geometry_hint: (0, 0, String::new()),
+            stream_geometry: None,
+            malformed_geometry: None,
+        }
+    }
+}
+
+impl BackendState {
+    /// Apply the device profile targets and catch-up policy of a configuration
+    pub fn apply_config(&mut self, config: &BackendConfig) {
+        self.catch_up_mode = config.catch_up;
+        self.expected_fps = config.expected_fps;
+        self.device_profile = config.device_profile.clone();
+        self.frame_stats.latency_target_ms = config.latency_target_ms;
+        self.frame_stats.processing_deadline_ms = config.processing_deadline.map_or(0.0, |deadline| deadline.as_secs_f64() * 1000.0);
+        self.max_display_fps = config.max_display_fps;
+        self.adaptive_catch_up = config.adaptive_catch_up;
+        self.catch_up_skipping = false;
+        self.frame_polling = config.frame_polling;
+        self.stats_interval = config.stats_interval.max(std::time::Duration::from_millis(100));
+        self.reconnect = config.reconnect;
+        self.frame_drop_policy = config.frame_drop_policy;
+        self.geometry_hint = (config.width, config.height, config.format.clone());
+    }
+
+    /// Follow the geometry of the stream, returning true when it differs from what was assumed so far
+    ///
+    /// The first frame is compared with the configured hint, later frames with the previous one.
+    pub fn observe_geometry(&mut self, geometry: StreamGeometry) -> bool {
+        let changed = match self.stream_geometry {
+            Some(previous) if previous == geometry => return false,
+            Some(previous) => {
+                info!("📐 Stream geometry changed: {} -> {}", previous, geometry);
+                true
+            }
+            None => {
+                let (width, height, ref format) = self.geometry_hint;
+                let differs = !geometry.matches_hint(width, height, format);
+                if differs {
+                    info!("📐 Stream is {} (configured {}x{} {}), following the frame headers", geometry, width, height, format);
+                } else {
+                    debug!("📐 Stream geometry: {}", geometry);
+                }
+                differs
+            }
+        };
+        self.stream_geometry = Some(geometry);
+        changed
+    }
+    
+    /// Decide whether the next read skips to the latest frame, given the current backlog
+    pub fn resolve_catch_up(&mut self, backlog: u64) -> bool {
+        let Some(policy) = self.adaptive_catch_up else {
+            return self.catch_up_mode;
+        };
+        
+        let skipping = policy.should_skip(backlog, self.catch_up_skipping);
+        if skipping != self.catch_up_skipping {
+            debug!("⏩ Adaptive catch-up {} (backlog {})", if skipping { "skipping to latest" } else { "back to sequential" }, backlog);
+            self.catch_up_skipping = skipping;
+        }
+        skipping
+    }
+    
+    /// Check whether a frame arriving now fits under the display frame-rate cap
+    pub fn admit_frame(&mut self, now: std::time::Instant) -> bool {
+        if self.max_display_fps > 0 {
+            // Accept frames slightly early so producer timing noise doesn't skip an extra frame
+            let min_interval = std::time::Duration::from_secs_f64(0.9 / self.max_display_fps as f64);
+            if self.last_display_time.is_some_and(|last| now.duration_since(last) < min_interval) {
+                return false;
+            }
+        }
+        
+        self.last_display_time = Some(now);
+        true
+    }
+    
+    /// Get the frame polling interval
+    ///
+    /// In automatic mode this is twice the expected frame rate, clamped to 4-16ms.
+    pub fn poll_interval(&self) -> std::time::Duration {
+        match self.frame_polling {
+            FramePolling::Auto => {
+                let fps = if self.expected_fps > 0.0 { self.expected_fps } else { 30.0 };
+                let interval_ms = (1000.0 / (fps * 2.0)).clamp(4.0, 16.0);
+                std::time::Duration::from_secs_f64(interval_ms / 1000.0)
+            }
+            FramePolling::Every(interval) => interval.max(std::time::Duration::from_millis(1)),
+        }
+    }
+}
+
+/// How the backend loop polls shared memory for new frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FramePolling {
+    /// Derive the interval from the expected frame rate of the device profile
+    #[default]
+    Auto,
+    /// Poll at a fixed interval
+    Every(std::time::Duration),
+}
+
+impl FramePolling {
+    /// Build from a command line interval in milliseconds
+    pub fn from_millis(interval_ms: Option<u64>) -> Self {
+        match interval_ms {
+            None => Self::Auto,
+            Some(ms) => Self::Every(std::time::Duration::from_millis(ms)),
         }
     }
 }
@@ -343,7 +1561,102 @@ pub enum BackendCommand {
     Connect { shm_name: String, config: BackendConfig },
     Disconnect,
     SetCatchUpMode(bool),
+    SetWindowLevel(WindowLevel),
+    SetGamma(Gamma),
+    SetColormap(Colormap),
+    SetClahe(Option<ClaheParams>),
+    SetOrientation(Orientation),
+    /// Narrow the crop to a rectangle on the displayed frame (None = full frame)
+    SetCropRegion(Option<CropRect>),
+    SetZoom(Zoom),
+    /// Region for raw-value statistics on the displayed frame before zoom (None = off)
+    SetRoi(Option<Roi>),
+    SetResampleQuality(ResampleQuality),
+    /// Deinterlacing of the current stream's interlaced frames
+    SetDeinterlace(Deinterlace),
+    SetStageEnabled(StageKind, bool),
+    SetStageOrder(Vec<StageKind>),
+    SetStageStrength(StageKind, f32),
+    /// Segmentation mask opacity (0.0 hides the overlay)
+    SetOverlayOpacity(f32),
     UpdateConfig(BackendConfig),
+    /// Disconnect and stop the frame loop and watchdog; see `BackendHandle`
+    Shutdown,
+}
+
+/// Frame loop handed to the dedicated frame thread
+type BoxedFrameLoop = std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>>;
+
+/// Where the frame loop runs
+enum FrameLoop {
+    Task(tokio::task::JoinHandle<()>),
+    Thread(std::thread::JoinHandle<()>),
+}
+
+/// Handle to the tasks of a started backend
+///
+/// Dropping the handle leaves the backend running.
+pub struct BackendHandle {
+    frame_loop: FrameLoop,
+    watchdog: Option<tokio::task::JoinHandle<()>>,
+    command_tx: mpsc::UnboundedSender<BackendCommand>,
+}
+
+impl BackendHandle {
+    /// Check if the frame loop has exited
+    pub fn is_finished(&self) -> bool {
+        match &self.frame_loop {
+            FrameLoop::Task(task) => task.is_finished(),
+            FrameLoop::Thread(thread) => thread.is_finished(),
+        }
+    }
+
+    /// Send `BackendCommand::Shutdown` and wait for the tasks to exit
+    ///
+    /// Commands queued before it are still handled. Afterwards the backend can be started again.
+    pub async fn shutdown(self) -> Result<(), BackendError> {
+        // The loop may have exited already, in which case there is nothing to stop
+        let _ = self.command_tx.send(BackendCommand::Shutdown);
+        self.join().await
+    }
+
+    /// Wait for the tasks to exit without asking them to
+    pub async fn join(self) -> Result<(), BackendError> {
+        match self.frame_loop {
+            FrameLoop::Task(task) => match task.await {
+                Err(e) if !e.is_cancelled() => return Err(BackendError::Other(format!("Frame loop failed: {}", e))),
+                _ => {}
+            },
+            FrameLoop::Thread(thread) => {
+                tokio::task::spawn_blocking(move || thread.join())
+                    .await
+                    .map_err(|e| BackendError::Other(format!("Frame thread join failed: {}", e)))?
+                    .map_err(|_| BackendError::Other("Frame thread panicked".to_string()))?;
+            }
+        }
+        if let Some(watchdog) = self.watchdog {
+            watchdog.abort();
+            let _ = watchdog.await;
+        }
+        Ok(())
+    }
+
+    /// Stop the tasks immediately, without disconnecting cleanly
+    ///
+    /// An aborted task never returns the command receiver, so the backend cannot
+    /// be started again. A frame loop on its own thread cannot be aborted and is
+    /// sent `BackendCommand::Shutdown` instead.
+    pub fn abort(&self) {
+        match &self.frame_loop {
+            FrameLoop::Task(task) => task.abort(),
+            FrameLoop::Thread(_) => {
+                let _ = self.command_tx.send(BackendCommand::Shutdown);
+            }
+        }
+        if let Some(watchdog) = &self.watchdog {
+            watchdog.abort();
+        }
+    }
 }
 
 /// Events emitted by the backend
@@ -353,9 +1666,14 @@ pub enum BackendEvent {
     Disconnected,
     ConnectionError(String),
     ConnectionLost,
-    NewFrame(ProcessedFrame),
+    /// The source does not exist yet; the backend connects as soon as it appears
+    WaitingForProducer(String),
+    /// The watchdog restarted a stalled pipeline
+    Recovered(RecoveryReport),
     StatisticsUpdate(FrameStatistics),
     SettingsChanged,
+    /// External analysis attached detections to a frame
+    DetectionsReceived(FrameDetections),
 }
 
 /// Connection status
@@ -365,6 +1683,7 @@ pub enum ConnectionStatus {
     Connecting,
     Connected,
     Reconnecting,
+    WaitingForProducer,
     Error(String),
 }
 
@@ -373,12 +1692,18 @@ impl ConnectionStatus {
         matches!(self, ConnectionStatus::Connected)
     }
     
+    /// Check if the backend should reconnect once the producer shows up
+    pub fn awaits_producer(&self) -> bool {
+        matches!(self, ConnectionStatus::Reconnecting | ConnectionStatus::WaitingForProducer)
+    }
+    
     pub fn to_string(&self) -> String {
         match self {
             ConnectionStatus::Disconnected => "Disconnected".to_string(),
             ConnectionStatus::Connecting => "Connecting...".to_string(),
             ConnectionStatus::Connected => "Connected".to_string(),
             ConnectionStatus::Reconnecting => "Reconnecting...".to_string(),
+            ConnectionStatus::WaitingForProducer => "Waiting for producer...".to_string(),
             ConnectionStatus::Error(e) => format!("Error: {}", e),
         }
     }
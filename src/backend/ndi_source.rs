@@ -0,0 +1,376 @@
+// src/backend/ndi_source.rs - NDI Receiver for Operating Room Video Feeds (feature "ndi")
+
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+use crate::backend::frame_source::{FrameQueue, FrameSource};
+use crate::backend::shared_memory::{ConnectionStatistics, SharedMemoryError};
+use crate::backend::types::{ConnectionConfig, FrameFormat, FrameHeader, RawFrame};
+
+/// Prefix of `--shm-name` values that name an NDI source
+pub const NDI_PREFIX: &str = "ndi:";
+
+/// Frames buffered before the oldest is dropped
+const MAX_QUEUED_FRAMES: usize = 4;
+
+/// How long one capture call waits, bounding how quickly a stop is noticed
+const CAPTURE_TIMEOUT_MS: u32 = 100;
+
+/// Runtime libraries tried in order; `NDI_RUNTIME_DIR_V6`/`_V5` take precedence
+const RUNTIME_LIBRARIES: [(&str, &str); 2] = [("NDI_RUNTIME_DIR_V6", "libndi.so.6"), ("NDI_RUNTIME_DIR_V5", "libndi.so.5")];
+
+const FRAME_TYPE_VIDEO: i32 = 1;
+const FRAME_TYPE_ERROR: i32 = 4;
+const RECV_COLOR_FORMAT_BGRX_BGRA: i32 = 0;
+const RECV_BANDWIDTH_HIGHEST: i32 = 100;
+
+/// Little-endian fourcc as used by `NDIlib_FourCC_video_type_e`
+const fn fourcc(code: &[u8; 4]) -> u32 {
+    u32::from_le_bytes(*code)
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct NdiSource {
+    ndi_name: *const c_char,
+    url_address: *const c_char,
+}
+
+#[repr(C)]
+struct FindCreate {
+    show_local_sources: bool,
+    groups: *const c_char,
+    extra_ips: *const c_char,
+}
+
+#[repr(C)]
+struct RecvCreateV3 {
+    source: NdiSource,
+    color_format: i32,
+    bandwidth: i32,
+    allow_video_fields: bool,
+    recv_name: *const c_char,
+}
+
+#[repr(C)]
+struct VideoFrameV2 {
+    xres: i32,
+    yres: i32,
+    fourcc: u32,
+    frame_rate_n: i32,
+    frame_rate_d: i32,
+    picture_aspect_ratio: f32,
+    frame_format_type: i32,
+    timecode: i64,
+    data: *mut u8,
+    line_stride_in_bytes: i32,
+    metadata: *const c_char,
+    timestamp: i64,
+}
+
+/// Entry points of the NDI runtime, resolved when the source connects
+struct NdiLib {
+    _library: libloading::Library,
+    find_create_v2: unsafe extern "C" fn(*const FindCreate) -> *mut c_void,
+    find_destroy: unsafe extern "C" fn(*mut c_void),
+    find_wait_for_sources: unsafe extern "C" fn(*mut c_void, u32) -> bool,
+    find_get_current_sources: unsafe extern "C" fn(*mut c_void, *mut u32) -> *const NdiSource,
+    recv_create_v3: unsafe extern "C" fn(*const RecvCreateV3) -> *mut c_void,
+    recv_destroy: unsafe extern "C" fn(*mut c_void),
+    recv_capture_v2: unsafe extern "C" fn(*mut c_void, *mut VideoFrameV2, *mut c_void, *mut c_void, u32) -> i32,
+    recv_free_video_v2: unsafe extern "C" fn(*mut c_void, *const VideoFrameV2),
+}
+
+impl NdiLib {
+    fn load() -> Result<Self, SharedMemoryError> {
+        let candidates = RUNTIME_LIBRARIES.iter().flat_map(|&(variable, file)| {
+            let from_env = std::env::var_os(variable).map(|dir| std::path::Path::new(&dir).join(file));
+            from_env.into_iter().chain(std::iter::once(file.into()))
+        });
+
+        let library = candidates
+            .filter_map(|path| unsafe { libloading::Library::new(path) }.ok())
+            .next()
+            .ok_or_else(|| SharedMemoryError::Other("NDI runtime (libndi.so.6 or .5) not found".to_string()))?;
+
+        unsafe {
+            let symbol_error = |e: libloading::Error| SharedMemoryError::Other(format!("NDI runtime: {}", e));
+            let initialize: unsafe extern "C" fn() -> bool = *library.get(b"NDIlib_initialize\0").map_err(symbol_error)?;
+            if !initialize() {
+                return Err(SharedMemoryError::Other("NDI runtime does not support this CPU".to_string()));
+            }
+
+            Ok(Self {
+                find_create_v2: *library.get(b"NDIlib_find_create_v2\0").map_err(symbol_error)?,
+                find_destroy: *library.get(b"NDIlib_find_destroy\0").map_err(symbol_error)?,
+                find_wait_for_sources: *library.get(b"NDIlib_find_wait_for_sources\0").map_err(symbol_error)?,
+                find_get_current_sources: *library.get(b"NDIlib_find_get_current_sources\0").map_err(symbol_error)?,
+                recv_create_v3: *library.get(b"NDIlib_recv_create_v3\0").map_err(symbol_error)?,
+                recv_destroy: *library.get(b"NDIlib_recv_destroy\0").map_err(symbol_error)?,
+                recv_capture_v2: *library.get(b"NDIlib_recv_capture_v2\0").map_err(symbol_error)?,
+                recv_free_video_v2: *library.get(b"NDIlib_recv_free_video_v2\0").map_err(symbol_error)?,
+                _library: library,
+            })
+        }
+    }
+
+    /// Find the named source on the network and connect a receiver to it
+    fn connect_receiver(&self, wanted: &str, timeout: std::time::Duration) -> Result<*mut c_void, SharedMemoryError> {
+        let find_settings = FindCreate { show_local_sources: true, groups: std::ptr::null(), extra_ips: std::ptr::null() };
+        let finder = unsafe { (self.find_create_v2)(&find_settings) };
+        if finder.is_null() {
+            return Err(SharedMemoryError::Other("NDI source finder could not be created".to_string()));
+        }
+
+        let recv_name = CString::new("MiVi Frame Viewer").unwrap_or_default();
+        let deadline = Instant::now() + timeout;
+        let mut receiver = std::ptr::null_mut();
+        while receiver.is_null() && Instant::now() < deadline {
+            unsafe {
+                (self.find_wait_for_sources)(finder, 500);
+                let mut count = 0u32;
+                let sources = (self.find_get_current_sources)(finder, &mut count);
+                let sources = if sources.is_null() { &[][..] } else { std::slice::from_raw_parts(sources, count as usize) };
+
+                if let Some(source) = sources.iter().find(|source| {
+                    !source.ndi_name.is_null() && matches_source(&CStr::from_ptr(source.ndi_name).to_string_lossy(), wanted)
+                }) {
+                    let settings = RecvCreateV3 {
+                        source: *source,
+                        color_format: RECV_COLOR_FORMAT_BGRX_BGRA,
+                        bandwidth: RECV_BANDWIDTH_HIGHEST,
+                        allow_video_fields: false,
+                        recv_name: recv_name.as_ptr(),
+                    };
+                    // The receiver copies the source, so the finder can go afterwards
+                    receiver = (self.recv_create_v3)(&settings);
+                    if receiver.is_null() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        unsafe { (self.find_destroy)(finder) };
+        if receiver.is_null() {
+            return Err(SharedMemoryError::NotFound(wanted.to_string()));
+        }
+        Ok(receiver)
+    }
+}
+
+/// Check an NDI name ("MACHINE (Source)") against the requested one
+///
+/// Either the full name or just the part in parentheses may be given.
+pub fn matches_source(ndi_name: &str, wanted: &str) -> bool {
+    if ndi_name == wanted {
+        return true;
+    }
+    ndi_name
+        .rsplit_once(" (")
+        .and_then(|(_, source)| source.strip_suffix(')'))
+        .is_some_and(|source| source == wanted)
+}
+
+/// Receives an NDI video feed as BGRA frames
+///
+/// The runtime is loaded when connecting, so builds with the feature still run
+/// on machines without NDI installed. Capture runs on its own thread.
+pub struct NdiFrameSource {
+    source_name: String,
+    config: ConnectionConfig,
+    queue: Arc<FrameQueue>,
+    stop: Arc<AtomicBool>,
+}
+
+impl NdiFrameSource {
+    /// Create a receiver for the named NDI source
+    pub fn new(source_name: &str, config: ConnectionConfig) -> Self {
+        Self {
+            source_name: source_name.to_string(),
+            config,
+            queue: Arc::new(FrameQueue::new(MAX_QUEUED_FRAMES)),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn capture(
+        source_name: String,
+        config: ConnectionConfig,
+        queue: Arc<FrameQueue>,
+        stop: Arc<AtomicBool>,
+        ready: oneshot::Sender<Result<(), SharedMemoryError>>,
+    ) {
+        let opened = NdiLib::load().and_then(|ndi| {
+            let receiver = ndi.connect_receiver(&source_name, config.frame_timeout)?;
+            Ok((ndi, receiver))
+        });
+        let (ndi, receiver) = match opened {
+            Ok(opened) => opened,
+            Err(e) => {
+                let _ = ready.send(Err(e));
+                return;
+            }
+        };
+
+        let mut sequence = 0u64;
+        if ready.send(Ok(())).is_ok() {
+            while !stop.load(Ordering::Acquire) {
+                let mut video: VideoFrameV2 = unsafe { std::mem::zeroed() };
+                let frame_type = unsafe {
+                    (ndi.recv_capture_v2)(receiver, &mut video, std::ptr::null_mut(), std::ptr::null_mut(), CAPTURE_TIMEOUT_MS)
+                };
+
+                match frame_type {
+                    FRAME_TYPE_VIDEO => {
+                        sequence += 1;
+                        match video_to_frame(&video, sequence) {
+                            Some(frame) => queue.push(frame),
+                            None => {
+                                warn!("⚠️ Dropping NDI frame with fourcc {:08x}", video.fourcc);
+                                queue.record_error();
+                            }
+                        }
+                        unsafe { (ndi.recv_free_video_v2)(receiver, &video) };
+                    }
+                    FRAME_TYPE_ERROR => {
+                        warn!("❌ NDI source {} disconnected", source_name);
+                        queue.record_error();
+                        break;
+                    }
+                    _ => {} // Timeouts, status changes
+                }
+            }
+        }
+
+        unsafe { (ndi.recv_destroy)(receiver) };
+        queue.set_connected(false);
+    }
+}
+
+/// Copy a BGRX/BGRA video frame into a tightly packed frame
+fn video_to_frame(video: &VideoFrameV2, sequence: u64) -> Option<RawFrame> {
+    if video.fourcc != fourcc(b"BGRA") && video.fourcc != fourcc(b"BGRX") {
+        return None;
+    }
+    if video.data.is_null() || video.xres <= 0 || video.yres <= 0 {
+        return None;
+    }
+
+    let (width, height) = (video.xres as usize, video.yres as usize);
+    let row = width * 4;
+    let stride = (video.line_stride_in_bytes as usize).max(row);
+    let source = unsafe { std::slice::from_raw_parts(video.data, stride * (height - 1) + row) };
+    let mut data = Vec::with_capacity(row * height);
+    for y in 0..height {
+        data.extend_from_slice(&source[y * stride..y * stride + row]);
+    }
+
+    let metadata = (!video.metadata.is_null())
+        .then(|| unsafe { CStr::from_ptr(video.metadata) }.to_string_lossy().into_owned());
+
+    let header = FrameHeader {
+        frame_id: sequence,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64,
+        width: width as u32,
+        height: height as u32,
+        bytes_per_pixel: 4,
+        data_size: data.len() as u32,
+        format_code: FrameFormat::BGR.to_code(),
+        flags: 0,
+        sequence_number: sequence,
+        metadata_offset: 0,
+        metadata_size: 0,
+        padding: [0; 4],
+    };
+    Some(RawFrame::new(header, Arc::from(data), metadata))
+}
+
+#[async_trait]
+impl FrameSource for NdiFrameSource {
+    async fn connect(&mut self) -> Result<(), SharedMemoryError> {
+        if self.config.verbose_logging {
+            info!("🔌 Looking for NDI source: {}", self.source_name);
+        }
+
+        self.stop = Arc::new(AtomicBool::new(false));
+        self.queue.set_connected(true);
+
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (source_name, config) = (self.source_name.clone(), self.config.clone());
+        let (queue, stop) = (Arc::clone(&self.queue), Arc::clone(&self.stop));
+        std::thread::Builder::new()
+            .name("mivi-ndi".to_string())
+            .spawn(move || Self::capture(source_name, config, queue, stop, ready_tx))?;
+
+        let result = ready_rx.await.unwrap_or(Err(SharedMemoryError::ConnectionLost));
+        if let Err(e) = result {
+            self.queue.set_connected(false);
+            return Err(e);
+        }
+
+        info!("🔗 Receiving NDI source: {}", self.source_name);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        self.queue.set_connected(false);
+
+        info!("🔌 Stopped NDI source: {}", self.source_name);
+    }
+
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        self.queue.next(catch_up)
+    }
+
+    fn check_connection_health(&self) -> bool {
+        self.queue.is_healthy(self.config.frame_timeout)
+    }
+
+    fn backlog(&self) -> u64 {
+        self.queue.backlog()
+    }
+
+    fn get_statistics(&self) -> ConnectionStatistics {
+        self.queue.statistics(format!("{}{}", NDI_PREFIX, self.source_name))
+    }
+}
+
+impl Drop for NdiFrameSource {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_matching_and_frames() {
+        assert!(matches_source("OR-PC (Endoscope)", "OR-PC (Endoscope)"));
+        assert!(matches_source("OR-PC (Endoscope)", "Endoscope"));
+        assert!(!matches_source("OR-PC (Endoscope)", "OR-PC"));
+
+        // Stride padding is dropped
+        let mut pixels = [1u8, 2, 3, 4, 0, 0, 5, 6, 7, 8, 0, 0];
+        let mut video: VideoFrameV2 = unsafe { std::mem::zeroed() };
+        video.xres = 1;
+        video.yres = 2;
+        video.fourcc = fourcc(b"BGRX");
+        video.line_stride_in_bytes = 6;
+        video.data = pixels.as_mut_ptr();
+        let frame = video_to_frame(&video, 1).unwrap();
+        assert_eq!(&frame.data[..], &[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        video.fourcc = fourcc(b"UYVY");
+        assert!(video_to_frame(&video, 2).is_none());
+    }
+}
@@ -0,0 +1,163 @@
+// src/backend/numa.rs - NUMA Topology, Node-Local Placement and Cross-Node Traffic Accounting
+
+use std::path::Path;
+
+const NODE_ROOT: &str = "/sys/devices/system/node";
+
+/// NUMA nodes of this machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumaTopology {
+    pub nodes: Vec<usize>,
+}
+
+impl NumaTopology {
+    /// Read the online nodes from sysfs, falling back to a single node
+    pub fn detect() -> Self {
+        let nodes = std::fs::read_to_string(Path::new(NODE_ROOT).join("online"))
+            .ok()
+            .and_then(|list| parse_cpu_list(&list))
+            .filter(|nodes| !nodes.is_empty())
+            .unwrap_or_else(|| vec![0]);
+        Self { nodes }
+    }
+
+    /// Check if memory placement matters on this machine
+    pub fn is_numa(&self) -> bool {
+        self.nodes.len() > 1
+    }
+
+    /// CPUs belonging to a node
+    ///
+    /// Pinning the frame thread to these makes the kernel's first-touch policy place
+    /// every conversion buffer it allocates on that node.
+    pub fn node_cpus(&self, node: usize) -> Option<Vec<usize>> {
+        if !self.nodes.contains(&node) {
+            return None;
+        }
+        std::fs::read_to_string(Path::new(NODE_ROOT).join(format!("node{}", node)).join("cpulist"))
+            .ok()
+            .and_then(|list| parse_cpu_list(&list))
+    }
+}
+
+/// Parse a kernel CPU/node list such as "0-3,8,10-11"
+pub fn parse_cpu_list(list: &str) -> Option<Vec<usize>> {
+    let mut ids = Vec::new();
+    for part in list.trim().split(',').filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (start.parse::<usize>().ok()?, end.parse::<usize>().ok()?);
+                if end < start {
+                    return None;
+                }
+                ids.extend(start..=end);
+            }
+            None => ids.push(part.parse().ok()?),
+        }
+    }
+    Some(ids)
+}
+
+/// Node of the CPU the calling thread is running on
+#[cfg(target_os = "linux")]
+pub fn current_node() -> Option<usize> {
+    let (mut cpu, mut node) = (0u32, 0u32);
+    let result = unsafe {
+        libc::syscall(libc::SYS_getcpu, &mut cpu as *mut u32, &mut node as *mut u32, std::ptr::null_mut::<libc::c_void>())
+    };
+    (result == 0).then_some(node as usize)
+}
+
+/// Node holding the first page of a buffer, if it has been faulted in
+#[cfg(target_os = "linux")]
+pub fn page_node(data: &[u8]) -> Option<usize> {
+    if data.is_empty() {
+        return None;
+    }
+
+    // move_pages without target nodes only reports where each page lives
+    let pages = [data.as_ptr() as *mut libc::c_void];
+    let mut status = [-1i32];
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_move_pages,
+            0,
+            1usize,
+            pages.as_ptr(),
+            std::ptr::null::<i32>(),
+            status.as_mut_ptr(),
+            0,
+        )
+    };
+    (result == 0 && status[0] >= 0).then_some(status[0] as usize)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn current_node() -> Option<usize> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn page_node(_data: &[u8]) -> Option<usize> {
+    None
+}
+
+/// Where frame memory lived relative to the thread converting it
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NumaStatistics {
+    pub nodes: usize,
+    pub local_frames: u64,
+    /// Frames whose source or output buffer sat on another node
+    pub cross_node_frames: u64,
+    pub cross_node_bytes: u64,
+}
+
+impl NumaStatistics {
+    /// Record one converted frame
+    pub fn record(&mut self, thread_node: usize, buffers: &[(Option<usize>, usize)]) {
+        let remote_bytes: usize = buffers
+            .iter()
+            .filter(|(node, _)| node.is_some_and(|node| node != thread_node))
+            .map(|(_, bytes)| bytes)
+            .sum();
+
+        if remote_bytes > 0 {
+            self.cross_node_frames += 1;
+            self.cross_node_bytes += remote_bytes as u64;
+        } else {
+            self.local_frames += 1;
+        }
+    }
+
+    /// Percentage of sampled frames that touched remote memory
+    pub fn cross_node_percent(&self) -> f64 {
+        let total = self.local_frames + self.cross_node_frames;
+        if total > 0 {
+            self.cross_node_frames as f64 / total as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_list_and_cross_node_accounting() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), Some(vec![0, 1, 2, 3, 8, 10, 11]));
+        assert_eq!(parse_cpu_list("0"), Some(vec![0]));
+        assert_eq!(parse_cpu_list("3-1"), None);
+        assert_eq!(parse_cpu_list("a-b"), None);
+
+        let mut stats = NumaStatistics { nodes: 2, ..Default::default() };
+        stats.record(0, &[(Some(0), 100), (Some(0), 400)]);
+        stats.record(0, &[(Some(1), 100), (Some(0), 400)]);
+        // Unknown placement is not counted as remote
+        stats.record(1, &[(None, 100)]);
+
+        assert_eq!((stats.local_frames, stats.cross_node_frames, stats.cross_node_bytes), (2, 1, 100));
+        assert!((stats.cross_node_percent() - 100.0 / 3.0).abs() < 1e-9);
+    }
+}
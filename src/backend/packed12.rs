@@ -0,0 +1,58 @@
+// src/backend/packed12.rs - 12-bit Packed Grayscale Samples
+
+//! X-ray detectors commonly deliver 12-bit grayscale with two pixels in three
+//! bytes: the first byte holds the upper 8 bits of the even pixel, the second
+//! the low nibbles of the even (bits 0-3) and odd (bits 4-7) pixel, and the
+//! third the upper 8 bits of the odd pixel. Pixels are packed continuously
+//! across rows, so an odd pixel count leaves the last nibble unused.
+
+/// Bytes taking `pixels` packed samples
+pub fn packed_len(pixels: usize) -> usize {
+    pixels.div_ceil(2) * 3
+}
+
+/// Read the 12-bit sample of pixel `index`
+#[inline]
+pub fn sample(data: &[u8], index: usize) -> u16 {
+    let group = &data[index / 2 * 3..index / 2 * 3 + 3];
+    if index % 2 == 0 {
+        ((group[0] as u16) << 4) | (group[1] & 0x0F) as u16
+    } else {
+        ((group[2] as u16) << 4) | (group[1] >> 4) as u16
+    }
+}
+
+/// Unpack `pixels` samples, or None if the data is too short
+pub fn unpack(data: &[u8], pixels: usize) -> Option<Vec<u16>> {
+    if data.len() < packed_len(pixels) {
+        return None;
+    }
+    Some((0..pixels).map(|index| sample(data, index)).collect())
+}
+
+/// Pack 12-bit samples, ignoring their upper 4 bits
+pub fn pack(samples: &[u16]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(packed_len(samples.len()));
+    for pair in samples.chunks(2) {
+        let even = pair[0] & 0x0FFF;
+        let odd = pair.get(1).map_or(0, |odd| odd & 0x0FFF);
+        data.extend_from_slice(&[(even >> 4) as u8, (even & 0x0F) as u8 | (((odd & 0x0F) as u8) << 4), (odd >> 4) as u8]);
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_roundtrip() {
+        // 0xABC and 0x123 share the middle byte's nibbles
+        let data = pack(&[0xABC, 0x123, 0xFFF]);
+        assert_eq!(data, vec![0xAB, 0x3C, 0x12, 0xFF, 0x0F, 0x00]);
+        assert_eq!(packed_len(3), data.len());
+        assert_eq!(sample(&data, 1), 0x123);
+        assert_eq!(unpack(&data, 3).unwrap(), vec![0xABC, 0x123, 0xFFF]);
+        assert!(unpack(&data[..5], 3).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+// src/backend/palette.rs - Indexed Color Frames with a Palette from Metadata
+
+//! Some legacy imaging front-ends send 8-bit indexed frames: every byte is an
+//! index into a color table carried in the JSON metadata, either as `[r, g, b]`
+//! / `[r, g, b, a]` arrays or as `"#rrggbb"` strings:
+//!
+//! ```json
+//! {"palette": [[0, 0, 0], [255, 0, 0], "#00ff00"]}
+//! ```
+//!
+//! Indices past the end of the table show as opaque black.
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Metadata key holding the color table
+pub const METADATA_KEY: &str = "palette";
+
+/// Most entries an 8-bit index can address
+pub const MAX_ENTRIES: usize = 256;
+
+/// Errors that can occur while reading a palette
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PaletteError {
+    #[error("Indexed frame without a palette in its metadata")]
+    Missing,
+
+    #[error("Palette has {0} entries, an 8-bit index addresses at most 256")]
+    TooManyEntries(usize),
+
+    #[error("Invalid palette entry {index}: {entry}")]
+    InvalidEntry { index: usize, entry: String },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Channels(Vec<u8>),
+    Hex(String),
+}
+
+impl Entry {
+    fn to_rgba(&self) -> Option<[u8; 4]> {
+        match self {
+            Entry::Channels(channels) => match channels[..] {
+                [r, g, b] => Some([r, g, b, 255]),
+                [r, g, b, a] => Some([r, g, b, a]),
+                _ => None,
+            },
+            Entry::Hex(text) => {
+                let hex = text.strip_prefix('#')?;
+                if hex.len() != 6 {
+                    return None;
+                }
+                let value = u32::from_str_radix(hex, 16).ok()?;
+                Some([(value >> 16) as u8, (value >> 8) as u8, value as u8, 255])
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PaletteMetadata {
+    palette: Vec<serde_json::Value>,
+}
+
+/// Color table mapping 8-bit indices to RGBA
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    entries: Box<[[u8; 4]; MAX_ENTRIES]>,
+}
+
+impl Palette {
+    /// Build a palette from RGBA entries, padding the rest with opaque black
+    pub fn new(colors: &[[u8; 4]]) -> Result<Self, PaletteError> {
+        if colors.len() > MAX_ENTRIES {
+            return Err(PaletteError::TooManyEntries(colors.len()));
+        }
+        let mut entries = Box::new([[0, 0, 0, 255]; MAX_ENTRIES]);
+        entries[..colors.len()].copy_from_slice(colors);
+        Ok(Self { entries })
+    }
+
+    /// Read the palette of an indexed frame from its metadata
+    pub fn from_metadata(metadata: Option<&str>) -> Result<Self, PaletteError> {
+        let metadata = metadata
+            .filter(|text| text.contains(METADATA_KEY))
+            .and_then(|text| serde_json::from_str::<PaletteMetadata>(text).ok())
+            .ok_or(PaletteError::Missing)?;
+
+        let colors = metadata.palette
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                serde_json::from_value::<Entry>(value.clone())
+                    .ok()
+                    .and_then(|entry| entry.to_rgba())
+                    .ok_or_else(|| PaletteError::InvalidEntry { index, entry: value.to_string() })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(&colors)
+    }
+
+    /// Color of one index
+    #[inline]
+    pub fn color(&self, index: u8) -> [u8; 4] {
+        self.entries[index as usize]
+    }
+
+    /// Look every index up into a preallocated RGBA buffer
+    pub fn to_rgba(&self, indices: &[u8], dst: &mut [u8]) {
+        for (&index, out) in indices.iter().zip(dst.chunks_exact_mut(4)) {
+            out.copy_from_slice(&self.entries[index as usize]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_from_metadata() {
+        let palette = Palette::from_metadata(Some(r##"{"palette": [[0, 0, 0], [255, 0, 0, 128], "#00FF00"], "probe": "linear"}"##)).unwrap();
+        let mut rgba = [0u8; 16];
+        palette.to_rgba(&[1, 2, 0, 200], &mut rgba);
+        assert_eq!(rgba, [255, 0, 0, 128, 0, 255, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255]);
+
+        assert_eq!(Palette::from_metadata(None), Err(PaletteError::Missing));
+        assert_eq!(Palette::from_metadata(Some(r#"{"probe": "linear"}"#)), Err(PaletteError::Missing));
+        assert!(matches!(
+            Palette::from_metadata(Some(r#"{"palette": [[1, 2]]}"#)),
+            Err(PaletteError::InvalidEntry { index: 0, .. })
+        ));
+        assert_eq!(Palette::new(&[[0; 4]; 257]), Err(PaletteError::TooManyEntries(257)));
+    }
+}
@@ -0,0 +1,416 @@
+// src/backend/pipeline.rs - Configurable Image Filter Pipeline for Medical Imaging
+
+use std::collections::HashMap;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::adjustments::{ColorPalette, Colormap, DisplayLut, Gamma, WindowLevel};
+use crate::backend::filters::{self, ClaheParams, TemporalFilter};
+use crate::backend::types::FrameFormat;
+
+/// Filter stage that can be enabled and reordered at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StageKind {
+    Temporal,
+    Speckle,
+    Denoise,
+    Lut,
+    Gamma,
+    Clahe,
+    Sharpen,
+    EdgeEnhance,
+    Colormap,
+}
+
+impl StageKind {
+    /// All stages in default pipeline order
+    pub const ALL: [StageKind; 9] = [
+        StageKind::Temporal,
+        StageKind::Speckle,
+        StageKind::Denoise,
+        StageKind::Lut,
+        StageKind::Gamma,
+        StageKind::Clahe,
+        StageKind::Sharpen,
+        StageKind::EdgeEnhance,
+        StageKind::Colormap,
+    ];
+
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            StageKind::Temporal => "Persistence",
+            StageKind::Speckle => "Speckle Reduction",
+            StageKind::Denoise => "Denoise",
+            StageKind::Lut => "Window/Level LUT",
+            StageKind::Gamma => "Gamma",
+            StageKind::Clahe => "CLAHE",
+            StageKind::Sharpen => "Sharpen",
+            StageKind::EdgeEnhance => "Edge Enhancement",
+            StageKind::Colormap => "Colormap",
+        }
+    }
+
+    /// Check if the stage runs by default (strength-driven stages idle at zero strength)
+    pub fn enabled_by_default(self) -> bool {
+        !matches!(self, StageKind::Denoise | StageKind::Clahe | StageKind::Sharpen)
+    }
+}
+
+/// A stage slot in the pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PipelineStage {
+    pub kind: StageKind,
+    pub enabled: bool,
+}
+
+/// Ordered filter stages applied to converted RGBA frames
+///
+/// The pipeline is immutable while frames are in flight: the frame processor
+/// shares it behind an `Arc` and clones it on change. High bit depth sources
+/// are mapped through the window/level and gamma stages during conversion
+/// (in that order), so those two stages are skipped afterwards.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    stages: Vec<PipelineStage>,
+
+    // Stage parameters
+    window_level: WindowLevel,
+    gamma: Gamma,
+    clahe: ClaheParams,
+    persistence_frames: u32,
+    motion_threshold: u8,
+    speckle_strength: f32,
+    denoise_strength: f32,
+    sharpen_amount: f32,
+    edge_amount: f32,
+
+    // Precomputed tables
+    window_lut: DisplayLut,
+    gamma_lut: DisplayLut,
+    conversion_lut: DisplayLut,
+    palette: ColorPalette,
+}
+
+impl Pipeline {
+    /// Longest supported persistence window
+    pub const MAX_PERSISTENCE_FRAMES: u32 = 16;
+
+    /// Create a pipeline with the default stage order
+    pub fn new() -> Self {
+        Self {
+            stages: StageKind::ALL
+                .iter()
+                .map(|&kind| PipelineStage { kind, enabled: kind.enabled_by_default() })
+                .collect(),
+            window_level: WindowLevel::default(),
+            gamma: Gamma::default(),
+            clahe: ClaheParams::default(),
+            persistence_frames: 1,
+            motion_threshold: 24,
+            speckle_strength: 0.0,
+            denoise_strength: 0.5,
+            sharpen_amount: 0.5,
+            edge_amount: 0.0,
+            window_lut: DisplayLut::default(),
+            gamma_lut: DisplayLut::default(),
+            conversion_lut: DisplayLut::default(),
+            palette: ColorPalette::default(),
+        }
+    }
+
+    /// Get the ordered stages
+    pub fn stages(&self) -> &[PipelineStage] {
+        &self.stages
+    }
+
+    /// Check if a stage is enabled
+    pub fn is_enabled(&self, kind: StageKind) -> bool {
+        self.stages.iter().any(|s| s.kind == kind && s.enabled)
+    }
+
+    /// Enable or disable a stage
+    pub fn set_enabled(&mut self, kind: StageKind, enabled: bool) {
+        if let Some(stage) = self.stages.iter_mut().find(|s| s.kind == kind) {
+            stage.enabled = enabled;
+        }
+        self.rebuild_conversion_lut();
+    }
+
+    /// Reorder stages; stages missing from `order` keep their relative order at the end
+    pub fn set_order(&mut self, order: &[StageKind]) {
+        let mut reordered: Vec<PipelineStage> = Vec::with_capacity(self.stages.len());
+        for &kind in order {
+            if reordered.iter().any(|s| s.kind == kind) {
+                continue;
+            }
+            if let Some(stage) = self.stages.iter().find(|s| s.kind == kind) {
+                reordered.push(*stage);
+            }
+        }
+        for stage in &self.stages {
+            if !reordered.iter().any(|s| s.kind == stage.kind) {
+                reordered.push(*stage);
+            }
+        }
+        self.stages = reordered;
+    }
+
+    /// Set the strength parameter of a stage, returning false if the stage has none
+    ///
+    /// Speckle, denoise, sharpen and edge enhancement take `0.0..=1.0`, CLAHE its clip
+    /// limit, gamma its exponent and persistence the averaging window in frames (1 = off).
+    pub fn set_strength(&mut self, kind: StageKind, value: f32) -> bool {
+        match kind {
+            StageKind::Temporal => self.persistence_frames = (value.round().max(1.0) as u32).min(Self::MAX_PERSISTENCE_FRAMES),
+            StageKind::Speckle => self.speckle_strength = value.clamp(0.0, 1.0),
+            StageKind::Denoise => self.denoise_strength = value.clamp(0.0, 1.0),
+            StageKind::Sharpen => self.sharpen_amount = value.clamp(0.0, 1.0),
+            StageKind::EdgeEnhance => self.edge_amount = value.clamp(0.0, 1.0),
+            StageKind::Clahe => self.clahe = ClaheParams { clip_limit: ClaheParams::new(value).clip_limit, ..self.clahe },
+            StageKind::Gamma => self.set_gamma(Gamma::new(value)),
+            StageKind::Lut | StageKind::Colormap => return false,
+        }
+        true
+    }
+
+    /// Set the window/level of the LUT stage
+    pub fn set_window_level(&mut self, window_level: WindowLevel) {
+        self.window_level = window_level;
+        self.window_lut = DisplayLut::new(window_level, Gamma::default());
+        self.rebuild_conversion_lut();
+    }
+
+    /// Set the exponent of the gamma stage
+    pub fn set_gamma(&mut self, gamma: Gamma) {
+        self.gamma = gamma;
+        self.gamma_lut = DisplayLut::new(WindowLevel::default(), gamma);
+        self.rebuild_conversion_lut();
+    }
+
+    /// Set the colormap stage palette
+    pub fn set_colormap(&mut self, colormap: Colormap) {
+        self.palette = ColorPalette::new(colormap);
+    }
+
+    /// Set CLAHE parameters and enable (Some) or disable (None) the stage
+    pub fn set_clahe(&mut self, params: Option<ClaheParams>) {
+        if let Some(params) = params {
+            self.clahe = params;
+        }
+        self.set_enabled(StageKind::Clahe, params.is_some());
+    }
+
+    /// Get the LUT stage window/level
+    pub fn window_level(&self) -> WindowLevel {
+        self.window_level
+    }
+
+    /// Get the gamma stage exponent
+    pub fn gamma(&self) -> Gamma {
+        self.gamma
+    }
+
+    /// Get the colormap stage palette
+    pub fn colormap(&self) -> Colormap {
+        self.palette.colormap()
+    }
+
+    /// Get the CLAHE parameters if the stage is enabled
+    pub fn clahe(&self) -> Option<ClaheParams> {
+        self.is_enabled(StageKind::Clahe).then_some(self.clahe)
+    }
+
+    /// Get the lookup table used while converting high bit depth sources
+    pub fn conversion_lut(&self) -> &DisplayLut {
+        &self.conversion_lut
+    }
+
+    fn rebuild_conversion_lut(&mut self) {
+        let window_level = if self.is_enabled(StageKind::Lut) { self.window_level } else { WindowLevel::default() };
+        let gamma = if self.is_enabled(StageKind::Gamma) { self.gamma } else { Gamma::default() };
+        self.conversion_lut = DisplayLut::new(window_level, gamma);
+    }
+
+    /// Get the enabled stages that would modify a frame of the given format, in order
+    pub fn active_stages(&self, format: FrameFormat) -> Vec<StageKind> {
+        self.stages
+            .iter()
+            .filter(|stage| stage.enabled)
+            .map(|stage| stage.kind)
+            .filter(|&kind| match kind {
+                StageKind::Temporal => self.persistence_frames > 1,
+                StageKind::Speckle => self.speckle_strength > 0.0,
+                StageKind::Denoise => self.denoise_strength > 0.0,
+                StageKind::Sharpen => self.sharpen_amount > 0.0,
+                StageKind::EdgeEnhance => self.edge_amount > 0.0,
+                StageKind::Lut => !format.is_high_bit_depth() && !self.window_level.is_identity(),
+                StageKind::Gamma => !format.is_high_bit_depth() && !self.gamma.is_identity(),
+                StageKind::Clahe => true,
+                StageKind::Colormap => format.is_single_channel() && !self.palette.is_identity(),
+            })
+            .collect()
+    }
+
+    /// Get the persistence window in frames (1 = off)
+    pub fn persistence_frames(&self) -> u32 {
+        self.persistence_frames
+    }
+
+    /// Set the per-channel difference above which persistence treats a pixel as moving
+    pub fn set_motion_threshold(&mut self, threshold: u8) {
+        self.motion_threshold = threshold.max(1);
+    }
+
+    /// Get the speckle reduction strength (0.0 = off)
+    pub fn speckle_strength(&self) -> f32 {
+        self.speckle_strength
+    }
+
+    /// Get the edge enhancement amount (0.0 = off)
+    pub fn edge_amount(&self) -> f32 {
+        self.edge_amount
+    }
+
+    /// Apply a single stage in place to RGBA data converted from `format`
+    pub fn apply_stage(
+        &self,
+        kind: StageKind,
+        rgba_data: &mut [u8],
+        width: usize,
+        height: usize,
+        format: FrameFormat,
+        state: &mut PipelineState,
+    ) {
+        match kind {
+            StageKind::Temporal => {
+                state.temporal.apply_rgba(rgba_data, width, height, self.persistence_frames, self.motion_threshold)
+            }
+            StageKind::Speckle => {
+                filters::speckle_rgba(rgba_data, width, height, self.speckle_strength, format.is_single_channel())
+            }
+            StageKind::Denoise => filters::denoise_rgba(rgba_data, width, height, self.denoise_strength),
+            StageKind::Lut => self.window_lut.apply_rgba(rgba_data),
+            StageKind::Gamma => self.gamma_lut.apply_rgba(rgba_data),
+            StageKind::Clahe => filters::clahe_rgba(rgba_data, width, height, &self.clahe),
+            StageKind::Sharpen => filters::sharpen_rgba(rgba_data, width, height, self.sharpen_amount),
+            StageKind::EdgeEnhance => filters::edge_enhance_rgba(rgba_data, width, height, self.edge_amount),
+            StageKind::Colormap => self.palette.apply_rgba(rgba_data),
+        }
+    }
+}
+
+/// Mutable per-stream state of stateful stages
+#[derive(Debug, Default)]
+pub struct PipelineState {
+    pub temporal: TemporalFilter,
+}
+
+impl PipelineState {
+    /// Forget all history (new stream, disconnect or stage disabled)
+    pub fn reset(&mut self) {
+        self.temporal.reset();
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Accumulated execution time of a stage
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StageTiming {
+    pub runs: u64,
+    pub last: Duration,
+    pub total: Duration,
+}
+
+impl StageTiming {
+    /// Record one execution
+    pub fn record(&mut self, elapsed: Duration) {
+        self.runs += 1;
+        self.last = elapsed;
+        self.total += elapsed;
+    }
+
+    /// Get average execution time in milliseconds
+    pub fn average_ms(&self) -> f64 {
+        if self.runs > 0 {
+            self.total.as_secs_f64() * 1000.0 / self.runs as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Per-stage statistics reported to the frontend
+#[derive(Debug, Clone)]
+pub struct StageStatistics {
+    pub kind: StageKind,
+    pub enabled: bool,
+    pub runs: u64,
+    pub last_ms: f64,
+    pub average_ms: f64,
+}
+
+/// Combine the pipeline order with recorded timings
+pub fn stage_statistics(pipeline: &Pipeline, timings: &HashMap<StageKind, StageTiming>) -> Vec<StageStatistics> {
+    pipeline
+        .stages()
+        .iter()
+        .map(|stage| {
+            let timing = timings.get(&stage.kind).copied().unwrap_or_default();
+            StageStatistics {
+                kind: stage.kind,
+                enabled: stage.enabled,
+                runs: timing.runs,
+                last_ms: timing.last.as_secs_f64() * 1000.0,
+                average_ms: timing.average_ms(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorder_and_enable() {
+        let mut pipeline = Pipeline::new();
+        pipeline.set_order(&[StageKind::Sharpen, StageKind::Sharpen, StageKind::Denoise]);
+
+        let order: Vec<StageKind> = pipeline.stages().iter().map(|s| s.kind).collect();
+        assert_eq!(order[0], StageKind::Sharpen);
+        assert_eq!(order[1], StageKind::Denoise);
+        assert_eq!(order.len(), StageKind::ALL.len());
+
+        // Identity adjustments and disabled filters do nothing
+        assert!(pipeline.active_stages(FrameFormat::Grayscale).is_empty());
+
+        pipeline.set_enabled(StageKind::Sharpen, true);
+        pipeline.set_gamma(Gamma::new(2.0));
+        assert_eq!(pipeline.active_stages(FrameFormat::Grayscale), vec![StageKind::Sharpen, StageKind::Gamma]);
+
+        // High bit depth sources get gamma during conversion instead
+        assert_eq!(pipeline.active_stages(FrameFormat::YUV10), vec![StageKind::Sharpen]);
+        assert!(!pipeline.conversion_lut().is_identity());
+        pipeline.set_enabled(StageKind::Gamma, false);
+        assert!(pipeline.conversion_lut().is_identity());
+    }
+
+    #[test]
+    fn test_clahe_toggle() {
+        let mut pipeline = Pipeline::new();
+        assert_eq!(pipeline.clahe(), None);
+
+        pipeline.set_clahe(Some(ClaheParams::new(4.0)));
+        assert_eq!(pipeline.clahe().map(|p| p.clip_limit), Some(4.0));
+        assert!(pipeline.active_stages(FrameFormat::RGB).contains(&StageKind::Clahe));
+
+        pipeline.set_clahe(None);
+        assert_eq!(pipeline.clahe(), None);
+    }
+}
@@ -0,0 +1,160 @@
+// src/backend/pixel_pool.rs - Pooled Display Buffers Shared Directly with the UI
+
+use std::collections::VecDeque;
+
+#[cfg(feature = "ui")]
+use slint::{Rgba8Pixel, SharedPixelBuffer};
+
+/// RGBA frame buffer that Slint can display without copying
+#[cfg(feature = "ui")]
+pub type RgbaBuffer = SharedPixelBuffer<Rgba8Pixel>;
+
+#[cfg(not(feature = "ui"))]
+pub use headless::RgbaBuffer;
+
+#[cfg(not(feature = "ui"))]
+mod headless {
+    use std::sync::Arc;
+
+    /// RGBA frame buffer for builds without the UI, shared like Slint's pixel buffers
+    ///
+    /// Clones share the pixels; the first write through a shared clone copies them.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct RgbaBuffer {
+        width: u32,
+        height: u32,
+        data: Arc<Vec<u8>>,
+    }
+
+    impl RgbaBuffer {
+        /// Create a transparent black buffer
+        pub fn new(width: u32, height: u32) -> Self {
+            Self { width, height, data: Arc::new(vec![0; width as usize * height as usize * 4]) }
+        }
+
+        /// Width in pixels
+        pub fn width(&self) -> u32 {
+            self.width
+        }
+
+        /// Height in pixels
+        pub fn height(&self) -> u32 {
+            self.height
+        }
+
+        /// Tightly packed RGBA8 pixels
+        pub fn as_bytes(&self) -> &[u8] {
+            &self.data
+        }
+
+        /// Mutable pixels, copied first if another clone still shares them
+        pub fn make_mut_bytes(&mut self) -> &mut [u8] {
+            Arc::make_mut(&mut self.data)
+        }
+    }
+}
+
+/// Buffers kept for reuse: one being displayed, one being converted, one in flight
+pub const DEFAULT_POOL_CAPACITY: usize = 3;
+
+/// Recycles display buffers between the frame processor and the UI
+///
+/// The processor converts straight into an acquired buffer and the UI only swaps
+/// it in; once the UI replaces a frame it hands the old buffer back here.
+pub struct PixelBufferPool {
+    buffers: parking_lot::Mutex<VecDeque<RgbaBuffer>>,
+    capacity: usize,
+    stats: parking_lot::Mutex<PoolStatistics>,
+}
+
+/// Buffer reuse counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStatistics {
+    pub reused: u64,
+    pub allocated: u64,
+}
+
+impl PixelBufferPool {
+    /// Create a pool holding at most `capacity` idle buffers
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffers: parking_lot::Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity.max(1),
+            stats: parking_lot::Mutex::new(PoolStatistics::default()),
+        }
+    }
+
+    /// Take a buffer of the given size, allocating only if none is idle
+    ///
+    /// The contents are whatever the previous frame left behind; callers overwrite
+    /// every pixel. A recycled buffer still referenced elsewhere (a snapshot, say)
+    /// is copied on its first write rather than modified under that holder.
+    pub fn acquire(&self, width: u32, height: u32) -> RgbaBuffer {
+        let recycled = {
+            let mut buffers = self.buffers.lock();
+            let position = buffers.iter().position(|buffer| buffer.width() == width && buffer.height() == height);
+            position.and_then(|index| buffers.remove(index))
+        };
+
+        let mut stats = self.stats.lock();
+        match recycled {
+            Some(buffer) => {
+                stats.reused += 1;
+                buffer
+            }
+            None => {
+                stats.allocated += 1;
+                RgbaBuffer::new(width, height)
+            }
+        }
+    }
+
+    /// Return a buffer that is no longer displayed
+    ///
+    /// Buffers of a stale size are dropped, so a resolution change drains the pool.
+    pub fn recycle(&self, buffer: RgbaBuffer) {
+        let mut buffers = self.buffers.lock();
+        buffers.retain(|idle| idle.width() == buffer.width() && idle.height() == buffer.height());
+        if buffers.len() < self.capacity {
+            buffers.push_back(buffer);
+        }
+    }
+
+    /// Drop all idle buffers, e.g. before frames change size
+    pub fn clear(&self) {
+        self.buffers.lock().clear();
+    }
+
+    /// Get buffer reuse counters
+    pub fn statistics(&self) -> PoolStatistics {
+        *self.stats.lock()
+    }
+}
+
+impl Default for PixelBufferPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_POOL_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_reuses_matching_buffers() {
+        let pool = PixelBufferPool::new(2);
+
+        let first = pool.acquire(4, 2);
+        pool.recycle(first);
+        let reused = pool.acquire(4, 2);
+        assert_eq!((reused.width(), reused.height()), (4, 2));
+        assert_eq!(pool.statistics(), PoolStatistics { reused: 1, allocated: 1 });
+
+        // A new resolution drops idle buffers of the old one
+        pool.recycle(reused);
+        pool.recycle(RgbaBuffer::new(8, 8));
+        let _ = pool.acquire(4, 2);
+        assert_eq!(pool.statistics().allocated, 2);
+    }
+}
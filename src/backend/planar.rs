@@ -0,0 +1,298 @@
+// src/backend/planar.rs - Planar and Semi-Planar YUV Layouts from Video Pipelines
+
+//! Video pipelines (decoders, capture cards, GStreamer) usually hand over YUV
+//! in planes: a full-resolution Y plane followed by subsampled U and V planes
+//! (I420, YV12) or one interleaved UV plane (NV12, NV21). A producer writing
+//! such frames into a slot keeps `format_code` YUV and describes the layout in
+//! the JSON metadata:
+//!
+//! ```json
+//! {"plane_layout": "nv12", "planes": [{"offset": 0, "stride": 1088}, {"offset": 835584, "stride": 1088}]}
+//! ```
+//!
+//! `planes` is optional; without it the planes are assumed to follow each other
+//! tightly packed. Planar frames are converted to packed BGR on arrival, with
+//! the matrix and range from the metadata (see `yuv`), so the converters, crop, ROI statistics and broadcasters
+//! only ever see packed pixels.
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::backend::stride::FLAG_ROW_STRIDE;
+use crate::backend::types::{FrameFormat, FrameHeader, RawFrame};
+use crate::backend::yuv::{YuvConverter, YuvEncoding};
+
+/// Metadata key naming the plane layout
+pub const METADATA_KEY: &str = "plane_layout";
+
+/// How chroma is subsampled and stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChromaLayout {
+    /// Y, U, V planes; chroma halved in both directions
+    I420,
+    /// Y, V, U planes; chroma halved in both directions
+    Yv12,
+    /// Y plane and interleaved UV; chroma halved in both directions
+    Nv12,
+    /// Y plane and interleaved VU; chroma halved in both directions
+    Nv21,
+    /// Y, U, V planes; chroma halved horizontally
+    Yuv422p,
+    /// Y, U, V planes at full resolution
+    Yuv444p,
+}
+
+impl ChromaLayout {
+    /// Parse a layout name as used in metadata
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "i420" | "yuv420p" => Some(Self::I420),
+            "yv12" => Some(Self::Yv12),
+            "nv12" => Some(Self::Nv12),
+            "nv21" => Some(Self::Nv21),
+            "yuv422p" | "i422" => Some(Self::Yuv422p),
+            "yuv444p" | "i444" => Some(Self::Yuv444p),
+            _ => None,
+        }
+    }
+
+    /// Horizontal and vertical chroma subsampling factors
+    pub fn subsampling(&self) -> (usize, usize) {
+        match self {
+            Self::I420 | Self::Yv12 | Self::Nv12 | Self::Nv21 => (2, 2),
+            Self::Yuv422p => (2, 1),
+            Self::Yuv444p => (1, 1),
+        }
+    }
+
+    /// Check if U and V share one interleaved plane
+    pub fn is_semi_planar(&self) -> bool {
+        matches!(self, Self::Nv12 | Self::Nv21)
+    }
+
+    fn plane_count(&self) -> usize {
+        if self.is_semi_planar() { 2 } else { 3 }
+    }
+
+    /// Width in bytes and height in rows of a plane
+    fn plane_size(&self, index: usize, width: usize, height: usize) -> (usize, usize) {
+        if index == 0 {
+            return (width, height);
+        }
+        let (sx, sy) = self.subsampling();
+        let (chroma_width, chroma_height) = (width.div_ceil(sx), height.div_ceil(sy));
+        if self.is_semi_planar() {
+            (chroma_width * 2, chroma_height)
+        } else {
+            (chroma_width, chroma_height)
+        }
+    }
+}
+
+/// Where a plane starts in the slot and how far apart its rows are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Plane {
+    pub offset: usize,
+    pub stride: usize,
+}
+
+#[derive(Deserialize)]
+struct LayoutMetadata {
+    #[serde(rename = "plane_layout")]
+    layout: String,
+    #[serde(default)]
+    planes: Option<Vec<Plane>>,
+}
+
+/// Errors that can occur while reading a planar frame
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PlanarError {
+    #[error("Unknown plane layout: {0}")]
+    UnknownLayout(String),
+
+    #[error("Plane layout {layout:?} needs {expected} planes, metadata lists {actual}")]
+    PlaneCount { layout: ChromaLayout, expected: usize, actual: usize },
+
+    #[error("Plane {index} ({offset} + {stride} x {rows} rows) does not fit in {data_len} bytes of data")]
+    PlaneOutOfBounds { index: usize, offset: usize, stride: usize, rows: usize, data_len: usize },
+}
+
+/// Plane layout of a frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanarLayout {
+    pub chroma: ChromaLayout,
+    pub planes: Vec<Plane>,
+}
+
+impl PlanarLayout {
+    /// Tightly packed planes following each other
+    pub fn contiguous(chroma: ChromaLayout, width: usize, height: usize) -> Self {
+        let mut offset = 0;
+        let planes = (0..chroma.plane_count())
+            .map(|index| {
+                let (row, rows) = chroma.plane_size(index, width, height);
+                let plane = Plane { offset, stride: row };
+                offset += row * rows;
+                plane
+            })
+            .collect();
+        Self { chroma, planes }
+    }
+
+    /// Layout of a YUV frame whose metadata names one; None for packed frames
+    pub fn from_frame(header: &FrameHeader, metadata: Option<&str>) -> Result<Option<Self>, PlanarError> {
+        if FrameFormat::from_code(header.format_code) != FrameFormat::YUV {
+            return Ok(None);
+        }
+        // Only parse metadata that mentions the key
+        let Some(metadata) = metadata.filter(|text| text.contains(METADATA_KEY)) else {
+            return Ok(None);
+        };
+        let Ok(metadata) = serde_json::from_str::<LayoutMetadata>(metadata) else {
+            return Ok(None);
+        };
+
+        let chroma = ChromaLayout::from_name(&metadata.layout).ok_or(PlanarError::UnknownLayout(metadata.layout))?;
+        let layout = match metadata.planes {
+            Some(planes) if planes.len() != chroma.plane_count() => {
+                return Err(PlanarError::PlaneCount { layout: chroma, expected: chroma.plane_count(), actual: planes.len() });
+            }
+            Some(planes) => Self { chroma, planes },
+            None => Self::contiguous(chroma, header.width as usize, header.height as usize),
+        };
+        Ok(Some(layout))
+    }
+
+    /// Check that every plane lies within `data_len` bytes
+    ///
+    /// Offsets and strides come from the producer, so the end of a plane is
+    /// computed with checked arithmetic; the conversion indexes within it.
+    fn check_bounds(&self, width: usize, height: usize, data_len: usize) -> Result<(), PlanarError> {
+        for (index, plane) in self.planes.iter().enumerate() {
+            let (row, rows) = self.chroma.plane_size(index, width, height);
+            let end = rows
+                .checked_sub(1)
+                .and_then(|last| plane.stride.checked_mul(last))
+                .and_then(|last_row| plane.offset.checked_add(last_row))
+                .and_then(|last_row| last_row.checked_add(row));
+            let fits = plane.stride >= row && row > 0 && end.is_some_and(|end| end <= data_len);
+            if !fits {
+                return Err(PlanarError::PlaneOutOfBounds { index, offset: plane.offset, stride: plane.stride, rows, data_len });
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert a planar frame to packed BGR
+    pub fn to_bgr(&self, raw_frame: RawFrame) -> Result<RawFrame, PlanarError> {
+        let width = raw_frame.header.width as usize;
+        let height = raw_frame.header.height as usize;
+        let data = &raw_frame.data;
+        self.check_bounds(width, height, data.len())?;
+
+        let encoding = YuvEncoding::from_frame(&raw_frame.header, raw_frame.metadata.as_deref(), true);
+        let converter = YuvConverter::new(&encoding);
+        let (sx, sy) = self.chroma.subsampling();
+        let luma = self.planes[0];
+        let mut bgr = vec![0u8; width * height * 3];
+        for (y, out_row) in bgr.chunks_exact_mut(width * 3).enumerate() {
+            let y_row = &data[luma.offset + y * luma.stride..][..width];
+            let cy = y / sy;
+            for (x, out) in out_row.chunks_exact_mut(3).enumerate() {
+                let cx = x / sx;
+                let (u, v) = self.chroma_at(data, cx, cy);
+                let [r, g, b] = converter.to_rgb(y_row[x], u, v);
+                out.copy_from_slice(&[b, g, r]);
+            }
+        }
+
+        let mut header = raw_frame.header;
+        header.format_code = FrameFormat::BGR.to_code();
+        header.bytes_per_pixel = 3;
+        header.data_size = bgr.len() as u32;
+        header.flags &= !FLAG_ROW_STRIDE;
+        Ok(RawFrame {
+            header,
+            data: Arc::from(bgr.into_boxed_slice()),
+            metadata: raw_frame.metadata,
+            received_at: raw_frame.received_at,
+        })
+    }
+
+    fn chroma_at(&self, data: &[u8], cx: usize, cy: usize) -> (u8, u8) {
+        match self.chroma {
+            ChromaLayout::Nv12 | ChromaLayout::Nv21 => {
+                let uv = self.planes[1];
+                let at = uv.offset + cy * uv.stride + cx * 2;
+                let (first, second) = (data[at], data[at + 1]);
+                if self.chroma == ChromaLayout::Nv12 { (first, second) } else { (second, first) }
+            }
+            _ => {
+                let (u_index, v_index) = if self.chroma == ChromaLayout::Yv12 { (2, 1) } else { (1, 2) };
+                let (u, v) = (self.planes[u_index], self.planes[v_index]);
+                (data[u.offset + cy * u.stride + cx], data[v.offset + cy * v.stride + cx])
+            }
+        }
+    }
+}
+
+/// Convert a planar frame to packed BGR, returning packed frames unchanged
+pub fn unpack_planes(raw_frame: RawFrame) -> Result<RawFrame, PlanarError> {
+    match PlanarLayout::from_frame(&raw_frame.header, raw_frame.metadata.as_deref())? {
+        Some(layout) => layout.to_bgr(raw_frame),
+        None => Ok(raw_frame),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(data: Vec<u8>, metadata: &str) -> RawFrame {
+        let header = FrameHeader { data_size: data.len() as u32, ..FrameHeader::for_test(2, 2, 1, FrameFormat::YUV.to_code()) };
+        RawFrame::new(header, Arc::from(data), Some(metadata.to_string()))
+    }
+
+    #[test]
+    fn test_planar_layouts_convert_to_bgr() {
+        // Mid-gray luma with neutral chroma, in three layouts
+        let i420 = unpack_planes(frame(vec![126, 126, 126, 126, 128, 128], r#"{"plane_layout": "i420"}"#)).unwrap();
+        let nv12 = unpack_planes(frame(vec![126, 126, 126, 126, 128, 128], r#"{"plane_layout": "nv12"}"#)).unwrap();
+        // Luma rows padded to 4 bytes, chroma plane placed after a gap
+        let strided = unpack_planes(frame(
+            vec![126, 126, 0, 0, 126, 126, 0, 0, 9, 128, 128],
+            r#"{"plane_layout": "nv12", "planes": [{"offset": 0, "stride": 4}, {"offset": 9, "stride": 2}]}"#,
+        ))
+        .unwrap();
+
+        for converted in [&i420, &nv12, &strided] {
+            assert_eq!(converted.header.format_code, FrameFormat::BGR.to_code());
+            assert_eq!(converted.header.bytes_per_pixel, 3);
+            assert_eq!(&converted.data[..], &[128; 12]);
+        }
+    }
+
+    #[test]
+    fn test_packed_and_invalid_layouts() {
+        let packed = unpack_planes(frame(vec![1, 2, 3, 4], r#"{"probe": "L12"}"#)).unwrap();
+        assert_eq!(packed.header.format_code, FrameFormat::YUV.to_code());
+
+        assert!(matches!(unpack_planes(frame(vec![0; 6], r#"{"plane_layout": "p010"}"#)), Err(PlanarError::UnknownLayout(_))));
+        assert!(matches!(unpack_planes(frame(vec![0; 5], r#"{"plane_layout": "i420"}"#)), Err(PlanarError::PlaneOutOfBounds { .. })));
+        assert!(matches!(
+            unpack_planes(frame(vec![0; 6], r#"{"plane_layout": "i420", "planes": [{"offset": 0, "stride": 2}]}"#)),
+            Err(PlanarError::PlaneCount { .. })
+        ));
+
+        // Offsets that overflow the bounds computation are rejected, not wrapped
+        assert!(matches!(
+            unpack_planes(frame(
+                vec![0; 6],
+                r#"{"plane_layout": "nv12", "planes": [{"offset": 0, "stride": 2}, {"offset": 18446744073709551615, "stride": 2}]}"#
+            )),
+            Err(PlanarError::PlaneOutOfBounds { index: 1, .. })
+        ));
+    }
+}
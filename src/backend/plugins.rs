@@ -0,0 +1,406 @@
+// src/backend/plugins.rs - Frame Filter Plugins Loaded from Shared Libraries
+
+//! Third-party enhancement stages, loaded at startup from a plugin directory.
+//!
+//! Each plugin is described by a `*.plugin.json` manifest next to its library:
+//!
+//! ```json
+//! { "name": "vendor-enhance", "version": "1.2.0", "library": "libvendor_enhance.so",
+//!   "abi_version": 1, "min_viewer_version": "0.2.0" }
+//! ```
+//!
+//! A native library exports `mivi_frame_filter_plugin`, returning a pointer to a
+//! static [`FrameFilterPluginV1`]. Manifests with `"runtime": "wasm"` name a
+//! sandboxed WebAssembly module instead (see [`crate::backend::wasm_filter`])
+//! for filters that are not trusted with the viewer process.
+//!
+//! Plugins run in manifest file name order on filtered RGBA frames in sensor
+//! geometry, after the built-in pipeline and before segmentation. An instance
+//! is never called concurrently, but calls may come from different threads. A
+//! plugin that reports an error is disabled so the display never depends on a
+//! broken stage. Native libraries need the `plugins` feature and WebAssembly
+//! modules the `wasm` feature; without them loading reports [`PluginError::Unavailable`].
+
+use std::ffi::{c_char, c_void};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::backend::wasm_filter::{WasmFilter, WASM_ABI_VERSION};
+
+/// Version of the C plugin ABI this viewer implements
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Symbol every plugin library exports
+pub const PLUGIN_ENTRY_SYMBOL: &str = "mivi_frame_filter_plugin";
+
+/// File name suffix of plugin manifests
+pub const MANIFEST_SUFFIX: &str = ".plugin.json";
+
+/// Errors that can occur while loading or running a plugin
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("This plugin type is not available in this build (enable the \"{0}\" feature)")]
+    Unavailable(&'static str),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid manifest {path}: {reason}")]
+    Manifest { path: PathBuf, reason: String },
+
+    #[error("Plugin {name} uses ABI version {found}, this viewer supports {expected}")]
+    AbiMismatch { name: String, expected: u32, found: u32 },
+
+    #[error("Plugin {name} requires viewer version {required} or newer")]
+    ViewerTooOld { name: String, required: String },
+
+    #[error("Failed to load plugin library: {0}")]
+    Library(String),
+
+    #[error("WebAssembly plugin error: {0}")]
+    Wasm(String),
+
+    #[error("Plugin {name} failed with code {code}")]
+    Process { name: String, code: i32 },
+}
+
+/// How a plugin's code is run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginRuntime {
+    /// Shared library loaded into the viewer process
+    #[default]
+    Native,
+    /// WebAssembly module run in a sandbox without host access
+    Wasm,
+}
+
+impl PluginRuntime {
+    /// ABI version plugins for this runtime must declare
+    pub fn abi_version(self) -> u32 {
+        match self {
+            PluginRuntime::Native => PLUGIN_ABI_VERSION,
+            PluginRuntime::Wasm => WASM_ABI_VERSION,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PluginRuntime::Native => "native",
+            PluginRuntime::Wasm => "wasm",
+        }
+    }
+}
+
+/// Plugin description read before its library is loaded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    pub version: String,
+    /// Library or module path, relative to the manifest
+    pub library: PathBuf,
+    #[serde(default)]
+    pub runtime: PluginRuntime,
+    pub abi_version: u32,
+    #[serde(default)]
+    pub min_viewer_version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl PluginManifest {
+    /// Read a manifest file
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(|e| PluginError::Manifest { path: path.to_path_buf(), reason: e.to_string() })
+    }
+
+    /// Check the ABI and viewer versions before anything is loaded
+    pub fn check_compatibility(&self, viewer_version: &str) -> Result<(), PluginError> {
+        let expected = self.runtime.abi_version();
+        if self.abi_version != expected {
+            return Err(PluginError::AbiMismatch { name: self.name.clone(), expected, found: self.abi_version });
+        }
+        if let Some(required) = &self.min_viewer_version {
+            let too_old = match (parse_version(required), parse_version(viewer_version)) {
+                (Some(required), Some(viewer)) => viewer < required,
+                _ => true,
+            };
+            if too_old {
+                return Err(PluginError::ViewerTooOld { name: self.name.clone(), required: required.clone() });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse `major.minor.patch`, ignoring any pre-release suffix
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let core = version.trim().split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+/// Function table a plugin library exports (ABI version 1)
+///
+/// `process` filters tightly packed RGBA8 pixels (`width * 4` bytes per row)
+/// in place and returns 0 on success. `reset` drops any temporal state when
+/// the stream changes and may be null.
+#[repr(C)]
+pub struct FrameFilterPluginV1 {
+    pub abi_version: u32,
+    pub create: unsafe extern "C" fn() -> *mut c_void,
+    pub process: unsafe extern "C" fn(instance: *mut c_void, rgba: *mut u8, width: u32, height: u32) -> i32,
+    pub reset: Option<unsafe extern "C" fn(instance: *mut c_void)>,
+    pub destroy: unsafe extern "C" fn(instance: *mut c_void),
+}
+
+/// Type of the exported `mivi_frame_filter_plugin` function
+pub type PluginEntry = unsafe extern "C" fn() -> *const FrameFilterPluginV1;
+
+/// An enhancement stage run on every converted frame
+///
+/// Implemented by loaded plugins; in-process stages can implement it directly.
+pub trait FrameFilter: Send {
+    /// Name shown in logs
+    fn name(&self) -> &str;
+
+    /// Filter RGBA pixels in place
+    fn process(&mut self, rgba_data: &mut [u8], width: usize, height: usize) -> Result<(), PluginError>;
+
+    /// Forget temporal state (new stream or crop)
+    fn reset(&mut self) {}
+}
+
+/// A filter plugin backed by a shared library
+pub struct LoadedPlugin {
+    manifest: PluginManifest,
+    table: *const FrameFilterPluginV1,
+    instance: *mut c_void,
+    // Declared last so the library outlives the instance it created
+    _library: Library,
+}
+
+// The ABI requires instances to tolerate calls from any thread; FrameFilter users never call concurrently
+unsafe impl Send for LoadedPlugin {}
+
+impl LoadedPlugin {
+    /// Load a native plugin library, checking the ABI version it reports
+    pub fn load(manifest: PluginManifest, library_path: &Path) -> Result<Self, PluginError> {
+        let library = Library::open(library_path)?;
+        let table = library.entry()?;
+        let found = unsafe { (*table).abi_version };
+        if found != PLUGIN_ABI_VERSION {
+            return Err(PluginError::AbiMismatch { name: manifest.name.clone(), expected: PLUGIN_ABI_VERSION, found });
+        }
+
+        let instance = unsafe { ((*table).create)() };
+        if instance.is_null() {
+            return Err(PluginError::Library(format!("{} could not create an instance", manifest.name)));
+        }
+        Ok(Self { manifest, table, instance, _library: library })
+    }
+
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+}
+
+impl FrameFilter for LoadedPlugin {
+    fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    fn process(&mut self, rgba_data: &mut [u8], width: usize, height: usize) -> Result<(), PluginError> {
+        debug_assert!(rgba_data.len() >= width * height * 4);
+        let code = unsafe { ((*self.table).process)(self.instance, rgba_data.as_mut_ptr(), width as u32, height as u32) };
+        match code {
+            0 => Ok(()),
+            code => Err(PluginError::Process { name: self.manifest.name.clone(), code }),
+        }
+    }
+
+    fn reset(&mut self) {
+        if let Some(reset) = unsafe { (*self.table).reset } {
+            unsafe { reset(self.instance) };
+        }
+    }
+}
+
+impl Drop for LoadedPlugin {
+    fn drop(&mut self) {
+        unsafe { ((*self.table).destroy)(self.instance) };
+    }
+}
+
+#[cfg(feature = "plugins")]
+struct Library(libloading::Library);
+
+#[cfg(feature = "plugins")]
+impl Library {
+    fn open(path: &Path) -> Result<Self, PluginError> {
+        unsafe { libloading::Library::new(path) }
+            .map(Self)
+            .map_err(|e| PluginError::Library(format!("{}: {}", path.display(), e)))
+    }
+
+    fn entry(&self) -> Result<*const FrameFilterPluginV1, PluginError> {
+        let symbol = format!("{}\0", PLUGIN_ENTRY_SYMBOL);
+        let entry: PluginEntry = unsafe { *self.0.get(symbol.as_bytes()) }.map_err(|e| PluginError::Library(e.to_string()))?;
+        let table = unsafe { entry() };
+        if table.is_null() {
+            return Err(PluginError::Library(format!("{} returned no function table", PLUGIN_ENTRY_SYMBOL)));
+        }
+        Ok(table)
+    }
+}
+
+/// Stand-in when the `plugins` feature is disabled
+#[cfg(not(feature = "plugins"))]
+struct Library;
+
+#[cfg(not(feature = "plugins"))]
+impl Library {
+    fn open(_path: &Path) -> Result<Self, PluginError> {
+        Err(PluginError::Unavailable("plugins"))
+    }
+
+    fn entry(&self) -> Result<*const FrameFilterPluginV1, PluginError> {
+        Err(PluginError::Unavailable("plugins"))
+    }
+}
+
+/// Find plugin manifests in a directory, in the order the plugins run
+pub fn find_manifests(dir: &Path) -> Result<Vec<PathBuf>, PluginError> {
+    let mut manifests: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.ends_with(MANIFEST_SUFFIX)))
+        .collect();
+    manifests.sort();
+    Ok(manifests)
+}
+
+/// Load the plugin a manifest describes, checking versions before any code is loaded
+pub fn load_plugin(manifest_path: &Path) -> Result<(PluginManifest, Box<dyn FrameFilter>), PluginError> {
+    let manifest = PluginManifest::load(manifest_path)?;
+    manifest.check_compatibility(env!("CARGO_PKG_VERSION"))?;
+
+    let library_path = manifest_path.parent().unwrap_or(Path::new(".")).join(&manifest.library);
+    let filter: Box<dyn FrameFilter> = match manifest.runtime {
+        PluginRuntime::Native => Box::new(LoadedPlugin::load(manifest.clone(), &library_path)?),
+        PluginRuntime::Wasm => Box::new(WasmFilter::load(&manifest.name, &library_path)?),
+    };
+    Ok((manifest, filter))
+}
+
+/// Load every plugin in a directory, skipping (and logging) those that fail
+pub fn load_dir(dir: &Path) -> Result<Vec<Box<dyn FrameFilter>>, PluginError> {
+    let mut filters: Vec<Box<dyn FrameFilter>> = Vec::new();
+    for path in find_manifests(dir)? {
+        match load_plugin(&path) {
+            Ok((manifest, filter)) => {
+                info!("🧩 Plugin {} {} ({}) loaded from {}",
+                      manifest.name, manifest.version, manifest.runtime.name(), path.display());
+                filters.push(filter);
+            }
+            Err(e) => warn!("⚠️ Skipping plugin {}: {}", path.display(), e),
+        }
+    }
+    Ok(filters)
+}
+
+/// A filter in the processing chain, disabled after its first failure
+pub struct PluginStage {
+    filter: parking_lot::Mutex<Box<dyn FrameFilter>>,
+    failed: AtomicBool,
+}
+
+impl PluginStage {
+    pub fn new(filter: Box<dyn FrameFilter>) -> Self {
+        Self { filter: parking_lot::Mutex::new(filter), failed: AtomicBool::new(false) }
+    }
+
+    /// Run the filter on a frame unless it failed before
+    pub fn process_rgba(&self, rgba_data: &mut [u8], width: usize, height: usize) {
+        if self.failed.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut filter = self.filter.lock();
+        if let Err(e) = filter.process(rgba_data, width, height) {
+            warn!("⚠️ Disabling plugin {}: {}", filter.name(), e);
+            self.failed.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub fn reset(&self) {
+        self.filter.lock().reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(abi_version: u32, min_viewer_version: Option<&str>) -> PluginManifest {
+        PluginManifest {
+            name: "test".to_string(),
+            version: "1.0.0".to_string(),
+            library: PathBuf::from("libtest.so"),
+            runtime: PluginRuntime::Native,
+            abi_version,
+            min_viewer_version: min_viewer_version.map(str::to_string),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_compatibility_checks() {
+        assert!(manifest(PLUGIN_ABI_VERSION, None).check_compatibility("0.2.0").is_ok());
+        assert!(manifest(PLUGIN_ABI_VERSION, Some("0.2")).check_compatibility("0.2.0").is_ok());
+        assert!(matches!(
+            manifest(PLUGIN_ABI_VERSION + 1, None).check_compatibility("0.2.0"),
+            Err(PluginError::AbiMismatch { .. })
+        ));
+        assert!(matches!(
+            manifest(PLUGIN_ABI_VERSION, Some("0.10.0")).check_compatibility("0.2.0"),
+            Err(PluginError::ViewerTooOld { .. })
+        ));
+        assert!(manifest(PLUGIN_ABI_VERSION, Some("latest")).check_compatibility("0.2.0").is_err());
+        assert_eq!(parse_version("1.4.2-beta"), Some((1, 4, 2)));
+
+        let wasm: PluginManifest = serde_json::from_str(
+            r#"{"name": "w", "version": "1", "library": "w.wasm", "runtime": "wasm", "abi_version": 1}"#,
+        ).unwrap();
+        assert_eq!(wasm.runtime, PluginRuntime::Wasm);
+        assert!(wasm.check_compatibility("0.2.0").is_ok());
+    }
+
+    struct Failing;
+
+    impl FrameFilter for Failing {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn process(&mut self, rgba_data: &mut [u8], _width: usize, _height: usize) -> Result<(), PluginError> {
+            rgba_data[0] = rgba_data[0].wrapping_add(1);
+            Err(PluginError::Process { name: "failing".to_string(), code: 3 })
+        }
+    }
+
+    #[test]
+    fn test_failing_stage_is_disabled() {
+        let stage = PluginStage::new(Box::new(Failing));
+        let mut frame = vec![0u8; 4];
+        stage.process_rgba(&mut frame, 1, 1);
+        stage.process_rgba(&mut frame, 1, 1);
+        assert_eq!(frame[0], 1);
+    }
+}
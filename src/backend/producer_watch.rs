@@ -0,0 +1,217 @@
+// src/backend/producer_watch.rs - inotify Watch for the Producer's Region Appearing
+
+use std::ffi::{CString, OsStr};
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use tokio::io::unix::AsyncFd;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+use crate::backend::frame_source::watch_path;
+use crate::backend::reconnect::ReconnectPolicy;
+
+/// Directory events that can mean the producer (re)created its region or socket
+const WATCH_MASK: u32 = libc::IN_CREATE | libc::IN_MOVED_TO | libc::IN_MODIFY | libc::IN_CLOSE_WRITE | libc::IN_ATTRIB;
+
+/// Size of `struct inotify_event` before the name
+const EVENT_HEADER_SIZE: usize = std::mem::size_of::<libc::inotify_event>();
+
+/// Wakes the reconnect logic when a file appears or changes in its directory
+///
+/// Watching the directory rather than the file works before the file exists,
+/// and after the producer deletes and recreates it on restart.
+pub struct ProducerWatch {
+    fd: AsyncFd<OwnedFd>,
+    path: PathBuf,
+}
+
+impl ProducerWatch {
+    /// Watch the directory containing `path` for changes to that file
+    pub fn new(path: &Path) -> io::Result<Self> {
+        path.file_name().ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+
+        let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let directory = CString::new(directory.as_os_str().as_bytes())?;
+        if unsafe { libc::inotify_add_watch(fd.as_raw_fd(), directory.as_ptr(), WATCH_MASK) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { fd: AsyncFd::new(fd)?, path: path.to_path_buf() })
+    }
+
+    /// The watched file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Wait until the watched file is created, replaced or modified
+    ///
+    /// Cancel safe: events are only consumed after a read, with no await in between.
+    pub async fn changed(&self) -> io::Result<()> {
+        let file_name = self.path.file_name().unwrap_or_default();
+        // Aligned for `inotify_event`; large enough for several events at once
+        let mut buffer = [0u64; 512];
+
+        loop {
+            let mut guard = self.fd.readable().await?;
+            let read = guard.try_io(|fd| {
+                let bytes = unsafe {
+                    libc::read(fd.as_raw_fd(), buffer.as_mut_ptr() as *mut libc::c_void, std::mem::size_of_val(&buffer))
+                };
+                if bytes < 0 { Err(io::Error::last_os_error()) } else { Ok(bytes as usize) }
+            });
+
+            match read {
+                Ok(Ok(length)) => {
+                    let bytes = unsafe { std::slice::from_raw_parts(buffer.as_ptr() as *const u8, length) };
+                    if event_names(bytes).any(|name| name == file_name) {
+                        return Ok(());
+                    }
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+/// Reconnect schedule while the backend waits for a producer
+///
+/// Retries when the producer's file changes, or after the policy's backoff delay
+/// for sources that cannot be watched (and in case an event was missed).
+pub struct ProducerWait {
+    watch: Option<ProducerWatch>,
+    policy: ReconnectPolicy,
+    /// Next retry without a file event
+    pub retry_at: Instant,
+    /// Retries made so far, which set the backoff delay
+    pub attempts: u32,
+    /// Failed retries that were not just a missing producer
+    pub failures: u32,
+}
+
+impl ProducerWait {
+    /// Start waiting for the producer behind `name`
+    ///
+    /// The first retry is immediate, in case the region appeared before the watch was set up.
+    pub fn new(name: &str, policy: ReconnectPolicy) -> Self {
+        let watch = watch_path(name).and_then(|path| match ProducerWatch::new(&path) {
+            Ok(watch) => {
+                debug!("👀 Watching {} for the producer", path.display());
+                Some(watch)
+            }
+            Err(e) => {
+                warn!("⚠️ Cannot watch {} ({}), retrying from every {:?}", path.display(), e, policy.initial_delay);
+                None
+            }
+        });
+
+        Self { watch, policy, retry_at: Instant::now(), attempts: 0, failures: 0 }
+    }
+
+    /// Back off before the next timed retry
+    pub fn schedule_retry(&mut self) {
+        let delay = self.policy.next_delay(self.attempts);
+        self.attempts = self.attempts.saturating_add(1);
+        self.retry_at = Instant::now() + delay;
+        debug!("🔄 Next reconnect attempt in {:?}", delay);
+    }
+
+    /// Check if the failures so far leave room for another attempt
+    pub fn can_retry(&self) -> bool {
+        self.policy.allows(self.failures)
+    }
+
+    /// Resolve when the watched file changes or the retry time is reached
+    pub async fn ready(&self) {
+        let deadline = tokio::time::sleep_until(self.retry_at);
+        tokio::pin!(deadline);
+
+        if let Some(watch) = &self.watch {
+            tokio::select! {
+                result = watch.changed() => match result {
+                    Ok(()) => return,
+                    Err(e) => warn!("⚠️ Watching {} failed: {}", watch.path().display(), e),
+                },
+                _ = &mut deadline => return,
+            }
+        }
+        deadline.await
+    }
+}
+
+/// Names of the entries in a buffer of inotify events
+///
+/// Names are NUL padded; events on the watched directory itself have none.
+pub fn event_names(mut buffer: &[u8]) -> impl Iterator<Item = &OsStr> {
+    std::iter::from_fn(move || loop {
+        if buffer.len() < EVENT_HEADER_SIZE {
+            return None;
+        }
+
+        // `len` follows wd, mask and cookie
+        let length = u32::from_ne_bytes(buffer[12..16].try_into().ok()?) as usize;
+        let name = buffer.get(EVENT_HEADER_SIZE..EVENT_HEADER_SIZE + length)?;
+        buffer = &buffer[EVENT_HEADER_SIZE + length..];
+
+        let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+        if end > 0 {
+            return Some(OsStr::from_bytes(&name[..end]));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, padded: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; EVENT_HEADER_SIZE];
+        bytes[12..16].copy_from_slice(&(padded as u32).to_ne_bytes());
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.resize(EVENT_HEADER_SIZE + padded, 0);
+        bytes
+    }
+
+    #[test]
+    fn test_event_names() {
+        let mut buffer = event("", 0);
+        buffer.extend(event("other", 16));
+        buffer.extend(event("ultrasound_frames", 32));
+        let names: Vec<_> = event_names(&buffer).collect();
+        assert_eq!(names, [OsStr::new("other"), OsStr::new("ultrasound_frames")]);
+
+        // A truncated event ends the iteration
+        assert_eq!(event_names(&buffer[..EVENT_HEADER_SIZE + 4]).count(), 0);
+    }
+
+    #[test]
+    fn test_watch_sees_region_created() {
+        let directory = std::env::temp_dir().join(format!("mivi_watch_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("frames");
+        let _ = std::fs::remove_file(&path);
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let watch = ProducerWatch::new(&path).unwrap();
+            std::fs::write(directory.join("unrelated"), b"x").unwrap();
+            std::fs::write(&path, b"region").unwrap();
+
+            tokio::time::timeout(std::time::Duration::from_secs(5), watch.changed()).await.unwrap().unwrap();
+        });
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,162 @@
+// src/backend/protocol.rs - Protocol Version Handshake and Capability Negotiation
+
+use tracing::warn;
+
+use crate::backend::types::ControlBlock;
+
+/// "MIVI" in the control block marks a versioned producer
+pub const PROTOCOL_MAGIC: u32 = 0x4D49_5649;
+
+/// Protocol version implemented by this crate
+pub const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion { major: 1, minor: 0 };
+
+/// Frames may carry a CRC32 in their header (see `integrity`)
+pub const CAP_CHECKSUMS: u32 = 0x0000_0001;
+/// Frame slots may carry JSON metadata after the pixel data
+pub const CAP_FRAME_METADATA: u32 = 0x0000_0002;
+/// The metadata area describes the ring layout as JSON
+pub const CAP_LAYOUT_JSON: u32 = 0x0000_0004;
+
+/// Capabilities this consumer understands
+pub const SUPPORTED_CAPABILITIES: u32 = CAP_CHECKSUMS | CAP_FRAME_METADATA | CAP_LAYOUT_JSON;
+
+/// Capabilities of the original, unversioned layout
+pub const LEGACY_CAPABILITIES: u32 = CAP_FRAME_METADATA | CAP_LAYOUT_JSON;
+
+/// Protocol version, packed as `major << 16 | minor` in the control block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl ProtocolVersion {
+    /// Unpack from the control block field
+    pub fn from_packed(packed: u32) -> Self {
+        Self { major: (packed >> 16) as u16, minor: packed as u16 }
+    }
+
+    /// Pack for the control block field
+    pub fn packed(&self) -> u32 {
+        (self.major as u32) << 16 | self.minor as u32
+    }
+}
+
+impl std::fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Outcome of checking a producer's control block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handshake {
+    /// None for producers that predate the version field
+    pub version: Option<ProtocolVersion>,
+    /// Capabilities both sides support
+    pub capabilities: u32,
+}
+
+impl Handshake {
+    /// Check the producer's magic and version, refusing layouts this reader would misread
+    ///
+    /// A zero magic is a legacy producer, read with the original layout and only its
+    /// baseline capabilities (checksums came with versioning). A different
+    /// major version is refused; a newer minor version only adds capabilities, so
+    /// the ones this reader doesn't know are ignored.
+    pub fn negotiate(control_block: &ControlBlock) -> Result<Self, ProtocolError> {
+        match control_block.magic {
+            0 => return Ok(Self { version: None, capabilities: LEGACY_CAPABILITIES }),
+            PROTOCOL_MAGIC => {}
+            magic => return Err(ProtocolError::BadMagic(magic)),
+        }
+
+        let version = ProtocolVersion::from_packed(control_block.protocol_version);
+        if version.major != PROTOCOL_VERSION.major {
+            return Err(ProtocolError::UnsupportedVersion { producer: version, consumer: PROTOCOL_VERSION });
+        }
+
+        let unknown = control_block.producer_capabilities & !SUPPORTED_CAPABILITIES;
+        if unknown != 0 {
+            warn!("⚠️ Producer (protocol {}) offers capabilities {:#x} this viewer does not use", version, unknown);
+        }
+
+        Ok(Self {
+            version: Some(version),
+            capabilities: control_block.producer_capabilities & SUPPORTED_CAPABILITIES,
+        })
+    }
+
+    /// Check if a capability was negotiated
+    pub fn supports(&self, capability: u32) -> bool {
+        self.capabilities & capability != 0
+    }
+
+    /// Short description for the UI, e.g. "v1.0 (checksums, metadata)"
+    pub fn describe(&self) -> String {
+        let Some(version) = self.version else {
+            return "legacy (unversioned)".to_string();
+        };
+
+        let names: Vec<&str> = [(CAP_CHECKSUMS, "checksums"), (CAP_FRAME_METADATA, "metadata")]
+            .into_iter()
+            .filter(|&(capability, _)| self.supports(capability))
+            .map(|(_, name)| name)
+            .collect();
+        if names.is_empty() {
+            format!("v{}", version)
+        } else {
+            format!("v{} ({})", version, names.join(", "))
+        }
+    }
+}
+
+/// Control block fields a versioned producer fills in
+pub fn stamp(control_block: &mut ControlBlock, capabilities: u32) {
+    control_block.magic = PROTOCOL_MAGIC;
+    control_block.protocol_version = PROTOCOL_VERSION.packed();
+    control_block.producer_capabilities = capabilities;
+}
+
+/// Errors from the protocol handshake
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ProtocolError {
+    #[error("Not a MiVi shared memory region (magic {0:#010x})")]
+    BadMagic(u32),
+
+    #[error("Producer speaks protocol {producer}, this viewer supports {consumer}")]
+    UnsupportedVersion { producer: ProtocolVersion, consumer: ProtocolVersion },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn control_block() -> ControlBlock {
+        unsafe { std::mem::zeroed() }
+    }
+
+    #[test]
+    fn test_handshake() {
+        // Legacy producers are read with the original layout
+        let mut block = control_block();
+        let legacy = Handshake::negotiate(&block).unwrap();
+        assert_eq!(legacy.version, None);
+        assert_eq!(legacy.describe(), "legacy (unversioned)");
+        assert!(legacy.supports(CAP_FRAME_METADATA));
+        assert!(!legacy.supports(CAP_CHECKSUMS));
+
+        // Same major version: unknown capabilities are dropped
+        stamp(&mut block, CAP_CHECKSUMS | 0x100);
+        block.protocol_version = ProtocolVersion { major: 1, minor: 3 }.packed();
+        let handshake = Handshake::negotiate(&block).unwrap();
+        assert_eq!(handshake.capabilities, CAP_CHECKSUMS);
+        assert_eq!(handshake.describe(), "v1.3 (checksums)");
+
+        block.protocol_version = ProtocolVersion { major: 2, minor: 0 }.packed();
+        assert!(matches!(Handshake::negotiate(&block), Err(ProtocolError::UnsupportedVersion { .. })));
+
+        block.magic = 0xDEAD_BEEF;
+        assert!(matches!(Handshake::negotiate(&block), Err(ProtocolError::BadMagic(0xDEAD_BEEF))));
+    }
+}
@@ -0,0 +1,136 @@
+// src/backend/reconnect.rs - Reconnection Policy with Exponential Backoff
+
+use std::time::Duration;
+
+/// How long to wait between reconnection attempts, and how many to make
+///
+/// The delay starts at `initial_delay` and grows by `multiplier` per failed
+/// attempt up to `max_delay`; `jitter` spreads it by up to that fraction either
+/// way, so several viewers don't hammer a restarting producer in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    pub initial_delay: Duration,
+    pub multiplier: f64,
+    pub max_delay: Duration,
+    /// Fraction of the delay (0.0-1.0) added or subtracted at random
+    pub jitter: f64,
+    pub max_attempts: u32,
+    /// Start again from `initial_delay` and a fresh attempt budget once connected
+    pub reset_on_success: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(30),
+            jitter: 0.1,
+            max_attempts: 10,
+            reset_on_success: true,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// A policy retrying every `delay` without backoff, as before policies existed
+    pub fn fixed(delay: Duration, max_attempts: u32) -> Self {
+        Self { initial_delay: delay, multiplier: 1.0, max_delay: delay, jitter: 0.0, max_attempts, reset_on_success: true }
+    }
+
+    /// Delay before retry number `attempt` (0 for the first retry), without jitter
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let max_delay = self.max_delay.max(self.initial_delay);
+        let factor = self.multiplier.max(1.0).powi(attempt.min(64) as i32);
+        let delay = self.initial_delay.as_secs_f64() * factor;
+        if delay.is_finite() && delay < max_delay.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            max_delay
+        }
+    }
+
+    /// Delay before retry number `attempt`, spread by jitter
+    ///
+    /// `unit` is a uniformly distributed value in 0.0..1.0; 0.5 gives the base delay.
+    pub fn delay(&self, attempt: u32, unit: f64) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0) * (unit.clamp(0.0, 1.0) * 2.0 - 1.0);
+        self.base_delay(attempt).mul_f64(1.0 + jitter)
+    }
+
+    /// Delay before retry number `attempt`, with fresh random jitter
+    pub fn next_delay(&self, attempt: u32) -> Duration {
+        self.delay(attempt, random_unit())
+    }
+
+    /// Check if another attempt is allowed after `attempts` failures
+    pub fn allows(&self, attempts: u32) -> bool {
+        attempts < self.max_attempts
+    }
+
+    /// Short description for the connection panel, e.g. "1s ×2 ≤30s ±10%, 10 attempts"
+    pub fn describe(&self) -> String {
+        let mut description = format_duration(self.initial_delay);
+        if self.multiplier > 1.0 {
+            description.push_str(&format!(" ×{} ≤{}", self.multiplier, format_duration(self.max_delay)));
+        }
+        if self.jitter > 0.0 {
+            description.push_str(&format!(" ±{:.0}%", self.jitter * 100.0));
+        }
+        description.push_str(&format!(", {} attempt{}", self.max_attempts, if self.max_attempts == 1 { "" } else { "s" }));
+        description
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration.subsec_millis() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+/// A pseudo-random value in 0.0..1.0, good enough to spread retries
+fn random_unit() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos());
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_backoff() {
+        let policy = ReconnectPolicy {
+            initial_delay: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(3),
+            jitter: 0.2,
+            max_attempts: 5,
+            reset_on_success: true,
+        };
+
+        let delays: Vec<_> = (0..5).map(|attempt| policy.base_delay(attempt).as_millis()).collect();
+        assert_eq!(delays, [500, 1000, 2000, 3000, 3000]);
+        assert_eq!(policy.base_delay(u32::MAX), Duration::from_secs(3));
+
+        // Jitter spreads the delay by up to 20% either way
+        assert_eq!(policy.delay(1, 0.0), Duration::from_millis(800));
+        assert_eq!(policy.delay(1, 0.5), Duration::from_millis(1000));
+        assert_eq!(policy.delay(1, 1.0), Duration::from_millis(1200));
+        let random = policy.next_delay(1);
+        assert!(random >= Duration::from_millis(800) && random <= Duration::from_millis(1200));
+
+        assert!(policy.allows(4));
+        assert!(!policy.allows(5));
+        assert_eq!(policy.describe(), "500ms ×2 ≤3s ±20%, 5 attempts");
+
+        let fixed = ReconnectPolicy::fixed(Duration::from_secs(1), 1);
+        assert_eq!(fixed.base_delay(7), Duration::from_secs(1));
+        assert_eq!(fixed.describe(), "1s, 1 attempt");
+    }
+}
@@ -0,0 +1,326 @@
+// src/backend/registry.rs - Well-Known Region Where Producers Announce Their Streams
+
+use std::fs::{File, OpenOptions};
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use memmap2::{MmapMut, MmapOptions};
+use tracing::{debug, info};
+
+use crate::backend::shared_memory::{region_path, SharedMemoryError};
+use crate::backend::types::FrameFormat;
+
+/// Name of the registry region in /dev/shm
+pub const REGISTRY_NAME: &str = "mivi_registry";
+
+/// "MVRG" in little-endian order
+const REGISTRY_MAGIC: u32 = 0x4752_564D;
+const REGISTRY_VERSION: u32 = 1;
+
+/// Streams the registry can hold at once
+pub const MAX_STREAMS: usize = 32;
+
+const NAME_SIZE: usize = 96;
+const FORMAT_SIZE: usize = 16;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct RegistryHeader {
+    magic: u32,
+    version: u32,
+    capacity: u32,
+    _padding: u32,
+}
+
+/// One announced stream; a zero `pid` marks a free slot
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct RegistryEntry {
+    pid: u32,
+    width: u32,
+    height: u32,
+    _padding: u32,
+    announced_at: u64,
+    format: [u8; FORMAT_SIZE],
+    name: [u8; NAME_SIZE],
+}
+
+const HEADER_SIZE: usize = std::mem::size_of::<RegistryHeader>();
+const ENTRY_SIZE: usize = std::mem::size_of::<RegistryEntry>();
+const REGISTRY_SIZE: usize = HEADER_SIZE + MAX_STREAMS * ENTRY_SIZE;
+
+/// A stream a producer has announced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamAnnouncement {
+    /// Value for `--shm-name`
+    pub shm_name: String,
+    /// Lower-case format name as accepted by `--format`
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    /// Producer process
+    pub pid: u32,
+    /// Nanoseconds since the Unix epoch
+    pub announced_at: u64,
+}
+
+impl StreamAnnouncement {
+    /// Announcement of a stream by the current process
+    pub fn new(shm_name: &str, format: FrameFormat, width: u32, height: u32) -> Self {
+        Self {
+            shm_name: shm_name.to_string(),
+            format: format_name(format).to_string(),
+            width,
+            height,
+            pid: std::process::id(),
+            announced_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64,
+        }
+    }
+
+    /// Short description for the stream picker, e.g. "ultrasound_frames — YUV 1024×768"
+    pub fn label(&self) -> String {
+        format!("{} — {} {}×{}", self.shm_name, self.format.to_uppercase(), self.width, self.height)
+    }
+}
+
+/// Registry of streams, shared by all producers and viewers on the machine
+///
+/// Producers add an entry when they create their region and remove it when they
+/// exit; entries of producers that died without removing them are skipped when
+/// listing and reused when announcing. Every access holds an `flock` on the file.
+pub struct Registry {
+    mmap: MmapMut,
+    file: File,
+    path: PathBuf,
+}
+
+impl Registry {
+    /// Open the registry in /dev/shm, creating it if no producer has yet
+    pub fn open() -> Result<Self, SharedMemoryError> {
+        Self::open_at(&region_path(REGISTRY_NAME))
+    }
+
+    /// Open or create a registry at `path`
+    pub fn open_at(path: &Path) -> Result<Self, SharedMemoryError> {
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        let mmap = {
+            // The first opener sizes and lays out the file while others wait
+            let _lock = FileLock::exclusive(&file)?;
+            if file.metadata()?.len() < REGISTRY_SIZE as u64 {
+                file.set_len(REGISTRY_SIZE as u64)?;
+            }
+            let mut mmap = unsafe {
+                MmapOptions::new()
+                    .len(REGISTRY_SIZE)
+                    .map_mut(&file)
+                    .map_err(|e| SharedMemoryError::MappingFailed(e.to_string()))?
+            };
+            initialize(&mut mmap, path)?;
+            mmap
+        };
+
+        Ok(Self { mmap, file, path: path.to_path_buf() })
+    }
+
+    /// File backing the registry
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Add or refresh the announcement of a stream
+    pub fn announce(&mut self, announcement: &StreamAnnouncement) -> Result<(), SharedMemoryError> {
+        if announcement.shm_name.is_empty() || announcement.shm_name.len() > NAME_SIZE {
+            return Err(SharedMemoryError::Other(format!(
+                "Stream name must be 1-{} bytes to be announced: \"{}\"", NAME_SIZE, announcement.shm_name
+            )));
+        }
+
+        let _lock = FileLock::exclusive(&self.file)?;
+        let slot = (0..MAX_STREAMS)
+            .find(|&slot| self.entry(slot).map_or(false, |entry| entry.shm_name == announcement.shm_name))
+            .or_else(|| (0..MAX_STREAMS).find(|&slot| self.entry(slot).map_or(true, |entry| !is_alive(&entry))))
+            .ok_or_else(|| SharedMemoryError::Other(format!("Stream registry is full ({} streams)", MAX_STREAMS)))?;
+
+        let mut entry = RegistryEntry {
+            pid: announcement.pid,
+            width: announcement.width,
+            height: announcement.height,
+            _padding: 0,
+            announced_at: announcement.announced_at,
+            format: [0; FORMAT_SIZE],
+            name: [0; NAME_SIZE],
+        };
+        copy_str(&mut entry.format, &announcement.format);
+        copy_str(&mut entry.name, &announcement.shm_name);
+        write_entry(&mut self.mmap, slot, entry);
+
+        info!("📇 Announced stream {} in the registry", announcement.label());
+        Ok(())
+    }
+
+    /// Remove the announcement of a stream made by this process
+    pub fn withdraw(&mut self, shm_name: &str) -> Result<(), SharedMemoryError> {
+        let _lock = FileLock::exclusive(&self.file)?;
+        let pid = std::process::id();
+        for slot in 0..MAX_STREAMS {
+            if self.entry(slot).is_some_and(|entry| entry.shm_name == shm_name && entry.pid == pid) {
+                write_entry(&mut self.mmap, slot, unsafe { std::mem::zeroed() });
+                debug!("📇 Withdrew stream {} from the registry", shm_name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Streams whose producer is still running, by name
+    pub fn streams(&self) -> Result<Vec<StreamAnnouncement>, SharedMemoryError> {
+        let _lock = FileLock::shared(&self.file)?;
+        let mut streams: Vec<_> = (0..MAX_STREAMS).filter_map(|slot| self.entry(slot)).filter(is_alive).collect();
+        streams.sort_by(|a, b| a.shm_name.cmp(&b.shm_name));
+        Ok(streams)
+    }
+
+    fn entry(&self, slot: usize) -> Option<StreamAnnouncement> {
+        let offset = HEADER_SIZE + slot * ENTRY_SIZE;
+        let entry = unsafe { std::ptr::read_unaligned(self.mmap[offset..].as_ptr() as *const RegistryEntry) };
+        (entry.pid != 0).then(|| StreamAnnouncement {
+            shm_name: read_str(&entry.name),
+            format: read_str(&entry.format),
+            width: entry.width,
+            height: entry.height,
+            pid: entry.pid,
+            announced_at: entry.announced_at,
+        })
+    }
+}
+
+fn write_entry(mmap: &mut MmapMut, slot: usize, entry: RegistryEntry) {
+    let offset = HEADER_SIZE + slot * ENTRY_SIZE;
+    unsafe { std::ptr::write_unaligned(mmap[offset..].as_mut_ptr() as *mut RegistryEntry, entry) };
+}
+
+/// Streams announced in the registry, empty if no producer has created it
+pub fn discover() -> Vec<StreamAnnouncement> {
+    let path = region_path(REGISTRY_NAME);
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    match Registry::open_at(&path).and_then(|registry| registry.streams()) {
+        Ok(streams) => streams,
+        Err(e) => {
+            debug!("📇 Cannot read the stream registry: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Lay out an empty registry, or check the layout of an existing one
+fn initialize(mmap: &mut MmapMut, path: &Path) -> Result<(), SharedMemoryError> {
+    let header = unsafe { std::ptr::read_unaligned(mmap.as_ptr() as *const RegistryHeader) };
+    match header.magic {
+        0 => {
+            let header = RegistryHeader { magic: REGISTRY_MAGIC, version: REGISTRY_VERSION, capacity: MAX_STREAMS as u32, _padding: 0 };
+            unsafe { std::ptr::write_unaligned(mmap.as_mut_ptr() as *mut RegistryHeader, header) };
+            debug!("📇 Initialized stream registry at {}", path.display());
+            Ok(())
+        }
+        REGISTRY_MAGIC if header.version == REGISTRY_VERSION && header.capacity as usize == MAX_STREAMS => Ok(()),
+        _ => Err(SharedMemoryError::InvalidLayout(format!(
+            "{} is not a version {} stream registry", path.display(), REGISTRY_VERSION
+        ))),
+    }
+}
+
+/// Name of a format as accepted by `--format`
+pub fn format_name(format: FrameFormat) -> &'static str {
+    match format {
+        FrameFormat::YUV => "yuv",
+        FrameFormat::BGR => "bgr",
+        FrameFormat::BGRA => "bgra",
+        FrameFormat::RGB => "rgb",
+        FrameFormat::RGBA => "rgba",
+        FrameFormat::YUV10 => "yuv10",
+        FrameFormat::RGB10 => "rgb10",
+        FrameFormat::RGB565 => "rgb565",
+        FrameFormat::Indexed => "indexed",
+        FrameFormat::Grayscale => "grayscale",
+        FrameFormat::Gray12Packed => "gray12packed",
+        FrameFormat::Unknown => "unknown",
+    }
+}
+
+/// Check that the announcing producer is still running
+fn is_alive(stream: &StreamAnnouncement) -> bool {
+    let result = unsafe { libc::kill(stream.pid as libc::pid_t, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+fn copy_str(buffer: &mut [u8], value: &str) {
+    let length = value.len().min(buffer.len());
+    buffer[..length].copy_from_slice(&value.as_bytes()[..length]);
+}
+
+fn read_str(buffer: &[u8]) -> String {
+    let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    String::from_utf8_lossy(&buffer[..end]).into_owned()
+}
+
+/// `flock` held until dropped
+struct FileLock<'a>(&'a File);
+
+impl<'a> FileLock<'a> {
+    fn exclusive(file: &'a File) -> std::io::Result<Self> {
+        Self::lock(file, libc::LOCK_EX)
+    }
+
+    fn shared(file: &'a File) -> std::io::Result<Self> {
+        Self::lock(file, libc::LOCK_SH)
+    }
+
+    fn lock(file: &'a File, operation: libc::c_int) -> std::io::Result<Self> {
+        if unsafe { libc::flock(file.as_raw_fd(), operation) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self(file))
+    }
+}
+
+impl Drop for FileLock<'_> {
+    fn drop(&mut self) {
+        unsafe { libc::flock(self.0.as_raw_fd(), libc::LOCK_UN) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announce_and_withdraw() {
+        let path = std::env::temp_dir().join(format!("mivi_registry_test_{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut registry = Registry::open_at(&path).unwrap();
+        assert!(registry.streams().unwrap().is_empty());
+
+        registry.announce(&StreamAnnouncement::new("probe_b", FrameFormat::YUV, 1024, 768)).unwrap();
+        registry.announce(&StreamAnnouncement::new("probe_a", FrameFormat::BGRA, 640, 480)).unwrap();
+        // Announcing again replaces the entry
+        registry.announce(&StreamAnnouncement::new("probe_b", FrameFormat::Grayscale, 800, 600)).unwrap();
+
+        // A producer that is gone is not listed
+        let mut dead = StreamAnnouncement::new("probe_dead", FrameFormat::RGB, 2, 2);
+        dead.pid = i32::MAX as u32;
+        registry.announce(&dead).unwrap();
+
+        let streams = Registry::open_at(&path).unwrap().streams().unwrap();
+        let names: Vec<_> = streams.iter().map(|s| (s.shm_name.as_str(), s.format.as_str(), s.width)).collect();
+        assert_eq!(names, [("probe_a", "bgra", 640), ("probe_b", "grayscale", 800)]);
+        assert_eq!(streams[1].label(), "probe_b — GRAYSCALE 800×600");
+
+        registry.withdraw("probe_a").unwrap();
+        assert_eq!(registry.streams().unwrap().len(), 1);
+        assert!(registry.announce(&StreamAnnouncement::new("", FrameFormat::YUV, 1, 1)).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
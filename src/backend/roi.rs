@@ -0,0 +1,189 @@
+// src/backend/roi.rs - Region-of-Interest Statistics on Raw Medical Imaging Data
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::transform::CropRect;
+use crate::backend::frame_processor::rgb565_to_rgba;
+use crate::backend::packed12;
+use crate::backend::types::{read_sample16, FrameFormat};
+
+/// Outline of a region of interest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum RoiShape {
+    #[default]
+    Rectangle,
+    /// Ellipse inscribed in the bounding rectangle
+    Ellipse,
+}
+
+impl RoiShape {
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            RoiShape::Rectangle => "Rectangle",
+            RoiShape::Ellipse => "Ellipse",
+        }
+    }
+}
+
+/// Region of interest: a shape inside a normalized bounding rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Roi {
+    pub shape: RoiShape,
+    pub bounds: CropRect,
+}
+
+impl Roi {
+    /// Create a region of interest
+    pub fn new(shape: RoiShape, bounds: CropRect) -> Self {
+        Self { shape, bounds }
+    }
+
+    /// Compute statistics of the raw pixel values inside the region
+    ///
+    /// Single-channel formats use the stored sample (10-bit samples are not
+    /// rescaled); colour formats use the Rec.601 luma of the raw channels.
+    /// `big_endian` gives the byte order of 16-bit samples; 12-bit packed
+    /// samples are unpacked first.
+    pub fn statistics(&self, data: &[u8], width: usize, height: usize, format: FrameFormat, big_endian: bool) -> Option<RoiStatistics> {
+        let pixel_count = width * height;
+        if format == FrameFormat::Gray12Packed {
+            let samples = packed12::unpack(data, pixel_count)?;
+            let bytes: Vec<u8> = samples.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+            return self.statistics(&bytes, width, height, FrameFormat::Grayscale, false);
+        }
+        if pixel_count == 0 || data.len() % pixel_count != 0 {
+            return None;
+        }
+        let bytes_per_pixel = data.len() / pixel_count;
+
+        let (x0, y0, roi_width, roi_height) = self.bounds.to_pixels(width, height);
+        let (center_x, center_y) = (x0 as f64 + roi_width as f64 / 2.0, y0 as f64 + roi_height as f64 / 2.0);
+        let (radius_x, radius_y) = (roi_width as f64 / 2.0, roi_height as f64 / 2.0);
+
+        let mut count = 0u64;
+        let mut sum = 0f64;
+        let mut sum_squares = 0f64;
+        let mut min = u32::MAX;
+        let mut max = 0u32;
+
+        for y in y0..y0 + roi_height {
+            let row = &data[y * width * bytes_per_pixel..(y + 1) * width * bytes_per_pixel];
+            let dy = (y as f64 + 0.5 - center_y) / radius_y;
+
+            for x in x0..x0 + roi_width {
+                if self.shape == RoiShape::Ellipse {
+                    let dx = (x as f64 + 0.5 - center_x) / radius_x;
+                    if dx * dx + dy * dy > 1.0 {
+                        continue;
+                    }
+                }
+
+                let value = raw_value(&row[x * bytes_per_pixel..(x + 1) * bytes_per_pixel], format, big_endian);
+                count += 1;
+                sum += value as f64;
+                sum_squares += value as f64 * value as f64;
+                min = min.min(value);
+                max = max.max(value);
+            }
+        }
+
+        if count == 0 {
+            return None;
+        }
+
+        let mean = sum / count as f64;
+        let variance = (sum_squares / count as f64 - mean * mean).max(0.0);
+
+        Some(RoiStatistics {
+            shape: self.shape,
+            pixel_count: count,
+            mean,
+            std_dev: variance.sqrt(),
+            min,
+            max,
+        })
+    }
+}
+
+/// Statistics of raw pixel values inside a region of interest
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RoiStatistics {
+    pub shape: RoiShape,
+    pub pixel_count: u64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl RoiStatistics {
+    /// Get a one-line summary for display
+    pub fn summary(&self) -> String {
+        format!("Mean {:.1} ± {:.1} | Min {} | Max {} | n = {}",
+                self.mean, self.std_dev, self.min, self.max, self.pixel_count)
+    }
+}
+
+/// Decode the raw intensity of one pixel
+fn raw_value(pixel: &[u8], format: FrameFormat, big_endian: bool) -> u32 {
+    let luma = |r: u32, g: u32, b: u32| (299 * r + 587 * g + 114 * b + 500) / 1000;
+
+    match (format, pixel.len()) {
+        (_, 1) => pixel[0] as u32,
+        (FrameFormat::RGB565, 2) => {
+            let [r, g, b, _] = rgb565_to_rgba(read_sample16(pixel, big_endian));
+            luma(r as u32, g as u32, b as u32)
+        }
+        (_, 2) => read_sample16(pixel, big_endian) as u32,
+        (FrameFormat::BGR | FrameFormat::BGRA, 3 | 4) => luma(pixel[2] as u32, pixel[1] as u32, pixel[0] as u32),
+        (_, 3 | 4) => luma(pixel[0] as u32, pixel[1] as u32, pixel[2] as u32),
+        (_, 6) => luma(
+            read_sample16(&pixel[0..2], big_endian) as u32,
+            read_sample16(&pixel[2..4], big_endian) as u32,
+            read_sample16(&pixel[4..6], big_endian) as u32,
+        ),
+        _ => pixel[0] as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectangle_statistics() {
+        // 4x1 grayscale: 10, 20, 30, 40
+        let data = [10u8, 20, 30, 40];
+        let roi = Roi::new(RoiShape::Rectangle, CropRect::new(0.0, 0.0, 0.5, 1.0));
+        let stats = roi.statistics(&data, 4, 1, FrameFormat::Grayscale, false).unwrap();
+        assert_eq!((stats.pixel_count, stats.min, stats.max), (2, 10, 20));
+        assert!((stats.mean - 15.0).abs() < 1e-9);
+        assert!((stats.std_dev - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ellipse_excludes_corners() {
+        // 10-bit samples: corners are 1023, the rest 100
+        let mut data = Vec::new();
+        for y in 0..8 {
+            for x in 0..8 {
+                let corner = (x == 0 || x == 7) && (y == 0 || y == 7);
+                let value: u16 = if corner { 1023 } else { 100 };
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        let ellipse = Roi::new(RoiShape::Ellipse, CropRect::full());
+        let stats = ellipse.statistics(&data, 8, 8, FrameFormat::YUV10, false).unwrap();
+        assert_eq!(stats.max, 100);
+        assert!(stats.pixel_count < 64);
+
+        let rectangle = Roi::new(RoiShape::Rectangle, CropRect::full());
+        assert_eq!(rectangle.statistics(&data, 8, 8, FrameFormat::YUV10, false).unwrap().max, 1023);
+
+        // The same samples sent big-endian
+        let swapped: Vec<u8> = data.chunks_exact(2).flat_map(|sample| [sample[1], sample[0]]).collect();
+        assert_eq!(rectangle.statistics(&swapped, 8, 8, FrameFormat::YUV10, true).unwrap().max, 1023);
+    }
+}
@@ -0,0 +1,317 @@
+// src/backend/rtsp_source.rs - RTSP/RTP Client for IP Camera Video (feature "rtsp")
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use retina::client::{Credentials, Demuxed, PlayOptions, Session, SessionOptions, SetupOptions, Transport};
+use retina::codec::{CodecItem, ParametersRef};
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::backend::frame_source::{FrameQueue, FrameSource};
+use crate::backend::shared_memory::{ConnectionStatistics, SharedMemoryError};
+use crate::backend::types::{ConnectionConfig, FrameFormat, FrameHeader, RawFrame};
+
+/// Decoded frames buffered before the oldest is dropped
+const MAX_QUEUED_FRAMES: usize = 4;
+
+const START_CODE: [u8; 4] = [0, 0, 0, 1];
+
+/// H.264 video from an IP camera, decoded into BGR frames
+///
+/// The RTSP session and the OpenH264 decoder run on their own thread, since the
+/// decoder cannot move between the runtime's workers; decoded frames are queued
+/// like any other network source.
+pub struct RtspFrameSource {
+    url: Url,
+    credentials: Option<Credentials>,
+    config: ConnectionConfig,
+    queue: Arc<FrameQueue>,
+    stop: Option<oneshot::Sender<()>>,
+}
+
+impl RtspFrameSource {
+    /// Create a source for `rtsp://[user:password@]host[:port]/path`
+    pub fn new(url: &str, config: ConnectionConfig) -> Result<Self, SharedMemoryError> {
+        let mut url = Url::parse(url).map_err(|e| SharedMemoryError::Other(format!("Invalid RTSP URL: {}", e)))?;
+
+        // Retina takes credentials separately, and they must not end up in logs
+        let credentials = (!url.username().is_empty()).then(|| Credentials {
+            username: url.username().to_string(),
+            password: url.password().unwrap_or_default().to_string(),
+        });
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+
+        Ok(Self {
+            url,
+            credentials,
+            config,
+            queue: Arc::new(FrameQueue::new(MAX_QUEUED_FRAMES)),
+            stop: None,
+        })
+    }
+
+    /// Describe, set up and play the first H.264 video stream
+    async fn open_session(url: Url, credentials: Option<Credentials>) -> Result<(Session<Demuxed>, usize, Vec<u8>), SharedMemoryError> {
+        let rtsp_error = |e: retina::Error| SharedMemoryError::Other(format!("RTSP: {}", e));
+
+        let mut session = Session::describe(url, SessionOptions::default().creds(credentials).user_agent("mivi_frame_viewer".to_string()))
+            .await
+            .map_err(rtsp_error)?;
+
+        let stream_index = session
+            .streams()
+            .iter()
+            .position(|stream| stream.media() == "video" && stream.encoding_name() == "h264")
+            .ok_or_else(|| SharedMemoryError::Other("RTSP source has no H.264 video stream".to_string()))?;
+
+        session
+            .setup(stream_index, SetupOptions::default().transport(Transport::Tcp(Default::default())))
+            .await
+            .map_err(rtsp_error)?;
+        let session = session
+            .play(PlayOptions::default())
+            .await
+            .map_err(rtsp_error)?
+            .demuxed()
+            .map_err(rtsp_error)?;
+
+        let parameter_sets = Self::parameter_sets(&session, stream_index);
+        Ok((session, stream_index, parameter_sets))
+    }
+
+    fn parameter_sets(session: &Session<Demuxed>, stream_index: usize) -> Vec<u8> {
+        match session.streams()[stream_index].parameters() {
+            Some(ParametersRef::Video(video)) => avcc_parameter_sets(video.extra_data()).unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn run(
+        url: Url,
+        credentials: Option<Credentials>,
+        queue: Arc<FrameQueue>,
+        ready: oneshot::Sender<Result<(), SharedMemoryError>>,
+        mut stop: oneshot::Receiver<()>,
+    ) {
+        let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                let _ = ready.send(Err(SharedMemoryError::Io(e)));
+                return;
+            }
+        };
+
+        runtime.block_on(async {
+            let mut decoder = match openh264::decoder::Decoder::new() {
+                Ok(decoder) => decoder,
+                Err(e) => {
+                    let _ = ready.send(Err(SharedMemoryError::Other(format!("H.264 decoder: {}", e))));
+                    return;
+                }
+            };
+
+            let (mut session, stream_index, mut parameter_sets) = match Self::open_session(url, credentials).await {
+                Ok(opened) => opened,
+                Err(e) => {
+                    let _ = ready.send(Err(e));
+                    return;
+                }
+            };
+            if ready.send(Ok(())).is_err() {
+                return; // connect() gave up waiting
+            }
+
+            let mut sequence = 0u64;
+            let mut access_unit = Vec::new();
+            loop {
+                let item = tokio::select! {
+                    _ = &mut stop => break,
+                    item = session.next() => item,
+                };
+
+                let frame = match item {
+                    Some(Ok(CodecItem::VideoFrame(frame))) if frame.stream_id() == stream_index => frame,
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => {
+                        warn!("❌ RTSP stream failed: {}", e);
+                        queue.record_error();
+                        break;
+                    }
+                    None => {
+                        info!("🔌 RTSP stream ended");
+                        break;
+                    }
+                };
+
+                if frame.has_new_parameters() {
+                    parameter_sets = Self::parameter_sets(&session, stream_index);
+                }
+
+                // OpenH264 wants Annex B, with SPS/PPS ahead of every keyframe
+                access_unit.clear();
+                if frame.is_random_access_point() {
+                    access_unit.extend_from_slice(&parameter_sets);
+                }
+                if length_prefixed_to_annex_b(frame.data(), &mut access_unit).is_none() {
+                    warn!("⚠️ Malformed H.264 access unit ({} bytes)", frame.data().len());
+                    queue.record_error();
+                    continue;
+                }
+
+                match decoder.decode(&access_unit) {
+                    Ok(Some(yuv)) => {
+                        use openh264::formats::YUVSource;
+
+                        let (width, height) = yuv.dimensions();
+                        let mut bgr = vec![0u8; width * height * 3];
+                        yuv.write_rgb8(&mut bgr);
+                        for pixel in bgr.chunks_exact_mut(3) {
+                            pixel.swap(0, 2);
+                        }
+
+                        sequence += 1;
+                        queue.push(RawFrame::new(bgr_header(sequence, width as u32, height as u32, bgr.len()), Arc::from(bgr), None));
+                    }
+                    Ok(None) => {} // Decoder still buffering
+                    Err(e) => {
+                        warn!("⚠️ H.264 decode failed: {}", e);
+                        queue.record_error();
+                    }
+                }
+            }
+        });
+
+        queue.set_connected(false);
+    }
+}
+
+#[async_trait]
+impl FrameSource for RtspFrameSource {
+    async fn connect(&mut self) -> Result<(), SharedMemoryError> {
+        if self.config.verbose_logging {
+            info!("🔌 Connecting to RTSP source: {}", self.url);
+        }
+
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let (url, credentials, queue) = (self.url.clone(), self.credentials.clone(), Arc::clone(&self.queue));
+        self.queue.set_connected(true);
+        std::thread::Builder::new()
+            .name("mivi-rtsp".to_string())
+            .spawn(move || Self::run(url, credentials, queue, ready_tx, stop_rx))?;
+
+        let result = match tokio::time::timeout(self.config.frame_timeout, ready_rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(SharedMemoryError::ConnectionLost),
+            Err(_) => Err(SharedMemoryError::NotFound(self.url.to_string())),
+        };
+        if let Err(e) = result {
+            self.queue.set_connected(false);
+            return Err(e);
+        }
+        self.stop = Some(stop_tx);
+
+        info!("🔗 Connected to RTSP source: {}", self.url);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) {
+        // Dropping the sender ends the session loop
+        self.stop = None;
+        self.queue.set_connected(false);
+
+        info!("🔌 Disconnected from RTSP source: {}", self.url);
+    }
+
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        self.queue.next(catch_up)
+    }
+
+    fn check_connection_health(&self) -> bool {
+        self.queue.is_healthy(self.config.frame_timeout)
+    }
+
+    fn backlog(&self) -> u64 {
+        self.queue.backlog()
+    }
+
+    fn get_statistics(&self) -> ConnectionStatistics {
+        self.queue.statistics(self.url.to_string())
+    }
+}
+
+fn bgr_header(sequence: u64, width: u32, height: u32, data_size: usize) -> FrameHeader {
+    FrameHeader {
+        frame_id: sequence,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64,
+        width,
+        height,
+        bytes_per_pixel: 3,
+        data_size: data_size as u32,
+        format_code: FrameFormat::BGR.to_code(),
+        flags: 0,
+        sequence_number: sequence,
+        metadata_offset: 0,
+        metadata_size: 0,
+        padding: [0; 4],
+    }
+}
+
+/// SPS and PPS from an AVCDecoderConfigurationRecord, as Annex B NAL units
+fn avcc_parameter_sets(extra_data: &[u8]) -> Option<Vec<u8>> {
+    fn copy_nal(src: &[u8], pos: usize, out: &mut Vec<u8>) -> Option<usize> {
+        let length = u16::from_be_bytes([*src.get(pos)?, *src.get(pos + 1)?]) as usize;
+        out.extend_from_slice(&START_CODE);
+        out.extend_from_slice(src.get(pos + 2..pos + 2 + length)?);
+        Some(pos + 2 + length)
+    }
+
+    let mut out = Vec::new();
+    let mut pos = 5;
+    let sps_count = *extra_data.get(pos)? & 0x1F;
+    pos += 1;
+    for _ in 0..sps_count {
+        pos = copy_nal(extra_data, pos, &mut out)?;
+    }
+    let pps_count = *extra_data.get(pos)?;
+    pos += 1;
+    for _ in 0..pps_count {
+        pos = copy_nal(extra_data, pos, &mut out)?;
+    }
+    Some(out)
+}
+
+/// Append 4-byte length-prefixed NAL units to `out` with Annex B start codes
+fn length_prefixed_to_annex_b(data: &[u8], out: &mut Vec<u8>) -> Option<()> {
+    let mut pos = 0;
+    while pos < data.len() {
+        let length = u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        out.extend_from_slice(&START_CODE);
+        out.extend_from_slice(data.get(pos + 4..pos + 4 + length)?);
+        pos += 4 + length;
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_h264_to_annex_b() {
+        // avcC: version, profile, compat, level, length size, 1 SPS, 1 PPS
+        let avcc = [1, 0x42, 0, 0x1E, 0xFF, 0xE1, 0, 2, 0x67, 0x42, 1, 0, 1, 0x68];
+        assert_eq!(avcc_parameter_sets(&avcc).unwrap(), [0, 0, 0, 1, 0x67, 0x42, 0, 0, 0, 1, 0x68]);
+        assert!(avcc_parameter_sets(&avcc[..9]).is_none());
+
+        let mut out = Vec::new();
+        length_prefixed_to_annex_b(&[0, 0, 0, 2, 0x65, 0xAA, 0, 0, 0, 1, 0x41], &mut out).unwrap();
+        assert_eq!(out, [0, 0, 0, 1, 0x65, 0xAA, 0, 0, 0, 1, 0x41]);
+        assert!(length_prefixed_to_annex_b(&[0, 0, 0, 9, 0x65], &mut out).is_none());
+    }
+}
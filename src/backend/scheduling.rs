@@ -0,0 +1,100 @@
+// src/backend/scheduling.rs - CPU Affinity and Real-Time Priority for the Frame Thread
+
+use thiserror::Error;
+
+/// Highest SCHED_FIFO priority accepted on Linux
+pub const MAX_RT_PRIORITY: i32 = 99;
+
+/// Scheduling requested for the frame-processing thread
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadScheduling {
+    /// Cores the thread may run on (empty = any)
+    pub cpu_affinity: Vec<usize>,
+    /// SCHED_FIFO priority (1-99), None keeps the default time-sharing policy
+    pub rt_priority: Option<i32>,
+}
+
+impl ThreadScheduling {
+    /// Check if any scheduling change was requested
+    pub fn is_default(&self) -> bool {
+        self.cpu_affinity.is_empty() && self.rt_priority.is_none()
+    }
+
+    /// Apply the requested affinity and priority to the calling thread
+    ///
+    /// Each part is attempted independently so a missing CAP_SYS_NICE doesn't also
+    /// lose the affinity; threads spawned afterwards inherit both settings.
+    pub fn apply_to_current_thread(&self) -> Vec<Result<(), SchedulingError>> {
+        let mut results = Vec::new();
+        if !self.cpu_affinity.is_empty() {
+            results.push(set_affinity(&self.cpu_affinity));
+        }
+        if let Some(priority) = self.rt_priority {
+            results.push(set_fifo_priority(priority));
+        }
+        results
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn set_affinity(cpus: &[usize]) -> Result<(), SchedulingError> {
+    let available = num_cpus::get();
+    if let Some(&cpu) = cpus.iter().find(|&&cpu| cpu >= available || cpu >= libc::CPU_SETSIZE as usize) {
+        return Err(SchedulingError::InvalidCpu { cpu, available });
+    }
+
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        for &cpu in cpus {
+            libc::CPU_SET(cpu, &mut set);
+        }
+
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(SchedulingError::Affinity(std::io::Error::last_os_error()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn set_fifo_priority(priority: i32) -> Result<(), SchedulingError> {
+    if !(1..=MAX_RT_PRIORITY).contains(&priority) {
+        return Err(SchedulingError::InvalidPriority(priority));
+    }
+
+    let param = libc::sched_param { sched_priority: priority };
+    if unsafe { libc::sched_setscheduler(0, libc::SCHED_FIFO, &param) } != 0 {
+        return Err(SchedulingError::RealTime(std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_affinity(_cpus: &[usize]) -> Result<(), SchedulingError> {
+    Err(SchedulingError::Unsupported("CPU affinity"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_fifo_priority(_priority: i32) -> Result<(), SchedulingError> {
+    Err(SchedulingError::Unsupported("real-time priority"))
+}
+
+/// Errors applying thread scheduling
+#[derive(Debug, Error)]
+pub enum SchedulingError {
+    #[error("CPU {cpu} does not exist ({available} available)")]
+    InvalidCpu { cpu: usize, available: usize },
+
+    #[error("Real-time priority {0} out of range (1-99)")]
+    InvalidPriority(i32),
+
+    #[error("Failed to set CPU affinity: {0}")]
+    Affinity(std::io::Error),
+
+    #[error("Failed to set SCHED_FIFO priority (needs CAP_SYS_NICE or an rtprio limit): {0}")]
+    RealTime(std::io::Error),
+
+    #[error("{0} is not supported on this platform")]
+    Unsupported(&'static str),
+}
@@ -2,8 +2,10 @@
 
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use std::fs::OpenOptions;
+use std::fs::{File, OpenOptions};
 use std::io::ErrorKind;
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
 use memmap2::{MmapOptions, MmapMut};
 use parking_lot::RwLock;
 use tracing::{info, warn, error, debug};
@@ -11,6 +13,23 @@ use tracing::{info, warn, error, debug};
 use crate::backend::types::{
     FrameHeader, ControlBlock, RawFrame, ConnectionConfig
 };
+use crate::backend::fd_passing;
+use crate::backend::integrity::Integrity;
+use crate::backend::memory::MappingMemory;
+use crate::backend::protocol::{Handshake, ProtocolError, CAP_CHECKSUMS, CAP_FRAME_METADATA};
+
+/// File backing a shared memory region
+///
+/// A bare name lives in `/dev/shm`; anything containing a `/` is used as a path,
+/// so hugetlbfs mounts and bind-mounted files from containers work as well.
+/// Regions handed over a socket (`unix:...`) have no file; see `fd_passing`.
+pub fn region_path(shm_name: &str) -> PathBuf {
+    if shm_name.contains('/') {
+        PathBuf::from(shm_name)
+    } else {
+        PathBuf::from("/dev/shm").join(shm_name)
+    }
+}
 
 /// Shared memory reader with zero-copy frame access
 pub struct SharedMemoryReader {
@@ -37,6 +56,9 @@ pub struct SharedMemoryReader {
     // Performance monitoring
     frame_count: Arc<RwLock<u64>>,
     error_count: Arc<RwLock<u64>>,
+    corrupted_count: Arc<RwLock<u64>>,
+    mapping_memory: Arc<RwLock<MappingMemory>>,
+    protocol: Option<Handshake>,
 }
 
 impl SharedMemoryReader {
@@ -57,6 +79,9 @@ impl SharedMemoryReader {
             last_frame_time: Arc::new(RwLock::new(Instant::now())),
             frame_count: Arc::new(RwLock::new(0)),
             error_count: Arc::new(RwLock::new(0)),
+            corrupted_count: Arc::new(RwLock::new(0)),
+            mapping_memory: Arc::new(RwLock::new(MappingMemory::default())),
+            protocol: None,
         };
         
         Ok(reader)
@@ -66,23 +91,11 @@ impl SharedMemoryReader {
     pub async fn connect(&mut self) -> Result<(), SharedMemoryError> {
         *self.last_connection_attempt.write() = Instant::now();
         
-        let file_path = format!("/dev/shm/{}", self.shm_name);
-        if self.config.verbose_logging {
-            info!("🔌 Opening shared memory: {}", file_path);
-        }
-        
-        // Open the shared memory file
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(&file_path)
-            .map_err(|e| match e.kind() {
-                ErrorKind::NotFound => SharedMemoryError::NotFound(self.shm_name.clone()),
-                _ => SharedMemoryError::Io(e),
-            })?;
+        // Open the shared memory file, or receive the producer's memfd
+        let file = self.open_region()?;
         
         // Memory map the file
-        let mmap = unsafe { 
+        let mut mmap = unsafe { 
             MmapOptions::new()
                 .map_mut(&file)
                 .map_err(|e| SharedMemoryError::MappingFailed(e.to_string()))?
@@ -93,7 +106,14 @@ impl SharedMemoryReader {
         }
         
         // Validate and initialize memory layout
-        self.initialize_memory_layout(&mmap)?;
+        self.initialize_memory_layout(&mut mmap)?;
+        
+        // Keep the frame ring resident if requested
+        let memory = self.config.memory.prepare_mapping(&mmap);
+        if memory.locked || memory.huge_pages {
+            info!("🔒 Shared memory mapping: locked={}, huge pages={}", memory.locked, memory.huge_pages);
+        }
+        *self.mapping_memory.write() = memory;
         
         // Store the memory map
         *self.mmap.write() = Some(mmap);
@@ -104,8 +124,39 @@ impl SharedMemoryReader {
         Ok(())
     }
     
+    /// Open the file backing the region
+    fn open_region(&self) -> Result<File, SharedMemoryError> {
+        if let Some(socket) = fd_passing::socket_path(&self.shm_name) {
+            if self.config.verbose_logging {
+                info!("🔌 Requesting shared memory descriptor from {}", socket);
+            }
+            
+            let stream = UnixStream::connect(socket).map_err(|e| match e.kind() {
+                // A stale socket left behind by an exited producer refuses connections
+                ErrorKind::NotFound | ErrorKind::ConnectionRefused => SharedMemoryError::NotFound(socket.to_string()),
+                _ => SharedMemoryError::Io(e),
+            })?;
+            stream.set_read_timeout(Some(self.config.frame_timeout))?;
+            return Ok(File::from(fd_passing::recv_fd(&stream)?));
+        }
+        
+        let file_path = region_path(&self.shm_name);
+        if self.config.verbose_logging {
+            info!("🔌 Opening shared memory: {}", file_path.display());
+        }
+        
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&file_path)
+            .map_err(|e| match e.kind() {
+                ErrorKind::NotFound => SharedMemoryError::NotFound(file_path.display().to_string()),
+                _ => SharedMemoryError::Io(e),
+            })
+    }
+    
     /// Initialize memory layout from control block
-    fn initialize_memory_layout(&mut self, mmap: &MmapMut) -> Result<(), SharedMemoryError> {
+    fn initialize_memory_layout(&mut self, mmap: &mut MmapMut) -> Result<(), SharedMemoryError> {
         // Validate memory size
         if mmap.len() < self.control_block_size {
             return Err(SharedMemoryError::InvalidLayout(
@@ -115,7 +166,7 @@ impl SharedMemoryReader {
         
         // Read control block
         let control_block = unsafe {
-            &*(mmap.as_ptr() as *const ControlBlock)
+            &mut *(mmap.as_mut_ptr() as *mut ControlBlock)
         };
         
         if self.config.verbose_logging {
@@ -123,6 +174,19 @@ impl SharedMemoryReader {
                    control_block.write_index, control_block.active, control_block.frame_count);
         }
         
+        // Refuse layouts we would misread before trusting any other field
+        let handshake = Handshake::negotiate(control_block)?;
+        match handshake.version {
+            Some(version) => {
+                info!("🤝 Producer protocol {} ({})", version, handshake.describe());
+                unsafe {
+                    std::ptr::write_volatile(&mut control_block.consumer_capabilities, handshake.capabilities);
+                }
+            }
+            None => warn!("⚠️ Producer does not report a protocol version, assuming the legacy layout"),
+        }
+        self.protocol = Some(handshake);
+        
         // Extract metadata area size
         self.metadata_area_size = control_block.metadata_size as usize;
         if self.metadata_area_size == 0 {
@@ -189,6 +253,11 @@ impl SharedMemoryReader {
         *self.connected.read()
     }
     
+    /// Check if the producer negotiated a capability on connect
+    fn supports(&self, capability: u32) -> bool {
+        self.protocol.is_some_and(|handshake| handshake.supports(capability))
+    }
+    
     /// Check connection health
     pub fn check_connection_health(&self) -> bool {
         if !self.is_connected() {
@@ -222,6 +291,19 @@ impl SharedMemoryReader {
         true
     }
     
+    /// Number of frames written by the producer but not yet read
+    pub fn backlog(&self) -> u64 {
+        let mmap_lock = self.mmap.read();
+        let Some(mmap) = mmap_lock.as_ref() else {
+            return 0;
+        };
+        
+        let control_block = unsafe {
+            &*(mmap.as_ptr() as *const ControlBlock)
+        };
+        control_block.write_index.saturating_sub(*self.last_processed_index.read())
+    }
+    
     /// Get next frame with zero-copy semantics
     pub async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
         if !self.is_connected() {
@@ -314,8 +396,22 @@ impl SharedMemoryReader {
             Arc::from(slice)
         };
         
-        // Read metadata if present
-        let metadata = if header.metadata_size > 0 {
+        // Verify the copy against the producer checksum to catch frames overwritten mid-read
+        let integrity = if self.supports(CAP_CHECKSUMS) { Integrity::verify(&header, &frame_data) } else { Integrity::Unchecked };
+        if let Integrity::Corrupted { expected, actual } = integrity {
+            let corrupted = {
+                let mut count = self.corrupted_count.write();
+                *count += 1;
+                *count
+            };
+            warn!("🧨 Corrupted frame {} (seq {}): checksum {:08x}, expected {:08x} ({} corrupted so far)",
+                  header.frame_id, header.sequence_number, actual, expected, corrupted);
+            *self.last_processed_index.write() = frame_index;
+            return Ok(None);
+        }
+        
+        // Read metadata if present and negotiated
+        let metadata = if header.metadata_size > 0 && self.supports(CAP_FRAME_METADATA) {
             let metadata_start = frame_offset + header.metadata_offset as usize;
             let metadata_end = metadata_start + header.metadata_size as usize;
             
@@ -404,8 +500,11 @@ impl SharedMemoryReader {
             shm_name: self.shm_name.clone(),
             frames_processed: *self.frame_count.read(),
             error_count: *self.error_count.read(),
+            corrupted_frames: *self.corrupted_count.read(),
             last_frame_elapsed: self.last_frame_time.read().elapsed(),
             control_block: control_stats,
+            memory: *self.mapping_memory.read(),
+            protocol: self.protocol,
         }
     }
     
@@ -435,6 +534,9 @@ pub enum SharedMemoryError {
     #[error("Invalid memory layout: {0}")]
     InvalidLayout(String),
     
+    #[error("Protocol mismatch: {0}")]
+    Protocol(#[from] ProtocolError),
+    
     #[error("Invalid frame offset: {0}")]
     InvalidFrameOffset(usize),
     
@@ -462,8 +564,14 @@ pub struct ConnectionStatistics {
     pub shm_name: String,
     pub frames_processed: u64,
     pub error_count: u64,
+    /// Frames rejected because their data did not match the producer checksum
+    pub corrupted_frames: u64,
     pub last_frame_elapsed: Duration,
     pub control_block: Option<ControlBlockStats>,
+    /// Locking and huge page advice actually applied to the mapping
+    pub memory: MappingMemory,
+    /// Handshake from the last connection, None before connecting
+    pub protocol: Option<Handshake>,
 }
 
 /// Control block statistics
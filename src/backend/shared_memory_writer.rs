@@ -0,0 +1,386 @@
+// src/backend/shared_memory_writer.rs - Producer Side of the MiVi Shared Memory Protocol
+
+use std::fs::{File, OpenOptions};
+use std::os::fd::AsRawFd;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{fence, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use memmap2::{MmapMut, MmapOptions};
+use tracing::{info, warn};
+
+use crate::backend::fd_passing;
+use crate::backend::integrity::{self, FLAG_CRC32};
+use crate::backend::protocol::{self, CAP_CHECKSUMS, CAP_FRAME_METADATA, CAP_LAYOUT_JSON};
+use crate::backend::registry::{Registry, StreamAnnouncement};
+use crate::backend::shared_memory::{region_path, SharedMemoryError};
+use crate::backend::types::{ControlBlock, FrameFormat, FrameHeader};
+
+/// Layout of a shared memory region created by the writer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriterConfig {
+    /// Frame slots in the ring
+    pub max_frames: usize,
+    /// Bytes per slot, including the frame header and per-frame metadata
+    pub frame_slot_size: usize,
+    /// Size of the JSON metadata area after the control block
+    pub metadata_size: usize,
+    /// Send a CRC32 of every frame so readers can detect torn reads
+    pub checksums: bool,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        Self {
+            max_frames: 7,
+            frame_slot_size: 3840 * 2160 * 4 + std::mem::size_of::<FrameHeader>(),
+            metadata_size: 4096,
+            checksums: false,
+        }
+    }
+}
+
+impl WriterConfig {
+    /// Slots sized for frames of the given dimensions and format, plus `metadata_capacity` bytes of JSON
+    pub fn for_frames(width: u32, height: u32, format: FrameFormat, metadata_capacity: usize) -> Self {
+        let data_size = format.data_size(width, height);
+        Self {
+            frame_slot_size: std::mem::size_of::<FrameHeader>() + data_size + metadata_capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Total size of the region
+    pub fn region_size(&self) -> usize {
+        std::mem::size_of::<ControlBlock>() + self.metadata_size + self.max_frames * self.frame_slot_size
+    }
+}
+
+/// Publishes frames into a shared memory ring readable by `SharedMemoryReader`
+///
+/// Frame `n` (counting from 1) is written to slot `n % max_frames` and then
+/// published by storing `n` in the control block's `write_index`, so a sequential
+/// reader finds it at `last_processed + 1` and a catch-up reader never sees a
+/// slot that is still being written.
+pub struct SharedMemoryWriter {
+    mmap: MmapMut,
+    file: File,
+    /// None for memfd regions, which have no name to clean up
+    path: Option<PathBuf>,
+    /// Name readers pass as `--shm-name`, None for memfd regions
+    shm_name: Option<String>,
+    /// Whether the stream is listed in the discovery registry
+    announced: bool,
+    config: WriterConfig,
+    data_offset: usize,
+    next_index: u64,
+}
+
+impl SharedMemoryWriter {
+    /// Create (or replace) the region file and mark it active
+    ///
+    /// `shm_name` is resolved like the reader's, see `region_path`.
+    pub fn create(shm_name: &str, config: WriterConfig) -> Result<Self, SharedMemoryError> {
+        validate(&config)?;
+
+        let path = region_path(shm_name);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        file.set_len(config.region_size() as u64)?;
+
+        let mut writer = Self::initialize(file, Some(path), config)?;
+        writer.shm_name = Some(shm_name.to_string());
+        Ok(writer)
+    }
+
+    /// Create an anonymous memfd region, handed to readers with `share_with`
+    ///
+    /// Nothing is created in /dev/shm, so only processes given the descriptor can
+    /// map the frames, and the memory is freed once both sides have exited.
+    pub fn create_memfd(name: &str, config: WriterConfig) -> Result<Self, SharedMemoryError> {
+        validate(&config)?;
+        let file = fd_passing::create_memfd(name, config.region_size() as u64)?;
+        Self::initialize(file, None, config)
+    }
+
+    /// Lay out the control block and ring in a file already sized for `config`
+    fn initialize(file: File, path: Option<PathBuf>, config: WriterConfig) -> Result<Self, SharedMemoryError> {
+        let mut mmap = unsafe {
+            MmapOptions::new()
+                .map_mut(&file)
+                .map_err(|e| SharedMemoryError::MappingFailed(e.to_string()))?
+        };
+
+        let control_block_size = std::mem::size_of::<ControlBlock>();
+        let layout = serde_json::json!({
+            "frame_slot_size": config.frame_slot_size,
+            "max_frames": config.max_frames,
+            "producer": "mivi_frame_viewer",
+        })
+        .to_string();
+        if layout.len() >= config.metadata_size {
+            return Err(SharedMemoryError::InvalidLayout(format!(
+                "metadata area of {} bytes cannot hold the layout description", config.metadata_size
+            )));
+        }
+        mmap[control_block_size..control_block_size + layout.len()].copy_from_slice(layout.as_bytes());
+
+        let mut control_block = ControlBlock {
+            write_index: 0,
+            read_index: 0,
+            frame_count: 0,
+            total_frames_written: 0,
+            total_frames_read: 0,
+            dropped_frames: 0,
+            active: true,
+            _padding1: [0; 7],
+            last_write_time: 0,
+            last_read_time: 0,
+            metadata_offset: control_block_size as u32,
+            metadata_size: config.metadata_size as u32,
+            flags: 0,
+            magic: 0,
+            protocol_version: 0,
+            producer_capabilities: 0,
+            consumer_capabilities: 0,
+            _padding2: [0; 168],
+        };
+        let checksums = if config.checksums { CAP_CHECKSUMS } else { 0 };
+        protocol::stamp(&mut control_block, checksums | CAP_FRAME_METADATA | CAP_LAYOUT_JSON);
+        unsafe {
+            std::ptr::write_volatile(mmap.as_mut_ptr() as *mut ControlBlock, control_block);
+        }
+
+        info!("📤 Created shared memory writer: {} ({} slots of {} bytes)",
+              path.as_deref().map_or("memfd".into(), Path::to_string_lossy), config.max_frames, config.frame_slot_size);
+
+        Ok(Self {
+            mmap,
+            file,
+            path,
+            shm_name: None,
+            announced: false,
+            config,
+            data_offset: control_block_size + config.metadata_size,
+            next_index: 1,
+        })
+    }
+
+    /// Write a frame into the next slot and publish it, returning its sequence number
+    pub fn publish(
+        &mut self,
+        width: u32,
+        height: u32,
+        format: FrameFormat,
+        data: &[u8],
+        metadata: Option<&str>,
+    ) -> Result<u64, SharedMemoryError> {
+        let format_code = format.to_code();
+        if format_code == 0 {
+            return Err(SharedMemoryError::Other(format!("{} has no shared memory format code", format.to_string())));
+        }
+
+        let header_size = std::mem::size_of::<FrameHeader>();
+        let metadata = metadata.unwrap_or("").as_bytes();
+        let metadata_size = if metadata.is_empty() { 0 } else { metadata.len() + 1 }; // NUL-terminated
+        let needed = header_size + data.len() + metadata_size;
+        if needed > self.config.frame_slot_size {
+            return Err(SharedMemoryError::InvalidFrameSize {
+                start: header_size,
+                end: needed,
+                total: self.config.frame_slot_size,
+            });
+        }
+
+        let index = self.next_index;
+        let now = now_ns();
+        let mut header = FrameHeader {
+            frame_id: index,
+            timestamp: now,
+            width,
+            height,
+            bytes_per_pixel: format.bytes_per_pixel(),
+            data_size: data.len() as u32,
+            format_code,
+            flags: 0,
+            sequence_number: index,
+            metadata_offset: if metadata_size > 0 { (header_size + data.len()) as u32 } else { 0 },
+            metadata_size: metadata_size as u32,
+            padding: [0; 4],
+        };
+        if self.config.checksums {
+            header.flags |= FLAG_CRC32;
+            header.padding[0] = integrity::crc32(data) as u64;
+        }
+
+        // Fill the slot completely before the index that makes it visible
+        let slot_offset = self.data_offset + (index as usize % self.config.max_frames) * self.config.frame_slot_size;
+        let data_start = slot_offset + header_size;
+        self.mmap[data_start..data_start + data.len()].copy_from_slice(data);
+        if metadata_size > 0 {
+            let metadata_start = data_start + data.len();
+            self.mmap[metadata_start..metadata_start + metadata.len()].copy_from_slice(metadata);
+            self.mmap[metadata_start + metadata.len()] = 0;
+        }
+        unsafe {
+            std::ptr::write_volatile(self.mmap.as_mut_ptr().add(slot_offset) as *mut FrameHeader, header);
+        }
+        fence(Ordering::Release);
+
+        let max_frames = self.config.max_frames as u64;
+        let control_block = self.control_block_mut();
+        if control_block.frame_count >= max_frames {
+            control_block.dropped_frames += 1;
+        } else {
+            control_block.frame_count += 1;
+        }
+        control_block.total_frames_written += 1;
+        control_block.last_write_time = now;
+        unsafe {
+            std::ptr::write_volatile(&mut control_block.write_index, index);
+        }
+
+        self.next_index += 1;
+        Ok(index)
+    }
+
+    /// Number of frames published so far
+    pub fn frames_written(&self) -> u64 {
+        self.next_index - 1
+    }
+
+    /// Path of the region file, None for memfd regions
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// List the stream in the discovery registry so viewers can pick it without knowing its name
+    ///
+    /// The entry is removed again when the writer is dropped. memfd regions cannot be announced.
+    pub fn announce(&mut self, width: u32, height: u32, format: FrameFormat) -> Result<(), SharedMemoryError> {
+        let shm_name = self.shm_name.as_deref()
+            .ok_or_else(|| SharedMemoryError::Other("memfd regions cannot be announced".to_string()))?;
+        Registry::open()?.announce(&StreamAnnouncement::new(shm_name, format, width, height))?;
+        self.announced = true;
+        Ok(())
+    }
+
+    /// Pass the region's descriptor to a reader connected over a Unix socket
+    pub fn share_with(&self, stream: &UnixStream) -> Result<(), SharedMemoryError> {
+        fd_passing::send_fd(stream, self.file.as_raw_fd())?;
+        Ok(())
+    }
+
+    fn control_block_mut(&mut self) -> &mut ControlBlock {
+        unsafe { &mut *(self.mmap.as_mut_ptr() as *mut ControlBlock) }
+    }
+}
+
+impl Drop for SharedMemoryWriter {
+    fn drop(&mut self) {
+        // Readers see the producer go away before the file disappears
+        unsafe {
+            std::ptr::write_volatile(&mut self.control_block_mut().active, false);
+        }
+        if let (true, Some(shm_name)) = (self.announced, &self.shm_name) {
+            if let Err(e) = Registry::open().and_then(|mut registry| registry.withdraw(shm_name)) {
+                warn!("⚠️ Failed to withdraw {} from the stream registry: {}", shm_name, e);
+            }
+        }
+        if let Some(path) = &self.path {
+            if let Err(e) = std::fs::remove_file(path) {
+                warn!("⚠️ Failed to remove {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+fn validate(config: &WriterConfig) -> Result<(), SharedMemoryError> {
+    if config.max_frames == 0 || config.frame_slot_size <= std::mem::size_of::<FrameHeader>() {
+        return Err(SharedMemoryError::InvalidLayout(format!(
+            "{} slots of {} bytes cannot hold a frame", config.max_frames, config.frame_slot_size
+        )));
+    }
+    Ok(())
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::shared_memory::SharedMemoryReader;
+    use crate::backend::types::ConnectionConfig;
+
+    #[test]
+    fn test_writer_reader_loopback() {
+        let name = format!("mivi_writer_test_{}", std::process::id());
+        let config = WriterConfig { max_frames: 3, checksums: true, ..WriterConfig::for_frames(4, 2, FrameFormat::Grayscale, 64) };
+        let mut writer = SharedMemoryWriter::create(&name, config).unwrap();
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let mut reader = SharedMemoryReader::new(&name, ConnectionConfig::default()).unwrap();
+            reader.connect().await.unwrap();
+            let handshake = reader.get_statistics().protocol.unwrap();
+            assert_eq!(handshake.version, Some(protocol::PROTOCOL_VERSION));
+            assert!(handshake.supports(CAP_CHECKSUMS));
+            assert!(reader.get_next_frame(false).await.unwrap().is_none());
+
+            for value in 1..=2u8 {
+                writer.publish(4, 2, FrameFormat::Grayscale, &[value; 8], Some(r#"{"probe":"linear"}"#)).unwrap();
+            }
+
+            let first = reader.get_next_frame(false).await.unwrap().unwrap();
+            assert_eq!((first.header.sequence_number, &first.data[..]), (1, &[1u8; 8][..]));
+            assert_eq!(first.metadata.as_deref(), Some(r#"{"probe":"linear"}"#));
+            let second = reader.get_next_frame(false).await.unwrap().unwrap();
+            assert_eq!((second.header.width, second.header.height, second.data[0]), (4, 2, 2));
+            assert!(reader.get_next_frame(false).await.unwrap().is_none());
+        });
+
+        // Frames larger than a slot are rejected
+        assert!(writer.publish(64, 64, FrameFormat::Grayscale, &[0; 4096], None).is_err());
+
+        // Packed 12-bit slots hold three bytes per two pixels
+        let packed = WriterConfig::for_frames(4, 2, FrameFormat::Gray12Packed, 0);
+        assert_eq!(packed.frame_slot_size, std::mem::size_of::<FrameHeader>() + 12);
+    }
+
+    #[test]
+    fn test_memfd_over_unix_socket() {
+        let socket = std::env::temp_dir().join(format!("mivi_writer_test_{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&socket);
+        let listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+
+        let config = WriterConfig { max_frames: 2, ..WriterConfig::for_frames(2, 2, FrameFormat::Grayscale, 0) };
+        let mut writer = SharedMemoryWriter::create_memfd("mivi_writer_test", config).unwrap();
+        assert!(writer.path().is_none());
+        writer.publish(2, 2, FrameFormat::Grayscale, &[7; 4], None).unwrap();
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let shm_name = format!("unix:{}", socket.display());
+        let reader = std::thread::spawn(move || {
+            runtime.block_on(async {
+                let mut reader = SharedMemoryReader::new(&shm_name, ConnectionConfig::default()).unwrap();
+                reader.connect().await.unwrap();
+                reader.get_next_frame(false).await.unwrap().unwrap()
+            })
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        writer.share_with(&stream).unwrap();
+        let frame = reader.join().unwrap();
+        assert_eq!(&frame.data[..], &[7; 4]);
+        std::fs::remove_file(&socket).unwrap();
+    }
+}
@@ -0,0 +1,407 @@
+// src/backend/sinks.rs - Recorder, Network Forwarder and Snapshot Frame Sinks
+
+//! Frame sinks the backend attaches from its configuration.
+//!
+//! The recorder and the forwarder write displayed frames in the TCP transport
+//! format (`tcp_source::encode_frame`), so a recording can be replayed with
+//! `tcp_source::read_frame` and a forwarded stream opened by another viewer as
+//! `tcp://host:port`. Frames are sent as BGRA, which every viewer converts.
+//! The auto-recorder writes the same format, one file per burst of frames.
+//!
+//! Producer metadata leaves the viewer through the export anonymization
+//! profile, and with a recording key, recordings are sealed chunk by chunk
+//! (see `encryption`) and snapshots as whole files, under an `.enc` suffix.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::backend::frame_dumper::FrameDumper;
+use crate::backend::frame_sink::FrameSink;
+use crate::backend::tcp_source::encode_frame;
+use crate::backend::types::ProcessedFrame;
+use crate::backend::BackendEvent;
+use crate::encryption::{self, RecordingCipher};
+use crate::redaction::AnonymizationProfile;
+
+/// Format code of BGR(A) frames in the wire header
+const FORMAT_BGR: u32 = 0x02;
+
+/// Messages buffered per forwarding client before it skips ahead
+const MAX_QUEUED_MESSAGES: usize = 4;
+
+/// Extension of recordings started by `AutoRecorderSink`
+pub const RECORDING_EXTENSION: &str = "mivi";
+
+/// How frames written or sent by the sinks are protected
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordingPolicy {
+    /// Applied to producer metadata before it leaves the viewer
+    pub anonymization: AnonymizationProfile,
+    /// Seal every written file with the recording key
+    pub encryption: Option<RecordingCipher>,
+}
+
+/// Encode the displayed image of a frame as one BGRA transport message, with anonymized metadata
+pub fn encode_display_frame(frame: &ProcessedFrame, anonymization: AnonymizationProfile) -> Vec<u8> {
+    let mut data = frame.rgba().as_bytes().to_vec();
+    for pixel in data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+
+    let mut header = frame.header;
+    header.format_code = FORMAT_BGR;
+    header.bytes_per_pixel = 4;
+    let metadata = frame.metadata.as_deref().map(|text| anonymization.metadata_text(text));
+    encode_frame(&header, &data, metadata.as_deref())
+}
+
+/// Appends displayed frames to a file
+pub struct RecorderSink {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    policy: RecordingPolicy,
+    frames: u64,
+    failed: bool,
+}
+
+impl RecorderSink {
+    /// Create or truncate the recording file, `path` + `.enc` when it is sealed
+    pub async fn create(path: &Path, policy: &RecordingPolicy) -> std::io::Result<Self> {
+        let path = match &policy.encryption {
+            Some(_) => encryption::encrypted_path(path),
+            None => path.to_path_buf(),
+        };
+        let mut writer = BufWriter::new(File::create(&path).await?);
+        if let Some(cipher) = &policy.encryption {
+            writer.write_all(&cipher.stream_header()).await?;
+        }
+        info!("⏺️ Recording displayed frames to {}", path.display());
+        Ok(Self { path, writer, policy: policy.clone(), frames: 0, failed: false })
+    }
+
+    /// File the frames are written to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl FrameSink for RecorderSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        let message = encode_display_frame(&frame, self.policy.anonymization);
+        let message = match &self.policy.encryption {
+            Some(cipher) => match cipher.encrypt_chunk(self.frames, &message) {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    warn!("⚠️ Sealing recording {} failed: {}", self.path.display(), e);
+                    self.failed = true;
+                    return;
+                }
+            },
+            None => message,
+        };
+        match self.writer.write_all(&message).await {
+            Ok(()) => self.frames += 1,
+            Err(e) => {
+                warn!("⚠️ Recording to {} failed: {}", self.path.display(), e);
+                self.failed = true;
+            }
+        }
+    }
+
+    async fn on_event(&mut self, _event: BackendEvent) {}
+
+    fn keep_running(&self) -> bool {
+        !self.failed
+    }
+
+    async fn finish(&mut self) {
+        if let Err(e) = self.writer.flush().await {
+            warn!("⚠️ Failed to flush recording {}: {}", self.path.display(), e);
+        }
+        info!("⏺️ Recorded {} frames to {}", self.frames, self.path.display());
+    }
+}
+
+/// Starts a new recording when frames begin arriving and ends it once they stop
+///
+/// Idleness is checked on every backend event; statistics updates arrive at
+/// the statistics interval, so a recording ends at most that much later than
+/// the idle timeout.
+pub struct AutoRecorderSink {
+    dir: PathBuf,
+    idle_timeout: Duration,
+    policy: RecordingPolicy,
+    recorder: Option<RecorderSink>,
+    last_frame: Option<Instant>,
+}
+
+impl AutoRecorderSink {
+    /// Record into new files in `dir` (created on the first recording), protected by `policy`
+    pub fn new(dir: &Path, idle_timeout: Duration, policy: &RecordingPolicy) -> Self {
+        info!("⏺️ Auto-recording to {} (stops after {:?} without frames)", dir.display(), idle_timeout);
+        Self { dir: dir.to_path_buf(), idle_timeout, policy: policy.clone(), recorder: None, last_frame: None }
+    }
+
+    /// Check if a recording is in progress
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    async fn start_recording(&mut self) -> std::io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let name = format!("recording_{}.{}", chrono::Local::now().format("%Y%m%d_%H%M%S"), RECORDING_EXTENSION);
+        self.recorder = Some(RecorderSink::create(&self.dir.join(name), &self.policy).await?);
+        Ok(())
+    }
+
+    async fn stop_recording(&mut self) {
+        if let Some(mut recorder) = self.recorder.take() {
+            recorder.finish().await;
+        }
+    }
+}
+
+#[async_trait]
+impl FrameSink for AutoRecorderSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        let now = Instant::now();
+        let resumed = self.last_frame.map_or(true, |last| now.duration_since(last) >= self.idle_timeout);
+        self.last_frame = Some(now);
+        if resumed {
+            // A gap longer than the timeout ends the previous study even without an event in between
+            self.stop_recording().await;
+        }
+
+        if self.recorder.is_none() {
+            // Retry a failed start only when a new study begins, not on every frame
+            if !resumed {
+                return;
+            }
+            if let Err(e) = self.start_recording().await {
+                warn!("⚠️ Failed to start auto-recording in {}: {}", self.dir.display(), e);
+                return;
+            }
+        }
+        if let Some(recorder) = self.recorder.as_mut().filter(|recorder| recorder.keep_running()) {
+            recorder.on_frame(frame).await;
+        }
+    }
+
+    async fn on_event(&mut self, _event: BackendEvent) {
+        if self.recorder.is_some() && self.last_frame.is_some_and(|last| last.elapsed() >= self.idle_timeout) {
+            info!("⏹️ No frames for {:?}, auto-recording stopped", self.idle_timeout);
+            self.stop_recording().await;
+        }
+    }
+
+    async fn finish(&mut self) {
+        self.stop_recording().await;
+    }
+}
+
+/// Sends displayed frames to every connected TCP client
+pub struct ForwarderSink {
+    address: SocketAddr,
+    anonymization: AnonymizationProfile,
+    messages: broadcast::Sender<Arc<[u8]>>,
+    task: JoinHandle<()>,
+}
+
+impl ForwarderSink {
+    /// Listen for clients on `host:port`, sending metadata through `anonymization`
+    pub async fn bind(address: &str, anonymization: AnonymizationProfile) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+        let (messages, _) = broadcast::channel(MAX_QUEUED_MESSAGES);
+        let task = tokio::spawn(Self::accept_clients(listener, messages.clone()));
+
+        info!("📤 Forwarding displayed frames on {}", address);
+        Ok(Self { address, anonymization, messages, task })
+    }
+
+    /// Address clients connect to
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    async fn accept_clients(listener: TcpListener, messages: broadcast::Sender<Arc<[u8]>>) {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    info!("🔗 Forwarding client connected: {}", peer);
+                    let _ = stream.set_nodelay(true);
+                    tokio::spawn(Self::serve_client(stream, peer, messages.subscribe()));
+                }
+                Err(e) => warn!("⚠️ Forwarding accept failed: {}", e),
+            }
+        }
+    }
+
+    async fn serve_client(mut stream: TcpStream, peer: SocketAddr, mut messages: broadcast::Receiver<Arc<[u8]>>) {
+        loop {
+            match messages.recv().await {
+                Ok(message) => {
+                    if let Err(e) = stream.write_all(&message).await {
+                        info!("🔌 Forwarding client {} disconnected: {}", peer, e);
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!("Forwarding client {} skipped {} frames", peer, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl FrameSink for ForwarderSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        if self.messages.receiver_count() > 0 {
+            let _ = self.messages.send(Arc::from(encode_display_frame(&frame, self.anonymization)));
+        }
+    }
+
+    async fn on_event(&mut self, _event: BackendEvent) {}
+}
+
+impl Drop for ForwarderSink {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Writes a PNG of the displayed frame at a fixed interval
+pub struct SnapshotSink {
+    dir: PathBuf,
+    interval: Duration,
+    encryption: Option<RecordingCipher>,
+    last: Option<Instant>,
+}
+
+impl SnapshotSink {
+    /// Write snapshots into `dir` (created on the first one) every `interval`, sealed if a cipher is given
+    pub fn new(dir: &Path, interval: Duration, encryption: Option<RecordingCipher>) -> Self {
+        info!("📸 Writing snapshots to {} every {:?}", dir.display(), interval);
+        Self { dir: dir.to_path_buf(), interval, encryption, last: None }
+    }
+}
+
+#[async_trait]
+impl FrameSink for SnapshotSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        let now = Instant::now();
+        if self.last.is_some_and(|last| now.duration_since(last) < self.interval) {
+            return;
+        }
+        self.last = Some(now);
+
+        let path = self.dir.join(format!("snapshot_{}_{:06}.png", chrono::Local::now().format("%Y%m%d_%H%M%S"), frame.header.frame_id));
+        let dir = self.dir.clone();
+        let encryption = self.encryption.clone();
+        let written = tokio::task::spawn_blocking(move || -> Result<PathBuf, String> {
+            let png = FrameDumper::encode_png(&frame).map_err(|e| e.to_string())?;
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            encryption::write_recording(&path, &png, encryption.as_ref()).map_err(|e| e.to_string())
+        })
+        .await;
+        match written {
+            Ok(Ok(path)) => debug!("📸 Snapshot written to {}", path.display()),
+            Ok(Err(e)) => warn!("⚠️ Failed to write snapshot: {}", e),
+            Err(e) => warn!("⚠️ Snapshot task failed: {}", e),
+        }
+    }
+
+    async fn on_event(&mut self, _event: BackendEvent) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::pixel_pool::RgbaBuffer;
+    use crate::backend::tcp_source::decode_message;
+    use crate::backend::types::{FrameFormat, FrameHeader};
+
+    fn frame() -> ProcessedFrame {
+        let header = FrameHeader { frame_id: 3, sequence_number: 3, ..FrameHeader::for_test(2, 1, 4, 0) };
+        let mut rgba = RgbaBuffer::new(2, 1);
+        rgba.make_mut_bytes().copy_from_slice(&[1, 2, 3, 255, 4, 5, 6, 255]);
+        ProcessedFrame::new(header, rgba, Some("{\"probe\":\"L12\"}".to_string()), Instant::now(), FrameFormat::RGBA)
+    }
+
+    #[test]
+    fn test_display_frame_roundtrip() {
+        let message = encode_display_frame(&frame(), AnonymizationProfile::Basic);
+        let decoded = decode_message(&message[4..]).unwrap();
+        assert_eq!(decoded.header.frame_id, 3);
+        assert_eq!(decoded.header.format_code, FORMAT_BGR);
+        assert_eq!(&decoded.data[..], &[3, 2, 1, 255, 6, 5, 4, 255]);
+        assert_eq!(decoded.metadata.as_deref(), Some("{\"probe\":\"L12\"}"));
+    }
+
+    #[test]
+    fn test_auto_recording_stops_when_idle() {
+        let dir = std::env::temp_dir().join(format!("mivi_auto_record_test_{}", std::process::id()));
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let mut sink = AutoRecorderSink::new(&dir, Duration::from_millis(20), &RecordingPolicy::default());
+            sink.on_event(BackendEvent::Connected).await;
+            assert!(!sink.is_recording());
+
+            sink.on_frame(frame()).await;
+            sink.on_frame(frame()).await;
+            assert!(sink.is_recording());
+
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            sink.on_event(BackendEvent::Disconnected).await;
+            assert!(!sink.is_recording());
+        });
+
+        let recordings: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(recordings.len(), 1);
+        let path = recordings[0].as_ref().unwrap().path();
+        assert_eq!(std::fs::metadata(&path).unwrap().len() as usize, 2 * encode_display_frame(&frame(), AnonymizationProfile::Basic).len());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_recording_sealed_and_anonymized() {
+        let dir = std::env::temp_dir().join(format!("mivi_sealed_record_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cipher = RecordingCipher::new(&encryption::generate_key());
+        let policy = RecordingPolicy { anonymization: AnonymizationProfile::Basic, encryption: Some(cipher.clone()) };
+
+        let mut identified = frame();
+        identified.metadata = Some(r#"{"probe":"L12","PatientName":"ROE^RICHARD","PatientID":"MRN-551902"}"#.to_string());
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let path = runtime.block_on(async {
+            let mut recorder = RecorderSink::create(&dir.join("study.mivi"), &policy).await.unwrap();
+            recorder.on_frame(identified).await;
+            recorder.finish().await;
+            recorder.path().to_path_buf()
+        });
+
+        assert_eq!(path, dir.join("study.mivi.enc"));
+        assert!(!dir.join("study.mivi").exists());
+        let sealed = std::fs::read(&path).unwrap();
+        assert!(!sealed.windows(3).any(|w| w == b"L12"));
+
+        let message = cipher.decrypt_stream(&sealed).unwrap();
+        let metadata = decode_message(&message[4..]).unwrap().metadata.unwrap();
+        assert!(metadata.contains("L12"));
+        assert!(!metadata.contains("PatientName") && !metadata.contains("ROE^RICHARD") && !metadata.contains("MRN-551902"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,151 @@
+// src/backend/stride.rs - Row Stride (Pitch) of Padded Producer Frames
+
+//! Some producers pad every row to an alignment (64 or 256 bytes is common for
+//! DMA and GPU buffers), so row `y` starts at `y * row_stride` rather than at
+//! `y * width * bytes_per_pixel`. Read as tightly packed, such frames appear
+//! sheared. The stride is announced either in the header, with `FLAG_ROW_STRIDE`
+//! and the stride in the low 32 bits of `padding[1]`, or as `"row_stride"` in
+//! the JSON metadata. Padded frames are packed once on arrival, so everything
+//! downstream keeps assuming tightly packed rows. Padded 12-bit packed rows each
+//! start on a byte boundary, so an odd width leaves the last sample slot of every
+//! row unused.
+
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::backend::packed12;
+use crate::backend::types::{FrameFormat, FrameHeader, RawFrame};
+
+/// Header flag: the low 32 bits of `padding[1]` carry the row stride in bytes
+pub const FLAG_ROW_STRIDE: u32 = 0x0000_0200;
+
+/// Metadata key announcing the row stride in bytes
+pub const METADATA_KEY: &str = "row_stride";
+
+/// Bytes of pixel data in one row
+pub fn row_bytes(header: &FrameHeader) -> usize {
+    match FrameFormat::from_code(header.format_code) {
+        FrameFormat::Gray12Packed => packed12::packed_len(header.width as usize),
+        _ => header.width as usize * header.bytes_per_pixel as usize,
+    }
+}
+
+/// Row stride announced by the producer, if any
+pub fn row_stride(header: &FrameHeader, metadata: Option<&str>) -> Option<usize> {
+    if header.flags & FLAG_ROW_STRIDE != 0 {
+        return Some(header.padding[1] as u32 as usize);
+    }
+
+    // Only parse metadata that mentions the key
+    let metadata = metadata.filter(|text| text.contains(METADATA_KEY))?;
+    let json: serde_json::Value = serde_json::from_str(metadata).ok()?;
+    json.get(METADATA_KEY).and_then(|v| v.as_u64()).map(|stride| stride as usize)
+}
+
+/// Error packing a padded frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("Row stride {row_stride} does not fit rows of {row_bytes} bytes in {data_len} bytes of data")]
+pub struct StrideError {
+    pub row_stride: usize,
+    pub row_bytes: usize,
+    pub data_len: usize,
+}
+
+/// Drop the row padding of a frame, returning it unchanged if it has none
+///
+/// The last row may omit its padding. The packed frame no longer carries the
+/// stride flag, and `data_size` is updated to the packed size.
+pub fn pack_rows(raw_frame: RawFrame) -> Result<RawFrame, StrideError> {
+    let Some(row_stride) = row_stride(&raw_frame.header, raw_frame.metadata.as_deref()) else {
+        return Ok(raw_frame);
+    };
+    let row = row_bytes(&raw_frame.header);
+    let height = raw_frame.header.height as usize;
+    if row_stride == row || row_stride == 0 {
+        return Ok(raw_frame);
+    }
+
+    // The stride comes from the producer and may overflow the size computation
+    let error = StrideError { row_stride, row_bytes: row, data_len: raw_frame.data.len() };
+    let needed = height
+        .checked_sub(1)
+        .and_then(|rows| row_stride.checked_mul(rows))
+        .and_then(|offset| offset.checked_add(row));
+    if row_stride < row || !needed.is_some_and(|needed| raw_frame.data.len() >= needed) {
+        return Err(error);
+    }
+
+    let rows = (0..height).map(|y| &raw_frame.data[y * row_stride..y * row_stride + row]);
+    let width = raw_frame.header.width as usize;
+    let data = if FrameFormat::from_code(raw_frame.header.format_code) == FrameFormat::Gray12Packed && width % 2 == 1 {
+        // Packed samples continue across rows, so the unused slots have to go
+        let samples: Vec<u16> = rows.flat_map(|row| packed12::unpack(row, width).unwrap_or_default()).collect();
+        packed12::pack(&samples)
+    } else {
+        rows.flatten().copied().collect::<Vec<u8>>()
+    };
+
+    let mut header = raw_frame.header;
+    header.flags &= !FLAG_ROW_STRIDE;
+    header.padding[1] = 0;
+    header.data_size = data.len() as u32;
+    Ok(RawFrame {
+        header,
+        data: Arc::from(data.into_boxed_slice()),
+        metadata: raw_frame.metadata,
+        received_at: raw_frame.received_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(flags: u32, stride_padding: u64, data: Vec<u8>, metadata: Option<&str>) -> RawFrame {
+        let header = FrameHeader {
+            data_size: data.len() as u32,
+            flags,
+            padding: [0, stride_padding, 0, 0],
+            ..FrameHeader::for_test(3, 2, 1, 0x10)
+        };
+        RawFrame::new(header, Arc::from(data), metadata.map(str::to_string))
+    }
+
+    #[test]
+    fn test_pack_padded_rows() {
+        // Rows of 3 pixels padded to 4 bytes, last row unpadded
+        let packed = pack_rows(frame(FLAG_ROW_STRIDE, 4, vec![1, 2, 3, 0, 4, 5, 6], None)).unwrap();
+        assert_eq!(&packed.data[..], &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(packed.header.data_size, 6);
+        assert_eq!(packed.header.flags & FLAG_ROW_STRIDE, 0);
+
+        let packed = pack_rows(frame(0, 0, vec![1, 2, 3, 9, 4, 5, 6, 9], Some("{\"row_stride\": 4}"))).unwrap();
+        assert_eq!(&packed.data[..], &[1, 2, 3, 4, 5, 6]);
+
+        // Unpadded frames pass through, strides that cannot hold a row are rejected
+        let unpadded = pack_rows(frame(0, 0, vec![1, 2, 3, 4, 5, 6], None)).unwrap();
+        assert_eq!(unpadded.data.len(), 6);
+        assert!(pack_rows(frame(FLAG_ROW_STRIDE, 2, vec![1, 2, 3, 4, 5, 6], None)).is_err());
+        assert!(pack_rows(frame(FLAG_ROW_STRIDE, 8, vec![1, 2, 3, 4, 5, 6], None)).is_err());
+
+        // A stride overflowing the frame size is an error, not a panic
+        let huge = format!("{{\"row_stride\": {}}}", u64::MAX);
+        assert!(pack_rows(frame(0, 0, vec![1, 2, 3, 4, 5, 6], Some(&huge))).is_err());
+    }
+
+    #[test]
+    fn test_pack_padded_12bit_rows() {
+        // Rows of 3 packed pixels (6 bytes, the last slot unused) padded to 8 bytes
+        let samples = [0x123, 0x456, 0x789, 0xABC, 0xDEF, 0x321];
+        let mut data = packed12::pack(&samples[..3]);
+        data.resize(8, 0);
+        data.extend(packed12::pack(&samples[3..]));
+
+        let mut raw_frame = frame(FLAG_ROW_STRIDE, 8, data, None);
+        raw_frame.header.format_code = FrameFormat::Gray12Packed.to_code();
+        assert_eq!(row_bytes(&raw_frame.header), 6);
+        let packed = pack_rows(raw_frame).unwrap();
+        assert_eq!(packed12::unpack(&packed.data, 6).unwrap(), samples);
+    }
+}
@@ -0,0 +1,245 @@
+// src/backend/tcp_source.rs - Length-Prefixed Frame Transport over TCP
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::backend::frame_source::{FrameQueue, FrameSource};
+use crate::backend::shared_memory::{ConnectionStatistics, SharedMemoryError};
+use crate::backend::types::{ConnectionConfig, FrameHeader, RawFrame};
+
+/// Prefix of `--shm-name` values that name a TCP producer
+pub const TCP_PREFIX: &str = "tcp://";
+
+/// Largest message accepted, so a corrupt length prefix cannot exhaust memory
+pub const MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
+/// Frames buffered before the oldest is dropped, matching the default ring depth
+const MAX_QUEUED_FRAMES: usize = 7;
+
+/// Producer address if the name is `tcp://host:port`
+pub fn tcp_address(name: &str) -> Option<&str> {
+    name.strip_prefix(TCP_PREFIX).filter(|address| !address.is_empty())
+}
+
+/// Encode a frame as one message: a little-endian u32 length, the `FrameHeader`,
+/// the pixel data and the optional NUL-terminated JSON metadata
+///
+/// The header is sent in the same in-memory layout as in shared memory, so both
+/// ends must share endianness. `data_size` and `metadata_size` are filled in here
+/// and `metadata_offset` is ignored: metadata always follows the data.
+pub fn encode_frame(header: &FrameHeader, data: &[u8], metadata: Option<&str>) -> Vec<u8> {
+    let metadata = metadata.map(|m| format!("{}\0", m).into_bytes()).unwrap_or_default();
+    let mut header = *header;
+    header.data_size = data.len() as u32;
+    header.metadata_size = metadata.len() as u32;
+    header.metadata_offset = 0;
+
+    let header_size = std::mem::size_of::<FrameHeader>();
+    let length = header_size + data.len() + metadata.len();
+    let mut message = Vec::with_capacity(4 + length);
+    message.extend_from_slice(&(length as u32).to_le_bytes());
+    message.extend_from_slice(unsafe {
+        std::slice::from_raw_parts(&header as *const FrameHeader as *const u8, header_size)
+    });
+    message.extend_from_slice(data);
+    message.extend_from_slice(&metadata);
+    message
+}
+
+/// Read one message written by `encode_frame`
+pub async fn read_frame<R: AsyncRead + Unpin>(stream: &mut R) -> Result<RawFrame, SharedMemoryError> {
+    let mut length = [0u8; 4];
+    stream.read_exact(&mut length).await?;
+    let length = u32::from_le_bytes(length) as usize;
+
+    let header_size = std::mem::size_of::<FrameHeader>();
+    if length < header_size || length > MAX_MESSAGE_SIZE {
+        return Err(SharedMemoryError::InvalidFrameSize { start: header_size, end: length, total: MAX_MESSAGE_SIZE });
+    }
+
+    let mut message = vec![0u8; length];
+    stream.read_exact(&mut message).await?;
+    decode_message(&message)
+}
+
+/// Decode the part of an `encode_frame` message after the length prefix
+pub fn decode_message(message: &[u8]) -> Result<RawFrame, SharedMemoryError> {
+    let length = message.len();
+    let header_size = std::mem::size_of::<FrameHeader>();
+    if length < header_size {
+        return Err(SharedMemoryError::InvalidFrameSize { start: header_size, end: length, total: MAX_MESSAGE_SIZE });
+    }
+
+    let header = unsafe { std::ptr::read_unaligned(message.as_ptr() as *const FrameHeader) };
+    let data_end = header_size + header.data_size as usize;
+    if data_end + header.metadata_size as usize != length {
+        return Err(SharedMemoryError::InvalidFrameSize { start: header_size, end: data_end, total: length });
+    }
+
+    let metadata = (header.metadata_size > 0).then(|| {
+        let metadata = &message[data_end..];
+        let end = metadata.iter().position(|&b| b == 0).unwrap_or(metadata.len());
+        String::from_utf8_lossy(&metadata[..end]).into_owned()
+    });
+
+    Ok(RawFrame::new(header, Arc::from(&message[header_size..data_end]), metadata))
+}
+
+/// Receives frames from a producer on another machine
+///
+/// A background task reads messages as they arrive and queues them, so
+/// `get_next_frame` never blocks on the network, like the shared memory reader.
+pub struct TcpFrameReceiver {
+    address: String,
+    config: ConnectionConfig,
+    queue: Arc<FrameQueue>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl TcpFrameReceiver {
+    /// Create a receiver for `host:port`
+    pub fn new(address: &str, config: ConnectionConfig) -> Self {
+        Self {
+            address: address.to_string(),
+            config,
+            queue: Arc::new(FrameQueue::new(MAX_QUEUED_FRAMES)),
+            task: None,
+        }
+    }
+
+    async fn receive_frames(mut stream: TcpStream, queue: Arc<FrameQueue>) {
+        loop {
+            match read_frame(&mut stream).await {
+                Ok(frame) => queue.push(frame),
+                Err(SharedMemoryError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    info!("🔌 TCP producer closed the connection");
+                    break;
+                }
+                Err(e) => {
+                    // A bad length leaves the stream out of step, so there is no resyncing
+                    warn!("❌ TCP frame stream failed: {}", e);
+                    queue.record_error();
+                    break;
+                }
+            }
+        }
+
+        queue.set_connected(false);
+    }
+}
+
+#[async_trait]
+impl FrameSource for TcpFrameReceiver {
+    async fn connect(&mut self) -> Result<(), SharedMemoryError> {
+        if self.config.verbose_logging {
+            info!("🔌 Connecting to TCP producer: {}", self.address);
+        }
+
+        let stream = tokio::time::timeout(self.config.frame_timeout, TcpStream::connect(&self.address))
+            .await
+            .map_err(|_| SharedMemoryError::NotFound(self.address.clone()))?
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::ConnectionRefused => SharedMemoryError::NotFound(self.address.clone()),
+                _ => SharedMemoryError::Io(e),
+            })?;
+        stream.set_nodelay(true)?;
+
+        self.queue.set_connected(true);
+        self.task = Some(tokio::spawn(Self::receive_frames(stream, Arc::clone(&self.queue))));
+
+        info!("🔗 Connected to TCP producer: {}", self.address);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.queue.set_connected(false);
+
+        info!("🔌 Disconnected from TCP producer: {}", self.address);
+    }
+
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        self.queue.next(catch_up)
+    }
+
+    fn check_connection_health(&self) -> bool {
+        let healthy = self.queue.is_healthy(self.config.frame_timeout);
+        if !healthy && self.config.verbose_logging && self.queue.is_connected() {
+            warn!("⚠️ No frames received for {:?}", self.config.frame_timeout);
+        }
+        healthy
+    }
+
+    fn backlog(&self) -> u64 {
+        self.queue.backlog()
+    }
+
+    fn get_statistics(&self) -> ConnectionStatistics {
+        self.queue.statistics(format!("{}{}", TCP_PREFIX, self.address))
+    }
+}
+
+impl Drop for TcpFrameReceiver {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+
+    fn header(sequence_number: u64) -> FrameHeader {
+        FrameHeader { frame_id: sequence_number, sequence_number, data_size: 0, ..FrameHeader::for_test(2, 2, 1, 0x10) }
+    }
+
+    #[test]
+    fn test_tcp_loopback() {
+        assert_eq!(tcp_address("tcp://10.0.0.5:7000"), Some("10.0.0.5:7000"));
+        assert_eq!(tcp_address("ultrasound_frames"), None);
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap().to_string();
+
+            let mut receiver = TcpFrameReceiver::new(&address, ConnectionConfig::default());
+            receiver.connect().await.unwrap();
+            let (mut producer, _) = listener.accept().await.unwrap();
+
+            for sequence in 1..=3u8 {
+                let metadata = (sequence == 1).then_some(r#"{"probe":"linear"}"#);
+                producer.write_all(&encode_frame(&header(sequence as u64), &[sequence; 4], metadata)).await.unwrap();
+            }
+            while receiver.backlog() < 3 {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+
+            let first = receiver.get_next_frame(false).await.unwrap().unwrap();
+            assert_eq!((first.header.sequence_number, &first.data[..]), (1, &[1u8; 4][..]));
+            assert_eq!(first.metadata.as_deref(), Some(r#"{"probe":"linear"}"#));
+
+            // Catching up skips straight to the newest frame
+            let newest = receiver.get_next_frame(true).await.unwrap().unwrap();
+            assert_eq!(newest.header.sequence_number, 3);
+            assert!(receiver.get_next_frame(false).await.unwrap().is_none());
+
+            // A closed connection is reported once the queue is drained
+            drop(producer);
+            while receiver.get_statistics().connected {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+            assert!(matches!(receiver.get_next_frame(false).await, Err(SharedMemoryError::ConnectionLost)));
+        });
+    }
+}
@@ -0,0 +1,315 @@
+// src/backend/transform.rs - Geometric Frame Transforms (Rotate/Flip) for Medical Imaging
+
+use serde::{Deserialize, Serialize};
+
+/// Clockwise rotation in quarter turns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Rotation {
+    #[default]
+    None,
+    Cw90,
+    Cw180,
+    Cw270,
+}
+
+impl Rotation {
+    /// Create from a number of clockwise quarter turns (wraps)
+    pub fn from_quarter_turns(turns: i32) -> Self {
+        match turns.rem_euclid(4) {
+            1 => Rotation::Cw90,
+            2 => Rotation::Cw180,
+            3 => Rotation::Cw270,
+            _ => Rotation::None,
+        }
+    }
+
+    /// Create from degrees, rounded to the nearest quarter turn
+    pub fn from_degrees(degrees: i32) -> Self {
+        Self::from_quarter_turns((degrees as f32 / 90.0).round() as i32)
+    }
+
+    /// Get the number of clockwise quarter turns
+    pub fn quarter_turns(self) -> i32 {
+        match self {
+            Rotation::None => 0,
+            Rotation::Cw90 => 1,
+            Rotation::Cw180 => 2,
+            Rotation::Cw270 => 3,
+        }
+    }
+
+    /// Get rotation in degrees
+    pub fn degrees(self) -> i32 {
+        self.quarter_turns() * 90
+    }
+}
+
+/// Frame orientation: horizontal flip, then vertical flip, then clockwise rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub struct Orientation {
+    pub rotation: Rotation,
+    pub flip_horizontal: bool,
+    pub flip_vertical: bool,
+}
+
+impl Orientation {
+    /// FrameHeader flag bits 0-1: clockwise quarter turns reported by the probe
+    pub const FLAG_ROTATION_MASK: u32 = 0b0011;
+    /// FrameHeader flag bit 2: image is mirrored left/right
+    pub const FLAG_FLIP_HORIZONTAL: u32 = 0b0100;
+    /// FrameHeader flag bit 3: image is mirrored top/bottom
+    pub const FLAG_FLIP_VERTICAL: u32 = 0b1000;
+
+    /// Create an orientation
+    pub fn new(rotation: Rotation, flip_horizontal: bool, flip_vertical: bool) -> Self {
+        Self { rotation, flip_horizontal, flip_vertical }
+    }
+
+    /// Decode the probe orientation from FrameHeader flags
+    pub fn from_header_flags(flags: u32) -> Self {
+        Self {
+            rotation: Rotation::from_quarter_turns((flags & Self::FLAG_ROTATION_MASK) as i32),
+            flip_horizontal: flags & Self::FLAG_FLIP_HORIZONTAL != 0,
+            flip_vertical: flags & Self::FLAG_FLIP_VERTICAL != 0,
+        }
+    }
+
+    /// Check if the orientation leaves frames unchanged
+    pub fn is_identity(&self) -> bool {
+        self.canonical() == (false, 0)
+    }
+
+    /// Reduce to (mirror left/right, clockwise quarter turns)
+    ///
+    /// A vertical flip equals a horizontal flip followed by a half turn.
+    fn canonical(&self) -> (bool, i32) {
+        let mirror = self.flip_horizontal ^ self.flip_vertical;
+        let turns = self.rotation.quarter_turns() + if self.flip_vertical { 2 } else { 0 };
+        (mirror, turns.rem_euclid(4))
+    }
+
+    fn from_canonical(mirror: bool, turns: i32) -> Self {
+        Self {
+            rotation: Rotation::from_quarter_turns(turns),
+            flip_horizontal: mirror,
+            flip_vertical: false,
+        }
+    }
+
+    /// Combine with an orientation applied afterwards
+    pub fn then(&self, next: &Orientation) -> Orientation {
+        let (m1, k1) = self.canonical();
+        let (m2, k2) = next.canonical();
+        // Mirroring reverses the direction of earlier rotations
+        let turns = k2 + if m2 { -k1 } else { k1 };
+        Self::from_canonical(m1 ^ m2, turns)
+    }
+
+    /// Get the orientation that undoes this one
+    pub fn inverse(&self) -> Orientation {
+        let (mirror, turns) = self.canonical();
+        Self::from_canonical(mirror, if mirror { turns } else { -turns })
+    }
+
+    /// Map a point in normalized (0-1) coordinates from the source to the transformed frame
+    pub fn map_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let (mirror, turns) = self.canonical();
+        let x = if mirror { 1.0 - x } else { x };
+        match turns {
+            1 => (1.0 - y, x),
+            2 => (1.0 - x, 1.0 - y),
+            3 => (y, 1.0 - x),
+            _ => (x, y),
+        }
+    }
+
+    /// Map a normalized rectangle from the source to the transformed frame
+    pub fn map_rect(&self, rect: &CropRect) -> CropRect {
+        let (x0, y0) = self.map_point(rect.x, rect.y);
+        let (x1, y1) = self.map_point(rect.x + rect.width, rect.y + rect.height);
+        CropRect::new(x0.min(x1), y0.min(y1), (x1 - x0).abs(), (y1 - y0).abs())
+    }
+
+    /// Check if the orientation exchanges the horizontal and vertical axes
+    pub fn swaps_axes(&self) -> bool {
+        self.canonical().1 % 2 == 1
+    }
+
+    /// Get output dimensions for a frame of the given size
+    pub fn output_dimensions(&self, width: usize, height: usize) -> (usize, usize) {
+        if self.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Transform RGBA data, returning the new buffer and its dimensions
+    pub fn apply_rgba(&self, rgba_data: &[u8], width: usize, height: usize) -> (Vec<u8>, usize, usize) {
+        let (mirror, turns) = self.canonical();
+        let (out_width, out_height) = self.output_dimensions(width, height);
+        let mut output = vec![0u8; width * height * 4];
+
+        for y in 0..height {
+            for x in 0..width {
+                let sx = if mirror { width - 1 - x } else { x };
+                let (dx, dy) = match turns {
+                    1 => (height - 1 - y, sx),
+                    2 => (width - 1 - sx, height - 1 - y),
+                    3 => (y, width - 1 - sx),
+                    _ => (sx, y),
+                };
+
+                let src = (y * width + x) * 4;
+                let dst = (dy * out_width + dx) * 4;
+                output[dst..dst + 4].copy_from_slice(&rgba_data[src..src + 4]);
+            }
+        }
+
+        (output, out_width, out_height)
+    }
+}
+
+/// Region of interest in normalized (0-1) frame coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CropRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl CropRect {
+    /// Create a rectangle clamped to the frame
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        let x = x.clamp(0.0, 1.0);
+        let y = y.clamp(0.0, 1.0);
+        Self {
+            x,
+            y,
+            width: width.clamp(0.0, 1.0 - x),
+            height: height.clamp(0.0, 1.0 - y),
+        }
+    }
+
+    /// Rectangle covering the whole frame
+    pub fn full() -> Self {
+        Self::new(0.0, 0.0, 1.0, 1.0)
+    }
+
+    /// Check if the rectangle covers the whole frame
+    pub fn is_full(&self) -> bool {
+        self.x <= 0.0 && self.y <= 0.0 && self.width >= 1.0 && self.height >= 1.0
+    }
+
+    /// Express a rectangle given relative to this one in frame coordinates
+    pub fn sub_rect(&self, inner: &CropRect) -> CropRect {
+        CropRect::new(
+            self.x + inner.x * self.width,
+            self.y + inner.y * self.height,
+            inner.width * self.width,
+            inner.height * self.height,
+        )
+    }
+
+    /// Get the pixel rectangle (x, y, width, height) for a frame size, at least 1x1
+    pub fn to_pixels(&self, width: usize, height: usize) -> (usize, usize, usize, usize) {
+        let x0 = ((self.x * width as f32).floor() as usize).min(width.saturating_sub(1));
+        let y0 = ((self.y * height as f32).floor() as usize).min(height.saturating_sub(1));
+        let x1 = (((self.x + self.width) * width as f32).ceil() as usize).clamp(x0 + 1, width.max(x0 + 1));
+        let y1 = (((self.y + self.height) * height as f32).ceil() as usize).clamp(y0 + 1, height.max(y0 + 1));
+        (x0, y0, x1 - x0, y1 - y0)
+    }
+
+    /// Copy the region out of packed pixel data with `bytes_per_pixel` bytes per pixel
+    pub fn crop_packed(&self, data: &[u8], width: usize, height: usize, bytes_per_pixel: usize) -> (Vec<u8>, usize, usize) {
+        let (x, y, crop_width, crop_height) = self.to_pixels(width, height);
+        let row_bytes = crop_width * bytes_per_pixel;
+        let mut output = Vec::with_capacity(row_bytes * crop_height);
+
+        for row in y..y + crop_height {
+            let start = (row * width + x) * bytes_per_pixel;
+            output.extend_from_slice(&data[start..start + row_bytes]);
+        }
+
+        (output, crop_width, crop_height)
+    }
+}
+
+impl Default for CropRect {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(rgba: &[u8]) -> Vec<u8> {
+        rgba.chunks_exact(4).map(|p| p[0]).collect()
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        // 2x3 frame:  1 2 3
+        //             4 5 6
+        let rgba: Vec<u8> = (1..=6).flat_map(|v| [v, 0, 0, 255]).collect();
+
+        let (out, w, h) = Orientation::new(Rotation::Cw90, false, false).apply_rgba(&rgba, 3, 2);
+        assert_eq!((w, h), (2, 3));
+        assert_eq!(labels(&out), vec![4, 1, 5, 2, 6, 3]);
+
+        let (out, _, _) = Orientation::new(Rotation::None, true, false).apply_rgba(&rgba, 3, 2);
+        assert_eq!(labels(&out), vec![3, 2, 1, 6, 5, 4]);
+
+        let (out, _, _) = Orientation::new(Rotation::None, false, true).apply_rgba(&rgba, 3, 2);
+        assert_eq!(labels(&out), vec![4, 5, 6, 1, 2, 3]);
+
+        let (out, w, h) = Orientation::new(Rotation::Cw270, false, false).apply_rgba(&rgba, 3, 2);
+        assert_eq!((w, h), (2, 3));
+        assert_eq!(labels(&out), vec![3, 6, 2, 5, 1, 4]);
+    }
+
+    #[test]
+    fn test_compose_with_header_flags() {
+        let header = Orientation::from_header_flags(Orientation::FLAG_FLIP_HORIZONTAL | 1);
+        assert_eq!(header.rotation, Rotation::Cw90);
+        assert!(header.flip_horizontal);
+
+        let rgba: Vec<u8> = (1..=6).flat_map(|v| [v, 0, 0, 255]).collect();
+        let user = Orientation::new(Rotation::Cw180, false, true);
+
+        // Composition matches applying both transforms in sequence
+        let (first, w, h) = header.apply_rgba(&rgba, 3, 2);
+        let (expected, _, _) = user.apply_rgba(&first, w, h);
+        let (combined, _, _) = header.then(&user).apply_rgba(&rgba, 3, 2);
+        assert_eq!(labels(&combined), labels(&expected));
+
+        // Undoing a rotation gives identity
+        let undo = Orientation::new(Rotation::Cw270, false, false);
+        assert!(Orientation::new(Rotation::Cw90, false, false).then(&undo).is_identity());
+    }
+
+    #[test]
+    fn test_crop() {
+        // 4x2 frame of 2-byte pixels
+        let data: Vec<u8> = (0..16).collect();
+        let rect = CropRect::new(0.5, 0.5, 0.5, 0.5);
+        let (cropped, w, h) = rect.crop_packed(&data, 4, 2, 2);
+        assert_eq!((w, h), (2, 1));
+        assert_eq!(cropped, vec![12, 13, 14, 15]);
+
+        // Nested selections compose
+        let nested = CropRect::new(0.5, 0.0, 0.5, 1.0).sub_rect(&CropRect::new(0.5, 0.5, 0.5, 0.5));
+        assert_eq!(nested, CropRect::new(0.75, 0.5, 0.25, 0.5));
+
+        // A selection on a rotated display maps back through the inverse orientation
+        let rotated = Orientation::new(Rotation::Cw90, false, false);
+        let display = CropRect::new(0.0, 0.0, 0.5, 0.25);
+        let sensor = rotated.inverse().map_rect(&display);
+        let back = rotated.map_rect(&sensor);
+        assert!((back.x - display.x).abs() < 1e-6 && (back.height - display.height).abs() < 1e-6);
+        assert!(rotated.then(&rotated.inverse()).is_identity());
+    }
+}
@@ -4,6 +4,20 @@ use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 
+use crate::backend::pipeline::StageStatistics;
+use crate::backend::inference::InferenceStatistics;
+use crate::backend::roi::RoiStatistics;
+use crate::backend::calibration::PixelSpacing;
+use crate::backend::memory::MemoryConfig;
+use crate::backend::numa::NumaStatistics;
+use crate::backend::protocol::Handshake;
+use crate::backend::reconnect::ReconnectPolicy;
+use crate::backend::pixel_pool::RgbaBuffer;
+use crate::backend::gpu_frame::GpuFrame;
+use crate::backend::detections::DisplayGeometry;
+use crate::backend::packed12;
+use crate::perf::PerformanceSnapshot;
+
 /// Frame header structure matching C++ implementation
 #[repr(C, align(8))]
 #[derive(Debug, Copy, Clone)]
@@ -22,6 +36,45 @@ pub struct FrameHeader {
     pub padding: [u64; 4],         // Reserved for future use
 }
 
+/// Header flag: 16-bit samples are stored big-endian instead of little-endian
+pub const FLAG_BIG_ENDIAN: u32 = 0x0000_0400;
+
+impl FrameHeader {
+    /// Check if 16-bit samples arrive most significant byte first
+    pub fn is_big_endian(&self) -> bool {
+        self.flags & FLAG_BIG_ENDIAN != 0
+    }
+
+    /// Header of a tightly packed test frame: frame and sequence number 1, no flags or metadata
+    #[cfg(test)]
+    pub fn for_test(width: u32, height: u32, bytes_per_pixel: u32, format_code: u32) -> Self {
+        Self {
+            frame_id: 1,
+            timestamp: 0,
+            width,
+            height,
+            bytes_per_pixel,
+            data_size: width * height * bytes_per_pixel,
+            format_code,
+            flags: 0,
+            sequence_number: 1,
+            metadata_offset: 0,
+            metadata_size: 0,
+            padding: [0; 4],
+        }
+    }
+}
+
+/// Read one 16-bit sample in the producer's byte order
+#[inline]
+pub fn read_sample16(bytes: &[u8], big_endian: bool) -> u16 {
+    if big_endian {
+        u16::from_be_bytes([bytes[0], bytes[1]])
+    } else {
+        u16::from_le_bytes([bytes[0], bytes[1]])
+    }
+}
+
 /// Control block structure matching C++ implementation
 #[repr(C, align(64))]
 #[derive(Debug, Copy, Clone)]
@@ -39,7 +92,11 @@ pub struct ControlBlock {
     pub metadata_offset: u32,      // Offset to metadata area
     pub metadata_size: u32,        // Size of metadata area
     pub flags: u32,                // Additional flags
-    pub _padding2: [u8; 184],      // Padding to ensure proper alignment
+    pub magic: u32,                // PROTOCOL_MAGIC, or 0 for legacy producers
+    pub protocol_version: u32,     // major << 16 | minor
+    pub producer_capabilities: u32, // CAP_* bits offered by the producer
+    pub consumer_capabilities: u32, // CAP_* bits the reader accepted
+    pub _padding2: [u8; 168],      // Padding to ensure proper alignment
 }
 
 /// Raw frame data from shared memory (Zero-Copy)
@@ -91,18 +148,22 @@ impl RawFrame {
 #[derive(Debug, Clone)]
 pub struct ProcessedFrame {
     pub header: FrameHeader,
-    pub rgb_data: Arc<[u8]>,       // Zero-copy RGB data for display
+    pub rgb_data: RgbaBuffer,      // Pooled RGBA buffer, displayed by Slint without copying
     pub metadata: Option<String>,
     pub received_at: Instant,
     pub processed_at: Instant,
     pub format: FrameFormat,
+    pub roi_statistics: Option<RoiStatistics>, // Raw-value statistics of the active ROI
+    pub pixel_spacing: Option<PixelSpacing>,   // From metadata, oriented like the displayed frame
+    pub gpu_source: Option<GpuFrame>,          // Left unconverted for the GPU; rgb_data is then empty
+    pub geometry: DisplayGeometry,             // Crop and orientation from sensor to displayed frame
 }
 
 impl ProcessedFrame {
     /// Create a new processed frame
     pub fn new(
         header: FrameHeader,
-        rgb_data: Arc<[u8]>,
+        rgb_data: RgbaBuffer,
         metadata: Option<String>,
         received_at: Instant,
         format: FrameFormat,
@@ -114,6 +175,18 @@ impl ProcessedFrame {
             received_at,
             processed_at: Instant::now(),
             format,
+            roi_statistics: None,
+            pixel_spacing: None,
+            gpu_source: None,
+            geometry: DisplayGeometry::default(),
+        }
+    }
+
+    /// Get the frame as RGBA, converting on the CPU if it was left for the GPU
+    pub fn rgba(&self) -> RgbaBuffer {
+        match &self.gpu_source {
+            Some(gpu_frame) => gpu_frame.to_rgba(),
+            None => self.rgb_data.clone(),
         }
     }
     
@@ -152,6 +225,54 @@ impl ProcessedFrame {
     }
 }
 
+/// Dimensions and pixel format of a stream, as its frame headers report them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamGeometry {
+    pub width: u32,
+    pub height: u32,
+    pub format_code: u32,
+    pub bytes_per_pixel: u32,
+}
+
+impl StreamGeometry {
+    /// Take the geometry from a frame header
+    pub fn from_header(header: &FrameHeader) -> Self {
+        Self {
+            width: header.width,
+            height: header.height,
+            format_code: header.format_code,
+            bytes_per_pixel: header.bytes_per_pixel,
+        }
+    }
+
+    /// Format name as used by `--format`
+    pub fn format_name(&self) -> &'static str {
+        match (self.format_code, self.bytes_per_pixel) {
+            (0x01, _) => "yuv",
+            (0x02, 4) => "bgra",
+            (0x02, _) => "bgr",
+            (0x03, _) => "yuv10",
+            (0x04, _) => "rgb10",
+            (0x05, _) => "rgb565",
+            (0x06, _) => "indexed",
+            (0x10, _) => "grayscale",
+            (0x12, _) => "gray12",
+            _ => "unknown",
+        }
+    }
+
+    /// Check if the configured width, height and format agree with this geometry
+    pub fn matches_hint(&self, width: usize, height: usize, format: &str) -> bool {
+        self.width as usize == width && self.height as usize == height && self.format_name().eq_ignore_ascii_case(format)
+    }
+}
+
+impl std::fmt::Display for StreamGeometry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{} {}", self.width, self.height, self.format_name())
+    }
+}
+
 /// Frame format enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FrameFormat {
@@ -162,7 +283,10 @@ pub enum FrameFormat {
     RGBA,
     YUV10,
     RGB10,
+    RGB565,
+    Indexed,
     Grayscale,
+    Gray12Packed,
     Unknown,
 }
 
@@ -170,13 +294,34 @@ impl FrameFormat {
     /// Get bytes per pixel for this format
     pub fn bytes_per_pixel(&self) -> u32 {
         match self {
-            FrameFormat::YUV | FrameFormat::Grayscale => 1,
+            FrameFormat::YUV | FrameFormat::Grayscale | FrameFormat::Indexed => 1,
             FrameFormat::BGR | FrameFormat::RGB => 3,
             FrameFormat::BGRA | FrameFormat::RGBA => 4,
-            FrameFormat::YUV10 | FrameFormat::RGB10 => 2,
+            FrameFormat::YUV10 | FrameFormat::RGB10 | FrameFormat::RGB565 => 2,
+            // Two pixels share three bytes; see `packed12::packed_len`
+            FrameFormat::Gray12Packed => 2,
             FrameFormat::Unknown => 1,
         }
     }
+
+    /// Get the bytes of pixel data a tightly packed frame of this size takes
+    pub fn data_size(&self, width: u32, height: u32) -> usize {
+        let pixels = width as usize * height as usize;
+        match self {
+            FrameFormat::Gray12Packed => packed12::packed_len(pixels),
+            _ => pixels * self.bytes_per_pixel() as usize,
+        }
+    }
+    
+    /// Check if the format carries a single intensity channel
+    pub fn is_single_channel(&self) -> bool {
+        matches!(self, FrameFormat::YUV | FrameFormat::YUV10 | FrameFormat::Grayscale | FrameFormat::Gray12Packed | FrameFormat::Unknown)
+    }
+    
+    /// Check if the format has more than 8 bits per channel
+    pub fn is_high_bit_depth(&self) -> bool {
+        matches!(self, FrameFormat::YUV10 | FrameFormat::RGB10 | FrameFormat::Gray12Packed)
+    }
     
     /// Create from format code
     pub fn from_code(code: u32) -> Self {
@@ -185,7 +330,10 @@ impl FrameFormat {
             0x02 => FrameFormat::BGR,
             0x03 => FrameFormat::YUV10,
             0x04 => FrameFormat::RGB10,
+            0x05 => FrameFormat::RGB565,
+            0x06 => FrameFormat::Indexed,
             0x10 => FrameFormat::Grayscale,
+            0x12 => FrameFormat::Gray12Packed,
             _ => FrameFormat::Unknown,
         }
     }
@@ -197,7 +345,10 @@ impl FrameFormat {
             FrameFormat::BGR => 0x02,
             FrameFormat::YUV10 => 0x03,
             FrameFormat::RGB10 => 0x04,
+            FrameFormat::RGB565 => 0x05,
+            FrameFormat::Indexed => 0x06,
             FrameFormat::Grayscale => 0x10,
+            FrameFormat::Gray12Packed => 0x12,
             _ => 0x00,
         }
     }
@@ -208,7 +359,21 @@ impl FrameFormat {
 pub struct FrameStatistics {
     pub total_frames_received: u64,
     pub total_frames_processed: u64,
+    /// Frames missing from the sequence number stream (lost before the viewer read them)
     pub frames_dropped: u64,
+    /// Number of discontinuities in the sequence number stream
+    pub sequence_gaps: u64,
+    pub last_sequence: Option<u64>,
+    /// Frames skipped on purpose to honor the display frame-rate cap
+    pub frames_decimated: u64,
+    /// Frames passed over by catch-up reads that jumped to the latest frame
+    pub frames_skipped: u64,
+    /// Frames rejected by checksum verification
+    pub frames_corrupted: u64,
+    /// Frames skipped because their data size did not match their header geometry
+    pub frames_malformed: u64,
+    /// Frames read and processed but dropped because the frontend fell behind
+    pub frames_dropped_by_consumer: u64,
     pub current_fps: f64,
     pub average_latency_ms: f64,
     pub last_frame_time: Option<Instant>,
@@ -216,6 +381,29 @@ pub struct FrameStatistics {
     pub fps_frame_count: u64,
     pub latency_samples: Vec<f64>,
     pub max_latency_samples: usize,
+    pub latency_target_ms: f64,
+    pub latency_target_exceeded: u64,
+    /// Budget for converting and dispatching one frame (0 = not monitored)
+    pub processing_deadline_ms: f64,
+    /// Frames whose conversion and dispatch overran the deadline
+    pub deadline_overruns: u64,
+    /// Slowest conversion and dispatch so far
+    pub max_processing_ms: f64,
+    /// Recent intervals between received frames
+    pub frame_interval_samples: Vec<f64>,
+    /// Standard deviation of the frame interval
+    pub jitter_ms: f64,
+    /// Largest deviation of a frame interval from the mean
+    pub max_jitter_ms: f64,
+    pub pipeline_stages: Vec<StageStatistics>,
+    /// Buffer placement relative to the converting thread (multi-node machines only)
+    pub numa: Option<NumaStatistics>,
+    /// Protocol version and capabilities negotiated with the producer
+    pub protocol: Option<Handshake>,
+    /// Performance monitor metrics, with `--perf-monitor`
+    pub performance: Option<PerformanceSnapshot>,
+    /// Segmentation model timing, with `--onnx-model`
+    pub inference: Option<InferenceStatistics>,
 }
 
 impl Default for FrameStatistics {
@@ -224,6 +412,13 @@ impl Default for FrameStatistics {
             total_frames_received: 0,
             total_frames_processed: 0,
             frames_dropped: 0,
+            sequence_gaps: 0,
+            last_sequence: None,
+            frames_decimated: 0,
+            frames_skipped: 0,
+            frames_corrupted: 0,
+            frames_malformed: 0,
+            frames_dropped_by_consumer: 0,
             current_fps: 0.0,
             average_latency_ms: 0.0,
             last_frame_time: None,
@@ -231,6 +426,19 @@ impl Default for FrameStatistics {
             fps_frame_count: 0,
             latency_samples: Vec::new(),
             max_latency_samples: 100,
+            latency_target_ms: 0.0,
+            latency_target_exceeded: 0,
+            processing_deadline_ms: 0.0,
+            deadline_overruns: 0,
+            max_processing_ms: 0.0,
+            frame_interval_samples: Vec::new(),
+            jitter_ms: 0.0,
+            max_jitter_ms: 0.0,
+            pipeline_stages: Vec::new(),
+            numa: None,
+            protocol: None,
+            performance: None,
+            inference: None,
         }
     }
 }
@@ -247,15 +455,28 @@ impl FrameStatistics {
     
     /// Update statistics when a frame is received
     pub fn update_frame_received(&mut self) {
+        let now = Instant::now();
         self.total_frames_received += 1;
         self.fps_frame_count += 1;
-        self.last_frame_time = Some(Instant::now());
+        
+        if let Some(last) = self.last_frame_time {
+            self.frame_interval_samples.push(now.duration_since(last).as_secs_f64() * 1000.0);
+            if self.frame_interval_samples.len() > self.max_latency_samples {
+                self.frame_interval_samples.remove(0);
+            }
+        }
+        self.last_frame_time = Some(now);
     }
     
     /// Update statistics when a frame is processed
     pub fn update_frame_processed(&mut self, latency_ms: f64) {
         self.total_frames_processed += 1;
         
+        // Track frames over the device profile latency target
+        if self.latency_target_ms > 0.0 && latency_ms > self.latency_target_ms {
+            self.latency_target_exceeded += 1;
+        }
+        
         // Update latency statistics
         self.latency_samples.push(latency_ms);
         if self.latency_samples.len() > self.max_latency_samples {
@@ -268,6 +489,53 @@ impl FrameStatistics {
         }
     }
     
+    /// Record how long converting and dispatching a frame took, returning whether it overran the deadline
+    pub fn update_processing_time(&mut self, processing_ms: f64) -> bool {
+        self.max_processing_ms = self.max_processing_ms.max(processing_ms);
+        let overrun = self.processing_deadline_ms > 0.0 && processing_ms > self.processing_deadline_ms;
+        if overrun {
+            self.deadline_overruns += 1;
+        }
+        overrun
+    }
+    
+    /// Track sequence number continuity, returning how many frames were skipped before this one
+    pub fn update_sequence(&mut self, sequence: u64) -> u64 {
+        // Repeated or decreasing sequence numbers mean the producer restarted, not a drop
+        let missing = match self.last_sequence {
+            Some(last) if sequence > last + 1 => sequence - last - 1,
+            _ => 0,
+        };
+        
+        if missing > 0 {
+            self.sequence_gaps += 1;
+            self.frames_dropped += missing;
+        }
+        self.last_sequence = Some(sequence);
+        missing
+    }
+    
+    /// Track sequence continuity after a catch-up read, returning how many frames were jumped over
+    ///
+    /// The consumer chose to skip these frames, so they count as skipped rather than dropped.
+    pub fn update_sequence_skipped(&mut self, sequence: u64) -> u64 {
+        let skipped = match self.last_sequence {
+            Some(last) if sequence > last + 1 => sequence - last - 1,
+            _ => 0,
+        };
+        
+        self.frames_skipped += skipped;
+        self.last_sequence = Some(sequence);
+        skipped
+    }
+    
+    /// Forget per-stream continuity (sequence and frame timing) when a new stream starts
+    pub fn reset_stream(&mut self) {
+        self.last_sequence = None;
+        self.last_frame_time = None;
+        self.frame_interval_samples.clear();
+    }
+    
     /// Calculate current FPS
     pub fn calculate_fps(&mut self) {
         let elapsed = self.fps_measurement_start.elapsed();
@@ -275,9 +543,25 @@ impl FrameStatistics {
             self.current_fps = self.fps_frame_count as f64 / elapsed.as_secs_f64();
             self.fps_frame_count = 0;
             self.fps_measurement_start = Instant::now();
+            self.calculate_jitter();
         }
     }
     
+    /// Calculate frame interval jitter (standard deviation and maximum deviation)
+    pub fn calculate_jitter(&mut self) {
+        if self.frame_interval_samples.len() < 2 {
+            self.jitter_ms = 0.0;
+            self.max_jitter_ms = 0.0;
+            return;
+        }
+        
+        let count = self.frame_interval_samples.len() as f64;
+        let mean = self.frame_interval_samples.iter().sum::<f64>() / count;
+        let variance = self.frame_interval_samples.iter().map(|i| (i - mean).powi(2)).sum::<f64>() / count;
+        self.jitter_ms = variance.sqrt();
+        self.max_jitter_ms = self.frame_interval_samples.iter().fold(0.0, |a, &i| a.max((i - mean).abs()));
+    }
+    
     /// Get maximum latency
     pub fn max_latency_ms(&self) -> f64 {
         self.latency_samples.iter().fold(0.0, |a, &b| a.max(b))
@@ -288,10 +572,20 @@ impl FrameStatistics {
         self.latency_samples.iter().fold(f64::INFINITY, |a, &b| a.min(b))
     }
     
-    /// Get frame drop rate as percentage
+    /// Get percentage of processed frames that exceeded the latency target
+    pub fn latency_target_miss_percent(&self) -> f64 {
+        if self.total_frames_processed > 0 {
+            (self.latency_target_exceeded as f64 / self.total_frames_processed as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+    
+    /// Get frame drop rate as percentage of all frames the producer sent
     pub fn drop_rate_percent(&self) -> f64 {
-        if self.total_frames_received > 0 {
-            (self.frames_dropped as f64 / self.total_frames_received as f64) * 100.0
+        let total_sent = self.total_frames_received + self.frames_dropped;
+        if total_sent > 0 {
+            (self.frames_dropped as f64 / total_sent as f64) * 100.0
         } else {
             0.0
         }
@@ -359,21 +653,21 @@ pub struct PatientInfo {
 /// Connection configuration
 #[derive(Debug, Clone)]
 pub struct ConnectionConfig {
-    pub reconnect_delay: Duration,
-    pub max_reconnect_attempts: u32,
+    pub reconnect: ReconnectPolicy,
     pub frame_timeout: Duration,
     pub buffer_size: usize,
     pub verbose_logging: bool,
+    pub memory: MemoryConfig,
 }
 
 impl Default for ConnectionConfig {
     fn default() -> Self {
         Self {
-            reconnect_delay: Duration::from_secs(1),
-            max_reconnect_attempts: 10,
+            reconnect: ReconnectPolicy::default(),
             frame_timeout: Duration::from_secs(5),
             buffer_size: 1024 * 1024 * 50, // 50MB buffer
             verbose_logging: false,
+            memory: MemoryConfig::default(),
         }
     }
 }
@@ -385,7 +679,10 @@ pub fn format_code_to_string(format_code: u32) -> &'static str {
         0x02 => "BGR/BGRA",
         0x03 => "YUV10",
         0x04 => "RGB10",
+        0x05 => "RGB565",
+        0x06 => "Indexed",
         0x10 => "Grayscale",
+        0x12 => "Gray12Packed",
         _ => "Unknown",
     }
 }
@@ -421,3 +718,21 @@ impl MemoryStats {
         self.peak_memory_usage as f64 / (1024.0 * 1024.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geometry_matches_format_hint() {
+        let geometry = |format_code, bytes_per_pixel| StreamGeometry { width: 640, height: 480, format_code, bytes_per_pixel };
+
+        assert!(geometry(0x02, 4).matches_hint(640, 480, "bgra"));
+        assert!(!geometry(0x02, 3).matches_hint(640, 480, "bgra"));
+        assert!(geometry(0x05, 2).matches_hint(640, 480, "rgb565"));
+        assert!(geometry(0x06, 1).matches_hint(640, 480, "indexed"));
+        assert!(geometry(0x12, 2).matches_hint(640, 480, "gray12"));
+        assert!(!geometry(0x05, 2).matches_hint(800, 480, "rgb565"));
+        assert_eq!(geometry(0x7F, 1).format_name(), "unknown");
+    }
+}
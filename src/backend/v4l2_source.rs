@@ -0,0 +1,343 @@
+// src/backend/v4l2_source.rs - Direct V4L2 Capture from USB Frame Grabbers (feature "v4l2")
+
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+use v4l::buffer::Type;
+use v4l::io::mmap::Stream as MmapStream;
+use v4l::io::traits::CaptureStream;
+use v4l::video::Capture;
+use v4l::{Device, FourCC};
+
+use crate::backend::frame_source::{FrameQueue, FrameSource};
+use crate::backend::shared_memory::{ConnectionStatistics, SharedMemoryError};
+use crate::backend::types::{ConnectionConfig, FrameFormat, FrameHeader, RawFrame};
+
+/// Prefix of `--shm-name` values that name a capture device
+pub const V4L2_PREFIX: &str = "v4l2:";
+
+/// Kernel buffers mapped for streaming
+const BUFFER_COUNT: u32 = 4;
+
+/// Captured frames buffered before the oldest is dropped
+const MAX_QUEUED_FRAMES: usize = 4;
+
+/// Device path and requested size for `v4l2:/dev/video0` or `v4l2:/dev/video0@1920x1080`
+pub fn parse_device(name: &str) -> Option<(&str, Option<(u32, u32)>)> {
+    let device = name.strip_prefix(V4L2_PREFIX)?;
+    let Some((path, size)) = device.rsplit_once('@') else {
+        return (!device.is_empty()).then_some((device, None));
+    };
+
+    let (width, height) = size.split_once('x')?;
+    let size = (width.parse().ok()?, height.parse().ok()?);
+    (!path.is_empty()).then_some((path, Some(size)))
+}
+
+/// Pixel formats negotiated with the device, in order of preference
+///
+/// Packed BGR and grayscale go straight to the processor; YUYV and MJPEG,
+/// which most USB grabbers only offer, are converted to BGR while capturing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureFormat {
+    Bgr24,
+    Bgrx32,
+    Grey,
+    Yuyv,
+    Mjpeg,
+}
+
+impl CaptureFormat {
+    const PREFERENCE: [Self; 5] = [Self::Bgr24, Self::Bgrx32, Self::Grey, Self::Yuyv, Self::Mjpeg];
+
+    /// Map a V4L2 fourcc, None for formats the viewer cannot use
+    pub fn from_fourcc(fourcc: &[u8; 4]) -> Option<Self> {
+        match fourcc {
+            b"BGR3" => Some(Self::Bgr24),
+            b"XR24" | b"BGR4" => Some(Self::Bgrx32),
+            b"GREY" => Some(Self::Grey),
+            b"YUYV" => Some(Self::Yuyv),
+            b"MJPG" => Some(Self::Mjpeg),
+            _ => None,
+        }
+    }
+
+    /// Format code and bytes per pixel of the frames handed to the processor
+    pub fn output(&self) -> (u32, u32) {
+        match self {
+            Self::Grey => (FrameFormat::Grayscale.to_code(), 1),
+            Self::Bgrx32 => (FrameFormat::BGR.to_code(), 4),
+            Self::Bgr24 | Self::Yuyv | Self::Mjpeg => (FrameFormat::BGR.to_code(), 3),
+        }
+    }
+
+    /// Pack one captured buffer into processor-ready pixels
+    pub fn convert(&self, buffer: &[u8], width: usize, height: usize, stride: usize) -> Option<Vec<u8>> {
+        let (_, bytes_per_pixel) = self.output();
+        let mut data = Vec::with_capacity(width * height * bytes_per_pixel as usize);
+
+        match self {
+            Self::Bgr24 | Self::Bgrx32 | Self::Grey => {
+                let row = width * bytes_per_pixel as usize;
+                for y in 0..height {
+                    data.extend_from_slice(buffer.get(y * stride..y * stride + row)?);
+                }
+            }
+            Self::Yuyv => {
+                for y in 0..height {
+                    let row = buffer.get(y * stride..y * stride + width * 2)?;
+                    for pair in row.chunks_exact(4) {
+                        data.extend_from_slice(&yuv_to_bgr(pair[0], pair[1], pair[3]));
+                        data.extend_from_slice(&yuv_to_bgr(pair[2], pair[1], pair[3]));
+                    }
+                }
+            }
+            Self::Mjpeg => {
+                let rgb = image::load_from_memory_with_format(buffer, image::ImageFormat::Jpeg).ok()?.to_rgb8();
+                if (rgb.width() as usize, rgb.height() as usize) != (width, height) {
+                    return None;
+                }
+                data.extend(rgb.pixels().flat_map(|pixel| [pixel[2], pixel[1], pixel[0]]));
+            }
+        }
+
+        Some(data)
+    }
+}
+
+/// BT.601 limited-range YCbCr to BGR
+fn yuv_to_bgr(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let c = (y as i32 - 16) * 298;
+    let d = u as i32 - 128;
+    let e = v as i32 - 128;
+    let clamp = |value: i32| ((value + 128) >> 8).clamp(0, 255) as u8;
+    [clamp(c + 516 * d), clamp(c - 100 * d - 208 * e), clamp(c + 409 * e)]
+}
+
+/// Negotiated capture parameters
+#[derive(Debug, Clone, Copy)]
+struct Negotiated {
+    format: CaptureFormat,
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+/// Frames captured straight from a V4L2 device, without a producer process
+///
+/// Capturing blocks on the driver, so it runs on its own thread and queues
+/// frames like the network sources.
+pub struct V4l2FrameSource {
+    path: String,
+    size: Option<(u32, u32)>,
+    config: ConnectionConfig,
+    queue: Arc<FrameQueue>,
+    stop: Arc<AtomicBool>,
+}
+
+impl V4l2FrameSource {
+    /// Create a source for a device path, optionally requesting a frame size
+    pub fn new(path: &str, size: Option<(u32, u32)>, config: ConnectionConfig) -> Self {
+        Self {
+            path: path.to_string(),
+            size,
+            config,
+            queue: Arc::new(FrameQueue::new(MAX_QUEUED_FRAMES)),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Pick the most preferred format the device offers and apply it
+    fn negotiate(device: &Device, size: Option<(u32, u32)>) -> io::Result<Negotiated> {
+        let offered: Vec<CaptureFormat> = device
+            .enum_formats()?
+            .iter()
+            .filter_map(|description| CaptureFormat::from_fourcc(&description.fourcc.repr))
+            .collect();
+        let format = CaptureFormat::PREFERENCE
+            .into_iter()
+            .find(|format| offered.contains(format))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "device offers no BGR, grayscale, YUYV or MJPEG format"))?;
+
+        let mut requested = device.format()?;
+        requested.fourcc = FourCC::new(match format {
+            CaptureFormat::Bgr24 => b"BGR3",
+            CaptureFormat::Bgrx32 => b"XR24",
+            CaptureFormat::Grey => b"GREY",
+            CaptureFormat::Yuyv => b"YUYV",
+            CaptureFormat::Mjpeg => b"MJPG",
+        });
+        if let Some((width, height)) = size {
+            requested.width = width;
+            requested.height = height;
+        }
+
+        // Drivers adjust the request to what they support; use what they chose
+        let applied = device.set_format(&requested)?;
+        let format = CaptureFormat::from_fourcc(&applied.fourcc.repr)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, format!("driver switched to {}", applied.fourcc)))?;
+
+        Ok(Negotiated {
+            format,
+            width: applied.width as usize,
+            height: applied.height as usize,
+            stride: applied.stride as usize,
+        })
+    }
+
+    fn capture(
+        path: String,
+        size: Option<(u32, u32)>,
+        config: ConnectionConfig,
+        queue: Arc<FrameQueue>,
+        stop: Arc<AtomicBool>,
+        ready: oneshot::Sender<Result<(), SharedMemoryError>>,
+    ) {
+        let opened = Device::with_path(&path).and_then(|device| {
+            let negotiated = Self::negotiate(&device, size)?;
+            let mut stream = MmapStream::with_buffers(&device, Type::VideoCapture, BUFFER_COUNT)?;
+            stream.set_timeout(config.frame_timeout);
+            Ok((device, negotiated, stream))
+        });
+        let (_device, negotiated, mut stream) = match opened {
+            Ok(opened) => opened,
+            Err(e) => {
+                let _ = ready.send(Err(match e.kind() {
+                    io::ErrorKind::NotFound => SharedMemoryError::NotFound(path),
+                    _ => SharedMemoryError::Io(e),
+                }));
+                return;
+            }
+        };
+
+        info!("📹 V4L2 capture: {} {}x{} {:?}", path, negotiated.width, negotiated.height, negotiated.format);
+        if ready.send(Ok(())).is_err() {
+            return;
+        }
+
+        let (format_code, bytes_per_pixel) = negotiated.format.output();
+        let mut sequence = 0u64;
+        while !stop.load(Ordering::Acquire) {
+            let (buffer, metadata) = match stream.next() {
+                Ok(captured) => captured,
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => continue,
+                Err(e) => {
+                    warn!("❌ V4L2 capture failed: {}", e);
+                    queue.record_error();
+                    break;
+                }
+            };
+
+            let used = &buffer[..(metadata.bytesused as usize).min(buffer.len())];
+            let Some(data) = negotiated.format.convert(used, negotiated.width, negotiated.height, negotiated.stride) else {
+                warn!("⚠️ Dropping malformed V4L2 buffer ({} bytes)", used.len());
+                queue.record_error();
+                continue;
+            };
+
+            sequence += 1;
+            let header = FrameHeader {
+                frame_id: sequence,
+                timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64,
+                width: negotiated.width as u32,
+                height: negotiated.height as u32,
+                bytes_per_pixel,
+                data_size: data.len() as u32,
+                format_code,
+                flags: 0,
+                sequence_number: sequence,
+                metadata_offset: 0,
+                metadata_size: 0,
+                padding: [0; 4],
+            };
+            queue.push(RawFrame::new(header, Arc::from(data), None));
+        }
+
+        queue.set_connected(false);
+    }
+}
+
+#[async_trait]
+impl FrameSource for V4l2FrameSource {
+    async fn connect(&mut self) -> Result<(), SharedMemoryError> {
+        if self.config.verbose_logging {
+            info!("🔌 Opening V4L2 device: {}", self.path);
+        }
+
+        // A fresh flag, so a capture thread still winding down keeps its stop request
+        self.stop = Arc::new(AtomicBool::new(false));
+        self.queue.set_connected(true);
+
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (path, size, config) = (self.path.clone(), self.size, self.config.clone());
+        let (queue, stop) = (Arc::clone(&self.queue), Arc::clone(&self.stop));
+        std::thread::Builder::new()
+            .name("mivi-v4l2".to_string())
+            .spawn(move || Self::capture(path, size, config, queue, stop, ready_tx))?;
+
+        let result = ready_rx.await.unwrap_or(Err(SharedMemoryError::ConnectionLost));
+        if let Err(e) = result {
+            self.queue.set_connected(false);
+            return Err(e);
+        }
+
+        info!("🔗 Capturing from V4L2 device: {}", self.path);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        self.queue.set_connected(false);
+
+        info!("🔌 Closed V4L2 device: {}", self.path);
+    }
+
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        self.queue.next(catch_up)
+    }
+
+    fn check_connection_health(&self) -> bool {
+        self.queue.is_healthy(self.config.frame_timeout)
+    }
+
+    fn backlog(&self) -> u64 {
+        self.queue.backlog()
+    }
+
+    fn get_statistics(&self) -> ConnectionStatistics {
+        self.queue.statistics(format!("{}{}", V4L2_PREFIX, self.path))
+    }
+}
+
+impl Drop for V4l2FrameSource {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_names_and_conversion() {
+        assert_eq!(parse_device("v4l2:/dev/video0"), Some(("/dev/video0", None)));
+        assert_eq!(parse_device("v4l2:/dev/video2@1920x1080"), Some(("/dev/video2", Some((1920, 1080)))));
+        assert_eq!(parse_device("v4l2:/dev/video0@big"), None);
+        assert_eq!(parse_device("v4l2:"), None);
+
+        // Row padding is dropped
+        let grey = CaptureFormat::Grey.convert(&[1, 2, 0, 3, 4, 0], 2, 2, 3).unwrap();
+        assert_eq!(grey, [1, 2, 3, 4]);
+
+        // Mid-gray YUYV pair becomes two gray BGR pixels
+        let bgr = CaptureFormat::Yuyv.convert(&[126, 128, 126, 128], 2, 1, 4).unwrap();
+        assert_eq!(bgr, [128; 6]);
+        assert!(CaptureFormat::Yuyv.convert(&[0; 3], 2, 1, 4).is_none());
+    }
+}
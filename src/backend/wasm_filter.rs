@@ -0,0 +1,194 @@
+// src/backend/wasm_filter.rs - Sandboxed WebAssembly Frame Filters (feature "wasm")
+
+//! Frame filters compiled to WebAssembly and run by wasmtime.
+//!
+//! A module is instantiated without any imports, so it has no file system,
+//! network, clock or host calls: it can only read and write its own linear
+//! memory. Memory is capped and every frame gets a fuel budget proportional to
+//! its pixel count, so a runaway or hostile filter traps instead of stalling the
+//! display; the plugin stage then disables it.
+//!
+//! Guest ABI (version 1), all integers `i32`:
+//!
+//! - `memory`: the exported linear memory
+//! - `mivi_abi_version() -> i32`: must return 1
+//! - `mivi_alloc(len) -> ptr`: reserve `len` bytes for frames; called again when the frame size changes
+//! - `mivi_process(ptr, width, height) -> i32`: filter tightly packed RGBA8 pixels in place, 0 on success
+//! - `mivi_reset()` (optional): forget temporal state
+
+use std::path::Path;
+
+use crate::backend::plugins::{FrameFilter, PluginError};
+
+/// Version of the WebAssembly guest ABI this viewer implements
+pub const WASM_ABI_VERSION: u32 = 1;
+
+/// Largest linear memory a module may grow to
+pub const DEFAULT_MEMORY_LIMIT: usize = 512 * 1024 * 1024;
+
+/// Instructions budgeted per pixel and frame before the module traps
+pub const DEFAULT_FUEL_PER_PIXEL: u64 = 5_000;
+
+/// Fixed fuel for calls that do not depend on the frame size
+#[cfg(feature = "wasm")]
+const CALL_FUEL: u64 = 1_000_000;
+
+#[cfg(feature = "wasm")]
+mod engine {
+    use std::path::Path;
+
+    use wasmtime::{Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc};
+
+    use super::{CALL_FUEL, DEFAULT_FUEL_PER_PIXEL, DEFAULT_MEMORY_LIMIT, WASM_ABI_VERSION};
+    use crate::backend::plugins::PluginError;
+
+    fn wasm_error(e: impl std::fmt::Display) -> PluginError {
+        PluginError::Wasm(e.to_string())
+    }
+
+    /// Instantiated module with its entry points
+    pub struct WasmModule {
+        store: Store<StoreLimits>,
+        memory: Memory,
+        alloc: TypedFunc<i32, i32>,
+        process: TypedFunc<(i32, i32, i32), i32>,
+        reset: Option<TypedFunc<(), ()>>,
+        // Guest buffer reserved for the current frame size
+        buffer: Option<(i32, usize)>,
+    }
+
+    impl WasmModule {
+        pub fn load(path: &Path) -> Result<Self, PluginError> {
+            let mut config = Config::new();
+            config.consume_fuel(true);
+            let engine = Engine::new(&config).map_err(wasm_error)?;
+            let module = Module::from_file(&engine, path).map_err(wasm_error)?;
+
+            let limits = StoreLimitsBuilder::new()
+                .memory_size(DEFAULT_MEMORY_LIMIT)
+                .instances(1)
+                .memories(1)
+                .tables(1)
+                .build();
+            let mut store = Store::new(&engine, limits);
+            store.limiter(|limits| limits);
+            store.set_fuel(CALL_FUEL).map_err(wasm_error)?;
+
+            // No imports are offered: modules that need host functions fail here
+            let instance = Instance::new(&mut store, &module, &[]).map_err(wasm_error)?;
+            let memory = instance
+                .get_memory(&mut store, "memory")
+                .ok_or_else(|| PluginError::Wasm("module does not export its memory".to_string()))?;
+
+            let abi_version = instance
+                .get_typed_func::<(), i32>(&mut store, "mivi_abi_version")
+                .and_then(|version| version.call(&mut store, ()))
+                .map_err(wasm_error)?;
+            if abi_version as u32 != WASM_ABI_VERSION {
+                return Err(PluginError::Wasm(format!(
+                    "module implements ABI version {}, this viewer supports {}", abi_version, WASM_ABI_VERSION
+                )));
+            }
+
+            Ok(Self {
+                alloc: instance.get_typed_func(&mut store, "mivi_alloc").map_err(wasm_error)?,
+                process: instance.get_typed_func(&mut store, "mivi_process").map_err(wasm_error)?,
+                reset: instance.get_typed_func(&mut store, "mivi_reset").ok(),
+                memory,
+                store,
+                buffer: None,
+            })
+        }
+
+        /// Run the filter on one frame, returning the guest's result code
+        pub fn process(&mut self, rgba_data: &mut [u8], width: usize, height: usize) -> Result<i32, PluginError> {
+            let len = width * height * 4;
+            let pixels = (width * height) as u64;
+            self.store.set_fuel(CALL_FUEL + pixels * DEFAULT_FUEL_PER_PIXEL).map_err(wasm_error)?;
+
+            let ptr = match self.buffer {
+                Some((ptr, buffer_len)) if buffer_len == len => ptr,
+                _ => {
+                    let ptr = self.alloc.call(&mut self.store, len as i32).map_err(wasm_error)?;
+                    self.buffer = Some((ptr, len));
+                    ptr
+                }
+            };
+
+            // Out-of-range guest pointers fail here instead of touching host memory
+            let offset = ptr as u32 as usize;
+            self.memory.write(&mut self.store, offset, &rgba_data[..len]).map_err(wasm_error)?;
+            let code = self.process.call(&mut self.store, (ptr, width as i32, height as i32)).map_err(wasm_error)?;
+            if code == 0 {
+                self.memory.read(&self.store, offset, &mut rgba_data[..len]).map_err(wasm_error)?;
+            }
+            Ok(code)
+        }
+
+        pub fn reset(&mut self) -> Result<(), PluginError> {
+            if let Some(reset) = &self.reset {
+                self.store.set_fuel(CALL_FUEL).map_err(wasm_error)?;
+                reset.call(&mut self.store, ()).map_err(wasm_error)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(feature = "wasm"))]
+mod engine {
+    use std::path::Path;
+
+    use crate::backend::plugins::PluginError;
+
+    /// Stand-in when the `wasm` feature is disabled
+    pub struct WasmModule;
+
+    impl WasmModule {
+        pub fn load(_path: &Path) -> Result<Self, PluginError> {
+            Err(PluginError::Unavailable("wasm"))
+        }
+
+        pub fn process(&mut self, _rgba_data: &mut [u8], _width: usize, _height: usize) -> Result<i32, PluginError> {
+            Err(PluginError::Unavailable("wasm"))
+        }
+
+        pub fn reset(&mut self) -> Result<(), PluginError> {
+            Ok(())
+        }
+    }
+}
+
+use engine::WasmModule;
+
+/// A frame filter run in a WebAssembly sandbox
+pub struct WasmFilter {
+    name: String,
+    module: WasmModule,
+}
+
+impl WasmFilter {
+    /// Compile and instantiate a module, checking the ABI version it reports
+    pub fn load(name: &str, path: &Path) -> Result<Self, PluginError> {
+        Ok(Self { name: name.to_string(), module: WasmModule::load(path)? })
+    }
+}
+
+impl FrameFilter for WasmFilter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(&mut self, rgba_data: &mut [u8], width: usize, height: usize) -> Result<(), PluginError> {
+        match self.module.process(rgba_data, width, height)? {
+            0 => Ok(()),
+            code => Err(PluginError::Process { name: self.name.clone(), code }),
+        }
+    }
+
+    fn reset(&mut self) {
+        if let Err(e) = self.module.reset() {
+            tracing::warn!("⚠️ Plugin {} reset failed: {}", self.name, e);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+// src/backend/watchdog.rs - Stall Detection for the Frame Processing Loop
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Frames read by the processing loop, shared with the watchdog task
+#[derive(Debug, Default)]
+pub struct PipelineHeartbeat {
+    frames_read: AtomicU64,
+}
+
+impl PipelineHeartbeat {
+    /// Record that the loop read a frame
+    pub fn beat(&self) {
+        self.frames_read.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Frames read so far
+    pub fn frames_read(&self) -> u64 {
+        self.frames_read.load(Ordering::Relaxed)
+    }
+}
+
+/// What the watchdog saw at one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogSample {
+    pub frames_read: u64,
+    /// Producer's write counter, for sources with a control block
+    pub producer_frames_written: Option<u64>,
+    /// Frames waiting to be read
+    pub backlog: u64,
+}
+
+/// Why the pipeline was considered stalled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallReason {
+    /// The producer kept writing frames the loop never read
+    ProducerAhead,
+    /// Frames stayed queued without being read
+    BacklogStuck,
+}
+
+impl StallReason {
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            StallReason::ProducerAhead => "producer writing, no frames read",
+            StallReason::BacklogStuck => "frames queued, none read",
+        }
+    }
+}
+
+/// Decides from periodic samples whether the frame loop has stalled
+///
+/// Only a loop that stops reading while the producer is active counts; an idle
+/// or paused producer restarts the clock.
+#[derive(Debug, Clone)]
+pub struct StallDetector {
+    timeout: Duration,
+    last_frames_read: u64,
+    last_written: Option<u64>,
+    last_progress: Instant,
+}
+
+impl StallDetector {
+    /// Create a detector reporting stalls longer than `timeout`
+    pub fn new(timeout: Duration, now: Instant) -> Self {
+        Self { timeout, last_frames_read: 0, last_written: None, last_progress: now }
+    }
+
+    /// Take a sample, returning the reason once the loop has been stalled for the timeout
+    pub fn check(&mut self, sample: WatchdogSample, now: Instant) -> Option<StallReason> {
+        let producer_ahead = matches!((sample.producer_frames_written, self.last_written), (Some(written), Some(last)) if written > last);
+        self.last_written = sample.producer_frames_written;
+
+        if sample.frames_read != self.last_frames_read {
+            self.last_frames_read = sample.frames_read;
+            self.last_progress = now;
+            return None;
+        }
+
+        let reason = if producer_ahead {
+            StallReason::ProducerAhead
+        } else if sample.backlog > 0 {
+            StallReason::BacklogStuck
+        } else {
+            self.last_progress = now;
+            return None;
+        };
+
+        (now.duration_since(self.last_progress) >= self.timeout).then_some(reason)
+    }
+
+    /// How long the loop has gone without reading a frame
+    pub fn stalled_for(&self, now: Instant) -> Duration {
+        now.duration_since(self.last_progress)
+    }
+
+    /// Start over after a recovery or reconnection
+    pub fn reset(&mut self, now: Instant) {
+        self.last_written = None;
+        self.last_progress = now;
+    }
+}
+
+/// Diagnostics sent with `BackendEvent::Recovered`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveryReport {
+    pub reason: StallReason,
+    pub stalled_for: Duration,
+    pub sample: WatchdogSample,
+    /// Recoveries since the backend started, including this one
+    pub recoveries: u32,
+    /// Error from reconnecting, None if the connection was restored
+    pub error: Option<String>,
+}
+
+impl RecoveryReport {
+    /// Check if the connection was restored
+    pub fn recovered(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl fmt::Display for RecoveryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stalled {:.1}s ({}), {} frames read, backlog {}",
+               self.stalled_for.as_secs_f64(), self.reason.name(), self.sample.frames_read, self.sample.backlog)?;
+        if let Some(written) = self.sample.producer_frames_written {
+            write!(f, ", producer at {}", written)?;
+        }
+        match &self.error {
+            None => write!(f, "; reconnected (recovery #{})", self.recoveries),
+            Some(error) => write!(f, "; reconnect failed: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stall_detection() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let sample = |frames_read, written, backlog| WatchdogSample { frames_read, producer_frames_written: written, backlog };
+        let mut detector = StallDetector::new(Duration::from_millis(1000), start);
+
+        // Frames keep being read
+        assert_eq!(detector.check(sample(5, Some(5), 0), at(250)), None);
+        assert_eq!(detector.check(sample(9, Some(9), 0), at(500)), None);
+
+        // An idle producer is not a stall
+        assert_eq!(detector.check(sample(9, Some(9), 0), at(1000)), None);
+        assert_eq!(detector.check(sample(9, Some(9), 0), at(2000)), None);
+
+        // The producer moves on but nothing is read
+        assert_eq!(detector.check(sample(9, Some(12), 3), at(2500)), None);
+        assert_eq!(detector.check(sample(9, Some(15), 6), at(3000)), Some(StallReason::ProducerAhead));
+        assert_eq!(detector.stalled_for(at(3000)), Duration::from_millis(1000));
+
+        // Queue-fed sources only show a stuck backlog
+        detector.reset(at(3000));
+        assert_eq!(detector.check(sample(9, None, 4), at(3500)), None);
+        assert_eq!(detector.check(sample(9, None, 4), at(4000)), Some(StallReason::BacklogStuck));
+
+        let report = RecoveryReport {
+            reason: StallReason::BacklogStuck,
+            stalled_for: Duration::from_millis(1000),
+            sample: sample(9, None, 4),
+            recoveries: 1,
+            error: None,
+        };
+        assert!(report.recovered());
+        assert_eq!(report.to_string(), "stalled 1.0s (frames queued, none read), 9 frames read, backlog 4; reconnected (recovery #1)");
+    }
+}
@@ -0,0 +1,233 @@
+// src/backend/yuv.rs - BT.601/BT.709 YUV to RGB Matrices with Range Handling
+
+//! YUV frames are converted with the matrix and range the producer declares in
+//! the JSON metadata:
+//!
+//! ```json
+//! {"yuv_matrix": "bt709", "yuv_range": "limited"}
+//! ```
+//!
+//! Without a declaration the usual video conventions apply: BT.709 from 720
+//! rows up and BT.601 below, limited range for frames carrying chroma, and full
+//! range for luma-only frames (ultrasound Y planes use all 256 levels).
+//!
+//! Packed YUV is recognised by its bytes per pixel: 1 is luma only, 2 is 4:2:2
+//! (YUYV, or UYVY with `"yuv_packing": "uyvy"`) and 3 is 4:4:4 (Y, U, V).
+
+use crate::backend::types::FrameHeader;
+
+/// Metadata key selecting the matrix
+pub const MATRIX_KEY: &str = "yuv_matrix";
+
+/// Metadata key selecting the range
+pub const RANGE_KEY: &str = "yuv_range";
+
+/// Metadata key selecting the byte order of packed 4:2:2
+pub const PACKING_KEY: &str = "yuv_packing";
+
+/// Rows from which undeclared frames are taken as HD (BT.709)
+pub const HD_MIN_HEIGHT: u32 = 720;
+
+/// YUV to RGB matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YuvMatrix {
+    /// SD video (Kr = 0.299, Kb = 0.114)
+    Bt601,
+    /// HD video (Kr = 0.2126, Kb = 0.0722)
+    Bt709,
+}
+
+impl YuvMatrix {
+    /// Parse a matrix name as used in metadata
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "bt601" | "bt.601" | "rec601" | "smpte170m" => Some(Self::Bt601),
+            "bt709" | "bt.709" | "rec709" => Some(Self::Bt709),
+            _ => None,
+        }
+    }
+
+    /// Red and blue luma weights (Kr, Kb)
+    pub fn coefficients(&self) -> (f64, f64) {
+        match self {
+            Self::Bt601 => (0.299, 0.114),
+            Self::Bt709 => (0.2126, 0.0722),
+        }
+    }
+}
+
+/// Code value range of the samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YuvRange {
+    /// Luma 16-235, chroma 16-240 (video)
+    Limited,
+    /// All 256 levels (JPEG, most sensors)
+    Full,
+}
+
+impl YuvRange {
+    /// Parse a range name as used in metadata
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "limited" | "tv" | "studio" | "mpeg" => Some(Self::Limited),
+            "full" | "pc" | "jpeg" => Some(Self::Full),
+            _ => None,
+        }
+    }
+}
+
+/// Matrix, range and 4:2:2 byte order of a YUV frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct YuvEncoding {
+    pub matrix: YuvMatrix,
+    pub range: YuvRange,
+    /// Packed 4:2:2 starts with chroma (UYVY) rather than luma (YUYV)
+    pub chroma_first: bool,
+}
+
+impl YuvEncoding {
+    /// Encoding of a frame, from its metadata or the conventions for its size
+    pub fn from_frame(header: &FrameHeader, metadata: Option<&str>, has_chroma: bool) -> Self {
+        let mut encoding = Self {
+            matrix: if header.height >= HD_MIN_HEIGHT { YuvMatrix::Bt709 } else { YuvMatrix::Bt601 },
+            range: if has_chroma { YuvRange::Limited } else { YuvRange::Full },
+            chroma_first: false,
+        };
+
+        // Only parse metadata that mentions one of the keys
+        let Some(metadata) = metadata.filter(|text| text.contains("yuv_")) else {
+            return encoding;
+        };
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(metadata) else {
+            return encoding;
+        };
+        let field = |key: &str| json.get(key).and_then(|v| v.as_str());
+        if let Some(matrix) = field(MATRIX_KEY).and_then(YuvMatrix::from_name) {
+            encoding.matrix = matrix;
+        }
+        if let Some(range) = field(RANGE_KEY).and_then(YuvRange::from_name) {
+            encoding.range = range;
+        }
+        encoding.chroma_first = field(PACKING_KEY).is_some_and(|packing| packing.eq_ignore_ascii_case("uyvy"));
+        encoding
+    }
+}
+
+/// Fixed-point (16.16) YUV to RGB conversion for one encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YuvConverter {
+    y_offset: i32,
+    y_scale: i32,
+    r_v: i32,
+    g_u: i32,
+    g_v: i32,
+    b_u: i32,
+}
+
+impl YuvConverter {
+    /// Precompute the coefficients of an encoding
+    pub fn new(encoding: &YuvEncoding) -> Self {
+        let (kr, kb) = encoding.matrix.coefficients();
+        let kg = 1.0 - kr - kb;
+        let (y_offset, y_scale, c_scale) = match encoding.range {
+            YuvRange::Limited => (16, 255.0 / 219.0, 255.0 / 224.0),
+            YuvRange::Full => (0, 1.0, 1.0),
+        };
+        let fixed = |value: f64| (value * 65536.0).round() as i32;
+        Self {
+            y_offset,
+            y_scale: fixed(y_scale),
+            r_v: fixed(2.0 * (1.0 - kr) * c_scale),
+            g_u: fixed(2.0 * kb * (1.0 - kb) / kg * c_scale),
+            g_v: fixed(2.0 * kr * (1.0 - kr) / kg * c_scale),
+            b_u: fixed(2.0 * (1.0 - kb) * c_scale),
+        }
+    }
+
+    /// Convert one pixel to R, G, B
+    #[inline]
+    pub fn to_rgb(&self, y: u8, u: u8, v: u8) -> [u8; 3] {
+        let luma = (y as i32 - self.y_offset) * self.y_scale + 32768;
+        let (u, v) = (u as i32 - 128, v as i32 - 128);
+        let clamp = |value: i32| (value >> 16).clamp(0, 255) as u8;
+        [clamp(luma + self.r_v * v), clamp(luma - self.g_u * u - self.g_v * v), clamp(luma + self.b_u * u)]
+    }
+
+    /// Convert a luma-only sample to gray
+    #[inline]
+    pub fn luma(&self, y: u8) -> u8 {
+        (((y as i32 - self.y_offset) * self.y_scale + 32768) >> 16).clamp(0, 255) as u8
+    }
+
+    /// Convert packed YUV with 1, 2 (4:2:2) or 3 (4:4:4) bytes per pixel into RGBA
+    pub fn packed_to_rgba(&self, src: &[u8], dst: &mut [u8], bytes_per_pixel: usize, chroma_first: bool) {
+        match bytes_per_pixel {
+            2 => {
+                // Two pixels share one U and one V sample
+                let (y0, u, y1, v) = if chroma_first { (1, 0, 3, 2) } else { (0, 1, 2, 3) };
+                for (pair, out) in src.chunks_exact(4).zip(dst.chunks_mut(8)) {
+                    let [r, g, b] = self.to_rgb(pair[y0], pair[u], pair[v]);
+                    out[..4].copy_from_slice(&[r, g, b, 255]);
+                    if out.len() == 8 {
+                        let [r, g, b] = self.to_rgb(pair[y1], pair[u], pair[v]);
+                        out[4..].copy_from_slice(&[r, g, b, 255]);
+                    }
+                }
+            }
+            3 => {
+                for (pixel, out) in src.chunks_exact(3).zip(dst.chunks_exact_mut(4)) {
+                    let [r, g, b] = self.to_rgb(pixel[0], pixel[1], pixel[2]);
+                    out.copy_from_slice(&[r, g, b, 255]);
+                }
+            }
+            _ => {
+                for (&y, out) in src.iter().zip(dst.chunks_exact_mut(4)) {
+                    let gray = self.luma(y);
+                    out.copy_from_slice(&[gray, gray, gray, 255]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn converter(matrix: YuvMatrix, range: YuvRange) -> YuvConverter {
+        YuvConverter::new(&YuvEncoding { matrix, range, chroma_first: false })
+    }
+
+    #[test]
+    fn test_matrices_and_ranges() {
+        let limited = converter(YuvMatrix::Bt601, YuvRange::Limited);
+        assert_eq!(limited.to_rgb(16, 128, 128), [0, 0, 0]);
+        assert_eq!(limited.to_rgb(235, 128, 128), [255, 255, 255]);
+        assert_eq!(limited.luma(126), 128);
+
+        let full = converter(YuvMatrix::Bt601, YuvRange::Full);
+        assert_eq!(full.to_rgb(0, 128, 128), [0, 0, 0]);
+        assert_eq!(full.luma(200), 200);
+
+        // BT.709 red decodes to red only through the BT.709 matrix
+        let [r, g, b] = converter(YuvMatrix::Bt709, YuvRange::Limited).to_rgb(63, 102, 240);
+        assert!(r == 255 && g <= 1 && b <= 1);
+        assert!(converter(YuvMatrix::Bt601, YuvRange::Limited).to_rgb(63, 102, 240)[0] < 240);
+    }
+
+    #[test]
+    fn test_packed_422_and_metadata() {
+        let converter = converter(YuvMatrix::Bt601, YuvRange::Full);
+        let mut rgba = [0u8; 8];
+        converter.packed_to_rgba(&[10, 128, 200, 128], &mut rgba, 2, false);
+        assert_eq!(rgba, [10, 10, 10, 255, 200, 200, 200, 255]);
+        converter.packed_to_rgba(&[128, 10, 128, 200], &mut rgba, 2, true);
+        assert_eq!(rgba, [10, 10, 10, 255, 200, 200, 200, 255]);
+
+        let header = FrameHeader::for_test(1920, 1080, 2, 0x01);
+        let conventional = YuvEncoding::from_frame(&header, None, true);
+        assert_eq!((conventional.matrix, conventional.range), (YuvMatrix::Bt709, YuvRange::Limited));
+        let declared = YuvEncoding::from_frame(&header, Some(r#"{"yuv_matrix": "BT601", "yuv_range": "full", "yuv_packing": "uyvy"}"#), true);
+        assert_eq!(declared, YuvEncoding { matrix: YuvMatrix::Bt601, range: YuvRange::Full, chroma_first: true });
+    }
+}
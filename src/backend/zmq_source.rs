@@ -0,0 +1,196 @@
+// src/backend/zmq_source.rs - ZeroMQ SUB Socket Frame Source (feature "zmq")
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use zeromq::{Socket, SocketRecv, SubSocket};
+
+use crate::backend::frame_source::{FrameQueue, FrameSource};
+use crate::backend::shared_memory::{ConnectionStatistics, SharedMemoryError};
+use crate::backend::tcp_source::{self, MAX_MESSAGE_SIZE};
+use crate::backend::types::{ConnectionConfig, FrameHeader, RawFrame};
+
+/// Prefix of `--shm-name` values that name a ZeroMQ publisher
+pub const ZMQ_PREFIX: &str = "zmq:";
+
+/// Frames buffered before the oldest is dropped, matching the TCP receiver
+const MAX_QUEUED_FRAMES: usize = 7;
+
+/// Endpoint and topic if the name is `zmq:<endpoint>[#topic]`
+///
+/// Without a topic every message is taken and none carries a topic part.
+pub fn parse_endpoint(name: &str) -> Option<(&str, &str)> {
+    let endpoint = name.strip_prefix(ZMQ_PREFIX)?;
+    let (endpoint, topic) = endpoint.split_once('#').unwrap_or((endpoint, ""));
+    (!endpoint.is_empty()).then_some((endpoint, topic))
+}
+
+/// Decode a published frame
+///
+/// Either one part laid out like a TCP message without its length prefix, or
+/// separate header, data and optional metadata parts. A subscribed topic comes
+/// first and is skipped.
+pub fn decode_parts(parts: &[&[u8]], has_topic: bool) -> Result<RawFrame, SharedMemoryError> {
+    let parts = if has_topic { parts.get(1..).unwrap_or_default() } else { parts };
+    let size: usize = parts.iter().map(|part| part.len()).sum();
+    let header_size = std::mem::size_of::<FrameHeader>();
+    if size > MAX_MESSAGE_SIZE {
+        return Err(SharedMemoryError::InvalidFrameSize { start: header_size, end: size, total: MAX_MESSAGE_SIZE });
+    }
+
+    match parts {
+        [message] => tcp_source::decode_message(message),
+        [header, data, metadata @ ..] if header.len() == header_size && metadata.len() <= 1 => {
+            let mut header = unsafe { std::ptr::read_unaligned(header.as_ptr() as *const FrameHeader) };
+            let metadata = metadata.first().filter(|m| !m.is_empty()).map(|m| {
+                let end = m.iter().position(|&b| b == 0).unwrap_or(m.len());
+                String::from_utf8_lossy(&m[..end]).into_owned()
+            });
+            header.data_size = data.len() as u32;
+            header.metadata_offset = 0;
+            header.metadata_size = metadata.as_ref().map_or(0, |m| m.len() as u32 + 1);
+            Ok(RawFrame::new(header, Arc::from(*data), metadata))
+        }
+        _ => Err(SharedMemoryError::Other(format!(
+            "Unexpected ZeroMQ message: {} parts, {} bytes", parts.len(), size
+        ))),
+    }
+}
+
+/// Receives frames from a ZeroMQ PUB socket
+///
+/// Like the TCP receiver, a background task queues frames as they arrive. ZeroMQ
+/// reconnects to the publisher by itself, so a silent publisher only shows up
+/// as missing frames.
+pub struct ZmqFrameSource {
+    endpoint: String,
+    topic: String,
+    config: ConnectionConfig,
+    queue: Arc<FrameQueue>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl ZmqFrameSource {
+    /// Create a source subscribing to `topic` (empty for all messages) on `endpoint`
+    pub fn new(endpoint: &str, topic: &str, config: ConnectionConfig) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            topic: topic.to_string(),
+            config,
+            queue: Arc::new(FrameQueue::new(MAX_QUEUED_FRAMES)),
+            task: None,
+        }
+    }
+
+    async fn receive_frames(mut socket: SubSocket, has_topic: bool, queue: Arc<FrameQueue>) {
+        loop {
+            let message = match socket.recv().await {
+                Ok(message) => message.into_vec(),
+                Err(e) => {
+                    warn!("❌ ZeroMQ subscription failed: {}", e);
+                    queue.record_error();
+                    break;
+                }
+            };
+
+            // Each message stands alone, so a bad one does not end the subscription
+            let parts: Vec<&[u8]> = message.iter().map(|part| part.as_ref()).collect();
+            match decode_parts(&parts, has_topic) {
+                Ok(frame) => queue.push(frame),
+                Err(e) => {
+                    warn!("⚠️ Dropping ZeroMQ message: {}", e);
+                    queue.record_error();
+                }
+            }
+        }
+
+        queue.set_connected(false);
+    }
+}
+
+#[async_trait]
+impl FrameSource for ZmqFrameSource {
+    async fn connect(&mut self) -> Result<(), SharedMemoryError> {
+        if self.config.verbose_logging {
+            info!("🔌 Subscribing to ZeroMQ publisher: {} (topic \"{}\")", self.endpoint, self.topic);
+        }
+
+        let zmq_error = |e: zeromq::ZmqError| SharedMemoryError::Other(format!("ZeroMQ: {}", e));
+        let mut socket = SubSocket::new();
+        tokio::time::timeout(self.config.frame_timeout, socket.connect(&self.endpoint))
+            .await
+            .map_err(|_| SharedMemoryError::NotFound(self.endpoint.clone()))?
+            .map_err(zmq_error)?;
+        socket.subscribe(&self.topic).await.map_err(zmq_error)?;
+
+        self.queue.set_connected(true);
+        self.task = Some(tokio::spawn(Self::receive_frames(socket, !self.topic.is_empty(), Arc::clone(&self.queue))));
+
+        info!("🔗 Subscribed to ZeroMQ publisher: {}", self.endpoint);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.queue.set_connected(false);
+
+        info!("🔌 Unsubscribed from ZeroMQ publisher: {}", self.endpoint);
+    }
+
+    async fn get_next_frame(&self, catch_up: bool) -> Result<Option<RawFrame>, SharedMemoryError> {
+        self.queue.next(catch_up)
+    }
+
+    fn check_connection_health(&self) -> bool {
+        self.queue.is_healthy(self.config.frame_timeout)
+    }
+
+    fn backlog(&self) -> u64 {
+        self.queue.backlog()
+    }
+
+    fn get_statistics(&self) -> ConnectionStatistics {
+        self.queue.statistics(format!("{}{}", ZMQ_PREFIX, self.endpoint))
+    }
+}
+
+impl Drop for ZmqFrameSource {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoints_and_parts() {
+        assert_eq!(parse_endpoint("zmq:tcp://10.0.0.5:5556"), Some(("tcp://10.0.0.5:5556", "")));
+        assert_eq!(parse_endpoint("zmq:ipc:///tmp/frames#probe1"), Some(("ipc:///tmp/frames", "probe1")));
+        assert_eq!(parse_endpoint("zmq:#probe1"), None);
+
+        let header = FrameHeader { frame_id: 4, sequence_number: 4, data_size: 0, ..FrameHeader::for_test(2, 1, 1, 0x10) };
+        let header_bytes = unsafe {
+            std::slice::from_raw_parts(&header as *const FrameHeader as *const u8, std::mem::size_of::<FrameHeader>())
+        };
+
+        // Topic, header, data and metadata as separate parts
+        let frame = decode_parts(&[&b"probe1"[..], header_bytes, &[7, 8], b"{\"gain\":3}\0"], true).unwrap();
+        assert_eq!((frame.header.sequence_number, &frame.data[..]), (4, &[7u8, 8][..]));
+        assert_eq!(frame.metadata.as_deref(), Some("{\"gain\":3}"));
+
+        // The TCP message layout in a single part
+        let message = tcp_source::encode_frame(&header, &[9, 9], None);
+        let frame = decode_parts(&[&message[4..]], false).unwrap();
+        assert_eq!(&frame.data[..], &[9, 9]);
+
+        assert!(decode_parts(&[&b"short"[..], &[0]], false).is_err());
+    }
+}
@@ -0,0 +1,379 @@
+// src/encryption.rs - Encrypted Recordings and Key Management
+
+//! AES-256-GCM encryption of recorded media: frame dumps and session reports with
+//! their snapshots, so files carried off on removable drives are unreadable
+//! without the key.
+//!
+//! The 256-bit key comes from a key file (64 hex digits) or from the system
+//! keyring; keyring access needs the `keyring` feature. Each file is sealed as a
+//! whole and written next to the plain name with an `.enc` suffix; `mivi decrypt`
+//! restores the original name, so a decrypted report still finds its thumbnails.
+//!
+//! File layout: `MIVIENC1` | key id (8 bytes) | nonce (12 bytes) | ciphertext and tag.
+//! The header is authenticated along with the content.
+//!
+//! Recordings that grow while frames arrive are sealed chunk by chunk instead:
+//! `MIVISTR1` | key id, then per chunk its length (u32 LE) | nonce | ciphertext
+//! and tag. The chunk index is authenticated with each chunk, so chunks cannot
+//! be reordered or dropped from the middle; a file cut short by a crash opens
+//! up to its last complete chunk.
+
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Magic bytes and format version at the start of every encrypted file
+const MAGIC: &[u8; 8] = b"MIVIENC1";
+
+/// Magic bytes and format version of files sealed chunk by chunk
+const STREAM_MAGIC: &[u8; 8] = b"MIVISTR1";
+
+/// Bytes of the key fingerprint stored in the header
+const KEY_ID_LEN: usize = 8;
+
+/// AES-GCM nonce size
+const NONCE_LEN: usize = 12;
+
+/// Suffix appended to encrypted files
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Keyring service the recording key is stored under
+pub const KEYRING_SERVICE: &str = "mivi-recordings";
+
+/// Errors that can occur while loading keys or sealing files
+#[derive(Debug, Error)]
+pub enum EncryptionError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid recording key: {0}")]
+    InvalidKey(String),
+
+    #[error("Keyring access is not available in this build (enable the \"keyring\" feature)")]
+    KeyringUnavailable,
+
+    #[error("Keyring error: {0}")]
+    Keyring(String),
+
+    #[error("Not an encrypted MiVi recording")]
+    NotEncrypted,
+
+    #[error("File was encrypted with a different key (key id {0})")]
+    WrongKey(String),
+
+    #[error("Encrypted file is damaged or was modified")]
+    Tampered,
+}
+
+/// Where the recording key comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySource {
+    /// File holding the key as 64 hex digits
+    File(PathBuf),
+    /// Entry of the system keyring under [`KEYRING_SERVICE`] and this account
+    Keyring(String),
+}
+
+impl KeySource {
+    /// Read the key from its source
+    pub fn load(&self) -> Result<RecordingCipher, EncryptionError> {
+        let hex = match self {
+            KeySource::File(path) => std::fs::read_to_string(path)?,
+            KeySource::Keyring(account) => keyring_get(account)?,
+        };
+        RecordingCipher::from_hex(&hex)
+    }
+
+    /// Store a key at its source, readable only by the current user
+    pub fn store(&self, key: &[u8; 32]) -> Result<(), EncryptionError> {
+        let hex = to_hex(key);
+        match self {
+            KeySource::File(path) => write_private(path, hex.as_bytes()),
+            KeySource::Keyring(account) => keyring_set(account, &hex),
+        }
+    }
+}
+
+/// Generate a random 256-bit recording key
+pub fn generate_key() -> [u8; 32] {
+    Aes256Gcm::generate_key(OsRng).into()
+}
+
+/// Seals and opens recordings with one key
+#[derive(Clone)]
+pub struct RecordingCipher {
+    cipher: Aes256Gcm,
+    key_id: [u8; KEY_ID_LEN],
+}
+
+impl RecordingCipher {
+    /// Create a cipher from raw key bytes
+    pub fn new(key: &[u8; 32]) -> Self {
+        let digest = Sha256::digest(key);
+        let mut key_id = [0u8; KEY_ID_LEN];
+        key_id.copy_from_slice(&digest[..KEY_ID_LEN]);
+        Self { cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)), key_id }
+    }
+
+    /// Create a cipher from a key written as 64 hex digits
+    pub fn from_hex(hex: &str) -> Result<Self, EncryptionError> {
+        let bytes = from_hex(hex.trim()).ok_or_else(|| EncryptionError::InvalidKey("expected hex digits".to_string()))?;
+        let key: [u8; 32] = bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| EncryptionError::InvalidKey(format!("expected 32 bytes, found {}", bytes.len())))?;
+        Ok(Self::new(&key))
+    }
+
+    /// Fingerprint of the key, safe to show and log
+    pub fn key_id(&self) -> String {
+        to_hex(&self.key_id)
+    }
+
+    /// Seal `plaintext` into the encrypted file format
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let mut sealed = Vec::with_capacity(MAGIC.len() + KEY_ID_LEN + NONCE_LEN + plaintext.len() + 16);
+        sealed.extend_from_slice(MAGIC);
+        sealed.extend_from_slice(&self.key_id);
+        sealed.extend_from_slice(&nonce);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, Payload { msg: plaintext, aad: &sealed })
+            .map_err(|_| EncryptionError::InvalidKey("encryption failed".to_string()))?;
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Open a file sealed with [`encrypt`](Self::encrypt)
+    pub fn decrypt(&self, sealed: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let header_len = MAGIC.len() + KEY_ID_LEN + NONCE_LEN;
+        if sealed.len() < header_len || &sealed[..MAGIC.len()] != MAGIC {
+            return Err(EncryptionError::NotEncrypted);
+        }
+
+        let (header, ciphertext) = sealed.split_at(header_len);
+        let key_id = &header[MAGIC.len()..MAGIC.len() + KEY_ID_LEN];
+        if key_id != self.key_id {
+            return Err(EncryptionError::WrongKey(to_hex(key_id)));
+        }
+
+        let nonce = Nonce::from_slice(&header[MAGIC.len() + KEY_ID_LEN..]);
+        self.cipher
+            .decrypt(nonce, Payload { msg: ciphertext, aad: header })
+            .map_err(|_| EncryptionError::Tampered)
+    }
+
+    /// Header starting a file sealed chunk by chunk
+    pub fn stream_header(&self) -> Vec<u8> {
+        [&STREAM_MAGIC[..], &self.key_id].concat()
+    }
+
+    /// Seal chunk `index` of a file started with [`stream_header`](Self::stream_header), length prefix included
+    pub fn encrypt_chunk(&self, index: u64, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let aad = [self.stream_header(), index.to_le_bytes().to_vec()].concat();
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, Payload { msg: plaintext, aad: &aad })
+            .map_err(|_| EncryptionError::InvalidKey("encryption failed".to_string()))?;
+
+        let mut chunk = Vec::with_capacity(4 + NONCE_LEN + ciphertext.len());
+        chunk.extend_from_slice(&((NONCE_LEN + ciphertext.len()) as u32).to_le_bytes());
+        chunk.extend_from_slice(&nonce);
+        chunk.extend_from_slice(&ciphertext);
+        Ok(chunk)
+    }
+
+    /// Open a file sealed chunk by chunk, returning its chunks joined
+    pub fn decrypt_stream(&self, sealed: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let header_len = STREAM_MAGIC.len() + KEY_ID_LEN;
+        if sealed.len() < header_len || &sealed[..STREAM_MAGIC.len()] != STREAM_MAGIC {
+            return Err(EncryptionError::NotEncrypted);
+        }
+        let key_id = &sealed[STREAM_MAGIC.len()..header_len];
+        if key_id != self.key_id {
+            return Err(EncryptionError::WrongKey(to_hex(key_id)));
+        }
+
+        let mut plaintext = Vec::new();
+        let mut rest = &sealed[header_len..];
+        let mut index = 0u64;
+        // A chunk cut short by a crash ends the file
+        while rest.len() >= 4 {
+            let len = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            if len < NONCE_LEN || rest.len() - 4 < len {
+                break;
+            }
+            let (nonce, ciphertext) = rest[4..4 + len].split_at(NONCE_LEN);
+            let aad = [&sealed[..header_len], &index.to_le_bytes()[..]].concat();
+            let chunk = self
+                .cipher
+                .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: &aad })
+                .map_err(|_| EncryptionError::Tampered)?;
+            plaintext.extend_from_slice(&chunk);
+            rest = &rest[4 + len..];
+            index += 1;
+        }
+        Ok(plaintext)
+    }
+
+    /// Decrypt a `.enc` file next to itself, returning the restored path
+    pub fn decrypt_file(&self, path: &Path) -> Result<PathBuf, EncryptionError> {
+        let output = match path.extension() {
+            Some(extension) if extension == ENCRYPTED_EXTENSION => path.with_extension(""),
+            _ => return Err(EncryptionError::NotEncrypted),
+        };
+        let sealed = std::fs::read(path)?;
+        let plaintext = if sealed.starts_with(STREAM_MAGIC) { self.decrypt_stream(&sealed)? } else { self.decrypt(&sealed)? };
+        std::fs::write(&output, plaintext)?;
+        Ok(output)
+    }
+}
+
+impl std::fmt::Debug for RecordingCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordingCipher").field("key_id", &self.key_id()).finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RecordingCipher {
+    fn eq(&self, other: &Self) -> bool {
+        self.key_id == other.key_id
+    }
+}
+
+impl Eq for RecordingCipher {}
+
+/// Path a recording is sealed under: `path` + `.enc`
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Write a recorded file, sealed under `path` + `.enc` when a cipher is given
+///
+/// Returns the path actually written.
+pub fn write_recording(path: &Path, data: &[u8], cipher: Option<&RecordingCipher>) -> Result<PathBuf, EncryptionError> {
+    match cipher {
+        Some(cipher) => {
+            let path = encrypted_path(path);
+            std::fs::write(&path, cipher.encrypt(data)?)?;
+            Ok(path)
+        }
+        None => {
+            std::fs::write(path, data)?;
+            Ok(path.to_path_buf())
+        }
+    }
+}
+
+fn write_private(path: &Path, data: &[u8]) -> Result<(), EncryptionError> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(data)?;
+    Ok(())
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_get(account: &str) -> Result<String, EncryptionError> {
+    keyring::Entry::new(KEYRING_SERVICE, account)
+        .and_then(|entry| entry.get_password())
+        .map_err(|e| EncryptionError::Keyring(e.to_string()))
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_set(account: &str, hex: &str) -> Result<(), EncryptionError> {
+    keyring::Entry::new(KEYRING_SERVICE, account)
+        .and_then(|entry| entry.set_password(hex))
+        .map_err(|e| EncryptionError::Keyring(e.to_string()))
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_get(_account: &str) -> Result<String, EncryptionError> {
+    Err(EncryptionError::KeyringUnavailable)
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_set(_account: &str, _hex: &str) -> Result<(), EncryptionError> {
+    Err(EncryptionError::KeyringUnavailable)
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = generate_key();
+        let cipher = RecordingCipher::from_hex(&to_hex(&key)).unwrap();
+        assert_eq!(cipher, RecordingCipher::new(&key));
+
+        let sealed = cipher.encrypt(b"frame bytes").unwrap();
+        assert!(!sealed.windows(5).any(|w| w == b"frame"));
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), b"frame bytes");
+
+        // Flipping any byte, header included, is detected
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(cipher.decrypt(&tampered), Err(EncryptionError::Tampered)));
+        let mut tampered = sealed.clone();
+        tampered[MAGIC.len() + KEY_ID_LEN] ^= 1;
+        assert!(matches!(cipher.decrypt(&tampered), Err(EncryptionError::Tampered)));
+
+        let other = RecordingCipher::new(&generate_key());
+        assert!(matches!(other.decrypt(&sealed), Err(EncryptionError::WrongKey(_))));
+        assert!(matches!(cipher.decrypt(b"plain text file"), Err(EncryptionError::NotEncrypted)));
+        assert!(RecordingCipher::from_hex("abcd").is_err());
+    }
+
+    #[test]
+    fn test_recording_files() {
+        let dir = std::env::temp_dir().join(format!("mivi_encryption_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let key_source = KeySource::File(dir.join("recording.key"));
+        key_source.store(&generate_key()).unwrap();
+        let cipher = key_source.load().unwrap();
+
+        let written = write_recording(&dir.join("frame.raw"), &[1, 2, 3], Some(&cipher)).unwrap();
+        assert_eq!(written, dir.join("frame.raw.enc"));
+        assert!(!dir.join("frame.raw").exists());
+        assert_eq!(cipher.decrypt_file(&written).unwrap(), dir.join("frame.raw"));
+        assert_eq!(std::fs::read(dir.join("frame.raw")).unwrap(), [1, 2, 3]);
+
+        // Chunked files open up to the last complete chunk, but not out of order
+        let mut stream = cipher.stream_header();
+        let (first, second) = (cipher.encrypt_chunk(0, b"ab").unwrap(), cipher.encrypt_chunk(1, b"cd").unwrap());
+        stream.extend_from_slice(&first);
+        stream.extend_from_slice(&second);
+        assert_eq!(cipher.decrypt_stream(&stream).unwrap(), b"abcd");
+        assert_eq!(cipher.decrypt_stream(&stream[..stream.len() - 1]).unwrap(), b"ab");
+        let swapped = [cipher.stream_header(), second, first].concat();
+        assert!(matches!(cipher.decrypt_stream(&swapped), Err(EncryptionError::Tampered)));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,6 +1,9 @@
 // src/error.rs - Comprehensive Error Handling for MiVi Medical Frame Viewer
 
 use std::fmt;
+use std::sync::Arc;
+
+use crate::telemetry::{TelemetryEvent, TelemetrySink};
 
 /// Main error type for the MiVi Medical Frame Viewer application
 #[derive(Debug, thiserror::Error)]
@@ -22,10 +25,12 @@ pub enum MiViError {
     FrameProcessing(#[from] crate::backend::frame_processor::ProcessingError),
     
     /// Image conversion errors
+    #[cfg(feature = "ui")]
     #[error("Image conversion error: {0}")]
     ImageConversion(#[from] crate::frontend::image_converter::ImageConversionError),
     
     /// Slint UI errors
+    #[cfg(feature = "ui")]
     #[error("UI error: {0}")]
     Ui(#[from] crate::frontend::slint_bridge::SlintBridgeError),
     
@@ -171,6 +176,7 @@ impl MiViError {
             MiViError::Network(_) => true,
             MiViError::Timeout(_) => true,
             MiViError::MedicalDevice(_) => true,
+            #[cfg(feature = "ui")]
             MiViError::ImageConversion(_) => true,
             MiViError::FrameProcessing(_) => true,
             
@@ -207,11 +213,13 @@ impl MiViError {
             // Medium severity errors that partially impact functionality
             MiViError::MedicalDevice(_) |
             MiViError::Network(_) |
-            MiViError::FrameProcessing(_) |
+            MiViError::FrameProcessing(_) => ErrorSeverity::Medium,
+            #[cfg(feature = "ui")]
             MiViError::ImageConversion(_) => ErrorSeverity::Medium,
             
             // Low severity errors that have minimal impact
-            MiViError::Ui(_) |
+            #[cfg(feature = "ui")]
+            MiViError::Ui(_) => ErrorSeverity::Low,
             MiViError::Validation(_) |
             MiViError::Timeout(_) |
             MiViError::Cancelled(_) => ErrorSeverity::Low,
@@ -260,7 +268,8 @@ impl MiViError {
             MiViError::SharedMemory(_) |
             MiViError::FrameProcessing(_) => ErrorCategory::Backend,
             
-            MiViError::Frontend(_) |
+            MiViError::Frontend(_) => ErrorCategory::Frontend,
+            #[cfg(feature = "ui")]
             MiViError::Ui(_) |
             MiViError::ImageConversion(_) => ErrorCategory::Frontend,
             
@@ -333,7 +342,9 @@ impl MiViError {
             MiViError::Frontend(_) => 2000,
             MiViError::SharedMemory(_) => 3000,
             MiViError::FrameProcessing(_) => 3100,
+            #[cfg(feature = "ui")]
             MiViError::ImageConversion(_) => 3200,
+            #[cfg(feature = "ui")]
             MiViError::Ui(_) => 4000,
             MiViError::Configuration(_) => 5000,
             MiViError::Application(_) => 5100,
@@ -469,17 +480,37 @@ where
 pub struct ErrorReporter {
     enable_logging: bool,
     enable_telemetry: bool,
+    sink: Option<Arc<dyn TelemetrySink>>,
+    include_details: bool,
+    in_flight: parking_lot::Mutex<Vec<std::thread::JoinHandle<()>>>,
 }
 
 impl ErrorReporter {
     /// Create a new error reporter
+    ///
+    /// Telemetry also needs a sink from `with_sink`; without one nothing leaves the machine.
     pub fn new(enable_logging: bool, enable_telemetry: bool) -> Self {
         Self {
             enable_logging,
             enable_telemetry,
+            sink: None,
+            include_details: false,
+            in_flight: parking_lot::Mutex::new(Vec::new()),
         }
     }
     
+    /// Send telemetry to `sink`
+    pub fn with_sink(mut self, sink: Arc<dyn TelemetrySink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+    
+    /// Include raw error messages in telemetry, which may contain paths or device names
+    pub fn with_details(mut self, include_details: bool) -> Self {
+        self.include_details = include_details;
+        self
+    }
+    
     /// Report an error
     pub fn report(&self, error: &MiViError) {
         if self.enable_logging {
@@ -543,38 +574,41 @@ impl ErrorReporter {
         }
     }
     
-    /// Send error telemetry (placeholder for external telemetry systems)
+    /// Send error telemetry to the configured sink
+    ///
+    /// Delivery happens on its own thread so a slow collector never holds up the caller.
     fn send_telemetry(&self, error: &MiViError) {
-        // In a real implementation, this would send error data to an external
-        // telemetry system like Sentry, DataDog, etc.
-        
-        let _telemetry_data = ErrorTelemetryData {
-            error_code: error.error_code(),
-            severity: error.severity(),
-            category: error.category(),
-            message: error.to_string(),
-            user_message: error.user_message(),
-            suggested_action: error.suggested_action().to_string(),
-            is_recoverable: error.is_recoverable(),
-            timestamp: std::time::SystemTime::now(),
+        let Some(sink) = self.sink.clone() else {
+            return;
         };
+        let event = TelemetryEvent::from_error(error, self.include_details);
         
-        // Send telemetry_data to external system
-        // telemetry_client.send(telemetry_data);
+        let spawned = std::thread::Builder::new()
+            .name("mivi-telemetry".to_string())
+            .spawn(move || {
+                if let Err(e) = sink.send(&event) {
+                    tracing::debug!("{} telemetry not sent: {}", sink.name(), e);
+                }
+            });
+        match spawned {
+            Ok(thread) => {
+                let mut in_flight = self.in_flight.lock();
+                in_flight.retain(|thread| !thread.is_finished());
+                in_flight.push(thread);
+            }
+            Err(e) => tracing::debug!("Telemetry thread failed to start: {}", e),
+        }
+    }
+    
+    /// Wait up to `timeout` for reports still being sent, e.g. before exiting
+    pub fn flush(&self, timeout: std::time::Duration) {
+        let deadline = std::time::Instant::now() + timeout;
+        let mut in_flight = self.in_flight.lock();
+        while in_flight.iter().any(|thread| !thread.is_finished()) && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        in_flight.retain(|thread| !thread.is_finished());
     }
-}
-
-/// Telemetry data structure for error reporting
-#[derive(Debug)]
-struct ErrorTelemetryData {
-    error_code: u32,
-    severity: ErrorSeverity,
-    category: ErrorCategory,
-    message: String,
-    user_message: String,
-    suggested_action: String,
-    is_recoverable: bool,
-    timestamp: std::time::SystemTime,
 }
 
 #[cfg(test)]
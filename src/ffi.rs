@@ -0,0 +1,474 @@
+// src/ffi.rs - C API for Embedding the Backend Without the UI
+
+//! C interface to [`MedicalFrameBackend`] for device software written in C or C++.
+//!
+//! The crate builds as a `cdylib` alongside the Rust library; `include/mivi.h`
+//! declares these functions. A `MiviBackend` owns its own tokio runtime, so the
+//! host application needs no async support. Frames are either polled with
+//! [`mivi_backend_poll_frame`] or pushed to a callback registered with
+//! [`mivi_backend_set_frame_callback`], which runs on a runtime worker thread.
+//!
+//! Functions return [`MIVI_OK`] (or a positive count) on success and
+//! [`MIVI_ERROR`] on failure; [`mivi_last_error`] then describes the failure
+//! on the calling thread. A handle may be used from several threads, but not
+//! concurrently.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::backend::{
+    BackendCommand, BackendConfig, BackendHandle, FrameReceiver, MedicalFrameBackend, Orientation, ProcessedFrame,
+    RgbaBuffer, Rotation, WindowLevel,
+};
+
+/// The call succeeded
+pub const MIVI_OK: c_int = 0;
+
+/// The call failed; see [`mivi_last_error`]
+pub const MIVI_ERROR: c_int = -1;
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|error| *error.borrow_mut() = message);
+}
+
+/// Run an FFI body, turning errors and panics into [`MIVI_ERROR`]
+fn guarded(body: impl FnOnce() -> Result<c_int, String>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(code)) => code,
+        Ok(Err(message)) => {
+            set_last_error(message);
+            MIVI_ERROR
+        }
+        Err(_) => {
+            set_last_error("internal panic");
+            MIVI_ERROR
+        }
+    }
+}
+
+/// Borrow a C string argument, rejecting null and invalid UTF-8
+unsafe fn c_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, String> {
+    if value.is_null() {
+        return Err(format!("{} is null", name));
+    }
+    CStr::from_ptr(value).to_str().map_err(|_| format!("{} is not valid UTF-8", name))
+}
+
+unsafe fn backend_mut<'a>(backend: *mut MiviBackend) -> Result<&'a mut MiviBackend, String> {
+    backend.as_mut().ok_or_else(|| "backend is null".to_string())
+}
+
+/// A frame handed to C; pixels are tightly packed RGBA8 (`width * 4` bytes per row)
+#[repr(C)]
+pub struct MiviFrame {
+    /// Producer frame id
+    pub frame_id: u64,
+    /// Producer sequence number
+    pub sequence_number: u64,
+    /// Producer timestamp in nanoseconds
+    pub timestamp_ns: u64,
+    /// Width in pixels after crop and orientation
+    pub width: u32,
+    /// Height in pixels after crop and orientation
+    pub height: u32,
+    /// Pixel data, valid until the next poll (or until the callback returns)
+    pub rgba: *const u8,
+    /// Length of `rgba` in bytes
+    pub rgba_len: usize,
+}
+
+impl MiviFrame {
+    fn new(frame: &ProcessedFrame, rgba: &RgbaBuffer) -> Self {
+        let bytes = rgba.as_bytes();
+        Self {
+            frame_id: frame.header.frame_id,
+            sequence_number: frame.header.sequence_number,
+            timestamp_ns: frame.header.timestamp,
+            width: rgba.width(),
+            height: rgba.height(),
+            rgba: bytes.as_ptr(),
+            rgba_len: bytes.len(),
+        }
+    }
+}
+
+/// Stream statistics
+#[repr(C)]
+#[derive(Default)]
+pub struct MiviStats {
+    /// 1 while connected to the source
+    pub connected: c_int,
+    /// Frames read from the source
+    pub total_frames_received: u64,
+    /// Frames converted for display
+    pub total_frames_processed: u64,
+    /// Frames lost before the backend read them
+    pub frames_dropped: u64,
+    /// Frames rejected by checksum verification
+    pub frames_corrupted: u64,
+    /// Received frames per second
+    pub current_fps: f64,
+    /// Producer-to-backend latency
+    pub average_latency_ms: f64,
+    /// Standard deviation of the frame interval
+    pub jitter_ms: f64,
+}
+
+/// Called with each new frame; `frame` is only valid during the call, and the callback must not unwind
+pub type MiviFrameCallback = Option<unsafe extern "C" fn(frame: *const MiviFrame, user_data: *mut c_void)>;
+
+/// Host pointer passed back to the callback on a runtime thread
+struct UserData(*mut c_void);
+
+// The host promises the pointer may be used from the callback thread (documented in mivi.h)
+unsafe impl Send for UserData {}
+
+/// Opaque backend handle owned by C
+pub struct MiviBackend {
+    runtime: tokio::runtime::Runtime,
+    backend: Arc<MedicalFrameBackend>,
+    config: BackendConfig,
+    handle: Option<BackendHandle>,
+    // None while a callback receives the frames
+    frames: Option<FrameReceiver>,
+    callback: Option<tokio::task::JoinHandle<()>>,
+    // Frame returned by the last poll, kept alive for the caller
+    polled: Option<RgbaBuffer>,
+}
+
+impl MiviBackend {
+    fn send(&self, command: BackendCommand) -> Result<c_int, String> {
+        self.backend.get_command_sender().send(command).map_err(|e| e.to_string())?;
+        Ok(MIVI_OK)
+    }
+
+    fn recycle_polled(&mut self) {
+        if let Some(buffer) = self.polled.take() {
+            self.backend.pixel_pool().recycle(buffer);
+        }
+    }
+
+    /// Stop the callback task and wait for a running callback to return
+    ///
+    /// Aborting only takes effect at the task's next await, so without waiting
+    /// the old callback could still see `user_data` after the host freed it.
+    fn stop_callback(&mut self) -> Result<(), String> {
+        let Some(task) = self.callback.take() else {
+            return Ok(());
+        };
+        task.abort();
+        match self.runtime.block_on(task) {
+            Err(e) if !e.is_cancelled() => Err(format!("frame callback task failed: {}", e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Create a backend reading `shm_name`
+///
+/// `format` may be null for the default. Returns null on failure.
+///
+/// # Safety
+///
+/// `shm_name` must be a valid C string and `format` null or a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_create(
+    shm_name: *const c_char,
+    format: *const c_char,
+    width: u32,
+    height: u32,
+) -> *mut MiviBackend {
+    let mut created = std::ptr::null_mut();
+    guarded(|| {
+        let mut config = BackendConfig {
+            shm_name: c_str(shm_name, "shm_name")?.to_string(),
+            ..BackendConfig::default()
+        };
+        if !format.is_null() {
+            config.format = c_str(format, "format")?.to_string();
+        }
+        if width > 0 && height > 0 {
+            config.width = width as usize;
+            config.height = height as usize;
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("mivi-ffi")
+            .build()
+            .map_err(|e| e.to_string())?;
+        let backend = {
+            let _runtime = runtime.enter();
+            Arc::new(MedicalFrameBackend::new(config.clone()))
+        };
+        let frames = Some(backend.get_frame_receiver());
+
+        created = Box::into_raw(Box::new(MiviBackend {
+            runtime,
+            backend,
+            config,
+            handle: None,
+            frames,
+            callback: None,
+            polled: None,
+        }));
+        Ok(MIVI_OK)
+    });
+    created
+}
+
+/// Start the frame loop and connect to the source
+///
+/// # Safety
+///
+/// `backend` must come from [`mivi_backend_create`] and not be destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_start(backend: *mut MiviBackend) -> c_int {
+    guarded(|| {
+        let backend = backend_mut(backend)?;
+        let handle = backend.runtime.block_on(backend.backend.start()).map_err(|e| e.to_string())?;
+        backend.handle = Some(handle);
+        backend.send(BackendCommand::Connect {
+            shm_name: backend.config.shm_name.clone(),
+            config: backend.config.clone(),
+        })
+    })
+}
+
+/// Wait up to `timeout_ms` for a new frame
+///
+/// Returns 1 and fills `frame` when one arrived, 0 on timeout. The pixels stay
+/// valid until the next poll or until the backend is destroyed. Fails while a
+/// frame callback is registered.
+///
+/// # Safety
+///
+/// `backend` must be a live handle and `frame` point to writable memory for a `MiviFrame`.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_poll_frame(backend: *mut MiviBackend, frame: *mut MiviFrame, timeout_ms: u32) -> c_int {
+    guarded(|| {
+        let backend = backend_mut(backend)?;
+        if frame.is_null() {
+            return Err("frame is null".to_string());
+        }
+        backend.recycle_polled();
+
+        let frames = backend.frames.as_mut().ok_or("frames are delivered to the registered callback")?;
+        let arrived = backend.runtime.block_on(async {
+            tokio::time::timeout(Duration::from_millis(timeout_ms as u64), frames.changed()).await
+        });
+        match arrived {
+            Err(_) => return Ok(0),
+            Ok(Err(_)) => return Err("backend stopped".to_string()),
+            Ok(Ok(())) => {}
+        }
+
+        let Some(processed) = frames.take_latest() else {
+            return Ok(0);
+        };
+        let rgba = processed.rgba();
+        *frame = MiviFrame::new(&processed, &rgba);
+        backend.polled = Some(rgba);
+        Ok(1)
+    })
+}
+
+/// Deliver every new frame to `callback` instead of polling; null unregisters it
+///
+/// The callback runs on a backend thread and must return quickly; frames
+/// arriving meanwhile are handled by the drop policy. A previous callback has
+/// returned for the last time when this function returns.
+///
+/// # Safety
+///
+/// `backend` must be a live handle; `user_data` must stay valid, and usable
+/// from another thread, until the callback is replaced or the backend destroyed.
+/// The callback must not unwind (C++ exceptions, Rust panics): unwinding out of
+/// an `extern "C"` function is undefined behaviour, so it cannot be caught here.
+/// It must not call this function or [`mivi_backend_destroy`] either: both wait
+/// for the callback to return.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_set_frame_callback(
+    backend: *mut MiviBackend,
+    callback: MiviFrameCallback,
+    user_data: *mut c_void,
+) -> c_int {
+    guarded(|| {
+        let backend = backend_mut(backend)?;
+        backend.stop_callback()?;
+        let mut frames = backend.backend.get_frame_receiver();
+        let Some(callback) = callback else {
+            backend.frames = Some(frames);
+            return Ok(MIVI_OK);
+        };
+
+        backend.frames = None;
+        let pool = backend.backend.pixel_pool();
+        let user_data = UserData(user_data);
+        backend.callback = Some(backend.runtime.spawn(async move {
+            let user_data = user_data;
+            while frames.changed().await.is_ok() {
+                let Some(processed) = frames.take_latest() else {
+                    continue;
+                };
+                let rgba = processed.rgba();
+                let frame = MiviFrame::new(&processed, &rgba);
+                callback(&frame, user_data.0);
+                pool.recycle(rgba);
+            }
+        }));
+        Ok(MIVI_OK)
+    })
+}
+
+/// Disconnect and connect to another source
+///
+/// # Safety
+///
+/// `backend` must be a live handle and `shm_name` a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_connect(backend: *mut MiviBackend, shm_name: *const c_char) -> c_int {
+    guarded(|| {
+        let backend = backend_mut(backend)?;
+        backend.config.shm_name = c_str(shm_name, "shm_name")?.to_string();
+        backend.send(BackendCommand::Connect {
+            shm_name: backend.config.shm_name.clone(),
+            config: backend.config.clone(),
+        })
+    })
+}
+
+/// Disconnect from the source, keeping the frame loop running
+///
+/// # Safety
+///
+/// `backend` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_disconnect(backend: *mut MiviBackend) -> c_int {
+    guarded(|| backend_mut(backend)?.send(BackendCommand::Disconnect))
+}
+
+/// Set the display window (normalized 0-1 center and width)
+///
+/// # Safety
+///
+/// `backend` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_set_window_level(backend: *mut MiviBackend, center: f32, width: f32) -> c_int {
+    guarded(|| backend_mut(backend)?.send(BackendCommand::SetWindowLevel(WindowLevel::new(center, width))))
+}
+
+/// Rotate (multiples of 90 degrees clockwise) and flip the output frames
+///
+/// # Safety
+///
+/// `backend` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_set_orientation(
+    backend: *mut MiviBackend,
+    rotation_degrees: c_int,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+) -> c_int {
+    guarded(|| {
+        let orientation = Orientation::new(Rotation::from_degrees(rotation_degrees), flip_horizontal, flip_vertical);
+        backend_mut(backend)?.send(BackendCommand::SetOrientation(orientation))
+    })
+}
+
+/// Always read the newest frame instead of every frame in order
+///
+/// # Safety
+///
+/// `backend` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_set_catch_up(backend: *mut MiviBackend, enabled: bool) -> c_int {
+    guarded(|| backend_mut(backend)?.send(BackendCommand::SetCatchUpMode(enabled)))
+}
+
+/// Copy the current stream statistics into `stats`
+///
+/// # Safety
+///
+/// `backend` must be a live handle and `stats` point to writable memory for a `MiviStats`.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_get_stats(backend: *mut MiviBackend, stats: *mut MiviStats) -> c_int {
+    guarded(|| {
+        let backend = backend_mut(backend)?;
+        let stats = stats.as_mut().ok_or("stats is null")?;
+        let state = backend.runtime.block_on(backend.backend.get_state());
+        let frame_stats = &state.frame_stats;
+        *stats = MiviStats {
+            connected: state.connection_status.is_connected() as c_int,
+            total_frames_received: frame_stats.total_frames_received,
+            total_frames_processed: frame_stats.total_frames_processed,
+            frames_dropped: frame_stats.frames_dropped,
+            frames_corrupted: frame_stats.frames_corrupted,
+            current_fps: frame_stats.current_fps,
+            average_latency_ms: frame_stats.average_latency_ms,
+            jitter_ms: frame_stats.jitter_ms,
+        };
+        Ok(MIVI_OK)
+    })
+}
+
+/// Stop the backend and free the handle; null is ignored
+///
+/// Waits for a running frame callback to return, so the callback's `user_data`
+/// may be freed afterwards.
+///
+/// # Safety
+///
+/// `backend` must be null or a handle from [`mivi_backend_create`], and is invalid afterwards.
+/// Must not be called from the frame callback.
+#[no_mangle]
+pub unsafe extern "C" fn mivi_backend_destroy(backend: *mut MiviBackend) {
+    if backend.is_null() {
+        return;
+    }
+    let mut backend = Box::from_raw(backend);
+    guarded(|| {
+        backend.stop_callback()?;
+        if let Some(handle) = backend.handle {
+            backend.runtime.block_on(handle.shutdown()).map_err(|e| e.to_string())?;
+        }
+        Ok(MIVI_OK)
+    });
+}
+
+/// Describe the last failure on this thread; valid until the next failing call
+#[no_mangle]
+pub extern "C" fn mivi_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| error.borrow().as_ptr())
+}
+
+/// Library version as a static C string
+#[no_mangle]
+pub extern "C" fn mivi_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_are_reported() {
+        unsafe {
+            assert_eq!(mivi_backend_disconnect(std::ptr::null_mut()), MIVI_ERROR);
+            assert_eq!(CStr::from_ptr(mivi_last_error()).to_str().unwrap(), "backend is null");
+            assert!(mivi_backend_create(std::ptr::null(), std::ptr::null(), 0, 0).is_null());
+            assert_eq!(CStr::from_ptr(mivi_last_error()).to_str().unwrap(), "shm_name is null");
+            mivi_backend_destroy(std::ptr::null_mut());
+        }
+        assert!(unsafe { CStr::from_ptr(mivi_version()) }.to_str().unwrap().starts_with(crate::VERSION));
+    }
+}
@@ -0,0 +1,248 @@
+// src/frontend/access.rs - Operator Login and Role-Based Feature Gating
+
+use std::path::Path;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::encryption::{from_hex, to_hex};
+
+/// Hash rounds, so a stolen users file is slow to brute-force
+const HASH_ROUNDS: u32 = 100_000;
+
+/// Salt bytes per account
+const SALT_LEN: usize = 16;
+
+/// Errors that can occur while loading accounts or signing in
+#[derive(Debug, Error)]
+pub enum AccessError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid users file: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unknown user or wrong password")]
+    InvalidCredentials,
+
+    #[error("{0} requires the {1} role")]
+    Denied(&'static str, &'static str),
+}
+
+/// What a user may do, in increasing order of trust
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Watch the stream and measure on screen
+    Viewer,
+    /// Also export measurements and record session reports
+    Operator,
+    /// Also change settings
+    Admin,
+}
+
+impl Role {
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Viewer => "Viewer",
+            Role::Operator => "Operator",
+            Role::Admin => "Admin",
+        }
+    }
+
+    /// Check if the role grants a permission
+    pub fn allows(self, permission: Permission) -> bool {
+        self >= permission.required_role()
+    }
+}
+
+/// Gated features
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// Export measurements and annotations
+    Export,
+    /// Write session reports with their snapshots
+    Record,
+    /// Open and apply the settings dialog
+    ChangeSettings,
+}
+
+impl Permission {
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Permission::Export => "Exporting",
+            Permission::Record => "Recording",
+            Permission::ChangeSettings => "Changing settings",
+        }
+    }
+
+    /// Lowest role granting the permission
+    pub fn required_role(self) -> Role {
+        match self {
+            Permission::Export | Permission::Record => Role::Operator,
+            Permission::ChangeSettings => Role::Admin,
+        }
+    }
+}
+
+/// The user the viewer currently acts for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveUser {
+    pub name: String,
+    pub role: Role,
+}
+
+impl ActiveUser {
+    /// The operating system user with full access, used when no users file is configured
+    pub fn system() -> Self {
+        let name = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        Self { name, role: Role::Admin }
+    }
+
+    /// Nobody signed in; the stream stays visible but nothing can be exported or changed
+    pub fn signed_out() -> Self {
+        Self { name: String::new(), role: Role::Viewer }
+    }
+
+    /// Check if someone is signed in
+    pub fn is_signed_in(&self) -> bool {
+        !self.name.is_empty()
+    }
+
+    /// Get the name shown in the UI, the audit log and reports
+    pub fn display_name(&self) -> String {
+        if self.is_signed_in() {
+            format!("{} ({})", self.name, self.role.name())
+        } else {
+            "Not signed in".to_string()
+        }
+    }
+
+    /// Check a permission, describing the missing role if denied
+    pub fn require(&self, permission: Permission) -> Result<(), AccessError> {
+        if self.role.allows(permission) {
+            Ok(())
+        } else {
+            Err(AccessError::Denied(permission.name(), permission.required_role().name()))
+        }
+    }
+}
+
+/// One account in the users file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserAccount {
+    pub name: String,
+    pub role: Role,
+    /// Hex salt
+    pub salt: String,
+    /// Hex salted, iterated SHA-256 of the password
+    pub password_hash: String,
+}
+
+impl UserAccount {
+    /// Create an account with a fresh salt
+    pub fn new(name: &str, role: Role, password: &str) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            name: name.to_string(),
+            role,
+            salt: to_hex(&salt),
+            password_hash: hash_password(&salt, password),
+        }
+    }
+
+    /// Check a password against the stored hash
+    pub fn verify(&self, password: &str) -> bool {
+        match from_hex(&self.salt) {
+            Some(salt) => {
+                let hash = hash_password(&salt, password);
+                // Compare without an early exit so timing does not reveal the matching prefix
+                hash.len() == self.password_hash.len()
+                    && hash.bytes().zip(self.password_hash.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+            }
+            None => false,
+        }
+    }
+}
+
+/// Accounts allowed to sign in (--users-file)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserDirectory {
+    pub users: Vec<UserAccount>,
+}
+
+impl UserDirectory {
+    /// Load the users file
+    pub fn load(path: &Path) -> Result<Self, AccessError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Save the users file
+    pub fn save(&self, path: &Path) -> Result<(), AccessError> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Add an account, replacing one with the same name
+    pub fn upsert(&mut self, account: UserAccount) {
+        self.users.retain(|user| user.name != account.name);
+        self.users.push(account);
+    }
+
+    /// Sign a user in
+    pub fn authenticate(&self, name: &str, password: &str) -> Result<ActiveUser, AccessError> {
+        self.users
+            .iter()
+            .find(|user| user.name == name.trim())
+            .filter(|user| user.verify(password))
+            .map(|user| ActiveUser { name: user.name.clone(), role: user.role })
+            .ok_or(AccessError::InvalidCredentials)
+    }
+}
+
+fn hash_password(salt: &[u8], password: &str) -> String {
+    let mut digest = Sha256::new().chain_update(salt).chain_update(password.as_bytes()).finalize();
+    for _ in 1..HASH_ROUNDS {
+        digest = Sha256::new().chain_update(salt).chain_update(digest).finalize();
+    }
+    to_hex(&digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_and_sign_in() {
+        assert!(!Role::Viewer.allows(Permission::Export));
+        assert!(Role::Operator.allows(Permission::Record));
+        assert!(!Role::Operator.allows(Permission::ChangeSettings));
+        assert!(Role::Admin.allows(Permission::ChangeSettings));
+
+        let mut directory = UserDirectory::default();
+        directory.upsert(UserAccount::new("sonographer", Role::Operator, "probe-L12"));
+        let json = serde_json::to_string(&directory).unwrap();
+        assert!(!json.contains("probe-L12"));
+        let directory: UserDirectory = serde_json::from_str(&json).unwrap();
+
+        let user = directory.authenticate("sonographer", "probe-L12").unwrap();
+        assert_eq!(user.role, Role::Operator);
+        assert!(user.require(Permission::Export).is_ok());
+        assert!(matches!(user.require(Permission::ChangeSettings), Err(AccessError::Denied(_, "Admin"))));
+
+        assert!(directory.authenticate("sonographer", "wrong").is_err());
+        assert!(directory.authenticate("nobody", "probe-L12").is_err());
+        assert!(ActiveUser::signed_out().require(Permission::Export).is_err());
+    }
+}
@@ -2,32 +2,59 @@
 
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
-use tokio::sync::{mpsc, broadcast};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
 use tracing::{info, error, warn, debug};
 
+use crate::backend::detections::DetectionOverlays;
 use crate::backend::{
-    MedicalFrameBackend, BackendCommand, BackendEvent, BackendConfig
+    registry, MedicalFrameBackend, BackendCommand, BackendEvent, BackendConfig, DetectionStore, FrameSink, Gamma, GpuFrame, InferenceStatistics, PixelSpacing, ProcessedFrame, RgbaBuffer, Roi, StageKind, WindowLevel, Zoom
 };
 use crate::frontend::{
-    SlintBridge, ImageConverter, UiState, FrontendError
+    SlintBridge, UiState, FrontendError, NotificationLevel, WindowOptions
 };
+use crate::frontend::access::{ActiveUser, Permission, UserDirectory};
+use crate::frontend::audit_log::{AuditAction, AuditLog};
+use crate::frontend::measurement_export::MeasurementReport;
+use crate::frontend::measurements::{MeasurementOverlay, MeasurementShape};
+use crate::frontend::rulers::Rulers;
+use crate::frontend::scripting::{ScriptAction, ScriptEvent, ScriptHost};
+use crate::frontend::session_report::{SessionEventKind, SessionReport};
+use crate::diagnostics::DiagnosticsReport;
+use crate::error::MiViError;
+use crate::perf::PerformanceSnapshot;
+use crate::{BUILD_INFO, LICENSE_INFO, THIRD_PARTY_NOTICE};
 
 /// Internal UI command to avoid sending Slint types across threads
 #[derive(Debug)]
 pub enum UiCommand {
     UpdateFrame {
-        frame_data: Arc<[u8]>,
-        width: u32,
-        height: u32,
+        frame_data: RgbaBuffer,
+        /// Unconverted frame for the GPU upload path (frame_data is then empty)
+        gpu_source: Option<GpuFrame>,
         frame_id: u64,
         sequence_number: u64,
+        /// Producer timestamp, for end-to-end latency
+        timestamp_ns: u64,
         resolution: String,
         format: String,
     },
     UpdateConnectionStatus(String, bool),
-    UpdateStatistics(f64, f64, u64),
+    UpdateStatistics(f64, u64),
+    UpdateFrameLoss { dropped: u64, skipped: u64, corrupted: u64, consumer_dropped: u64 },
+    UpdateJitter(f64, f64),
     ClearFrame,
-    ShowNotification(String, bool),
+    ShowNotification(NotificationLevel, String),
+    /// Show an error with its user message and suggested action
+    ShowError(MiViError),
+    UpdateRoiStatistics(String),
+    UpdateCalibration(Vec<MeasurementOverlay>, String),
+    UpdateRulers(Rulers),
+    UpdateProducerProtocol(String),
+    SetWaitingForProducer(bool),
+    UpdatePerformance(PerformanceSnapshot),
+    UpdateInference(InferenceStatistics),
+    UpdateDetections(DetectionOverlays),
 }
 
 /// Main application frontend that coordinates between Slint UI and backend
@@ -39,7 +66,6 @@ pub struct MedicalFrameApp {
     // UI components
     slint_bridge: Arc<SlintBridge>,
     ui_state: Arc<tokio::sync::RwLock<UiState>>,
-    image_converter: Arc<ImageConverter>,
 
     // Application state
     is_running: Arc<AtomicBool>,
@@ -48,11 +74,39 @@ pub struct MedicalFrameApp {
     // Internal UI communication
     ui_command_tx: mpsc::UnboundedSender<UiCommand>,
     ui_command_rx: Option<mpsc::UnboundedReceiver<UiCommand>>,
+
+    // Actions requested by the automation script
+    script_action_rx: Option<mpsc::UnboundedReceiver<ScriptAction>>,
+}
+
+/// Turns backend frames and events into UI commands
+struct AppSink {
+    detections: Arc<DetectionStore>,
+    ui_state: Arc<tokio::sync::RwLock<UiState>>,
+    ui_command_tx: mpsc::UnboundedSender<UiCommand>,
+    is_running: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl FrameSink for AppSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        MedicalFrameApp::handle_new_frame(frame, &self.detections, &self.ui_state, &self.ui_command_tx).await;
+    }
+
+    async fn on_event(&mut self, event: BackendEvent) {
+        if let Err(e) = MedicalFrameApp::handle_backend_event(event, &self.ui_state, &self.ui_command_tx).await {
+            error!("Error handling backend event: {}", e);
+        }
+    }
+
+    fn keep_running(&self) -> bool {
+        self.is_running.load(std::sync::atomic::Ordering::Relaxed)
+    }
 }
 
 impl MedicalFrameApp {
     /// Create a new medical frame application
-    pub async fn new(backend_config: BackendConfig) -> Result<Self, FrontendError> {
+    pub async fn new(backend_config: BackendConfig, window_options: WindowOptions) -> Result<Self, FrontendError> {
         info!("🏥 Initializing MiVi Medical Frame Application");
 
         // Create backend
@@ -62,6 +116,18 @@ impl MedicalFrameApp {
         // Create UI components
         let slint_bridge = Arc::new(SlintBridge::new()
             .map_err(|e| FrontendError::Slint(e.to_string()))?);
+        slint_bridge.apply_window_options(&window_options);
+        slint_bridge.set_frame_pool(backend.pixel_pool());
+        if let Some(monitor) = backend.performance_monitor() {
+            slint_bridge.set_performance_monitor(monitor);
+        }
+        slint_bridge.set_health_monitor(backend.health_monitor());
+        slint_bridge.set_privacy_pin_required(backend_config.privacy_pin.is_some());
+        if backend_config.gpu_upload {
+            if let Err(e) = slint_bridge.enable_gpu_upload() {
+                warn!("⚠️ {}, converting frames on the CPU", e);
+            }
+        }
 
         // Initialize UI state
         let mut ui_state = UiState::new();
@@ -69,10 +135,49 @@ impl MedicalFrameApp {
         ui_state.format = backend_config.format.clone();
         ui_state.catch_up_mode = backend_config.catch_up;
         ui_state.verbose_logging = backend_config.verbose;
-        ui_state.reconnect_delay_ms = backend_config.reconnect_delay.as_millis() as u64;
+        ui_state.set_reconnect_policy(&backend_config.reconnect);
+        ui_state.width = backend_config.width;
+        ui_state.height = backend_config.height;
+        ui_state.frame_polling = backend_config.frame_polling;
+        ui_state.stats_interval = backend_config.stats_interval;
+        ui_state.frame_drop_policy = backend_config.frame_drop_policy;
+        ui_state.processing_deadline = backend_config.processing_deadline;
+        ui_state.profile_duration = backend_config.profile_duration;
+        ui_state.export_anonymization = backend_config.export_anonymization;
+        ui_state.recording_encryption = backend_config.recording_encryption.clone();
+        ui_state.privacy_pin = backend_config.privacy_pin.clone();
+        if let Some(path) = &backend_config.audit_log {
+            // Traceability is required where it is configured, so a broken log stops startup
+            let log = AuditLog::open(path)
+                .map_err(|e| FrontendError::Other(format!("Failed to open audit log {}: {}", path.display(), e)))?;
+            info!("🧾 Audit log: {:?}", path);
+            ui_state.audit_log = Some(log);
+        }
+        if let Some(path) = &backend_config.users_file {
+            let users = UserDirectory::load(path)
+                .map_err(|e| FrontendError::Other(format!("Failed to load users file {}: {}", path.display(), e)))?;
+            info!("👥 {} user accounts from {:?}; sign-in required", users.users.len(), path);
+            ui_state.users = Some(users);
+            ui_state.active_user = ActiveUser::signed_out();
+        }
+        ui_state.memory = backend_config.memory;
+        ui_state.deinterlace = backend_config.deinterlace;
+        ui_state.apply_device_profile(&backend_config);
+
+        // A requested automation script must load, or the actions it should take would silently not happen
+        let script_action_rx = match &backend_config.script {
+            Some(path) => {
+                let (action_tx, action_rx) = mpsc::unbounded_channel();
+                let host = ScriptHost::start(path, action_tx)
+                    .map_err(|e| FrontendError::Other(format!("Failed to load script {}: {}", path.display(), e)))?;
+                info!("📜 Automation script: {:?}", path);
+                ui_state.scripts = Some(Arc::new(host));
+                Some(action_rx)
+            }
+            None => None,
+        };
 
         let ui_state = Arc::new(tokio::sync::RwLock::new(ui_state));
-        let image_converter = Arc::new(ImageConverter::new());
 
         // Settings path
         let settings_path = Self::get_settings_path();
@@ -85,16 +190,35 @@ impl MedicalFrameApp {
             command_sender,
             slint_bridge,
             ui_state,
-            image_converter,
             is_running: Arc::new(AtomicBool::new(false)),
             settings_path,
             ui_command_tx,
             ui_command_rx: Some(ui_command_rx),
+            script_action_rx,
         };
 
         // Load saved settings
         app.load_settings().await?;
 
+        // An explicit device profile takes precedence over saved format and catch-up settings
+        if backend_config.device_profile.is_some() {
+            let mut state = app.ui_state.write().await;
+            state.format = backend_config.format.clone();
+            state.catch_up_mode = backend_config.catch_up;
+        }
+
+        // Adaptive catch-up requested on the command line overrides the saved policy
+        if let Some(policy) = backend_config.adaptive_catch_up {
+            let mut state = app.ui_state.write().await;
+            state.adaptive_catch_up = true;
+            state.catch_up_backlog = policy.skip_above;
+        }
+
+        // A display FPS cap given on the command line overrides the saved one
+        if backend_config.max_display_fps > 0 {
+            app.ui_state.write().await.max_display_fps = backend_config.max_display_fps;
+        }
+
         info!("✅ MiVi Medical Frame Application initialized");
         Ok(app)
     }
@@ -107,8 +231,9 @@ impl MedicalFrameApp {
         self.is_running.store(true, std::sync::atomic::Ordering::Relaxed);
 
         // Start the backend
-        self.backend.start().await
+        let backend_handle = self.backend.start().await
             .map_err(|e| FrontendError::Backend(e.to_string()))?;
+        self.ui_state.read().await.audit(AuditAction::ApplicationStarted, format!("MiVi {}", crate::VERSION));
 
         // Setup UI event handlers
         self.setup_ui_handlers().await?;
@@ -119,13 +244,15 @@ impl MedicalFrameApp {
         // Start periodic tasks
         let periodic_task = self.start_periodic_tasks().await;
 
+        // Carry out what the automation script asks for
+        let script_task = self.script_action_rx.take().map(|actions| self.start_script_actions(actions));
+
         // Take the UI command receiver
         let mut ui_command_rx = self.ui_command_rx.take()
             .ok_or(FrontendError::Other("Application already started".to_string()))?;
 
         // Start UI command processing in main thread
         let slint_bridge_for_ui = Arc::clone(&self.slint_bridge);
-        let image_converter_for_ui = Arc::clone(&self.image_converter);
         let is_running_for_ui = Arc::clone(&self.is_running);
 
         let ui_task = tokio::spawn(async move {
@@ -134,7 +261,7 @@ impl MedicalFrameApp {
                     break;
                 }
 
-                if let Err(e) = Self::handle_ui_command(cmd, &slint_bridge_for_ui, &image_converter_for_ui).await {
+                if let Err(e) = Self::handle_ui_command(cmd, &slint_bridge_for_ui).await {
                     error!("Failed to handle UI command: {}", e);
                 }
             }
@@ -154,125 +281,948 @@ impl MedicalFrameApp {
         event_task.abort();
         periodic_task.abort();
         ui_task.abort();
+        if let Some(script_task) = script_task {
+            script_task.abort();
+        }
+
+        // Release the source and stop the frame loop
+        if let Err(e) = backend_handle.shutdown().await {
+            warn!("Backend shutdown failed: {}", e);
+        }
+
+        // Write the session summary before exit if requested
+        if self.ui_state.read().await.report_on_exit {
+            match Self::write_session_report(&self.ui_state).await {
+                Ok(path) => {
+                    info!("📄 Session report written to {:?}", path);
+                    self.ui_state.read().await.audit(AuditAction::ReportWritten, path.display().to_string());
+                }
+                Err(e) => warn!("Failed to write session report: {}", e),
+            }
+        }
 
         // Save settings before exit
         if let Err(e) = self.save_settings().await {
             warn!("Failed to save settings: {}", e);
         }
 
-        info!("✅ MiVi Medical Frame Application stopped");
-        ui_result.map_err(|e| FrontendError::Slint(e.to_string()))
-    }
+        self.ui_state.read().await.audit(AuditAction::ApplicationStopped, "");
+        info!("✅ MiVi Medical Frame Application stopped");
+        ui_result.map_err(|e| FrontendError::Slint(e.to_string()))
+    }
+
+    /// Handle UI commands on the main thread
+    async fn handle_ui_command(
+        command: UiCommand,
+        slint_bridge: &Arc<SlintBridge>,
+    ) -> Result<(), FrontendError> {
+        match command {
+            UiCommand::UpdateFrame { frame_data, gpu_source, frame_id, sequence_number, timestamp_ns, resolution, format } => {
+                // The pooled buffer becomes the displayed image as-is
+                slint_bridge.update_frame(
+                    frame_data,
+                    gpu_source,
+                    &resolution,
+                    &format,
+                    frame_id as i32,
+                    sequence_number as i32,
+                    timestamp_ns,
+                ).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateConnectionStatus(status, connected) => {
+                slint_bridge.update_connection_status(&status, connected).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateStatistics(fps, total_frames) => {
+                slint_bridge.update_statistics(fps as f32, total_frames as i32).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateFrameLoss { dropped, skipped, corrupted, consumer_dropped } => {
+                slint_bridge.set_frame_loss(dropped as i32, skipped as i32, corrupted as i32, consumer_dropped as i32).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdatePerformance(snapshot) => {
+                slint_bridge.set_performance(snapshot).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateInference(stats) => {
+                slint_bridge.set_inference(stats).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateDetections(overlays) => {
+                slint_bridge.set_detections(overlays).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateJitter(jitter, max_jitter) => {
+                slint_bridge.set_jitter(jitter as f32, max_jitter as f32).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::ClearFrame => {
+                slint_bridge.clear_frame().await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::ShowNotification(level, message) => {
+                slint_bridge.notify(level, &message).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::ShowError(error) => {
+                slint_bridge.report_error(&error).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateRoiStatistics(summary) => {
+                slint_bridge.set_roi_statistics(summary).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateRulers(rulers) => {
+                slint_bridge.set_rulers(rulers).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateCalibration(measurements, status) => {
+                slint_bridge.set_measurements(measurements).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+                slint_bridge.set_calibration_status(status).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::UpdateProducerProtocol(protocol) => {
+                slint_bridge.set_producer_protocol(protocol).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+            UiCommand::SetWaitingForProducer(waiting) => {
+                slint_bridge.set_waiting_for_producer(waiting).await
+                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Setup UI event handlers
+    async fn setup_ui_handlers(&self) -> Result<(), FrontendError> {
+        info!("⚙️ Setting up UI event handlers");
+
+        // Reconnect button handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_reconnect_clicked(move || {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    info!("🔄 Reconnect button clicked");
+
+                    let (shm_name, config) = {
+                        let mut state = ui_state.write().await;
+                        state.mark_connection_attempt();
+                        let config = state.get_backend_config();
+                        (state.shm_name.clone(), config)
+                    };
+
+                    if let Err(e) = command_sender.send(BackendCommand::Connect { shm_name, config }) {
+                        error!("Failed to send connect command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Catch-up mode toggle handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_toggle_catch_up(move |enabled| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    info!("⚙️ Catch-up mode toggled: {}", enabled);
+
+                    // Update UI state
+                    {
+                        let mut state = ui_state.write().await;
+                        state.catch_up_mode = enabled;
+                    }
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetCatchUpMode(enabled)) {
+                        error!("Failed to send catch-up mode command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Window/level adjustment handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_window_level_changed(move |center, width| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    let window_level = WindowLevel::new(center, width);
+                    debug!("🎚️ Window/level changed: center {:.3}, width {:.3}", window_level.center, window_level.width);
+
+                    ui_state.write().await.set_window_level(window_level);
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetWindowLevel(window_level)) {
+                        error!("Failed to send window/level command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Gamma adjustment handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_gamma_changed(move |gamma| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    let gamma = Gamma::new(gamma);
+                    debug!("🌗 Gamma changed: {:.2}", gamma.0);
+
+                    ui_state.write().await.set_gamma(gamma);
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetGamma(gamma)) {
+                        error!("Failed to send gamma command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Colormap selection handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_colormap_changed(move |colormap| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    info!("🌈 Colormap selected: {}", colormap.name());
+
+                    ui_state.write().await.colormap = colormap;
+
+                    if let Err(e) = slint_bridge.set_colormap(colormap).await {
+                        error!("Failed to update colormap legend: {}", e);
+                    }
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetColormap(colormap)) {
+                        error!("Failed to send colormap command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // CLAHE handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_clahe_changed(move |enabled, clip_limit| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    debug!("📈 CLAHE changed: enabled {}, clip limit {:.2}", enabled, clip_limit);
+
+                    let params = {
+                        let mut state = ui_state.write().await;
+                        state.clahe_enabled = enabled;
+                        state.clahe_clip_limit = clip_limit;
+                        state.clahe_params()
+                    };
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetClahe(params)) {
+                        error!("Failed to send CLAHE command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Rotate/flip handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_orientation_changed(move |orientation| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    debug!("🔄 Orientation changed: {:?}", orientation);
+                    {
+                        let mut state = ui_state.write().await;
+                        state.orientation = orientation;
+                        // Measurements refer to the previous display geometry (the backend drops the ROI)
+                        state.measurements.clear();
+                        state.annotations.clear();
+                        state.roi = None;
+                    }
+
+                    if let Err(e) = slint_bridge.set_roi(None).await {
+                        error!("Failed to update ROI: {}", e);
+                    }
+
+                    if let Err(e) = slint_bridge.set_measurements(Vec::new()).await {
+                        error!("Failed to update measurements: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_annotations(Vec::new()).await {
+                        error!("Failed to update annotations: {}", e);
+                    }
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetOrientation(orientation)) {
+                        error!("Failed to send orientation command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Crop region handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_crop_changed(move |selection| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    debug!("✂️ Crop selection: {:?}", selection);
+                    {
+                        let mut state = ui_state.write().await;
+                        state.crop_active = selection.is_some();
+                        // The backend drops the zoom along with the view it referred to
+                        state.zoom = Zoom::default();
+                        state.measurements.clear();
+                        state.annotations.clear();
+                        state.roi = None;
+                    }
+
+                    if let Err(e) = slint_bridge.set_roi(None).await {
+                        error!("Failed to update ROI: {}", e);
+                    }
+
+                    if let Err(e) = slint_bridge.set_zoom(Zoom::default()).await {
+                        error!("Failed to update zoom indicator: {}", e);
+                    }
+
+                    if let Err(e) = slint_bridge.set_measurements(Vec::new()).await {
+                        error!("Failed to update measurements: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_annotations(Vec::new()).await {
+                        error!("Failed to update annotations: {}", e);
+                    }
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetCropRegion(selection)) {
+                        error!("Failed to send crop command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Digital zoom handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_zoom_changed(move |action| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let (zoom, measurements, annotations, roi, rulers, detections) = {
+                        let mut state = ui_state.write().await;
+                        let zoom = action.apply(&state.zoom);
+                        if zoom == state.zoom {
+                            return;
+                        }
+                        state.zoom = zoom;
+                        let roi = state.roi.map(|roi| (roi.shape, zoom.rect_in_view(&roi.bounds)));
+                        let overlays = state.measurements.overlays(&zoom, &state.calibration());
+                        let detections = state.detections.is_some().then(|| state.detection_overlays());
+                        (zoom, overlays, state.annotations.overlays(&zoom), roi, state.rulers(), detections)
+                    };
+                    debug!("🔍 Zoom {:?}: {:.2}x", action, zoom.factor);
+
+                    if let Err(e) = slint_bridge.set_zoom(zoom).await {
+                        error!("Failed to update zoom indicator: {}", e);
+                    }
+
+                    if let Err(e) = slint_bridge.set_measurements(measurements).await {
+                        error!("Failed to update measurements: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_annotations(annotations).await {
+                        error!("Failed to update annotations: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_rulers(rulers).await {
+                        error!("Failed to update rulers: {}", e);
+                    }
+                    if let Some(detections) = detections {
+                        if let Err(e) = slint_bridge.set_detections(detections).await {
+                            error!("Failed to update detections: {}", e);
+                        }
+                    }
+                    if roi.is_some() {
+                        if let Err(e) = slint_bridge.set_roi(roi).await {
+                            error!("Failed to update ROI: {}", e);
+                        }
+                    }
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetZoom(zoom)) {
+                        error!("Failed to send zoom command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Zoom quality handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_zoom_quality_changed(move |quality| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    info!("🔍 Zoom quality: {}", quality.name());
+                    ui_state.write().await.zoom_quality = quality;
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetResampleQuality(quality)) {
+                        error!("Failed to send zoom quality command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Measurement handler (distance, angle and ellipse)
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_measurement_added(move |shape, width, height, frame_id| {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let overlays = {
+                        let mut state = ui_state.write().await;
+                        let zoom = state.zoom;
+                        // Store in frame coordinates so the measurement follows zoom and pan
+                        let shape = shape.to_frame(&zoom);
+                        let calibration = state.calibration();
+                        let measurement = state.measurements.add(shape, width, height, frame_id);
+                        state.session_log.capture_snapshot();
+                        info!("📏 {} on frame {}: {}", measurement.shape.name(), frame_id, measurement.label(&calibration));
+                        state.measurements.overlays(&zoom, &calibration)
+                    };
+
+                    if let Err(e) = slint_bridge.set_measurements(overlays).await {
+                        error!("Failed to update measurements: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Two-point calibration handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_calibration_measured(move |shape, width, height, known_mm| {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let (overlays, status, rulers) = {
+                        let mut state = ui_state.write().await;
+                        let zoom = state.zoom;
+                        let MeasurementShape::Distance { start, end } = shape.to_frame(&zoom) else {
+                            return;
+                        };
+                        let dx = (end.x - start.x) * width as f32;
+                        let dy = (end.y - start.y) * height as f32;
+
+                        let Some(spacing) = PixelSpacing::isotropic(known_mm / (dx * dx + dy * dy).sqrt()) else {
+                            warn!("📐 Ignoring degenerate calibration line");
+                            return;
+                        };
+                        state.manual_spacing = Some(spacing);
+                        let calibration = state.calibration();
+                        info!("📐 Manual calibration: {}", calibration.describe());
+                        state.audit(AuditAction::CalibrationChanged, calibration.describe());
+                        (state.measurements.overlays(&zoom, &calibration), calibration.describe(), state.rulers())
+                    };
+
+                    if let Err(e) = slint_bridge.set_measurements(overlays).await {
+                        error!("Failed to update measurements: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_calibration_status(status).await {
+                        error!("Failed to update calibration status: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_rulers(rulers).await {
+                        error!("Failed to update rulers: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Calibration reset handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_calibration_reset(move || {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let (overlays, status, rulers) = {
+                        let mut state = ui_state.write().await;
+                        state.manual_spacing = None;
+                        let calibration = state.calibration();
+                        info!("📐 Calibration reset: {}", calibration.describe());
+                        state.audit(AuditAction::CalibrationChanged, format!("reset to {}", calibration.describe()));
+                        (state.measurements.overlays(&state.zoom, &calibration), calibration.describe(), state.rulers())
+                    };
+
+                    if let Err(e) = slint_bridge.set_measurements(overlays).await {
+                        error!("Failed to update measurements: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_calibration_status(status).await {
+                        error!("Failed to update calibration status: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_rulers(rulers).await {
+                        error!("Failed to update rulers: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Rulers and grid visibility handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_rulers_toggled(move |rulers, grid| {
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    debug!("📐 Rulers: {}, grid: {}", rulers, grid);
+                    let mut state = ui_state.write().await;
+                    state.show_rulers = rulers;
+                    state.show_grid = grid;
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Measurement export handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_measurements_exported(move |format| {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    if !Self::permitted(&ui_state, &slint_bridge, Permission::Export).await {
+                        return;
+                    }
+
+                    let (report, export_dir) = {
+                        let state = ui_state.read().await;
+                        let report = MeasurementReport::new(&state.measurements, &state.annotations, &state.calibration())
+                            .anonymize(state.export_anonymization);
+                        (report, std::path::PathBuf::from(&state.export_dir))
+                    };
+
+                    if report.is_empty() {
+                        if let Err(e) = slint_bridge.notify(NotificationLevel::Warning, "No measurements to export").await {
+                            error!("Failed to show notification: {}", e);
+                        }
+                        return;
+                    }
+
+                    let path = export_dir.join(format!(
+                        "measurements_{}.{}",
+                        chrono::Local::now().format("%Y%m%d_%H%M%S"),
+                        format.extension()
+                    ));
+                    let result = match report.render(format) {
+                        Ok(content) => match tokio::fs::create_dir_all(&export_dir).await {
+                            Ok(()) => tokio::fs::write(&path, content).await.map_err(|e| e.to_string()),
+                            Err(e) => Err(format!("Failed to create export directory: {}", e)),
+                        },
+                        Err(e) => Err(format!("Failed to serialize measurements: {}", e)),
+                    };
+
+                    let notification = match result {
+                        Ok(()) => {
+                            info!("📤 Exported {} measurement records to {:?}", report.records.len(), path);
+                            ui_state.read().await.audit(
+                                AuditAction::MeasurementsExported,
+                                format!("{} records to {}", report.records.len(), path.display()),
+                            );
+                            slint_bridge.notify(NotificationLevel::Info, &format!("Measurements exported to {}", path.display())).await
+                        }
+                        Err(e) => {
+                            warn!("Failed to export measurements: {}", e);
+                            slint_bridge.notify(NotificationLevel::Error, &format!("Measurement export failed: {}", e)).await
+                        }
+                    };
+                    if let Err(e) = notification {
+                        error!("Failed to show notification: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Sign-in dialog handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_sign_in_requested(move |user, password| {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let Some(users) = ui_state.read().await.users.clone() else {
+                        return;
+                    };
+                    let attempted = user.trim().to_string();
+
+                    // Password hashing is deliberately slow, so keep it off the runtime threads
+                    let result = tokio::task::spawn_blocking(move || users.authenticate(&user, &password))
+                        .await
+                        .map_err(|e| e.to_string())
+                        .and_then(|result| result.map_err(|e| e.to_string()));
+
+                    let mut state = ui_state.write().await;
+                    let update = match result {
+                        Ok(active_user) => {
+                            info!("🔑 {} signed in as {}", active_user.name, active_user.role.name());
+                            state.sign_in(active_user);
+                            slint_bridge.set_active_user(&state.active_user, true).await
+                        }
+                        Err(e) => {
+                            warn!("🔑 Sign-in failed for {:?}: {}", attempted, e);
+                            state.audit(AuditAction::SignInFailed, attempted);
+                            slint_bridge.open_login(&e).await
+                        }
+                    };
+                    if let Err(e) = update {
+                        error!("Failed to update sign-in state: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Sign-out handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_sign_out_requested(move || {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let mut state = ui_state.write().await;
+                    info!("🔑 {} signed out", state.active_user.name);
+                    state.sign_out();
+
+                    if let Err(e) = slint_bridge.set_active_user(&state.active_user, true).await {
+                        error!("Failed to update sign-in state: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.open_login("").await {
+                        error!("Failed to open sign-in dialog: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Privacy mode handlers
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_privacy_lock_requested(move || {
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    info!("🔒 Display hidden (privacy mode)");
+                    ui_state.read().await.audit(AuditAction::PrivacyLocked, "");
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_privacy_unlock_requested(move |pin| {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let update = if ui_state.read().await.unlock_privacy(&pin) {
+                        info!("🔓 Display resumed");
+                        slint_bridge.set_privacy_locked(false, "").await
+                    } else {
+                        warn!("🔒 Wrong privacy PIN entered");
+                        slint_bridge.set_privacy_locked(true, "Wrong PIN").await
+                    };
+                    if let Err(e) = update {
+                        error!("Failed to update privacy mode: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Session report handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_report_requested(move || {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    if !Self::permitted(&ui_state, &slint_bridge, Permission::Record).await {
+                        return;
+                    }
+
+                    let notification = match Self::write_session_report(&ui_state).await {
+                        Ok(path) => {
+                            info!("📄 Session report written to {:?}", path);
+                            ui_state.read().await.audit(AuditAction::ReportWritten, path.display().to_string());
+                            slint_bridge.notify(NotificationLevel::Info, &format!("Session report written to {}", path.display())).await
+                        }
+                        Err(e) => {
+                            warn!("Failed to write session report: {}", e);
+                            slint_bridge.notify(NotificationLevel::Error, &format!("Session report failed: {}", e)).await
+                        }
+                    };
+                    if let Err(e) = notification {
+                        error!("Failed to show notification: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // CPU profile handler (F9)
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_profile_requested(move || {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let (duration, export_dir) = {
+                        let state = ui_state.read().await;
+                        (state.profile_duration, std::path::PathBuf::from(&state.export_dir))
+                    };
+
+                    if crate::profiler::is_available() {
+                        info!("🔥 Sampling CPU for {}s", duration.as_secs());
+                        if let Err(e) = slint_bridge.notify(NotificationLevel::Info, &format!("Profiling CPU for {}s", duration.as_secs())).await {
+                            error!("Failed to show notification: {}", e);
+                        }
+                    }
+
+                    let notification = match crate::profiler::capture_flamegraph(duration, &export_dir).await {
+                        Ok(path) => {
+                            info!("🔥 Flamegraph written to {:?}", path);
+                            ui_state.read().await.audit(AuditAction::ProfileCaptured, path.display().to_string());
+                            slint_bridge.notify(NotificationLevel::Info, &format!("Flamegraph written to {}", path.display())).await
+                        }
+                        Err(e) => {
+                            warn!("CPU profile failed: {}", e);
+                            slint_bridge.notify(NotificationLevel::Error, &format!("CPU profile failed: {}", e)).await
+                        }
+                    };
+                    if let Err(e) = notification {
+                        error!("Failed to show notification: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Clear measurements handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_measurements_cleared(move || {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    info!("📏 Clearing measurements and annotations");
+                    {
+                        let mut state = ui_state.write().await;
+                        state.measurements.clear();
+                        state.annotations.clear();
+                        state.audit(AuditAction::MeasurementsCleared, "measurements and annotations");
+                    }
+
+                    if let Err(e) = slint_bridge.set_measurements(Vec::new()).await {
+                        error!("Failed to update measurements: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_annotations(Vec::new()).await {
+                        error!("Failed to update annotations: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // ROI handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_roi_changed(move |selection| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    let (roi, outline) = {
+                        let mut state = ui_state.write().await;
+                        let zoom = state.zoom;
+                        // Bounds arrive in view coordinates and are stored in frame coordinates
+                        let roi = selection.map(|roi| Roi::new(roi.shape, zoom.view_rect().sub_rect(&roi.bounds)));
+                        state.roi = roi;
+                        (roi, roi.map(|roi| (roi.shape, zoom.rect_in_view(&roi.bounds))))
+                    };
+                    debug!("🔲 ROI selection: {:?}", roi);
+
+                    if let Err(e) = slint_bridge.set_roi(outline).await {
+                        error!("Failed to update ROI: {}", e);
+                    }
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetRoi(roi)) {
+                        error!("Failed to send ROI command: {}", e);
+                    }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Annotation handler
+        {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_annotation_changed(move |action| {
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
 
-    /// Handle UI commands on the main thread
-    async fn handle_ui_command(
-        command: UiCommand,
-        slint_bridge: &Arc<SlintBridge>,
-        image_converter: &Arc<ImageConverter>,
-    ) -> Result<(), FrontendError> {
-        match command {
-            UiCommand::UpdateFrame { frame_data, width, height, frame_id, sequence_number, resolution, format } => {
-                // Convert frame data to Slint image on main thread
-                match image_converter.create_slint_image_from_rgba(&frame_data, width, height) {
-                    Ok(slint_image) => {
-                        slint_bridge.update_frame(
-                            slint_image,
-                            &resolution,
-                            &format,
-                            frame_id as i32,
-                            sequence_number as i32,
-                        ).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
-                    }
-                    Err(e) => {
-                        error!("Failed to convert frame to Slint image: {}", e);
-                        // Show error image
-                        match image_converter.create_error_image(width, height, &e.to_string()).await {
-                            Ok(error_image) => {
-                                slint_bridge.update_frame(
-                                    error_image,
-                                    &resolution,
-                                    "Error",
-                                    frame_id as i32,
-                                    sequence_number as i32,
-                                ).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
-                            }
-                            Err(ie) => {
-                                error!("Failed to create error image: {}", ie);
-                            }
+                tokio::spawn(async move {
+                    debug!("🏷️ Annotation: {:?}", action);
+                    let overlays = {
+                        let mut state = ui_state.write().await;
+                        let zoom = state.zoom;
+                        // Positions arrive in view coordinates and are stored in frame coordinates
+                        if !state.annotations.apply(action, &zoom) {
+                            return;
                         }
+                        state.session_log.capture_snapshot();
+                        state.annotations.overlays(&zoom)
+                    };
+
+                    if let Err(e) = slint_bridge.set_annotations(overlays).await {
+                        error!("Failed to update annotations: {}", e);
                     }
-                }
-            }
-            UiCommand::UpdateConnectionStatus(status, connected) => {
-                slint_bridge.update_connection_status(&status, connected).await
-                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
-            }
-            UiCommand::UpdateStatistics(fps, latency, total_frames) => {
-                slint_bridge.update_statistics(fps as f32, latency as f32, total_frames as i32).await
-                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
-            }
-            UiCommand::ClearFrame => {
-                slint_bridge.clear_frame().await
-                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
-            }
-            UiCommand::ShowNotification(message, is_error) => {
-                slint_bridge.show_notification(&message, is_error).await
-                    .map_err(|e| FrontendError::Ui(e.to_string()))?;
-            }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
         }
-        Ok(())
-    }
-
-    /// Setup UI event handlers
-    async fn setup_ui_handlers(&self) -> Result<(), FrontendError> {
-        info!("⚙️ Setting up UI event handlers");
 
-        // Reconnect button handler
+        // Persistence handler
         {
             let command_sender = self.command_sender.clone();
             let ui_state = Arc::clone(&self.ui_state);
 
-            self.slint_bridge.on_reconnect_clicked(move || {
+            self.slint_bridge.on_persistence_changed(move |frames| {
                 let command_sender = command_sender.clone();
                 let ui_state = Arc::clone(&ui_state);
 
                 tokio::spawn(async move {
-                    info!("🔄 Reconnect button clicked");
-
-                    let (shm_name, config) = {
-                        let mut state = ui_state.write().await;
-                        state.mark_connection_attempt();
-                        let config = state.get_backend_config();
-                        (state.shm_name.clone(), config)
-                    };
+                    debug!("🎞️ Persistence: {} frames", frames);
+                    ui_state.write().await.persistence_frames = frames;
 
-                    if let Err(e) = command_sender.send(BackendCommand::Connect { shm_name, config }) {
-                        error!("Failed to send connect command: {}", e);
+                    if let Err(e) = command_sender.send(BackendCommand::SetStageStrength(StageKind::Temporal, frames as f32)) {
+                        error!("Failed to send persistence command: {}", e);
                     }
                 });
             }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
         }
 
-        // Catch-up mode toggle handler
+        // Speckle reduction handler
         {
             let command_sender = self.command_sender.clone();
             let ui_state = Arc::clone(&self.ui_state);
 
-            self.slint_bridge.on_toggle_catch_up(move |enabled| {
+            self.slint_bridge.on_speckle_changed(move |strength| {
                 let command_sender = command_sender.clone();
                 let ui_state = Arc::clone(&ui_state);
 
                 tokio::spawn(async move {
-                    info!("⚙️ Catch-up mode toggled: {}", enabled);
+                    debug!("🔊 Speckle reduction: {:.2}", strength);
+                    ui_state.write().await.speckle_strength = strength;
 
-                    // Update UI state
-                    {
-                        let mut state = ui_state.write().await;
-                        state.catch_up_mode = enabled;
+                    if let Err(e) = command_sender.send(BackendCommand::SetStageStrength(StageKind::Speckle, strength)) {
+                        error!("Failed to send speckle reduction command: {}", e);
                     }
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
 
-                    if let Err(e) = command_sender.send(BackendCommand::SetCatchUpMode(enabled)) {
-                        error!("Failed to send catch-up mode command: {}", e);
+        // Segmentation overlay opacity handler
+        {
+            let command_sender = self.command_sender.clone();
+
+            self.slint_bridge.on_overlay_opacity_changed(move |opacity| {
+                debug!("🧠 Segmentation overlay opacity: {:.2}", opacity);
+                if let Err(e) = command_sender.send(BackendCommand::SetOverlayOpacity(opacity)) {
+                    error!("Failed to send overlay opacity command: {}", e);
+                }
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Edge enhancement handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+
+            self.slint_bridge.on_edge_enhancement_changed(move |amount| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+
+                tokio::spawn(async move {
+                    debug!("🩻 Edge enhancement: {:.2}", amount);
+                    ui_state.write().await.edge_enhancement = amount;
+
+                    if let Err(e) = command_sender.send(BackendCommand::SetStageStrength(StageKind::EdgeEnhance, amount)) {
+                        error!("Failed to send edge enhancement command: {}", e);
                     }
                 });
             }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
@@ -281,36 +1231,151 @@ impl MedicalFrameApp {
         // Settings button handler
         {
             let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
 
             self.slint_bridge.on_settings_clicked(move || {
                 let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
 
                 tokio::spawn(async move {
                     info!("⚙️ Settings button clicked");
+                    if !Self::permitted(&ui_state, &slint_bridge, Permission::ChangeSettings).await {
+                        return;
+                    }
 
-                    // For now, just log current settings
-                    // In a full implementation, you'd open a settings dialog
-                    let state = ui_state.read().await;
-                    info!("Current settings:");
-                    info!("  SHM Name: {}", state.shm_name);
-                    info!("  Format: {}", state.format);
-                    info!("  Catch-up: {}", state.catch_up_mode);
-                    info!("  Verbose: {}", state.verbose_logging);
-                    info!("  Auto-reconnect: {}", state.auto_reconnect);
+                    let settings = ui_state.read().await.settings();
+                    if let Err(e) = slint_bridge.open_settings(settings).await {
+                        error!("Failed to open settings dialog: {}", e);
+                    }
+                    Self::refresh_discovered_streams(&slint_bridge).await;
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Stream discovery refresh in the settings dialog
+        {
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
+            self.slint_bridge.on_discover_streams(move || {
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    Self::refresh_discovered_streams(&slint_bridge).await;
+                });
+            }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Settings dialog apply handler
+        {
+            let command_sender = self.command_sender.clone();
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+            let settings_path = self.settings_path.clone();
+
+            self.slint_bridge.on_settings_applied(move |settings| {
+                let command_sender = command_sender.clone();
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+                let settings_path = settings_path.clone();
+
+                tokio::spawn(async move {
+                    info!("⚙️ Applying settings: {:?}", settings);
+                    if !Self::permitted(&ui_state, &slint_bridge, Permission::ChangeSettings).await {
+                        return;
+                    }
+
+                    let (reconnect_required, shm_name, config, state_snapshot) = {
+                        let mut state = ui_state.write().await;
+                        let reconnect_required = state.apply_settings(settings);
+                        if reconnect_required {
+                            state.mark_connection_attempt();
+                        }
+                        let detail = format!("source {}, format {}{}", state.shm_name, state.format,
+                                             if reconnect_required { ", reconnecting" } else { "" });
+                        state.audit(AuditAction::SettingsChanged, detail);
+                        (reconnect_required, state.shm_name.clone(), state.get_backend_config(), state.clone())
+                    };
+
+                    // Reflect the new settings in the UI
+                    if let Err(e) = slint_bridge.update_config(&state_snapshot.shm_name, &state_snapshot.format).await {
+                        error!("Failed to update UI config: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_catch_up_mode(state_snapshot.catch_up_mode).await {
+                        error!("Failed to update catch-up mode: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_show_overlays(state_snapshot.show_overlays, state_snapshot.hud_corner).await {
+                        error!("Failed to update overlays: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_theme(state_snapshot.theme).await {
+                        error!("Failed to update theme: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_reconnect_policy(state_snapshot.reconnect_summary()).await {
+                        error!("Failed to update reconnect policy: {}", e);
+                    }
+                    let measurements = state_snapshot.measurements.overlays(&state_snapshot.zoom, &state_snapshot.calibration());
+                    if let Err(e) = slint_bridge.set_measurements(measurements).await {
+                        error!("Failed to update measurements: {}", e);
+                    }
+                    if let Err(e) = slint_bridge.set_rulers(state_snapshot.rulers()).await {
+                        error!("Failed to update rulers: {}", e);
+                    }
+
+                    // Push the new configuration (including catch-up mode) to the backend
+                    let command = if reconnect_required {
+                        BackendCommand::Connect { shm_name, config }
+                    } else {
+                        BackendCommand::UpdateConfig(config)
+                    };
+                    if let Err(e) = command_sender.send(command) {
+                        error!("Failed to send configuration to backend: {}", e);
+                    }
+
+                    // Persist immediately so settings survive a crash
+                    let notification = match Self::write_settings(&settings_path, &state_snapshot).await {
+                        Ok(()) => slint_bridge.notify(NotificationLevel::Info, "Settings applied and saved").await,
+                        Err(e) => {
+                            warn!("Failed to save settings: {}", e);
+                            slint_bridge.report_error(&MiViError::from(e).with_context("Saving settings")).await
+                        }
+                    };
+                    if let Err(e) = notification {
+                        error!("Failed to show notification: {}", e);
+                    }
                 });
             }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
         }
 
         // About button handler
         {
+            let ui_state = Arc::clone(&self.ui_state);
+            let slint_bridge = Arc::clone(&self.slint_bridge);
+
             self.slint_bridge.on_about_clicked(move || {
-                info!("ℹ️ About button clicked");
+                let ui_state = Arc::clone(&ui_state);
+                let slint_bridge = Arc::clone(&slint_bridge);
+
+                tokio::spawn(async move {
+                    info!("ℹ️ About button clicked");
+
+                    let connection = {
+                        let state = ui_state.read().await;
+                        format!("{} [{}] - {}", state.shm_name, state.format, state.get_status_summary())
+                    };
 
-                // Show about information
-                info!("MiVi - Medical Imaging Virtual Intelligence");
-                info!("Version: 0.2.0");
-                info!("Professional real-time DICOM frame viewer");
-                info!("Built with Rust and Slint UI framework");
+                    // Collecting diagnostics touches sysfs, keep it off the async workers
+                    let diagnostics = tokio::task::spawn_blocking(move || {
+                        DiagnosticsReport::collect(connection).to_text()
+                    }).await.unwrap_or_else(|e| format!("Diagnostics unavailable: {}", e));
+
+                    if let Err(e) = slint_bridge.open_about(
+                        &BUILD_INFO.formatted(),
+                        LICENSE_INFO,
+                        THIRD_PARTY_NOTICE,
+                        &diagnostics,
+                    ).await {
+                        error!("Failed to open about dialog: {}", e);
+                    }
+                });
             }).await.map_err(|e| FrontendError::Ui(e.to_string()))?;
         }
 
@@ -320,38 +1385,87 @@ impl MedicalFrameApp {
 
     /// Start event processing from backend
     async fn start_event_processing(&self) -> tokio::task::JoinHandle<()> {
-        let mut event_receiver = self.backend.get_event_receiver();
-        let ui_state = Arc::clone(&self.ui_state);
-        let ui_command_tx = self.ui_command_tx.clone();
-        let is_running = Arc::clone(&self.is_running);
+        info!("🔄 Starting backend event processing loop");
+        self.backend.spawn_sink(AppSink {
+            detections: self.backend.detections(),
+            ui_state: Arc::clone(&self.ui_state),
+            ui_command_tx: self.ui_command_tx.clone(),
+            is_running: Arc::clone(&self.is_running),
+        })
+    }
 
-        tokio::spawn(async move {
-            info!("🔄 Starting backend event processing loop");
+    /// Show a frame from the backend's latest-frame channel
+    async fn handle_new_frame(
+        processed_frame: ProcessedFrame,
+        detections: &DetectionStore,
+        ui_state: &Arc<tokio::sync::RwLock<UiState>>,
+        ui_command_tx: &mpsc::UnboundedSender<UiCommand>,
+    ) {
+        // Update UI state
+        let (calibration_update, rulers_update, detections_update) = {
+            let mut state = ui_state.write().await;
+            state.update_frame_info(
+                processed_frame.header.frame_id,
+                processed_frame.header.sequence_number,
+                processed_frame.resolution_string(),
+                processed_frame.format_string(),
+            );
+
+            state.session_log.set_current_frame(&processed_frame);
+            state.script_event(ScriptEvent::Frame(processed_frame.header.frame_id));
+
+            let size_changed = (state.frame_width, state.frame_height)
+                != (processed_frame.header.width, processed_frame.header.height);
+            state.frame_width = processed_frame.header.width;
+            state.frame_height = processed_frame.header.height;
+
+            // Relabel measurements when the metadata spacing changes
+            let calibration_update = if state.metadata_spacing != processed_frame.pixel_spacing {
+                state.metadata_spacing = processed_frame.pixel_spacing;
+                let calibration = state.calibration();
+                Some((state.measurements.overlays(&state.zoom, &calibration), calibration.describe()))
+            } else {
+                None
+            };
+            let rulers_update = (size_changed || calibration_update.is_some()).then(|| state.rulers());
+
+            // Detections are only drawn over the frame they were computed for
+            let had_detections = state.detections.is_some();
+            state.frame_geometry = processed_frame.geometry;
+            state.detections = detections.get(processed_frame.header.frame_id);
+            let detections_update = (had_detections || state.detections.is_some()).then(|| state.detection_overlays());
+            (calibration_update, rulers_update, detections_update)
+        };
 
-            while is_running.load(std::sync::atomic::Ordering::Relaxed) {
-                match event_receiver.recv().await {
-                    Ok(event) => {
-                        if let Err(e) = Self::handle_backend_event(
-                            event,
-                            &ui_state,
-                            &ui_command_tx,
-                        ).await {
-                            error!("Error handling backend event: {}", e);
-                        }
-                    }
-                    Err(broadcast::error::RecvError::Closed) => {
-                        info!("Backend event channel closed");
-                        break;
-                    }
-                    Err(broadcast::error::RecvError::Lagged(count)) => {
-                        warn!("Backend event receiver lagged by {} events", count);
-                        continue;
-                    }
-                }
-            }
+        if let Some((measurements, status)) = calibration_update {
+            let _ = ui_command_tx.send(UiCommand::UpdateCalibration(measurements, status));
+        }
+        if let Some(rulers) = rulers_update {
+            let _ = ui_command_tx.send(UiCommand::UpdateRulers(rulers));
+        }
 
-            info!("🔄 Backend event processing loop stopped");
-        })
+        // Send UI command with the pixel buffer (a Slint Image can't cross threads)
+        let _ = ui_command_tx.send(UiCommand::UpdateFrame {
+            frame_data: processed_frame.rgb_data.clone(),
+            gpu_source: processed_frame.gpu_source.clone(),
+            frame_id: processed_frame.header.frame_id,
+            sequence_number: processed_frame.header.sequence_number,
+            timestamp_ns: processed_frame.header.timestamp,
+            resolution: processed_frame.resolution_string(),
+            format: processed_frame.format_string(),
+        });
+
+        if let Some(roi_statistics) = processed_frame.roi_statistics {
+            let _ = ui_command_tx.send(UiCommand::UpdateRoiStatistics(roi_statistics.summary()));
+        }
+        if let Some(overlays) = detections_update {
+            let _ = ui_command_tx.send(UiCommand::UpdateDetections(overlays));
+        }
+
+        debug!("📺 Frame processed: {} {}x{}", 
+               processed_frame.header.frame_id,
+               processed_frame.header.width,
+               processed_frame.header.height);
     }
 
     /// Handle a single backend event
@@ -368,10 +1482,18 @@ impl MedicalFrameApp {
                 {
                     let mut state = ui_state.write().await;
                     state.update_connection_status("Connected".to_string(), true);
+                    let shm_name = state.shm_name.clone();
+                    state.audit(AuditAction::Connected, shm_name.as_str());
+                    state.session_log.record_event(SessionEventKind::Connected, shm_name.clone());
+                    state.script_event(ScriptEvent::Connected(shm_name));
                 }
 
-                // Send UI command
+                // Send UI commands
                 let _ = ui_command_tx.send(UiCommand::UpdateConnectionStatus("Connected".to_string(), true));
+                let _ = ui_command_tx.send(UiCommand::ShowNotification(
+                    NotificationLevel::Info,
+                    "Connected to medical device".to_string(),
+                ));
             }
 
             BackendEvent::Disconnected => {
@@ -381,6 +1503,9 @@ impl MedicalFrameApp {
                 {
                     let mut state = ui_state.write().await;
                     state.update_connection_status("Disconnected".to_string(), false);
+                    state.session_log.record_event(SessionEventKind::Disconnected, "");
+                    state.audit(AuditAction::Disconnected, state.shm_name.as_str());
+                    state.script_event(ScriptEvent::Disconnected);
                 }
 
                 // Send UI commands
@@ -395,10 +1520,13 @@ impl MedicalFrameApp {
                 {
                     let mut state = ui_state.write().await;
                     state.update_connection_status(format!("Error: {}", error), false);
+                    state.session_log.record_event(SessionEventKind::Error, error.clone());
+                    state.audit(AuditAction::ConnectionFailed, error.as_str());
                 }
 
-                // Send UI command
+                // Send UI commands
                 let _ = ui_command_tx.send(UiCommand::UpdateConnectionStatus(format!("Error: {}", error), false));
+                let _ = ui_command_tx.send(UiCommand::ShowError(Self::connection_error(error)));
             }
 
             BackendEvent::ConnectionLost => {
@@ -408,44 +1536,53 @@ impl MedicalFrameApp {
                 {
                     let mut state = ui_state.write().await;
                     state.update_connection_status("Connection Lost - Attempting reconnection...".to_string(), false);
+                    state.session_log.record_event(SessionEventKind::ConnectionLost, "Attempting reconnection");
+                    state.audit(AuditAction::ConnectionLost, state.shm_name.as_str());
+                    state.script_event(ScriptEvent::Disconnected);
                 }
 
-                // Send UI command
+                // Send UI commands
                 let _ = ui_command_tx.send(UiCommand::UpdateConnectionStatus("Connection Lost - Attempting reconnection...".to_string(), false));
+                let _ = ui_command_tx.send(UiCommand::ShowNotification(
+                    NotificationLevel::Warning,
+                    "Connection lost - attempting reconnection".to_string(),
+                ));
             }
 
-            BackendEvent::NewFrame(processed_frame) => {
-                // Update UI state
+            BackendEvent::WaitingForProducer(shm_name) => {
+                info!("⏳ Waiting for producer: {}", shm_name);
+
+                let status = format!("Waiting for producer: {}", shm_name);
                 {
                     let mut state = ui_state.write().await;
-                    state.update_frame_info(
-                        processed_frame.header.frame_id,
-                        processed_frame.header.sequence_number,
-                        processed_frame.resolution_string(),
-                        processed_frame.format_string(),
-                    );
+                    state.update_connection_status(status.clone(), false);
+                    state.session_log.record_event(SessionEventKind::WaitingForProducer, shm_name);
                 }
 
-                // Send UI command with raw frame data (avoid sending Slint Image across threads)
-                let _ = ui_command_tx.send(UiCommand::UpdateFrame {
-                    frame_data: processed_frame.rgb_data.clone(),
-                    width: processed_frame.header.width,
-                    height: processed_frame.header.height,
-                    frame_id: processed_frame.header.frame_id,
-                    sequence_number: processed_frame.header.sequence_number,
-                    resolution: processed_frame.resolution_string(),
-                    format: processed_frame.format_string(),
-                });
+                let _ = ui_command_tx.send(UiCommand::UpdateConnectionStatus(status, false));
+                let _ = ui_command_tx.send(UiCommand::SetWaitingForProducer(true));
+            }
+
+            BackendEvent::Recovered(report) => {
+                warn!("🐕 Pipeline watchdog: {}", report);
 
-                debug!("📺 Frame processed: {} {}x{}", 
-                       processed_frame.header.frame_id,
-                       processed_frame.header.width,
-                       processed_frame.header.height);
+                let status = if report.recovered() { "Connected" } else { "Recovering stalled pipeline..." };
+                {
+                    let mut state = ui_state.write().await;
+                    state.update_connection_status(status.to_string(), report.recovered());
+                    state.session_log.record_event(SessionEventKind::Recovered, report.to_string());
+                }
+
+                let _ = ui_command_tx.send(UiCommand::UpdateConnectionStatus(status.to_string(), report.recovered()));
+                let _ = ui_command_tx.send(UiCommand::ShowNotification(
+                    if report.recovered() { NotificationLevel::Warning } else { NotificationLevel::Error },
+                    format!("Pipeline stalled and was restarted: {}", report),
+                ));
             }
 
             BackendEvent::StatisticsUpdate(stats) => {
                 // Update UI state
-                {
+                let (newly_corrupted, drop_alert, protocol) = {
                     let mut state = ui_state.write().await;
                     state.update_performance(
                         stats.current_fps,
@@ -453,12 +1590,52 @@ impl MedicalFrameApp {
                         stats.total_frames_received,
                         stats.frames_dropped,
                     );
+                    state.session_log.record_sample(stats.current_fps, stats.average_latency_ms, stats.frames_dropped);
+                    let drop_alert = state.check_drop_alert(stats.total_frames_received, stats.frames_dropped)
+                        .map(|rate| (rate, state.drop_alert_percent));
+                    if let Some((rate, threshold)) = drop_alert {
+                        state.script_event(ScriptEvent::DropThreshold { rate, threshold: threshold as f64 });
+                    }
+                    let protocol = state.update_producer_protocol(stats.protocol.as_ref());
+                    (state.update_corrupted_frames(stats.frames_corrupted), drop_alert, protocol)
+                };
+
+                if let Some(protocol) = protocol {
+                    let _ = ui_command_tx.send(UiCommand::UpdateProducerProtocol(protocol));
+                }
+
+                let _ = ui_command_tx.send(UiCommand::UpdateFrameLoss {
+                    dropped: stats.frames_dropped,
+                    skipped: stats.frames_skipped,
+                    corrupted: stats.frames_corrupted,
+                    consumer_dropped: stats.frames_dropped_by_consumer,
+                });
+                let _ = ui_command_tx.send(UiCommand::UpdateJitter(stats.jitter_ms, stats.max_jitter_ms));
+                if let Some(performance) = stats.performance {
+                    let _ = ui_command_tx.send(UiCommand::UpdatePerformance(performance));
+                }
+                if let Some(inference) = stats.inference {
+                    let _ = ui_command_tx.send(UiCommand::UpdateInference(inference));
+                }
+                if let Some((rate, threshold)) = drop_alert {
+                    warn!("🕳️ Dropping {:.1}% of frames ({} gaps, {} frames total)",
+                          rate, stats.sequence_gaps, stats.frames_dropped);
+                    let _ = ui_command_tx.send(UiCommand::ShowNotification(
+                        NotificationLevel::Warning,
+                        format!("Dropping {:.1}% of frames (alert threshold {}%)", rate, threshold),
+                    ));
+                }
+                if newly_corrupted > 0 {
+                    let _ = ui_command_tx.send(UiCommand::ShowNotification(
+                        NotificationLevel::Warning,
+                        format!("{} frame(s) failed checksum verification ({} total)",
+                                newly_corrupted, stats.frames_corrupted),
+                    ));
                 }
 
                 // Send UI command
                 let _ = ui_command_tx.send(UiCommand::UpdateStatistics(
                     stats.current_fps,
-                    stats.average_latency_ms,
                     stats.total_frames_received,
                 ));
 
@@ -466,17 +1643,76 @@ impl MedicalFrameApp {
                     debug!("📊 Stats updated: {:.1} FPS, {:.1}ms latency", 
                            stats.current_fps, stats.average_latency_ms);
                 }
+
+                for stage in stats.pipeline_stages.iter().filter(|s| s.runs > 0) {
+                    debug!("🧩 Stage {}: {:.2}ms last, {:.2}ms avg over {} frames",
+                           stage.kind.name(), stage.last_ms, stage.average_ms, stage.runs);
+                }
+
+                if let Some(numa) = stats.numa.filter(|numa| numa.cross_node_frames > 0) {
+                    debug!("🧭 NUMA: {:.1}% of frames crossed nodes ({} MiB remote, {} nodes)",
+                           numa.cross_node_percent(), numa.cross_node_bytes / (1024 * 1024), numa.nodes);
+                }
             }
 
             BackendEvent::SettingsChanged => {
                 info!("⚙️ Backend settings changed");
                 // Handle settings changes if needed
             }
+
+            BackendEvent::DetectionsReceived(detections) => {
+                // Results for a frame that is no longer shown are not drawn
+                let overlays = {
+                    let mut state = ui_state.write().await;
+                    if !state.has_frame || state.current_frame_id != detections.frame_id {
+                        debug!("🎯 Detections for frame {} arrived after it was replaced", detections.frame_id);
+                        return Ok(());
+                    }
+                    state.detections = Some(detections);
+                    state.detection_overlays()
+                };
+                let _ = ui_command_tx.send(UiCommand::UpdateDetections(overlays));
+            }
         }
 
         Ok(())
     }
 
+    /// Handle actions requested by the automation script
+    fn start_script_actions(&self, mut actions: mpsc::UnboundedReceiver<ScriptAction>) -> tokio::task::JoinHandle<()> {
+        let ui_state = Arc::clone(&self.ui_state);
+        let ui_command_tx = self.ui_command_tx.clone();
+        let command_sender = self.command_sender.clone();
+
+        tokio::spawn(async move {
+            while let Some(action) = actions.recv().await {
+                info!("📜 Script action: {:?}", action);
+                match action {
+                    ScriptAction::Notify(message) => {
+                        let _ = ui_command_tx.send(UiCommand::ShowNotification(NotificationLevel::Info, message));
+                    }
+                    ScriptAction::CaptureSnapshot => {
+                        if !ui_state.write().await.session_log.capture_snapshot() {
+                            debug!("📜 No new frame to capture");
+                        }
+                    }
+                    ScriptAction::WriteReport => match Self::write_session_report(&ui_state).await {
+                        Ok(path) => {
+                            info!("📄 Session report written to {:?}", path);
+                            ui_state.read().await.audit(AuditAction::ReportWritten, path.display().to_string());
+                        }
+                        Err(e) => warn!("Failed to write session report: {}", e),
+                    },
+                    ScriptAction::Disconnect => {
+                        if let Err(e) = command_sender.send(BackendCommand::Disconnect) {
+                            error!("Failed to send disconnect command: {}", e);
+                        }
+                    }
+                }
+            }
+        })
+    }
+
     /// Start periodic tasks
     async fn start_periodic_tasks(&self) -> tokio::task::JoinHandle<()> {
         let ui_state = Arc::clone(&self.ui_state);
@@ -521,8 +1757,78 @@ impl MedicalFrameApp {
         self.slint_bridge.set_catch_up_mode(state.catch_up_mode).await
             .map_err(|e| FrontendError::Ui(e.to_string()))?;
 
+        // Update overlays
+        self.slint_bridge.set_show_overlays(state.show_overlays, state.hud_corner).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+
+        // Update theme
+        self.slint_bridge.set_theme(state.theme).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+
+        // Show who is signed in, asking for a sign-in when accounts are configured
+        self.slint_bridge.set_active_user(&state.active_user, state.users.is_some()).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        if state.users.is_some() && !state.active_user.is_signed_in() {
+            self.slint_bridge.open_login("").await
+                .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        }
+
+        // Update image adjustments and restore them in the backend
+        let window_level = state.window_level();
+        self.slint_bridge.set_window_level(window_level.center, window_level.width).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetWindowLevel(window_level)).await?;
+
+        let gamma = state.gamma();
+        self.slint_bridge.set_gamma(gamma.0).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetGamma(gamma)).await?;
+
+        self.slint_bridge.set_colormap(state.colormap).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetColormap(state.colormap)).await?;
+
+        self.slint_bridge.set_clahe(state.clahe_enabled, state.clahe_clip_limit).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetClahe(state.clahe_params())).await?;
+
+        self.slint_bridge.set_orientation(state.orientation).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetOrientation(state.orientation)).await?;
+
+        self.slint_bridge.set_persistence_frames(state.persistence_frames).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetStageStrength(StageKind::Temporal, state.persistence_frames as f32)).await?;
+
+        self.slint_bridge.set_speckle_strength(state.speckle_strength).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetStageStrength(StageKind::Speckle, state.speckle_strength)).await?;
+
+        self.slint_bridge.set_edge_enhancement(state.edge_enhancement).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetStageStrength(StageKind::EdgeEnhance, state.edge_enhancement)).await?;
+
+        self.slint_bridge.set_rulers_visible(state.show_rulers, state.show_grid).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+
+        self.slint_bridge.set_zoom_quality(state.zoom_quality).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+        self.send_command(BackendCommand::SetResampleQuality(state.zoom_quality)).await?;
+
+        // Update notification preferences
+        self.slint_bridge.set_notifications_enabled(state.notification_enabled).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+
+        // Update device profile
+        self.slint_bridge.set_device_profile(state.device_profile.as_deref(), state.expected_fps, state.latency_target_ms).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+
+        // Update reconnect policy
+        self.slint_bridge.set_reconnect_policy(state.reconnect_summary()).await
+            .map_err(|e| FrontendError::Ui(e.to_string()))?;
+
         // Update statistics
-        self.slint_bridge.update_statistics(state.fps, state.latency_ms, state.total_frames).await
+        self.slint_bridge.update_statistics(state.fps, state.total_frames).await
             .map_err(|e| FrontendError::Ui(e.to_string()))?;
 
         Ok(())
@@ -555,21 +1861,84 @@ impl MedicalFrameApp {
     /// Save settings to file
     async fn save_settings(&self) -> Result<(), FrontendError> {
         let state = self.ui_state.read().await;
+        Self::write_settings(&self.settings_path, &state).await
+    }
+
+    /// Read the discovery registry and list its streams in the settings dialog
+    async fn refresh_discovered_streams(slint_bridge: &SlintBridge) {
+        let streams = tokio::task::spawn_blocking(registry::discover).await.unwrap_or_default();
+        if let Err(e) = slint_bridge.set_discovered_streams(streams).await {
+            error!("Failed to list discovered streams: {}", e);
+        }
+    }
+
+    /// Build the session summary report and write it to the export directory
+    async fn write_session_report(ui_state: &Arc<tokio::sync::RwLock<UiState>>) -> Result<std::path::PathBuf, String> {
+        let (report, export_dir, cipher) = {
+            let state = ui_state.read().await;
+            let measurements = MeasurementReport::new(&state.measurements, &state.annotations, &state.calibration());
+            let report = SessionReport::new(
+                &state.session_log,
+                &state.session_stats,
+                &state.shm_name,
+                state.device_profile.as_deref(),
+                &state.active_user.display_name(),
+                measurements,
+            )
+            .anonymize(state.export_anonymization);
+            (report, std::path::PathBuf::from(&state.export_dir), state.recording_encryption.clone())
+        };
+
+        tokio::task::spawn_blocking(move || report.write(&export_dir, cipher.as_ref()))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())
+    }
+
+    /// Check that the active user may use a feature, telling them which role it needs if not
+    async fn permitted(ui_state: &Arc<tokio::sync::RwLock<UiState>>, slint_bridge: &SlintBridge, permission: Permission) -> bool {
+        let result = ui_state.read().await.require(permission);
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("🔒 {}", e);
+                if let Err(e) = slint_bridge.notify(NotificationLevel::Warning, &e.to_string()).await {
+                    error!("Failed to show notification: {}", e);
+                }
+                false
+            }
+        }
+    }
+
+    /// Classify a backend connection error for the operator
+    ///
+    /// Access to the source is refused for the whole session, so it is critical;
+    /// anything else is a device problem the backend keeps retrying.
+    fn connection_error(error: String) -> MiViError {
+        if error.contains("Permission denied") || error.contains("Operation not permitted") {
+            MiViError::permission(error)
+        } else {
+            MiViError::device(error)
+        }
+        .with_context("Connection failed")
+    }
 
+    /// Write UI state settings to the given path
+    async fn write_settings(settings_path: &std::path::Path, state: &UiState) -> Result<(), FrontendError> {
         match state.to_json() {
             Ok(json) => {
                 // Create settings directory if it doesn't exist
-                if let Some(parent) = self.settings_path.parent() {
+                if let Some(parent) = settings_path.parent() {
                     if let Err(e) = tokio::fs::create_dir_all(parent).await {
                         return Err(FrontendError::Other(format!("Failed to create settings directory: {}", e)));
                     }
                 }
 
-                if let Err(e) = tokio::fs::write(&self.settings_path, json).await {
+                if let Err(e) = tokio::fs::write(settings_path, json).await {
                     return Err(FrontendError::Other(format!("Failed to write settings file: {}", e)));
                 }
 
-                info!("📁 Settings saved to {:?}", self.settings_path);
+                info!("📁 Settings saved to {:?}", settings_path);
             }
             Err(e) => {
                 return Err(FrontendError::Other(format!("Failed to serialize settings: {}", e)));
@@ -0,0 +1,227 @@
+// src/frontend/audit_log.rs - Tamper-Evident Audit Log for Clinical Traceability
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Previous hash of the first entry
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Errors that can occur while writing or verifying the audit log
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Audit log broken at line {line}: {reason}")]
+    Broken { line: usize, reason: String },
+}
+
+/// What an audit entry records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    ApplicationStarted,
+    ApplicationStopped,
+    Connected,
+    Disconnected,
+    ConnectionLost,
+    ConnectionFailed,
+    SettingsChanged,
+    MeasurementsExported,
+    MeasurementsCleared,
+    CalibrationChanged,
+    ReportWritten,
+    ProfileCaptured,
+    SignedIn,
+    SignedOut,
+    SignInFailed,
+    AccessDenied,
+    PrivacyLocked,
+    PrivacyUnlocked,
+    PrivacyUnlockFailed,
+}
+
+/// One line of the audit log
+///
+/// `hash` is the SHA-256 of `prev_hash` and the entry serialized without `hash`,
+/// so editing, removing or reordering a line breaks the chain after it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    /// Local time with its offset as written, so verification does not depend on the time zone
+    pub time: chrono::DateTime<chrono::FixedOffset>,
+    pub user: String,
+    pub action: AuditAction,
+    pub detail: String,
+    pub prev_hash: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hash over everything but the hash itself
+    fn compute_hash(&self) -> Result<String, serde_json::Error> {
+        let unsigned = AuditEntry { hash: String::new(), ..self.clone() };
+        let mut hasher = Sha256::new();
+        hasher.update(self.prev_hash.as_bytes());
+        hasher.update(serde_json::to_vec(&unsigned)?);
+        Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+}
+
+#[derive(Debug)]
+struct AuditWriter {
+    path: PathBuf,
+    file: File,
+    next_seq: u64,
+    last_hash: String,
+    user: String,
+}
+
+/// Append-only, hash-chained JSONL audit log
+///
+/// Cloning shares the same file. Entries are flushed as they are written.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    writer: Arc<parking_lot::Mutex<AuditWriter>>,
+}
+
+impl AuditLog {
+    /// Open the log at `path`, continuing the chain of an existing log
+    ///
+    /// Refuses to append to a log whose chain is already broken.
+    pub fn open(path: &Path) -> Result<Self, AuditError> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let (next_seq, last_hash) = match Self::verify(path) {
+            Ok(Some(last)) => (last.seq + 1, last.hash),
+            Ok(None) => (0, GENESIS_HASH.to_string()),
+            Err(AuditError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => (0, GENESIS_HASH.to_string()),
+            Err(e) => return Err(e),
+        };
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+
+        Ok(Self {
+            writer: Arc::new(parking_lot::Mutex::new(AuditWriter {
+                path: path.to_path_buf(),
+                file,
+                next_seq,
+                last_hash,
+                user,
+            })),
+        })
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> PathBuf {
+        self.writer.lock().path.clone()
+    }
+
+    /// Attribute following entries to the signed-in user (the system user until then)
+    pub fn set_user(&self, user: impl Into<String>) {
+        self.writer.lock().user = user.into();
+    }
+
+    /// Append an entry
+    pub fn record(&self, action: AuditAction, detail: impl Into<String>) -> Result<(), AuditError> {
+        let mut writer = self.writer.lock();
+        let mut entry = AuditEntry {
+            seq: writer.next_seq,
+            time: chrono::Local::now().fixed_offset(),
+            user: writer.user.clone(),
+            action,
+            detail: detail.into(),
+            prev_hash: writer.last_hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash()?;
+
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        writer.file.write_all(&line)?;
+        writer.file.sync_data()?;
+
+        writer.next_seq += 1;
+        writer.last_hash = entry.hash;
+        Ok(())
+    }
+
+    /// Check the whole chain, returning the last entry (None for an empty log)
+    pub fn verify(path: &Path) -> Result<Option<AuditEntry>, AuditError> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut last: Option<AuditEntry> = None;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let broken = |reason: String| AuditError::Broken { line: index + 1, reason };
+            let entry: AuditEntry = serde_json::from_str(&line).map_err(|e| broken(e.to_string()))?;
+
+            let (expected_seq, expected_prev) = match &last {
+                Some(last) => (last.seq + 1, last.hash.as_str()),
+                None => (0, GENESIS_HASH),
+            };
+            if entry.seq != expected_seq {
+                return Err(broken(format!("expected sequence {}, found {}", expected_seq, entry.seq)));
+            }
+            if entry.prev_hash != expected_prev {
+                return Err(broken("previous hash does not match".to_string()));
+            }
+            if entry.compute_hash()? != entry.hash {
+                return Err(broken("entry was modified".to_string()));
+            }
+            last = Some(entry);
+        }
+
+        Ok(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_and_tampering() {
+        let dir = std::env::temp_dir().join(format!("mivi_audit_{}", std::process::id()));
+        let path = dir.join("audit.jsonl");
+        let _ = std::fs::remove_file(&path);
+
+        let log = AuditLog::open(&path).unwrap();
+        log.record(AuditAction::ApplicationStarted, "").unwrap();
+        log.record(AuditAction::Connected, "ultrasound_frames").unwrap();
+        drop(log);
+
+        // Reopening continues the chain
+        let log = AuditLog::open(&path).unwrap();
+        log.record(AuditAction::SettingsChanged, "gamma 1.2").unwrap();
+        assert_eq!(AuditLog::verify(&path).unwrap().map(|entry| entry.seq), Some(2));
+
+        // Editing a line breaks the chain and blocks further appends
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, content.replace("ultrasound_frames", "other_frames")).unwrap();
+        assert!(matches!(AuditLog::verify(&path), Err(AuditError::Broken { line: 2, .. })));
+        assert!(AuditLog::open(&path).is_err());
+
+        // So does dropping one
+        let lines: Vec<&str> = content.lines().collect();
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert!(matches!(AuditLog::verify(&path), Err(AuditError::Broken { line: 2, .. })));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,309 @@
+// src/frontend/egui_viewer.rs - Lightweight egui Viewer (feature "egui")
+
+//! Minimal eframe/egui viewer for machines where the Slint UI is too heavy or
+//! does not run, selected with `--ui egui`.
+//!
+//! Frames come from `MedicalFrameBackend` (and so from any `FrameSource` it
+//! supports) and reach the window through a `FrameSink`, exactly like the Slint
+//! frontends; the viewer only shows the image, status and frame counters.
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use eframe::egui;
+use egui::{Color32, RichText, Stroke, Vec2};
+use tracing::{info, warn};
+
+use crate::backend::{
+    BackendCommand, BackendConfig, BackendEvent, BackendHandle, FrameSink, MedicalFrameBackend, ProcessedFrame,
+};
+use crate::frontend::FrontendError;
+
+/// What the sink hands over to the egui thread
+#[derive(Default)]
+struct ViewerState {
+    frame: Option<ProcessedFrame>,
+    connected: bool,
+    connection_status: String,
+    fps: f64,
+    latency_ms: f64,
+    total_frames: u64,
+}
+
+/// Stores the newest frame and status for the next repaint
+struct EguiSink {
+    state: Arc<Mutex<ViewerState>>,
+    ctx: egui::Context,
+}
+
+#[async_trait]
+impl FrameSink for EguiSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        self.state.lock().unwrap().frame = Some(frame);
+        self.ctx.request_repaint();
+    }
+
+    async fn on_event(&mut self, event: BackendEvent) {
+        {
+            let mut state = self.state.lock().unwrap();
+            match event {
+                BackendEvent::Connected => {
+                    state.connected = true;
+                    state.connection_status = "Connected".to_string();
+                }
+                BackendEvent::Disconnected => {
+                    state.connected = false;
+                    state.connection_status = "Disconnected".to_string();
+                    state.frame = None;
+                }
+                BackendEvent::ConnectionError(error) => {
+                    state.connected = false;
+                    state.connection_status = format!("Disconnected - {}", error);
+                }
+                BackendEvent::ConnectionLost => {
+                    state.connected = false;
+                    state.connection_status = "Reconnecting...".to_string();
+                }
+                BackendEvent::WaitingForProducer(name) => {
+                    state.connected = false;
+                    state.connection_status = format!("Disconnected - Waiting for producer {}", name);
+                }
+                BackendEvent::StatisticsUpdate(stats) => {
+                    state.fps = stats.current_fps;
+                    state.latency_ms = stats.average_latency_ms;
+                    state.total_frames = stats.total_frames_received;
+                }
+                _ => {}
+            }
+        }
+        self.ctx.request_repaint();
+    }
+}
+
+// Application state
+struct EchoViewer {
+    runtime: tokio::runtime::Handle,
+    backend: Arc<MedicalFrameBackend>,
+    handle: Option<BackendHandle>,
+    config: BackendConfig,
+    state: Arc<Mutex<ViewerState>>,
+    texture: Option<egui::TextureHandle>,
+    shown_frame_id: Option<u64>,
+    catch_up: bool,
+}
+
+impl EchoViewer {
+    fn new(config: BackendConfig, runtime: tokio::runtime::Handle, ctx: egui::Context) -> Self {
+        let backend = {
+            let _runtime = runtime.enter();
+            Arc::new(MedicalFrameBackend::new(config.clone()))
+        };
+        let state = Arc::new(Mutex::new(ViewerState {
+            connection_status: "Disconnected - Waiting for producer".to_string(),
+            ..ViewerState::default()
+        }));
+
+        let handle = runtime.block_on(async {
+            backend.spawn_sink(EguiSink { state: Arc::clone(&state), ctx });
+            backend.start().await
+        });
+        let handle = match handle {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!("⚠️ Backend failed to start: {}", e);
+                state.lock().unwrap().connection_status = format!("Backend failed to start - {}", e);
+                None
+            }
+        };
+
+        let catch_up = config.catch_up;
+        let viewer = Self { runtime, backend, handle, config, state, texture: None, shown_frame_id: None, catch_up };
+        viewer.connect();
+        viewer
+    }
+
+    fn connect(&self) {
+        let _ = self.backend.get_command_sender().send(BackendCommand::Connect {
+            shm_name: self.config.shm_name.clone(),
+            config: self.config.clone(),
+        });
+    }
+
+    /// Upload the newest frame if it differs from the displayed one
+    fn update_texture(&mut self, ctx: &egui::Context) {
+        let state = self.state.lock().unwrap();
+        let Some(frame) = state.frame.as_ref() else {
+            self.texture = None;
+            self.shown_frame_id = None;
+            return;
+        };
+        if self.shown_frame_id == Some(frame.header.frame_id) {
+            return;
+        }
+
+        let rgba = frame.rgba();
+        let image = egui::ColorImage::from_rgba_unmultiplied(
+            [rgba.width() as usize, rgba.height() as usize],
+            rgba.as_bytes(),
+        );
+        match self.texture.as_mut() {
+            Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+            None => self.texture = Some(ctx.load_texture("frame_image", image, egui::TextureOptions::LINEAR)),
+        }
+        self.shown_frame_id = Some(frame.header.frame_id);
+    }
+}
+
+impl Drop for EchoViewer {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = self.runtime.block_on(handle.shutdown());
+        }
+    }
+}
+
+impl eframe::App for EchoViewer {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.update_texture(ctx);
+
+        // Top panel for status information
+        egui::TopBottomPanel::top("status_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let state = self.state.lock().unwrap();
+
+                // Connection status with color indicator
+                let status_color = if state.connected {
+                    Color32::from_rgb(50, 200, 70) // Green for connected
+                } else {
+                    Color32::from_rgb(220, 50, 50) // Red for disconnected
+                };
+
+                ui.label(RichText::new("Status:").strong());
+                ui.label(RichText::new(&state.connection_status).color(status_color));
+
+                ui.separator();
+
+                // Frame info if we have a valid frame
+                if let Some(frame) = state.frame.as_ref() {
+                    ui.label(RichText::new(format!("Resolution: {}", frame.resolution_string())).strong());
+                    ui.separator();
+                    ui.label(RichText::new(format!("Format: {}", frame.format_string())).strong());
+                    ui.separator();
+                    ui.label(RichText::new(format!("FPS: {:.1}", state.fps)).strong());
+                    ui.separator();
+                    ui.label(RichText::new(format!("Latency: {:.1} ms", state.latency_ms)).strong());
+                }
+                drop(state);
+
+                // Force reconnect button
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Reconnect").clicked() {
+                        self.connect();
+                    }
+                });
+            });
+        });
+
+        // Bottom panel for additional stats
+        egui::TopBottomPanel::bottom("stats_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                {
+                    let state = self.state.lock().unwrap();
+                    if let Some(frame) = state.frame.as_ref() {
+                        ui.label(RichText::new(format!("Frame ID: {}", frame.header.frame_id)).monospace());
+                        ui.separator();
+                        ui.label(RichText::new(format!("Sequence: {}", frame.header.sequence_number)).monospace());
+                        ui.separator();
+                        ui.label(RichText::new(format!("Total Frames: {}", state.total_frames)).monospace());
+                    }
+                }
+
+                // Toggle catch-up mode
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.checkbox(&mut self.catch_up, "Skip to Latest Frame").changed() {
+                        let _ = self.backend.get_command_sender().send(BackendCommand::SetCatchUpMode(self.catch_up));
+                    }
+                });
+            });
+        });
+
+        // Central panel for the image
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // If we're not connected, show a message
+            if !self.state.lock().unwrap().connected {
+                ui.centered_and_justified(|ui| {
+                    ui.add(egui::Label::new(
+                        RichText::new("Waiting for Connection...")
+                            .color(Color32::LIGHT_GRAY)
+                            .size(24.0)
+                    ));
+                });
+                return;
+            }
+
+            let Some(texture) = self.texture.as_ref() else {
+                // No valid frame yet
+                ui.centered_and_justified(|ui| {
+                    ui.add(egui::Label::new(
+                        RichText::new("Waiting for Frames...")
+                            .color(Color32::LIGHT_GRAY)
+                            .size(24.0)
+                    ));
+                });
+                return;
+            };
+
+            // Calculate display size with aspect ratio preservation
+            let available_size = ui.available_size();
+            let [frame_width, frame_height] = texture.size();
+            let image_aspect_ratio = frame_width as f32 / frame_height as f32;
+            let panel_aspect_ratio = available_size.x / available_size.y;
+
+            let display_size = if image_aspect_ratio > panel_aspect_ratio {
+                // Width constrained
+                Vec2::new(available_size.x, available_size.x / image_aspect_ratio)
+            } else {
+                // Height constrained
+                Vec2::new(available_size.y * image_aspect_ratio, available_size.y)
+            };
+
+            // Display the image centered
+            ui.centered_and_justified(|ui| {
+                // Draw a debug border to ensure image positioning is correct
+                let rect = ui.min_rect().expand2(display_size * 0.5);
+                ui.painter().rect_stroke(rect, 0.0, Stroke::new(2.0, Color32::RED));
+
+                // Display the image
+                ui.image((texture.id(), display_size));
+            });
+        });
+    }
+}
+
+/// Run the egui viewer until its window closes
+///
+/// Blocks the calling thread, which has to be the main thread on some
+/// platforms; on a tokio runtime, call it inside `tokio::task::block_in_place`.
+pub fn run(config: BackendConfig, window_width: u32, window_height: u32) -> Result<(), FrontendError> {
+    let runtime = tokio::runtime::Handle::try_current().map_err(|e| FrontendError::Other(e.to_string()))?;
+
+    let options = eframe::NativeOptions {
+        initial_window_size: Some(egui::vec2(window_width as f32, window_height as f32)),
+        min_window_size: Some(egui::vec2(640.0, 480.0)),
+        centered: true,
+        resizable: true,
+        depth_buffer: 0,
+        stencil_buffer: 0,
+        multisampling: 0,
+        hardware_acceleration: eframe::HardwareAcceleration::Preferred,
+        ..Default::default()
+    };
+
+    info!("🪶 Starting egui viewer");
+    eframe::run_native(
+        "MiVi Medical Frame Viewer",
+        options,
+        Box::new(move |cc| Box::new(EchoViewer::new(config, runtime, cc.egui_ctx.clone()))),
+    )
+    .map_err(|e| FrontendError::Ui(e.to_string()))
+}
@@ -0,0 +1,405 @@
+// src/frontend/gpu_renderer.rs - OpenGL Upload and On-GPU RGBA Conversion of Raw Frames
+
+use std::time::{Duration, Instant};
+
+use glow::HasContext;
+use thiserror::Error;
+use tracing::debug;
+
+use crate::backend::GpuFrame;
+
+/// Frames between upload timing reports in the debug log
+const STATS_LOG_INTERVAL: u64 = 120;
+
+const VERTEX_SHADER: &str = r#"
+in vec2 position;
+out vec2 uv;
+
+void main() {
+    // Texture row 0 is the top of the frame, matching Slint's top-left texture origin
+    uv = position * 0.5 + 0.5;
+    gl_Position = vec4(position, 0.0, 1.0);
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"
+precision mediump float;
+
+in vec2 uv;
+out vec4 color;
+
+uniform sampler2D source;
+uniform int channels;
+uniform bool swap_red_blue;
+
+void main() {
+    vec4 texel = texture(source, uv);
+    if (channels == 1) {
+        color = vec4(texel.rrr, 1.0);
+    } else {
+        vec3 rgb = swap_red_blue ? texel.bgr : texel.rgb;
+        color = vec4(rgb, channels == 4 ? texel.a : 1.0);
+    }
+}
+"#;
+
+/// Window/level and gamma on 10-bit samples, at full precision into a 10-bit target
+const DEEP_FRAGMENT_SHADER: &str = r#"
+precision highp float;
+precision highp usampler2D;
+
+in vec2 uv;
+out vec4 color;
+
+uniform usampler2D source;
+uniform int channels;
+uniform vec2 window;
+uniform float gamma;
+
+float tone(uint sample) {
+    float low = window.x - window.y / 2.0;
+    float value = clamp((float(sample & 1023u) / 1023.0 - low) / window.y, 0.0, 1.0);
+    return pow(value, 1.0 / gamma);
+}
+
+void main() {
+    uvec4 texel = texture(source, uv);
+    if (channels == 1) {
+        float gray = tone(texel.r);
+        color = vec4(gray, gray, gray, 1.0);
+    } else {
+        color = vec4(tone(texel.r), tone(texel.g), tone(texel.b), 1.0);
+    }
+}
+"#;
+
+/// Upload timing of the GPU path
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GpuUploadStats {
+    pub frames: u64,
+    pub last: Duration,
+    pub total: Duration,
+}
+
+impl GpuUploadStats {
+    /// Average upload and conversion time in milliseconds
+    pub fn average_ms(&self) -> f64 {
+        if self.frames > 0 {
+            self.total.as_secs_f64() * 1000.0 / self.frames as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Texture the converted frame is rendered into and Slint displays
+struct OutputTarget {
+    texture: glow::Texture,
+    framebuffer: glow::Framebuffer,
+    width: u32,
+    height: u32,
+    internal_format: u32,
+}
+
+/// Size and channel count of the currently allocated source texture
+type SourceShape = (u32, u32, u32);
+
+/// Shader and upload texture for 10-bit frames (deep color)
+struct DeepPath {
+    program: glow::Program,
+    channels_location: Option<glow::UniformLocation>,
+    window_location: Option<glow::UniformLocation>,
+    gamma_location: Option<glow::UniformLocation>,
+    source: glow::Texture,
+    source_shape: Option<SourceShape>,
+}
+
+/// Uploads packed frames as textures and converts them to RGBA in a shader
+///
+/// 8-bit frames render into an RGBA8 texture. 10-bit frames are uploaded as
+/// 16-bit integer textures, window/levelled in the shader and rendered into an
+/// RGB10_A2 texture, so no precision is lost before Slint composites them;
+/// whether all 10 bits reach the panel depends on the window surface Slint
+/// picked.
+///
+/// Lives on the UI thread and is driven from Slint's rendering notifier, so it
+/// always runs with the renderer's OpenGL context current.
+pub struct GpuFrameRenderer {
+    gl: glow::Context,
+    program: glow::Program,
+    vertex_array: glow::VertexArray,
+    vertex_buffer: glow::Buffer,
+    channels_location: Option<glow::UniformLocation>,
+    swap_location: Option<glow::UniformLocation>,
+    source: glow::Texture,
+    source_shape: Option<SourceShape>,
+    deep: DeepPath,
+    output: Option<OutputTarget>,
+    stats: GpuUploadStats,
+}
+
+impl GpuFrameRenderer {
+    /// Compile the conversion shader and allocate the upload texture
+    pub fn new(gl: glow::Context) -> Result<Self, GpuRenderError> {
+        unsafe {
+            let version = if gl.version().is_embedded { "#version 300 es" } else { "#version 330 core" };
+            let program = Self::link_program(&gl, version, FRAGMENT_SHADER)?;
+            let deep_program = Self::link_program(&gl, version, DEEP_FRAGMENT_SHADER)?;
+
+            let vertex_array = gl.create_vertex_array().map_err(GpuRenderError::Allocation)?;
+            let vertex_buffer = gl.create_buffer().map_err(GpuRenderError::Allocation)?;
+            gl.bind_vertex_array(Some(vertex_array));
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(vertex_buffer));
+
+            // Full-viewport quad as a triangle strip
+            let quad: [f32; 8] = [-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0];
+            let quad_bytes: Vec<u8> = quad.iter().flat_map(|v| v.to_ne_bytes()).collect();
+            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, &quad_bytes, glow::STATIC_DRAW);
+
+            let position = gl.get_attrib_location(program, "position").ok_or(GpuRenderError::MissingAttribute("position"))?;
+            gl.enable_vertex_attrib_array(position);
+            gl.vertex_attrib_pointer_f32(position, 2, glow::FLOAT, false, 8, 0);
+            gl.bind_vertex_array(None);
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+
+            let source = Self::create_texture(&gl, glow::NEAREST)?;
+            let channels_location = gl.get_uniform_location(program, "channels");
+            let swap_location = gl.get_uniform_location(program, "swap_red_blue");
+            let deep = DeepPath {
+                program: deep_program,
+                channels_location: gl.get_uniform_location(deep_program, "channels"),
+                window_location: gl.get_uniform_location(deep_program, "window"),
+                gamma_location: gl.get_uniform_location(deep_program, "gamma"),
+                source: Self::create_texture(&gl, glow::NEAREST)?,
+                source_shape: None,
+            };
+
+            Ok(Self {
+                gl,
+                program,
+                vertex_array,
+                vertex_buffer,
+                channels_location,
+                swap_location,
+                source,
+                source_shape: None,
+                deep,
+                output: None,
+                stats: GpuUploadStats::default(),
+            })
+        }
+    }
+
+    /// Upload a frame, convert it into the output texture and wrap that as a Slint image
+    pub fn render(&mut self, frame: &GpuFrame) -> Result<slint::Image, GpuRenderError> {
+        let start = Instant::now();
+        let (width, height) = frame.dimensions();
+        let channels = frame.layout.channels;
+
+        unsafe {
+            let output_format = if frame.tone.is_some() { glow::RGB10_A2 } else { glow::RGBA8 };
+            self.ensure_output(width, height, output_format)?;
+            let (output_texture, output_framebuffer) = match &self.output {
+                Some(output) => (output.texture, output.framebuffer),
+                None => unreachable!("output target allocated above"),
+            };
+            let gl = &self.gl;
+
+            // Upload straight from the shared frame data, no RGBA staging buffer
+            let (source, source_shape, program) = match frame.tone {
+                Some(_) => (self.deep.source, &mut self.deep.source_shape, self.deep.program),
+                None => (self.source, &mut self.source_shape, self.program),
+            };
+            let (internal_format, pixel_format, sample_type) = match (channels, frame.tone.is_some()) {
+                (1, true) => (glow::R16UI, glow::RED_INTEGER, glow::UNSIGNED_SHORT),
+                (_, true) => (glow::RGB16UI, glow::RGB_INTEGER, glow::UNSIGNED_SHORT),
+                (1, false) => (glow::R8, glow::RED, glow::UNSIGNED_BYTE),
+                (3, false) => (glow::RGB8, glow::RGB, glow::UNSIGNED_BYTE),
+                _ => (glow::RGBA8, glow::RGBA, glow::UNSIGNED_BYTE),
+            };
+            let saved_alignment = gl.get_parameter_i32(glow::UNPACK_ALIGNMENT);
+            gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+            gl.bind_texture(glow::TEXTURE_2D, Some(source));
+            let pixels = glow::PixelUnpackData::Slice(Some(&frame.frame.data));
+            if *source_shape == Some((width, height, channels)) {
+                gl.tex_sub_image_2d(glow::TEXTURE_2D, 0, 0, 0, width as i32, height as i32, pixel_format, sample_type, pixels);
+            } else {
+                gl.tex_image_2d(glow::TEXTURE_2D, 0, internal_format as i32, width as i32, height as i32, 0, pixel_format, sample_type, pixels);
+                *source_shape = Some((width, height, channels));
+            }
+            gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, saved_alignment);
+
+            // Convert into the output texture, leaving Slint's framebuffer and viewport as they were
+            let saved_framebuffer = gl.get_parameter_i32(glow::DRAW_FRAMEBUFFER_BINDING);
+            let mut saved_viewport = [0i32; 4];
+            gl.get_parameter_i32_slice(glow::VIEWPORT, &mut saved_viewport);
+
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(output_framebuffer));
+            gl.viewport(0, 0, width as i32, height as i32);
+            gl.use_program(Some(program));
+            match &frame.tone {
+                Some(tone) => {
+                    gl.uniform_1_i32(self.deep.channels_location.as_ref(), channels as i32);
+                    gl.uniform_2_f32(self.deep.window_location.as_ref(), tone.window_level.center, tone.window_level.width);
+                    gl.uniform_1_f32(self.deep.gamma_location.as_ref(), tone.gamma.0);
+                }
+                None => {
+                    gl.uniform_1_i32(self.channels_location.as_ref(), channels as i32);
+                    gl.uniform_1_i32(self.swap_location.as_ref(), frame.layout.swap_red_blue as i32);
+                }
+            }
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_texture(glow::TEXTURE_2D, Some(source));
+            gl.bind_vertex_array(Some(self.vertex_array));
+            gl.draw_arrays(glow::TRIANGLE_STRIP, 0, 4);
+
+            gl.bind_vertex_array(None);
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            gl.use_program(None);
+            gl.viewport(saved_viewport[0], saved_viewport[1], saved_viewport[2], saved_viewport[3]);
+            gl.bind_framebuffer(
+                glow::FRAMEBUFFER,
+                std::num::NonZeroU32::new(saved_framebuffer as u32).map(glow::NativeFramebuffer),
+            );
+
+            self.record(start.elapsed());
+
+            Ok(slint::BorrowedOpenGLTextureBuilder::new_gl_2d_rgba_texture(output_texture.0, [width, height].into()).build())
+        }
+    }
+
+    /// Get upload timing statistics
+    pub fn statistics(&self) -> GpuUploadStats {
+        self.stats
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.stats.frames += 1;
+        self.stats.last = elapsed;
+        self.stats.total += elapsed;
+        if self.stats.frames % STATS_LOG_INTERVAL == 0 {
+            debug!("🎮 GPU upload: {:.2}ms last, {:.2}ms average over {} frames",
+                   elapsed.as_secs_f64() * 1000.0, self.stats.average_ms(), self.stats.frames);
+        }
+    }
+
+    /// (Re)allocate the output texture and its framebuffer for a frame size and texture format
+    unsafe fn ensure_output(&mut self, width: u32, height: u32, internal_format: u32) -> Result<(), GpuRenderError> {
+        if self.output.as_ref().is_some_and(|output| {
+            output.width == width && output.height == height && output.internal_format == internal_format
+        }) {
+            return Ok(());
+        }
+        if let Some(output) = self.output.take() {
+            self.gl.delete_framebuffer(output.framebuffer);
+            self.gl.delete_texture(output.texture);
+        }
+
+        let gl = &self.gl;
+        let texture = Self::create_texture(gl, glow::LINEAR)?;
+        gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+        let sample_type = if internal_format == glow::RGB10_A2 { glow::UNSIGNED_INT_2_10_10_10_REV } else { glow::UNSIGNED_BYTE };
+        gl.tex_image_2d(
+            glow::TEXTURE_2D, 0, internal_format as i32, width as i32, height as i32, 0,
+            glow::RGBA, sample_type, glow::PixelUnpackData::Slice(None),
+        );
+        gl.bind_texture(glow::TEXTURE_2D, None);
+
+        let framebuffer = gl.create_framebuffer().map_err(GpuRenderError::Allocation)?;
+        let saved_framebuffer = gl.get_parameter_i32(glow::DRAW_FRAMEBUFFER_BINDING);
+        gl.bind_framebuffer(glow::FRAMEBUFFER, Some(framebuffer));
+        gl.framebuffer_texture_2d(glow::FRAMEBUFFER, glow::COLOR_ATTACHMENT0, glow::TEXTURE_2D, Some(texture), 0);
+        let status = gl.check_framebuffer_status(glow::FRAMEBUFFER);
+        gl.bind_framebuffer(
+            glow::FRAMEBUFFER,
+            std::num::NonZeroU32::new(saved_framebuffer as u32).map(glow::NativeFramebuffer),
+        );
+
+        if status != glow::FRAMEBUFFER_COMPLETE {
+            gl.delete_framebuffer(framebuffer);
+            gl.delete_texture(texture);
+            return Err(GpuRenderError::IncompleteFramebuffer(status));
+        }
+
+        self.output = Some(OutputTarget { texture, framebuffer, width, height, internal_format });
+        Ok(())
+    }
+
+    unsafe fn create_texture(gl: &glow::Context, filter: u32) -> Result<glow::Texture, GpuRenderError> {
+        let texture = gl.create_texture().map_err(GpuRenderError::Allocation)?;
+        gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, filter as i32);
+        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, filter as i32);
+        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE as i32);
+        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE as i32);
+        gl.bind_texture(glow::TEXTURE_2D, None);
+        Ok(texture)
+    }
+
+    unsafe fn link_program(gl: &glow::Context, version: &str, fragment_shader: &str) -> Result<glow::Program, GpuRenderError> {
+        let program = gl.create_program().map_err(GpuRenderError::Allocation)?;
+        let mut shaders = Vec::new();
+
+        for (kind, source) in [(glow::VERTEX_SHADER, VERTEX_SHADER), (glow::FRAGMENT_SHADER, fragment_shader)] {
+            let shader = gl.create_shader(kind).map_err(GpuRenderError::Allocation)?;
+            gl.shader_source(shader, &format!("{}\n{}", version, source));
+            gl.compile_shader(shader);
+            if !gl.get_shader_compile_status(shader) {
+                let log = gl.get_shader_info_log(shader);
+                gl.delete_shader(shader);
+                gl.delete_program(program);
+                return Err(GpuRenderError::Shader(log));
+            }
+            gl.attach_shader(program, shader);
+            shaders.push(shader);
+        }
+
+        // Both programs share the quad's vertex array
+        gl.bind_attrib_location(program, 0, "position");
+        gl.link_program(program);
+        for shader in shaders {
+            gl.detach_shader(program, shader);
+            gl.delete_shader(shader);
+        }
+        if !gl.get_program_link_status(program) {
+            let log = gl.get_program_info_log(program);
+            gl.delete_program(program);
+            return Err(GpuRenderError::Shader(log));
+        }
+
+        Ok(program)
+    }
+}
+
+impl Drop for GpuFrameRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(output) = self.output.take() {
+                self.gl.delete_framebuffer(output.framebuffer);
+                self.gl.delete_texture(output.texture);
+            }
+            self.gl.delete_texture(self.source);
+            self.gl.delete_texture(self.deep.source);
+            self.gl.delete_program(self.deep.program);
+            self.gl.delete_buffer(self.vertex_buffer);
+            self.gl.delete_vertex_array(self.vertex_array);
+            self.gl.delete_program(self.program);
+        }
+    }
+}
+
+/// Errors setting up or running the GPU upload path
+#[derive(Debug, Error)]
+pub enum GpuRenderError {
+    #[error("Failed to allocate GL object: {0}")]
+    Allocation(String),
+
+    #[error("Conversion shader failed to build: {0}")]
+    Shader(String),
+
+    #[error("Shader attribute {0} not found")]
+    MissingAttribute(&'static str),
+
+    #[error("Output framebuffer incomplete (status {0:#x})")]
+    IncompleteFramebuffer(u32),
+}
@@ -0,0 +1,84 @@
+// src/frontend/headless.rs - Headless Frontend Logging Connection and Statistics
+
+//! `--ui none`: runs the backend without a window. Frames are still read and
+//! converted (so health checks, recording and OpenIGTLink re-broadcast keep
+//! working); the frontend only logs connection changes and statistics.
+
+use async_trait::async_trait;
+use tracing::{debug, info, warn};
+
+use crate::backend::{BackendCommand, BackendConfig, BackendEvent, FrameSink, MedicalFrameBackend, ProcessedFrame};
+use crate::frontend::FrontendError;
+
+/// Logs what a windowed frontend would show
+struct LogSink {
+    frames: u64,
+}
+
+#[async_trait]
+impl FrameSink for LogSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        self.frames += 1;
+        debug!("📺 Frame {} {}", frame.header.frame_id, frame.resolution_string());
+    }
+
+    async fn on_event(&mut self, event: BackendEvent) {
+        match event {
+            BackendEvent::Connected => info!("✅ Backend connected"),
+            BackendEvent::Disconnected => info!("🔌 Backend disconnected"),
+            BackendEvent::ConnectionError(error) => warn!("❌ Backend connection error: {}", error),
+            BackendEvent::ConnectionLost => warn!("⚠️ Backend connection lost, attempting reconnection"),
+            BackendEvent::WaitingForProducer(name) => info!("⏳ Waiting for producer: {}", name),
+            BackendEvent::Recovered(report) => warn!("🐕 Pipeline watchdog: {}", report),
+            BackendEvent::StatisticsUpdate(stats) => info!(
+                "📊 {:.1} fps, {:.1}ms latency, {} received, {} dropped, {} shown",
+                stats.current_fps, stats.average_latency_ms, stats.total_frames_received, stats.frames_dropped, self.frames
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Run the backend without a window until Ctrl+C or SIGTERM
+pub async fn run(config: BackendConfig) -> Result<(), FrontendError> {
+    info!("🖥️ Running headless (no UI)");
+    let backend = MedicalFrameBackend::new(config.clone());
+    let sink = backend.spawn_sink(LogSink { frames: 0 });
+    let handle = backend.start().await.map_err(|e| FrontendError::Backend(e.to_string()))?;
+
+    backend
+        .get_command_sender()
+        .send(BackendCommand::Connect { shm_name: config.shm_name.clone(), config })
+        .map_err(|e| FrontendError::Communication(e.to_string()))?;
+
+    shutdown_signal().await;
+    info!("📡 Shutdown requested");
+
+    sink.abort();
+    handle.shutdown().await.map_err(|e| FrontendError::Backend(e.to_string()))
+}
+
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = sigterm.recv() => {}
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+            }
+            Err(e) => {
+                warn!("⚠️ Failed to set up SIGTERM handler: {}", e);
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
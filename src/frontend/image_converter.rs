@@ -56,10 +56,11 @@ impl ImageConverter {
 
         // Validate data size (expecting RGBA format from backend)
         let expected_size = (width * height * 4) as usize;
-        if frame.rgb_data.len() != expected_size {
+        let rgba = frame.rgba();
+        if rgba.as_bytes().len() != expected_size {
             return Err(ImageConversionError::InvalidDataSize {
                 expected: expected_size,
-                actual: frame.rgb_data.len(),
+                actual: rgba.as_bytes().len(),
                 width,
                 height,
             });
@@ -68,8 +69,8 @@ impl ImageConverter {
         debug!("🖼️ Converting frame {} to Slint image: {}x{}",
                frame.header.frame_id, width, height);
 
-        // Create the Slint image with zero-copy where possible
-        let image = self.create_slint_image_optimized(&frame.rgb_data, width, height)?;
+        // The processor already filled a Slint pixel buffer, so the image just shares it
+        let image = Image::from_rgba8(rgba);
 
         // Cache the image if enabled
         if self.enable_caching {
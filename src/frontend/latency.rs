@@ -0,0 +1,142 @@
+// src/frontend/latency.rs - End-to-End Latency Percentiles and Sparkline
+
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Presented frames kept for percentiles (about 10 s at 60 fps)
+const DEFAULT_CAPACITY: usize = 600;
+
+/// Most recent samples drawn in the sparkline
+const SPARKLINE_POINTS: usize = 90;
+
+/// Side of the square sparkline viewbox
+const SPARKLINE_VIEWBOX: f64 = 100.0;
+
+/// Latency distribution over the recent window, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LatencySummary {
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+    pub samples: usize,
+}
+
+/// Rolling window of producer-to-display latencies
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    samples: VecDeque<f64>,
+    capacity: usize,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl LatencyHistogram {
+    /// Create a histogram over the given number of most recent frames
+    pub fn new(capacity: usize) -> Self {
+        Self { samples: VecDeque::with_capacity(capacity), capacity: capacity.max(1) }
+    }
+
+    /// Record one latency sample
+    pub fn record(&mut self, latency_ms: f64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency_ms);
+    }
+
+    /// Record the latency of a frame presented now, from its producer timestamp
+    ///
+    /// Frames without a timestamp, or stamped ahead of the local clock, are ignored.
+    pub fn record_presented(&mut self, timestamp_ns: u64) -> Option<f64> {
+        let now_ns = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64;
+        if timestamp_ns == 0 || timestamp_ns > now_ns {
+            return None;
+        }
+
+        let latency_ms = (now_ns - timestamp_ns) as f64 / 1_000_000.0;
+        self.record(latency_ms);
+        Some(latency_ms)
+    }
+
+    /// Forget all samples, e.g. when the stream changes
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Percentiles over the current window (nearest-rank)
+    pub fn summary(&self) -> LatencySummary {
+        if self.samples.is_empty() {
+            return LatencySummary::default();
+        }
+
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+            sorted[rank.clamp(1, sorted.len()) - 1]
+        };
+
+        LatencySummary {
+            p50: percentile(50.0),
+            p95: percentile(95.0),
+            p99: percentile(99.0),
+            max: sorted[sorted.len() - 1],
+            samples: sorted.len(),
+        }
+    }
+
+    /// SVG path commands plotting the most recent samples in a 100x100 viewbox, scaled to their maximum
+    pub fn sparkline_path(&self) -> String {
+        let recent: Vec<f64> = self.samples.iter().rev().take(SPARKLINE_POINTS).rev().copied().collect();
+        if recent.len() < 2 {
+            return String::new();
+        }
+
+        let peak = recent.iter().fold(0.0f64, |a, &b| a.max(b)).max(f64::EPSILON);
+        let step = SPARKLINE_VIEWBOX / (recent.len() - 1) as f64;
+        recent
+            .iter()
+            .enumerate()
+            .map(|(i, &latency)| {
+                let command = if i == 0 { 'M' } else { 'L' };
+                format!("{} {:.1} {:.1}", command, i as f64 * step, SPARKLINE_VIEWBOX * (1.0 - latency / peak))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_percentiles() {
+        let mut histogram = LatencyHistogram::new(100);
+        assert_eq!(histogram.summary(), LatencySummary::default());
+        assert_eq!(histogram.sparkline_path(), "");
+
+        // Old samples fall out of the window
+        histogram.record(1000.0);
+        for latency in 1..=100 {
+            histogram.record(latency as f64);
+        }
+
+        let summary = histogram.summary();
+        assert_eq!((summary.p50, summary.p95, summary.p99, summary.max), (50.0, 95.0, 99.0, 100.0));
+        assert_eq!(summary.samples, 100);
+
+        let path = histogram.sparkline_path();
+        assert!(path.starts_with("M 0.0 "));
+        assert!(path.ends_with("L 100.0 0.0"));
+
+        assert_eq!(histogram.record_presented(0), None);
+        histogram.clear();
+        assert_eq!(histogram.summary().samples, 0);
+    }
+}
@@ -0,0 +1,188 @@
+// src/frontend/measurement_export.rs - Measurement and Annotation Export for Downstream Reporting
+
+use serde::Serialize;
+
+use crate::frontend::measurements::{AnnotationSet, Calibration, MeasurementSet};
+use crate::redaction::AnonymizationProfile;
+
+/// File format for measurement export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    /// Get file extension
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// One exported measurement or annotation
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportRecord {
+    /// Distance, Angle, Ellipse or Annotation
+    pub kind: String,
+    pub id: u64,
+    pub frame_id: u64,
+    pub timestamp: String,
+    pub value: Option<f32>,
+    pub unit: Option<String>,
+    pub area: Option<f32>,
+    pub area_unit: Option<String>,
+    /// Measurement label or annotation text
+    pub label: String,
+    /// Points as fractions of the displayed frame
+    pub points: Vec<[f32; 2]>,
+}
+
+/// Measurements and annotations of a session with their calibration context
+#[derive(Debug, Clone, Serialize)]
+pub struct MeasurementReport {
+    pub generated_at: String,
+    pub calibration: String,
+    pub pixel_spacing_mm: Option<[f32; 2]>,
+    pub records: Vec<ExportRecord>,
+}
+
+impl MeasurementReport {
+    /// CSV column names
+    const CSV_HEADER: &'static str = "kind,id,frame_id,timestamp,value,unit,area,area_unit,label,points";
+
+    /// Build a report from the current measurements and annotations
+    pub fn new(measurements: &MeasurementSet, annotations: &AnnotationSet, calibration: &Calibration) -> Self {
+        let mut records: Vec<ExportRecord> = measurements
+            .iter()
+            .map(|m| {
+                let value = m.value(calibration);
+                let area = m.area(calibration);
+                ExportRecord {
+                    kind: m.shape.name().to_string(),
+                    id: m.id,
+                    frame_id: m.frame_id,
+                    timestamp: m.timestamp.to_rfc3339(),
+                    value: value.as_ref().map(|(v, _)| *v),
+                    unit: value.map(|(_, u)| u),
+                    area: area.as_ref().map(|(v, _)| *v),
+                    area_unit: area.map(|(_, u)| u),
+                    label: m.label(calibration),
+                    points: m.shape.points().iter().map(|p| [p.x, p.y]).collect(),
+                }
+            })
+            .collect();
+
+        records.extend(annotations.iter().map(|a| ExportRecord {
+            kind: "Annotation".to_string(),
+            id: a.id,
+            frame_id: a.frame_id,
+            timestamp: a.timestamp.to_rfc3339(),
+            value: None,
+            unit: None,
+            area: None,
+            area_unit: None,
+            label: a.text.clone(),
+            points: vec![[a.position.x, a.position.y]],
+        }));
+
+        Self {
+            generated_at: chrono::Local::now().to_rfc3339(),
+            calibration: calibration.describe(),
+            pixel_spacing_mm: calibration.spacing.map(|(s, _)| [s.x_mm, s.y_mm]),
+            records,
+        }
+    }
+
+    /// Check if there is nothing to export
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Apply an anonymization profile to the free-text labels
+    pub fn anonymize(mut self, profile: AnonymizationProfile) -> Self {
+        for record in &mut self.records {
+            record.label = profile.text(&record.label);
+        }
+        self
+    }
+
+    /// Render the report in the given format
+    pub fn render(&self, format: ExportFormat) -> Result<String, serde_json::Error> {
+        match format {
+            ExportFormat::Json => serde_json::to_string_pretty(self),
+            ExportFormat::Csv => Ok(self.to_csv()),
+        }
+    }
+
+    /// Render the records as CSV (one row per measurement or annotation)
+    pub fn to_csv(&self) -> String {
+        let optional = |value: Option<f32>| value.map(|v| format!("{:.4}", v)).unwrap_or_default();
+
+        let mut csv = String::from(Self::CSV_HEADER);
+        csv.push('\n');
+        for record in &self.records {
+            let points: Vec<String> = record.points.iter().map(|[x, y]| format!("{:.4} {:.4}", x, y)).collect();
+            let row = [
+                csv_field(&record.kind),
+                record.id.to_string(),
+                record.frame_id.to_string(),
+                csv_field(&record.timestamp),
+                optional(record.value),
+                csv_field(record.unit.as_deref().unwrap_or_default()),
+                optional(record.area),
+                csv_field(record.area_unit.as_deref().unwrap_or_default()),
+                csv_field(&record.label),
+                csv_field(&points.join(";")),
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Quote a CSV field if it contains separators, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Zoom;
+    use crate::frontend::measurements::{AnnotationAction, ImagePoint, MeasurementShape};
+
+    #[test]
+    fn test_report_rows() {
+        let mut measurements = MeasurementSet::default();
+        measurements.add(
+            MeasurementShape::Distance { start: ImagePoint::new(0.0, 0.0), end: ImagePoint::new(0.3, 0.4) },
+            100,
+            100,
+            5,
+        );
+        let mut annotations = AnnotationSet::default();
+        annotations.apply(
+            AnnotationAction::Place { position: ImagePoint::new(0.5, 0.5), text: "Cyst, \"left\"".to_string(), frame_id: 6 },
+            &Zoom::default(),
+        );
+
+        let report = MeasurementReport::new(&measurements, &annotations, &Calibration::default());
+        assert_eq!(report.records.len(), 2);
+        assert!((report.records[0].value.unwrap() - 50.0).abs() < 1e-3);
+        assert_eq!(report.records[0].unit.as_deref(), Some("px"));
+
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("Distance,1,5,"));
+        assert!(lines[2].contains("\"Cyst, \"\"left\"\"\""));
+    }
+}
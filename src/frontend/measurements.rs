@@ -0,0 +1,607 @@
+// src/frontend/measurements.rs - On-Image Measurement Tools for Medical Frame Viewer
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{PixelSpacing, Zoom};
+
+/// Unit used to report lengths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LengthUnit {
+    Pixels,
+    #[default]
+    Millimeters,
+    Centimeters,
+}
+
+impl LengthUnit {
+    /// All units in UI order
+    pub const ALL: [LengthUnit; 3] = [LengthUnit::Pixels, LengthUnit::Millimeters, LengthUnit::Centimeters];
+
+    /// Get unit symbol
+    pub fn symbol(self) -> &'static str {
+        match self {
+            LengthUnit::Pixels => "px",
+            LengthUnit::Millimeters => "mm",
+            LengthUnit::Centimeters => "cm",
+        }
+    }
+
+    /// Get unit from its UI index
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Get the UI index of this unit
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|&u| u == self).unwrap_or(0)
+    }
+
+    /// Get the number of decimals shown for values in this unit
+    pub fn decimals(self) -> usize {
+        match self {
+            LengthUnit::Centimeters => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// Area and circumference (Ramanujan approximation) of an ellipse inscribed in a box
+pub fn ellipse_metrics(width: f32, height: f32) -> (f32, f32) {
+    let (a, b) = (width.abs() / 2.0, height.abs() / 2.0);
+    let area = std::f32::consts::PI * a * b;
+    let circumference = std::f32::consts::PI * (3.0 * (a + b) - ((3.0 * a + b) * (a + 3.0 * b)).sqrt());
+    (area, circumference)
+}
+
+/// Where the pixel spacing comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpacingSource {
+    Metadata,
+    Manual,
+}
+
+/// Pixel spacing and preferred unit used to label measurements
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Calibration {
+    pub spacing: Option<(PixelSpacing, SpacingSource)>,
+    pub unit: LengthUnit,
+}
+
+impl Calibration {
+    /// Scale a pixel displacement to physical proportions (unchanged when uncalibrated)
+    pub fn to_physical(&self, dx_px: f32, dy_px: f32) -> (f32, f32) {
+        match self.spacing {
+            Some((spacing, _)) => (dx_px * spacing.x_mm, dy_px * spacing.y_mm),
+            None => (dx_px, dy_px),
+        }
+    }
+
+    /// Get the unit values are reported in (pixels when uncalibrated)
+    pub fn effective_unit(&self) -> LengthUnit {
+        if self.spacing.is_some() { self.unit } else { LengthUnit::Pixels }
+    }
+
+    /// Convert a pixel displacement to the reported unit
+    pub fn scale(&self, dx_px: f32, dy_px: f32) -> (f32, f32) {
+        match (self.spacing, self.unit) {
+            (Some((spacing, _)), LengthUnit::Millimeters) => (dx_px * spacing.x_mm, dy_px * spacing.y_mm),
+            (Some((spacing, _)), LengthUnit::Centimeters) => (dx_px * spacing.x_mm / 10.0, dy_px * spacing.y_mm / 10.0),
+            _ => (dx_px, dy_px),
+        }
+    }
+
+    /// Get the length of a pixel displacement in the reported unit
+    pub fn length(&self, dx_px: f32, dy_px: f32) -> f32 {
+        let (dx, dy) = self.scale(dx_px, dy_px);
+        dx.hypot(dy)
+    }
+
+    /// Format a pixel displacement as a length, falling back to pixels when uncalibrated
+    pub fn format_length(&self, dx_px: f32, dy_px: f32) -> String {
+        let unit = self.effective_unit();
+        format!("{:.*} {}", unit.decimals(), self.length(dx_px, dy_px), unit.symbol())
+    }
+
+    /// Format the area and circumference of an ellipse inscribed in a pixel box
+    pub fn format_ellipse(&self, width_px: f32, height_px: f32) -> String {
+        let unit = self.effective_unit();
+        let (width, height) = self.scale(width_px, height_px);
+        let (area, circumference) = ellipse_metrics(width, height);
+        format!("A {:.*} {}² · C {:.*} {}",
+                unit.decimals(), area, unit.symbol(), unit.decimals(), circumference, unit.symbol())
+    }
+
+    /// Describe the calibration for the UI
+    pub fn describe(&self) -> String {
+        match self.spacing {
+            Some((spacing, source)) => {
+                let source = match source {
+                    SpacingSource::Metadata => "metadata",
+                    SpacingSource::Manual => "manual",
+                };
+                if (spacing.x_mm - spacing.y_mm).abs() < 1e-6 {
+                    format!("{:.3} mm/px ({})", spacing.x_mm, source)
+                } else {
+                    format!("{:.3} × {:.3} mm/px ({})", spacing.x_mm, spacing.y_mm, source)
+                }
+            }
+            None => "Uncalibrated - lengths in pixels".to_string(),
+        }
+    }
+}
+
+/// Point on the displayed frame as fractions of its width/height (before digital zoom)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImagePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl ImagePoint {
+    /// Create a point, clamped to the frame
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x: x.clamp(0.0, 1.0), y: y.clamp(0.0, 1.0) }
+    }
+
+    /// Convert a point picked on the zoomed view to frame coordinates
+    pub fn from_view(x: f32, y: f32, zoom: &Zoom) -> Self {
+        let view = zoom.view_rect();
+        Self::new(view.x + x * view.width, view.y + y * view.height)
+    }
+
+    /// Convert to coordinates on the zoomed view (may fall outside 0..1 when off-screen)
+    pub fn to_view(self, zoom: &Zoom) -> (f32, f32) {
+        let view = zoom.view_rect();
+        ((self.x - view.x) / view.width, (self.y - view.y) / view.height)
+    }
+}
+
+/// Geometry of a measurement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeasurementShape {
+    Distance { start: ImagePoint, end: ImagePoint },
+    /// Angle at `vertex` between the arms towards `start` and `end`
+    Angle { start: ImagePoint, vertex: ImagePoint, end: ImagePoint },
+    /// Ellipse inscribed in the box with opposite corners `start` and `end`
+    Ellipse { start: ImagePoint, end: ImagePoint },
+}
+
+impl MeasurementShape {
+    /// Get shape name for display and export
+    pub fn name(&self) -> &'static str {
+        match self {
+            MeasurementShape::Distance { .. } => "Distance",
+            MeasurementShape::Angle { .. } => "Angle",
+            MeasurementShape::Ellipse { .. } => "Ellipse",
+        }
+    }
+
+    /// Get the points of the drawn polyline in order
+    pub fn points(&self) -> Vec<ImagePoint> {
+        match *self {
+            MeasurementShape::Distance { start, end } | MeasurementShape::Ellipse { start, end } => vec![start, end],
+            MeasurementShape::Angle { start, vertex, end } => vec![start, vertex, end],
+        }
+    }
+
+    /// Convert a shape picked on the zoomed view to frame coordinates
+    pub fn to_frame(self, zoom: &Zoom) -> Self {
+        let map = |p: ImagePoint| ImagePoint::from_view(p.x, p.y, zoom);
+        match self {
+            MeasurementShape::Distance { start, end } => MeasurementShape::Distance { start: map(start), end: map(end) },
+            MeasurementShape::Angle { start, vertex, end } => MeasurementShape::Angle {
+                start: map(start),
+                vertex: map(vertex),
+                end: map(end),
+            },
+            MeasurementShape::Ellipse { start, end } => MeasurementShape::Ellipse { start: map(start), end: map(end) },
+        }
+    }
+}
+
+/// Kind of overlay drawn for a measurement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeasurementKind {
+    Distance,
+    Angle,
+    Ellipse,
+}
+
+/// A measurement placed by the operator
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub id: u64,
+    pub shape: MeasurementShape,
+    // Size in pixels of the frame the measurement was drawn on
+    pub image_width: u32,
+    pub image_height: u32,
+    pub frame_id: u64,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+}
+
+impl Measurement {
+    /// Get the displacement between two points in image pixels
+    fn delta_px(&self, from: ImagePoint, to: ImagePoint) -> (f32, f32) {
+        (
+            (to.x - from.x) * self.image_width as f32,
+            (to.y - from.y) * self.image_height as f32,
+        )
+    }
+
+    /// Get the length of the drawn outline in image pixels (circumference for ellipses)
+    pub fn length_px(&self) -> f32 {
+        if let MeasurementShape::Ellipse { start, end } = self.shape {
+            let (width, height) = self.delta_px(start, end);
+            return ellipse_metrics(width, height).1;
+        }
+        self.shape.points().windows(2)
+            .map(|pair| {
+                let (dx, dy) = self.delta_px(pair[0], pair[1]);
+                dx.hypot(dy)
+            })
+            .sum()
+    }
+
+    /// Get the angle in degrees for angle measurements (in physical space when calibrated)
+    pub fn angle_degrees(&self, calibration: &Calibration) -> Option<f32> {
+        let MeasurementShape::Angle { start, vertex, end } = self.shape else {
+            return None;
+        };
+        let (ax, ay) = {
+            let (dx, dy) = self.delta_px(vertex, start);
+            calibration.to_physical(dx, dy)
+        };
+        let (bx, by) = {
+            let (dx, dy) = self.delta_px(vertex, end);
+            calibration.to_physical(dx, dy)
+        };
+        if (ax == 0.0 && ay == 0.0) || (bx == 0.0 && by == 0.0) {
+            return None;
+        }
+        Some((ax * by - ay * bx).abs().atan2(ax * bx + ay * by).to_degrees())
+    }
+
+    /// Get the primary value and its unit: length, angle in degrees or ellipse circumference
+    pub fn value(&self, calibration: &Calibration) -> Option<(f32, String)> {
+        let unit = calibration.effective_unit().symbol().to_string();
+        match self.shape {
+            MeasurementShape::Distance { start, end } => {
+                let (dx, dy) = self.delta_px(start, end);
+                Some((calibration.length(dx, dy), unit))
+            }
+            MeasurementShape::Angle { .. } => self.angle_degrees(calibration).map(|d| (d, "deg".to_string())),
+            MeasurementShape::Ellipse { start, end } => {
+                let (width, height) = self.delta_px(start, end);
+                let (width, height) = calibration.scale(width, height);
+                Some((ellipse_metrics(width, height).1, unit))
+            }
+        }
+    }
+
+    /// Get the enclosed area and its unit for ellipses
+    pub fn area(&self, calibration: &Calibration) -> Option<(f32, String)> {
+        let MeasurementShape::Ellipse { start, end } = self.shape else {
+            return None;
+        };
+        let (width, height) = self.delta_px(start, end);
+        let (width, height) = calibration.scale(width, height);
+        Some((ellipse_metrics(width, height).0, format!("{}²", calibration.effective_unit().symbol())))
+    }
+
+    /// Get the on-image label in calibrated units
+    pub fn label(&self, calibration: &Calibration) -> String {
+        match self.shape {
+            MeasurementShape::Distance { start, end } => {
+                let (dx, dy) = self.delta_px(start, end);
+                calibration.format_length(dx, dy)
+            }
+            MeasurementShape::Angle { .. } => match self.angle_degrees(calibration) {
+                Some(degrees) => format!("{:.1}°", degrees),
+                None => "-".to_string(),
+            },
+            MeasurementShape::Ellipse { start, end } => {
+                let (width, height) = self.delta_px(start, end);
+                calibration.format_ellipse(width, height)
+            }
+        }
+    }
+
+    /// Get the overlay geometry for the current zoom
+    pub fn overlay(&self, zoom: &Zoom, calibration: &Calibration) -> MeasurementOverlay {
+        let (kind, points) = match self.shape {
+            MeasurementShape::Distance { start, end } => (MeasurementKind::Distance, [start, end, end]),
+            MeasurementShape::Angle { start, vertex, end } => (MeasurementKind::Angle, [start, vertex, end]),
+            MeasurementShape::Ellipse { start, end } => (MeasurementKind::Ellipse, [start, end, end]),
+        };
+        let (x1, y1) = points[0].to_view(zoom);
+        let (x2, y2) = points[1].to_view(zoom);
+        let (x3, y3) = points[2].to_view(zoom);
+        MeasurementOverlay { id: self.id, kind, x1, y1, x2, y2, x3, y3, label: self.label(calibration) }
+    }
+}
+
+/// Measurement geometry in view coordinates, ready for the UI overlay
+///
+/// Distances use the first two points, ellipses are inscribed in the box they span;
+/// angles have their vertex at the second point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasurementOverlay {
+    pub id: u64,
+    pub kind: MeasurementKind,
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+    pub x3: f32,
+    pub y3: f32,
+    pub label: String,
+}
+
+/// Measurements placed during the session (kept across frame updates)
+#[derive(Debug, Clone, Default)]
+pub struct MeasurementSet {
+    items: Vec<Measurement>,
+    next_id: u64,
+}
+
+impl MeasurementSet {
+    /// Add a measurement and return it
+    pub fn add(&mut self, shape: MeasurementShape, image_width: u32, image_height: u32, frame_id: u64) -> &Measurement {
+        self.next_id += 1;
+        self.items.push(Measurement {
+            id: self.next_id,
+            shape,
+            image_width,
+            image_height,
+            frame_id,
+            timestamp: chrono::Local::now(),
+        });
+        self.items.last().expect("measurement just added")
+    }
+
+    /// Remove a measurement by id
+    pub fn remove(&mut self, id: u64) -> bool {
+        let before = self.items.len();
+        self.items.retain(|m| m.id != id);
+        self.items.len() != before
+    }
+
+    /// Remove all measurements
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Iterate over measurements in placement order
+    pub fn iter(&self) -> impl Iterator<Item = &Measurement> {
+        self.items.iter()
+    }
+
+    /// Get number of measurements
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if there are no measurements
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get overlay geometry for all measurements at the current zoom
+    pub fn overlays(&self, zoom: &Zoom, calibration: &Calibration) -> Vec<MeasurementOverlay> {
+        self.items.iter().map(|m| m.overlay(zoom, calibration)).collect()
+    }
+}
+
+/// Text annotation pinned to a point on the frame
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub id: u64,
+    pub position: ImagePoint,
+    pub text: String,
+    pub frame_id: u64,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+}
+
+/// Annotation position in view coordinates, ready for the UI overlay
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationOverlay {
+    pub id: u64,
+    pub x: f32,
+    pub y: f32,
+    pub text: String,
+}
+
+/// Annotation edit from the image view (positions in view coordinates)
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationAction {
+    Place { position: ImagePoint, text: String, frame_id: u64 },
+    Move { id: u64, position: ImagePoint },
+    Edit { id: u64, text: String },
+    Delete { id: u64 },
+}
+
+/// Annotations placed during the session (kept across frame updates)
+#[derive(Debug, Clone, Default)]
+pub struct AnnotationSet {
+    items: Vec<Annotation>,
+    next_id: u64,
+}
+
+impl AnnotationSet {
+    /// Apply an edit made on the zoomed view, returning whether anything changed
+    pub fn apply(&mut self, action: AnnotationAction, zoom: &Zoom) -> bool {
+        match action {
+            AnnotationAction::Place { position, text, frame_id } => {
+                let text = text.trim();
+                if text.is_empty() {
+                    return false;
+                }
+                self.next_id += 1;
+                self.items.push(Annotation {
+                    id: self.next_id,
+                    position: ImagePoint::from_view(position.x, position.y, zoom),
+                    text: text.to_string(),
+                    frame_id,
+                    timestamp: chrono::Local::now(),
+                });
+                true
+            }
+            AnnotationAction::Move { id, position } => match self.items.iter_mut().find(|a| a.id == id) {
+                Some(annotation) => {
+                    annotation.position = ImagePoint::from_view(position.x, position.y, zoom);
+                    true
+                }
+                None => false,
+            },
+            AnnotationAction::Edit { id, text } => {
+                // Clearing the text removes the annotation
+                if text.trim().is_empty() {
+                    return self.remove(id);
+                }
+                match self.items.iter_mut().find(|a| a.id == id) {
+                    Some(annotation) => {
+                        annotation.text = text.trim().to_string();
+                        true
+                    }
+                    None => false,
+                }
+            }
+            AnnotationAction::Delete { id } => self.remove(id),
+        }
+    }
+
+    /// Remove an annotation by id
+    pub fn remove(&mut self, id: u64) -> bool {
+        let before = self.items.len();
+        self.items.retain(|a| a.id != id);
+        self.items.len() != before
+    }
+
+    /// Remove all annotations
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Iterate over annotations in placement order
+    pub fn iter(&self) -> impl Iterator<Item = &Annotation> {
+        self.items.iter()
+    }
+
+    /// Get number of annotations
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if there are no annotations
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get overlay positions for all annotations at the current zoom
+    pub fn overlays(&self, zoom: &Zoom) -> Vec<AnnotationOverlay> {
+        self.items
+            .iter()
+            .map(|a| {
+                let (x, y) = a.position.to_view(zoom);
+                AnnotationOverlay { id: a.id, x, y, text: a.text.clone() }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_measurement() {
+        let mut set = MeasurementSet::default();
+        let shape = MeasurementShape::Distance {
+            start: ImagePoint::new(0.0, 0.0),
+            end: ImagePoint::new(0.3, 0.4),
+        };
+        let measurement = set.add(shape, 100, 100, 7);
+        assert!((measurement.length_px() - 50.0).abs() < 1e-3);
+        assert_eq!(measurement.label(&Calibration::default()), "50.0 px");
+
+        let calibration = Calibration {
+            spacing: PixelSpacing::isotropic(0.2).map(|s| (s, SpacingSource::Manual)),
+            unit: LengthUnit::Centimeters,
+        };
+        assert_eq!(measurement.label(&calibration), "1.00 cm");
+
+        let id = measurement.id;
+        assert!(set.remove(id));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_angle_measurement() {
+        let mut set = MeasurementSet::default();
+        let shape = MeasurementShape::Angle {
+            start: ImagePoint::new(0.5, 0.0),
+            vertex: ImagePoint::new(0.0, 0.0),
+            end: ImagePoint::new(0.5, 0.5),
+        };
+        let measurement = set.add(shape, 100, 100, 1);
+        assert_eq!(measurement.label(&Calibration::default()), "45.0°");
+
+        // Anisotropic pixels change the physical angle
+        let calibration = Calibration {
+            spacing: PixelSpacing::new(0.1, 0.2).map(|s| (s, SpacingSource::Metadata)),
+            unit: LengthUnit::Millimeters,
+        };
+        let degrees = measurement.angle_degrees(&calibration).unwrap();
+        assert!((degrees - 63.435).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_ellipse_measurement() {
+        let mut set = MeasurementSet::default();
+        let shape = MeasurementShape::Ellipse {
+            start: ImagePoint::new(0.5, 0.5),
+            end: ImagePoint::new(0.0, 0.0),
+        };
+        let measurement = set.add(shape, 200, 100, 1);
+        assert!((measurement.length_px() - 242.2).abs() < 0.1);
+        assert_eq!(measurement.label(&Calibration::default()), "A 3927.0 px² · C 242.2 px");
+
+        let calibration = Calibration {
+            spacing: PixelSpacing::isotropic(0.1).map(|s| (s, SpacingSource::Manual)),
+            unit: LengthUnit::Millimeters,
+        };
+        assert_eq!(measurement.label(&calibration), "A 39.3 mm² · C 24.2 mm");
+    }
+
+    #[test]
+    fn test_annotation_actions() {
+        let mut set = AnnotationSet::default();
+        let zoom = Zoom::default();
+        let place = |text: &str| AnnotationAction::Place {
+            position: ImagePoint::new(0.5, 0.5),
+            text: text.to_string(),
+            frame_id: 3,
+        };
+
+        assert!(!set.apply(place("   "), &zoom));
+        assert!(set.apply(place(" Liver "), &zoom));
+        let id = set.iter().next().unwrap().id;
+        assert_eq!(set.iter().next().unwrap().text, "Liver");
+
+        assert!(set.apply(AnnotationAction::Move { id, position: ImagePoint::new(0.1, 0.2) }, &zoom));
+        assert_eq!(set.overlays(&zoom)[0].x, 0.1);
+
+        assert!(set.apply(AnnotationAction::Edit { id, text: String::new() }, &zoom));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_zoom_mapping() {
+        let zoom = Zoom::new(2.0, 0.25, 0.25);
+        let point = ImagePoint::from_view(1.0, 1.0, &zoom);
+        assert!((point.x - 0.5).abs() < 1e-6 && (point.y - 0.5).abs() < 1e-6);
+
+        let (x, y) = point.to_view(&zoom);
+        assert!((x - 1.0).abs() < 1e-6 && (y - 1.0).abs() < 1e-6);
+    }
+}
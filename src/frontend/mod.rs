@@ -1,47 +1,80 @@
 // src/frontend/mod.rs - Frontend Module for Medical Frame Viewer
 
+pub mod access;
+#[cfg(feature = "ui")]
 pub mod app;
+#[cfg(feature = "ui")]
 pub mod slint_bridge;
+#[cfg(feature = "ui")]
 pub mod image_converter;
+#[cfg(feature = "egui")]
+pub mod egui_viewer;
+pub mod headless;
 pub mod ui_state;
-
+pub mod notifications;
+pub mod measurements;
+pub mod measurement_export;
+pub mod rulers;
+pub mod touch_gestures;
+pub mod latency;
+pub mod session_report;
+pub mod audit_log;
+pub mod scripting;
+#[cfg(feature = "ui")]
+pub mod gpu_renderer;
+
+#[cfg(feature = "ui")]
 pub use app::MedicalFrameApp;
-pub use slint_bridge::SlintBridge;
+#[cfg(feature = "ui")]
+pub use slint_bridge::{SlintBridge, WindowOptions};
+#[cfg(feature = "ui")]
 pub use image_converter::ImageConverter;
-pub use ui_state::UiState;
+pub use ui_state::{HudCorner, Theme, UiSettings, UiState};
+pub use notifications::{NotificationLevel, NotificationManager};
 
+#[cfg(feature = "ui")]
 use std::sync::Arc;
-use tokio::sync::{mpsc, broadcast};
-use tracing::{info, error};
-
+#[cfg(feature = "ui")]
+use async_trait::async_trait;
+#[cfg(feature = "ui")]
+use tokio::sync::mpsc;
+#[cfg(feature = "ui")]
+use tracing::{debug, info, warn, error};
+
+#[cfg(feature = "ui")]
 use crate::backend::{
-    MedicalFrameBackend, BackendCommand, BackendEvent, BackendConfig
+    MedicalFrameBackend, BackendCommand, BackendEvent, BackendConfig, FrameSink, GpuFrame, ProcessedFrame, RgbaBuffer
 };
+#[cfg(feature = "ui")]
 use crate::frontend::image_converter::ImageConversionError;
+#[cfg(feature = "ui")]
 use crate::frontend::slint_bridge::SlintBridgeError;
 
 /// Frontend command for internal communication
+#[cfg(feature = "ui")]
 #[derive(Debug, Clone)]
 pub enum FrontendCommand {
-    /// Update UI with new frame data (raw data, not Slint Image)
+    /// Update UI with a new frame (pixel buffer, not Slint Image)
     UpdateFrame {
-        frame_data: Arc<[u8]>,
-        width: u32,
-        height: u32,
+        frame_data: RgbaBuffer,
+        /// Unconverted frame for the GPU upload path (frame_data is then empty)
+        gpu_source: Option<GpuFrame>,
         frame_id: u64,
         sequence_number: u64,
+        timestamp_ns: u64,
         resolution: String,
         format: String,
     },
     /// Update connection status
     UpdateConnectionStatus(String, bool),
     /// Update statistics
-    UpdateStatistics(f64, f64, u64),
+    UpdateStatistics(f64, u64),
     /// Clear frame display
     ClearFrame,
 }
 
 /// Frontend service that manages the Slint UI and communicates with backend
+#[cfg(feature = "ui")]
 pub struct MedicalFrameFrontend {
     // Backend communication
     backend: Arc<MedicalFrameBackend>,
@@ -51,14 +84,136 @@ pub struct MedicalFrameFrontend {
     slint_bridge: Arc<SlintBridge>,
     ui_state: Arc<tokio::sync::RwLock<UiState>>,
 
-    // Image processing
-    image_converter: Arc<ImageConverter>,
-
     // Internal frontend communication
     frontend_command_tx: mpsc::UnboundedSender<FrontendCommand>,
     frontend_command_rx: Option<mpsc::UnboundedReceiver<FrontendCommand>>,
 }
 
+/// Turns backend frames and events into frontend commands
+#[cfg(feature = "ui")]
+struct FrontendSink {
+    ui_state: Arc<tokio::sync::RwLock<UiState>>,
+    frontend_command_tx: mpsc::UnboundedSender<FrontendCommand>,
+}
+
+#[cfg(feature = "ui")]
+#[async_trait]
+impl FrameSink for FrontendSink {
+    async fn on_frame(&mut self, frame: ProcessedFrame) {
+        MedicalFrameFrontend::show_frame(frame, &self.ui_state, &self.frontend_command_tx).await;
+    }
+
+    async fn on_event(&mut self, event: BackendEvent) {
+        match event {
+            BackendEvent::Connected => {
+                info!("✅ Backend connected");
+
+                // Update UI state
+                {
+                    let mut state = self.ui_state.write().await;
+                    state.is_connected = true;
+                    state.connection_status = "Connected".to_string();
+                }
+
+                // Send frontend command
+                let _ = self.frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus("Connected".to_string(), true));
+            }
+
+            BackendEvent::Disconnected => {
+                info!("🔌 Backend disconnected");
+
+                // Update UI state
+                {
+                    let mut state = self.ui_state.write().await;
+                    state.is_connected = false;
+                    state.connection_status = "Disconnected".to_string();
+                    state.has_frame = false;
+                }
+
+                // Send frontend commands
+                let _ = self.frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus("Disconnected".to_string(), false));
+                let _ = self.frontend_command_tx.send(FrontendCommand::ClearFrame);
+            }
+
+            BackendEvent::ConnectionError(error) => {
+                error!("❌ Backend connection error: {}", error);
+
+                // Update UI state
+                {
+                    let mut state = self.ui_state.write().await;
+                    state.is_connected = false;
+                    state.connection_status = format!("Error: {}", error);
+                }
+
+                // Send frontend command
+                let _ = self.frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus(format!("Error: {}", error), false));
+            }
+
+            BackendEvent::ConnectionLost => {
+                info!("⚠️ Backend connection lost, attempting reconnection");
+
+                // Update UI state
+                {
+                    let mut state = self.ui_state.write().await;
+                    state.connection_status = "Reconnecting...".to_string();
+                }
+
+                // Send frontend command
+                let _ = self.frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus("Reconnecting...".to_string(), false));
+            }
+
+            BackendEvent::WaitingForProducer(shm_name) => {
+                let status = format!("Waiting for producer: {}", shm_name);
+                {
+                    let mut state = self.ui_state.write().await;
+                    state.is_connected = false;
+                    state.connection_status = status.clone();
+                }
+
+                let _ = self.frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus(status, false));
+            }
+
+            BackendEvent::Recovered(report) => {
+                warn!("🐕 Pipeline watchdog: {}", report);
+                let status = if report.recovered() { "Connected" } else { "Recovering stalled pipeline..." };
+                {
+                    let mut state = self.ui_state.write().await;
+                    state.is_connected = report.recovered();
+                    state.connection_status = status.to_string();
+                }
+
+                let _ = self.frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus(status.to_string(), report.recovered()));
+            }
+
+            BackendEvent::StatisticsUpdate(stats) => {
+                // Update UI state with statistics
+                {
+                    let mut state = self.ui_state.write().await;
+                    state.fps = stats.current_fps as f32;
+                    state.latency_ms = stats.average_latency_ms as f32;
+                    state.total_frames = stats.total_frames_received as i32;
+                }
+
+                // Send frontend command
+                let _ = self.frontend_command_tx.send(FrontendCommand::UpdateStatistics(
+                    stats.current_fps,
+                    stats.total_frames_received,
+                ));
+            }
+
+            BackendEvent::SettingsChanged => {
+                info!("⚙️ Backend settings changed");
+                // Handle settings changes if needed
+            }
+
+            BackendEvent::DetectionsReceived(detections) => {
+                debug!("🎯 Detections for frame {} (not drawn by this frontend)", detections.frame_id);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ui")]
 impl MedicalFrameFrontend {
     /// Create a new frontend service
     pub fn new(backend_config: BackendConfig) -> Result<Self, FrontendError> {
@@ -71,7 +226,7 @@ impl MedicalFrameFrontend {
         // Create UI components
         let slint_bridge = Arc::new(SlintBridge::new()?);
         let ui_state = Arc::new(tokio::sync::RwLock::new(UiState::new()));
-        let image_converter = Arc::new(ImageConverter::new());
+        slint_bridge.set_frame_pool(backend.pixel_pool());
 
         // Create internal command channel
         let (frontend_command_tx, frontend_command_rx) = mpsc::unbounded_channel();
@@ -81,7 +236,6 @@ impl MedicalFrameFrontend {
             command_sender,
             slint_bridge,
             ui_state,
-            image_converter,
             frontend_command_tx,
             frontend_command_rx: Some(frontend_command_rx),
         })
@@ -92,7 +246,7 @@ impl MedicalFrameFrontend {
         info!("🚀 Starting MiVi Medical Frame Frontend");
 
         // Start the backend
-        self.backend.start().await
+        let backend_handle = self.backend.start().await
             .map_err(|e| FrontendError::Backend(e.to_string()))?;
 
         // Setup UI event handlers
@@ -107,11 +261,10 @@ impl MedicalFrameFrontend {
 
         // Start frontend command processing loop in main thread
         let slint_bridge = Arc::clone(&self.slint_bridge);
-        let image_converter = Arc::clone(&self.image_converter);
 
         tokio::spawn(async move {
             while let Some(cmd) = frontend_command_rx.recv().await {
-                if let Err(e) = Self::handle_frontend_command(cmd, &slint_bridge, &image_converter).await {
+                if let Err(e) = Self::handle_frontend_command(cmd, &slint_bridge).await {
                     error!("Failed to handle frontend command: {}", e);
                 }
             }
@@ -119,59 +272,41 @@ impl MedicalFrameFrontend {
 
         // Run the Slint UI (blocks until UI closes)
         info!("🎨 Starting Slint UI");
-        self.slint_bridge.run().await?;
+        let ui_result = self.slint_bridge.run().await;
 
         // Cleanup
         event_processor.abort();
+        if let Err(e) = backend_handle.shutdown().await {
+            warn!("Backend shutdown failed: {}", e);
+        }
         info!("✅ MiVi Medical Frame Frontend stopped");
 
-        Ok(())
+        ui_result.map_err(Into::into)
     }
 
     /// Handle frontend commands (runs on main thread)
     async fn handle_frontend_command(
         command: FrontendCommand,
         slint_bridge: &Arc<SlintBridge>,
-        image_converter: &Arc<ImageConverter>,
     ) -> Result<(), FrontendError> {
         match command {
-            FrontendCommand::UpdateFrame { frame_data, width, height, frame_id, sequence_number, resolution, format } => {
-                // Convert raw data to Slint image on main thread
-                match image_converter.create_slint_image_from_rgba(&frame_data, width, height) {
-                    Ok(slint_image) => {
-                        slint_bridge.update_frame(
-                            slint_image,
-                            &resolution,
-                            &format,
-                            frame_id as i32,
-                            sequence_number as i32,
-                        ).await?;
-                    }
-                    Err(e) => {
-                        error!("Failed to create Slint image: {}", e);
-                        // Create error image
-                        match image_converter.create_error_image(width, height, &e.to_string()).await {
-                            Ok(error_image) => {
-                                slint_bridge.update_frame(
-                                    error_image,
-                                    &resolution,
-                                    "Error",
-                                    frame_id as i32,
-                                    sequence_number as i32,
-                                ).await?;
-                            }
-                            Err(ie) => {
-                                error!("Failed to create error image: {}", ie);
-                            }
-                        }
-                    }
-                }
+            FrontendCommand::UpdateFrame { frame_data, gpu_source, frame_id, sequence_number, timestamp_ns, resolution, format } => {
+                // The pooled buffer becomes the displayed image as-is
+                slint_bridge.update_frame(
+                    frame_data,
+                    gpu_source,
+                    &resolution,
+                    &format,
+                    frame_id as i32,
+                    sequence_number as i32,
+                    timestamp_ns,
+                ).await?;
             }
             FrontendCommand::UpdateConnectionStatus(status, connected) => {
                 slint_bridge.update_connection_status(&status, connected).await?;
             }
-            FrontendCommand::UpdateStatistics(fps, latency, total_frames) => {
-                slint_bridge.update_statistics(fps as f32, latency as f32, total_frames as i32).await?;
+            FrontendCommand::UpdateStatistics(fps, total_frames) => {
+                slint_bridge.update_statistics(fps as f32, total_frames as i32).await?;
             }
             FrontendCommand::ClearFrame => {
                 slint_bridge.clear_frame().await?;
@@ -241,122 +376,40 @@ impl MedicalFrameFrontend {
 
     /// Start event processing from backend (background thread)
     async fn start_event_processing(&mut self) -> tokio::task::JoinHandle<()> {
-        let mut event_receiver = self.backend.get_event_receiver();
-        let ui_state = Arc::clone(&self.ui_state);
-        let frontend_command_tx = self.frontend_command_tx.clone();
+        info!("🔄 Starting backend event processing");
+        self.backend.spawn_sink(FrontendSink {
+            ui_state: Arc::clone(&self.ui_state),
+            frontend_command_tx: self.frontend_command_tx.clone(),
+        })
+    }
 
-        tokio::spawn(async move {
-            info!("🔄 Starting backend event processing");
-
-            while let Ok(event) = event_receiver.recv().await {
-                match event {
-                    BackendEvent::Connected => {
-                        info!("✅ Backend connected");
-
-                        // Update UI state
-                        {
-                            let mut state = ui_state.write().await;
-                            state.is_connected = true;
-                            state.connection_status = "Connected".to_string();
-                        }
-
-                        // Send frontend command
-                        let _ = frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus("Connected".to_string(), true));
-                    }
-
-                    BackendEvent::Disconnected => {
-                        info!("🔌 Backend disconnected");
-
-                        // Update UI state
-                        {
-                            let mut state = ui_state.write().await;
-                            state.is_connected = false;
-                            state.connection_status = "Disconnected".to_string();
-                            state.has_frame = false;
-                        }
-
-                        // Send frontend commands
-                        let _ = frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus("Disconnected".to_string(), false));
-                        let _ = frontend_command_tx.send(FrontendCommand::ClearFrame);
-                    }
-
-                    BackendEvent::ConnectionError(error) => {
-                        error!("❌ Backend connection error: {}", error);
-
-                        // Update UI state
-                        {
-                            let mut state = ui_state.write().await;
-                            state.is_connected = false;
-                            state.connection_status = format!("Error: {}", error);
-                        }
-
-                        // Send frontend command
-                        let _ = frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus(format!("Error: {}", error), false));
-                    }
-
-                    BackendEvent::ConnectionLost => {
-                        info!("⚠️ Backend connection lost, attempting reconnection");
-
-                        // Update UI state
-                        {
-                            let mut state = ui_state.write().await;
-                            state.connection_status = "Reconnecting...".to_string();
-                        }
-
-                        // Send frontend command
-                        let _ = frontend_command_tx.send(FrontendCommand::UpdateConnectionStatus("Reconnecting...".to_string(), false));
-                    }
-
-                    BackendEvent::NewFrame(processed_frame) => {
-                        // Update UI state
-                        {
-                            let mut state = ui_state.write().await;
-                            state.has_frame = true;
-                            state.frame_id = processed_frame.header.frame_id as i32;
-                            state.sequence_number = processed_frame.header.sequence_number as i32;
-                            state.resolution = processed_frame.resolution_string();
-                            state.frame_format = processed_frame.format_string();
-                            state.last_frame_time = std::time::Instant::now();
-                        }
-
-                        // Send frontend command with raw data (avoid sending Slint Image across threads)
-                        let _ = frontend_command_tx.send(FrontendCommand::UpdateFrame {
-                            frame_data: processed_frame.rgb_data.clone(),
-                            width: processed_frame.header.width,
-                            height: processed_frame.header.height,
-                            frame_id: processed_frame.header.frame_id,
-                            sequence_number: processed_frame.header.sequence_number,
-                            resolution: processed_frame.resolution_string(),
-                            format: processed_frame.format_string(),
-                        });
-                    }
-
-                    BackendEvent::StatisticsUpdate(stats) => {
-                        // Update UI state with statistics
-                        {
-                            let mut state = ui_state.write().await;
-                            state.fps = stats.current_fps as f32;
-                            state.latency_ms = stats.average_latency_ms as f32;
-                            state.total_frames = stats.total_frames_received as i32;
-                        }
-
-                        // Send frontend command
-                        let _ = frontend_command_tx.send(FrontendCommand::UpdateStatistics(
-                            stats.current_fps,
-                            stats.average_latency_ms,
-                            stats.total_frames_received,
-                        ));
-                    }
-
-                    BackendEvent::SettingsChanged => {
-                        info!("⚙️ Backend settings changed");
-                        // Handle settings changes if needed
-                    }
-                }
-            }
+    /// Show a frame from the backend's latest-frame channel
+    async fn show_frame(
+        processed_frame: ProcessedFrame,
+        ui_state: &Arc<tokio::sync::RwLock<UiState>>,
+        frontend_command_tx: &mpsc::UnboundedSender<FrontendCommand>,
+    ) {
+        // Update UI state
+        {
+            let mut state = ui_state.write().await;
+            state.has_frame = true;
+            state.frame_id = processed_frame.header.frame_id as i32;
+            state.sequence_number = processed_frame.header.sequence_number as i32;
+            state.resolution = processed_frame.resolution_string();
+            state.frame_format = processed_frame.format_string();
+            state.last_frame_time = std::time::Instant::now();
+        }
 
-            info!("🔄 Backend event processing stopped");
-        })
+        // Send frontend command with the pixel buffer (a Slint Image can't cross threads)
+        let _ = frontend_command_tx.send(FrontendCommand::UpdateFrame {
+            frame_data: processed_frame.rgb_data.clone(),
+            gpu_source: processed_frame.gpu_source.clone(),
+            frame_id: processed_frame.header.frame_id,
+            sequence_number: processed_frame.header.sequence_number,
+            timestamp_ns: processed_frame.header.timestamp,
+            resolution: processed_frame.resolution_string(),
+            format: processed_frame.format_string(),
+        });
     }
 
     /// Send a command to the backend
@@ -382,6 +435,7 @@ impl MedicalFrameFrontend {
 }
 
 // Add method to ImageConverter for creating Slint images from raw RGBA data
+#[cfg(feature = "ui")]
 impl ImageConverter {
     /// Create Slint image from raw RGBA data (helper method)
     pub fn create_slint_image_from_rgba(&self, rgba_data: &[u8], width: u32, height: u32) -> Result<slint::Image, ImageConversionError> {
@@ -411,12 +465,14 @@ pub enum FrontendError {
     Other(String),
 }
 
+#[cfg(feature = "ui")]
 impl From<SlintBridgeError> for FrontendError {
     fn from(err: SlintBridgeError) -> Self {
         FrontendError::Slint(err.to_string())
     }
 }
 
+#[cfg(feature = "ui")]
 impl From<crate::frontend::image_converter::ImageConversionError> for FrontendError {
     fn from(err: crate::frontend::image_converter::ImageConversionError) -> Self {
         FrontendError::ImageConversion(err.to_string())
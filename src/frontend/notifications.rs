@@ -0,0 +1,322 @@
+// src/frontend/notifications.rs - Operator Notification Queue for Medical Frame Viewer
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::error::{ErrorCategory, ErrorSeverity, MiViError};
+
+/// Severity of an operator notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    /// Get how long a toast of this level stays on screen (None = until dismissed)
+    pub fn default_timeout(self) -> Option<Duration> {
+        match self {
+            NotificationLevel::Info => Some(Duration::from_secs(4)),
+            NotificationLevel::Warning => Some(Duration::from_secs(8)),
+            NotificationLevel::Error => None,
+        }
+    }
+
+    /// Get the numeric level used by the Slint UI
+    pub fn ui_level(self) -> i32 {
+        match self {
+            NotificationLevel::Info => 0,
+            NotificationLevel::Warning => 1,
+            NotificationLevel::Error => 2,
+        }
+    }
+
+    /// Get the toast level for an error below critical severity
+    pub fn for_severity(severity: ErrorSeverity) -> Self {
+        match severity {
+            ErrorSeverity::Low => NotificationLevel::Info,
+            ErrorSeverity::Medium => NotificationLevel::Warning,
+            ErrorSeverity::High | ErrorSeverity::Critical => NotificationLevel::Error,
+        }
+    }
+
+    /// Get level icon for UI and logs
+    pub fn icon(self) -> &'static str {
+        match self {
+            NotificationLevel::Info => "ℹ️",
+            NotificationLevel::Warning => "⚠️",
+            NotificationLevel::Error => "❌",
+        }
+    }
+}
+
+/// A single operator notification
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: u64,
+    pub level: NotificationLevel,
+    pub message: String,
+    /// Technical details shown on request
+    pub details: Option<String>,
+    pub created_at: Instant,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub timeout: Option<Duration>,
+}
+
+impl Notification {
+    /// Check if the notification has outlived its timeout
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.timeout
+            .map_or(false, |timeout| now.duration_since(self.created_at) >= timeout)
+    }
+
+    /// Get the wall-clock time for display
+    pub fn time_string(&self) -> String {
+        self.timestamp.format("%H:%M:%S").to_string()
+    }
+}
+
+/// Queue of visible toasts plus a bounded history
+#[derive(Debug)]
+pub struct NotificationManager {
+    active: VecDeque<Notification>,
+    history: VecDeque<Notification>,
+    next_id: u64,
+    max_active: usize,
+    max_history: usize,
+    toasts_enabled: bool,
+}
+
+impl NotificationManager {
+    /// Create a new notification manager
+    pub fn new(max_active: usize, max_history: usize) -> Self {
+        Self {
+            active: VecDeque::with_capacity(max_active),
+            history: VecDeque::with_capacity(max_history),
+            next_id: 1,
+            max_active: max_active.max(1),
+            max_history: max_history.max(1),
+            toasts_enabled: true,
+        }
+    }
+
+    /// Queue a notification with the default timeout of its level
+    pub fn push(&mut self, level: NotificationLevel, message: impl Into<String>) -> u64 {
+        self.push_with_timeout(level, message, level.default_timeout())
+    }
+
+    /// Queue a notification with an explicit timeout
+    pub fn push_with_timeout(
+        &mut self,
+        level: NotificationLevel,
+        message: impl Into<String>,
+        timeout: Option<Duration>,
+    ) -> u64 {
+        self.insert(level, message.into(), None, timeout, true)
+    }
+
+    /// Queue a notification with technical details behind an expander
+    pub fn push_with_details(&mut self, level: NotificationLevel, message: impl Into<String>, details: impl Into<String>) -> u64 {
+        self.insert(level, message.into(), Some(details.into()), level.default_timeout(), true)
+    }
+
+    /// Record a notification in the history without a toast, for errors shown elsewhere
+    pub fn record(&mut self, level: NotificationLevel, message: impl Into<String>, details: impl Into<String>) -> u64 {
+        self.insert(level, message.into(), Some(details.into()), None, false)
+    }
+
+    fn insert(
+        &mut self,
+        level: NotificationLevel,
+        message: String,
+        details: Option<String>,
+        timeout: Option<Duration>,
+        toast: bool,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let notification = Notification {
+            id,
+            level,
+            message,
+            details,
+            created_at: Instant::now(),
+            timestamp: chrono::Local::now(),
+            timeout,
+        };
+
+        // History keeps everything, newest first
+        self.history.push_front(notification.clone());
+        self.history.truncate(self.max_history);
+
+        if toast && self.toasts_enabled {
+            // Stack newest on top, evicting the oldest toast of the lowest severity first
+            if self.active.len() >= self.max_active {
+                let evict = self
+                    .active
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, n)| (n.level, n.id))
+                    .map(|(index, _)| index);
+                if let Some(index) = evict {
+                    self.active.remove(index);
+                }
+            }
+            self.active.push_front(notification);
+        }
+
+        id
+    }
+
+    /// Dismiss a visible toast, returning true if it was shown
+    pub fn dismiss(&mut self, id: u64) -> bool {
+        let before = self.active.len();
+        self.active.retain(|n| n.id != id);
+        self.active.len() != before
+    }
+
+    /// Remove expired toasts, returning true if anything changed
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let before = self.active.len();
+        self.active.retain(|n| !n.is_expired(now));
+        self.active.len() != before
+    }
+
+    /// Enable or disable popup toasts (history is always recorded)
+    pub fn set_toasts_enabled(&mut self, enabled: bool) {
+        self.toasts_enabled = enabled;
+        if !enabled {
+            self.active.clear();
+        }
+    }
+
+    /// Clear the notification history
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Get visible toasts, newest first
+    pub fn active(&self) -> impl Iterator<Item = &Notification> {
+        self.active.iter()
+    }
+
+    /// Get notification history, newest first
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter()
+    }
+
+    /// Get number of visible toasts
+    pub fn active_count(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Get number of entries in the history
+    pub fn history_count(&self) -> usize {
+        self.history.len()
+    }
+}
+
+impl Default for NotificationManager {
+    fn default() -> Self {
+        Self::new(4, 100)
+    }
+}
+
+/// What the operator is shown for an error
+///
+/// Critical errors open a modal dialog; anything less becomes a toast.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorNotice {
+    pub severity: ErrorSeverity,
+    pub title: String,
+    pub message: String,
+    pub action: String,
+    /// Raw error, code and classification for support
+    pub details: String,
+}
+
+impl ErrorNotice {
+    /// Build the notice from the user-facing APIs of the error
+    pub fn from_error(error: &MiViError) -> Self {
+        let severity = error.severity();
+        Self {
+            severity,
+            title: match error.category() {
+                ErrorCategory::Backend => "Frame pipeline error",
+                ErrorCategory::Frontend => "Display error",
+                ErrorCategory::MedicalDevice => "Medical device error",
+                ErrorCategory::Configuration => "Configuration error",
+                ErrorCategory::Network => "Network error",
+                ErrorCategory::System => "System error",
+                ErrorCategory::Other => "Error",
+            }
+            .to_string(),
+            message: error.user_message(),
+            action: error.suggested_action().to_string(),
+            details: format!("{}\nCode {} · {} · {}", error, error.error_code(), error.category(), severity),
+        }
+    }
+
+    /// Check if the error needs a modal dialog
+    pub fn is_modal(&self) -> bool {
+        self.severity == ErrorSeverity::Critical
+    }
+
+    /// Toast text: the message followed by the suggested action
+    pub fn toast_message(&self) -> String {
+        format!("{} {}.", self.message, self.action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stacking_and_eviction() {
+        let mut manager = NotificationManager::new(2, 10);
+
+        let error = manager.push(NotificationLevel::Error, "device lost");
+        manager.push(NotificationLevel::Info, "connected");
+        manager.push(NotificationLevel::Warning, "high latency");
+
+        // The info toast is evicted before the error
+        let ids: Vec<u64> = manager.active().map(|n| n.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&error));
+        assert_eq!(manager.history_count(), 3);
+
+        assert!(manager.dismiss(error));
+        assert!(!manager.dismiss(error));
+        assert_eq!(manager.active_count(), 1);
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut manager = NotificationManager::default();
+
+        manager.push_with_timeout(NotificationLevel::Info, "saved", Some(Duration::from_millis(10)));
+        manager.push(NotificationLevel::Error, "sticky");
+
+        assert!(!manager.expire(Instant::now()));
+        assert!(manager.expire(Instant::now() + Duration::from_millis(20)));
+        assert_eq!(manager.active_count(), 1);
+        assert_eq!(manager.history_count(), 2);
+    }
+
+    #[test]
+    fn test_error_notice() {
+        let notice = ErrorNotice::from_error(&MiViError::permission("/dev/shm/frames: Permission denied"));
+        assert!(notice.is_modal());
+        assert!(notice.details.contains("Permission denied"));
+        assert!(notice.details.contains("9000"));
+
+        let notice = ErrorNotice::from_error(&MiViError::device("no frames"));
+        assert!(!notice.is_modal());
+        assert_eq!(NotificationLevel::for_severity(notice.severity), NotificationLevel::Warning);
+        assert_eq!(notice.title, "Medical device error");
+        assert!(notice.toast_message().ends_with("configuration."));
+    }
+}
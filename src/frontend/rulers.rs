@@ -0,0 +1,104 @@
+// src/frontend/rulers.rs - Calibrated Rulers and Grid for the Image View
+
+use crate::backend::Zoom;
+use crate::frontend::measurements::Calibration;
+
+/// Approximate number of labelled ticks along a ruler
+const TARGET_MAJOR_TICKS: f32 = 8.0;
+
+/// Ruler tick at a fraction of the displayed view
+#[derive(Debug, Clone, PartialEq)]
+pub struct RulerTick {
+    pub position: f32,
+    pub major: bool,
+    /// Value label (major ticks only)
+    pub label: String,
+}
+
+/// Ticks for both rulers of the current view, in calibrated units
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Rulers {
+    pub horizontal: Vec<RulerTick>,
+    pub vertical: Vec<RulerTick>,
+    pub unit: String,
+}
+
+impl Rulers {
+    /// Compute ruler ticks for the visible part of a frame
+    pub fn new(zoom: &Zoom, image_width: u32, image_height: u32, calibration: &Calibration) -> Self {
+        let view = zoom.view_rect();
+        let (width, height) = (image_width as f32, image_height as f32);
+        let (x_scale, _) = calibration.scale(1.0, 0.0);
+        let (_, y_scale) = calibration.scale(0.0, 1.0);
+
+        Self {
+            horizontal: axis_ticks(view.x * width, view.width * width, x_scale),
+            vertical: axis_ticks(view.y * height, view.height * height, y_scale),
+            unit: calibration.effective_unit().symbol().to_string(),
+        }
+    }
+}
+
+/// Ticks along one axis from the view start and span in image pixels, at 1-2-5 steps
+fn axis_ticks(start_px: f32, span_px: f32, units_per_px: f32) -> Vec<RulerTick> {
+    if !(span_px > 0.0 && units_per_px > 0.0) {
+        return Vec::new();
+    }
+
+    let start = start_px * units_per_px;
+    let span = span_px * units_per_px;
+    let raw_step = span / TARGET_MAJOR_TICKS;
+    let magnitude = 10f32.powf(raw_step.log10().floor());
+    let (mantissa, subdivisions) = match raw_step / magnitude {
+        r if r < 1.5 => (1.0, 5),
+        r if r < 3.5 => (2.0, 4),
+        r if r < 7.5 => (5.0, 5),
+        _ => (10.0, 5),
+    };
+    let step = mantissa * magnitude;
+    let minor_step = step / subdivisions as f32;
+    let decimals = if step >= 1.0 { 0 } else { (-step.log10()).ceil() as usize };
+
+    let first = (start / minor_step - 1e-3).ceil() as i64;
+    let last = ((start + span) / minor_step + 1e-3).floor() as i64;
+
+    (first..=last)
+        .map(|i| {
+            let value = i as f32 * minor_step;
+            let major = i % subdivisions == 0;
+            RulerTick {
+                position: (value - start) / span,
+                major,
+                label: if major { format!("{:.*}", decimals, value) } else { String::new() },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::PixelSpacing;
+    use crate::frontend::measurements::{LengthUnit, SpacingSource};
+
+    #[test]
+    fn test_ruler_ticks() {
+        let rulers = Rulers::new(&Zoom::default(), 1000, 500, &Calibration::default());
+        assert_eq!(rulers.unit, "px");
+
+        let majors: Vec<&str> = rulers.horizontal.iter().filter(|t| t.major).map(|t| t.label.as_str()).collect();
+        assert_eq!(majors.first(), Some(&"0"));
+        assert_eq!(majors.last(), Some(&"1000"));
+        assert_eq!(majors.len(), 11);
+
+        // Zooming in shows finer calibrated ticks over the visible part only
+        let calibration = Calibration {
+            spacing: PixelSpacing::isotropic(0.1).map(|s| (s, SpacingSource::Manual)),
+            unit: LengthUnit::Millimeters,
+        };
+        let zoomed = Rulers::new(&Zoom::new(4.0, 0.5, 0.5), 1000, 500, &calibration);
+        let majors: Vec<&str> = zoomed.horizontal.iter().filter(|t| t.major).map(|t| t.label.as_str()).collect();
+        assert_eq!(majors, ["38", "40", "42", "44", "46", "48", "50", "52", "54", "56", "58", "60", "62"]);
+        assert!(zoomed.horizontal.iter().all(|t| (-1e-3..=1.0 + 1e-3).contains(&t.position)));
+    }
+}
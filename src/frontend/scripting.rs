@@ -0,0 +1,229 @@
+// src/frontend/scripting.rs - Rhai Automation Scripts Reacting to Viewer Events
+
+//! Optional automation: a Rhai script defines hook functions the viewer calls
+//! when something happens, and uses a few built-in functions to act.
+//!
+//! ```rhai
+//! fn on_connect(source) { notify("Streaming from " + source); }
+//! fn on_frame(n) { if n % 1000 == 0 { capture_snapshot(); } }
+//! fn on_drop_threshold(rate, threshold) {
+//!     http_post("http://pacs-gw.local/alerts", `{"drop_rate": ${rate}}`);
+//!     write_report();
+//! }
+//! fn on_disconnect() { write_report(); }
+//! ```
+//!
+//! Built-ins: `notify(text)`, `capture_snapshot()`, `write_report()`,
+//! `disconnect()`, `http_post(url, body) -> bool` and `print(text)` (logged).
+//! The script runs on its own thread so a slow hook never holds up frames;
+//! events arriving while the queue is full are dropped. Each hook call is
+//! capped at a number of operations, so an endless loop fails instead of
+//! hanging the thread. Running scripts needs the `scripting` feature.
+
+use std::path::Path;
+use std::sync::mpsc::{SyncSender, TrySendError};
+
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// Events queued for the script before new ones are dropped
+#[cfg(feature = "scripting")]
+const EVENT_QUEUE: usize = 256;
+
+/// Errors that can occur while starting a script
+#[derive(Debug, Error)]
+pub enum ScriptError {
+    #[error("Scripting is not available in this build (enable the \"scripting\" feature)")]
+    Unavailable,
+
+    #[error("Script error: {0}")]
+    Script(String),
+
+    #[error("Failed to start script thread: {0}")]
+    Thread(#[from] std::io::Error),
+}
+
+/// Something the script may react to
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptEvent {
+    /// Connected to the named source: `on_connect(source)`
+    Connected(String),
+    /// Source lost or disconnected: `on_disconnect()`
+    Disconnected,
+    /// A frame was displayed: `on_frame(frame_id)`
+    Frame(u64),
+    /// The frame drop rate crossed the alert threshold: `on_drop_threshold(rate, threshold)`
+    DropThreshold { rate: f64, threshold: f64 },
+}
+
+impl ScriptEvent {
+    /// Name of the hook function handling the event
+    pub fn hook(&self) -> &'static str {
+        match self {
+            ScriptEvent::Connected(_) => "on_connect",
+            ScriptEvent::Disconnected => "on_disconnect",
+            ScriptEvent::Frame(_) => "on_frame",
+            ScriptEvent::DropThreshold { .. } => "on_drop_threshold",
+        }
+    }
+}
+
+/// Something the script asked the viewer to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptAction {
+    Notify(String),
+    CaptureSnapshot,
+    WriteReport,
+    Disconnect,
+}
+
+#[cfg(feature = "scripting")]
+mod engine {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+    use std::sync::mpsc::{Receiver, Sender};
+    use std::time::Duration;
+
+    use rhai::{CallFnOptions, Dynamic, Engine, Scope};
+    use tokio::sync::mpsc;
+    use tracing::{debug, info, warn};
+
+    use super::{ScriptAction, ScriptError, ScriptEvent};
+
+    /// Operations one hook call may run before it is stopped
+    const MAX_OPERATIONS: u64 = 1_000_000;
+
+    /// Longest an `http_post` call may take
+    const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn build_engine(actions: &mpsc::UnboundedSender<ScriptAction>) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.on_print(|text| info!("📜 {}", text));
+        engine.on_debug(|text, _, position| debug!("📜 {} ({})", text, position));
+
+        let action = |action: ScriptAction| {
+            let actions = actions.clone();
+            move || {
+                let _ = actions.send(action.clone());
+            }
+        };
+        engine.register_fn("capture_snapshot", action(ScriptAction::CaptureSnapshot));
+        engine.register_fn("write_report", action(ScriptAction::WriteReport));
+        engine.register_fn("disconnect", action(ScriptAction::Disconnect));
+
+        let notify = actions.clone();
+        engine.register_fn("notify", move |text: &str| {
+            let _ = notify.send(ScriptAction::Notify(text.to_string()));
+        });
+
+        engine.register_fn("http_post", |url: &str, body: &str| -> bool {
+            let result = ureq::post(url)
+                .timeout(HTTP_TIMEOUT)
+                .set("Content-Type", "application/json")
+                .send_string(body);
+            match result {
+                Ok(_) => true,
+                Err(e) => {
+                    warn!("⚠️ Script HTTP callback to {} failed: {}", url, e);
+                    false
+                }
+            }
+        });
+        engine
+    }
+
+    /// Compile and run the script's top level, then call hooks until the event queue closes
+    pub fn run(
+        path: PathBuf,
+        events: Receiver<ScriptEvent>,
+        actions: mpsc::UnboundedSender<ScriptAction>,
+        ready: Sender<Result<bool, ScriptError>>,
+    ) {
+        let engine = build_engine(&actions);
+        let mut scope = Scope::new();
+        let ast = match engine.compile_file(path.clone()) {
+            Ok(ast) => ast,
+            Err(e) => {
+                let _ = ready.send(Err(ScriptError::Script(e.to_string())));
+                return;
+            }
+        };
+        if let Err(e) = engine.run_ast_with_scope(&mut scope, &ast) {
+            let _ = ready.send(Err(ScriptError::Script(e.to_string())));
+            return;
+        }
+
+        let hooks: HashSet<String> = ast.iter_functions().map(|function| function.name.to_string()).collect();
+        info!("📜 Script {:?} loaded, hooks: {:?}", path, hooks);
+        let _ = ready.send(Ok(hooks.contains("on_frame")));
+
+        // Hooks run against the scope the top level left behind, without re-running it
+        let options = || CallFnOptions::new().eval_ast(false);
+        for event in events {
+            let hook = event.hook();
+            if !hooks.contains(hook) {
+                continue;
+            }
+            let result = match event {
+                ScriptEvent::Connected(source) => {
+                    engine.call_fn_with_options::<Dynamic>(options(), &mut scope, &ast, hook, (source,))
+                }
+                ScriptEvent::Disconnected => engine.call_fn_with_options::<Dynamic>(options(), &mut scope, &ast, hook, ()),
+                ScriptEvent::Frame(frame_id) => {
+                    engine.call_fn_with_options::<Dynamic>(options(), &mut scope, &ast, hook, (frame_id as i64,))
+                }
+                ScriptEvent::DropThreshold { rate, threshold } => {
+                    engine.call_fn_with_options::<Dynamic>(options(), &mut scope, &ast, hook, (rate, threshold))
+                }
+            };
+            if let Err(e) = result {
+                warn!("⚠️ Script hook {} failed: {}", hook, e);
+            }
+        }
+        debug!("📜 Script thread stopped");
+    }
+}
+
+/// Runs a script and feeds it events
+#[derive(Debug)]
+pub struct ScriptHost {
+    events: SyncSender<ScriptEvent>,
+    // Per-frame events are only queued when the script has an on_frame hook
+    handles_frames: bool,
+}
+
+impl ScriptHost {
+    /// Load a script; the actions it requests arrive on `actions`
+    #[cfg(feature = "scripting")]
+    pub fn start(path: &Path, actions: mpsc::UnboundedSender<ScriptAction>) -> Result<Self, ScriptError> {
+        let (events, event_rx) = std::sync::mpsc::sync_channel(EVENT_QUEUE);
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+        let path = path.to_path_buf();
+        std::thread::Builder::new()
+            .name("mivi-script".to_string())
+            .spawn(move || engine::run(path, event_rx, actions, ready_tx))?;
+
+        let handles_frames = ready_rx
+            .recv()
+            .map_err(|_| ScriptError::Script("script thread stopped while loading".to_string()))??;
+        Ok(Self { events, handles_frames })
+    }
+
+    /// Stand-in when the `scripting` feature is disabled
+    #[cfg(not(feature = "scripting"))]
+    pub fn start(_path: &Path, _actions: mpsc::UnboundedSender<ScriptAction>) -> Result<Self, ScriptError> {
+        Err(ScriptError::Unavailable)
+    }
+
+    /// Queue an event for the script, dropping it if the script is falling behind
+    pub fn dispatch(&self, event: ScriptEvent) {
+        if matches!(event, ScriptEvent::Frame(_)) && !self.handles_frames {
+            return;
+        }
+        if let Err(TrySendError::Full(event)) = self.events.try_send(event) {
+            debug!("📜 Script busy, dropped {}", event.hook());
+        }
+    }
+}
@@ -0,0 +1,515 @@
+// src/frontend/session_report.rs - Session Summary Report (HTML)
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use thiserror::Error;
+
+use crate::backend::{GpuFrame, ProcessedFrame, RgbaBuffer};
+use crate::encryption::{self, EncryptionError, RecordingCipher};
+use crate::frontend::measurement_export::MeasurementReport;
+use crate::frontend::ui_state::SessionStatistics;
+use crate::redaction::AnonymizationProfile;
+
+/// Connection events kept for the timeline
+const MAX_EVENTS: usize = 500;
+
+/// Statistics samples kept for the charts (one hour at one sample per second)
+const MAX_SAMPLES: usize = 3600;
+
+/// Frames captured as report thumbnails
+const MAX_SNAPSHOTS: usize = 12;
+
+/// Longest side of a report thumbnail in pixels
+const THUMBNAIL_SIZE: u32 = 240;
+
+/// Chart size in SVG user units
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 120.0;
+
+/// Kind of connection timeline event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEventKind {
+    Connected,
+    Disconnected,
+    ConnectionLost,
+    WaitingForProducer,
+    /// The pipeline watchdog restarted a stalled connection
+    Recovered,
+    Error,
+}
+
+impl SessionEventKind {
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            SessionEventKind::Connected => "Connected",
+            SessionEventKind::Disconnected => "Disconnected",
+            SessionEventKind::ConnectionLost => "Connection lost",
+            SessionEventKind::WaitingForProducer => "Waiting for producer",
+            SessionEventKind::Recovered => "Recovered",
+            SessionEventKind::Error => "Error",
+        }
+    }
+
+    /// Get the CSS class used to color the event
+    fn css_class(self) -> &'static str {
+        match self {
+            SessionEventKind::Connected => "ok",
+            SessionEventKind::Disconnected => "info",
+            SessionEventKind::ConnectionLost => "warn",
+            SessionEventKind::WaitingForProducer => "info",
+            SessionEventKind::Recovered => "warn",
+            SessionEventKind::Error => "error",
+        }
+    }
+}
+
+/// Connection timeline entry
+#[derive(Debug, Clone)]
+pub struct SessionEvent {
+    pub time: DateTime<Local>,
+    pub kind: SessionEventKind,
+    pub detail: String,
+}
+
+/// Stream statistics at one point in time
+#[derive(Debug, Clone, Copy)]
+pub struct StatsSample {
+    pub time: DateTime<Local>,
+    pub fps: f64,
+    pub latency_ms: f64,
+    pub dropped: u64,
+}
+
+/// Displayed frame kept for a report thumbnail
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub frame_id: u64,
+    pub captured_at: DateTime<Local>,
+    pub width: u32,
+    pub height: u32,
+    pub rgba: RgbaBuffer,
+    /// Unconverted frame when it went through the GPU path, converted only when written
+    pub gpu_source: Option<GpuFrame>,
+}
+
+impl Snapshot {
+    /// Keep a reference to the display image of a processed frame
+    pub fn from_frame(frame: &ProcessedFrame) -> Self {
+        let (width, height) = frame.dimensions();
+        Self {
+            frame_id: frame.header.frame_id,
+            captured_at: Local::now(),
+            width,
+            height,
+            rgba: frame.rgb_data.clone(),
+            gpu_source: frame.gpu_source.clone(),
+        }
+    }
+
+    /// Encode a downscaled PNG of the frame
+    fn encode_thumbnail(&self) -> Result<Vec<u8>, ReportError> {
+        let rgba = match &self.gpu_source {
+            Some(gpu_frame) => gpu_frame.to_rgba(),
+            None => self.rgba.clone(),
+        };
+        let image = image::RgbaImage::from_raw(self.width, self.height, rgba.as_bytes().to_vec())
+            .ok_or_else(|| ReportError::Image(format!("{} bytes do not match a {}x{} RGBA image", rgba.as_bytes().len(), self.width, self.height)))?;
+
+        let scale = THUMBNAIL_SIZE as f32 / self.width.max(self.height).max(1) as f32;
+        let thumbnail = if scale < 1.0 {
+            let width = ((self.width as f32 * scale).round() as u32).max(1);
+            let height = ((self.height as f32 * scale).round() as u32).max(1);
+            image::imageops::thumbnail(&image, width, height)
+        } else {
+            image
+        };
+
+        let mut png = std::io::Cursor::new(Vec::new());
+        thumbnail
+            .write_to(&mut png, image::ImageOutputFormat::Png)
+            .map_err(|e| ReportError::Image(e.to_string()))?;
+        Ok(png.into_inner())
+    }
+}
+
+/// Everything recorded during a session for its summary report
+#[derive(Debug, Clone)]
+pub struct SessionLog {
+    pub started_at: DateTime<Local>,
+    events: VecDeque<SessionEvent>,
+    samples: VecDeque<StatsSample>,
+    snapshots: Vec<Snapshot>,
+    current_frame: Option<Snapshot>,
+}
+
+impl Default for SessionLog {
+    fn default() -> Self {
+        Self {
+            started_at: Local::now(),
+            events: VecDeque::new(),
+            samples: VecDeque::new(),
+            snapshots: Vec::new(),
+            current_frame: None,
+        }
+    }
+}
+
+impl SessionLog {
+    /// Add a connection timeline event
+    pub fn record_event(&mut self, kind: SessionEventKind, detail: impl Into<String>) {
+        if self.events.len() == MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(SessionEvent { time: Local::now(), kind, detail: detail.into() });
+    }
+
+    /// Add a statistics sample for the charts
+    pub fn record_sample(&mut self, fps: f64, latency_ms: f64, dropped: u64) {
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(StatsSample { time: Local::now(), fps, latency_ms, dropped });
+    }
+
+    /// Remember the frame on screen so it can be captured later
+    pub fn set_current_frame(&mut self, frame: &ProcessedFrame) {
+        self.current_frame = Some(Snapshot::from_frame(frame));
+    }
+
+    /// Capture the frame on screen for the report, returning false if there is none or it is already captured
+    pub fn capture_snapshot(&mut self) -> bool {
+        let Some(frame) = &self.current_frame else {
+            return false;
+        };
+        if self.snapshots.len() >= MAX_SNAPSHOTS || self.snapshots.iter().any(|s| s.frame_id == frame.frame_id) {
+            return false;
+        }
+
+        let mut snapshot = frame.clone();
+        snapshot.captured_at = Local::now();
+        self.snapshots.push(snapshot);
+        true
+    }
+
+    /// Get the connection timeline
+    pub fn events(&self) -> impl Iterator<Item = &SessionEvent> {
+        self.events.iter()
+    }
+
+    /// Get the recorded statistics samples
+    pub fn samples(&self) -> impl Iterator<Item = &StatsSample> {
+        self.samples.iter()
+    }
+
+    /// Get the captured snapshots
+    pub fn snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
+    }
+}
+
+/// Session summary report with timeline, charts, snapshots and measurements
+#[derive(Debug, Clone)]
+pub struct SessionReport {
+    pub generated_at: DateTime<Local>,
+    pub started_at: DateTime<Local>,
+    pub source: String,
+    pub device_profile: Option<String>,
+    /// User the session was run by, with their role
+    pub operator: String,
+    pub duration: std::time::Duration,
+    pub frames_received: u64,
+    pub peak_fps: f64,
+    pub average_latency_ms: f64,
+    pub connections: u64,
+    pub disconnections: u64,
+    pub events: Vec<SessionEvent>,
+    pub samples: Vec<StatsSample>,
+    /// Captured snapshots, followed by the last displayed frame
+    pub snapshots: Vec<Snapshot>,
+    pub measurements: MeasurementReport,
+}
+
+impl SessionReport {
+    /// Build a report from the session log and statistics
+    pub fn new(
+        log: &SessionLog,
+        stats: &SessionStatistics,
+        source: &str,
+        device_profile: Option<&str>,
+        operator: &str,
+        measurements: MeasurementReport,
+    ) -> Self {
+        let mut snapshots = log.snapshots.clone();
+        if let Some(frame) = log.current_frame.as_ref().filter(|f| snapshots.iter().all(|s| s.frame_id != f.frame_id)) {
+            snapshots.push(frame.clone());
+        }
+
+        Self {
+            generated_at: Local::now(),
+            started_at: log.started_at,
+            source: source.to_string(),
+            device_profile: device_profile.map(str::to_string),
+            operator: operator.to_string(),
+            duration: stats.session_start.elapsed(),
+            frames_received: stats.frames_received,
+            peak_fps: stats.peak_fps,
+            average_latency_ms: stats.average_latency,
+            connections: stats.successful_connections,
+            disconnections: stats.disconnections,
+            events: log.events.iter().cloned().collect(),
+            samples: log.samples.iter().copied().collect(),
+            snapshots,
+            measurements,
+        }
+    }
+
+    /// Apply an anonymization profile to the source name, event details and measurements
+    pub fn anonymize(mut self, profile: AnonymizationProfile) -> Self {
+        self.source = profile.text(&self.source);
+        for event in &mut self.events {
+            event.detail = profile.text(&event.detail);
+        }
+        self.measurements = self.measurements.anonymize(profile);
+        self
+    }
+
+    /// Write the HTML report and its thumbnails into a directory, returning the report path
+    ///
+    /// With a cipher every file is sealed with an `.enc` suffix; the report still links the
+    /// plain names, which decrypting in place restores.
+    pub fn write(&self, dir: &Path, cipher: Option<&RecordingCipher>) -> Result<PathBuf, ReportError> {
+        let stem = format!("session_{}", self.generated_at.format("%Y%m%d_%H%M%S"));
+        let assets = format!("{}_files", stem);
+        std::fs::create_dir_all(dir.join(&assets))?;
+
+        let mut thumbnails = Vec::with_capacity(self.snapshots.len());
+        for snapshot in &self.snapshots {
+            let file = format!("{}/frame_{}.png", assets, snapshot.frame_id);
+            encryption::write_recording(&dir.join(&file), &snapshot.encode_thumbnail()?, cipher)?;
+            thumbnails.push(file);
+        }
+
+        let html = self.render_html(&thumbnails);
+        Ok(encryption::write_recording(&dir.join(format!("{}.html", stem)), html.as_bytes(), cipher)?)
+    }
+
+    /// Render the report as a standalone HTML page, with thumbnails at the given relative paths
+    pub fn render_html(&self, thumbnails: &[String]) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>MiVi Session Report - {}</title>\n<style>{}</style>\n</head>\n<body>\n",
+            self.started_at.format("%Y-%m-%d %H:%M"),
+            REPORT_CSS
+        );
+
+        // Summary
+        html.push_str("<h1>MiVi Session Report</h1>\n<table class=\"summary\">\n");
+        let mut row = |label: &str, value: String| {
+            let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", label, escape_html(&value));
+        };
+        row("Source", self.source.clone());
+        row("Device profile", self.device_profile.clone().unwrap_or_else(|| "None".to_string()));
+        row("Operator", self.operator.clone());
+        row("Started", self.started_at.format("%Y-%m-%d %H:%M:%S").to_string());
+        row("Report generated", self.generated_at.format("%Y-%m-%d %H:%M:%S").to_string());
+        row("Duration", format_duration(self.duration));
+        row("Frames received", self.frames_received.to_string());
+        row("Peak FPS", format!("{:.1}", self.peak_fps));
+        row("Average latency", format!("{:.1} ms", self.average_latency_ms));
+        row("Connections / disconnections", format!("{} / {}", self.connections, self.disconnections));
+        html.push_str("</table>\n");
+
+        // Connection timeline
+        html.push_str("<h2>Connection Timeline</h2>\n");
+        if self.events.is_empty() {
+            html.push_str("<p class=\"empty\">No connection events recorded.</p>\n");
+        } else {
+            html.push_str("<table>\n<tr><th>Time</th><th>Event</th><th>Details</th></tr>\n");
+            for event in &self.events {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td class=\"{}\">{}</td><td>{}</td></tr>",
+                    event.time.format("%H:%M:%S"),
+                    event.kind.css_class(),
+                    event.kind.name(),
+                    escape_html(&event.detail)
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        // Statistics charts
+        html.push_str("<h2>Statistics</h2>\n");
+        if self.samples.len() < 2 {
+            html.push_str("<p class=\"empty\">Not enough statistics recorded for charts.</p>\n");
+        } else {
+            let fps: Vec<f64> = self.samples.iter().map(|s| s.fps).collect();
+            let latency: Vec<f64> = self.samples.iter().map(|s| s.latency_ms).collect();
+            let dropped: Vec<f64> = self.samples.iter().map(|s| s.dropped as f64).collect();
+            html.push_str(&line_chart("Frame rate", "fps", &fps));
+            html.push_str(&line_chart("Latency", "ms", &latency));
+            html.push_str(&line_chart("Dropped frames (cumulative)", "frames", &dropped));
+        }
+
+        // Snapshots
+        html.push_str("<h2>Snapshots</h2>\n");
+        if thumbnails.is_empty() {
+            html.push_str("<p class=\"empty\">No frames captured.</p>\n");
+        } else {
+            html.push_str("<div class=\"snapshots\">\n");
+            for (snapshot, file) in self.snapshots.iter().zip(thumbnails) {
+                let _ = writeln!(
+                    html,
+                    "<figure><img src=\"{}\" alt=\"Frame {}\"><figcaption>Frame {} · {}x{} · {}</figcaption></figure>",
+                    escape_html(file),
+                    snapshot.frame_id,
+                    snapshot.frame_id,
+                    snapshot.width,
+                    snapshot.height,
+                    snapshot.captured_at.format("%H:%M:%S")
+                );
+            }
+            html.push_str("</div>\n");
+        }
+
+        // Measurements
+        let _ = writeln!(html, "<h2>Measurements</h2>\n<p>Calibration: {}</p>", escape_html(&self.measurements.calibration));
+        if self.measurements.is_empty() {
+            html.push_str("<p class=\"empty\">No measurements or annotations.</p>\n");
+        } else {
+            html.push_str("<table>\n<tr><th>#</th><th>Kind</th><th>Frame</th><th>Value</th><th>Area</th><th>Label</th><th>Time</th></tr>\n");
+            for record in &self.measurements.records {
+                let value = record.value.map(|v| format!("{:.2} {}", v, record.unit.as_deref().unwrap_or_default()));
+                let area = record.area.map(|v| format!("{:.2} {}", v, record.area_unit.as_deref().unwrap_or_default()));
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    record.id,
+                    escape_html(&record.kind),
+                    record.frame_id,
+                    escape_html(&value.unwrap_or_default()),
+                    escape_html(&area.unwrap_or_default()),
+                    escape_html(&record.label),
+                    escape_html(&record.timestamp)
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Session report errors
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Thumbnail encoding error: {0}")]
+    Image(String),
+
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] EncryptionError),
+}
+
+/// Print-friendly report stylesheet
+const REPORT_CSS: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em; color: #1e293b; }\n\
+h1 { color: #0f4c81; } h2 { border-bottom: 1px solid #cbd5e1; padding-bottom: 4px; }\n\
+table { border-collapse: collapse; margin-bottom: 1em; }\n\
+th, td { border: 1px solid #cbd5e1; padding: 4px 8px; text-align: left; font-size: 0.9em; }\n\
+table.summary th { background: #f1f5f9; }\n\
+.ok { color: #15803d; } .info { color: #475569; } .warn { color: #b45309; } .error { color: #b91c1c; }\n\
+.empty { color: #64748b; font-style: italic; }\n\
+.chart { margin-bottom: 1em; } .chart svg { border: 1px solid #cbd5e1; background: #f8fafc; }\n\
+.snapshots { display: flex; flex-wrap: wrap; gap: 12px; }\n\
+figure { margin: 0; } figcaption { font-size: 0.8em; color: #475569; }\n\
+@media print { body { margin: 0; } h2 { page-break-after: avoid; } figure, tr { page-break-inside: avoid; } }\n";
+
+/// Inline SVG line chart of a series, scaled to its maximum
+fn line_chart(title: &str, unit: &str, values: &[f64]) -> String {
+    let peak = values.iter().fold(0.0f64, |a, &b| a.max(b));
+    let scale = if peak > 0.0 { CHART_HEIGHT / peak } else { 0.0 };
+    let step = CHART_WIDTH / (values.len().max(2) - 1) as f64;
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| format!("{:.1},{:.1}", i as f64 * step, CHART_HEIGHT - v * scale))
+        .collect();
+
+    format!(
+        "<div class=\"chart\"><h3>{} (max {:.1} {})</h3>\
+         <svg viewBox=\"0 0 {} {}\" width=\"{}\" height=\"{}\" preserveAspectRatio=\"none\">\
+         <polyline fill=\"none\" stroke=\"#0f4c81\" stroke-width=\"1.5\" points=\"{}\"/></svg></div>\n",
+        escape_html(title),
+        peak,
+        escape_html(unit),
+        CHART_WIDTH,
+        CHART_HEIGHT,
+        CHART_WIDTH,
+        CHART_HEIGHT,
+        points.join(" ")
+    )
+}
+
+/// Format a duration as hours, minutes and seconds
+fn format_duration(duration: std::time::Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}h {:02}m {:02}s", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Escape text for HTML element content and attribute values
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::measurements::{AnnotationSet, Calibration, MeasurementSet};
+
+    #[test]
+    fn test_render_session_report() {
+        let mut log = SessionLog::default();
+        log.record_event(SessionEventKind::Connected, "ultrasound_frames");
+        log.record_event(SessionEventKind::Error, "<timeout> & retry");
+        for second in 0..5 {
+            log.record_sample(30.0, 20.0 + second as f64, second);
+        }
+        assert!(!log.capture_snapshot());
+
+        let measurements = MeasurementReport::new(&MeasurementSet::default(), &AnnotationSet::default(), &Calibration::default());
+        let report = SessionReport::new(&log, &SessionStatistics::new(), "ultrasound_frames", Some("Cardiac"), "sonographer (Operator)", measurements);
+        assert!(report.snapshots.is_empty());
+
+        let html = report.render_html(&[]);
+        assert!(html.contains("<td>Cardiac</td>"));
+        assert!(html.contains("<td>sonographer (Operator)</td>"));
+        assert!(html.contains("&lt;timeout&gt; &amp; retry"));
+        assert_eq!(html.matches("<polyline").count(), 3);
+        assert!(html.contains("No frames captured."));
+        assert!(html.contains("No measurements or annotations."));
+        assert!(html.trim_end().ends_with("</html>"));
+
+        assert_eq!(format_duration(std::time::Duration::from_secs(3725)), "1h 02m 05s");
+    }
+}
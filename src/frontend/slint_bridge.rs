@@ -2,14 +2,80 @@
 
 use std::sync::Arc;
 use slint::{Image, Rgba8Pixel, SharedPixelBuffer};
-use tracing::{info, error, debug};
+use slint::winit_030::{winit, WinitWindowAccessor, WinitWindowEventResult};
+use tracing::{info, warn, error, debug};
+
+use crate::backend::adjustments::{ColorPalette, Colormap};
+use crate::backend::gpu_frame::GpuFrame;
+use crate::backend::health::HealthMonitor;
+use crate::backend::inference::InferenceStatistics;
+use crate::backend::detections::DetectionOverlays;
+use crate::backend::pixel_pool::{PixelBufferPool, RgbaBuffer};
+use crate::backend::transform::{CropRect, Orientation, Rotation};
+use crate::backend::resample::{ResampleQuality, Zoom};
+use crate::backend::registry::StreamAnnouncement;
+use crate::backend::roi::{Roi, RoiShape};
+use crate::frontend::access::{ActiveUser, Permission};
+use crate::frontend::gpu_renderer::GpuFrameRenderer;
+use crate::frontend::measurement_export::ExportFormat;
+use crate::frontend::measurements::{
+    AnnotationAction, AnnotationOverlay, ImagePoint, LengthUnit, MeasurementKind, MeasurementOverlay, MeasurementShape,
+};
+use crate::error::MiViError;
+use crate::frontend::notifications::{ErrorNotice, NotificationLevel, NotificationManager};
+use crate::frontend::latency::LatencyHistogram;
+use crate::frontend::rulers::Rulers;
+use crate::frontend::touch_gestures::{TouchGesture, TouchGestures, TouchPhase};
+use crate::frontend::ui_state::{HudCorner, Theme, UiSettings};
+use crate::perf::{PerformanceMonitor, PerformanceSnapshot};
 
 // Include the generated Slint code
 slint::include_modules!();
 
+/// How often the UI event loop reports to the health monitor
+const HEALTH_TICK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Initial main window geometry and mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowOptions {
+    pub width: u32,
+    pub height: u32,
+    pub fullscreen: bool,
+    /// Fullscreen without window decorations; F11 cannot leave it
+    pub kiosk: bool,
+}
+
+impl Default for WindowOptions {
+    fn default() -> Self {
+        Self { width: 1400, height: 900, fullscreen: false, kiosk: false }
+    }
+}
+
 /// Bridge for interfacing with Slint UI
 pub struct SlintBridge {
     main_window: MainWindow,
+    // Sidebar detached onto a secondary monitor
+    control_window: ControlWindow,
+
+    // Notifications
+    notifications: Arc<parking_lot::Mutex<NotificationManager>>,
+    _notification_timer: slint::Timer, // Kept alive for auto-dismiss
+
+    // Producer-to-display latency, recorded when frames are presented
+    latency: Arc<parking_lot::Mutex<LatencyHistogram>>,
+
+    // Where replaced frame buffers are returned for reuse
+    frame_pool: parking_lot::Mutex<Option<Arc<PixelBufferPool>>>,
+
+    // GPU upload path: next unconverted frame, and whether an OpenGL renderer took it on
+    pending_gpu_frame: Arc<parking_lot::Mutex<Option<GpuFrame>>>,
+    gpu_active: Arc<std::sync::atomic::AtomicBool>,
+
+    // Performance monitor fed with the UI-thread time to present each frame (--perf-monitor)
+    performance: parking_lot::Mutex<Option<Arc<parking_lot::Mutex<PerformanceMonitor>>>>,
+
+    // Ticks the health monitor from the UI thread, so a hung event loop fails the liveness check
+    health_timer: slint::Timer,
 }
 
 impl SlintBridge {
@@ -23,94 +89,1555 @@ impl SlintBridge {
         // Initialize UI state
         Self::initialize_ui_state(&main_window)?;
 
-        Ok(Self { main_window })
+        let notifications = Arc::new(parking_lot::Mutex::new(NotificationManager::default()));
+        let notification_timer = slint::Timer::default();
+        Self::setup_notifications(&main_window, &notifications, &notification_timer);
+        Self::setup_fullscreen_toggle(&main_window);
+        Self::setup_touch_gestures(&main_window);
+
+        let control_window = ControlWindow::new()
+            .map_err(|e| SlintBridgeError::Creation(e.to_string()))?;
+        Self::setup_control_window(&main_window, &control_window);
+
+        Ok(Self {
+            main_window,
+            control_window,
+            notifications,
+            _notification_timer: notification_timer,
+            latency: Arc::new(parking_lot::Mutex::new(LatencyHistogram::default())),
+            frame_pool: parking_lot::Mutex::new(None),
+            pending_gpu_frame: Arc::new(parking_lot::Mutex::new(None)),
+            gpu_active: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            performance: parking_lot::Mutex::new(None),
+            health_timer: slint::Timer::default(),
+        })
+    }
+
+    /// Wire the F11 fullscreen toggle (disabled in kiosk mode)
+    fn setup_fullscreen_toggle(window: &MainWindow) {
+        let window_weak = window.as_weak();
+        window.on_fullscreen_toggled(move || {
+            if let Some(window) = window_weak.upgrade() {
+                if window.get_kiosk_mode() {
+                    return;
+                }
+                let fullscreen = !window.window().is_fullscreen();
+                info!("🖥️ Fullscreen: {}", fullscreen);
+                window.window().set_fullscreen(fullscreen);
+            }
+        });
+    }
+
+    /// Wire the detachable control window
+    ///
+    /// The main window stays the source of truth: sidebar state is mirrored both ways while
+    /// the panel is detached, and the control window's actions invoke the main window's callbacks.
+    fn setup_control_window(window: &MainWindow, control: &ControlWindow) {
+        let (window_weak, control_weak) = (window.as_weak(), control.as_weak());
+        window.on_panel_detach_toggled(move |detached| {
+            let (Some(window), Some(control)) = (window_weak.upgrade(), control_weak.upgrade()) else {
+                return;
+            };
+            info!("🪟 Control panel {}", if detached { "detached" } else { "attached" });
+
+            if detached {
+                control.invoke_apply_panel_state(window.get_panel_state());
+                if let Err(e) = control.show() {
+                    error!("Failed to show control window: {}", e);
+                    window.set_panel_detached(false);
+                }
+            } else if let Err(e) = control.hide() {
+                error!("Failed to hide control window: {}", e);
+            }
+        });
+
+        let (window_weak, control_weak) = (window.as_weak(), control.as_weak());
+        window.on_panel_state_changed(move |state| {
+            if let (Some(window), Some(control)) = (window_weak.upgrade(), control_weak.upgrade()) {
+                if window.get_panel_detached() {
+                    control.invoke_apply_panel_state(state);
+                }
+            }
+        });
+
+        let window_weak = window.as_weak();
+        control.on_panel_state_changed(move |state| {
+            if let Some(window) = window_weak.upgrade() {
+                if window.get_panel_detached() {
+                    window.invoke_apply_panel_state(state);
+                }
+            }
+        });
+
+        // Closing the control window or its attach button docks the panel again
+        let window_weak = window.as_weak();
+        control.on_attach_requested(move || {
+            if let Some(window) = window_weak.upgrade() {
+                window.set_panel_detached(false);
+                window.invoke_panel_detach_toggled(false);
+            }
+        });
+
+        let window_weak = window.as_weak();
+        control.window().on_close_requested(move || {
+            if let Some(window) = window_weak.upgrade() {
+                window.set_panel_detached(false);
+            }
+            slint::CloseRequestResponse::HideWindow
+        });
+
+        // Closing the main window must not leave the control window running on its own
+        let control_weak = control.as_weak();
+        window.window().on_close_requested(move || {
+            if let Some(control) = control_weak.upgrade() {
+                let _ = control.hide();
+            }
+            slint::CloseRequestResponse::HideWindow
+        });
+
+        macro_rules! forward {
+            ($($on:ident => $invoke:ident($($arg:ident),*);)*) => {$({
+                let window_weak = window.as_weak();
+                control.$on(move |$($arg),*| {
+                    if let Some(window) = window_weak.upgrade() {
+                        window.$invoke($($arg),*);
+                    }
+                });
+            })*};
+        }
+
+        forward! {
+            on_window_level_changed => invoke_window_level_changed(center, width);
+            on_gamma_changed => invoke_gamma_changed(gamma);
+            on_colormap_changed => invoke_colormap_changed(index);
+            on_clahe_changed => invoke_clahe_changed(enabled, clip_limit);
+            on_speckle_changed => invoke_speckle_changed(strength);
+            on_edge_enhancement_changed => invoke_edge_enhancement_changed(strength);
+            on_overlay_opacity_changed => invoke_overlay_opacity_changed(opacity);
+            on_persistence_changed => invoke_persistence_changed(frames);
+            on_orientation_changed => invoke_orientation_changed(degrees, flip_horizontal, flip_vertical);
+            on_crop_cleared => invoke_crop_cleared();
+            on_zoom_requested => invoke_zoom_requested(steps, x, y);
+            on_zoom_reset => invoke_zoom_reset();
+            on_zoom_quality_changed => invoke_zoom_quality_changed(index);
+            on_measurements_cleared => invoke_measurements_cleared();
+            on_measurements_exported => invoke_measurements_exported(csv);
+            on_rulers_toggled => invoke_rulers_toggled(rulers, grid);
+            on_roi_cleared => invoke_roi_cleared();
+            on_calibration_reset => invoke_calibration_reset();
+            on_settings_clicked => invoke_settings_clicked();
+            on_about_clicked => invoke_about_clicked();
+            on_report_requested => invoke_report_requested();
+        }
+    }
+
+    /// Turn two-finger touch input into pinch-zoom and pan of the image view
+    ///
+    /// Slint only delivers a single pointer, so raw touches are intercepted from winit.
+    /// The first finger stays the UI pointer (taps, long-press menus); further fingers are
+    /// consumed by the gesture recognizer.
+    fn setup_touch_gestures(window: &MainWindow) {
+        let window_weak = window.as_weak();
+        let mut gestures = TouchGestures::default();
+
+        window.window().on_winit_window_event(move |slint_window, event| {
+            let winit::event::WindowEvent::Touch(touch) = event else {
+                return WinitWindowEventResult::Propagate;
+            };
+            let Some(window) = window_weak.upgrade() else {
+                return WinitWindowEventResult::Propagate;
+            };
+
+            let phase = match touch.phase {
+                winit::event::TouchPhase::Started => TouchPhase::Started,
+                winit::event::TouchPhase::Moved => TouchPhase::Moved,
+                winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => TouchPhase::Ended,
+            };
+            let scale_factor = slint_window.scale_factor();
+            let position = touch.location.to_logical::<f32>(scale_factor as f64);
+            let response = gestures.handle(touch.id, phase, position.x, position.y);
+
+            match response.gesture {
+                Some(TouchGesture::Began) => {
+                    debug!("👆 Two-finger gesture started");
+                    window.set_touch_gesture(true);
+                }
+                Some(TouchGesture::Moved { scale, center_x, center_y, dx, dy }) if window.get_has_frame() => {
+                    let (view_x, view_y) = (window.get_image_view_x(), window.get_image_view_y());
+                    let (view_width, view_height) = (window.get_image_view_width(), window.get_image_view_height());
+                    if view_width > 0.0 && view_height > 0.0 {
+                        if scale != 1.0 {
+                            window.invoke_zoom_requested(
+                                ZoomAction::steps_for_scale(scale),
+                                ((center_x - view_x) / view_width).clamp(0.0, 1.0),
+                                ((center_y - view_y) / view_height).clamp(0.0, 1.0),
+                            );
+                        }
+                        if dx != 0.0 || dy != 0.0 {
+                            window.invoke_zoom_panned(dx / view_width, dy / view_height);
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if response.forward_to_ui {
+                WinitWindowEventResult::Propagate
+            } else {
+                WinitWindowEventResult::PreventDefault
+            }
+        });
+    }
+
+    /// Apply the initial window size, fullscreen and kiosk mode before the window is shown
+    pub fn apply_window_options(&self, options: &WindowOptions) {
+        info!("🖥️ Window: {}x{}, fullscreen: {}, kiosk: {}",
+              options.width, options.height, options.fullscreen, options.kiosk);
+
+        let window = self.main_window.window();
+        window.set_size(slint::LogicalSize::new(options.width as f32, options.height as f32));
+        self.main_window.set_kiosk_mode(options.kiosk);
+        window.set_fullscreen(options.fullscreen || options.kiosk);
+    }
+
+    /// Upload unconverted frames as OpenGL textures and convert them in a shader
+    ///
+    /// Runs from Slint's rendering notifier, so it needs the OpenGL (femtovg)
+    /// renderer; with any other renderer frames are converted on the CPU instead.
+    pub fn enable_gpu_upload(&self) -> Result<(), SlintBridgeError> {
+        let window_weak = self.main_window.as_weak();
+        let pending = Arc::clone(&self.pending_gpu_frame);
+        let active = Arc::clone(&self.gpu_active);
+        let mut renderer: Option<GpuFrameRenderer> = None;
+
+        self.main_window.window().set_rendering_notifier(move |state, graphics_api| match state {
+            slint::RenderingState::RenderingSetup => {
+                let slint::GraphicsAPI::NativeOpenGL { get_proc_address } = graphics_api else {
+                    warn!("⚠️ GPU upload needs the OpenGL renderer, converting frames on the CPU");
+                    return;
+                };
+                let gl = unsafe { glow::Context::from_loader_function_cstr(|name| get_proc_address(name)) };
+                match GpuFrameRenderer::new(gl) {
+                    Ok(gpu_renderer) => {
+                        info!("🎮 GPU upload path active");
+                        renderer = Some(gpu_renderer);
+                        active.store(true, std::sync::atomic::Ordering::Relaxed);
+                    }
+                    Err(e) => warn!("⚠️ GPU upload unavailable, converting frames on the CPU: {}", e),
+                }
+            }
+            slint::RenderingState::BeforeRendering => {
+                let (Some(gpu_renderer), Some(window)) = (renderer.as_mut(), window_weak.upgrade()) else {
+                    return;
+                };
+                let Some(frame) = pending.lock().take() else {
+                    return;
+                };
+                match gpu_renderer.render(&frame) {
+                    Ok(image) => window.set_current_frame(image),
+                    Err(e) => {
+                        error!("GPU frame conversion failed: {}", e);
+                        window.set_current_frame(Image::from_rgba8(frame.to_rgba()));
+                    }
+                }
+                window.window().request_redraw();
+            }
+            slint::RenderingState::RenderingTeardown => {
+                active.store(false, std::sync::atomic::Ordering::Relaxed);
+                renderer = None;
+            }
+            _ => {}
+        }).map_err(|e| SlintBridgeError::CallbackSetup(format!("GPU upload: {:?}", e)))
+    }
+
+    /// Wire notification callbacks and the auto-dismiss timer
+    fn setup_notifications(
+        window: &MainWindow,
+        notifications: &Arc<parking_lot::Mutex<NotificationManager>>,
+        timer: &slint::Timer,
+    ) {
+        {
+            let notifications = Arc::clone(notifications);
+            let window_weak = window.as_weak();
+            window.on_dismiss_notification(move |id| {
+                let mut manager = notifications.lock();
+                if manager.dismiss(id as u64) {
+                    if let Some(window) = window_weak.upgrade() {
+                        Self::refresh_notifications(&window, &manager);
+                    }
+                }
+            });
+        }
+
+        {
+            let notifications = Arc::clone(notifications);
+            let window_weak = window.as_weak();
+            window.on_clear_notification_history(move || {
+                let mut manager = notifications.lock();
+                manager.clear_history();
+                if let Some(window) = window_weak.upgrade() {
+                    Self::refresh_notifications(&window, &manager);
+                }
+            });
+        }
+
+        let notifications = Arc::clone(notifications);
+        let window_weak = window.as_weak();
+        timer.start(slint::TimerMode::Repeated, std::time::Duration::from_millis(250), move || {
+            let mut manager = notifications.lock();
+            if manager.expire(std::time::Instant::now()) {
+                if let Some(window) = window_weak.upgrade() {
+                    Self::refresh_notifications(&window, &manager);
+                }
+            }
+        });
+    }
+
+    /// Push the notification queue and history into the UI models
+    fn refresh_notifications(window: &MainWindow, manager: &NotificationManager) {
+        let to_ui = |n: &crate::frontend::notifications::Notification| ToastData {
+            id: n.id as i32,
+            level: n.level.ui_level(),
+            message: n.message.as_str().into(),
+            time: n.time_string().into(),
+            details: n.details.as_deref().unwrap_or_default().into(),
+        };
+
+        let toasts: Vec<ToastData> = manager.active().map(to_ui).collect();
+        let history: Vec<ToastData> = manager.history().map(to_ui).collect();
+
+        window.set_toasts(slint::ModelRc::new(slint::VecModel::from(toasts)));
+        window.set_notification_history(slint::ModelRc::new(slint::VecModel::from(history)));
+    }
+
+    /// Initialize default UI state
+    fn initialize_ui_state(window: &MainWindow) -> Result<(), SlintBridgeError> {
+        // Set initial values
+        window.set_connection_status("Disconnected - Waiting for medical device".into());
+        window.set_shm_name("ultrasound_frames".into());
+        window.set_format("YUV".into());
+        window.set_resolution("0x0".into());
+        window.set_fps(0.0);
+        window.set_latency_ms(0.0);
+        window.set_total_frames(0);
+        window.set_catch_up_mode(false);
+        window.set_is_connected(false);
+        window.set_has_frame(false);
+        window.set_frame_id(0);
+        window.set_sequence_number(0);
+        window.set_frame_format("Unknown".into());
+
+        info!("✅ Slint UI state initialized");
+        Ok(())
+    }
+
+    /// Setup reconnect button callback
+    pub async fn on_reconnect_clicked<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_reconnect_clicked(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup catch-up mode toggle callback
+    pub async fn on_toggle_catch_up<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(bool) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        let main_window_weak = self.main_window.as_weak();
+        self.main_window.on_toggle_catch_up(move || {
+            if let Some(window) = main_window_weak.upgrade() {
+                let current_mode = window.get_catch_up_mode();
+                callback(!current_mode);
+            }
+        });
+        Ok(())
+    }
+
+    /// Setup settings button callback
+    pub async fn on_settings_clicked<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_settings_clicked(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup settings dialog apply callback
+    pub async fn on_settings_applied<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(UiSettings) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_settings_applied(move |data| {
+            callback(Self::settings_from_ui(&data));
+        });
+        Ok(())
+    }
+
+    /// Setup the settings dialog's stream discovery refresh callback
+    pub async fn on_discover_streams<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_discover_streams(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup window/level change callback (center, width normalized to 0..1)
+    pub async fn on_window_level_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(f32, f32) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_window_level_changed(move |center, width| {
+            callback(center, width);
+        });
+        Ok(())
+    }
+
+    /// Setup gamma change callback
+    pub async fn on_gamma_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(f32) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_gamma_changed(move |gamma| {
+            callback(gamma);
+        });
+        Ok(())
+    }
+
+    /// Setup colormap selection callback
+    pub async fn on_colormap_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(Colormap) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_colormap_changed(move |index| {
+            callback(Colormap::from_index(index.max(0) as usize));
+        });
+        Ok(())
+    }
+
+    /// Setup CLAHE toggle/strength callback
+    pub async fn on_clahe_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(bool, f32) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_clahe_changed(move |enabled, clip_limit| {
+            callback(enabled, clip_limit);
+        });
+        Ok(())
+    }
+
+    /// Setup speckle reduction strength callback
+    pub async fn on_speckle_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(f32) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_speckle_changed(move |strength| {
+            callback(strength);
+        });
+        Ok(())
+    }
+
+    /// Setup edge enhancement amount callback
+    pub async fn on_edge_enhancement_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(f32) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_edge_enhancement_changed(move |amount| {
+            callback(amount);
+        });
+        Ok(())
+    }
+
+    /// Setup segmentation overlay opacity callback
+    pub async fn on_overlay_opacity_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(f32) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_overlay_opacity_changed(move |opacity| {
+            callback(opacity);
+        });
+        Ok(())
+    }
+
+    /// Setup persistence window callback
+    pub async fn on_persistence_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(u32) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_persistence_changed(move |frames| {
+            callback(frames.max(1) as u32);
+        });
+        Ok(())
+    }
+
+    /// Setup rotate/flip callback
+    pub async fn on_orientation_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(Orientation) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_orientation_changed(move |degrees, flip_horizontal, flip_vertical| {
+            callback(Orientation::new(Rotation::from_degrees(degrees), flip_horizontal, flip_vertical));
+        });
+        Ok(())
+    }
+
+    /// Setup crop selection callback (None when the operator returns to the full frame)
+    pub async fn on_crop_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(Option<CropRect>) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+
+        let selected = Arc::clone(&callback);
+        self.main_window.on_crop_changed(move |x, y, width, height| {
+            selected(Some(CropRect::new(x, y, width, height)));
+        });
+
+        self.main_window.on_crop_cleared(move || {
+            callback(None);
+        });
+        Ok(())
+    }
+
+    /// Setup digital zoom callbacks (wheel/buttons, middle-drag pan and fit)
+    pub async fn on_zoom_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(ZoomAction) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+
+        let requested = Arc::clone(&callback);
+        self.main_window.on_zoom_requested(move |steps, x, y| {
+            requested(ZoomAction::Step { steps, x, y });
+        });
+
+        let panned = Arc::clone(&callback);
+        self.main_window.on_zoom_panned(move |dx, dy| {
+            panned(ZoomAction::Pan { dx, dy });
+        });
+
+        self.main_window.on_zoom_reset(move || {
+            callback(ZoomAction::Reset);
+        });
+        Ok(())
+    }
+
+    /// Setup zoom quality callback
+    pub async fn on_zoom_quality_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(ResampleQuality) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_zoom_quality_changed(move |index| {
+            callback(ResampleQuality::from_index(index.max(0) as usize));
+        });
+        Ok(())
+    }
+
+    /// Setup measurement callbacks for all tools (shape in view coordinates, image size, frame id)
+    pub async fn on_measurement_added<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(MeasurementShape, u32, u32, u64) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+
+        let distance = Arc::clone(&callback);
+        self.main_window.on_distance_measured(move |x1, y1, x2, y2, width, height, frame_id| {
+            let shape = MeasurementShape::Distance {
+                start: ImagePoint::new(x1, y1),
+                end: ImagePoint::new(x2, y2),
+            };
+            distance(shape, width.max(0) as u32, height.max(0) as u32, frame_id.max(0) as u64);
+        });
+
+        let angle = Arc::clone(&callback);
+        self.main_window.on_angle_measured(move |x1, y1, x2, y2, x3, y3, width, height, frame_id| {
+            let shape = MeasurementShape::Angle {
+                start: ImagePoint::new(x1, y1),
+                vertex: ImagePoint::new(x2, y2),
+                end: ImagePoint::new(x3, y3),
+            };
+            angle(shape, width.max(0) as u32, height.max(0) as u32, frame_id.max(0) as u64);
+        });
+
+        self.main_window.on_ellipse_measured(move |x, y, w, h, width, height, frame_id| {
+            let shape = MeasurementShape::Ellipse {
+                start: ImagePoint::new(x, y),
+                end: ImagePoint::new(x + w, y + h),
+            };
+            callback(shape, width.max(0) as u32, height.max(0) as u32, frame_id.max(0) as u64);
+        });
+        Ok(())
+    }
+
+    /// Setup annotation callbacks (place, drag, edit and delete on the image)
+    pub async fn on_annotation_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(AnnotationAction) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+
+        let placed = Arc::clone(&callback);
+        self.main_window.on_annotation_placed(move |x, y, text, frame_id| {
+            placed(AnnotationAction::Place {
+                position: ImagePoint::new(x, y),
+                text: text.to_string(),
+                frame_id: frame_id.max(0) as u64,
+            });
+        });
+
+        let moved = Arc::clone(&callback);
+        self.main_window.on_annotation_moved(move |id, x, y| {
+            moved(AnnotationAction::Move { id: id as u64, position: ImagePoint::new(x, y) });
+        });
+
+        let edited = Arc::clone(&callback);
+        self.main_window.on_annotation_edited(move |id, text| {
+            edited(AnnotationAction::Edit { id: id as u64, text: text.to_string() });
+        });
+
+        self.main_window.on_annotation_deleted(move |id| {
+            callback(AnnotationAction::Delete { id: id as u64 });
+        });
+        Ok(())
+    }
+
+    /// Setup ROI callback (bounds in view coordinates, None when cleared)
+    pub async fn on_roi_changed<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(Option<Roi>) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+
+        let selected = Arc::clone(&callback);
+        self.main_window.on_roi_selected(move |ellipse, x, y, width, height| {
+            let shape = if ellipse { RoiShape::Ellipse } else { RoiShape::Rectangle };
+            selected(Some(Roi::new(shape, CropRect::new(x, y, width, height))));
+        });
+
+        self.main_window.on_roi_cleared(move || {
+            callback(None);
+        });
+        Ok(())
+    }
+
+    /// Setup two-point calibration callback (line in view coordinates, image size, known length in mm)
+    pub async fn on_calibration_measured<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(MeasurementShape, u32, u32, f32) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_calibration_measured(move |x1, y1, x2, y2, width, height, known_mm| {
+            let shape = MeasurementShape::Distance {
+                start: ImagePoint::new(x1, y1),
+                end: ImagePoint::new(x2, y2),
+            };
+            callback(shape, width.max(0) as u32, height.max(0) as u32, known_mm);
+        });
+        Ok(())
+    }
+
+    /// Setup calibration reset callback
+    pub async fn on_calibration_reset<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_calibration_reset(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup rulers and grid visibility callback
+    pub async fn on_rulers_toggled<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(bool, bool) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_rulers_toggled(move |rulers, grid| {
+            callback(rulers, grid);
+        });
+        Ok(())
+    }
+
+    /// Setup measurement export callback
+    pub async fn on_measurements_exported<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(ExportFormat) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_measurements_exported(move |csv| {
+            callback(if csv { ExportFormat::Csv } else { ExportFormat::Json });
+        });
+        Ok(())
+    }
+
+    /// Setup clear measurements callback
+    pub async fn on_measurements_cleared<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_measurements_cleared(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup about button callback
+    pub async fn on_about_clicked<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_about_clicked(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup CPU profile hotkey callback
+    pub async fn on_profile_requested<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_profile_requested(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup sign-in dialog callback (user name, password)
+    pub async fn on_sign_in_requested<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(String, String) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_sign_in_requested(move |user, password| {
+            callback(user.to_string(), password.to_string());
+        });
+        Ok(())
+    }
+
+    /// Setup privacy mode callback; the main window has already hidden itself
+    pub async fn on_privacy_lock_requested<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        let control_window = self.control_window.as_weak();
+        self.main_window.on_privacy_lock_requested(move || {
+            // Still on the UI thread, so the control window is covered in the same frame
+            if let Some(control) = control_window.upgrade() {
+                control.set_privacy_locked(true);
+            }
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup privacy resume callback (entered PIN, empty without one)
+    pub async fn on_privacy_unlock_requested<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_privacy_unlock_requested(move |pin| {
+            callback(pin.to_string());
+        });
+        Ok(())
+    }
+
+    /// Setup sign-out callback
+    pub async fn on_sign_out_requested<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_sign_out_requested(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Setup session report button callback
+    pub async fn on_report_requested<F>(&self, callback: F) -> Result<(), SlintBridgeError>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.main_window.on_report_requested(move || {
+            callback();
+        });
+        Ok(())
+    }
+
+    /// Update connection status in the UI
+    pub async fn update_connection_status(&self, status: &str, connected: bool) -> Result<(), SlintBridgeError> {
+        let status = status.to_string();
+        let main_window = self.main_window.as_weak();
+
+        // Use the invoke_from_event_loop_if_not_on_event_loop helper
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_connection_status(status.clone().into());
+                window.set_is_connected(connected);
+                window.set_waiting_for_producer(false);
+
+                debug!("🔄 UI connection status updated: {} (connected: {})", status.clone(), connected);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Return displayed frame buffers to this pool once they are replaced
+    pub fn set_frame_pool(&self, pool: Arc<PixelBufferPool>) {
+        *self.frame_pool.lock() = Some(pool);
+    }
+
+    /// Record frame presentation times and show the performance panel
+    pub fn set_performance_monitor(&self, monitor: Arc<parking_lot::Mutex<PerformanceMonitor>>) {
+        *self.performance.lock() = Some(monitor);
+        self.main_window.set_perf_monitor(true);
+    }
+
+    /// Report UI responsiveness to the health monitor; call it on the UI thread
+    pub fn set_health_monitor(&self, monitor: Arc<HealthMonitor>) {
+        monitor.ui_tick();
+        self.health_timer.start(slint::TimerMode::Repeated, HEALTH_TICK_INTERVAL, move || {
+            monitor.ui_tick();
+        });
+    }
+
+    /// Update the performance panel
+    pub async fn set_performance(&self, snapshot: PerformanceSnapshot) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+        let stats = PerformanceStats {
+            fps: snapshot.fps as f32,
+            conversion_ms: snapshot.average_processing_time.as_secs_f32() * 1000.0,
+            conversion_max_ms: snapshot.max_processing_time.as_secs_f32() * 1000.0,
+            render_ms: snapshot.average_render_time.as_secs_f32() * 1000.0,
+            memory: snapshot.resident_memory_bytes
+                .map_or_else(|| "n/a".to_string(), crate::utils::format_bytes)
+                .into(),
+        };
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_perf_stats(stats);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Update frame in the UI
+    ///
+    /// The buffer is shown without copying; the frame it replaces goes back to the
+    /// frame pool so the processor can convert the next frame into it. Frames left
+    /// for the GPU are handed to the renderer, or converted here if it isn't active.
+    pub async fn update_frame(
+        &self,
+        frame: RgbaBuffer,
+        gpu_source: Option<GpuFrame>,
+        resolution: &str,
+        format: &str,
+        frame_id: i32,
+        sequence_number: i32,
+        timestamp_ns: u64,
+    ) -> Result<(), SlintBridgeError> {
+        let resolution = resolution.to_string();
+        let format = format.to_string();
+        let main_window = self.main_window.as_weak();
+        let latency = Arc::clone(&self.latency);
+        let frame_pool = self.frame_pool.lock().clone();
+        let pending_gpu_frame = Arc::clone(&self.pending_gpu_frame);
+        let gpu_active = Arc::clone(&self.gpu_active);
+        let performance = self.performance.lock().clone();
+
+        // Pixel buffers are Send, so only the Image wrapper is built on the UI thread
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                let _span = tracing::info_span!("render", frame_id, sequence = sequence_number).entered();
+                let present_start = std::time::Instant::now();
+                let image = match gpu_source {
+                    Some(gpu_frame) if gpu_active.load(std::sync::atomic::Ordering::Relaxed) => {
+                        // Uploaded and converted in the next render pass
+                        *pending_gpu_frame.lock() = Some(gpu_frame);
+                        window.window().request_redraw();
+                        None
+                    }
+                    Some(gpu_frame) => Some(Image::from_rgba8(gpu_frame.to_rgba())),
+                    None => Some(Image::from_rgba8(frame)),
+                };
+                if let Some(image) = image {
+                    let previous = window.get_current_frame();
+                    window.set_current_frame(image);
+                    if let (Some(pool), Some(buffer)) = (frame_pool, previous.to_rgba8()) {
+                        drop(previous);
+                        pool.recycle(buffer);
+                    }
+                }
+                window.set_resolution(resolution.clone().into());
+                window.set_frame_format(format.clone().into());
+                window.set_frame_id(frame_id);
+                window.set_sequence_number(sequence_number);
+                window.set_has_frame(true);
+
+                // End-to-end latency ends when the frame is handed to the renderer
+                latency.lock().record_presented(timestamp_ns);
+                if let Some(performance) = performance {
+                    performance.lock().record_render(present_start.elapsed());
+                }
+
+                debug!("🖼️ UI frame updated: {} {}", resolution.clone(), format.clone());
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Update statistics in the UI, with latency percentiles over the recently presented frames
+    pub async fn update_statistics(
+        &self,
+        fps: f32,
+        total_frames: i32,
+    ) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+        let (latency, sparkline) = {
+            let histogram = self.latency.lock();
+            (histogram.summary(), histogram.sparkline_path())
+        };
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_fps(fps);
+                window.set_latency_ms(latency.p50 as f32);
+                window.set_latency_p95_ms(latency.p95 as f32);
+                window.set_latency_p99_ms(latency.p99 as f32);
+                window.set_latency_sparkline(sparkline.into());
+                window.set_total_frames(total_frames);
+
+                if fps > 0.0 {
+                    debug!("📊 UI stats updated: {:.1} FPS, latency p50/p95/p99 {:.1}/{:.1}/{:.1}ms, {} frames",
+                           fps, latency.p50, latency.p95, latency.p99, total_frames);
+                }
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Update configuration in the UI
+    pub async fn update_config(&self, shm_name: &str, format: &str) -> Result<(), SlintBridgeError> {
+        let shm_name = shm_name.to_string();
+        let format = format.to_string();
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            let shm_str_name = shm_name.clone();
+            let format_str = format.clone();
+
+            if let Some(window) = main_window.upgrade() {
+                window.set_shm_name(shm_name.into());
+                window.set_format(format.into());
+
+                debug!("⚙️ UI config updated: {} ({})", shm_str_name, format_str);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the active device profile and its performance targets in the UI
+    pub async fn set_device_profile(&self, profile: Option<&str>, expected_fps: f64, latency_target_ms: f64) -> Result<(), SlintBridgeError> {
+        let profile = profile.unwrap_or_default().to_string();
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                debug!("🩺 UI device profile updated: {:?}", profile);
+                window.set_device_profile(profile.into());
+                window.set_expected_fps(expected_fps as f32);
+                window.set_latency_target_ms(latency_target_ms as f32);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Show the producer's protocol version and capabilities
+    pub async fn set_producer_protocol(&self, protocol: String) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                debug!("🤝 UI producer protocol updated: {:?}", protocol);
+                window.set_producer_protocol(protocol.into());
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Show the reconnect backoff policy in the connection panel
+    pub async fn set_reconnect_policy(&self, description: String) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_reconnect_policy(description.into());
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Show the connection as waiting for the producer rather than failed
+    ///
+    /// Every connection status update clears it again, so send this after the status.
+    pub async fn set_waiting_for_producer(&self, waiting: bool) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_waiting_for_producer(waiting);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Open the settings dialog pre-filled with the given settings
+    pub async fn open_settings(&self, settings: UiSettings) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_settings(Self::settings_to_ui(&settings));
+                window.set_settings_open(true);
+                debug!("⚙️ Settings dialog opened");
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// List the streams found in the discovery registry in the settings dialog
+    pub async fn set_discovered_streams(&self, streams: Vec<StreamAnnouncement>) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                let streams: Vec<DiscoveredStream> = streams
+                    .into_iter()
+                    .map(|stream| DiscoveredStream {
+                        label: stream.label().into(),
+                        shm_name: stream.shm_name.into(),
+                        format: stream.format.into(),
+                        width: stream.width as i32,
+                        height: stream.height as i32,
+                    })
+                    .collect();
+                debug!("📇 {} discovered stream(s)", streams.len());
+                window.set_discovered_streams(slint::ModelRc::new(slint::VecModel::from(streams)));
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Open the about dialog with build, license and diagnostics information
+    pub async fn open_about(&self, build_info: &str, license: &str, notice: &str, diagnostics: &str) -> Result<(), SlintBridgeError> {
+        let build_info = build_info.to_string();
+        let license = license.to_string();
+        let notice = notice.to_string();
+        let diagnostics = diagnostics.to_string();
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_about_build_info(build_info.into());
+                window.set_about_license(license.into());
+                window.set_about_notice(notice.into());
+                window.set_about_diagnostics(diagnostics.into());
+                window.set_about_open(true);
+                debug!("ℹ️ About dialog opened");
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the window/level shown by the adjustment controls
+    pub async fn set_window_level(&self, center: f32, width: f32) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_window_center(center);
+                window.set_window_width(width);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the gamma shown by the adjustment controls
+    pub async fn set_gamma(&self, gamma: f32) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_gamma(gamma);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the selected colormap and its legend
+    pub async fn set_colormap(&self, colormap: Colormap) -> Result<(), SlintBridgeError> {
+        const LEGEND_WIDTH: u32 = 256;
+        const LEGEND_HEIGHT: u32 = 8;
+
+        let legend = ColorPalette::new(colormap).legend_rgba(LEGEND_WIDTH as usize, LEGEND_HEIGHT as usize);
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(&legend, LEGEND_WIDTH, LEGEND_HEIGHT);
+                window.set_colormap_legend(Image::from_rgba8(buffer));
+                window.set_colormap_index(colormap.index() as i32);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the CLAHE controls
+    pub async fn set_clahe(&self, enabled: bool, clip_limit: f32) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_clahe_enabled(enabled);
+                window.set_clahe_clip_limit(clip_limit);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the speckle reduction slider
+    pub async fn set_speckle_strength(&self, strength: f32) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_speckle_strength(strength);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the edge enhancement slider
+    pub async fn set_edge_enhancement(&self, amount: f32) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_edge_enhancement(amount);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the persistence slider
+    pub async fn set_persistence_frames(&self, frames: u32) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_persistence_frames(frames as i32);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the rotate/flip controls
+    pub async fn set_orientation(&self, orientation: Orientation) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_rotation_degrees(orientation.rotation.degrees());
+                window.set_flip_horizontal(orientation.flip_horizontal);
+                window.set_flip_vertical(orientation.flip_vertical);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set whether a crop region is active
+    pub async fn set_crop_active(&self, active: bool) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_crop_active(active);
+                if !active {
+                    window.set_crop_mode(false);
+                }
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the zoom indicator
+    pub async fn set_zoom(&self, zoom: Zoom) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_zoom_factor(zoom.factor);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the zoom quality selector
+    pub async fn set_zoom_quality(&self, quality: ResampleQuality) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_zoom_quality_index(quality.index() as i32);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the measurement overlay
+    pub async fn set_measurements(&self, overlays: Vec<MeasurementOverlay>) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                let measurements: Vec<MeasurementData> = overlays
+                    .into_iter()
+                    .map(|m| MeasurementData {
+                        id: m.id as i32,
+                        geometry: match m.kind {
+                            MeasurementKind::Distance => MeasurementGeometry::Line,
+                            MeasurementKind::Angle => MeasurementGeometry::Angle,
+                            MeasurementKind::Ellipse => MeasurementGeometry::Ellipse,
+                        },
+                        x1: m.x1,
+                        y1: m.y1,
+                        x2: m.x2,
+                        y2: m.y2,
+                        x3: m.x3,
+                        y3: m.y3,
+                        label: m.label.into(),
+                    })
+                    .collect();
+                window.set_measurements(slint::ModelRc::new(slint::VecModel::from(measurements)));
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the ruler ticks for the current view
+    pub async fn set_rulers(&self, rulers: Rulers) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                let ticks = |ticks: Vec<crate::frontend::rulers::RulerTick>| -> slint::ModelRc<RulerTick> {
+                    let ticks: Vec<RulerTick> = ticks
+                        .into_iter()
+                        .map(|t| RulerTick { position: t.position, major: t.major, label: t.label.into() })
+                        .collect();
+                    slint::ModelRc::new(slint::VecModel::from(ticks))
+                };
+                window.set_horizontal_ticks(ticks(rulers.horizontal));
+                window.set_vertical_ticks(ticks(rulers.vertical));
+                window.set_ruler_unit(rulers.unit.into());
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Show or hide the rulers and grid
+    pub async fn set_rulers_visible(&self, rulers: bool, grid: bool) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_show_rulers(rulers);
+                window.set_show_grid(grid);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Set the calibration status text
+    pub async fn set_calibration_status(&self, status: String) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_calibration_status(status.into());
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
     }
 
-    /// Initialize default UI state
-    fn initialize_ui_state(window: &MainWindow) -> Result<(), SlintBridgeError> {
-        // Set initial values
-        window.set_connection_status("Disconnected - Waiting for medical device".into());
-        window.set_shm_name("ultrasound_frames".into());
-        window.set_format("YUV".into());
-        window.set_resolution("0x0".into());
-        window.set_fps(0.0);
-        window.set_latency_ms(0.0);
-        window.set_total_frames(0);
-        window.set_catch_up_mode(false);
-        window.set_is_connected(false);
-        window.set_has_frame(false);
-        window.set_frame_id(0);
-        window.set_sequence_number(0);
-        window.set_frame_format("Unknown".into());
+    /// Set the annotation overlay
+    pub async fn set_annotations(&self, overlays: Vec<AnnotationOverlay>) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
 
-        info!("✅ Slint UI state initialized");
-        Ok(())
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                let annotations: Vec<AnnotationData> = overlays
+                    .into_iter()
+                    .map(|a| AnnotationData {
+                        id: a.id as i32,
+                        x: a.x,
+                        y: a.y,
+                        text: a.text.into(),
+                    })
+                    .collect();
+                window.set_annotations(slint::ModelRc::new(slint::VecModel::from(annotations)));
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
     }
 
-    /// Setup reconnect button callback
-    pub async fn on_reconnect_clicked<F>(&self, callback: F) -> Result<(), SlintBridgeError>
-    where
-        F: Fn() + Send + Sync + 'static,
-    {
-        let callback = Arc::new(callback);
-        self.main_window.on_reconnect_clicked(move || {
-            callback();
+    /// Set the ROI outline (bounds in view coordinates, None hides it)
+    pub async fn set_roi(&self, roi: Option<(RoiShape, (f32, f32, f32, f32))>) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_roi_visible(roi.is_some());
+                if let Some((shape, (x, y, width, height))) = roi {
+                    window.set_roi_ellipse(shape == RoiShape::Ellipse);
+                    window.set_roi_x(x);
+                    window.set_roi_y(y);
+                    window.set_roi_w(width);
+                    window.set_roi_h(height);
+                } else {
+                    window.set_roi_statistics("".into());
+                }
+            }
         });
-        Ok(())
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
     }
 
-    /// Setup catch-up mode toggle callback
-    pub async fn on_toggle_catch_up<F>(&self, callback: F) -> Result<(), SlintBridgeError>
-    where
-        F: Fn(bool) + Send + Sync + 'static,
-    {
-        let callback = Arc::new(callback);
-        let main_window_weak = self.main_window.as_weak();
-        self.main_window.on_toggle_catch_up(move || {
-            if let Some(window) = main_window_weak.upgrade() {
-                let current_mode = window.get_catch_up_mode();
-                callback(!current_mode);
+    /// Set the live ROI statistics text
+    pub async fn set_roi_statistics(&self, summary: String) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                if window.get_roi_visible() {
+                    window.set_roi_statistics(summary.into());
+                }
             }
         });
-        Ok(())
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
     }
 
-    /// Setup settings button callback
-    pub async fn on_settings_clicked<F>(&self, callback: F) -> Result<(), SlintBridgeError>
-    where
-        F: Fn() + Send + Sync + 'static,
-    {
-        let callback = Arc::new(callback);
-        self.main_window.on_settings_clicked(move || {
-            callback();
+    /// Update the number of frames lost to sequence gaps, skipped by catch-up, rejected by checksum
+    /// verification and dropped because the UI fell behind
+    pub async fn set_frame_loss(&self, dropped: i32, skipped: i32, corrupted: i32, consumer_dropped: i32) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_dropped_frames(dropped);
+                window.set_skipped_frames(skipped);
+                window.set_corrupted_frames(corrupted);
+                window.set_viewer_dropped_frames(consumer_dropped);
+            }
         });
-        Ok(())
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
     }
 
-    /// Setup about button callback
-    pub async fn on_about_clicked<F>(&self, callback: F) -> Result<(), SlintBridgeError>
-    where
-        F: Fn() + Send + Sync + 'static,
-    {
-        let callback = Arc::new(callback);
-        self.main_window.on_about_clicked(move || {
-            callback();
+    /// Update inter-frame jitter (standard deviation and maximum deviation of the frame interval)
+    pub async fn set_jitter(&self, jitter_ms: f32, max_jitter_ms: f32) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_jitter_ms(jitter_ms);
+                window.set_max_jitter_ms(max_jitter_ms);
+            }
         });
-        Ok(())
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
     }
 
-    /// Update connection status in the UI
-    pub async fn update_connection_status(&self, status: &str, connected: bool) -> Result<(), SlintBridgeError> {
-        let status = status.to_string();
+    /// Draw detection boxes and keypoints (view coordinates) over the displayed frame
+    pub async fn set_detections(&self, overlays: DetectionOverlays) -> Result<(), SlintBridgeError> {
         let main_window = self.main_window.as_weak();
 
-        // Use the invoke_from_event_loop_if_not_on_event_loop helper
         let result = slint::invoke_from_event_loop(move || {
             if let Some(window) = main_window.upgrade() {
-                window.set_connection_status(status.clone().into());
-                window.set_is_connected(connected);
-
-                debug!("🔄 UI connection status updated: {} (connected: {})", status.clone(), connected);
+                let boxes: Vec<DetectionBox> = overlays.boxes
+                    .into_iter()
+                    .map(|b| DetectionBox {
+                        x: b.rect.0,
+                        y: b.rect.1,
+                        w: b.rect.2,
+                        h: b.rect.3,
+                        caption: b.caption.into(),
+                    })
+                    .collect();
+                let points: Vec<DetectionPoint> = overlays.keypoints
+                    .into_iter()
+                    .map(|k| DetectionPoint { x: k.x, y: k.y, label: k.label.into() })
+                    .collect();
+                window.set_detection_boxes(slint::ModelRc::new(slint::VecModel::from(boxes)));
+                window.set_detection_points(slint::ModelRc::new(slint::VecModel::from(points)));
             }
         });
 
@@ -120,41 +1647,18 @@ impl SlintBridge {
         }
     }
 
-    /// Update frame in the UI
-    pub async fn update_frame(
-        &self,
-        image: Image,
-        resolution: &str,
-        format: &str,
-        frame_id: i32,
-        sequence_number: i32,
-    ) -> Result<(), SlintBridgeError> {
-        let resolution = resolution.to_string();
-        let format = format.to_string();
+    /// Show segmentation timing; the opacity slider starts at the configured value
+    pub async fn set_inference(&self, stats: InferenceStatistics) -> Result<(), SlintBridgeError> {
         let main_window = self.main_window.as_weak();
 
-        // Convert the image to raw data to avoid Send/Sync issues
-        let (width, height, rgba_data) = self.extract_image_data(image)?;
-
-        // Move the raw data to the UI thread and reconstruct the image
         let result = slint::invoke_from_event_loop(move || {
             if let Some(window) = main_window.upgrade() {
-                // Reconstruct the image from raw data
-                match Self::create_image_from_raw_data(rgba_data, width, height) {
-                    Ok(slint_image) => {
-                        window.set_current_frame(slint_image);
-                        window.set_resolution(resolution.clone().into());
-                        window.set_frame_format(format.clone().into());
-                        window.set_frame_id(frame_id);
-                        window.set_sequence_number(sequence_number);
-                        window.set_has_frame(true);
-
-                        debug!("🖼️ UI frame updated: {} {}", resolution.clone(), format.clone());
-                    }
-                    Err(e) => {
-                        error!("Failed to reconstruct image in UI thread: {}", e);
-                    }
+                if window.get_inference_model().is_empty() {
+                    window.set_overlay_opacity(stats.opacity);
                 }
+                window.set_inference_model(stats.model.into());
+                window.set_inference_ms(stats.last_ms as f32);
+                window.set_inference_interval(stats.interval as i32);
             }
         });
 
@@ -164,59 +1668,66 @@ impl SlintBridge {
         }
     }
 
-    /// Extract image data to avoid Send/Sync issues
-    fn extract_image_data(&self, image: Image) -> Result<(u32, u32, Vec<u8>), SlintBridgeError> {
-        // This is a simplified approach - in a real implementation you'd need
-        // to properly extract the image data from the Slint Image
-        // For now, we'll create a placeholder
-        let width = 640;
-        let height = 480;
-        let rgba_data = vec![128u8; (width * height * 4) as usize]; // Gray placeholder
+    /// Show or hide the HUD overlay and place it in a corner of the image view
+    pub async fn set_show_overlays(&self, enabled: bool, corner: HudCorner) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
 
-        Ok((width, height, rgba_data))
-    }
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_show_overlays(enabled);
+                window.set_hud_corner(corner.index() as i32);
+            }
+        });
 
-    /// Create Slint image from raw RGBA data
-    fn create_image_from_raw_data(rgba_data: Vec<u8>, width: u32, height: u32) -> Result<Image, SlintBridgeError> {
-        // Ensure data size is correct
-        let expected_size = (width * height * 4) as usize;
-        if rgba_data.len() != expected_size {
-            return Err(SlintBridgeError::InvalidImageData {
-                expected: expected_size,
-                actual: rgba_data.len(),
-            });
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
         }
+    }
 
-        // Create pixel buffer
-        let mut pixel_buffer = SharedPixelBuffer::<Rgba8Pixel>::new(width, height);
+    /// Show the active user and enable the features their role allows
+    ///
+    /// Closes the sign-in dialog once someone is signed in.
+    pub async fn set_active_user(&self, user: &ActiveUser, sign_in_enabled: bool) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+        let control_window = self.control_window.as_weak();
+        let user = user.clone();
 
-        // Copy RGBA data
-        let pixels = pixel_buffer.make_mut_bytes();
-        pixels.copy_from_slice(&rgba_data);
+        let result = slint::invoke_from_event_loop(move || {
+            let allows = |permission| user.role.allows(permission);
+            if let Some(window) = main_window.upgrade() {
+                window.set_current_user(user.display_name().into());
+                window.set_sign_in_enabled(sign_in_enabled);
+                window.set_can_export(allows(Permission::Export));
+                window.set_can_record(allows(Permission::Record));
+                window.set_can_change_settings(allows(Permission::ChangeSettings));
+                if user.is_signed_in() {
+                    window.set_login_open(false);
+                    window.set_login_error("".into());
+                }
+            }
+            if let Some(control) = control_window.upgrade() {
+                control.set_can_export(allows(Permission::Export));
+                control.set_can_record(allows(Permission::Record));
+                control.set_can_change_settings(allows(Permission::ChangeSettings));
+            }
+        });
 
-        // Create Slint image
-        Ok(Image::from_rgba8(pixel_buffer))
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
     }
 
-    /// Update statistics in the UI
-    pub async fn update_statistics(
-        &self,
-        fps: f32,
-        latency_ms: f32,
-        total_frames: i32,
-    ) -> Result<(), SlintBridgeError> {
+    /// Open the sign-in dialog, with the reason of a failed attempt if any
+    pub async fn open_login(&self, error: &str) -> Result<(), SlintBridgeError> {
         let main_window = self.main_window.as_weak();
+        let error = error.to_string();
 
         let result = slint::invoke_from_event_loop(move || {
             if let Some(window) = main_window.upgrade() {
-                window.set_fps(fps);
-                window.set_latency_ms(latency_ms);
-                window.set_total_frames(total_frames);
-
-                if fps > 0.0 {
-                    debug!("📊 UI stats updated: {:.1} FPS, {:.1}ms latency, {} frames",
-                           fps, latency_ms, total_frames);
-                }
+                window.set_login_error(error.into());
+                window.set_login_open(true);
             }
         });
 
@@ -226,21 +1737,44 @@ impl SlintBridge {
         }
     }
 
-    /// Update configuration in the UI
-    pub async fn update_config(&self, shm_name: &str, format: &str) -> Result<(), SlintBridgeError> {
-        let shm_name = shm_name.to_string();
-        let format = format.to_string();
+    /// Ask for a PIN, rather than a click, to leave privacy mode
+    pub fn set_privacy_pin_required(&self, required: bool) {
+        self.main_window.set_privacy_pin_required(required);
+    }
+
+    /// Hide or show the display in both windows, with the reason of a failed resume if any
+    pub async fn set_privacy_locked(&self, locked: bool, error: &str) -> Result<(), SlintBridgeError> {
         let main_window = self.main_window.as_weak();
+        let control_window = self.control_window.as_weak();
+        let error = error.to_string();
 
         let result = slint::invoke_from_event_loop(move || {
-            let shm_str_name = shm_name.clone();
-            let format_str = format.clone();
-
             if let Some(window) = main_window.upgrade() {
-                window.set_shm_name(shm_name.into());
-                window.set_format(format.into());
+                window.set_privacy_error(error.into());
+                window.set_privacy_locked(locked);
+            }
+            if let Some(control) = control_window.upgrade() {
+                control.set_privacy_locked(locked);
+            }
+        });
 
-                debug!("⚙️ UI config updated: {} ({})", shm_str_name, format_str);
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Switch the color theme of the whole UI
+    pub async fn set_theme(&self, theme: Theme) -> Result<(), SlintBridgeError> {
+        let main_window = self.main_window.as_weak();
+        let control_window = self.control_window.as_weak();
+
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                window.set_theme(theme.index() as i32);
+            }
+            if let Some(control) = control_window.upgrade() {
+                control.set_theme(theme.index() as i32);
             }
         });
 
@@ -250,6 +1784,58 @@ impl SlintBridge {
         }
     }
 
+    /// Convert UI state settings to the Slint settings struct
+    fn settings_to_ui(settings: &UiSettings) -> SettingsData {
+        SettingsData {
+            shm_name: settings.shm_name.as_str().into(),
+            format: settings.format.to_lowercase().into(),
+            width: settings.width as i32,
+            height: settings.height as i32,
+            catch_up: settings.catch_up_mode,
+            adaptive_catch_up: settings.adaptive_catch_up,
+            catch_up_backlog: settings.catch_up_backlog as i32,
+            max_display_fps: settings.max_display_fps as i32,
+            auto_reconnect: settings.auto_reconnect,
+            reconnect_delay_ms: settings.reconnect_delay_ms as i32,
+            max_reconnect_attempts: settings.max_reconnect_attempts as i32,
+            show_overlays: settings.show_overlays,
+            hud_corner: settings.hud_corner.index() as i32,
+            theme: settings.theme.index() as i32,
+            show_debug_info: settings.show_debug_info,
+            drop_alert_percent: settings.drop_alert_percent as i32,
+            export_dir: settings.export_dir.as_str().into(),
+            dump_dir: settings.dump_dir.as_str().into(),
+            report_on_exit: settings.report_on_exit,
+            length_unit: settings.length_unit.index() as i32,
+        }
+    }
+
+    /// Convert the Slint settings struct back to UI state settings
+    fn settings_from_ui(data: &SettingsData) -> UiSettings {
+        UiSettings {
+            shm_name: data.shm_name.trim().to_string(),
+            format: data.format.to_string(),
+            width: data.width.max(1) as usize,
+            height: data.height.max(1) as usize,
+            catch_up_mode: data.catch_up,
+            adaptive_catch_up: data.adaptive_catch_up,
+            catch_up_backlog: data.catch_up_backlog.max(1) as u64,
+            max_display_fps: data.max_display_fps.clamp(0, 240) as u32,
+            auto_reconnect: data.auto_reconnect,
+            reconnect_delay_ms: data.reconnect_delay_ms.max(1) as u64,
+            max_reconnect_attempts: data.max_reconnect_attempts.max(1) as u32,
+            show_overlays: data.show_overlays,
+            hud_corner: HudCorner::from_index(data.hud_corner.max(0) as usize),
+            theme: Theme::from_index(data.theme.max(0) as usize),
+            show_debug_info: data.show_debug_info,
+            drop_alert_percent: data.drop_alert_percent.clamp(0, 100) as u32,
+            export_dir: data.export_dir.trim().to_string(),
+            dump_dir: data.dump_dir.trim().to_string(),
+            report_on_exit: data.report_on_exit,
+            length_unit: LengthUnit::from_index(data.length_unit.max(0) as usize),
+        }
+    }
+
     /// Set catch-up mode in the UI
     pub async fn set_catch_up_mode(&self, enabled: bool) -> Result<(), SlintBridgeError> {
         let main_window = self.main_window.as_weak();
@@ -279,21 +1865,86 @@ impl SlintBridge {
 
     /// Show a notification or status message
     pub async fn show_notification(&self, message: &str, is_error: bool) -> Result<(), SlintBridgeError> {
-        let message = message.to_string();
+        let level = if is_error { NotificationLevel::Error } else { NotificationLevel::Info };
+        self.notify(level, message).await
+    }
+
+    /// Queue a notification toast with the given severity
+    pub async fn notify(&self, level: NotificationLevel, message: &str) -> Result<(), SlintBridgeError> {
+        info!("📢 UI notification {} {}", level.icon(), message);
+
+        self.notifications.lock().push(level, message);
+
+        let notifications = Arc::clone(&self.notifications);
         let main_window = self.main_window.as_weak();
 
         let result = slint::invoke_from_event_loop(move || {
             if let Some(window) = main_window.upgrade() {
-                // For now, update the connection status to show the notification
-                // In a more complex implementation, you might have a separate notification area
-                let status = if is_error {
-                    format!("Error: {}", message)
-                } else {
-                    format!("Info: {}", message)
-                };
-                window.set_connection_status(status.into());
+                Self::refresh_notifications(&window, &notifications.lock());
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Show an error to the operator: a modal dialog if critical, a toast otherwise
+    ///
+    /// Both show the user message and suggested action, with the raw error behind a details expander.
+    pub async fn report_error(&self, error: &MiViError) -> Result<(), SlintBridgeError> {
+        let notice = ErrorNotice::from_error(error);
+        error!("❌ {} [{}]: {}", notice.title, notice.severity, error);
+
+        if !notice.is_modal() {
+            let level = NotificationLevel::for_severity(notice.severity);
+            self.notifications.lock().push_with_details(level, notice.toast_message(), &notice.details);
+
+            let notifications = Arc::clone(&self.notifications);
+            let main_window = self.main_window.as_weak();
+            return slint::invoke_from_event_loop(move || {
+                if let Some(window) = main_window.upgrade() {
+                    Self::refresh_notifications(&window, &notifications.lock());
+                }
+            })
+            .map_err(|e| SlintBridgeError::UiUpdate(e.to_string()));
+        }
+
+        // Keep critical errors in the history as well
+        self.notifications.lock().record(NotificationLevel::Error, notice.toast_message(), &notice.details);
+
+        let notifications = Arc::clone(&self.notifications);
+        let main_window = self.main_window.as_weak();
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                Self::refresh_notifications(&window, &notifications.lock());
+                window.set_error_dialog(ErrorDialogData {
+                    title: notice.title.into(),
+                    message: notice.message.into(),
+                    action: notice.action.into(),
+                    details: notice.details.into(),
+                });
+                window.set_error_open(true);
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(SlintBridgeError::UiUpdate(e.to_string())),
+        }
+    }
+
+    /// Enable or disable popup toasts (history is always recorded)
+    pub async fn set_notifications_enabled(&self, enabled: bool) -> Result<(), SlintBridgeError> {
+        self.notifications.lock().set_toasts_enabled(enabled);
+
+        let notifications = Arc::clone(&self.notifications);
+        let main_window = self.main_window.as_weak();
 
-                info!("📢 UI notification: {} (error: {})", message, is_error);
+        let result = slint::invoke_from_event_loop(move || {
+            if let Some(window) = main_window.upgrade() {
+                Self::refresh_notifications(&window, &notifications.lock());
             }
         });
 
@@ -306,6 +1957,7 @@ impl SlintBridge {
     /// Clear the current frame from the UI
     pub async fn clear_frame(&self) -> Result<(), SlintBridgeError> {
         let main_window = self.main_window.as_weak();
+        self.latency.lock().clear();
 
         let result = slint::invoke_from_event_loop(move || {
             if let Some(window) = main_window.upgrade() {
@@ -388,6 +2040,36 @@ impl SlintBridge {
     }
 }
 
+/// Digital zoom gesture from the image view or the zoom controls
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoomAction {
+    /// Zoom in (positive) or out around a point given as fractions of the image
+    Step { steps: f32, x: f32, y: f32 },
+    /// Drag the view by fractions of the image
+    Pan { dx: f32, dy: f32 },
+    /// Return to the whole frame
+    Reset,
+}
+
+impl ZoomAction {
+    /// Magnification change per wheel notch or button press
+    const STEP_MULTIPLIER: f32 = 1.25;
+
+    /// Fractional number of steps giving a magnification change (pinch gestures)
+    pub fn steps_for_scale(scale: f32) -> f32 {
+        scale.ln() / Self::STEP_MULTIPLIER.ln()
+    }
+
+    /// Apply this gesture to the current zoom
+    pub fn apply(self, zoom: &Zoom) -> Zoom {
+        match self {
+            ZoomAction::Step { steps, x, y } => zoom.zoom_at(Self::STEP_MULTIPLIER.powf(steps), x, y),
+            ZoomAction::Pan { dx, dy } => zoom.pan(dx, dy),
+            ZoomAction::Reset => Zoom::default(),
+        }
+    }
+}
+
 /// Slint bridge errors
 #[derive(Debug, thiserror::Error)]
 pub enum SlintBridgeError {
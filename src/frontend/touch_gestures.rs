@@ -0,0 +1,158 @@
+// src/frontend/touch_gestures.rs - Pinch-Zoom and Two-Finger Pan Recognition for Touchscreens
+
+use std::collections::HashMap;
+
+/// Finger distance below which the pinch scale is too noisy to use (logical pixels)
+const MIN_PINCH_DISTANCE: f32 = 10.0;
+
+/// Phase of a single touch point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    /// Lifted or cancelled
+    Ended,
+}
+
+/// Two-finger gesture recognized from raw touch points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchGesture {
+    /// A second finger touched down; single-finger interactions must be abandoned
+    Began,
+    /// Fingers moved: scale change around the centroid and centroid movement, in logical pixels
+    Moved { scale: f32, center_x: f32, center_y: f32, dx: f32, dy: f32 },
+}
+
+/// Result of feeding one touch event to the recognizer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchResponse {
+    pub gesture: Option<TouchGesture>,
+    /// Whether the UI should still see this touch as its pointer
+    pub forward_to_ui: bool,
+}
+
+/// Tracks touch points and turns two-finger movement into pinch and pan gestures
+#[derive(Debug, Default)]
+pub struct TouchGestures {
+    touches: HashMap<u64, (f32, f32)>,
+    /// First finger down, delivered to the UI as the pointer
+    primary: Option<u64>,
+    /// Fingers driving the current gesture
+    pair: Option<(u64, u64)>,
+}
+
+impl TouchGestures {
+    /// Feed a touch event in logical window coordinates
+    pub fn handle(&mut self, id: u64, phase: TouchPhase, x: f32, y: f32) -> TouchResponse {
+        if phase == TouchPhase::Started && self.touches.is_empty() {
+            self.primary = Some(id);
+        }
+        let forward_to_ui = self.primary == Some(id);
+
+        let gesture = match phase {
+            TouchPhase::Started => {
+                self.touches.insert(id, (x, y));
+                if self.pair.is_none() && self.touches.len() == 2 {
+                    let mut ids = self.touches.keys().copied();
+                    self.pair = ids.next().zip(ids.next());
+                    Some(TouchGesture::Began)
+                } else {
+                    None
+                }
+            }
+            TouchPhase::Moved => {
+                let before = self.pair_geometry();
+                if let Some(point) = self.touches.get_mut(&id) {
+                    *point = (x, y);
+                }
+                match (before, self.pair_geometry()) {
+                    (Some(before), Some(after)) if before != after => {
+                        let scale = if before.2 >= MIN_PINCH_DISTANCE && after.2 >= MIN_PINCH_DISTANCE {
+                            after.2 / before.2
+                        } else {
+                            1.0
+                        };
+                        Some(TouchGesture::Moved {
+                            scale,
+                            center_x: after.0,
+                            center_y: after.1,
+                            dx: after.0 - before.0,
+                            dy: after.1 - before.1,
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            TouchPhase::Ended => {
+                self.touches.remove(&id);
+                if self.pair.is_some_and(|(a, b)| a == id || b == id) {
+                    self.pair = None;
+                }
+                if self.touches.is_empty() {
+                    self.primary = None;
+                }
+                None
+            }
+        };
+
+        TouchResponse { gesture, forward_to_ui }
+    }
+
+    /// Check if a two-finger gesture is in progress
+    pub fn is_active(&self) -> bool {
+        self.pair.is_some()
+    }
+
+    /// Centroid and finger distance of the current gesture
+    fn pair_geometry(&self) -> Option<(f32, f32, f32)> {
+        let (a, b) = self.pair?;
+        let (ax, ay) = *self.touches.get(&a)?;
+        let (bx, by) = *self.touches.get(&b)?;
+        Some(((ax + bx) / 2.0, (ay + by) / 2.0, (bx - ax).hypot(by - ay)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinch_and_pan() {
+        let mut gestures = TouchGestures::default();
+
+        let first = gestures.handle(1, TouchPhase::Started, 100.0, 100.0);
+        assert_eq!(first, TouchResponse { gesture: None, forward_to_ui: true });
+
+        let second = gestures.handle(2, TouchPhase::Started, 200.0, 100.0);
+        assert_eq!(second.gesture, Some(TouchGesture::Began));
+        assert!(!second.forward_to_ui);
+        assert!(gestures.is_active());
+
+        // Spreading the second finger doubles the distance around the new centroid
+        let pinch = gestures.handle(2, TouchPhase::Moved, 300.0, 100.0);
+        assert_eq!(
+            pinch.gesture,
+            Some(TouchGesture::Moved { scale: 2.0, center_x: 200.0, center_y: 100.0, dx: 50.0, dy: 0.0 })
+        );
+
+        // Moving both fingers together pans without a net scale change
+        let scales: Vec<f32> = [(1, 100.0), (2, 300.0)]
+            .into_iter()
+            .filter_map(|(id, x)| match gestures.handle(id, TouchPhase::Moved, x, 150.0).gesture {
+                Some(TouchGesture::Moved { scale, dx, dy, .. }) => {
+                    assert_eq!((dx, dy), (0.0, 25.0));
+                    Some(scale)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(scales.len(), 2);
+        assert!((scales[0] * scales[1] - 1.0).abs() < 1e-3);
+
+        // Lifting a finger ends the gesture; the primary finger is still delivered to the UI
+        gestures.handle(2, TouchPhase::Ended, 300.0, 150.0);
+        assert!(!gestures.is_active());
+        assert_eq!(gestures.handle(1, TouchPhase::Moved, 110.0, 150.0).gesture, None);
+        assert!(gestures.handle(1, TouchPhase::Ended, 110.0, 150.0).forward_to_ui);
+    }
+}
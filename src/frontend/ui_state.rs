@@ -1,9 +1,19 @@
 // src/frontend/ui_state.rs - UI State Management for Medical Frame Viewer
 
+use std::collections::HashMap;
 use std::time::Instant;
 use serde::{Deserialize, Serialize};
 
-use crate::backend::{BackendConfig, types::ConnectionConfig};
+use crate::backend::{AdaptiveCatchUp, BackendConfig, DEFAULT_EVENT_CAPACITY, FrameDropPolicy, ClaheParams, Colormap, Deinterlace, DisplayGeometry, Dithering, FrameDetections, FramePolling, Gamma, Handshake, MemoryConfig, Orientation, PixelSpacing, ReconnectPolicy, ResampleQuality, Roi, ThreadScheduling, WindowLevel, Zoom, types::ConnectionConfig};
+use crate::frontend::measurements::{AnnotationSet, Calibration, LengthUnit, MeasurementSet, SpacingSource};
+use crate::frontend::rulers::Rulers;
+use crate::frontend::audit_log::{AuditAction, AuditLog};
+use crate::frontend::session_report::SessionLog;
+use crate::encryption::RecordingCipher;
+use crate::frontend::access::{AccessError, ActiveUser, Permission, UserDirectory};
+use crate::redaction::AnonymizationProfile;
+use crate::backend::detections::DetectionOverlays;
+use crate::frontend::scripting::{ScriptEvent, ScriptHost};
 
 /// UI state for the medical frame viewer application
 #[derive(Debug, Clone)]
@@ -21,6 +31,8 @@ pub struct UiState {
     pub sequence_number: i32,
     pub resolution: String,
     pub frame_format: String,
+    pub frame_width: u32,
+    pub frame_height: u32,
     pub last_frame_time: Instant,
     
     // Performance metrics
@@ -28,16 +40,96 @@ pub struct UiState {
     pub latency_ms: f32,
     pub total_frames: i32,
     pub dropped_frames: i32,
+    pub corrupted_frames: i32,
+    /// Producer protocol as shown in the header, empty until a producer is connected
+    pub producer_protocol: String,
+    /// Drop rate (percent per statistics interval) that raises an alert; 0 disables it
+    pub drop_alert_percent: u32,
+    pub drop_alert_active: bool,
+    /// Received and dropped totals at the previous drop rate check
+    pub drop_alert_baseline: (u64, u64),
     
     // Configuration
     pub catch_up_mode: bool,
+    /// Skip to the latest frame only while the backlog exceeds `catch_up_backlog`
+    pub adaptive_catch_up: bool,
+    pub catch_up_backlog: u64,
     pub format: String,
     pub verbose_logging: bool,
     pub reconnect_delay_ms: u64,
+    pub max_reconnect_attempts: u32,
+    /// Exponential backoff on top of `reconnect_delay_ms`
+    pub reconnect_multiplier: f64,
+    pub reconnect_max_delay_ms: u64,
+    pub reconnect_jitter: f64,
+    pub reconnect_reset_on_success: bool,
+    pub width: usize,
+    pub height: usize,
+    
+    // Image adjustments
+    pub window_center: f32,
+    pub window_width: f32,
+    pub gamma_by_profile: HashMap<String, f32>,
+    pub colormap: Colormap,
+    pub clahe_enabled: bool,
+    pub clahe_clip_limit: f32,
+    pub speckle_strength: f32,
+    pub edge_enhancement: f32,
+    pub persistence_frames: u32,
+    pub orientation: Orientation,
+    pub crop_active: bool,
+    pub zoom: Zoom,
+    pub zoom_quality: ResampleQuality,
+    pub measurements: MeasurementSet,
+    pub annotations: AnnotationSet,
+    pub roi: Option<Roi>,
+    pub frame_geometry: DisplayGeometry,
+    pub detections: Option<FrameDetections>, // Pushed by external analysis for the displayed frame
+    pub length_unit: LengthUnit,
+    pub metadata_spacing: Option<PixelSpacing>,
+    pub manual_spacing: Option<PixelSpacing>,
+    pub show_rulers: bool,
+    pub show_grid: bool,
+    
+    // Export paths
+    pub export_dir: String,
+    pub dump_dir: String,
+    
+    // Device profile
+    pub device_profile: Option<String>,
+    pub frame_timeout_ms: u64,
+    pub expected_fps: f64,
+    pub latency_target_ms: f64,
+    /// Display frame-rate cap (0 = unlimited)
+    pub max_display_fps: u32,
+    /// Backend loop timing from the command line (not persisted)
+    pub frame_polling: FramePolling,
+    pub stats_interval: std::time::Duration,
+    pub frame_drop_policy: FrameDropPolicy,
+    pub processing_deadline: Option<std::time::Duration>,
+    pub profile_duration: std::time::Duration,
+    /// Traceability log of connections, exports and setting changes (--audit-log)
+    pub audit_log: Option<AuditLog>,
+    /// Applied to measurement exports and session reports (--export-identified keeps identifiers)
+    pub export_anonymization: AnonymizationProfile,
+    /// Seals session reports and their snapshots (--recording-key-file or --recording-keyring)
+    pub recording_encryption: Option<RecordingCipher>,
+    /// Accounts that may sign in (--users-file); without them the system user has full access
+    pub users: Option<UserDirectory>,
+    pub active_user: ActiveUser,
+    /// PIN that resumes the display from privacy mode (--privacy-pin); None resumes on a click
+    pub privacy_pin: Option<String>,
+    pub scripts: Option<std::sync::Arc<ScriptHost>>,
+    pub memory: MemoryConfig,
+    /// Deinterlacing of the stream, carried into every connect
+    pub deinterlace: Deinterlace,
     
     // UI preferences
     pub window_title: String,
     pub show_debug_info: bool,
+    pub show_overlays: bool,
+    pub hud_corner: HudCorner,
+    pub theme: Theme,
     pub auto_reconnect: bool,
     pub notification_enabled: bool,
     
@@ -48,6 +140,9 @@ pub struct UiState {
     
     // Statistics
     pub session_stats: SessionStatistics,
+    pub session_log: SessionLog,
+    /// Write a session summary report when the application exits
+    pub report_on_exit: bool,
 }
 
 impl UiState {
@@ -65,20 +160,86 @@ impl UiState {
             sequence_number: 0,
             resolution: "0x0".to_string(),
             frame_format: "Unknown".to_string(),
+            frame_width: 0,
+            frame_height: 0,
             last_frame_time: Instant::now(),
             
             fps: 0.0,
             latency_ms: 0.0,
             total_frames: 0,
             dropped_frames: 0,
+            corrupted_frames: 0,
+            producer_protocol: String::new(),
+            drop_alert_percent: default_drop_alert_percent(),
+            drop_alert_active: false,
+            drop_alert_baseline: (0, 0),
             
             catch_up_mode: false,
+            adaptive_catch_up: false,
+            catch_up_backlog: default_catch_up_backlog(),
             format: "YUV".to_string(),
             verbose_logging: false,
             reconnect_delay_ms: 1000,
+            max_reconnect_attempts: default_max_reconnect_attempts(),
+            reconnect_multiplier: default_reconnect_multiplier(),
+            reconnect_max_delay_ms: default_reconnect_max_delay_ms(),
+            reconnect_jitter: default_reconnect_jitter(),
+            reconnect_reset_on_success: true,
+            width: default_width(),
+            height: default_height(),
+            
+            window_center: default_window_center(),
+            window_width: default_window_width(),
+            gamma_by_profile: HashMap::new(),
+            colormap: Colormap::default(),
+            clahe_enabled: false,
+            clahe_clip_limit: default_clahe_clip_limit(),
+            speckle_strength: 0.0,
+            edge_enhancement: 0.0,
+            persistence_frames: 1,
+            orientation: Orientation::default(),
+            crop_active: false,
+            zoom: Zoom::default(),
+            zoom_quality: ResampleQuality::default(),
+            measurements: MeasurementSet::default(),
+            annotations: AnnotationSet::default(),
+            roi: None,
+            frame_geometry: DisplayGeometry::default(),
+            detections: None,
+            length_unit: LengthUnit::default(),
+            metadata_spacing: None,
+            manual_spacing: None,
+            show_rulers: false,
+            show_grid: false,
+            
+            export_dir: default_export_dir(),
+            dump_dir: default_dump_dir(),
+            
+            device_profile: None,
+            frame_timeout_ms: 5000,
+            expected_fps: 30.0,
+            latency_target_ms: 50.0,
+            max_display_fps: 0,
+            frame_polling: FramePolling::Auto,
+            stats_interval: std::time::Duration::from_secs(1),
+            frame_drop_policy: FrameDropPolicy::default(),
+            processing_deadline: None,
+            profile_duration: std::time::Duration::from_secs(10),
+            audit_log: None,
+            export_anonymization: AnonymizationProfile::default(),
+            recording_encryption: None,
+            users: None,
+            active_user: ActiveUser::system(),
+            privacy_pin: None,
+            scripts: None,
+            memory: MemoryConfig::default(),
+            deinterlace: Deinterlace::Off,
             
             window_title: "MiVi - Medical Imaging Virtual Intelligence".to_string(),
             show_debug_info: false,
+            show_overlays: true,
+            hud_corner: HudCorner::default(),
+            theme: Theme::default(),
             auto_reconnect: true,
             notification_enabled: true,
             
@@ -87,6 +248,8 @@ impl UiState {
             study_info: None,
             
             session_stats: SessionStatistics::new(),
+            session_log: SessionLog::default(),
+            report_on_exit: false,
         }
     }
     
@@ -137,30 +300,322 @@ impl UiState {
         self.session_stats.update_performance(fps, latency);
     }
     
+    /// Check the drop rate since the previous check, returning it when it newly exceeds the alert threshold
+    pub fn check_drop_alert(&mut self, received: u64, dropped: u64) -> Option<f64> {
+        let (last_received, last_dropped) = self.drop_alert_baseline;
+        self.drop_alert_baseline = (received, dropped);
+        
+        let interval_dropped = dropped.saturating_sub(last_dropped);
+        let interval_sent = received.saturating_sub(last_received) + interval_dropped;
+        if self.drop_alert_percent == 0 || interval_sent == 0 {
+            self.drop_alert_active = false;
+            return None;
+        }
+        
+        let rate = interval_dropped as f64 / interval_sent as f64 * 100.0;
+        let exceeded = rate > self.drop_alert_percent as f64;
+        let newly_exceeded = exceeded && !self.drop_alert_active;
+        self.drop_alert_active = exceeded;
+        newly_exceeded.then_some(rate)
+    }
+    
+    /// Update the corrupted frame count, returning how many were rejected since the last update
+    pub fn update_corrupted_frames(&mut self, corrupted: u64) -> u64 {
+        // The count restarts with each connection
+        let newly_corrupted = corrupted.saturating_sub(self.corrupted_frames as u64);
+        self.corrupted_frames = corrupted as i32;
+        newly_corrupted
+    }
+    
+    /// Update the producer protocol description, returning it if it changed
+    pub fn update_producer_protocol(&mut self, protocol: Option<&Handshake>) -> Option<String> {
+        let description = protocol.map(Handshake::describe).unwrap_or_default();
+        if description == self.producer_protocol {
+            return None;
+        }
+        self.producer_protocol = description.clone();
+        Some(description)
+    }
+    
+    /// Append to the audit log, if one is configured
+    pub fn audit(&self, action: AuditAction, detail: impl Into<String>) {
+        if let Some(log) = &self.audit_log {
+            if let Err(e) = log.record(action, detail) {
+                tracing::error!("❌ Audit log write failed for {:?}: {}", action, e);
+            }
+        }
+    }
+    
+    /// Pass an event to the automation script, if one is running
+    pub fn script_event(&self, event: ScriptEvent) {
+        if let Some(scripts) = &self.scripts {
+            scripts.dispatch(event);
+        }
+    }
+    
+    /// Check that the active user may use a feature, auditing refusals
+    pub fn require(&self, permission: Permission) -> Result<(), AccessError> {
+        let result = self.active_user.require(permission);
+        if let Err(e) = &result {
+            self.audit(AuditAction::AccessDenied, e.to_string());
+        }
+        result
+    }
+    
+    /// Make `user` the active user for gating, the audit log and reports
+    pub fn sign_in(&mut self, user: ActiveUser) {
+        if let Some(log) = &self.audit_log {
+            log.set_user(user.name.clone());
+        }
+        self.audit(AuditAction::SignedIn, user.role.name());
+        self.active_user = user;
+    }
+    
+    /// Sign the active user out, leaving view-only access
+    pub fn sign_out(&mut self) {
+        self.audit(AuditAction::SignedOut, "");
+        if let Some(log) = &self.audit_log {
+            log.set_user(ActiveUser::system().name);
+        }
+        self.active_user = ActiveUser::signed_out();
+    }
+    
+    /// Check the PIN entered to leave privacy mode, auditing the outcome
+    pub fn unlock_privacy(&self, pin: &str) -> bool {
+        let unlocked = match &self.privacy_pin {
+            // Compare without an early exit so timing does not reveal the matching prefix
+            Some(expected) => {
+                expected.len() == pin.len()
+                    && expected.bytes().zip(pin.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+            }
+            None => true,
+        };
+        if unlocked {
+            self.audit(AuditAction::PrivacyUnlocked, "");
+        } else {
+            self.audit(AuditAction::PrivacyUnlockFailed, "");
+        }
+        unlocked
+    }
+    
     /// Get backend configuration from UI state
     pub fn get_backend_config(&self) -> BackendConfig {
         BackendConfig {
             shm_name: self.shm_name.clone(),
             format: self.format.clone(),
-            width: 1024, // Default width
-            height: 768, // Default height
+            width: self.width,
+            height: self.height,
             catch_up: self.catch_up_mode,
+            adaptive_catch_up: self.adaptive_catch_up
+                .then(|| AdaptiveCatchUp::new(self.catch_up_backlog, AdaptiveCatchUp::default().resume_at)),
             verbose: self.verbose_logging,
-            reconnect_delay: std::time::Duration::from_millis(self.reconnect_delay_ms),
+            reconnect: self.reconnect_policy(),
+            frame_timeout: std::time::Duration::from_millis(self.frame_timeout_ms),
+            expected_fps: self.expected_fps,
+            latency_target_ms: self.latency_target_ms,
+            device_profile: self.device_profile.clone(),
+            max_display_fps: self.max_display_fps,
+            frame_polling: self.frame_polling,
+            stats_interval: self.stats_interval,
+            scheduling: ThreadScheduling::default(),
+            memory: self.memory,
+            gpu_upload: false,
+            deep_color: false,
+            dithering: Dithering::Off,
+            deinterlace: self.deinterlace,
+            display_calibration: None,
+            dump: None,
+            igtl_broadcast: None,
+            watchdog_timeout: None,
+            event_capacity: DEFAULT_EVENT_CAPACITY,
+            frame_drop_policy: self.frame_drop_policy,
+            processing_deadline: self.processing_deadline,
+            conversion_threads: None,
+            perf_monitor: false,
+            profile_duration: self.profile_duration,
+            health_listen: None,
+            detections_listen: None,
+            record_to: None,
+            forward_listen: None,
+            snapshot_dir: None,
+            snapshot_interval: std::time::Duration::from_secs(10),
+            auto_record_dir: None,
+            auto_record_idle: std::time::Duration::from_secs(30),
+            burst_capture: None,
+            audit_log: self.audit_log.as_ref().map(|log| log.path()),
+            export_anonymization: self.export_anonymization,
+            recording_encryption: self.recording_encryption.clone(),
+            users_file: None,
+            privacy_pin: self.privacy_pin.clone(),
+            inference: None,
+            plugin_dir: None,
+            script: None,
         }
     }
     
     /// Get connection configuration
     pub fn get_connection_config(&self) -> ConnectionConfig {
         ConnectionConfig {
-            reconnect_delay: std::time::Duration::from_millis(self.reconnect_delay_ms),
-            max_reconnect_attempts: if self.auto_reconnect { 10 } else { 1 },
-            frame_timeout: std::time::Duration::from_secs(5),
+            reconnect: self.reconnect_policy(),
+            frame_timeout: std::time::Duration::from_millis(self.frame_timeout_ms),
             buffer_size: 1024 * 1024 * 50, // 50MB
             verbose_logging: self.verbose_logging,
+            memory: self.memory,
+        }
+    }
+    
+    /// Get the current window/level adjustment
+    pub fn window_level(&self) -> WindowLevel {
+        WindowLevel::new(self.window_center, self.window_width)
+    }
+    
+    /// Set the window/level adjustment
+    pub fn set_window_level(&mut self, window_level: WindowLevel) {
+        self.window_center = window_level.center;
+        self.window_width = window_level.width;
+    }
+    
+    /// Get the key under which per-profile adjustments are stored
+    pub fn profile_key(&self) -> String {
+        self.device_profile.clone().unwrap_or_else(|| "default".to_string())
+    }
+    
+    /// Get the display gamma of the active device profile
+    pub fn gamma(&self) -> Gamma {
+        self.gamma_by_profile
+            .get(&self.profile_key())
+            .map(|&gamma| Gamma::new(gamma))
+            .unwrap_or_default()
+    }
+    
+    /// Set the display gamma of the active device profile
+    pub fn set_gamma(&mut self, gamma: Gamma) {
+        let key = self.profile_key();
+        self.gamma_by_profile.insert(key, gamma.0);
+    }
+    
+    /// Get the CLAHE parameters if contrast enhancement is enabled
+    pub fn clahe_params(&self) -> Option<ClaheParams> {
+        self.clahe_enabled.then(|| ClaheParams::new(self.clahe_clip_limit))
+    }
+    
+    /// Get the measurement calibration, preferring a manual calibration over frame metadata
+    pub fn calibration(&self) -> Calibration {
+        let spacing = self.manual_spacing.map(|s| (s, SpacingSource::Manual))
+            .or_else(|| self.metadata_spacing.map(|s| (s, SpacingSource::Metadata)));
+        Calibration { spacing, unit: self.length_unit }
+    }
+    
+    /// Get the ruler ticks for the current frame, zoom and calibration
+    pub fn rulers(&self) -> Rulers {
+        Rulers::new(&self.zoom, self.frame_width, self.frame_height, &self.calibration())
+    }
+    
+    /// Get the detection overlays of the displayed frame in view coordinates
+    pub fn detection_overlays(&self) -> DetectionOverlays {
+        self.detections.as_ref()
+            .map(|detections| detections.overlays(&self.frame_geometry, &self.zoom))
+            .unwrap_or_default()
+    }
+    
+    /// Get the reconnect attempt limit honouring the auto-reconnect switch
+    pub fn effective_reconnect_attempts(&self) -> u32 {
+        if self.auto_reconnect { self.max_reconnect_attempts.max(1) } else { 1 }
+    }
+    
+    /// Get the reconnect backoff policy honouring the auto-reconnect switch
+    pub fn reconnect_policy(&self) -> ReconnectPolicy {
+        let initial_delay = std::time::Duration::from_millis(self.reconnect_delay_ms);
+        ReconnectPolicy {
+            initial_delay,
+            multiplier: self.reconnect_multiplier,
+            max_delay: std::time::Duration::from_millis(self.reconnect_max_delay_ms).max(initial_delay),
+            jitter: self.reconnect_jitter,
+            max_attempts: self.effective_reconnect_attempts(),
+            reset_on_success: self.reconnect_reset_on_success,
+        }
+    }
+    
+    /// Describe the reconnect policy for the connection panel
+    pub fn reconnect_summary(&self) -> String {
+        if self.auto_reconnect { self.reconnect_policy().describe() } else { "off".to_string() }
+    }
+    
+    /// Adopt the reconnect policy of a backend configuration
+    pub fn set_reconnect_policy(&mut self, policy: &ReconnectPolicy) {
+        self.reconnect_delay_ms = policy.initial_delay.as_millis() as u64;
+        self.max_reconnect_attempts = policy.max_attempts;
+        self.reconnect_multiplier = policy.multiplier;
+        self.reconnect_max_delay_ms = policy.max_delay.as_millis() as u64;
+        self.reconnect_jitter = policy.jitter;
+        self.reconnect_reset_on_success = policy.reset_on_success;
+    }
+    
+    /// Get the user-editable settings
+    pub fn settings(&self) -> UiSettings {
+        UiSettings {
+            shm_name: self.shm_name.clone(),
+            format: self.format.clone(),
+            width: self.width,
+            height: self.height,
+            catch_up_mode: self.catch_up_mode,
+            adaptive_catch_up: self.adaptive_catch_up,
+            catch_up_backlog: self.catch_up_backlog,
+            max_display_fps: self.max_display_fps,
+            auto_reconnect: self.auto_reconnect,
+            reconnect_delay_ms: self.reconnect_delay_ms,
+            max_reconnect_attempts: self.max_reconnect_attempts,
+            show_overlays: self.show_overlays,
+            hud_corner: self.hud_corner,
+            theme: self.theme,
+            show_debug_info: self.show_debug_info,
+            drop_alert_percent: self.drop_alert_percent,
+            export_dir: self.export_dir.clone(),
+            dump_dir: self.dump_dir.clone(),
+            report_on_exit: self.report_on_exit,
+            length_unit: self.length_unit,
         }
     }
     
+    /// Apply user-edited settings, returning true if the connection must be re-established
+    pub fn apply_settings(&mut self, settings: UiSettings) -> bool {
+        let reconnect_required = self.shm_name != settings.shm_name
+            || !self.format.eq_ignore_ascii_case(&settings.format)
+            || self.width != settings.width
+            || self.height != settings.height;
+        
+        self.shm_name = settings.shm_name;
+        self.format = settings.format;
+        self.width = settings.width;
+        self.height = settings.height;
+        self.catch_up_mode = settings.catch_up_mode;
+        self.adaptive_catch_up = settings.adaptive_catch_up;
+        self.catch_up_backlog = settings.catch_up_backlog;
+        self.max_display_fps = settings.max_display_fps;
+        self.auto_reconnect = settings.auto_reconnect;
+        self.reconnect_delay_ms = settings.reconnect_delay_ms;
+        self.max_reconnect_attempts = settings.max_reconnect_attempts;
+        self.show_overlays = settings.show_overlays;
+        self.hud_corner = settings.hud_corner;
+        self.theme = settings.theme;
+        self.show_debug_info = settings.show_debug_info;
+        self.drop_alert_percent = settings.drop_alert_percent;
+        self.export_dir = settings.export_dir;
+        self.dump_dir = settings.dump_dir;
+        self.report_on_exit = settings.report_on_exit;
+        self.length_unit = settings.length_unit;
+        
+        reconnect_required
+    }
+    
+    /// Apply the device profile carried by a backend configuration
+    pub fn apply_device_profile(&mut self, config: &BackendConfig) {
+        self.device_profile = config.device_profile.clone();
+        self.frame_timeout_ms = config.frame_timeout.as_millis() as u64;
+        self.expected_fps = config.expected_fps;
+        self.latency_target_ms = config.latency_target_ms;
+    }
+    
     /// Check if reconnection should be attempted
     pub fn should_attempt_reconnection(&self) -> bool {
         if !self.auto_reconnect || self.is_connected {
@@ -238,6 +693,37 @@ impl UiState {
             show_debug_info: self.show_debug_info,
             auto_reconnect: self.auto_reconnect,
             notification_enabled: self.notification_enabled,
+            max_reconnect_attempts: self.max_reconnect_attempts,
+            reconnect_multiplier: self.reconnect_multiplier,
+            reconnect_max_delay_ms: self.reconnect_max_delay_ms,
+            reconnect_jitter: self.reconnect_jitter,
+            reconnect_reset_on_success: self.reconnect_reset_on_success,
+            width: self.width,
+            height: self.height,
+            show_overlays: self.show_overlays,
+            hud_corner: self.hud_corner,
+            theme: self.theme,
+            adaptive_catch_up: self.adaptive_catch_up,
+            catch_up_backlog: self.catch_up_backlog,
+            max_display_fps: self.max_display_fps,
+            drop_alert_percent: self.drop_alert_percent,
+            export_dir: self.export_dir.clone(),
+            dump_dir: self.dump_dir.clone(),
+            report_on_exit: self.report_on_exit,
+            window_center: self.window_center,
+            window_width: self.window_width,
+            gamma_by_profile: self.gamma_by_profile.clone(),
+            colormap: self.colormap,
+            clahe_enabled: self.clahe_enabled,
+            clahe_clip_limit: self.clahe_clip_limit,
+            speckle_strength: self.speckle_strength,
+            edge_enhancement: self.edge_enhancement,
+            persistence_frames: self.persistence_frames,
+            orientation: self.orientation,
+            zoom_quality: self.zoom_quality,
+            length_unit: self.length_unit,
+            show_rulers: self.show_rulers,
+            show_grid: self.show_grid,
         };
         
         serde_json::to_string_pretty(&serializable_state)
@@ -255,6 +741,36 @@ impl UiState {
         self.show_debug_info = serializable_state.show_debug_info;
         self.auto_reconnect = serializable_state.auto_reconnect;
         self.notification_enabled = serializable_state.notification_enabled;
+        self.max_reconnect_attempts = serializable_state.max_reconnect_attempts;
+        self.reconnect_multiplier = serializable_state.reconnect_multiplier;
+        self.reconnect_max_delay_ms = serializable_state.reconnect_max_delay_ms;
+        self.reconnect_jitter = serializable_state.reconnect_jitter;
+        self.reconnect_reset_on_success = serializable_state.reconnect_reset_on_success;
+        self.width = serializable_state.width;
+        self.height = serializable_state.height;
+        self.show_overlays = serializable_state.show_overlays;
+        self.hud_corner = serializable_state.hud_corner;
+        self.theme = serializable_state.theme;
+        self.adaptive_catch_up = serializable_state.adaptive_catch_up;
+        self.catch_up_backlog = serializable_state.catch_up_backlog;
+        self.max_display_fps = serializable_state.max_display_fps;
+        self.drop_alert_percent = serializable_state.drop_alert_percent;
+        self.export_dir = serializable_state.export_dir;
+        self.dump_dir = serializable_state.dump_dir;
+        self.report_on_exit = serializable_state.report_on_exit;
+        self.set_window_level(WindowLevel::new(serializable_state.window_center, serializable_state.window_width));
+        self.gamma_by_profile = serializable_state.gamma_by_profile;
+        self.colormap = serializable_state.colormap;
+        self.clahe_enabled = serializable_state.clahe_enabled;
+        self.clahe_clip_limit = serializable_state.clahe_clip_limit;
+        self.speckle_strength = serializable_state.speckle_strength;
+        self.edge_enhancement = serializable_state.edge_enhancement;
+        self.persistence_frames = serializable_state.persistence_frames;
+        self.orientation = serializable_state.orientation;
+        self.zoom_quality = serializable_state.zoom_quality;
+        self.length_unit = serializable_state.length_unit;
+        self.show_rulers = serializable_state.show_rulers;
+        self.show_grid = serializable_state.show_grid;
         
         Ok(())
     }
@@ -266,6 +782,57 @@ impl Default for UiState {
     }
 }
 
+/// Corner of the image view holding the HUD overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HudCorner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl HudCorner {
+    /// All corners in UI order
+    pub const ALL: [HudCorner; 4] = [HudCorner::TopLeft, HudCorner::TopRight, HudCorner::BottomLeft, HudCorner::BottomRight];
+
+    /// Get corner from its UI index
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Get the UI index of this corner
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|&c| c == self).unwrap_or(0)
+    }
+}
+
+/// Color theme of the Slint frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    /// Dim red-shifted palette for darkened reading rooms
+    Night,
+}
+
+impl Theme {
+    /// All themes in UI order
+    pub const ALL: [Theme; 4] = [Theme::Dark, Theme::Light, Theme::HighContrast, Theme::Night];
+
+    /// Get theme from its UI index
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Get the UI index of this theme
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|&t| t == self).unwrap_or(0)
+    }
+}
+
 /// Device information for medical context
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceInfo {
@@ -387,4 +954,159 @@ struct SerializableUiState {
     pub show_debug_info: bool,
     pub auto_reconnect: bool,
     pub notification_enabled: bool,
+    #[serde(default = "default_max_reconnect_attempts")]
+    pub max_reconnect_attempts: u32,
+    #[serde(default = "default_reconnect_multiplier")]
+    pub reconnect_multiplier: f64,
+    #[serde(default = "default_reconnect_max_delay_ms")]
+    pub reconnect_max_delay_ms: u64,
+    #[serde(default = "default_reconnect_jitter")]
+    pub reconnect_jitter: f64,
+    #[serde(default = "default_true")]
+    pub reconnect_reset_on_success: bool,
+    #[serde(default = "default_width")]
+    pub width: usize,
+    #[serde(default = "default_height")]
+    pub height: usize,
+    #[serde(default = "default_true")]
+    pub show_overlays: bool,
+    #[serde(default)]
+    pub hud_corner: HudCorner,
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default)]
+    pub adaptive_catch_up: bool,
+    #[serde(default = "default_catch_up_backlog")]
+    pub catch_up_backlog: u64,
+    #[serde(default)]
+    pub max_display_fps: u32,
+    #[serde(default = "default_drop_alert_percent")]
+    pub drop_alert_percent: u32,
+    #[serde(default = "default_export_dir")]
+    pub export_dir: String,
+    #[serde(default = "default_dump_dir")]
+    pub dump_dir: String,
+    #[serde(default)]
+    pub report_on_exit: bool,
+    #[serde(default = "default_window_center")]
+    pub window_center: f32,
+    #[serde(default = "default_window_width")]
+    pub window_width: f32,
+    #[serde(default)]
+    pub gamma_by_profile: HashMap<String, f32>,
+    #[serde(default)]
+    pub colormap: Colormap,
+    #[serde(default)]
+    pub clahe_enabled: bool,
+    #[serde(default = "default_clahe_clip_limit")]
+    pub clahe_clip_limit: f32,
+    #[serde(default)]
+    pub speckle_strength: f32,
+    #[serde(default)]
+    pub edge_enhancement: f32,
+    #[serde(default = "default_persistence_frames")]
+    pub persistence_frames: u32,
+    #[serde(default)]
+    pub orientation: Orientation,
+    #[serde(default)]
+    pub zoom_quality: ResampleQuality,
+    #[serde(default)]
+    pub length_unit: LengthUnit,
+    #[serde(default)]
+    pub show_rulers: bool,
+    #[serde(default)]
+    pub show_grid: bool,
+}
+
+/// User-editable settings exchanged with the settings dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiSettings {
+    pub shm_name: String,
+    pub format: String,
+    pub width: usize,
+    pub height: usize,
+    pub catch_up_mode: bool,
+    pub adaptive_catch_up: bool,
+    pub catch_up_backlog: u64,
+    pub max_display_fps: u32,
+    pub auto_reconnect: bool,
+    pub reconnect_delay_ms: u64,
+    pub max_reconnect_attempts: u32,
+    pub show_overlays: bool,
+    pub hud_corner: HudCorner,
+    pub theme: Theme,
+    pub show_debug_info: bool,
+    pub drop_alert_percent: u32,
+    pub export_dir: String,
+    pub dump_dir: String,
+    pub report_on_exit: bool,
+    pub length_unit: LengthUnit,
+}
+
+fn default_max_reconnect_attempts() -> u32 {
+    10
+}
+
+fn default_reconnect_multiplier() -> f64 {
+    ReconnectPolicy::default().multiplier
+}
+
+fn default_reconnect_max_delay_ms() -> u64 {
+    ReconnectPolicy::default().max_delay.as_millis() as u64
+}
+
+fn default_reconnect_jitter() -> f64 {
+    ReconnectPolicy::default().jitter
+}
+
+fn default_width() -> usize {
+    1024
+}
+
+fn default_height() -> usize {
+    768
+}
+
+fn default_window_center() -> f32 {
+    WindowLevel::default().center
+}
+
+fn default_window_width() -> f32 {
+    WindowLevel::default().width
+}
+
+fn default_persistence_frames() -> u32 {
+    1
+}
+
+fn default_clahe_clip_limit() -> f32 {
+    ClaheParams::default().clip_limit
+}
+
+fn default_catch_up_backlog() -> u64 {
+    AdaptiveCatchUp::default().skip_above
+}
+
+fn default_drop_alert_percent() -> u32 {
+    5
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_export_dir() -> String {
+    dirs::picture_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("mivi")
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn default_dump_dir() -> String {
+    std::env::current_dir()
+        .unwrap_or_else(|_| std::path::PathBuf::from("."))
+        .to_string_lossy()
+        .into_owned()
 }
@@ -22,12 +22,18 @@
 //! - **Frontend**: Manages the Slint UI, user interactions, and visual presentation
 //! - **Zero-Copy Bridge**: Efficient data transfer between backend and frontend
 //!
+//! The Slint viewer needs the default `ui` feature and the lightweight egui
+//! viewer the `egui` feature; the binary picks one with `--ui slint|egui|none`.
+//! Services that only consume frames can depend on the crate with
+//! `default-features = false` and use the backend (or the C API in [`ffi`])
+//! without pulling in Slint or a windowing stack.
+//!
 //! ## Usage
 //!
 //! ```rust
 //! use mivi_frame_viewer::{
 //!     backend::BackendConfig,
-//!     frontend::MedicalFrameApp,
+//!     frontend::{MedicalFrameApp, WindowOptions},
 //! };
 //!
 //! #[tokio::main]
@@ -39,10 +45,10 @@
 //!         height: 768,
 //!         catch_up: false,
 //!         verbose: false,
-//!         reconnect_delay: std::time::Duration::from_secs(1),
+//!         ..BackendConfig::default()
 //!     };
 //!     
-//!     let mut app = MedicalFrameApp::new(config).await?;
+//!     let mut app = MedicalFrameApp::new(config, WindowOptions::default()).await?;
 //!     app.run().await?;
 //!     
 //!     Ok(())
@@ -57,17 +63,24 @@
 // Public modules
 pub mod backend;
 pub mod cli;
+pub mod encryption;
 pub mod error;
+pub mod ffi;
 pub mod frontend;
+pub mod profiler;
+pub mod redaction;
+pub mod telemetry;
 
 // Re-exports for convenience
 pub use backend::{
     types::{FrameStatistics, ProcessedFrame, RawFrame},
-    BackendCommand, BackendConfig, BackendEvent, BackendState, ConnectionStatus,
+    BackendCommand, BackendHandle, BackendConfig, BackendEvent, BackendState, ConnectionStatus,
     MedicalFrameBackend,
 };
 
-pub use frontend::{FrontendError, ImageConverter, MedicalFrameApp, SlintBridge, UiState};
+pub use frontend::{FrontendError, UiState};
+#[cfg(feature = "ui")]
+pub use frontend::{ImageConverter, MedicalFrameApp, SlintBridge};
 
 pub use cli::Args;
 pub use error::MiViError;
@@ -133,6 +146,16 @@ impl BuildInfo {
     }
 }
 
+/// License information shown in the about dialog
+pub const LICENSE_INFO: &str = match option_env!("CARGO_PKG_LICENSE") {
+    Some(license) if !license.is_empty() => license,
+    _ => "See the LICENSE file distributed with this software",
+};
+
+/// Third-party notice shown in the about dialog
+pub const THIRD_PARTY_NOTICE: &str =
+    "Built with Rust, Tokio and the Slint UI toolkit (used under the Slint license terms)";
+
 /// Initialize the MiVi library with default settings
 ///
 /// This function sets up logging and other global configurations.
@@ -166,6 +189,7 @@ pub fn init_logging(level: LogLevel) -> Result<(), MiViError> {
         .with_target(false)
         .with_level(true)
         .with_ansi(true)
+        .with_writer(redaction::RedactingMakeWriter::new(std::io::stdout))
         .try_init()
         .map_err(|e| MiViError::Configuration(format!("Failed to initialize logging: {}", e)))?;
 
@@ -201,7 +225,10 @@ pub mod formats {
             FrameFormat::RGBA,
             FrameFormat::YUV10,
             FrameFormat::RGB10,
+            FrameFormat::RGB565,
+            FrameFormat::Indexed,
             FrameFormat::Grayscale,
+            FrameFormat::Gray12Packed,
         ]
     }
 
@@ -220,7 +247,10 @@ pub mod formats {
             "rgba" => Some(FrameFormat::RGBA),
             "yuv10" => Some(FrameFormat::YUV10),
             "rgb10" => Some(FrameFormat::RGB10),
+            "rgb565" => Some(FrameFormat::RGB565),
+            "indexed" | "palette" => Some(FrameFormat::Indexed),
             "grayscale" | "gray" => Some(FrameFormat::Grayscale),
+            "gray12packed" | "gray12p" | "gray12" => Some(FrameFormat::Gray12Packed),
             _ => None,
         }
     }
@@ -235,7 +265,10 @@ pub mod formats {
             FrameFormat::RGBA => "RGBA",
             FrameFormat::YUV10 => "YUV10",
             FrameFormat::RGB10 => "RGB10",
+            FrameFormat::RGB565 => "RGB565",
+            FrameFormat::Indexed => "Indexed",
             FrameFormat::Grayscale => "Grayscale",
+            FrameFormat::Gray12Packed => "Gray12Packed",
             FrameFormat::Unknown => "Unknown",
         }
     }
@@ -328,6 +361,136 @@ pub mod utils {
     }
 }
 
+/// Runtime diagnostics for support requests
+pub mod diagnostics {
+    use crate::BUILD_INFO;
+
+    /// Snapshot of the runtime environment
+    #[derive(Debug, Clone)]
+    pub struct DiagnosticsReport {
+        pub build: String,
+        pub os: String,
+        pub arch: &'static str,
+        pub cpu_count: usize,
+        pub simd_features: Vec<&'static str>,
+        pub gpu: String,
+        pub connection: String,
+    }
+
+    impl DiagnosticsReport {
+        /// Collect diagnostics together with a summary of the current connection
+        pub fn collect(connection: String) -> Self {
+            Self {
+                build: BUILD_INFO.formatted(),
+                os: os_description(),
+                arch: std::env::consts::ARCH,
+                cpu_count: num_cpus::get(),
+                simd_features: simd_features(),
+                gpu: gpu_description(),
+                connection,
+            }
+        }
+
+        /// Get the report as plain text suitable for the clipboard
+        pub fn to_text(&self) -> String {
+            let simd = if self.simd_features.is_empty() {
+                "none".to_string()
+            } else {
+                self.simd_features.join(", ")
+            };
+
+            format!(
+                "{}\nOS: {}\nArchitecture: {} ({} logical CPUs)\nSIMD: {}\nGPU: {}\nConnection: {}",
+                self.build, self.os, self.arch, self.cpu_count, simd, self.gpu, self.connection
+            )
+        }
+    }
+
+    /// Get a human readable operating system description
+    pub fn os_description() -> String {
+        let pretty_name = std::fs::read_to_string("/etc/os-release").ok().and_then(|content| {
+            content
+                .lines()
+                .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+                .map(|name| name.trim_matches('"').to_string())
+        });
+
+        let kernel = std::fs::read_to_string("/proc/sys/kernel/osrelease")
+            .ok()
+            .map(|release| release.trim().to_string());
+
+        match (pretty_name, kernel) {
+            (Some(name), Some(kernel)) => format!("{} (kernel {})", name, kernel),
+            (Some(name), None) => name,
+            (None, Some(kernel)) => format!("{} (kernel {})", std::env::consts::OS, kernel),
+            (None, None) => std::env::consts::OS.to_string(),
+        }
+    }
+
+    /// Get the SIMD instruction sets available on this CPU
+    pub fn simd_features() -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut features = Vec::new();
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("sse2") {
+                features.push("SSE2");
+            }
+            if is_x86_feature_detected!("sse4.1") {
+                features.push("SSE4.1");
+            }
+            if is_x86_feature_detected!("avx2") {
+                features.push("AVX2");
+            }
+            if is_x86_feature_detected!("avx512f") {
+                features.push("AVX-512F");
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            features.push("NEON");
+        }
+
+        features
+    }
+
+    /// Get a description of the display adapters known to the kernel
+    pub fn gpu_description() -> String {
+        let mut drivers: Vec<String> = std::fs::read_dir("/sys/class/drm")
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| {
+                        let name = entry.file_name();
+                        let name = name.to_string_lossy();
+                        name.starts_with("card") && !name.contains('-')
+                    })
+                    .filter_map(|entry| std::fs::read_to_string(entry.path().join("device/uevent")).ok())
+                    .filter_map(|uevent| {
+                        uevent
+                            .lines()
+                            .find_map(|line| line.strip_prefix("DRIVER="))
+                            .map(str::to_string)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        drivers.sort();
+        drivers.dedup();
+
+        let backend = std::env::var("SLINT_BACKEND").unwrap_or_else(|_| "default".to_string());
+
+        if drivers.is_empty() {
+            format!("unknown (Slint backend: {})", backend)
+        } else {
+            format!("{} (Slint backend: {})", drivers.join(", "), backend)
+        }
+    }
+}
+
 /// Performance monitoring utilities
 pub mod perf {
     use std::collections::VecDeque;
@@ -338,16 +501,30 @@ pub mod perf {
     pub struct PerformanceMonitor {
         frame_times: VecDeque<Instant>,
         processing_times: VecDeque<Duration>,
+        render_times: VecDeque<Duration>,
         max_samples: usize,
         start_time: Instant,
     }
 
+    /// Point-in-time view of the monitor for the performance panel
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct PerformanceSnapshot {
+        pub fps: f64,
+        pub average_processing_time: Duration,
+        pub max_processing_time: Duration,
+        pub average_render_time: Duration,
+        /// Resident memory of the process, where the platform reports it
+        pub resident_memory_bytes: Option<u64>,
+        pub uptime: Duration,
+    }
+
     impl PerformanceMonitor {
         /// Create a new performance monitor
         pub fn new(max_samples: usize) -> Self {
             Self {
                 frame_times: VecDeque::with_capacity(max_samples),
                 processing_times: VecDeque::with_capacity(max_samples),
+                render_times: VecDeque::with_capacity(max_samples),
                 max_samples,
                 start_time: Instant::now(),
             }
@@ -368,6 +545,14 @@ pub mod perf {
             }
         }
 
+        /// Record how long the UI took to display a frame
+        pub fn record_render(&mut self, render_time: Duration) {
+            self.render_times.push_back(render_time);
+            if self.render_times.len() > self.max_samples {
+                self.render_times.pop_front();
+            }
+        }
+
         /// Calculate current FPS
         pub fn fps(&self) -> f64 {
             if self.frame_times.len() < 2 {
@@ -397,18 +582,55 @@ pub mod perf {
             total / self.processing_times.len() as u32
         }
 
+        /// Get the slowest processing time among recent frames
+        pub fn max_processing_time(&self) -> Duration {
+            self.processing_times.iter().max().copied().unwrap_or(Duration::ZERO)
+        }
+
+        /// Calculate average time the UI took to display a frame
+        pub fn average_render_time(&self) -> Duration {
+            if self.render_times.is_empty() {
+                return Duration::ZERO;
+            }
+
+            let total: Duration = self.render_times.iter().sum();
+            total / self.render_times.len() as u32
+        }
+
         /// Get uptime since monitor creation
         pub fn uptime(&self) -> Duration {
             self.start_time.elapsed()
         }
 
+        /// Take a snapshot of all metrics
+        pub fn snapshot(&self) -> PerformanceSnapshot {
+            PerformanceSnapshot {
+                fps: self.fps(),
+                average_processing_time: self.average_processing_time(),
+                max_processing_time: self.max_processing_time(),
+                average_render_time: self.average_render_time(),
+                resident_memory_bytes: resident_memory_bytes(),
+                uptime: self.uptime(),
+            }
+        }
+
         /// Reset all statistics
         pub fn reset(&mut self) {
             self.frame_times.clear();
             self.processing_times.clear();
+            self.render_times.clear();
             self.start_time = Instant::now();
         }
     }
+
+    /// Resident memory of this process (Linux only)
+    pub fn resident_memory_bytes() -> Option<u64> {
+        let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+        let resident_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+        // SAFETY: sysconf has no preconditions
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        (page_size > 0).then(|| resident_pages * page_size as u64)
+    }
 }
 
 // Tests
@@ -429,6 +651,17 @@ mod tests {
         assert!(formatted.contains(info.version));
     }
 
+    #[test]
+    fn test_diagnostics_report() {
+        let report = diagnostics::DiagnosticsReport::collect("Disconnected".to_string());
+        let text = report.to_text();
+
+        assert!(text.contains(&BUILD_INFO.formatted()));
+        assert!(text.contains("Connection: Disconnected"));
+        assert!(report.cpu_count > 0);
+        assert!(!LICENSE_INFO.is_empty());
+    }
+
     #[test]
     fn test_formats() {
         use formats::*;
@@ -480,5 +713,11 @@ mod tests {
 
         assert!(monitor.average_processing_time() > Duration::ZERO);
         assert!(monitor.uptime() > Duration::ZERO);
+
+        monitor.record_render(Duration::from_millis(2));
+        monitor.record_render(Duration::from_millis(4));
+        let snapshot = monitor.snapshot();
+        assert_eq!(snapshot.max_processing_time, Duration::from_millis(17));
+        assert_eq!(snapshot.average_render_time, Duration::from_millis(3));
     }
 }
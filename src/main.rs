@@ -3,14 +3,18 @@
 use std::process;
 use clap::Parser;
 use tracing::{info, error, warn};
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing_subscriber::{fmt, fmt::format::FmtSpan, prelude::*, EnvFilter};
 
 use mivi_frame_viewer::{
-    backend::BackendConfig,
-    frontend::MedicalFrameApp,
-    cli::Args,
-    error::MiViError,
+    backend::{health, AdaptiveCatchUp, BackendConfig, DumpConfig, FramePolling, MemoryConfig, NumaTopology, ThreadScheduling},
+    frontend::{self, access::{Role, UserAccount, UserDirectory}, audit_log::AuditLog},
+    cli::{self, Args, Command, UiArg},
+    encryption::{self, KeySource, RecordingCipher},
+    error::{ErrorReporter, MiViError},
+    redaction::RedactingMakeWriter,
 };
+#[cfg(feature = "ui")]
+use mivi_frame_viewer::frontend::{MedicalFrameApp, WindowOptions};
 
 /// Main entry point for MiVi Medical Frame Viewer
 #[tokio::main]
@@ -18,6 +22,26 @@ async fn main() {
     // Parse command line arguments
     let args = Args::parse();
 
+    // Utility commands print their result and exit without starting the viewer
+    match &args.command {
+        Some(Command::Healthcheck { address, live, timeout }) => {
+            process::exit(run_healthcheck(address, *live, std::time::Duration::from_millis(*timeout)).await);
+        }
+        Some(Command::VerifyAuditLog { path }) => process::exit(verify_audit_log(path)),
+        Some(Command::GenerateRecordingKey { key_file, keyring }) => {
+            process::exit(generate_recording_key(key_file.as_ref(), keyring.as_ref()));
+        }
+        Some(Command::Decrypt { files, key_file, keyring }) => {
+            process::exit(decrypt_recordings(files, key_file.as_ref(), keyring.as_ref()));
+        }
+        Some(Command::AddUser { users_file, name, role }) => process::exit(add_user(users_file, name, role.to_role())),
+        None => {}
+    }
+
+    // Identifiers are registered before anything is logged so they never reach the output
+    args.register_identifiers();
+    mivi_frame_viewer::redaction::install_panic_hook();
+
     // Initialize logging
     if let Err(e) = setup_logging(&args) {
         eprintln!("❌ Failed to setup logging: {}", e);
@@ -33,21 +57,43 @@ async fn main() {
         process::exit(1);
     }
 
+    // Load the recording key before anything is recorded
+    let recording_cipher = match args.recording_key_source().map(|source| source.load()).transpose() {
+        Ok(cipher) => cipher,
+        Err(e) => {
+            error!("❌ Failed to load recording key: {}", e);
+            process::exit(1);
+        }
+    };
+
     // Create backend configuration
-    let backend_config = create_backend_config(&args);
+    let backend_config = create_backend_config(&args, recording_cipher);
 
-    // Initialize and run the application
-    match run_application(backend_config).await {
+    // Initialize and run the selected frontend
+    match run_frontend(&args, backend_config).await {
         Ok(()) => {
             info!("✅ MiVi Medical Frame Viewer exited normally");
         }
         Err(e) => {
             error!("❌ Application error: {}", e);
+            let reporter = error_reporter(&args);
+            reporter.report(&e);
+            reporter.flush(std::time::Duration::from_secs(5));
             process::exit(1);
         }
     }
 }
 
+/// Build the error reporter; telemetry stays off unless a sink was configured
+fn error_reporter(args: &Args) -> ErrorReporter {
+    match args.telemetry_sink() {
+        Some(sink) => ErrorReporter::new(false, true)
+            .with_sink(sink)
+            .with_details(args.telemetry_details),
+        None => ErrorReporter::new(false, false),
+    }
+}
+
 /// Setup logging configuration
 fn setup_logging(args: &Args) -> Result<(), MiViError> {
     let log_level = if args.verbose {
@@ -60,13 +106,21 @@ fn setup_logging(args: &Args) -> Result<(), MiViError> {
         .or_else(|_| EnvFilter::try_new(format!("mivi_frame_viewer={}", log_level)))
         .map_err(|e| MiViError::Configuration(format!("Invalid log filter: {}", e)))?;
 
-    // Use try_init to avoid panicking if logging is already initialized
-    let _result = tracing_subscriber::fmt()
-        .with_env_filter(env_filter)
+    // Frame stage spans are always recorded; their timings are only logged on request
+    let span_events = if args.trace_spans { FmtSpan::CLOSE } else { FmtSpan::NONE };
+    let fmt_layer = fmt::layer()
         .with_target(false)
         .with_level(true)
         .with_ansi(true)
-        .try_init();
+        .with_span_events(span_events)
+        .with_writer(RedactingMakeWriter::new(std::io::stdout))
+        .with_filter(env_filter);
+
+    // Use try_init to avoid panicking if logging is already initialized
+    let registry = tracing_subscriber::registry().with(fmt_layer);
+    #[cfg(feature = "tokio-console")]
+    let registry = registry.with(console_subscriber::spawn());
+    let _result = registry.try_init();
 
     Ok(())
 }
@@ -100,16 +154,6 @@ fn validate_args(args: &Args) -> Result<(), MiViError> {
         return Err(MiViError::Configuration("Shared memory name too long (max 255 characters)".to_string()));
     }
 
-    // Validate format
-    let valid_formats = ["yuv", "bgr", "rgb", "rgba", "grayscale"];
-    if !valid_formats.contains(&args.format.to_string().to_lowercase().as_str()) {
-        return Err(MiViError::Configuration(format!(
-            "Invalid format '{}'. Valid formats: {}",
-            args.format,
-            valid_formats.join(", ")
-        )));
-    }
-
     // Validate dimensions
     if args.width == 0 || args.height == 0 {
         return Err(MiViError::Configuration("Width and height must be greater than 0".to_string()));
@@ -128,37 +172,296 @@ fn validate_args(args: &Args) -> Result<(), MiViError> {
         warn!("⚠️ Very long reconnect delay: {}ms", args.reconnect_delay);
     }
 
+    if !args.ui.is_available() {
+        return Err(MiViError::Configuration(format!(
+            "The {} frontend is not available in this build (enable the \"{}\" feature)",
+            args.ui.name(),
+            args.ui.feature().unwrap_or_default()
+        )));
+    }
+
     info!("✅ Command line arguments validated");
     Ok(())
 }
 
 /// Create backend configuration from command line arguments
-fn create_backend_config(args: &Args) -> BackendConfig {
+fn create_backend_config(args: &Args, recording_cipher: Option<RecordingCipher>) -> BackendConfig {
     info!("⚙️ Creating backend configuration");
     info!("   📂 Shared memory: {}", args.shm_name);
-    info!("   🎨 Format: {}", args.format);
+    info!("   🎨 Format: {}", args.effective_format());
     info!("   📐 Dimensions: {}x{}", args.width, args.height);
-    info!("   ⚡ Catch-up mode: {}", args.catch_up);
-    info!("   🔄 Reconnect delay: {}ms", args.reconnect_delay);
+    info!("   ⚡ Catch-up mode: {}", args.effective_catch_up());
+    info!("   🔄 Reconnect: {}", args.reconnect_policy().describe());
     info!("   📝 Verbose logging: {}", args.verbose);
 
-    BackendConfig {
+    let mut config = BackendConfig {
         shm_name: args.shm_name.clone(),
-        format: args.format.to_string(),
+        format: args.effective_format().to_string(),
         width: args.width,
         height: args.height,
-        catch_up: args.catch_up,
+        catch_up: args.effective_catch_up(),
+        adaptive_catch_up: args.adaptive_catch_up
+            .then(|| AdaptiveCatchUp::new(args.catch_up_backlog, AdaptiveCatchUp::default().resume_at)),
+        max_display_fps: args.max_fps,
         verbose: args.verbose,
-        reconnect_delay: std::time::Duration::from_millis(args.reconnect_delay),
+        reconnect: args.reconnect_policy(),
+        frame_polling: FramePolling::from_millis(args.poll_interval),
+        stats_interval: std::time::Duration::from_millis(args.stats_interval),
+        scheduling: ThreadScheduling {
+            cpu_affinity: numa_node_cpus(args).unwrap_or_else(|| args.cpu_affinity.clone()),
+            rt_priority: args.rt_priority,
+        },
+        memory: MemoryConfig {
+            lock: args.mlock.to_backend_lock(),
+            huge_pages: args.huge_pages,
+        },
+        gpu_upload: args.gpu_upload,
+        deep_color: args.deep_color,
+        dithering: args.dither.to_backend_dithering(),
+        deinterlace: args.deinterlace.to_backend_deinterlace(),
+        display_calibration: args.display_calibration(),
+        igtl_broadcast: args.igtl_broadcast.clone(),
+        health_listen: args.health_listen.clone(),
+        detections_listen: args.detections_listen.clone(),
+        record_to: args.record_to.clone(),
+        forward_listen: args.forward_listen.clone(),
+        snapshot_dir: args.snapshot_dir.clone(),
+        snapshot_interval: std::time::Duration::from_secs(args.snapshot_interval),
+        auto_record_dir: args.auto_record.clone(),
+        auto_record_idle: std::time::Duration::from_secs(args.auto_record_idle),
+        burst_capture: args.burst_config(),
+        audit_log: args.audit_log.clone(),
+        export_anonymization: args.export_anonymization(),
+        recording_encryption: recording_cipher.clone(),
+        users_file: args.users_file.clone(),
+        privacy_pin: args.privacy_pin.clone(),
+        inference: args.inference_config(),
+        plugin_dir: args.plugin_dir.clone(),
+        script: args.script.clone(),
+        watchdog_timeout: (args.watchdog_timeout > 0).then(|| std::time::Duration::from_millis(args.watchdog_timeout)),
+        event_capacity: args.event_capacity,
+        frame_drop_policy: args.frame_drop_policy(),
+        conversion_threads: Some(args.effective_thread_count()),
+        perf_monitor: args.perf_monitor,
+        profile_duration: std::time::Duration::from_secs(args.profile_duration),
+        processing_deadline: (args.processing_deadline > 0.0).then(|| std::time::Duration::from_secs_f64(args.processing_deadline / 1000.0)),
+        ..BackendConfig::default()
+    };
+
+    // Apply the device profile on top of the defaults
+    if let (Some(device_type), Some(settings)) = (args.device_type, args.device_settings()) {
+        info!("   {} Device profile: {} ({})", device_type.icon(), device_type.label(), settings.description);
+        info!("   🎯 Expected FPS: {:.1}, latency target: {:.1}ms, frame timeout: {}ms",
+              settings.expected_fps, settings.latency_target_ms, settings.frame_timeout_ms);
+
+        config.frame_timeout = std::time::Duration::from_millis(settings.frame_timeout_ms);
+        config.expected_fps = settings.expected_fps;
+        config.latency_target_ms = settings.latency_target_ms;
+        config.device_profile = Some(device_type.label().to_string());
+    }
+
+    if args.dump_frames {
+        config.dump = Some(DumpConfig {
+            dir: args.effective_dump_dir(),
+            max_frames: args.max_dump_frames,
+            png: args.dump_png,
+            anonymization: args.export_anonymization(),
+            encryption: recording_cipher,
+        });
+    }
+
+    config
+}
+
+/// CPUs of the requested NUMA node, for pinning the frame thread there
+fn numa_node_cpus(args: &Args) -> Option<Vec<usize>> {
+    let node = args.numa_node?;
+    let topology = NumaTopology::detect();
+    match topology.node_cpus(node) {
+        Some(cpus) if !cpus.is_empty() => {
+            info!("   🧭 NUMA node {}: frame thread on CPUs {:?} ({} nodes)", node, cpus, topology.nodes.len());
+            Some(cpus)
+        }
+        _ => {
+            warn!("⚠️ NUMA node {} not found (online: {:?}), frame thread not pinned", node, topology.nodes);
+            None
+        }
+    }
+}
+
+/// Ask a running viewer's health endpoint and return the exit code for supervisors
+///
+/// 0 when the probe passes, 1 when it fails, 2 when the viewer cannot be reached.
+async fn run_healthcheck(address: &str, live: bool, timeout: std::time::Duration) -> i32 {
+    let probe = if live { health::HealthProbe::Liveness } else { health::HealthProbe::Readiness };
+    match health::query(address, probe, timeout).await {
+        Ok((healthy, report)) => {
+            println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+            if healthy { 0 } else { 1 }
+        }
+        Err(e) => {
+            eprintln!("❌ Health check failed: {}", e);
+            2
+        }
+    }
+}
+
+/// Check an audit log's hash chain and return the exit code
+fn verify_audit_log(path: &std::path::Path) -> i32 {
+    match AuditLog::verify(path) {
+        Ok(Some(last)) => {
+            println!("✅ Audit log intact: {} entries, last at {}", last.seq + 1, last.time);
+            0
+        }
+        Ok(None) => {
+            println!("✅ Audit log is empty");
+            0
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            1
+        }
+    }
+}
+
+/// Create a recording key at the given destination and return the exit code
+fn generate_recording_key(key_file: Option<&std::path::PathBuf>, keyring: Option<&String>) -> i32 {
+    let source = match cli::key_source(key_file, keyring) {
+        Ok(Some(source)) => source,
+        Ok(None) => {
+            eprintln!("❌ Give --key-file or --keyring to store the key");
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return 1;
+        }
+    };
+
+    let key = encryption::generate_key();
+    match source.store(&key) {
+        Ok(()) => {
+            let cipher = RecordingCipher::new(&key);
+            match source {
+                KeySource::File(path) => println!("🔐 Recording key {} written to {}", cipher.key_id(), path.display()),
+                KeySource::Keyring(account) => println!("🔐 Recording key {} stored in the keyring for {}", cipher.key_id(), account),
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to store recording key: {}", e);
+            1
+        }
+    }
+}
+
+/// Decrypt recordings next to themselves and return the exit code (1 if any failed)
+fn decrypt_recordings(files: &[std::path::PathBuf], key_file: Option<&std::path::PathBuf>, keyring: Option<&String>) -> i32 {
+    let cipher = match cli::key_source(key_file, keyring) {
+        Ok(Some(source)) => source.load(),
+        Ok(None) => {
+            eprintln!("❌ Give --key-file or --keyring with the recording key");
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return 1;
+        }
+    };
+    let cipher = match cipher {
+        Ok(cipher) => cipher,
+        Err(e) => {
+            eprintln!("❌ Failed to load recording key: {}", e);
+            return 1;
+        }
+    };
+
+    let mut failed = false;
+    for file in files {
+        match cipher.decrypt_file(file) {
+            Ok(output) => println!("🔓 {} -> {}", file.display(), output.display()),
+            Err(e) => {
+                eprintln!("❌ {}: {}", file.display(), e);
+                failed = true;
+            }
+        }
+    }
+    if failed { 1 } else { 0 }
+}
+
+/// Add or replace an account, reading its password from standard input, and return the exit code
+fn add_user(users_file: &std::path::Path, name: &str, role: Role) -> i32 {
+    let mut directory = if users_file.exists() {
+        match UserDirectory::load(users_file) {
+            Ok(directory) => directory,
+            Err(e) => {
+                eprintln!("❌ Failed to read users file: {}", e);
+                return 1;
+            }
+        }
+    } else {
+        UserDirectory::default()
+    };
+
+    eprintln!("🔑 Password for {}:", name);
+    let mut password = String::new();
+    if let Err(e) = std::io::stdin().read_line(&mut password) {
+        eprintln!("❌ Failed to read password: {}", e);
+        return 1;
+    }
+    let password = password.trim_end_matches(['\r', '\n']);
+    if password.is_empty() {
+        eprintln!("❌ Password must not be empty");
+        return 1;
+    }
+
+    directory.upsert(UserAccount::new(name, role, password));
+    match directory.save(users_file) {
+        Ok(()) => {
+            println!("👥 {} ({}) saved to {}", name, role.name(), users_file.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to write users file: {}", e);
+            1
+        }
+    }
+}
+
+/// Run the frontend chosen with --ui
+async fn run_frontend(args: &Args, backend_config: BackendConfig) -> Result<(), MiViError> {
+    info!("🖥️ Frontend: {}", args.ui.name());
+    match args.ui {
+        #[cfg(feature = "ui")]
+        UiArg::Slint => {
+            let window_options = WindowOptions {
+                width: args.window_width,
+                height: args.window_height,
+                fullscreen: args.fullscreen,
+                kiosk: args.kiosk,
+            };
+            run_application(backend_config, window_options).await
+        }
+        // eframe owns the calling thread until its window closes
+        #[cfg(feature = "egui")]
+        UiArg::Egui => tokio::task::block_in_place(|| {
+            frontend::egui_viewer::run(backend_config, args.window_width, args.window_height)
+        })
+        .map_err(MiViError::from),
+        UiArg::None => frontend::headless::run(backend_config).await.map_err(MiViError::from),
+        // validate_args rejects frontends missing from this build
+        #[allow(unreachable_patterns)]
+        ui => Err(MiViError::Configuration(format!("The {} frontend is not available in this build", ui.name()))),
     }
 }
 
 /// Run the main application
-async fn run_application(backend_config: BackendConfig) -> Result<(), MiViError> {
+#[cfg(feature = "ui")]
+async fn run_application(backend_config: BackendConfig, window_options: WindowOptions) -> Result<(), MiViError> {
     info!("🎬 Initializing MiVi Medical Frame Application");
 
     // Create the application
-    let mut app = MedicalFrameApp::new(backend_config).await
+    let mut app = MedicalFrameApp::new(backend_config, window_options).await
         .map_err(|e| MiViError::Application(format!("Failed to create application: {}", e)))?;
 
     // Setup signal handlers for graceful shutdown
@@ -174,6 +477,7 @@ async fn run_application(backend_config: BackendConfig) -> Result<(), MiViError>
 }
 
 /// Setup signal handlers for graceful shutdown
+#[cfg(feature = "ui")]
 async fn setup_signal_handlers() -> Result<(), MiViError> {
     #[cfg(unix)]
     {
@@ -0,0 +1,108 @@
+// src/profiler.rs - Built-in CPU Profiler
+
+//! Sampling CPU profiler that writes a flamegraph SVG.
+//!
+//! Intended for clinical machines where external profilers cannot be installed.
+//! Sampling needs the `profiler` feature; without it every capture reports
+//! [`ProfilerError::Unavailable`].
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use thiserror::Error;
+
+/// Samples per second; prime so sampling does not lock step with periodic work
+#[cfg(feature = "profiler")]
+const SAMPLE_FREQUENCY: i32 = 997;
+
+/// Only one capture may sample the process at a time
+static CAPTURE_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Errors that can occur while capturing a profile
+#[derive(Debug, Error)]
+pub enum ProfilerError {
+    #[error("CPU profiling is not available in this build (enable the \"profiler\" feature)")]
+    Unavailable,
+
+    #[error("A CPU profile is already being captured")]
+    Busy,
+
+    #[error("Profiler error: {0}")]
+    Sampling(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Releases the capture slot when a capture ends, even on error
+struct CaptureSlot;
+
+impl CaptureSlot {
+    fn acquire() -> Result<Self, ProfilerError> {
+        CAPTURE_RUNNING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .map(|_| CaptureSlot)
+            .map_err(|_| ProfilerError::Busy)
+    }
+}
+
+impl Drop for CaptureSlot {
+    fn drop(&mut self) {
+        CAPTURE_RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// Check if this build can capture profiles
+pub fn is_available() -> bool {
+    cfg!(feature = "profiler")
+}
+
+/// File name of a flamegraph captured now
+pub fn flamegraph_file_name() -> String {
+    format!("flamegraph_{}.svg", chrono::Local::now().format("%Y%m%d_%H%M%S"))
+}
+
+/// Sample the whole process for `duration` and write a flamegraph SVG into `dir`
+///
+/// Runs on a blocking thread; the application keeps running while it samples.
+pub async fn capture_flamegraph(duration: Duration, dir: &Path) -> Result<PathBuf, ProfilerError> {
+    if !is_available() {
+        return Err(ProfilerError::Unavailable);
+    }
+
+    let slot = CaptureSlot::acquire()?;
+    let path = dir.join(flamegraph_file_name());
+    let dir = dir.to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
+        let _slot = slot;
+        std::fs::create_dir_all(&dir)?;
+        sample_to_svg(duration, &path)?;
+        Ok(path)
+    })
+    .await
+    .map_err(|e| ProfilerError::Sampling(e.to_string()))?
+}
+
+#[cfg(feature = "profiler")]
+fn sample_to_svg(duration: Duration, path: &Path) -> Result<(), ProfilerError> {
+    let sampling = |e: pprof::Error| ProfilerError::Sampling(e.to_string());
+
+    // Unwinding inside these libraries can deadlock the sampled thread
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(SAMPLE_FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(sampling)?;
+    std::thread::sleep(duration);
+
+    let report = guard.report().build().map_err(sampling)?;
+    let file = std::fs::File::create(path)?;
+    report.flamegraph(file).map_err(sampling)
+}
+
+#[cfg(not(feature = "profiler"))]
+fn sample_to_svg(_duration: Duration, _path: &Path) -> Result<(), ProfilerError> {
+    Err(ProfilerError::Unavailable)
+}
@@ -0,0 +1,254 @@
+// src/redaction.rs - Patient Identifier Redaction
+
+//! Keeps patient identifiers out of everything the viewer writes.
+//!
+//! Identifiers known to the process, from the command line or from producer
+//! metadata, are registered once and replaced by [`REDACTED`] in tracing output,
+//! panic messages and telemetry. Exports additionally go through an
+//! [`AnonymizationProfile`], which also strips identifying metadata fields.
+
+use std::borrow::Cow;
+use std::io::Write;
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Replacement for a redacted identifier
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Shorter values would redact ordinary words and numbers
+const MIN_IDENTIFIER_LEN: usize = 3;
+
+/// Metadata fields that identify a patient or the people and site treating them,
+/// after the DICOM basic de-identification profile (compared without case or separators)
+const PHI_KEYS: &[&str] = &[
+    "patientid",
+    "patientname",
+    "patientbirthdate",
+    "birthdate",
+    "patientsex",
+    "patientage",
+    "patientaddress",
+    "otherpatientids",
+    "medicalrecordnumber",
+    "mrn",
+    "accessionnumber",
+    "studyid",
+    "studydescription",
+    "referringphysician",
+    "referringphysicianname",
+    "performingphysician",
+    "performingphysicianname",
+    "operatorsname",
+    "institutionname",
+    "institutionaddress",
+];
+
+/// Identifiers registered so far, longest first so overlapping values redact fully
+static IDENTIFIERS: parking_lot::RwLock<Vec<String>> = parking_lot::const_rwlock(Vec::new());
+
+/// Register a value that must never appear in output
+pub fn register_identifier(value: &str) {
+    let value = value.trim();
+    if value.chars().count() < MIN_IDENTIFIER_LEN {
+        return;
+    }
+
+    let mut identifiers = IDENTIFIERS.write();
+    if !identifiers.iter().any(|known| known == value) {
+        identifiers.push(value.to_string());
+        identifiers.sort_by_key(|known| std::cmp::Reverse(known.len()));
+    }
+}
+
+/// Register the identifying fields of producer metadata
+pub fn register_metadata(metadata: &serde_json::Value) {
+    match metadata {
+        serde_json::Value::Object(fields) => {
+            for (key, value) in fields {
+                match value {
+                    serde_json::Value::String(text) if is_phi_key(key) => register_identifier(text),
+                    serde_json::Value::Number(number) if is_phi_key(key) => register_identifier(&number.to_string()),
+                    other => register_metadata(other),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(register_metadata),
+        _ => {}
+    }
+}
+
+/// Check if a metadata field identifies a patient
+pub fn is_phi_key(key: &str) -> bool {
+    let normalized: String = key
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    PHI_KEYS.contains(&normalized.as_str())
+}
+
+/// Replace every registered identifier in `text`
+pub fn redact(text: &str) -> Cow<'_, str> {
+    let identifiers = IDENTIFIERS.read();
+    if !identifiers.iter().any(|identifier| text.contains(identifier.as_str())) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut redacted = text.to_string();
+    for identifier in identifiers.iter() {
+        redacted = redacted.replace(identifier.as_str(), REDACTED);
+    }
+    Cow::Owned(redacted)
+}
+
+/// Print panics with identifiers redacted instead of the default hook's raw message
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        let thread = std::thread::current();
+        eprintln!(
+            "thread '{}' {}",
+            thread.name().unwrap_or("<unnamed>"),
+            redact(&info.to_string())
+        );
+    }));
+}
+
+/// What happens to patient identifiers in exported files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnonymizationProfile {
+    /// Drop identifying metadata fields and redact registered identifiers
+    #[default]
+    Basic,
+    /// Keep identifiers; only when the user explicitly asks for it
+    Identified,
+}
+
+impl AnonymizationProfile {
+    /// Get display name
+    pub fn name(self) -> &'static str {
+        match self {
+            AnonymizationProfile::Basic => "anonymized",
+            AnonymizationProfile::Identified => "identified",
+        }
+    }
+
+    /// Apply the profile to exported text
+    pub fn text(self, text: &str) -> String {
+        match self {
+            AnonymizationProfile::Basic => redact(text).into_owned(),
+            AnonymizationProfile::Identified => text.to_string(),
+        }
+    }
+
+    /// Apply the profile to metadata text as producers send it, JSON or not
+    pub fn metadata_text(self, text: &str) -> String {
+        if self == AnonymizationProfile::Identified {
+            return text.to_string();
+        }
+        match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(metadata) => {
+                register_metadata(&metadata);
+                self.metadata(metadata).to_string()
+            }
+            Err(_) => self.text(text),
+        }
+    }
+
+    /// Apply the profile to exported metadata, dropping identifying fields
+    pub fn metadata(self, metadata: serde_json::Value) -> serde_json::Value {
+        if self == AnonymizationProfile::Identified {
+            return metadata;
+        }
+
+        match metadata {
+            serde_json::Value::Object(fields) => serde_json::Value::Object(
+                fields
+                    .into_iter()
+                    .filter(|(key, _)| !is_phi_key(key))
+                    .map(|(key, value)| (key, self.metadata(value)))
+                    .collect(),
+            ),
+            serde_json::Value::Array(items) => {
+                serde_json::Value::Array(items.into_iter().map(|item| self.metadata(item)).collect())
+            }
+            serde_json::Value::String(text) => serde_json::Value::String(self.text(&text)),
+            other => other,
+        }
+    }
+}
+
+/// Wraps a tracing writer so every formatted event is redacted before it is written
+#[derive(Debug, Clone)]
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    /// Redact everything written through `inner`
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter { inner: self.inner.make_writer() }
+    }
+}
+
+/// Writer handed out by [`RedactingMakeWriter`]
+///
+/// The fmt layer writes each event in one call, so an identifier is never split across writes.
+#[derive(Debug)]
+pub struct RedactingWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => match redact(text) {
+                Cow::Borrowed(_) => self.inner.write_all(buf)?,
+                Cow::Owned(redacted) => self.inner.write_all(redacted.as_bytes())?,
+            },
+            Err(_) => self.inner.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction() {
+        register_identifier("MRN-448812");
+        register_identifier("ab");
+        assert_eq!(redact("Patient MRN-448812 connected"), "Patient [REDACTED] connected");
+        assert_eq!(redact("ab stays"), "ab stays");
+
+        let mut output = Vec::new();
+        RedactingWriter { inner: &mut output }.write_all(b"loaded MRN-448812\n").unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "loaded [REDACTED]\n");
+
+        // Identifying metadata is learned, then dropped from exports
+        let metadata = serde_json::json!({
+            "probe": "L12",
+            "Patient Name": "DOE^JANE",
+            "series": [{ "note": "scan of DOE^JANE", "AccessionNumber": 77123 }],
+        });
+        register_metadata(&metadata);
+        assert_eq!(redact("DOE^JANE and 77123"), "[REDACTED] and [REDACTED]");
+
+        let exported = AnonymizationProfile::Basic.metadata(metadata.clone());
+        assert_eq!(exported, serde_json::json!({ "probe": "L12", "series": [{ "note": "scan of [REDACTED]" }] }));
+        assert_eq!(AnonymizationProfile::Identified.metadata(metadata.clone()), metadata);
+    }
+}
@@ -0,0 +1,215 @@
+// src/telemetry.rs - Pluggable Error Telemetry Sinks
+
+//! Where [`ErrorReporter`](crate::error::ErrorReporter) sends errors when telemetry is enabled.
+//!
+//! Telemetry is off unless a sink is configured, and raw error messages (which may
+//! contain paths or device names) are only sent when details are explicitly allowed.
+//! Registered patient identifiers are redacted either way.
+//! Sending needs the `telemetry` feature; without it every sink reports
+//! [`TelemetryError::Unavailable`].
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::error::MiViError;
+use crate::redaction::redact;
+
+/// How long a sink waits for the collector before giving up
+#[cfg(feature = "telemetry")]
+const SEND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Errors that can occur while sending telemetry
+#[derive(Debug, Error)]
+pub enum TelemetryError {
+    #[error("Telemetry is not available in this build (enable the \"telemetry\" feature)")]
+    Unavailable,
+
+    #[error("Invalid Sentry DSN: {0}")]
+    InvalidDsn(String),
+
+    #[error("Telemetry endpoint rejected the report: {0}")]
+    Transport(String),
+}
+
+/// One error as sent to a telemetry backend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TelemetryEvent {
+    pub error_code: u32,
+    pub severity: String,
+    pub category: String,
+    /// Raw error text, only with details allowed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub user_message: String,
+    pub suggested_action: String,
+    pub is_recoverable: bool,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub app_version: &'static str,
+}
+
+impl TelemetryEvent {
+    /// Describe an error, leaving out its raw text unless `include_details`
+    pub fn from_error(error: &MiViError, include_details: bool) -> Self {
+        Self {
+            error_code: error.error_code(),
+            severity: error.severity().to_string(),
+            category: error.category().to_string(),
+            message: include_details.then(|| redact(&error.to_string()).into_owned()),
+            user_message: redact(&error.user_message()).into_owned(),
+            suggested_action: error.suggested_action().to_string(),
+            is_recoverable: error.is_recoverable(),
+            timestamp: chrono::Utc::now(),
+            app_version: crate::VERSION,
+        }
+    }
+}
+
+/// A telemetry backend
+///
+/// `send` blocks until the report is delivered; the reporter calls it off the caller's thread.
+pub trait TelemetrySink: Send + Sync {
+    /// Get display name
+    fn name(&self) -> &str;
+
+    /// Deliver one event
+    fn send(&self, event: &TelemetryEvent) -> Result<(), TelemetryError>;
+}
+
+/// Posts each event as JSON to a collector URL
+pub struct HttpJsonSink {
+    url: String,
+}
+
+impl HttpJsonSink {
+    /// Create a sink posting to `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+impl TelemetrySink for HttpJsonSink {
+    fn name(&self) -> &str {
+        "HTTP"
+    }
+
+    fn send(&self, event: &TelemetryEvent) -> Result<(), TelemetryError> {
+        let body = serde_json::to_value(event).map_err(|e| TelemetryError::Transport(e.to_string()))?;
+        post_json(&self.url, &[], &body)
+    }
+}
+
+/// Sends events to a Sentry project through its store endpoint
+pub struct SentrySink {
+    store_url: String,
+    public_key: String,
+}
+
+impl SentrySink {
+    /// Create a sink from a DSN such as `https://<key>@o0.ingest.sentry.io/<project>`
+    pub fn from_dsn(dsn: &str) -> Result<Self, TelemetryError> {
+        let invalid = || TelemetryError::InvalidDsn(dsn.to_string());
+        let (scheme, rest) = dsn.split_once("://").ok_or_else(invalid)?;
+        let (public_key, location) = rest.split_once('@').ok_or_else(invalid)?;
+        let (host, project) = location.rsplit_once('/').ok_or_else(invalid)?;
+        let public_key = public_key.split(':').next().unwrap_or_default();
+
+        if !matches!(scheme, "http" | "https") || public_key.is_empty() || host.is_empty() || project.parse::<u64>().is_err() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            store_url: format!("{}://{}/api/{}/store/", scheme, host, project),
+            public_key: public_key.to_string(),
+        })
+    }
+
+    /// Endpoint events are posted to
+    pub fn store_url(&self) -> &str {
+        &self.store_url
+    }
+
+    /// Build the Sentry event payload
+    pub fn payload(event: &TelemetryEvent) -> serde_json::Value {
+        let level = match event.severity.as_str() {
+            "CRITICAL" => "fatal",
+            "HIGH" => "error",
+            "MEDIUM" => "warning",
+            _ => "info",
+        };
+        serde_json::json!({
+            "timestamp": event.timestamp.to_rfc3339(),
+            "platform": "native",
+            "level": level,
+            "logger": "mivi",
+            "release": format!("mivi@{}", event.app_version),
+            "message": { "formatted": event.message.as_deref().unwrap_or(&event.user_message) },
+            "tags": {
+                "error_code": event.error_code.to_string(),
+                "category": event.category,
+                "recoverable": event.is_recoverable.to_string(),
+            },
+            "extra": { "suggested_action": event.suggested_action },
+        })
+    }
+}
+
+impl TelemetrySink for SentrySink {
+    fn name(&self) -> &str {
+        "Sentry"
+    }
+
+    fn send(&self, event: &TelemetryEvent) -> Result<(), TelemetryError> {
+        let auth = format!(
+            "Sentry sentry_version=7, sentry_key={}, sentry_client=mivi/{}",
+            self.public_key, event.app_version
+        );
+        post_json(&self.store_url, &[("X-Sentry-Auth", auth.as_str())], &Self::payload(event))
+    }
+}
+
+#[cfg(feature = "telemetry")]
+fn post_json(url: &str, headers: &[(&str, &str)], body: &serde_json::Value) -> Result<(), TelemetryError> {
+    let mut request = ureq::post(url).timeout(SEND_TIMEOUT);
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+    request
+        .send_json(body)
+        .map(|_| ())
+        .map_err(|e| TelemetryError::Transport(e.to_string()))
+}
+
+#[cfg(not(feature = "telemetry"))]
+fn post_json(_url: &str, _headers: &[(&str, &str)], _body: &serde_json::Value) -> Result<(), TelemetryError> {
+    Err(TelemetryError::Unavailable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_privacy() {
+        let error = MiViError::device("/dev/shm/patient_1234: no frames");
+
+        let event = TelemetryEvent::from_error(&error, false);
+        assert_eq!(event.message, None);
+        assert!(!serde_json::to_string(&event).unwrap().contains("patient_1234"));
+
+        let event = TelemetryEvent::from_error(&error, true);
+        assert!(event.message.unwrap().contains("patient_1234"));
+    }
+
+    #[test]
+    fn test_sentry_dsn() {
+        let sink = SentrySink::from_dsn("https://abc123@o42.ingest.sentry.io/7").unwrap();
+        assert_eq!(sink.store_url(), "https://o42.ingest.sentry.io/api/7/store/");
+        assert!(SentrySink::from_dsn("https://o42.ingest.sentry.io/7").is_err());
+        assert!(SentrySink::from_dsn("ftp://abc@host/7").is_err());
+
+        let event = TelemetryEvent::from_error(&MiViError::permission("denied"), false);
+        let payload = SentrySink::payload(&event);
+        assert_eq!(payload["level"], "fatal");
+        assert_eq!(payload["tags"]["error_code"], "9000");
+    }
+}